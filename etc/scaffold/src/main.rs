@@ -0,0 +1,93 @@
+{%- if api == "storage1" -%}{%- assign hub_type = "Storage" -%}
+{%- elsif api == "bigquery2" -%}{%- assign hub_type = "Bigquery" -%}
+{%- elsif api == "drive3" -%}{%- assign hub_type = "DriveHub" -%}
+{%- endif -%}
+//! Lists resources of the {{hub_type}} API, using a hub authorized with the Application Default
+//! Credentials, whose calls are retried on transient failures and traced.
+//!
+//! Set `RUST_LOG=debug` to see each request sent by the hub.
+mod retry;
+mod telemetry;
+
+use std::time::Duration;
+
+use anyhow::Context;
+use google_{{api}}::client::connector::{self, AddressFamily};
+use google_{{api}}::{client, hyper, hyper_rustls, {{hub_type}}};
+
+type Connector =
+    hyper_rustls::HttpsConnector<hyper::client::HttpConnector<connector::FamilyResolver>>;
+
+/// Create a hub sending its requests over TLS, authorized with the credentials found in the
+/// environment: the file `GOOGLE_APPLICATION_CREDENTIALS` points to, those stored by
+/// `gcloud auth application-default login`, or those of the metadata server.
+async fn hub() -> anyhow::Result<{{hub_type}}<Connector>> {
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_native_roots()?
+        .https_or_http()
+        .enable_http1()
+        .wrap_connector(connector::http_connector(AddressFamily::Any));
+    let client = hyper::Client::builder().build(connector);
+    let auth = client::adc::application_default_credentials(client.clone())
+        .await
+        .context("no Application Default Credentials found")?;
+
+    let mut hub = {{hub_type}}::new(client, auth);
+    hub.timeouts(client::Timeouts {
+        attempt: Some(Duration::from_secs(30)),
+        deadline: Some(Duration::from_secs(120)),
+    });
+    hub.with_interceptor(telemetry::TracingInterceptor);
+    Ok(hub)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    let hub = hub().await?;
+{%- if api == "storage1" %}
+
+    let project = std::env::args()
+        .nth(1)
+        .context("usage: {{project-name}} <project-id>")?;
+    let (_, buckets) = hub
+        .buckets()
+        .list(&project)
+        .delegate(&mut retry::Backoff::default())
+        .doit()
+        .await?;
+    for bucket in buckets.items.unwrap_or_default() {
+        println!("{}", bucket.name.unwrap_or_default());
+    }
+{%- elsif api == "bigquery2" %}
+
+    let project = std::env::args()
+        .nth(1)
+        .context("usage: {{project-name}} <project-id>")?;
+    let (_, datasets) = hub
+        .datasets()
+        .list(&project)
+        .delegate(&mut retry::Backoff::default())
+        .doit()
+        .await?;
+    for dataset in datasets.datasets.unwrap_or_default() {
+        println!("{}", dataset.id.unwrap_or_default());
+    }
+{%- elsif api == "drive3" %}
+
+    let (_, files) = hub
+        .files()
+        .list()
+        .delegate(&mut retry::Backoff::default())
+        .doit()
+        .await?;
+    for file in files.files.unwrap_or_default() {
+        println!("{}", file.name.unwrap_or_default());
+    }
+{%- endif %}
+
+    hub.shutdown(Duration::from_secs(10)).await;
+    Ok(())
+}
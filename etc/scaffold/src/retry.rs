@@ -0,0 +1,74 @@
+//! Retrying calls which failed for transient reasons.
+use std::time::Duration;
+
+use google_{{api}}::client::{self, Retry};
+use google_{{api}}::hyper;
+
+/// A delegate retrying calls after network errors, timed out attempts and responses indicating a
+/// transient failure, waiting exponentially longer before each attempt.
+///
+/// Set it on each call using `.delegate(&mut Backoff::default())`.
+pub struct Backoff {
+    /// The number of retries after which a call fails.
+    pub max_retries: u32,
+    /// The delay before the first retry, which doubles with each further one.
+    pub initial_delay: Duration,
+    /// The maximum delay between two attempts.
+    pub max_delay: Duration,
+    method: &'static str,
+    retries: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Backoff {
+        Backoff {
+            max_retries: 5,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            method: "",
+            retries: 0,
+        }
+    }
+}
+
+impl Backoff {
+    fn retry(&mut self, reason: &dyn std::fmt::Display) -> Retry {
+        if self.retries >= self.max_retries {
+            tracing::warn!(method = self.method, %reason, "giving up after {} retries", self.retries);
+            return Retry::Abort;
+        }
+        let delay = self
+            .initial_delay
+            .saturating_mul(1 << self.retries.min(16))
+            .min(self.max_delay);
+        self.retries += 1;
+        tracing::info!(method = self.method, %reason, ?delay, "retrying");
+        Retry::After(delay)
+    }
+}
+
+impl client::Delegate for Backoff {
+    fn begin(&mut self, info: client::MethodInfo) {
+        self.method = info.id;
+        self.retries = 0;
+    }
+
+    fn http_error(&mut self, err: &hyper::Error) -> Retry {
+        self.retry(err)
+    }
+
+    fn attempt_timed_out(&mut self) -> Retry {
+        self.retry(&"attempt timed out")
+    }
+
+    fn http_failure(
+        &mut self,
+        response: &hyper::Response<hyper::body::Body>,
+        _err: Option<serde_json::Value>,
+    ) -> Retry {
+        match response.status().as_u16() {
+            408 | 429 | 500 | 502 | 503 | 504 => self.retry(&response.status()),
+            _ => Retry::Abort,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Tracing the requests sent by the hub.
+use std::time::Duration;
+
+use google_{{api}}::client::{interceptor::redacted_headers, HttpInterceptor};
+use google_{{api}}::hyper::http::{request, response, Method, Uri};
+
+/// Emits a `tracing` event for each request sent by the hub, and for each response received.
+///
+/// Headers are only traced at the `trace` level, with the credentials redacted.
+pub struct TracingInterceptor;
+
+impl HttpInterceptor for TracingInterceptor {
+    fn on_request(&self, parts: &request::Parts, body: &[u8]) {
+        tracing::debug!(method = %parts.method, uri = %parts.uri, body_len = body.len(), "sending request");
+        tracing::trace!(headers = ?redacted_headers(&parts.headers), "request headers");
+    }
+
+    fn on_response(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        response: &response::Parts,
+        elapsed: Duration,
+    ) {
+        tracing::info!(%method, %uri, status = response.status.as_u16(), ?elapsed, "received response");
+        tracing::trace!(headers = ?redacted_headers(&response.headers), "response headers");
+    }
+
+    fn on_error(&self, method: &Method, uri: &Uri, err: &google_{{api}}::hyper::Error) {
+        tracing::warn!(%method, %uri, error = %err, "request failed");
+    }
+}
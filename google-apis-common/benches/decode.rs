@@ -0,0 +1,142 @@
+//! Compares decoding large responses with `serde_json` and with `decode::from_str()`, which uses
+//! `simd-json` for them. Run with `cargo bench --features simd-json`.
+use std::collections::HashMap;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use google_apis_common::decode;
+use google_apis_common::serde::duration;
+use serde::Deserialize;
+use serde_json as json;
+use serde_with::{serde_as, DisplayFromStr};
+
+/// A task of Cloud Tasks, like a generated schema.
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Task {
+    name: Option<String>,
+    http_request: Option<HttpRequest>,
+    schedule_time: Option<chrono::DateTime<chrono::Utc>>,
+    create_time: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde_as(as = "Option<duration::Wrapper>")]
+    #[serde(default)]
+    dispatch_deadline: Option<chrono::Duration>,
+    dispatch_count: Option<i32>,
+    response_count: Option<i32>,
+    view: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct HttpRequest {
+    url: Option<String>,
+    http_method: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    body: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct ListTasksResponse {
+    tasks: Option<Vec<Task>>,
+    next_page_token: Option<String>,
+}
+
+/// A page of the rows of a BigQuery table, whose cells hold values of any type.
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct TableDataList {
+    kind: Option<String>,
+    etag: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    total_rows: Option<i64>,
+    page_token: Option<String>,
+    rows: Option<Vec<TableRow>>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct TableRow {
+    f: Option<Vec<TableCell>>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct TableCell {
+    v: Option<json::Value>,
+}
+
+fn tasks(count: usize) -> String {
+    let tasks = (0..count)
+        .map(|i| {
+            json::json!({
+                "name": format!("projects/my-project/locations/us-central1/queues/my-queue/tasks/{:020}", i),
+                "httpRequest": {
+                    "url": "https://example.com/handle",
+                    "httpMethod": "POST",
+                    "headers": {"Content-Type": "application/json", "X-Item": i.to_string()},
+                    "body": "eyJpdGVtIjogMTIzNDUsICJhY3Rpb24iOiAicHJvY2VzcyJ9",
+                },
+                "scheduleTime": "2024-05-01T12:00:00.123456Z",
+                "createTime": "2024-05-01T11:59:58.654321Z",
+                "dispatchDeadline": "600s",
+                "dispatchCount": i % 3,
+                "responseCount": i % 3,
+                "view": "BASIC",
+            })
+        })
+        .collect::<Vec<_>>();
+    json::json!({"tasks": tasks, "nextPageToken": "CiAKGjBpNDd2Nmp2Zml2cXRwYjBpOXA"}).to_string()
+}
+
+fn table_rows(count: usize) -> String {
+    let rows = (0..count)
+        .map(|i| {
+            json::json!({"f": [
+                {"v": i.to_string()},
+                {"v": format!("customer-{}", i)},
+                {"v": format!("{}.{:02}", i * 7 % 10000, i % 100)},
+                {"v": "1.7145648E9"},
+                {"v": null},
+                {"v": [{"v": "a"}, {"v": "b"}]},
+                {"v": {"f": [{"v": "true"}, {"v": "US"}]}},
+            ]})
+        })
+        .collect::<Vec<_>>();
+    json::json!({
+        "kind": "bigquery#tableDataList",
+        "etag": "hEuL1T/W8rJ4yGSzQuUhWA==",
+        "totalRows": "1000000",
+        "pageToken": "BEB4TGOYTQAQAAASA4EAAEEAQCAAKGQIBCEAQCAGKA",
+        "rows": rows,
+    })
+    .to_string()
+}
+
+fn bench<T: serde::de::DeserializeOwned>(c: &mut Criterion, name: &str, body: &str) {
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(body.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::new("serde_json", body.len()),
+        body,
+        |b, body| b.iter(|| json::from_str::<T>(body).unwrap()),
+    );
+    group.bench_with_input(BenchmarkId::new("decode", body.len()), body, |b, body| {
+        b.iter(|| decode::from_str::<T>(body).unwrap())
+    });
+    group.finish();
+}
+
+fn decoding(c: &mut Criterion) {
+    bench::<ListTasksResponse>(c, "10k tasks", &tasks(10_000));
+    bench::<TableDataList>(c, "10k bigquery rows", &table_rows(10_000));
+}
+
+criterion_group!(benches, decoding);
+criterion_main!(benches);
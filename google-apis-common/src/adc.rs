@@ -0,0 +1,202 @@
+//! Discovery of Application Default Credentials.
+//!
+//! Like the Google Cloud SDKs, [`application_default_credentials()`] looks for credentials in
+//! the following places, using the first one it finds:
+//! 1. the file named by the `GOOGLE_APPLICATION_CREDENTIALS` environment variable
+//! 2. the file written by `gcloud auth application-default login`, in the gcloud configuration
+//!    directory
+//! 3. the metadata server, if running on Google Cloud
+//!
+//! Credential files may contain service account keys, authorized user credentials or
+//! [external account credentials](crate::ExternalAccountCredentials).
+use std::env;
+use std::error::Error as StdError;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use http::Uri;
+use hyper::client::connect::Connection;
+use serde::Deserialize;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_service::Service;
+use yup_oauth2::authenticator::ApplicationDefaultCredentialsTypes;
+use yup_oauth2::authorized_user::AuthorizedUserSecret;
+use yup_oauth2::{
+    ApplicationDefaultCredentialsAuthenticator, ApplicationDefaultCredentialsFlowOpts,
+    AuthorizedUserAuthenticator, ServiceAccountAuthenticator,
+};
+
+use crate::{ExternalAccount, ExternalAccountCredentials, GetToken};
+
+const CREDENTIALS_ENV: &str = "GOOGLE_APPLICATION_CREDENTIALS";
+const WELL_KNOWN_FILE: &str = "application_default_credentials.json";
+
+#[derive(Deserialize)]
+struct CredentialsType {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Returns the authenticator for the Application Default Credentials, sending all its requests
+/// through `client`.
+///
+/// Fails if a credential file was found but couldn't be read. Whether the metadata server is
+/// reachable is only known once the first token is requested.
+pub async fn application_default_credentials<S>(
+    client: hyper::Client<S, hyper::body::Body>,
+) -> io::Result<Box<dyn GetToken>>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    if let Some(path) = credentials_path(|name| env::var(name).ok()) {
+        return credentials_from_file(client, &path).await;
+    }
+
+    let opts = ApplicationDefaultCredentialsFlowOpts {
+        metadata_url: env::var("GCE_METADATA_HOST").ok().map(|host| {
+            format!(
+                "http://{}/computeMetadata/v1/instance/service-accounts/default/token",
+                host
+            )
+        }),
+    };
+    let auth = match ApplicationDefaultCredentialsAuthenticator::with_client(opts, client).await {
+        ApplicationDefaultCredentialsTypes::InstanceMetadata(auth) => auth.build().await?,
+        ApplicationDefaultCredentialsTypes::ServiceAccount(auth) => auth.build().await?,
+    };
+    Ok(Box::new(auth))
+}
+
+/// Returns the path of the credential file to use, if there is one.
+fn credentials_path(var: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    if let Some(path) = var(CREDENTIALS_ENV).filter(|path| !path.is_empty()) {
+        return Some(path.into());
+    }
+    let config_dir = match var("CLOUDSDK_CONFIG") {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => PathBuf::from(var("APPDATA")?).join("gcloud"),
+        None => PathBuf::from(var("HOME")?).join(".config").join("gcloud"),
+    };
+    Some(config_dir.join(WELL_KNOWN_FILE)).filter(|path| path.is_file())
+}
+
+/// Returns the authenticator for the credentials in the file at `path`, depending on their type.
+async fn credentials_from_file<S>(
+    client: hyper::Client<S, hyper::body::Body>,
+    path: &Path,
+) -> io::Result<Box<dyn GetToken>>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    let json = fs::read_to_string(path)?;
+    let credentials: CredentialsType = serde_json::from_str(&json)?;
+    Ok(match credentials.kind.as_str() {
+        "service_account" => {
+            let key = yup_oauth2::parse_service_account_key(&json)?;
+            Box::new(
+                ServiceAccountAuthenticator::with_client(key, client)
+                    .build()
+                    .await?,
+            )
+        }
+        "authorized_user" => {
+            let secret: AuthorizedUserSecret = serde_json::from_str(&json)?;
+            Box::new(
+                AuthorizedUserAuthenticator::with_client(secret, client)
+                    .build()
+                    .await?,
+            )
+        }
+        "external_account" => Box::new(ExternalAccount::new(
+            client,
+            ExternalAccountCredentials::from_json(&json)?,
+        )),
+        kind => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Unsupported credentials of type '{}' in '{}'",
+                    kind,
+                    path.display()
+                ),
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn vars(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn credentials_path_discovery() {
+        assert_eq!(
+            credentials_path(vars(&[(CREDENTIALS_ENV, "/etc/key.json")])),
+            Some(PathBuf::from("/etc/key.json"))
+        );
+        assert_eq!(credentials_path(vars(&[])), None);
+
+        let dir = env::temp_dir().join("google-apis-common-adc-test");
+        fs::create_dir_all(&dir).unwrap();
+        let config_dir = dir.to_str().unwrap();
+        assert_eq!(
+            credentials_path(vars(&[
+                (CREDENTIALS_ENV, ""),
+                ("CLOUDSDK_CONFIG", config_dir)
+            ])),
+            None
+        );
+        fs::write(dir.join(WELL_KNOWN_FILE), "{}").unwrap();
+        assert_eq!(
+            credentials_path(vars(&[("CLOUDSDK_CONFIG", config_dir)])),
+            Some(dir.join(WELL_KNOWN_FILE))
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn credentials_by_type() {
+        let dir = env::temp_dir().join("google-apis-common-adc-type-test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("credentials.json");
+
+        fs::write(
+            &path,
+            serde_json::json!({
+                "type": "external_account",
+                "audience": "audience",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "credential_source": {"file": "/var/run/token"},
+            })
+            .to_string(),
+        )
+        .unwrap();
+        assert!(credentials_from_file(hyper::Client::new(), &path)
+            .await
+            .is_ok());
+
+        fs::write(&path, r#"{"type": "impersonated_service_account"}"#).unwrap();
+        let err = credentials_from_file(hyper::Client::new(), &path)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
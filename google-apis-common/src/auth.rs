@@ -6,6 +6,7 @@
 //! - [`Authenticator`] : An authenticator which supports a variety of authentication methods
 //! - [`String`] : Plain oauth2 token in String format
 //! - [`NoToken`] : No token, used for APIs which do not require a token
+//! - [`ApiKey`] : No token, but an API key sent with every request
 //!
 //! # Usage
 //! [`GetToken`] instances are designed to be used with the Hub constructor provided by the
@@ -18,7 +19,15 @@
 //! If you intend to use the API libraries server-side, with server-side client authentication,
 //! use the [`oauth2`] crate and convert the resulting [`AccessToken`] to [`String`].
 //!
-//! If you intend to use APIs which do not require authentication, use [`NoToken`].
+//! If you intend to use APIs which do not require authentication, use [`NoToken`], or [`ApiKey`]
+//! for those accepting an API key, like Translate or the YouTube Data API reading public data.
+//!
+//! If you intend to use the API libraries on AWS, Azure or any other platform with an OIDC identity
+//! provider, such as CI systems, use [`ExternalAccount`] to obtain tokens through workload identity
+//! federation without long-lived keys.
+//!
+//! If you need to act as a service account, wrap any of the above into a
+//! [`ServiceAccountImpersonation`], which exchanges its tokens for ones of the service account.
 //!
 //! If you have custom authentication requirements, you can implement [`GetToken`] manually.
 //!
@@ -70,11 +79,26 @@
 //! ```
 //! [`oauth2`]: https://docs.rs/oauth2/latest/oauth2/
 //! [`AccessToken`]: https://docs.rs/oauth2/latest/oauth2/struct.AccessToken.html
+//! [`ExternalAccount`]: crate::ExternalAccount
 //! [`Authenticator`]: yup_oauth2::authenticator::Authenticator
+use std::collections::HashMap;
+use std::error::Error as StdError;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use http::Uri;
+use hyper::client::connect::Connection;
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::Deserialize;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_service::Service;
 
-type GetTokenOutput<'a> = Pin<
+use crate::clock::ServerClock;
+
+pub(crate) type GetTokenOutput<'a> = Pin<
     Box<
         dyn Future<Output = Result<Option<String>, Box<dyn std::error::Error + Send + Sync>>>
             + Send
@@ -87,6 +111,11 @@ pub trait GetToken: GetTokenClone + Send + Sync {
     /// Returns `Ok(None)` if a token is not necessary - otherwise, returns an error
     /// indicating the reason why a token could not be produced.
     fn get_token<'a>(&'a self, _scopes: &'a [&str]) -> GetTokenOutput<'a>;
+
+    /// Returns the API key to send as `key` parameter of every request, if there is one.
+    fn api_key(&self) -> Option<&str> {
+        None
+    }
 }
 
 pub trait GetTokenClone {
@@ -104,7 +133,18 @@ where
 
 impl Clone for Box<dyn GetToken> {
     fn clone(&self) -> Box<dyn GetToken> {
-        self.clone_box()
+        // the box itself implements `GetTokenClone`, which would clone it using this very method
+        (**self).clone_box()
+    }
+}
+
+impl GetToken for Box<dyn GetToken> {
+    fn get_token<'a>(&'a self, scopes: &'a [&str]) -> GetTokenOutput<'a> {
+        (**self).get_token(scopes)
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        (**self).api_key()
     }
 }
 
@@ -125,6 +165,195 @@ impl GetToken for NoToken {
     }
 }
 
+/// Authorizes requests with an API key instead of a token, which is sent as `key` parameter of
+/// every request. Methods requiring a token fail with the status the server responds with.
+#[derive(Clone)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// Send `key` with every request.
+    pub fn new(key: impl Into<String>) -> ApiKey {
+        ApiKey(key.into())
+    }
+}
+
+impl GetToken for ApiKey {
+    fn get_token<'a>(&'a self, _scopes: &'a [&str]) -> GetTokenOutput<'a> {
+        Box::pin(async move { Ok(None) })
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        Some(&self.0)
+    }
+}
+
+pub(crate) const IAM_CREDENTIALS_SERVICE_ACCOUNTS_URL: &str =
+    "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/";
+pub(crate) const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+pub(crate) type TokenCache = Arc<Mutex<HashMap<Vec<String>, (String, DateTime<Utc>)>>>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateAccessTokenResponse {
+    access_token: String,
+    expire_time: DateTime<Utc>,
+}
+
+/// Impersonates a service account by exchanging the tokens of another [`GetToken`] implementation
+/// for short-lived access tokens of the service account, using the IAM Credentials
+/// [`generateAccessToken`] method.
+///
+/// The source credentials must be allowed to create tokens for the service account, e.g. by having
+/// the *Service Account Token Creator* role on it. Tokens are cached per set of scopes and reused
+/// until shortly before they expire, as determined by its [`ServerClock`].
+///
+/// [`generateAccessToken`]: https://cloud.google.com/iam/docs/reference/credentials/rest/v1/projects.serviceAccounts/generateAccessToken
+#[derive(Clone)]
+pub struct ServiceAccountImpersonation<S> {
+    client: hyper::Client<S, hyper::body::Body>,
+    source: Box<dyn GetToken>,
+    service_account: String,
+    delegates: Vec<String>,
+    lifetime: Option<Duration>,
+    tokens: TokenCache,
+    clock: ServerClock,
+}
+
+impl<S> ServiceAccountImpersonation<S> {
+    /// Impersonate `service_account`, identified by its email address, using the tokens
+    /// obtained from `source`.
+    pub fn new<A: 'static + GetToken>(
+        client: hyper::Client<S, hyper::body::Body>,
+        source: A,
+        service_account: impl Into<String>,
+    ) -> ServiceAccountImpersonation<S> {
+        ServiceAccountImpersonation {
+            client,
+            source: Box::new(source),
+            service_account: service_account.into(),
+            delegates: Vec::new(),
+            lifetime: None,
+            tokens: Default::default(),
+            clock: Default::default(),
+        }
+    }
+
+    /// Set the chain of service accounts, by email address, through which the impersonation is
+    /// delegated. Each of them must be allowed to create tokens for the next one, the last one for
+    /// the impersonated service account.
+    pub fn delegates<I, T>(mut self, delegates: I) -> ServiceAccountImpersonation<S>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.delegates = delegates.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the lifetime of the generated tokens, which defaults to one hour on the server side.
+    pub fn lifetime(mut self, lifetime: Duration) -> ServiceAccountImpersonation<S> {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    /// Set the clock used to determine whether tokens expired, which is updated with the time of
+    /// the server on each token request. Share it with the Hub to also sample its responses.
+    pub fn clock(mut self, clock: ServerClock) -> ServiceAccountImpersonation<S> {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the email address of the impersonated service account.
+    pub fn service_account(&self) -> &str {
+        &self.service_account
+    }
+
+    fn cached_token(&self, key: &[String]) -> Option<String> {
+        let tokens = self.tokens.lock().unwrap();
+        match tokens.get(key) {
+            Some((token, expires)) if !self.clock.is_expired(*expires) => Some(token.clone()),
+            _ => None,
+        }
+    }
+
+    fn request_body(&self, key: &[String]) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "scope": key,
+            "delegates": self
+                .delegates
+                .iter()
+                .map(|d| format!("projects/-/serviceAccounts/{}", d))
+                .collect::<Vec<_>>(),
+        });
+        if let Some(lifetime) = self.lifetime {
+            body["lifetime"] = format!("{}s", lifetime.as_secs()).into();
+        }
+        body
+    }
+}
+
+/// Returns the normalized scopes to request a token for, used as key into the token cache.
+pub(crate) fn scope_key(scopes: &[&str]) -> Vec<String> {
+    let mut key: Vec<String> = scopes.iter().map(|s| s.to_string()).collect();
+    key.sort();
+    key.dedup();
+    if key.is_empty() {
+        key.push(CLOUD_PLATFORM_SCOPE.to_string());
+    }
+    key
+}
+
+impl<S> GetToken for ServiceAccountImpersonation<S>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    fn get_token<'a>(&'a self, scopes: &'a [&str]) -> GetTokenOutput<'a> {
+        Box::pin(async move {
+            let key = scope_key(scopes);
+            if let Some(token) = self.cached_token(&key) {
+                return Ok(Some(token));
+            }
+
+            let mut req = hyper::Request::builder()
+                .method(hyper::Method::POST)
+                .uri(format!(
+                    "{}{}:generateAccessToken",
+                    IAM_CREDENTIALS_SERVICE_ACCOUNTS_URL, self.service_account
+                ))
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(token) = self.source.get_token(&[CLOUD_PLATFORM_SCOPE]).await? {
+                req = req.header(AUTHORIZATION, format!("Bearer {}", token));
+            }
+            let req = req.body(hyper::body::Body::from(self.request_body(&key).to_string()))?;
+
+            let res = self.client.request(req).await?;
+            self.clock.observe(res.headers());
+            let status = res.status();
+            let body = hyper::body::to_bytes(res.into_body()).await?;
+            if !status.is_success() {
+                return Err(format!(
+                    "Failed to impersonate service account '{}': {}: {}",
+                    self.service_account,
+                    status,
+                    String::from_utf8_lossy(&body)
+                )
+                .into());
+            }
+
+            let res: GenerateAccessTokenResponse = serde_json::from_slice(&body)?;
+            self.tokens
+                .lock()
+                .unwrap()
+                .insert(key, (res.access_token.clone(), res.expire_time));
+            Ok(Some(res.access_token))
+        })
+    }
+}
+
 #[cfg(feature = "yup-oauth2")]
 mod yup_oauth2_impl {
     use super::{GetToken, GetTokenOutput};
@@ -165,4 +394,41 @@ mod test {
         let dgt: &mut dyn GetToken = &mut gt;
         with_send(dgt);
     }
+
+    #[tokio::test]
+    async fn api_keys_are_kept_when_boxed() {
+        let auth: Box<dyn GetToken> = Box::new(ApiKey::new("secret"));
+        assert_eq!(auth.clone().api_key(), Some("secret"));
+        assert!(auth.get_token(&[]).await.unwrap().is_none());
+        assert_eq!(NoToken.api_key(), None);
+    }
+
+    #[test]
+    fn impersonation_scope_key() {
+        assert_eq!(
+            scope_key(&["b", "a", "b"]),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert_eq!(scope_key(&[]), vec![CLOUD_PLATFORM_SCOPE.to_string()]);
+    }
+
+    #[test]
+    fn impersonation_request_body() {
+        let auth = ServiceAccountImpersonation::new(
+            hyper::Client::new(),
+            String::from("token"),
+            "target@project.iam.gserviceaccount.com",
+        )
+        .delegates(["intermediate@project.iam.gserviceaccount.com"])
+        .lifetime(Duration::from_secs(600));
+
+        assert_eq!(
+            auth.request_body(&scope_key(&["scope"])),
+            serde_json::json!({
+                "scope": ["scope"],
+                "delegates": ["projects/-/serviceAccounts/intermediate@project.iam.gserviceaccount.com"],
+                "lifetime": "600s",
+            })
+        );
+    }
 }
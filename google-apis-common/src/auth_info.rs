@@ -0,0 +1,305 @@
+//! Introspection of the tokens calls are authorized with.
+//!
+//! When a call is denied, it helps to know who made it and which scopes its token was granted.
+//! [`auth_info()`] obtains a token from the authenticator of a hub, like its calls do, and asks
+//! Google's `tokeninfo` endpoint about it. The generated hubs expose it as `hub.auth_info()`.
+//!
+//! [`GrantedScopes`] uses the same endpoint to verify that the token of each call was granted a
+//! scope authorizing its method, which hubs do if enabled using `hub.verify_scopes(true)`.
+use std::error::Error as StdError;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use http::Uri;
+use hyper::client::connect::Connection;
+use hyper::header::{CONTENT_TYPE, USER_AGENT};
+use hyper::Method;
+use serde_json as json;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_service::Service;
+
+use crate::auth::GetToken;
+use crate::upload::failure;
+use crate::{get_body_as_string, Error};
+
+/// The endpoint describing access tokens.
+pub const TOKEN_INFO_URL: &str = "https://oauth2.googleapis.com/tokeninfo";
+
+/// What the server knows about an access token.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthInfo {
+    /// The email address of the principal, if the token was granted the `email` scope, which
+    /// tokens of service accounts usually are.
+    pub email: Option<String>,
+    /// The client the token was issued to.
+    pub audience: Option<String>,
+    /// The scopes granted to the token.
+    pub scopes: Vec<String>,
+    /// When the token expires.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AuthInfo {
+    /// Parse the response of the `tokeninfo` endpoint, like
+    /// `{"aud": "…", "scope": "https://… openid", "exp": "1700000000", "email": "…"}`, given the time
+    /// it was received at.
+    pub fn from_token_info(value: &json::Value, now: DateTime<Utc>) -> AuthInfo {
+        let field = |name: &str| value.get(name).and_then(json::Value::as_str);
+        // both are numbers sent as strings
+        let seconds = |name: &str| {
+            value.get(name).and_then(|v| match v {
+                json::Value::String(s) => s.parse::<i64>().ok(),
+                v => v.as_i64(),
+            })
+        };
+        let expires_at = match seconds("exp") {
+            Some(exp) => Utc.timestamp_opt(exp, 0).single(),
+            None => seconds("expires_in").map(|secs| now + Duration::seconds(secs)),
+        };
+        AuthInfo {
+            email: field("email").map(str::to_string),
+            audience: field("aud").or_else(|| field("azp")).map(str::to_string),
+            scopes: field("scope")
+                .map(|scope| scope.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            expires_at,
+        }
+    }
+}
+
+/// Obtain a token for `scopes` from `auth`, and describe it using the `tokeninfo` endpoint.
+///
+/// Returns `None` if `auth` doesn't authorize calls with tokens, like an API key.
+pub async fn auth_info<S>(
+    client: &hyper::Client<S, hyper::body::Body>,
+    auth: &dyn GetToken,
+    scopes: &[&str],
+    user_agent: &str,
+) -> crate::Result<Option<AuthInfo>>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    let token = match auth.get_token(scopes).await {
+        Ok(Some(token)) => token,
+        Ok(None) => return Ok(None),
+        Err(err) => return Err(Error::MissingToken(err)),
+    };
+    token_info(client, TOKEN_INFO_URL, &token, user_agent)
+        .await
+        .map(Some)
+}
+
+/// Describe `token` using the `tokeninfo` endpoint at `url`.
+async fn token_info<S>(
+    client: &hyper::Client<S, hyper::body::Body>,
+    url: &str,
+    token: &str,
+    user_agent: &str,
+) -> crate::Result<AuthInfo>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    // the token is sent in the body, so it doesn't end up in the logs of proxies
+    let request = hyper::Request::builder()
+        .method(Method::POST)
+        .uri(url)
+        .header(USER_AGENT, user_agent)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(hyper::body::Body::from(format!("access_token={}", token)))
+        .unwrap();
+    let mut res = client.request(request).await.map_err(Error::HttpError)?;
+    let body = get_body_as_string(res.body_mut()).await;
+    if !res.status().is_success() {
+        return Err(failure(res, body));
+    }
+    match json::from_str(&body) {
+        Ok(value) => Ok(AuthInfo::from_token_info(&value, Utc::now())),
+        Err(err) => Err(Error::JsonDecodeError(body, err)),
+    }
+}
+
+/// The scopes granted to the tokens calls are authorized with, which verifies that they cover the
+/// methods called.
+///
+/// The `tokeninfo` endpoint is asked once per token, and clones share what it answered about the
+/// last token.
+#[derive(Clone, Debug)]
+pub struct GrantedScopes {
+    url: String,
+    last: Arc<Mutex<Option<TokenScopes>>>,
+}
+
+/// A token and the scopes granted to it.
+type TokenScopes = (String, Arc<[String]>);
+
+impl Default for GrantedScopes {
+    fn default() -> Self {
+        GrantedScopes::new(TOKEN_INFO_URL)
+    }
+}
+
+impl GrantedScopes {
+    /// The scopes granted to tokens as described by the `tokeninfo` endpoint at `url`, which is
+    /// [`TOKEN_INFO_URL`] by default.
+    pub fn new(url: impl Into<String>) -> Self {
+        GrantedScopes {
+            url: url.into(),
+            last: Default::default(),
+        }
+    }
+
+    /// Fails with [`Error::InsufficientScopes`] if `token` was granted none of `required_scopes`,
+    /// one of which authorizes the method called with it.
+    pub async fn verify<S>(
+        &self,
+        client: &hyper::Client<S, hyper::body::Body>,
+        token: &str,
+        required_scopes: &[&str],
+        user_agent: &str,
+    ) -> crate::Result<()>
+    where
+        S: Service<Uri> + Clone + Send + Sync + 'static,
+        S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let known = match &*self.last.lock().unwrap() {
+            Some((last, scopes)) if last == token => Some(scopes.clone()),
+            _ => None,
+        };
+        let granted = match known {
+            Some(scopes) => scopes,
+            None => {
+                let info = token_info(client, &self.url, token, user_agent).await?;
+                let scopes: Arc<[String]> = info.scopes.into();
+                *self.last.lock().unwrap() = Some((token.to_string(), scopes.clone()));
+                scopes
+            }
+        };
+        if required_scopes
+            .iter()
+            .any(|required| granted.iter().any(|scope| scope == required))
+        {
+            return Ok(());
+        }
+        Err(Error::InsufficientScopes(
+            required_scopes.iter().map(|s| s.to_string()).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn token_info() {
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let value = json::json!({
+            "azp": "123.apps.googleusercontent.com",
+            "aud": "123.apps.googleusercontent.com",
+            "scope": "https://www.googleapis.com/auth/cloud-platform openid",
+            "exp": "1700003600",
+            "expires_in": "3599",
+            "email": "robot@project.iam.gserviceaccount.com",
+            "email_verified": "true"
+        });
+        let info = AuthInfo::from_token_info(&value, now);
+        assert_eq!(
+            info.email.as_deref(),
+            Some("robot@project.iam.gserviceaccount.com")
+        );
+        assert_eq!(
+            info.audience.as_deref(),
+            Some("123.apps.googleusercontent.com")
+        );
+        assert_eq!(
+            info.scopes,
+            ["https://www.googleapis.com/auth/cloud-platform", "openid"]
+        );
+        assert_eq!(info.expires_at, Some(now + Duration::seconds(3600)));
+
+        let info = AuthInfo::from_token_info(&json::json!({"expires_in": 60}), now);
+        assert_eq!(info.expires_at, Some(now + Duration::seconds(60)));
+        assert_eq!(info.email, None);
+        assert!(info.scopes.is_empty());
+    }
+
+    /// Serves a `tokeninfo` endpoint granting `scope` to all tokens, returning its url and the
+    /// amount of requests received.
+    async fn serve_token_info(scope: &'static str) -> (String, Arc<Mutex<usize>>) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/tokeninfo", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut stream = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    stream.read_line(&mut line).await.unwrap();
+                    let line = line.trim_end().to_ascii_lowercase();
+                    if line.is_empty() {
+                        break;
+                    } else if let Some(value) = line.strip_prefix("content-length: ") {
+                        length = value.parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                stream.read_exact(&mut body).await.unwrap();
+                *counter.lock().unwrap() += 1;
+                let body = json::json!({ "scope": scope }).to_string();
+                let res = format!(
+                    "HTTP/1.1 200 OK\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(res.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn granted_scopes_are_verified_once_per_token() {
+        let (url, requests) =
+            serve_token_info("https://www.googleapis.com/auth/drive openid").await;
+        let granted = GrantedScopes::new(url);
+        let client = hyper::Client::new();
+        let required = [
+            "https://www.googleapis.com/auth/drive",
+            "https://www.googleapis.com/auth/drive.file",
+        ];
+        granted
+            .verify(&client, "a", &required, "agent")
+            .await
+            .unwrap();
+        match granted.verify(&client, "a", &required[1..], "agent").await {
+            Err(Error::InsufficientScopes(scopes)) => assert_eq!(scopes, &required[1..]),
+            other => panic!("unexpected result {:?}", other),
+        }
+        // clones share what the endpoint answered about the last token
+        granted
+            .clone()
+            .verify(&client, "a", &required, "agent")
+            .await
+            .unwrap();
+        assert_eq!(*requests.lock().unwrap(), 1);
+
+        granted
+            .verify(&client, "b", &required, "agent")
+            .await
+            .unwrap();
+        assert_eq!(*requests.lock().unwrap(), 2);
+    }
+}
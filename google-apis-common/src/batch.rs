@@ -0,0 +1,446 @@
+//! Batching of calls into a single `multipart/mixed` request.
+//!
+//! APIs with a batch endpoint accept up to [`BatchRequest::MAX_CALLS`] calls in one request, each of
+//! which is encoded as an HTTP request in its own part. The server executes them independently, and
+//! responds with one part per call, which a [`BatchResponse`] demultiplexes into the typed results
+//! of the calls using the [`BatchEntry`] obtained when adding them. The calls failing within a
+//! batch which otherwise succeeded are reported together as [`BulkError`].
+use std::io;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use hyper::header::{HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use hyper::{Method, StatusCode};
+use mime::Mime;
+use serde::de::DeserializeOwned;
+
+use crate::bulk::BulkError;
+use crate::decode;
+use crate::upload::failure;
+use crate::{get_body_as_string, Error};
+
+const BATCH_BOUNDARY: &str = "batch_9ndzcwBk3HuNBVyLsEDkdE8fVpPrbY";
+
+/// A set of calls to be sent to the server in a single request.
+///
+/// Calls are added using the `add_to_batch()` method of call builders, and sent using the
+/// `execute_batch()` method of the hub.
+#[derive(Debug, Default)]
+pub struct BatchRequest {
+    parts: Vec<Vec<u8>>,
+    authorization: Option<HeaderValue>,
+}
+
+/// Identifies a call within a batch, to obtain its result of type `T` from the [`BatchResponse`].
+#[derive(Debug)]
+pub struct BatchEntry<T> {
+    index: usize,
+    request: String,
+    _response: PhantomData<fn() -> T>,
+}
+
+impl<T> BatchEntry<T> {
+    /// The position of the call within its batch.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// A summary of the call, like `GET /drive/v3/files/abc`.
+    pub fn request(&self) -> &str {
+        &self.request
+    }
+}
+
+impl BatchRequest {
+    /// The maximum amount of calls the server accepts in a single batch.
+    pub const MAX_CALLS: usize = 100;
+
+    /// An empty batch.
+    pub fn new() -> BatchRequest {
+        Default::default()
+    }
+
+    /// Add the call made by `request`, whose result is of type `T`.
+    ///
+    /// The authorization of the first call is also used for the batch itself.
+    pub async fn add<T>(
+        &mut self,
+        request: hyper::Request<hyper::body::Body>,
+    ) -> crate::Result<BatchEntry<T>> {
+        if self.parts.len() >= Self::MAX_CALLS {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("a batch holds at most {} calls", Self::MAX_CALLS),
+            )));
+        }
+        let (parts, body) = request.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .map_err(Error::HttpError)?;
+        if self.authorization.is_none() {
+            self.authorization = parts.headers.get(AUTHORIZATION).cloned();
+        }
+
+        let index = self.parts.len();
+        // the query is left out, as it may hold an API key
+        let request = format!("{} {}", parts.method, parts.uri.path());
+        self.parts.push(encode_part(
+            index,
+            &parts.method,
+            &parts.uri,
+            &parts.headers,
+            &body,
+        ));
+        Ok(BatchEntry {
+            index,
+            request,
+            _response: PhantomData,
+        })
+    }
+
+    /// The amount of calls in this batch.
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Returns true if no call was added yet.
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Returns the request sending all calls to the batch endpoint at `url`.
+    pub fn into_request(self, url: &str, user_agent: &str) -> hyper::Request<hyper::body::Body> {
+        let mut body = Vec::new();
+        for part in self.parts {
+            body.extend_from_slice(format!("--{}\r\n", BATCH_BOUNDARY).as_bytes());
+            body.extend(part);
+        }
+        body.extend_from_slice(format!("--{}--\r\n", BATCH_BOUNDARY).as_bytes());
+
+        let mut req_builder = hyper::Request::builder()
+            .method(Method::POST)
+            .uri(url)
+            .header(USER_AGENT, user_agent)
+            .header(
+                CONTENT_TYPE,
+                format!("multipart/mixed; boundary={}", BATCH_BOUNDARY),
+            );
+        if let Some(authorization) = self.authorization {
+            req_builder = req_builder.header(AUTHORIZATION, authorization);
+        }
+        req_builder.body(hyper::body::Body::from(body)).unwrap()
+    }
+}
+
+fn encode_part(
+    index: usize,
+    method: &Method,
+    uri: &http::Uri,
+    headers: &hyper::HeaderMap,
+    body: &[u8],
+) -> Vec<u8> {
+    let path = uri.path_and_query().map_or("/", |p| p.as_str());
+    let mut part = format!(
+        "Content-Type: application/http\r\nContent-ID: <item{}>\r\n\r\n{} {} HTTP/1.1\r\n",
+        index, method, path
+    )
+    .into_bytes();
+    for (name, value) in headers {
+        if name == USER_AGENT {
+            continue;
+        }
+        part.extend_from_slice(name.as_str().as_bytes());
+        part.extend_from_slice(b": ");
+        part.extend_from_slice(value.as_bytes());
+        part.extend_from_slice(b"\r\n");
+    }
+    part.extend_from_slice(b"\r\n");
+    part.extend_from_slice(body);
+    part.extend_from_slice(b"\r\n");
+    part
+}
+
+/// The responses to the calls of a batch.
+#[derive(Debug)]
+pub struct BatchResponse {
+    responses: Vec<Option<hyper::Response<String>>>,
+}
+
+impl BatchResponse {
+    /// Read the response of the server to a batch, failing if the batch as a whole failed.
+    pub async fn from_response(
+        mut res: hyper::Response<hyper::body::Body>,
+    ) -> crate::Result<BatchResponse> {
+        let res_body_string = get_body_as_string(res.body_mut()).await;
+        if !res.status().is_success() {
+            return Err(failure(res, res_body_string));
+        }
+        let boundary = res
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|ct| ct.to_str().ok())
+            .and_then(|ct| Mime::from_str(ct).ok())
+            .and_then(|ct| ct.get_param(mime::BOUNDARY).map(|b| b.to_string()));
+        match boundary {
+            Some(boundary) => Ok(BatchResponse {
+                responses: decode_parts(&res_body_string, &boundary),
+            }),
+            None => {
+                let (parts, _) = res.into_parts();
+                Err(Error::Failure(hyper::Response::from_parts(
+                    parts,
+                    res_body_string.into(),
+                )))
+            }
+        }
+    }
+
+    /// The amount of responses received.
+    pub fn len(&self) -> usize {
+        self.responses.iter().filter(|r| r.is_some()).count()
+    }
+
+    /// Returns true if no response is left.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Take the result of the call identified by `entry`, as it would have been returned by its
+    /// `doit()` method.
+    #[allow(clippy::result_large_err)]
+    pub fn take<T: DeserializeOwned>(
+        &mut self,
+        entry: &BatchEntry<T>,
+    ) -> crate::Result<(hyper::Response<hyper::body::Body>, T)> {
+        let res = self
+            .responses
+            .get_mut(entry.index)
+            .and_then(Option::take)
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("the batch has no response for call {}", entry.index),
+                ))
+            })?;
+        let (parts, body) = res.into_parts();
+        if !parts.status.is_success() {
+            return Err(failure(
+                hyper::Response::from_parts(parts, hyper::body::Body::empty()),
+                body,
+            ));
+        }
+        // calls without a response schema may respond without a body
+        let json_body = if body.trim().is_empty() {
+            "null"
+        } else {
+            body.as_str()
+        };
+        match decode::from_str(json_body) {
+            Ok(decoded) => Ok((hyper::Response::from_parts(parts, body.into()), decoded)),
+            Err(err) => Err(Error::JsonDecodeError(body, err)),
+        }
+    }
+
+    /// Take the results of all calls identified by `entries`, returning the values of the successful
+    /// ones along with their position in `entries`, and the failures of all others.
+    pub fn take_all<T: DeserializeOwned>(
+        &mut self,
+        entries: &[BatchEntry<T>],
+    ) -> (Vec<(usize, T)>, BulkError) {
+        crate::bulk::partition(entries.iter().map(|entry| {
+            (
+                entry.request.clone(),
+                self.take(entry).map(|(_, value)| value),
+            )
+        }))
+    }
+}
+
+/// Splits `head` from the rest of `s` at the first empty line.
+fn split_head(s: &str) -> (&str, &str) {
+    let crlf = s.find("\r\n\r\n").map(|pos| (pos, 4));
+    let lf = s.find("\n\n").map(|pos| (pos, 2));
+    match crlf.into_iter().chain(lf).min() {
+        Some((pos, len)) => (&s[..pos], &s[pos + len..]),
+        None => (s, ""),
+    }
+}
+
+/// Returns the responses in the parts of a `multipart/mixed` body, at the index of their call.
+fn decode_parts(body: &str, boundary: &str) -> Vec<Option<hyper::Response<String>>> {
+    let delimiter = format!("--{}", boundary);
+    let mut responses: Vec<Option<hyper::Response<String>>> = Vec::new();
+    for (position, part) in body.split(delimiter.as_str()).skip(1).enumerate() {
+        if part.starts_with("--") {
+            break;
+        }
+        let (part_head, content) = split_head(part.trim_start_matches(['\r', '\n']));
+        let index = part_head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-id"))
+            .and_then(|(_, id)| {
+                id.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .strip_prefix("response-item")
+                    .and_then(|index| index.parse::<usize>().ok())
+            })
+            .unwrap_or(position);
+        if let Some(res) = decode_response(content) {
+            if responses.len() <= index {
+                responses.resize_with(index + 1, || None);
+            }
+            responses[index] = Some(res);
+        }
+    }
+    responses
+}
+
+/// Parses an HTTP response like `HTTP/1.1 200 OK`, followed by its headers and body.
+fn decode_response(content: &str) -> Option<hyper::Response<String>> {
+    let (head, body) = split_head(content);
+    let mut lines = head.lines();
+    let status = lines
+        .next()?
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| StatusCode::from_str(code).ok())?;
+    // the line break before the next delimiter belongs to the delimiter
+    let body = body
+        .strip_suffix("\r\n")
+        .or_else(|| body.strip_suffix('\n'))
+        .unwrap_or(body);
+
+    let mut res = hyper::Response::new(body.to_string());
+    *res.status_mut() = status;
+    for (name, value) in lines.filter_map(|line| line.split_once(':')) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_str(name.trim()),
+            HeaderValue::from_str(value.trim()),
+        ) {
+            res.headers_mut().append(name, value);
+        }
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json as json;
+
+    #[tokio::test]
+    async fn encode_batch() {
+        let mut batch = BatchRequest::new();
+        let get = hyper::Request::builder()
+            .method(Method::GET)
+            .uri("https://www.googleapis.com/drive/v3/files/abc?alt=json")
+            .header(USER_AGENT, "agent")
+            .header(AUTHORIZATION, "Bearer token")
+            .body(hyper::body::Body::empty())
+            .unwrap();
+        let create = hyper::Request::builder()
+            .method(Method::POST)
+            .uri("https://www.googleapis.com/drive/v3/files")
+            .header(CONTENT_TYPE, "application/json")
+            .body(hyper::body::Body::from("{\"name\":\"x\"}"))
+            .unwrap();
+        assert_eq!(batch.add::<json::Value>(get).await.unwrap().index(), 0);
+        assert_eq!(batch.add::<json::Value>(create).await.unwrap().index(), 1);
+        assert_eq!(batch.len(), 2);
+
+        let req = batch.into_request("https://www.googleapis.com/batch/drive/v3", "agent");
+        assert_eq!(req.headers()[AUTHORIZATION], "Bearer token");
+        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+        let expected = format!(
+            "--{b}\r\nContent-Type: application/http\r\nContent-ID: <item0>\r\n\r\n\
+             GET /drive/v3/files/abc?alt=json HTTP/1.1\r\nauthorization: Bearer token\r\n\r\n\r\n\
+             --{b}\r\nContent-Type: application/http\r\nContent-ID: <item1>\r\n\r\n\
+             POST /drive/v3/files HTTP/1.1\r\ncontent-type: application/json\r\n\r\n\
+             {{\"name\":\"x\"}}\r\n--{b}--\r\n",
+            b = BATCH_BOUNDARY
+        );
+        assert_eq!(String::from_utf8_lossy(&body), expected);
+    }
+
+    #[tokio::test]
+    async fn decode_batch() {
+        let body = "--resp\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item1>\r\n\r\n\
+            HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\n\r\n\
+            {\"error\": {\"code\": 404, \"message\": \"File not found\"}}\r\n\
+            --resp\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item0>\r\n\r\n\
+            HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n\
+            {\"id\": \"abc\"}\r\n\
+            --resp--\r\n";
+        let res = hyper::Response::builder()
+            .header(CONTENT_TYPE, "multipart/mixed; boundary=resp")
+            .body(hyper::body::Body::from(body))
+            .unwrap();
+        let mut batch = BatchResponse::from_response(res).await.unwrap();
+        assert_eq!(batch.len(), 2);
+
+        let entry = |index| BatchEntry::<json::Value> {
+            index,
+            request: format!("GET /drive/v3/files/{}", index),
+            _response: PhantomData,
+        };
+        let (res, value) = batch.take(&entry(0)).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(value, json::json!({"id": "abc"}));
+        match batch.take(&entry(1)) {
+            Err(Error::Api(err)) => assert_eq!(err.code, 404),
+            other => panic!("unexpected result: {:?}", other.map(|(_, v)| v)),
+        }
+        assert!(batch.take(&entry(0)).is_err());
+        assert!(batch.is_empty());
+    }
+
+    #[tokio::test]
+    async fn take_all_results() {
+        let body = "--resp\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item0>\r\n\r\n\
+            HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n\
+            {\"id\": \"abc\"}\r\n\
+            --resp\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item1>\r\n\r\n\
+            HTTP/1.1 403 Forbidden\r\nContent-Type: application/json\r\n\r\n\
+            {\"error\": {\"code\": 403, \"message\": \"Denied\"}}\r\n\
+            --resp--\r\n";
+        let res = hyper::Response::builder()
+            .header(CONTENT_TYPE, "multipart/mixed; boundary=resp")
+            .body(hyper::body::Body::from(body))
+            .unwrap();
+        let mut batch = BatchResponse::from_response(res).await.unwrap();
+
+        let mut request = BatchRequest::new();
+        let mut entries = Vec::new();
+        for id in ["abc", "def"] {
+            let get = hyper::Request::builder()
+                .uri(format!(
+                    "https://www.googleapis.com/drive/v3/files/{}?key=k",
+                    id
+                ))
+                .body(hyper::body::Body::empty())
+                .unwrap();
+            entries.push(request.add::<json::Value>(get).await.unwrap());
+        }
+        assert_eq!(entries[1].request(), "GET /drive/v3/files/def");
+
+        let (values, errors) = batch.take_all(&entries);
+        assert_eq!(values, [(0, json::json!({"id": "abc"}))]);
+        assert_eq!(errors.total(), 2);
+        let failure = &errors.failures()[0];
+        assert_eq!(
+            (failure.index, failure.request.as_str()),
+            (1, "GET /drive/v3/files/def")
+        );
+        assert!(matches!(&failure.error, Error::Api(err) if err.code == 403));
+    }
+}
@@ -0,0 +1,154 @@
+//! Synchronous calls, for programs which don't run an async runtime, like build scripts.
+//!
+//! Like `reqwest::blocking`, calls block the calling thread until they are done, while their
+//! futures are run by a [`Runtime`] owned by the caller. Crates generated with their `blocking`
+//! feature have a `blocking` module with a hub of the same name as theirs, which wraps it along
+//! with a runtime. Its methods builders create [`Call`]s, which wrap the call builders of the hub:
+//!
+//! ```ignore
+//! let runtime = client::blocking::Runtime::new()?;
+//! let auth = runtime.block_on(oauth2::InstalledFlowAuthenticator::builder(secret, method).build())?;
+//! let hub = blocking::DriveHub::with_runtime(DriveHub::new(client, auth), runtime);
+//! let (_, list) = hub.files().list().with(|call| call.page_size(10)).doit()?;
+//! ```
+//!
+//! The tasks of a hub's connections run on a thread of the runtime, so they make progress between
+//! calls too. Calls must not be made from within an async context, whose thread they would block,
+//! as tokio panics then.
+use std::future::Future;
+use std::io;
+
+use crate::{BoxFuture, Result};
+
+/// Runs the futures of blocking calls, and the tasks they spawn on a thread of its own.
+///
+/// With the `async-std` feature, the global executor of async-std runs them instead.
+#[derive(Debug)]
+pub struct Runtime {
+    #[cfg(not(feature = "async-std"))]
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Runtime {
+    /// A runtime with a worker thread of its own, with timers and sockets enabled.
+    pub fn new() -> io::Result<Runtime> {
+        #[cfg(not(feature = "async-std"))]
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("google-apis-blocking")
+            .enable_all()
+            .build()?;
+        Ok(Runtime {
+            #[cfg(not(feature = "async-std"))]
+            runtime,
+        })
+    }
+
+    /// Blocks until `future` is ready, e.g. one creating the authenticator of a hub.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        #[cfg(not(feature = "async-std"))]
+        let output = self.runtime.block_on(future);
+        #[cfg(feature = "async-std")]
+        let output = async_std::task::block_on(future);
+        output
+    }
+}
+
+/// A call builder whose call can be made by a [`Call`], which is implemented by the call builders
+/// of generated crates which have a `doit()` method.
+pub trait Perform<'a> {
+    /// What the call results in, like the response and its decoded schema.
+    type Output;
+
+    /// Make the call, like the `doit()` method of the call builder.
+    fn perform(self) -> BoxFuture<'a, Result<Self::Output>>;
+}
+
+/// A call builder along with the runtime its call is made on.
+pub struct Call<'r, C> {
+    call: C,
+    runtime: &'r Runtime,
+}
+
+impl<'r, C> Call<'r, C> {
+    /// Make the call built by `call` on `runtime`.
+    pub fn new(call: C, runtime: &'r Runtime) -> Self {
+        Call { call, runtime }
+    }
+
+    /// Configure the call builder, e.g. to set optional parameters, a delegate or scopes.
+    pub fn with(self, configure: impl FnOnce(C) -> C) -> Self {
+        Call {
+            call: configure(self.call),
+            runtime: self.runtime,
+        }
+    }
+
+    /// The call builder, to make the call asynchronously.
+    pub fn into_inner(self) -> C {
+        self.call
+    }
+
+    /// Make the call, blocking until it is done.
+    #[allow(clippy::result_large_err)]
+    pub fn doit<'a>(self) -> Result<C::Output>
+    where
+        C: Perform<'a>,
+    {
+        self.runtime.block_on(self.call.perform())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::rt;
+
+    struct Sum {
+        terms: Vec<u32>,
+    }
+
+    impl Sum {
+        fn term(mut self, term: u32) -> Self {
+            self.terms.push(term);
+            self
+        }
+    }
+
+    impl Perform<'static> for Sum {
+        type Output = u32;
+
+        fn perform(self) -> BoxFuture<'static, Result<u32>> {
+            Box::pin(async move {
+                rt::sleep(Duration::from_millis(1)).await;
+                Ok(self.terms.iter().sum())
+            })
+        }
+    }
+
+    #[test]
+    fn calls_block_until_done() {
+        let runtime = Runtime::new().unwrap();
+        let call = Call::new(Sum { terms: vec![1] }, &runtime);
+        let sum = call.with(|sum| sum.term(2).term(3)).doit().unwrap();
+        assert_eq!(sum, 6);
+        let call = Call::new(Sum { terms: vec![1] }, &runtime);
+        assert_eq!(call.into_inner().terms, [1]);
+    }
+
+    #[test]
+    fn tasks_run_between_calls() {
+        let runtime = Runtime::new().unwrap();
+        let (sent, received) = mpsc::channel();
+        runtime.block_on(async move {
+            rt::spawn(async move {
+                rt::sleep(Duration::from_millis(10)).await;
+                sent.send(()).unwrap();
+            })
+        });
+        received.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+}
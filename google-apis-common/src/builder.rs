@@ -0,0 +1,76 @@
+//! Building request values fluently.
+//!
+//! The schemas sent in requests have a `builder()` function returning a builder with a setter for
+//! each field, which is an alternative to filling a `Default::default()` value field by field.
+//! Its `build()` method fails with a [`MissingFieldsError`] if fields the server requires, as
+//! documented by the API, weren't set.
+use std::error;
+use std::fmt::{self, Display};
+
+/// The required fields a built value lacks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingFieldsError {
+    /// The name of the type of the value.
+    pub type_name: &'static str,
+    /// The names of the missing fields, as in the API.
+    pub fields: Vec<&'static str>,
+}
+
+impl MissingFieldsError {
+    /// Fail with the `fields` of a `type_name` value which aren't set, given as names and whether
+    /// they are set.
+    pub fn check(
+        type_name: &'static str,
+        fields: &[(&'static str, bool)],
+    ) -> Result<(), MissingFieldsError> {
+        let missing: Vec<_> = fields
+            .iter()
+            .filter(|(_, is_set)| !is_set)
+            .map(|(name, _)| *name)
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(MissingFieldsError {
+            type_name,
+            fields: missing,
+        })
+    }
+}
+
+impl Display for MissingFieldsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} is missing required fields: {}",
+            self.type_name,
+            self.fields.join(", ")
+        )
+    }
+}
+
+impl error::Error for MissingFieldsError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn missing_fields_are_reported() {
+        assert_eq!(
+            MissingFieldsError::check("Queue", &[("name", true), ("rateLimits", true)]),
+            Ok(())
+        );
+        assert_eq!(MissingFieldsError::check("Queue", &[]), Ok(()));
+        let err = MissingFieldsError::check(
+            "Queue",
+            &[("name", false), ("state", true), ("rateLimits", false)],
+        )
+        .unwrap_err();
+        assert_eq!(err.fields, ["name", "rateLimits"]);
+        assert_eq!(
+            err.to_string(),
+            "Queue is missing required fields: name, rateLimits"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Failures of calls made on behalf of many items at once.
+//!
+//! Helpers making a call for each of many items, like batches, don't stop at the first failure.
+//! They report the failures of all items as a single [`BulkError`], holding for each failed item
+//! its position among the items, a summary of the request made for it and its error. Callers may
+//! thus retry or report exactly the items which failed, while using the results of all others.
+//!
+//! Helpers making a separate call for each item make a bounded amount of them at once using
+//! [`call_concurrently()`].
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::Poll;
+
+use crate::Error;
+
+/// The failure of the call made for a single item.
+#[derive(Debug)]
+pub struct ItemError<E = Error> {
+    /// The position of the item among all items.
+    pub index: usize,
+    /// A summary of the request made for the item, like `DELETE /storage/v1/b/bucket/o/object`.
+    pub request: String,
+    /// Why the call failed.
+    pub error: E,
+}
+
+/// The failures of the calls made for some of `total` items.
+#[derive(Debug)]
+pub struct BulkError<E = Error> {
+    total: usize,
+    failures: Vec<ItemError<E>>,
+}
+
+impl<E> BulkError<E> {
+    /// No failures yet among `total` items.
+    pub fn new(total: usize) -> BulkError<E> {
+        BulkError {
+            total,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Record that the call made by `request` for the item at `index` failed with `error`.
+    pub fn push(&mut self, index: usize, request: impl Into<String>, error: E) {
+        self.failures.push(ItemError {
+            index,
+            request: request.into(),
+            error,
+        });
+    }
+
+    /// The amount of items, including those whose call succeeded.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The amount of items whose call failed.
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Returns true if no call failed.
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The failures, in the order they were recorded.
+    pub fn failures(&self) -> &[ItemError<E>] {
+        &self.failures
+    }
+
+    /// The positions of the items whose call failed, e.g. to retry them.
+    pub fn failed_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.failures.iter().map(|failure| failure.index)
+    }
+
+    /// Returns the failures, in the order they were recorded.
+    pub fn into_failures(self) -> Vec<ItemError<E>> {
+        self.failures
+    }
+
+    /// Convert the error of each failure using `f`, e.g. to the error type of a program.
+    pub fn map_err<F>(self, mut f: impl FnMut(E) -> F) -> BulkError<F> {
+        BulkError {
+            total: self.total,
+            failures: self
+                .failures
+                .into_iter()
+                .map(|failure| ItemError {
+                    index: failure.index,
+                    request: failure.request,
+                    error: f(failure.error),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns `value` if no call failed, or this error otherwise.
+    pub fn into_result<T>(self, value: T) -> Result<T, BulkError<E>> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Split the results of the calls made for each item, along with the summary of their request,
+/// into the values of the successful ones at the position of their item, and the failures.
+pub fn partition<T, E>(
+    results: impl IntoIterator<Item = (String, Result<T, E>)>,
+) -> (Vec<(usize, T)>, BulkError<E>) {
+    let mut values = Vec::new();
+    let mut errors = BulkError::new(0);
+    for (index, (request, result)) in results.into_iter().enumerate() {
+        match result {
+            Ok(value) => values.push((index, value)),
+            Err(err) => errors.push(index, request, err),
+        }
+        errors.total += 1;
+    }
+    (values, errors)
+}
+
+/// Make the call returned by `call` for each of `items`, of which at most `concurrency` are in
+/// flight at once, and split their results like [`partition()`]. Besides the future of the call,
+/// `call` returns the summary of its request, which identifies the item if its call fails.
+///
+/// The calls are made on the current task, so they may borrow a hub, and are started in the order
+/// of the items.
+pub async fn call_concurrently<I, R, E, F, Fut>(
+    items: I,
+    concurrency: usize,
+    mut call: F,
+) -> (Vec<(usize, R)>, BulkError<E>)
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> (String, Fut),
+    Fut: Future<Output = Result<R, E>>,
+{
+    let mut items = items.into_iter();
+    let mut in_flight: Vec<(usize, String, Pin<Box<Fut>>)> = Vec::new();
+    let mut results = Vec::new();
+    let mut started = 0;
+    loop {
+        while in_flight.len() < concurrency.max(1) {
+            match items.next() {
+                Some(item) => {
+                    let (request, future) = call(item);
+                    in_flight.push((started, request, Box::pin(future)));
+                    started += 1;
+                }
+                None => break,
+            }
+        }
+        if in_flight.is_empty() {
+            break;
+        }
+        // wait for at least one call to finish, taking the results of all finished calls
+        let finished = poll_fn(|cx| {
+            let mut finished = Vec::new();
+            let mut i = 0;
+            while i < in_flight.len() {
+                match in_flight[i].2.as_mut().poll(cx) {
+                    Poll::Ready(result) => {
+                        let (index, request, _) = in_flight.swap_remove(i);
+                        finished.push((index, request, result));
+                    }
+                    Poll::Pending => i += 1,
+                }
+            }
+            if finished.is_empty() {
+                Poll::Pending
+            } else {
+                Poll::Ready(finished)
+            }
+        })
+        .await;
+        results.extend(finished);
+    }
+    results.sort_by_key(|(index, _, _)| *index);
+    partition(
+        results
+            .into_iter()
+            .map(|(_, request, result)| (request, result)),
+    )
+}
+
+impl<E: fmt::Display> fmt::Display for BulkError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} calls failed", self.failures.len(), self.total)?;
+        for failure in &self.failures {
+            write!(
+                f,
+                "\n  item {} ({}): {}",
+                failure.index, failure.request, failure.error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: StdError + 'static> StdError for BulkError<E> {
+    /// The error of the first failure.
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.failures
+            .first()
+            .map(|failure| &failure.error as &(dyn StdError + 'static))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn failures_are_aggregated() {
+        let results = vec![
+            ("DELETE /b/x/o/a".to_string(), Ok(1)),
+            (
+                "DELETE /b/x/o/b".to_string(),
+                Err(io::Error::new(io::ErrorKind::NotFound, "not found")),
+            ),
+            ("DELETE /b/x/o/c".to_string(), Ok(3)),
+            (
+                "DELETE /b/x/o/d".to_string(),
+                Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied")),
+            ),
+        ];
+        let (values, errors) = partition(results);
+        assert_eq!(values, [(0, 1), (2, 3)]);
+        assert_eq!((errors.len(), errors.total()), (2, 4));
+        assert_eq!(errors.failed_indices().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(
+            errors.to_string(),
+            "2 of 4 calls failed\n  item 1 (DELETE /b/x/o/b): not found\n  item 3 (DELETE /b/x/o/d): denied"
+        );
+        assert_eq!(errors.source().unwrap().to_string(), "not found");
+
+        let errors = errors.map_err(|err| err.kind());
+        assert_eq!(errors.failures()[0].error, io::ErrorKind::NotFound);
+        assert!(errors.into_result(()).is_err());
+        assert_eq!(BulkError::<io::Error>::new(2).into_result(5).unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn calls_are_bounded() {
+        use std::cell::Cell;
+        use std::time::Duration;
+
+        let (in_flight, most_in_flight) = (Cell::new(0), Cell::new(0));
+        let (values, errors) = call_concurrently(0..10u64, 3, |item| {
+            let (in_flight, most_in_flight) = (&in_flight, &most_in_flight);
+            let call = async move {
+                in_flight.set(in_flight.get() + 1);
+                most_in_flight.set(most_in_flight.get().max(in_flight.get()));
+                // later items finish first
+                tokio::time::sleep(Duration::from_millis(10 - item)).await;
+                in_flight.set(in_flight.get() - 1);
+                if item % 4 == 1 {
+                    Err(io::Error::other("failed"))
+                } else {
+                    Ok(item * 2)
+                }
+            };
+            (format!("POST /tasks/{}", item), call)
+        })
+        .await;
+        assert_eq!(most_in_flight.get(), 3);
+        assert_eq!(
+            values,
+            [(0, 0), (2, 4), (3, 6), (4, 8), (6, 12), (7, 14), (8, 16)]
+        );
+        assert_eq!(errors.total(), 10);
+        assert_eq!(errors.failed_indices().collect::<Vec<_>>(), [1, 5, 9]);
+        assert_eq!(errors.failures()[1].request, "POST /tasks/5");
+    }
+}
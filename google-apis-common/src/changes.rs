@@ -0,0 +1,320 @@
+//! Tracking the changes of resources incrementally using page tokens, like those of Drive.
+//!
+//! Drive returns the token of the current state of its files with `changes.getStartPageToken`,
+//! and the changes made after a token with `changes.list`, a page at a time. The last page holds
+//! the `newStartPageToken` the changes made afterwards are listed from. The generated
+//! `ChangesTracker` lists them as [`Changes`], a stream which keeps the token in a
+//! [`PageTokenStore`], so each tracker continues where the previous one stopped, even in another
+//! process.
+//!
+//! The token is stored once all changes of a page were taken from the stream, so changes are
+//! delivered at least once: if the process stops while handling the changes of a page, they are
+//! listed again by the next tracker.
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::future::{poll_fn, Future};
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::{Error, Result};
+
+/// Keeps the page token changes are listed from next.
+pub trait PageTokenStore: Send {
+    /// Returns the stored token, or `None` if there is none yet.
+    fn load(&mut self) -> io::Result<Option<String>>;
+
+    /// Store `page_token`, replacing the stored token.
+    fn save(&mut self, page_token: &str) -> io::Result<()>;
+}
+
+/// Keeps the token in memory, for trackers which list changes repeatedly within a process.
+impl PageTokenStore for Option<String> {
+    fn load(&mut self) -> io::Result<Option<String>> {
+        Ok(self.clone())
+    }
+
+    fn save(&mut self, page_token: &str) -> io::Result<()> {
+        *self = Some(page_token.to_string());
+        Ok(())
+    }
+}
+
+/// Keeps the token in a file, which is replaced atomically when a token is stored.
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Keep the token in the file at `path`, which is created when the first token is stored.
+    pub fn new(path: impl Into<PathBuf>) -> FileTokenStore {
+        FileTokenStore { path: path.into() }
+    }
+}
+
+impl PageTokenStore for FileTokenStore {
+    fn load(&mut self) -> io::Result<Option<String>> {
+        match fs::read_to_string(&self.path) {
+            Ok(token) if token.trim().is_empty() => Ok(None),
+            Ok(token) => Ok(Some(token.trim().to_string())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn save(&mut self, page_token: &str) -> io::Result<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, page_token)?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+/// A page of changes, as returned by `changes.list`.
+#[derive(Clone, Debug)]
+pub struct ChangesPage<T> {
+    pub changes: Vec<T>,
+    /// The token of the next page, if there is one.
+    pub next_page_token: Option<String>,
+    /// The token the changes made after those of the last page are listed from.
+    pub new_start_page_token: Option<String>,
+}
+
+type StartFuture<'a> = Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>>;
+type PageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<ChangesPage<T>>> + Send + 'a>>;
+
+/// A stream of the changes made after the token kept in a [`PageTokenStore`], which lists further
+/// pages as needed and stores the token of the next one once all changes of a page were taken.
+///
+/// If the store has no token yet, the stream stores the token of the current state and ends, as
+/// there are no changes to list from it yet. The stream ends after the last page, or after
+/// yielding the error a call or the store failed with.
+pub struct Changes<'a, T> {
+    store: &'a mut dyn PageTokenStore,
+    start: Option<Box<dyn FnOnce() -> StartFuture<'a> + Send + 'a>>,
+    list: Box<dyn FnMut(String) -> PageFuture<'a, T> + Send + 'a>,
+    pending: Option<Pending<'a, T>>,
+    // the token of the page to list next, or None after the last one
+    next_page: Option<String>,
+    // the token to store once all changes were taken
+    unsaved: Option<String>,
+    changes: VecDeque<T>,
+    loaded: bool,
+}
+
+enum Pending<'a, T> {
+    Start(StartFuture<'a>),
+    Page(PageFuture<'a, T>),
+}
+
+impl<T> Unpin for Changes<'_, T> {}
+
+impl<'a, T> Changes<'a, T> {
+    /// Stream the changes made after the token kept in `store`, listed by `list` with the token of
+    /// each page. If there is no token yet, the one obtained by `start` is stored.
+    pub fn new<SF, LF, SFut, LFut>(
+        store: &'a mut dyn PageTokenStore,
+        start: SF,
+        mut list: LF,
+    ) -> Changes<'a, T>
+    where
+        SF: FnOnce() -> SFut + Send + 'a,
+        SFut: Future<Output = Result<String>> + Send + 'a,
+        LF: FnMut(String) -> LFut + Send + 'a,
+        LFut: Future<Output = Result<ChangesPage<T>>> + Send + 'a,
+    {
+        Changes {
+            store,
+            start: Some(Box::new(move || Box::pin(start()))),
+            list: Box::new(move |page_token| Box::pin(list(page_token))),
+            pending: None,
+            next_page: None,
+            unsaved: None,
+            changes: VecDeque::new(),
+            loaded: false,
+        }
+    }
+
+    /// Returns the next change, or `None` if there are no more.
+    pub async fn next_change(&mut self) -> Option<Result<T>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    fn fail(&mut self, err: Error) -> Poll<Option<Result<T>>> {
+        self.pending = None;
+        self.next_page = None;
+        self.unsaved = None;
+        self.changes.clear();
+        Poll::Ready(Some(Err(err)))
+    }
+}
+
+impl<'a, T> Stream for Changes<'a, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        let this = self.get_mut();
+        if !this.loaded {
+            this.loaded = true;
+            match this.store.load() {
+                Ok(Some(page_token)) => this.next_page = Some(page_token),
+                Ok(None) => {
+                    let start = this.start.take().expect("start is only called once");
+                    this.pending = Some(Pending::Start(start()));
+                }
+                Err(err) => return this.fail(Error::Io(err)),
+            }
+        }
+        loop {
+            if let Some(change) = this.changes.pop_front() {
+                return Poll::Ready(Some(Ok(change)));
+            }
+            if let Some(page_token) = this.unsaved.take() {
+                if let Err(err) = this.store.save(&page_token) {
+                    return this.fail(Error::Io(err));
+                }
+            }
+            let pending = match &mut this.pending {
+                Some(pending) => pending,
+                None => match this.next_page.take() {
+                    Some(page_token) => this.pending.insert(Pending::Page((this.list)(page_token))),
+                    None => return Poll::Ready(None),
+                },
+            };
+            match pending {
+                Pending::Start(start) => match start.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(page_token)) => {
+                        this.pending = None;
+                        this.unsaved = Some(page_token);
+                    }
+                    Poll::Ready(Err(err)) => return this.fail(err),
+                },
+                Pending::Page(page) => match page.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(page)) => {
+                        this.pending = None;
+                        this.changes.extend(page.changes);
+                        match page.next_page_token.filter(|token| !token.is_empty()) {
+                            Some(page_token) => {
+                                this.next_page = Some(page_token.clone());
+                                this.unsaved = Some(page_token);
+                            }
+                            None => this.unsaved = page.new_start_page_token,
+                        }
+                    }
+                    Poll::Ready(Err(err)) => return this.fail(err),
+                },
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Changes<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Changes")
+            .field("next_page", &self.next_page)
+            .field("unsaved", &self.unsaved)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ApiError;
+    use std::sync::{Arc, Mutex};
+
+    fn page(changes: &[u32], next: Option<&str>, new_start: Option<&str>) -> ChangesPage<u32> {
+        ChangesPage {
+            changes: changes.to_vec(),
+            next_page_token: next.map(str::to_string),
+            new_start_page_token: new_start.map(str::to_string),
+        }
+    }
+
+    fn changes<'a>(
+        store: &'a mut dyn PageTokenStore,
+        listed: Arc<Mutex<Vec<String>>>,
+    ) -> Changes<'a, u32> {
+        Changes::new(
+            store,
+            || async { Ok("1".to_string()) },
+            move |page_token: String| {
+                listed.lock().unwrap().push(page_token.clone());
+                async move {
+                    match page_token.as_str() {
+                        "1" => Ok(page(&[1, 2], Some("1b"), None)),
+                        "1b" => Ok(page(&[3], None, Some("2"))),
+                        "2" => Ok(page(&[], None, Some("2"))),
+                        _ => Err(Error::Api(ApiError {
+                            code: 400,
+                            ..Default::default()
+                        })),
+                    }
+                }
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn tokens_are_stored_once_the_changes_were_taken() {
+        let listed = Arc::new(Mutex::new(Vec::new()));
+        let mut store: Option<String> = None;
+
+        // without a token, the current one is stored
+        let mut stream = changes(&mut store, listed.clone());
+        assert!(stream.next_change().await.is_none());
+        drop(stream);
+        assert_eq!(store.as_deref(), Some("1"));
+
+        let mut stream = changes(&mut store, listed.clone());
+        assert_eq!(stream.next_change().await.unwrap().unwrap(), 1);
+        assert_eq!(stream.next_change().await.unwrap().unwrap(), 2);
+        assert_eq!(stream.next_change().await.unwrap().unwrap(), 3);
+        assert!(stream.next_change().await.is_none());
+        drop(stream);
+        assert_eq!(store.as_deref(), Some("2"));
+        assert_eq!(*listed.lock().unwrap(), ["1", "1b"]);
+
+        // a stream dropped before taking all changes of a page lists them again
+        store = Some("1".into());
+        let mut stream = changes(&mut store, listed.clone());
+        assert_eq!(stream.next_change().await.unwrap().unwrap(), 1);
+        drop(stream);
+        assert_eq!(store.as_deref(), Some("1"));
+
+        let mut store = Some("2".to_string());
+        let mut stream = changes(&mut store, listed.clone());
+        assert!(stream.next_change().await.is_none());
+        drop(stream);
+
+        let mut store = Some("expired".to_string());
+        let mut stream = changes(&mut store, listed);
+        assert!(matches!(
+            stream.next_change().await,
+            Some(Err(Error::Api(_)))
+        ));
+        assert!(stream.next_change().await.is_none());
+        drop(stream);
+        assert_eq!(store.as_deref(), Some("expired"));
+    }
+
+    #[test]
+    fn file_token_store() {
+        let path = std::env::temp_dir().join(format!("page-token-{}", std::process::id()));
+        let mut store = FileTokenStore::new(&path);
+        assert_eq!(store.load().unwrap(), None);
+        store.save("1234").unwrap();
+        assert_eq!(
+            FileTokenStore::new(&path).load().unwrap().as_deref(),
+            Some("1234")
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+//! Tolerance for local clocks drifting from the time of the server.
+//!
+//! Tokens and signed urls are only valid until a point in time determined by the server. If the
+//! local clock is off, tokens are used after they expired, and requests fail to authorize.
+//! A [`ServerClock`] estimates the time of the server from the `Date` header of its responses,
+//! which doesn't depend on the local clock being synchronized, and considers a deadline to be
+//! reached a configurable tolerance before it actually is.
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hyper::header::{HeaderMap, DATE};
+
+/// The estimated time of the server, shared by all its clones.
+///
+/// Hubs update their clock with every response they receive, and use it for the tokens obtained
+/// by [`ServiceAccountImpersonation`](crate::ServiceAccountImpersonation).
+#[derive(Clone, Debug)]
+pub struct ServerClock {
+    offset: Arc<Mutex<Option<chrono::Duration>>>,
+    tolerance: Duration,
+}
+
+impl Default for ServerClock {
+    fn default() -> Self {
+        ServerClock {
+            offset: Default::default(),
+            tolerance: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ServerClock {
+    /// A clock which assumes the local time to be correct until the first response was observed,
+    /// and considers deadlines reached a minute early.
+    pub fn new() -> ServerClock {
+        Default::default()
+    }
+
+    /// Set how long before a deadline it is considered to be reached, to account for the
+    /// inaccuracy of the estimate and the time it takes for requests to reach the server.
+    pub fn tolerance(mut self, tolerance: Duration) -> ServerClock {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Update the estimate using the `Date` header of a response, if it has one.
+    pub fn observe(&self, headers: &HeaderMap) {
+        let date = headers
+            .get(DATE)
+            .and_then(|date| date.to_str().ok())
+            .and_then(|date| DateTime::parse_from_rfc2822(date).ok());
+        if let Some(date) = date {
+            self.observe_at(date.with_timezone(&Utc), Utc::now());
+        }
+    }
+
+    fn observe_at(&self, server_time: DateTime<Utc>, local_time: DateTime<Utc>) {
+        *self.offset.lock().unwrap() = Some(server_time - local_time);
+    }
+
+    /// Returns how far the server's clock is ahead of the local one, or `None` if no response was
+    /// observed yet.
+    pub fn offset(&self) -> Option<chrono::Duration> {
+        *self.offset.lock().unwrap()
+    }
+
+    /// Returns the estimated current time of the server.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.at(Utc::now())
+    }
+
+    fn at(&self, local_time: DateTime<Utc>) -> DateTime<Utc> {
+        local_time + self.offset().unwrap_or_else(chrono::Duration::zero)
+    }
+
+    /// Returns true if `deadline`, in the time of the server, is reached or will be within the
+    /// tolerance of this clock.
+    pub fn is_expired(&self, deadline: DateTime<Utc>) -> bool {
+        self.is_expired_at(deadline, Utc::now())
+    }
+
+    fn is_expired_at(&self, deadline: DateTime<Utc>, local_time: DateTime<Utc>) -> bool {
+        match chrono::Duration::from_std(self.tolerance) {
+            Ok(tolerance) => self.at(local_time) + tolerance >= deadline,
+            Err(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn offset_from_date_header() {
+        let clock = ServerClock::new();
+        assert_eq!(clock.offset(), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(DATE, "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap());
+        clock.observe(&headers);
+        assert!(clock.offset().unwrap() < chrono::Duration::zero());
+
+        clock.observe_at(time("2024-01-01T12:05:00Z"), time("2024-01-01T12:00:00Z"));
+        assert_eq!(clock.offset(), Some(chrono::Duration::minutes(5)));
+        assert_eq!(
+            clock.at(time("2024-01-01T13:00:00Z")),
+            time("2024-01-01T13:05:00Z")
+        );
+    }
+
+    #[test]
+    fn expiry_with_skew_and_tolerance() {
+        let local = time("2024-01-01T12:00:00Z");
+        let deadline = time("2024-01-01T12:03:00Z");
+        let clock = ServerClock::new().tolerance(Duration::from_secs(30));
+        assert!(!clock.is_expired_at(deadline, local));
+
+        // the local clock is late by two minutes and a half
+        clock.observe_at(time("2024-01-01T12:02:30Z"), local);
+        assert!(clock.is_expired_at(deadline, local));
+    }
+}
@@ -0,0 +1,365 @@
+//! Connectors with a configurable strategy for hosts reachable via IPv4 and IPv6.
+//!
+//! The [`HttpConnector`] returned by [`http_connector()`] attempts to connect to both address families
+//! as described in [RFC 6555 (Happy Eyeballs)][rfc], trying the [`AddressFamily`] of choice first and
+//! falling back to the other one shortly after. It is meant to be wrapped by a TLS connector, e.g.
+//! `hyper_rustls::HttpsConnectorBuilder::wrap_connector()`.
+//!
+//! [rfc]: https://datatracker.ietf.org/doc/html/rfc6555
+//!
+//! To authenticate using a client certificate, the TLS connector needs to be configured with it,
+//! and the Hub needs to use the API's mTLS endpoint. Like the Google Cloud SDKs, the Hubs choose
+//! the endpoint based on the [`USE_MTLS_ENDPOINT_ENV`] environment variable, see [`MtlsEndpoint`].
+//!
+//! On unix, the [`UnixConnector`] connects to a local proxy listening on a unix domain socket
+//! instead, like a sidecar brokering the egress of a pod.
+use std::env;
+use std::fmt::{self, Display};
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hyper::client::connect::dns::{GaiResolver, Name};
+use hyper::client::connect::{Connected, Connection};
+use hyper::client::HttpConnector;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tower_service::Service;
+
+/// The time to wait for a connection to the preferred address family before trying the other one.
+pub const HAPPY_EYEBALLS_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// The address family to try first when connecting to hosts with both IPv4 and IPv6 addresses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AddressFamily {
+    /// Use the order in which the system resolver returns the addresses.
+    #[default]
+    Any,
+    /// Try IPv4 addresses first.
+    Ipv4,
+    /// Try IPv6 addresses first.
+    Ipv6,
+}
+
+impl AddressFamily {
+    fn is_preferred(&self, addr: &SocketAddr) -> bool {
+        match *self {
+            AddressFamily::Any => true,
+            AddressFamily::Ipv4 => addr.is_ipv4(),
+            AddressFamily::Ipv6 => addr.is_ipv6(),
+        }
+    }
+}
+
+impl Display for AddressFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            AddressFamily::Any => "any",
+            AddressFamily::Ipv4 => "ipv4",
+            AddressFamily::Ipv6 => "ipv6",
+        })
+    }
+}
+
+impl FromStr for AddressFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(AddressFamily::Any),
+            "ipv4" => Ok(AddressFamily::Ipv4),
+            "ipv6" => Ok(AddressFamily::Ipv6),
+            _ => Err(format!(
+                "'{}' is not an address family, choose one of 'any', 'ipv4' or 'ipv6'",
+                s
+            )),
+        }
+    }
+}
+
+/// The environment variable which determines whether the mTLS endpoint of an API is used.
+pub const USE_MTLS_ENDPOINT_ENV: &str = "GOOGLE_API_USE_MTLS_ENDPOINT";
+
+/// When to send requests to the mTLS endpoint of an API, which requires a client certificate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MtlsEndpoint {
+    /// Never use the mTLS endpoint.
+    Never,
+    /// Use the mTLS endpoint if a client certificate is configured.
+    #[default]
+    Auto,
+    /// Always use the mTLS endpoint.
+    Always,
+}
+
+impl MtlsEndpoint {
+    /// Read the setting from [`USE_MTLS_ENDPOINT_ENV`], defaulting to [`MtlsEndpoint::Auto`] if
+    /// it is unset or invalid.
+    pub fn from_env() -> MtlsEndpoint {
+        env::var(USE_MTLS_ENDPOINT_ENV)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the mTLS endpoint should be used, given whether the connector presents a
+    /// client certificate.
+    pub fn use_mtls(&self, has_client_certificate: bool) -> bool {
+        match *self {
+            MtlsEndpoint::Never => false,
+            MtlsEndpoint::Auto => has_client_certificate,
+            MtlsEndpoint::Always => true,
+        }
+    }
+}
+
+impl FromStr for MtlsEndpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(MtlsEndpoint::Never),
+            "auto" => Ok(MtlsEndpoint::Auto),
+            "always" => Ok(MtlsEndpoint::Always),
+            _ => Err(format!(
+                "'{}' is not a valid value for {}, choose one of 'never', 'auto' or 'always'",
+                s, USE_MTLS_ENDPOINT_ENV
+            )),
+        }
+    }
+}
+
+/// A resolver which orders the resolved addresses by [`AddressFamily`], keeping the order
+/// of the system resolver otherwise.
+#[derive(Clone)]
+pub struct FamilyResolver {
+    inner: GaiResolver,
+    family: AddressFamily,
+}
+
+impl FamilyResolver {
+    pub fn new(family: AddressFamily) -> FamilyResolver {
+        FamilyResolver {
+            inner: GaiResolver::new(),
+            family,
+        }
+    }
+}
+
+impl Service<Name> for FamilyResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let resolved = self.inner.call(name);
+        let family = self.family;
+        Box::pin(async move {
+            let mut addrs: Vec<SocketAddr> = resolved.await?.collect();
+            sort_by_family(&mut addrs, family);
+            Ok(addrs.into_iter())
+        })
+    }
+}
+
+fn sort_by_family(addrs: &mut [SocketAddr], family: AddressFamily) {
+    addrs.sort_by_key(|addr| !family.is_preferred(addr));
+}
+
+/// Returns a dual-stack connector which tries the given address family first, falling back to the
+/// other one after [`HAPPY_EYEBALLS_TIMEOUT`].
+///
+/// Like the connectors built by `hyper_rustls`, it allows `https` urls so it can be wrapped by
+/// a TLS connector.
+pub fn http_connector(family: AddressFamily) -> HttpConnector<FamilyResolver> {
+    let mut connector = HttpConnector::new_with_resolver(FamilyResolver::new(family));
+    connector.enforce_http(false);
+    connector.set_happy_eyeballs_timeout(Some(HAPPY_EYEBALLS_TIMEOUT));
+    connector
+}
+
+/// A connector sending all requests through the unix domain socket at a path, regardless of the
+/// host of their url.
+///
+/// The urls of requests are kept as they are, so the `Host` header of requests names the host of
+/// the API, which allows proxies forwarding plain HTTP to route them. For proxies relaying the
+/// connection itself, wrap it by a TLS connector, e.g. using
+/// `hyper_rustls::HttpsConnectorBuilder::wrap_connector()`, which then performs the handshake with
+/// the API through the socket, using its host for SNI and to verify its certificate.
+#[cfg(unix)]
+#[derive(Clone, Debug)]
+pub struct UnixConnector {
+    path: std::sync::Arc<std::path::PathBuf>,
+}
+
+#[cfg(unix)]
+impl UnixConnector {
+    /// Connect to the socket at `path`.
+    pub fn new(path: impl Into<std::path::PathBuf>) -> UnixConnector {
+        UnixConnector {
+            path: std::sync::Arc::new(path.into()),
+        }
+    }
+
+    /// Returns the path of the socket.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+impl Service<hyper::Uri> for UnixConnector {
+    type Response = UnixConnection;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: hyper::Uri) -> Self::Future {
+        let path = self.path.clone();
+        Box::pin(async move {
+            let stream = tokio::net::UnixStream::connect(path.as_path())
+                .await
+                .map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!("Failed to connect to '{}': {}", path.display(), err),
+                    )
+                })?;
+            Ok(UnixConnection(stream))
+        })
+    }
+}
+
+/// A connection made by a [`UnixConnector`].
+#[cfg(unix)]
+pub struct UnixConnection(tokio::net::UnixStream);
+
+#[cfg(unix)]
+impl Connection for UnixConnection {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+#[cfg(unix)]
+impl AsyncRead for UnixConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+#[cfg(unix)]
+impl AsyncWrite for UnixConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn addresses_are_sorted_by_family() {
+        let v4: SocketAddr = "127.0.0.1:443".parse().unwrap();
+        let v6: SocketAddr = "[::1]:443".parse().unwrap();
+        let other_v6: SocketAddr = "[::2]:443".parse().unwrap();
+
+        let mut addrs = vec![v6, v4, other_v6];
+        sort_by_family(&mut addrs, AddressFamily::Any);
+        assert_eq!(addrs, vec![v6, v4, other_v6]);
+        sort_by_family(&mut addrs, AddressFamily::Ipv4);
+        assert_eq!(addrs, vec![v4, v6, other_v6]);
+        sort_by_family(&mut addrs, AddressFamily::Ipv6);
+        assert_eq!(addrs, vec![v6, other_v6, v4]);
+    }
+
+    #[test]
+    fn address_family_from_str() {
+        for family in [AddressFamily::Any, AddressFamily::Ipv4, AddressFamily::Ipv6] {
+            assert_eq!(family.to_string().parse::<AddressFamily>(), Ok(family));
+        }
+        assert!("ipv5".parse::<AddressFamily>().is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn requests_are_sent_through_unix_sockets() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let dir = std::env::temp_dir().join(format!("google-apis-uds-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("proxy.sock");
+        std::fs::remove_file(&path).ok();
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let proxy = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            while !request.ends_with(b"\r\n\r\n") {
+                let mut buf = [0; 1024];
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let connector = UnixConnector::new(&path);
+        assert_eq!(connector.path(), path);
+        let client = hyper::Client::builder().build::<_, hyper::Body>(connector);
+        let uri = "http://tasks.googleapis.com/v2/queues".parse().unwrap();
+        let response = client.get(uri).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], b"ok");
+        let request = proxy.await.unwrap().to_ascii_lowercase();
+        assert!(request.starts_with("get /v2/queues http/1.1\r\n"));
+        assert!(request.contains("host: tasks.googleapis.com\r\n"));
+        std::fs::remove_dir_all(&dir).ok();
+
+        let missing = UnixConnector::new(dir.join("missing.sock"));
+        let client = hyper::Client::builder().build::<_, hyper::Body>(missing);
+        let uri = "http://tasks.googleapis.com/v2/queues".parse().unwrap();
+        assert!(client.get(uri).await.is_err());
+    }
+
+    #[test]
+    fn mtls_endpoint_selection() {
+        assert_eq!("always".parse(), Ok(MtlsEndpoint::Always));
+        assert!("sometimes".parse::<MtlsEndpoint>().is_err());
+
+        for has_certificate in [false, true] {
+            assert!(!MtlsEndpoint::Never.use_mtls(has_certificate));
+            assert!(MtlsEndpoint::Always.use_mtls(has_certificate));
+            assert_eq!(MtlsEndpoint::Auto.use_mtls(has_certificate), has_certificate);
+        }
+    }
+}
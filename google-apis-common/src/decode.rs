@@ -0,0 +1,98 @@
+//! Decoding the JSON bodies of responses.
+//!
+//! Responses are decoded with [`from_str()`], which uses `serde_json` by default. With the
+//! `simd-json` feature, bodies of at least [`MIN_SIMD_SIZE`] bytes, like long lists of tasks or
+//! the rows of query results, are decoded by `simd-json` instead, which parses them considerably
+//! faster on CPUs with SIMD instructions. Bodies `simd-json` rejects are decoded again by
+//! `serde_json`, so the errors of both backends are alike, and so are the values they decode.
+//!
+//! The `arbitrary-precision` feature takes precedence, as `simd-json` would round the numbers
+//! within `serde_json::Value` fields to `f64` without failing.
+use serde::de::DeserializeOwned;
+use serde_json as json;
+
+/// The size of bodies below which they are decoded by `serde_json` with the `simd-json` feature,
+/// as copying them into the buffer `simd-json` parses in place costs more than it saves.
+pub const MIN_SIMD_SIZE: usize = 4096;
+
+/// Returns the value decoded from the JSON `body`, using `simd-json` for large bodies if the
+/// `simd-json` feature is enabled, and `serde_json` otherwise.
+pub fn from_str<T: DeserializeOwned>(body: &str) -> json::Result<T> {
+    #[cfg(all(feature = "simd-json", not(feature = "arbitrary-precision")))]
+    if body.len() >= MIN_SIMD_SIZE {
+        let mut buf = body.as_bytes().to_vec();
+        if let Ok(value) = simd_json::serde::from_slice(&mut buf) {
+            return Ok(value);
+        }
+    }
+    json::from_str(body)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::serde::duration;
+    use serde::Deserialize;
+    use serde_with::{serde_as, DisplayFromStr};
+    use std::collections::HashMap;
+
+    #[serde_as]
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct Task {
+        name: Option<String>,
+        #[serde_as(as = "Option<duration::Wrapper>")]
+        #[serde(default)]
+        dispatch_deadline: Option<chrono::Duration>,
+        #[serde_as(as = "Option<DisplayFromStr>")]
+        #[serde(default)]
+        dispatch_count: Option<i64>,
+        #[serde(default)]
+        labels: HashMap<String, String>,
+        payload: Option<json::Value>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct ListTasksResponse {
+        tasks: Option<Vec<Task>>,
+        next_page_token: Option<String>,
+    }
+
+    #[test]
+    fn large_and_small_bodies_decode_alike() {
+        let task = r#"{"name": "projects/p/locations/l/queues/q/tasks/TASK", "dispatchDeadline": "1.5s",
+            "dispatchCount": "3", "labels": {"a": "ä"}, "payload": {"n": [1, 2.5, null, true]}}"#;
+        for count in [1, 100] {
+            let tasks = (0..count)
+                .map(|i| task.replace("TASK", &i.to_string()))
+                .collect::<Vec<_>>();
+            let body = format!(
+                r#"{{"tasks": [{}], "nextPageToken": "t"}}"#,
+                tasks.join(",")
+            );
+            assert_eq!(body.len() >= MIN_SIMD_SIZE, count == 100);
+
+            let decoded: ListTasksResponse = from_str(&body).unwrap();
+            assert_eq!(decoded, json::from_str(&body).unwrap());
+            let tasks = decoded.tasks.unwrap();
+            assert_eq!(tasks.len(), count);
+            assert_eq!(
+                tasks[count - 1].dispatch_deadline,
+                Some(chrono::Duration::milliseconds(1500))
+            );
+            assert_eq!(tasks[0].dispatch_count, Some(3));
+            assert_eq!(tasks[0].labels["a"], "ä");
+
+            let truncated = &body[..body.len() - 1];
+            assert_eq!(
+                from_str::<ListTasksResponse>(truncated)
+                    .unwrap_err()
+                    .to_string(),
+                json::from_str::<ListTasksResponse>(truncated)
+                    .unwrap_err()
+                    .to_string()
+            );
+        }
+    }
+}
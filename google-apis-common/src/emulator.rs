@@ -0,0 +1,73 @@
+//! Routing of requests to local emulators of APIs.
+//!
+//! APIs with a local emulator, like Pub/Sub, Firestore and Cloud Storage, read the environment
+//! variable naming its host, like `PUBSUB_EMULATOR_HOST=localhost:8085`, when their hub is created.
+//! If it is set, all requests are sent to the emulator, without authorization, so no credentials are
+//! needed for local testing. The emulators of other APIs, and fakes of any API, are used by naming
+//! their host in [`ENDPOINT_OVERRIDES_ENV`], like
+//! `GOOGLE_API_ENDPOINT_OVERRIDES=spanner=localhost:9020,pubsub=localhost:8085`.
+//!
+//! Emulators are reached using plain HTTP unless their host is given with a scheme, so the connector
+//! of the hub has to support it.
+use std::env;
+
+/// The environment variable naming the hosts to which the requests of APIs are sent instead, as
+/// comma-separated `<api>=<host>` pairs.
+pub const ENDPOINT_OVERRIDES_ENV: &str = "GOOGLE_API_ENDPOINT_OVERRIDES";
+
+/// Returns the root url of the emulator to which the requests of the API called `api_name` are
+/// sent, if it is named in [`ENDPOINT_OVERRIDES_ENV`] or `emulator_host_env`, in this order.
+pub fn emulator_from_env(api_name: &str, emulator_host_env: Option<&str>) -> Option<String> {
+    env::var(ENDPOINT_OVERRIDES_ENV)
+        .ok()
+        .and_then(|overrides| endpoint_override(&overrides, api_name).map(str::to_string))
+        .or_else(|| emulator_host_env.and_then(|name| env::var(name).ok()))
+        .filter(|host| !host.trim().is_empty())
+        .map(|host| root_url(&host))
+}
+
+/// Returns the host of `api_name` among the comma-separated `<api>=<host>` pairs of `overrides`.
+pub fn endpoint_override<'a>(overrides: &'a str, api_name: &str) -> Option<&'a str> {
+    overrides
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(api, _)| api.trim() == api_name)
+        .map(|(_, host)| host.trim())
+}
+
+/// Returns the root url of an emulator at `host`, like `localhost:8085` or `http://[::1]:9023/`.
+pub fn root_url(host: &str) -> String {
+    let host = host.trim().trim_end_matches('/');
+    if host.contains("://") {
+        format!("{}/", host)
+    } else {
+        format!("http://{}/", host)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn emulator_urls() {
+        assert_eq!(root_url("localhost:8085"), "http://localhost:8085/");
+        assert_eq!(root_url(" http://[::1]:9023/ "), "http://[::1]:9023/");
+        assert_eq!(
+            root_url("https://fake.example.com"),
+            "https://fake.example.com/"
+        );
+
+        let overrides = "spanner=localhost:9020, pubsub = 127.0.0.1:8085,invalid";
+        assert_eq!(
+            endpoint_override(overrides, "pubsub"),
+            Some("127.0.0.1:8085")
+        );
+        assert_eq!(
+            endpoint_override(overrides, "spanner"),
+            Some("localhost:9020")
+        );
+        assert_eq!(endpoint_override(overrides, "storage"), None);
+        assert_eq!(endpoint_override("", "storage"), None);
+    }
+}
@@ -0,0 +1,121 @@
+//! Optimistic concurrency control using etags.
+//!
+//! Resources like IAM policies carry an etag, which the server compares with the current one when
+//! the resource is written, failing the write if the resource was modified concurrently. A
+//! read-modify-write cycle run with [`read_modify_write()`] is restarted in this case, so no
+//! concurrent modification is lost. The generated `modify_iam_policy()` methods are built on it.
+use std::future::Future;
+
+use crate::{Error, Result};
+
+/// The number of times a read-modify-write cycle is restarted after a conflicting write.
+pub const MAX_CONFLICT_RETRIES: usize = 5;
+
+/// Returns true if `err` indicates that a write was rejected as the resource was modified since it
+/// was read, i.e. its etag didn't match.
+pub fn is_conflict(err: &Error) -> bool {
+    let code = match err {
+        Error::Api(err) => err.code,
+        Error::Failure(response) => response.status().as_u16(),
+        _ => return false,
+    };
+    matches!(code, 409 | 412)
+}
+
+/// Run a read-modify-write cycle: the value obtained with `read` is changed by `modify` and passed
+/// to `write`, whose result is returned. If the write fails with a [conflict](is_conflict), the
+/// cycle is restarted, up to [`MAX_CONFLICT_RETRIES`] times.
+pub async fn read_modify_write<T, R, RF, M, W, WF>(
+    mut read: R,
+    mut modify: M,
+    mut write: W,
+) -> Result<T>
+where
+    R: FnMut() -> RF,
+    RF: Future<Output = Result<T>>,
+    M: FnMut(&mut T),
+    W: FnMut(T) -> WF,
+    WF: Future<Output = Result<T>>,
+{
+    let mut conflicts = 0;
+    loop {
+        let mut value = read().await?;
+        modify(&mut value);
+        match write(value).await {
+            Err(err) if is_conflict(&err) && conflicts < MAX_CONFLICT_RETRIES => conflicts += 1,
+            res => return res,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ApiError;
+    use std::cell::Cell;
+
+    fn api_error(code: u16) -> Error {
+        Error::Api(ApiError {
+            code,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn cycles_restart_on_conflicts() {
+        let stored = Cell::new(0);
+        let writes = Cell::new(0);
+        let res = read_modify_write(
+            || async { Ok(stored.get()) },
+            |value| *value += 1,
+            |value| {
+                writes.set(writes.get() + 1);
+                let conflict = writes.get() < 3;
+                if conflict {
+                    // another writer got there first
+                    stored.set(stored.get() + 10);
+                }
+                let stored = &stored;
+                async move {
+                    if conflict {
+                        return Err(api_error(409));
+                    }
+                    stored.set(value);
+                    Ok(value)
+                }
+            },
+        )
+        .await;
+        assert_eq!(res.unwrap(), 21);
+        assert_eq!(writes.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_are_bounded() {
+        let writes = Cell::new(0);
+        let res = read_modify_write(
+            || async { Ok(()) },
+            |_| {},
+            |_| {
+                writes.set(writes.get() + 1);
+                async { Err::<(), _>(api_error(412)) }
+            },
+        )
+        .await;
+        assert!(matches!(res, Err(Error::Api(_))));
+        assert_eq!(writes.get(), MAX_CONFLICT_RETRIES + 1);
+
+        writes.set(0);
+        let res = read_modify_write(
+            || async { Ok(()) },
+            |_| {},
+            |_| {
+                writes.set(writes.get() + 1);
+                async { Err::<(), _>(api_error(403)) }
+            },
+        )
+        .await;
+        assert!(res.is_err());
+        assert_eq!(writes.get(), 1);
+    }
+}
@@ -0,0 +1,713 @@
+//! Workload identity federation using external account credentials.
+//!
+//! Workloads running outside of Google Cloud, e.g. on AWS or in CI systems like GitHub Actions,
+//! can exchange the credentials of their own platform for Google access tokens, without any
+//! long-lived service account keys. The configuration of the workload identity pool provider is
+//! described by an [external account credential file], as created by
+//! `gcloud iam workload-identity-pools create-cred-config`, which is read into
+//! [`ExternalAccountCredentials`].
+//!
+//! The following sources of the external credentials (the *subject token*) are supported:
+//! - files, e.g. OIDC tokens written by the platform, as plain text or JSON
+//! - urls, e.g. local token endpoints of the platform, as plain text or JSON
+//! - AWS, signing a `GetCallerIdentity` request with the credentials taken from the environment
+//!   or the EC2 instance metadata (including IMDSv2 sessions)
+//!
+//! Executable-sourced credentials are not supported.
+//!
+//! # Example
+//! ```rust,no_run
+//! # async fn dox() -> Result<(), Box<dyn std::error::Error>> {
+//! use google_apis_common::{ExternalAccount, ExternalAccountCredentials, GetToken};
+//!
+//! let credentials = ExternalAccountCredentials::from_file("credentials.json")?;
+//! let auth = ExternalAccount::new(hyper::Client::new(), credentials);
+//! let token = auth.get_token(&["https://www.googleapis.com/auth/cloud-platform"]).await.unwrap();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [external account credential file]: https://google.aip.dev/auth/4117
+use std::collections::HashMap;
+use std::env;
+use std::error::Error as StdError;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use http::{Method, Uri};
+use hyper::client::connect::Connection;
+use hyper::header::CONTENT_TYPE;
+use ring::{digest, hmac};
+use serde::Deserialize;
+use serde_json as json;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_service::Service;
+
+use crate::auth::{scope_key, GetToken, GetTokenOutput, TokenCache, CLOUD_PLATFORM_SCOPE};
+use crate::clock::ServerClock;
+use crate::ServiceAccountImpersonation;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+const EXTERNAL_ACCOUNT_TYPE: &str = "external_account";
+const DEFAULT_TOKEN_URL: &str = "https://sts.googleapis.com/v1/token";
+const GENERATE_ACCESS_TOKEN_SUFFIX: &str = ":generateAccessToken";
+const AWS_ENVIRONMENT_PREFIX: &str = "aws";
+const AWS_SERVICE: &str = "sts";
+const AWS_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const AWS_METADATA_TOKEN_HEADER: &str = "x-aws-ec2-metadata-token";
+const AWS_METADATA_TOKEN_TTL_HEADER: &str = "x-aws-ec2-metadata-token-ttl-seconds";
+
+/// The contents of an external account credential file, describing how to obtain the subject
+/// token and exchange it for access tokens.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExternalAccountCredentials {
+    #[serde(rename = "type")]
+    kind: String,
+    audience: String,
+    subject_token_type: String,
+    #[serde(default = "default_token_url")]
+    token_url: String,
+    service_account_impersonation_url: Option<String>,
+    workforce_pool_user_project: Option<String>,
+    credential_source: CredentialSource,
+}
+
+fn default_token_url() -> String {
+    DEFAULT_TOKEN_URL.to_string()
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct CredentialSource {
+    file: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    format: Option<SubjectTokenFormat>,
+    environment_id: Option<String>,
+    region_url: Option<String>,
+    regional_cred_verification_url: Option<String>,
+    imdsv2_session_token_url: Option<String>,
+    executable: Option<json::Value>,
+}
+
+/// How the subject token is contained in the file or response it is read from.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum SubjectTokenFormat {
+    Text,
+    Json { subject_token_field_name: String },
+}
+
+impl ExternalAccountCredentials {
+    /// Parse the contents of an external account credential file.
+    pub fn from_json(json: &str) -> io::Result<ExternalAccountCredentials> {
+        let credentials: ExternalAccountCredentials = json::from_str(json)?;
+        credentials.validate().map_err(invalid_data)?;
+        Ok(credentials)
+    }
+
+    /// Read the external account credential file at `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<ExternalAccountCredentials> {
+        ExternalAccountCredentials::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the audience, identifying the workload identity pool provider.
+    pub fn audience(&self) -> &str {
+        &self.audience
+    }
+
+    /// Returns the email address of the service account to impersonate, if any.
+    pub fn service_account(&self) -> Option<&str> {
+        let url = self.service_account_impersonation_url.as_deref()?;
+        url.strip_suffix(GENERATE_ACCESS_TOKEN_SUFFIX)?
+            .rsplit('/')
+            .next()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.kind != EXTERNAL_ACCOUNT_TYPE {
+            return Err(format!(
+                "Expected credentials of type '{}', got '{}'",
+                EXTERNAL_ACCOUNT_TYPE, self.kind
+            ));
+        }
+        if self.service_account_impersonation_url.is_some() && self.service_account().is_none() {
+            return Err(
+                "The service account impersonation url doesn't name a service account".to_string(),
+            );
+        }
+        let source = &self.credential_source;
+        if source.executable.is_some() {
+            return Err("Executable-sourced credentials are not supported".to_string());
+        }
+        match &source.environment_id {
+            Some(id)
+                if id.starts_with(AWS_ENVIRONMENT_PREFIX)
+                    && source.regional_cred_verification_url.is_none() =>
+            {
+                return Err("AWS credentials require a regional_cred_verification_url".into())
+            }
+            Some(id) if id.starts_with(AWS_ENVIRONMENT_PREFIX) => {}
+            Some(id) => return Err(format!("Unsupported credential environment '{}'", id)),
+            None if source.file.is_none() && source.url.is_none() => {
+                return Err("The credential source requires a file or url".to_string())
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[derive(Deserialize)]
+struct TokenExchangeResponse {
+    access_token: String,
+    expires_in: Option<i64>,
+}
+
+/// Obtains access tokens through workload identity federation, by exchanging the subject token
+/// described by [`ExternalAccountCredentials`] at the Security Token Service.
+///
+/// If the credentials name a service account to impersonate, the federated tokens are in turn
+/// exchanged for tokens of the service account using [`ServiceAccountImpersonation`].
+/// Tokens are cached per set of scopes and reused until shortly before they expire, as determined
+/// by its [`ServerClock`].
+#[derive(Clone)]
+pub struct ExternalAccount<S> {
+    client: hyper::Client<S, hyper::body::Body>,
+    credentials: Arc<ExternalAccountCredentials>,
+    tokens: TokenCache,
+    clock: ServerClock,
+    impersonation: Option<ServiceAccountImpersonation<S>>,
+}
+
+impl<S> ExternalAccount<S>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    /// Obtain tokens as described by `credentials`, sending all requests through `client`.
+    pub fn new(
+        client: hyper::Client<S, hyper::body::Body>,
+        credentials: ExternalAccountCredentials,
+    ) -> ExternalAccount<S> {
+        ExternalAccount {
+            client,
+            credentials: Arc::new(credentials),
+            tokens: Default::default(),
+            clock: Default::default(),
+            impersonation: None,
+        }
+        .with_impersonation()
+    }
+
+    /// Set the clock used to determine whether tokens expired, which is updated with the time of
+    /// the server on each token request. Share it with the Hub to also sample its responses.
+    pub fn clock(mut self, clock: ServerClock) -> ExternalAccount<S> {
+        self.clock = clock;
+        self.with_impersonation()
+    }
+
+    /// Returns the credentials tokens are obtained for.
+    pub fn credentials(&self) -> &ExternalAccountCredentials {
+        &self.credentials
+    }
+
+    fn with_impersonation(mut self) -> ExternalAccount<S> {
+        self.impersonation = None;
+        if let Some(service_account) = self.credentials.service_account() {
+            let service_account = service_account.to_string();
+            self.impersonation = Some(
+                ServiceAccountImpersonation::new(
+                    self.client.clone(),
+                    self.clone(),
+                    service_account,
+                )
+                .clock(self.clock.clone()),
+            );
+        }
+        self
+    }
+
+    fn cached_token(&self, key: &[String]) -> Option<String> {
+        let tokens = self.tokens.lock().unwrap();
+        match tokens.get(key) {
+            Some((token, expires)) if !self.clock.is_expired(*expires) => Some(token.clone()),
+            _ => None,
+        }
+    }
+
+    async fn federated_token(&self, key: Vec<String>) -> Result<Option<String>, BoxError> {
+        if let Some(token) = self.cached_token(&key) {
+            return Ok(Some(token));
+        }
+        let subject_token = self.subject_token().await?;
+        let body = token_exchange_body(&self.credentials, &key, &subject_token);
+        let req = hyper::Request::builder()
+            .method(Method::POST)
+            .uri(&self.credentials.token_url)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(hyper::body::Body::from(body))?;
+
+        let res = self.client.request(req).await?;
+        self.clock.observe(res.headers());
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await?;
+        if !status.is_success() {
+            return Err(format!(
+                "Failed to exchange the external account token: {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into());
+        }
+
+        let res: TokenExchangeResponse = json::from_slice(&body)?;
+        let expires = self.clock.now() + chrono::Duration::seconds(res.expires_in.unwrap_or(3600));
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(key, (res.access_token.clone(), expires));
+        Ok(Some(res.access_token))
+    }
+
+    async fn subject_token(&self) -> Result<String, BoxError> {
+        let source = &self.credentials.credential_source;
+        if source.environment_id.is_some() {
+            return self.aws_subject_token(source).await;
+        }
+        let content = match (&source.file, &source.url) {
+            (Some(file), _) => fs::read_to_string(file)?,
+            (None, Some(url)) => {
+                let headers: Vec<_> = source
+                    .headers
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect();
+                self.fetch(Method::GET, url, &headers).await?
+            }
+            (None, None) => unreachable!("validated on parsing"),
+        };
+        parse_subject_token(source.format.as_ref(), &content)
+    }
+
+    async fn aws_subject_token(&self, source: &CredentialSource) -> Result<String, BoxError> {
+        let mut credentials = AwsCredentials::from_env();
+        let mut region = env::var("AWS_REGION")
+            .or_else(|_| env::var("AWS_DEFAULT_REGION"))
+            .ok();
+
+        if credentials.is_none() || region.is_none() {
+            let mut headers = Vec::new();
+            let session_token;
+            if let Some(url) = &source.imdsv2_session_token_url {
+                session_token = self
+                    .fetch(Method::PUT, url, &[(AWS_METADATA_TOKEN_TTL_HEADER, "300")])
+                    .await?;
+                headers.push((AWS_METADATA_TOKEN_HEADER, session_token.as_str()));
+            }
+            if region.is_none() {
+                let url = source
+                    .region_url
+                    .as_deref()
+                    .ok_or("AWS_REGION isn't set, and the credentials have no region_url")?;
+                // the metadata server provides the availability zone, e.g. `us-east-1b`
+                let mut zone = self.fetch(Method::GET, url, &headers).await?;
+                zone.pop();
+                region = Some(zone);
+            }
+            if credentials.is_none() {
+                let url = source
+                    .url
+                    .as_deref()
+                    .ok_or("AWS credentials aren't set, and the credentials have no url")?;
+                let role = self.fetch(Method::GET, url, &headers).await?;
+                let role_url = format!("{}/{}", url.trim_end_matches('/'), role.trim());
+                let body = self.fetch(Method::GET, &role_url, &headers).await?;
+                credentials = Some(json::from_str(&body)?);
+            }
+        }
+
+        let (credentials, region) = (credentials.unwrap(), region.unwrap());
+        let url = source
+            .regional_cred_verification_url
+            .as_deref()
+            .expect("validated on parsing")
+            .replace("{region}", &region);
+        let request = aws_signed_request(
+            &credentials,
+            &region,
+            &url,
+            &self.credentials.audience,
+            Utc::now(),
+        )?;
+        Ok(uri_encode(&request.to_string(), true))
+    }
+
+    async fn fetch(
+        &self,
+        method: Method,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<String, BoxError> {
+        let mut req = hyper::Request::builder().method(method).uri(url);
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        let res = self
+            .client
+            .request(req.body(hyper::body::Body::empty())?)
+            .await?;
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await?;
+        let body = String::from_utf8_lossy(&body).into_owned();
+        if !status.is_success() {
+            return Err(format!(
+                "Failed to obtain the external account credentials from '{}': {}: {}",
+                url, status, body
+            )
+            .into());
+        }
+        Ok(body)
+    }
+}
+
+impl<S> GetToken for ExternalAccount<S>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    fn get_token<'a>(&'a self, scopes: &'a [&str]) -> GetTokenOutput<'a> {
+        match &self.impersonation {
+            Some(impersonation) => impersonation.get_token(scopes),
+            None => Box::pin(self.federated_token(scope_key(scopes))),
+        }
+    }
+}
+
+/// Returns the form sent to the Security Token Service to exchange `subject_token` for a token
+/// with the scopes in `key`.
+fn token_exchange_body(
+    credentials: &ExternalAccountCredentials,
+    key: &[String],
+    subject_token: &str,
+) -> String {
+    let scope = if credentials.service_account_impersonation_url.is_some() {
+        CLOUD_PLATFORM_SCOPE.to_string()
+    } else {
+        key.join(" ")
+    };
+    let mut form = url::form_urlencoded::Serializer::new(String::new());
+    form.append_pair(
+        "grant_type",
+        "urn:ietf:params:oauth:grant-type:token-exchange",
+    )
+    .append_pair("audience", &credentials.audience)
+    .append_pair("scope", &scope)
+    .append_pair(
+        "requested_token_type",
+        "urn:ietf:params:oauth:token-type:access_token",
+    )
+    .append_pair("subject_token", subject_token)
+    .append_pair("subject_token_type", &credentials.subject_token_type);
+    if let Some(project) = &credentials.workforce_pool_user_project {
+        form.append_pair(
+            "options",
+            &json::json!({ "userProject": project }).to_string(),
+        );
+    }
+    form.finish()
+}
+
+fn parse_subject_token(
+    format: Option<&SubjectTokenFormat>,
+    content: &str,
+) -> Result<String, BoxError> {
+    match format {
+        None | Some(SubjectTokenFormat::Text) => Ok(content.trim().to_string()),
+        Some(SubjectTokenFormat::Json {
+            subject_token_field_name,
+        }) => {
+            let value: json::Value = json::from_str(content)?;
+            match value.get(subject_token_field_name) {
+                Some(json::Value::String(token)) => Ok(token.clone()),
+                _ => Err(format!(
+                    "The subject token field '{}' is missing",
+                    subject_token_field_name
+                )
+                .into()),
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    token: Option<String>,
+}
+
+impl AwsCredentials {
+    fn from_env() -> Option<AwsCredentials> {
+        Some(AwsCredentials {
+            access_key_id: env::var("AWS_ACCESS_KEY_ID").ok()?,
+            secret_access_key: env::var("AWS_SECRET_ACCESS_KEY").ok()?,
+            token: env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+/// Returns the `GetCallerIdentity` request to `url`, signed with AWS Signature Version 4 as
+/// expected by the Security Token Service in place of a subject token.
+fn aws_signed_request(
+    credentials: &AwsCredentials,
+    region: &str,
+    url: &str,
+    audience: &str,
+    now: DateTime<Utc>,
+) -> Result<json::Value, BoxError> {
+    let uri: Uri = url.parse()?;
+    let host = uri.host().ok_or("The verification url has no host")?;
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = &amz_date[..8];
+
+    let mut headers = vec![
+        ("host", host.to_string()),
+        ("x-amz-date", amz_date.clone()),
+        ("x-goog-cloud-target-resource", audience.to_string()),
+    ];
+    if let Some(token) = &credentials.token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    headers.sort();
+
+    let mut query: Vec<_> = url::form_urlencoded::parse(uri.query().unwrap_or("").as_bytes())
+        .map(|(k, v)| (uri_encode(&k, true), uri_encode(&v, true)))
+        .collect();
+    query.sort();
+    let path = match uri.path() {
+        "" => "/",
+        path => path,
+    };
+    let signed_headers = headers
+        .iter()
+        .map(|(k, _)| *k)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "POST\n{}\n{}\n{}\n{}\n{}",
+        uri_encode(path, false),
+        query
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&"),
+        headers
+            .iter()
+            .map(|(k, v)| format!("{}:{}\n", k, v.trim()))
+            .collect::<String>(),
+        signed_headers,
+        hex(digest::digest(&digest::SHA256, b"").as_ref()),
+    );
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, AWS_SERVICE);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        AWS_ALGORITHM,
+        amz_date,
+        scope,
+        hex(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref())
+    );
+    let key = aws_signing_key(&credentials.secret_access_key, date, region, AWS_SERVICE);
+    let key = hmac::Key::new(hmac::HMAC_SHA256, &key);
+    let signature = hex(hmac::sign(&key, string_to_sign.as_bytes()).as_ref());
+    let authorization = format!(
+        "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+        AWS_ALGORITHM, credentials.access_key_id, scope, signed_headers, signature
+    );
+
+    let headers: Vec<_> = std::iter::once(("Authorization", authorization))
+        .chain(headers)
+        .map(|(key, value)| json::json!({"key": key, "value": value}))
+        .collect();
+    Ok(json::json!({
+        "url": url,
+        "method": "POST",
+        "headers": headers,
+    }))
+}
+
+fn aws_signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let sign = |key: &[u8], data: &str| {
+        hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes())
+    };
+    let key = sign(format!("AWS4{}", secret).as_bytes(), date);
+    let key = sign(key.as_ref(), region);
+    let key = sign(key.as_ref(), service);
+    sign(key.as_ref(), "aws4_request").as_ref().to_vec()
+}
+
+/// Percent-encode all but the unreserved characters, and slashes unless `encode_slash` is set.
+pub(crate) fn uri_encode(s: &str, encode_slash: bool) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b'/' if !encode_slash => "/".to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const AUDIENCE: &str = "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/provider";
+
+    fn credentials(source: json::Value) -> json::Value {
+        json::json!({
+            "type": "external_account",
+            "audience": AUDIENCE,
+            "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+            "credential_source": source,
+        })
+    }
+
+    #[test]
+    fn parse_credentials() {
+        let mut json = credentials(json::json!({"file": "/var/run/token"}));
+        let parsed = ExternalAccountCredentials::from_json(&json.to_string()).unwrap();
+        assert_eq!(parsed.audience(), AUDIENCE);
+        assert_eq!(parsed.token_url, DEFAULT_TOKEN_URL);
+        assert_eq!(parsed.service_account(), None);
+
+        json["service_account_impersonation_url"] = "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/sa@project.iam.gserviceaccount.com:generateAccessToken".into();
+        let parsed = ExternalAccountCredentials::from_json(&json.to_string()).unwrap();
+        assert_eq!(
+            parsed.service_account(),
+            Some("sa@project.iam.gserviceaccount.com")
+        );
+
+        json["service_account_impersonation_url"] = "https://example.com".into();
+        assert!(ExternalAccountCredentials::from_json(&json.to_string()).is_err());
+
+        for source in [
+            json::json!({}),
+            json::json!({"executable": {"command": "token"}}),
+            json::json!({"environment_id": "azure1"}),
+            json::json!({"environment_id": "aws1"}),
+        ] {
+            let json = credentials(source).to_string();
+            assert!(ExternalAccountCredentials::from_json(&json).is_err());
+        }
+
+        let mut json = credentials(json::json!({"url": "http://localhost/token"}));
+        json["type"] = "service_account".into();
+        assert!(ExternalAccountCredentials::from_json(&json.to_string()).is_err());
+    }
+
+    #[test]
+    fn subject_token_formats() {
+        assert_eq!(parse_subject_token(None, "token\n").unwrap(), "token");
+        let format = SubjectTokenFormat::Json {
+            subject_token_field_name: "value".to_string(),
+        };
+        assert_eq!(
+            parse_subject_token(Some(&format), r#"{"value": "token", "count": 1}"#).unwrap(),
+            "token"
+        );
+        assert!(parse_subject_token(Some(&format), r#"{"count": 1}"#).is_err());
+    }
+
+    #[test]
+    fn token_exchange_form() {
+        let mut json = credentials(json::json!({"file": "/var/run/token"}));
+        json["workforce_pool_user_project"] = "project".into();
+        let parsed = ExternalAccountCredentials::from_json(&json.to_string()).unwrap();
+        let form = token_exchange_body(&parsed, &scope_key(&["a", "b"]), "subject");
+        let pairs: HashMap<_, _> = url::form_urlencoded::parse(form.as_bytes())
+            .into_owned()
+            .collect();
+        assert_eq!(pairs["scope"], "a b");
+        assert_eq!(pairs["subject_token"], "subject");
+        assert_eq!(pairs["audience"], AUDIENCE);
+        assert_eq!(pairs["options"], r#"{"userProject":"project"}"#);
+    }
+
+    #[test]
+    fn aws_signing() {
+        // https://docs.aws.amazon.com/general/latest/gr/signature-v4-examples.html
+        let key = aws_signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+
+        let credentials = AwsCredentials {
+            access_key_id: "AKID".to_string(),
+            secret_access_key: "secret".to_string(),
+            token: Some("session".to_string()),
+        };
+        let now = DateTime::parse_from_rfc3339("2024-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let url = "https://sts.us-east-1.amazonaws.com?Action=GetCallerIdentity&Version=2011-06-15";
+        let request = aws_signed_request(&credentials, "us-east-1", url, AUDIENCE, now).unwrap();
+        assert_eq!(request["url"], url);
+        assert_eq!(request["method"], "POST");
+        let headers = request["headers"].as_array().unwrap();
+        let keys: Vec<_> = headers.iter().map(|h| h["key"].as_str().unwrap()).collect();
+        assert_eq!(
+            keys,
+            [
+                "Authorization",
+                "host",
+                "x-amz-date",
+                "x-amz-security-token",
+                "x-goog-cloud-target-resource"
+            ]
+        );
+        let authorization = headers[0]["value"].as_str().unwrap();
+        assert!(authorization.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKID/20240102/us-east-1/sts/aws4_request, \
+             SignedHeaders=host;x-amz-date;x-amz-security-token;x-goog-cloud-target-resource, \
+             Signature="
+        ));
+        assert_eq!(headers[2]["value"], "20240102T030405Z");
+        assert_eq!(
+            aws_signed_request(&credentials, "us-east-1", url, AUDIENCE, now).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn aws_encoding() {
+        assert_eq!(uri_encode("a b/c~", true), "a%20b%2Fc~");
+        assert_eq!(uri_encode("/a b/", false), "/a%20b/");
+        assert_eq!(uri_encode(r#"{"k":"v"}"#, true), "%7B%22k%22%3A%22v%22%7D");
+    }
+}
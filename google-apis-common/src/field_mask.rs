@@ -1,7 +1,9 @@
+use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json as json;
 
 fn titlecase(source: &str, dest: &mut String) {
     let mut underscore = false;
@@ -38,7 +40,141 @@ impl FieldMask {
     /// Create a new `FieldMask` from a list of paths. These are converted to snake
     /// case if they aren't already.
     pub fn new<S: AsRef<str>>(values: &[S]) -> Self {
-        return Self(values.iter().map(|s| snakecase(s.as_ref())).collect());
+        Self(values.iter().map(|s| snakecase(s.as_ref())).collect())
+    }
+
+    /// Returns the mask of the fields in which `new` differs from `old`, to update a resource to
+    /// `new` without sending all of its fields, and without knowing which of them were changed.
+    ///
+    /// Both are compared in their JSON serialization. Nested messages are compared field by field,
+    /// while lists, and the fields which the [`MapFields`] of the resource tells are maps, are
+    /// replaced as a whole. Fields which are set in `old` but not in `new` are part of the mask, so
+    /// the update clears them.
+    ///
+    /// The mask is empty if nothing changed, which most APIs treat as updating all fields, so
+    /// check [`FieldMask::is_empty()`] before sending it. It fails if a resource can't be
+    /// serialized to JSON.
+    pub fn diff<T: Serialize + MapFields>(old: &T, new: &T) -> json::Result<FieldMask> {
+        let old = json::to_value(old)?;
+        let new = json::to_value(new)?;
+        let mut paths = Vec::new();
+        diff_values(
+            &mut Vec::new(),
+            Some(&old),
+            Some(&new),
+            &T::is_map,
+            &mut paths,
+        );
+        Ok(FieldMask(paths))
+    }
+
+    /// Returns true if the mask selects no fields.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|path| path.is_empty())
+    }
+
+    /// Returns the paths of the mask, in snake case.
+    pub fn into_paths(self) -> Vec<String> {
+        self.0
+    }
+
+    /// Returns the mask in the syntax of the `fields` parameter, which selects the fields of a
+    /// partial response: nested fields are selected with `/`, and fields of the same parent are
+    /// grouped in parentheses, like `files(id,name),nextPageToken` for the paths `files.id`,
+    /// `files.name` and `next_page_token`.
+    pub fn to_fields_param(&self) -> String {
+        let mut root = Selection::default();
+        for path in self.0.iter().filter(|path| !path.is_empty()) {
+            let mut selection = &mut root;
+            for field in path.split('.') {
+                let mut name = String::new();
+                titlecase(field, &mut name);
+                selection = selection.field(name);
+                if selection.whole {
+                    break;
+                }
+            }
+            selection.whole = true;
+            selection.fields.clear();
+        }
+        let mut param = String::new();
+        root.write_fields(&mut param);
+        param
+    }
+}
+
+/// The fields selected within a field, in the order of the paths selecting them first.
+#[derive(Default)]
+struct Selection {
+    fields: Vec<(String, Selection)>,
+    // whether the field is selected with all of its nested fields
+    whole: bool,
+}
+
+impl Selection {
+    fn field(&mut self, name: String) -> &mut Selection {
+        let i = match self.fields.iter().position(|(n, _)| *n == name) {
+            Some(i) => i,
+            None => {
+                self.fields.push((name, Selection::default()));
+                self.fields.len() - 1
+            }
+        };
+        &mut self.fields[i].1
+    }
+
+    fn write_fields(&self, param: &mut String) {
+        for (i, (name, selection)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                param.push(',');
+            }
+            param.push_str(name);
+            if selection.whole {
+                continue;
+            }
+            if selection.fields.len() == 1 {
+                param.push('/');
+                selection.write_fields(param);
+            } else {
+                param.push('(');
+                selection.write_fields(param);
+                param.push(')');
+            }
+        }
+    }
+}
+
+/// The schema of a resource, which tells [`FieldMask::diff()`] which of its fields are maps.
+///
+/// Generated crates implement it for the resources of requests.
+pub trait MapFields {
+    /// Returns true if the field at `path`, which holds the JSON names of the fields leading to
+    /// it, is a map or holds any JSON value, whose keys aren't the names of fields.
+    fn is_map(path: &[&str]) -> bool;
+}
+
+/// Adds the paths of the fields below `path` which differ between `old` and `new`.
+fn diff_values<'a>(
+    path: &mut Vec<&'a str>,
+    old: Option<&'a json::Value>,
+    new: Option<&'a json::Value>,
+    is_map: &dyn Fn(&[&str]) -> bool,
+    paths: &mut Vec<String>,
+) {
+    let old = old.filter(|value| !value.is_null());
+    let new = new.filter(|value| !value.is_null());
+    match (old, new) {
+        (Some(json::Value::Object(old)), Some(json::Value::Object(new))) if !is_map(path) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                path.push(key);
+                diff_values(path, old.get(key), new.get(key), is_map, paths);
+                path.pop();
+            }
+        }
+        (old, new) if old == new => {}
+        _ if path.is_empty() => {}
+        _ => paths.push(snakecase(&path.join("."))),
     }
 }
 
@@ -67,7 +203,7 @@ impl FromStr for FieldMask {
         let mut in_quotes = false;
         let mut prev_ind = 0;
         let mut paths = Vec::new();
-        for (i, c) in s.chars().enumerate() {
+        for (i, c) in s.char_indices() {
             if c == '`' {
                 in_quotes = !in_quotes;
             } else if in_quotes {
@@ -96,7 +232,7 @@ impl Display for FieldMask {
 
 #[cfg(test)]
 mod test {
-    use crate::field_mask::FieldMask;
+    use crate::field_mask::{FieldMask, MapFields};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -125,6 +261,123 @@ mod test {
         );
     }
 
+    #[derive(Serialize, Default)]
+    #[serde(rename_all = "camelCase")]
+    struct Instance {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        display_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        node_config: Option<NodeConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        labels: Option<std::collections::HashMap<String, String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        zones: Option<Vec<String>>,
+    }
+
+    impl MapFields for Instance {
+        fn is_map(path: &[&str]) -> bool {
+            match path {
+                ["labels"] => true,
+                ["nodeConfig", rest @ ..] => NodeConfig::is_map(rest),
+                _ => false,
+            }
+        }
+    }
+
+    #[derive(Serialize, Default, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct NodeConfig {
+        machine_type: Option<String>,
+        disk_size_gb: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        metadata: Option<std::collections::HashMap<String, String>>,
+    }
+
+    impl MapFields for NodeConfig {
+        fn is_map(path: &[&str]) -> bool {
+            path == ["metadata"]
+        }
+    }
+
+    #[test]
+    fn diff_of_resources() {
+        let old = Instance {
+            display_name: Some("old".into()),
+            node_config: Some(NodeConfig {
+                machine_type: Some("n1".into()),
+                disk_size_gb: Some(10),
+                metadata: Some([("env".to_string(), "dev".to_string())].into()),
+            }),
+            labels: Some([("team".to_string(), "x".to_string())].into()),
+            zones: Some(vec!["a".into()]),
+        };
+        let new = Instance {
+            display_name: None,
+            node_config: Some(NodeConfig {
+                machine_type: Some("n1".into()),
+                disk_size_gb: Some(20),
+                metadata: Some([("env".to_string(), "prod".to_string())].into()),
+            }),
+            labels: Some([("team".to_string(), "y".to_string())].into()),
+            zones: Some(vec!["a".into(), "b".into()]),
+        };
+        assert_eq!(
+            FieldMask::diff(&old, &new).unwrap().to_string(),
+            "displayName,labels,nodeConfig.diskSizeGb,nodeConfig.metadata,zones"
+        );
+
+        let unchanged = FieldMask::diff(&old, &old).unwrap();
+        assert!(unchanged.is_empty());
+        assert_eq!(unchanged.to_string(), "");
+        assert_eq!(
+            FieldMask::diff(&Instance::default(), &old)
+                .unwrap()
+                .to_string(),
+            "displayName,labels,nodeConfig,zones"
+        );
+    }
+
+    #[derive(Serialize, Default)]
+    struct Unserializable {
+        by_pair: std::collections::HashMap<(u8, u8), String>,
+    }
+
+    impl MapFields for Unserializable {
+        fn is_map(_path: &[&str]) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn diff_of_unserializable_resources() {
+        let old = Unserializable::default();
+        let new = Unserializable {
+            by_pair: [((1, 2), "a".to_string())].into(),
+        };
+        assert!(FieldMask::diff(&old, &new).is_err());
+    }
+
+    #[test]
+    fn fields_param() {
+        let mask = FieldMask::new(&[
+            "files.id",
+            "next_page_token",
+            "files.owners.display_name",
+            "files.name",
+            "incomplete_search",
+            "incomplete_search.reason",
+        ]);
+        assert_eq!(
+            mask.to_fields_param(),
+            "files(id,owners/displayName,name),nextPageToken,incompleteSearch"
+        );
+        assert_eq!(
+            FieldMask::new(&["user.photo_link", "user"]).to_fields_param(),
+            "user"
+        );
+        assert_eq!(FieldMask::default().to_fields_param(), "");
+    }
+
     #[test]
     fn test_empty_wrapper() {
         assert_eq!(
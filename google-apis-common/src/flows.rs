@@ -0,0 +1,132 @@
+//! Authenticators for flows which need more than a client secret or a service account key.
+//!
+//! [`domain_wide_delegation()`] lets a service account act as a user of a Google Workspace
+//! domain, as needed by APIs like Gmail or the Admin SDK, whose data belongs to users. The
+//! service account must have been granted domain-wide delegation of the scopes used by an
+//! administrator of the domain.
+//!
+//! [`device_flow()`] authorizes the user on another device, like a phone, and suits programs
+//! without a browser, like CLIs run on a server over SSH. Its client secret must be of the type
+//! *TVs and Limited Input devices*.
+use std::error::Error as StdError;
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use http::Uri;
+use hyper::client::connect::Connection;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_service::Service;
+use yup_oauth2::authenticator_delegate::{DeviceAuthResponse, DeviceFlowDelegate};
+use yup_oauth2::{
+    ApplicationSecret, DeviceFlowAuthenticator, ServiceAccountAuthenticator, ServiceAccountKey,
+};
+
+use crate::GetToken;
+
+/// Returns the authenticator for the service account of `key` acting as the user with the email
+/// address `subject`, sending all its requests through `client`.
+///
+/// Its tokens are those of the user, which are minted by signing a JWT whose `sub` claim is the
+/// user. Fails if the private key of `key` can't be read.
+pub async fn domain_wide_delegation<S>(
+    client: hyper::Client<S, hyper::body::Body>,
+    key: ServiceAccountKey,
+    subject: &str,
+) -> io::Result<Box<dyn GetToken>>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    let auth = ServiceAccountAuthenticator::with_client(key, client)
+        .subject(subject)
+        .build()
+        .await?;
+    Ok(Box::new(auth))
+}
+
+/// Returns the authenticator for the device flow of the client `secret`, sending all its
+/// requests through `client`.
+///
+/// When a token is first needed, the code to enter on another device and the address to enter it
+/// at are written to standard error, and the token is requested once the user granted access.
+/// Tokens are kept in the file at `token_path` if given, and are only kept in memory otherwise.
+pub async fn device_flow<S>(
+    client: hyper::Client<S, hyper::body::Body>,
+    secret: ApplicationSecret,
+    token_path: Option<PathBuf>,
+) -> io::Result<Box<dyn GetToken>>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    let builder =
+        DeviceFlowAuthenticator::with_client(secret, client).flow_delegate(Box::new(StderrPrompt));
+    let auth = match token_path {
+        Some(path) => builder.persist_tokens_to_disk(path).build().await?,
+        None => builder.build().await?,
+    };
+    Ok(Box::new(auth))
+}
+
+/// Asks the user to enter the code of the device flow on standard error, leaving standard
+/// output to the program's results.
+struct StderrPrompt;
+
+impl DeviceFlowDelegate for StderrPrompt {
+    fn present_user_code<'a>(
+        &'a self,
+        response: &'a DeviceAuthResponse,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        eprintln!(
+            "Please enter {} at {} and grant access to this application before {}.",
+            response.user_code, response.verification_uri, response.expires_at
+        );
+        Box::pin(async {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn secret() -> ApplicationSecret {
+        ApplicationSecret {
+            client_id: "client-id".to_string(),
+            client_secret: "client-secret".to_string(),
+            token_uri: "https://oauth2.googleapis.com/token".to_string(),
+            auth_uri: "https://accounts.google.com/o/oauth2/auth".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn flows_are_built_without_requests() {
+        assert!(device_flow(hyper::Client::new(), secret(), None)
+            .await
+            .is_ok());
+
+        let key = ServiceAccountKey {
+            key_type: Some("service_account".to_string()),
+            project_id: None,
+            private_key_id: None,
+            private_key: "not a key".to_string(),
+            client_email: "robot@project.iam.gserviceaccount.com".to_string(),
+            client_id: None,
+            auth_uri: None,
+            token_uri: "https://oauth2.googleapis.com/token".to_string(),
+            auth_provider_x509_cert_url: None,
+            client_x509_cert_url: None,
+        };
+        let err = domain_wide_delegation(hyper::Client::new(), key, "user@example.com")
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
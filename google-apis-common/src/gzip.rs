@@ -0,0 +1,151 @@
+//! Compressing the bodies of requests and responses with gzip.
+//!
+//! With the `gzip` feature, calls ask for compressed responses using [`accept()`], and decompress
+//! them with [`decode()`] before they are parsed. Like the Google client libraries of other
+//! languages, JSON request bodies of at least [`MIN_COMPRESSED_SIZE`] bytes are compressed by
+//! [`compress_request()`] as well. Calls streaming their response or transferring media are never
+//! compressed, and compression can be turned off for a hub using `hub.gzip(false)`, or for a
+//! single call using `.gzip(false)` on its builder.
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use http::header::{HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, USER_AGENT};
+use hyper::body::Body;
+
+use crate::{Error, Result};
+
+/// The size of request bodies below which they are sent uncompressed, as compressing them would
+/// barely reduce their size.
+pub const MIN_COMPRESSED_SIZE: usize = 1024;
+
+const GZIP: &str = "gzip";
+
+/// Ask for a compressed response, using the `Accept-Encoding` header. The `User-Agent` set before
+/// is marked with `gzip` too, as the servers of Google only compress responses to such agents.
+pub fn accept(mut builder: http::request::Builder) -> http::request::Builder {
+    if let Some(headers) = builder.headers_mut() {
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static(GZIP));
+        let agent = headers
+            .get(USER_AGENT)
+            .and_then(|agent| agent.to_str().ok())
+            .unwrap_or_default();
+        if !agent.contains(GZIP) {
+            let agent = format!("{} ({})", agent, GZIP);
+            if let Ok(agent) = HeaderValue::from_str(agent.trim_start()) {
+                headers.insert(USER_AGENT, agent);
+            }
+        }
+    }
+    builder
+}
+
+/// Compress `body` and set the `Content-Encoding` and `Content-Length` headers accordingly, unless
+/// it is smaller than [`MIN_COMPRESSED_SIZE`].
+pub fn compress_request(
+    mut builder: http::request::Builder,
+    body: Vec<u8>,
+) -> (http::request::Builder, Vec<u8>) {
+    if body.len() < MIN_COMPRESSED_SIZE {
+        return (builder, body);
+    }
+    let mut encoder = GzEncoder::new(Vec::with_capacity(body.len() / 2), Compression::default());
+    // writing to memory doesn't fail
+    let compressed = match encoder.write_all(&body).and_then(|_| encoder.finish()) {
+        Ok(compressed) => compressed,
+        Err(_) => return (builder, body),
+    };
+    if let Some(headers) = builder.headers_mut() {
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(GZIP));
+        headers.insert(CONTENT_LENGTH, compressed.len().into());
+    }
+    (builder, compressed)
+}
+
+/// Decompress the body of `res` if it is compressed, removing its `Content-Encoding` header.
+pub async fn decode(res: hyper::Response<Body>) -> Result<hyper::Response<Body>> {
+    let is_compressed = matches!(
+        res.headers().get(CONTENT_ENCODING),
+        Some(encoding) if encoding.as_bytes().eq_ignore_ascii_case(GZIP.as_bytes())
+    );
+    if !is_compressed {
+        return Ok(res);
+    }
+    let (mut parts, body) = res.into_parts();
+    let compressed = hyper::body::to_bytes(body)
+        .await
+        .map_err(Error::HttpError)?;
+    let mut decoded = Vec::new();
+    GzDecoder::new(&compressed[..])
+        .read_to_end(&mut decoded)
+        .map_err(Error::Io)?;
+    parts.headers.remove(CONTENT_ENCODING);
+    parts.headers.insert(CONTENT_LENGTH, decoded.len().into());
+    Ok(hyper::Response::from_parts(parts, Body::from(decoded)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn headers(builder: &http::request::Builder, name: http::header::HeaderName) -> Option<&str> {
+        builder.headers_ref()?.get(name)?.to_str().ok()
+    }
+
+    #[test]
+    fn compressed_responses_are_accepted() {
+        let builder = accept(http::Request::builder().header(USER_AGENT, "client/1.0"));
+        assert_eq!(headers(&builder, ACCEPT_ENCODING), Some("gzip"));
+        assert_eq!(headers(&builder, USER_AGENT), Some("client/1.0 (gzip)"));
+        let builder = accept(builder);
+        assert_eq!(headers(&builder, USER_AGENT), Some("client/1.0 (gzip)"));
+        let builder = accept(http::Request::builder());
+        assert_eq!(headers(&builder, USER_AGENT), Some("(gzip)"));
+    }
+
+    #[tokio::test]
+    async fn requests_and_responses_are_compressed() {
+        let small = b"{}".to_vec();
+        let (builder, body) = compress_request(http::Request::builder(), small.clone());
+        assert_eq!(body, small);
+        assert_eq!(headers(&builder, CONTENT_ENCODING), None);
+
+        let large = format!("{{\"items\": [{}]}}", vec!["1"; 1000].join(", ")).into_bytes();
+        let (builder, body) = compress_request(http::Request::builder(), large.clone());
+        assert_eq!(headers(&builder, CONTENT_ENCODING), Some("gzip"));
+        assert_eq!(
+            headers(&builder, CONTENT_LENGTH),
+            Some(body.len().to_string().as_str())
+        );
+        assert!(body.len() < large.len());
+
+        let res = hyper::Response::builder()
+            .header(CONTENT_ENCODING, "gzip")
+            .header(CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+            .unwrap();
+        let res = decode(res).await.unwrap();
+        assert_eq!(res.headers().get(CONTENT_ENCODING), None);
+        assert_eq!(
+            res.headers()[CONTENT_LENGTH],
+            large.len().to_string().as_str()
+        );
+        let decoded = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(&decoded[..], &large[..]);
+
+        let plain = decode(hyper::Response::new(Body::from("{}")))
+            .await
+            .unwrap();
+        assert_eq!(
+            &hyper::body::to_bytes(plain.into_body()).await.unwrap()[..],
+            b"{}"
+        );
+
+        let corrupt = hyper::Response::builder()
+            .header(CONTENT_ENCODING, "GZIP")
+            .body(Body::from("{}"))
+            .unwrap();
+        assert!(matches!(decode(corrupt).await, Err(Error::Io(_))));
+    }
+}
@@ -0,0 +1,386 @@
+//! Capturing the traffic of a Hub as [HTTP Archive](http://www.softwareishard.com/blog/har-12-spec/)
+//! for debugging.
+//!
+//! A [`HarRecorder`] is an [`HttpInterceptor`] which records each request sent by the hubs it was
+//! added to, along with the head of the response and its timing. The recording can be saved to a
+//! `.har` file, to be attached to bug reports or inspected with the network panel of browsers.
+//! Since the recorder works on the HTTP exchange rather than the connection, it also captures
+//! traffic sent through TLS connectors.
+//!
+//! Credentials are redacted from the recorded headers, as well as API keys and access tokens from
+//! the query. Request bodies are recorded as is, and response bodies only by recorders created
+//! with [`HarRecorder::with_bodies()`], whose recordings can be replayed as test fixtures, see
+//! the `replay` module.
+//!
+//! # Example
+//! ```rust
+//! use google_apis_common::har::HarRecorder;
+//! use google_apis_common::InterceptorChain;
+//!
+//! let recorder = HarRecorder::new();
+//! let mut interceptors = InterceptorChain::default();
+//! // hubs take the recorder using `hub.with_interceptor(recorder.clone())`
+//! interceptors.add_interceptor(recorder.clone());
+//! // ... make calls ...
+//! recorder.save("session.har").unwrap();
+//! # std::fs::remove_file("session.har").unwrap();
+//! ```
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use http::header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE, LOCATION};
+use http::request::Parts;
+use http::{response, Method, Uri};
+use serde_json as json;
+
+use crate::interceptor::{redacted_headers, HttpInterceptor};
+
+/// Query parameters which carry credentials.
+const REDACTED_PARAMS: &[&str] = &["key", "access_token"];
+
+/// Records the traffic of hubs, sharing the recording with all its clones.
+#[derive(Clone, Default)]
+pub struct HarRecorder {
+    recording: Arc<Mutex<Recording>>,
+    bodies: bool,
+}
+
+#[derive(Default)]
+struct Recording {
+    pending: Vec<PendingRequest>,
+    entries: Vec<json::Value>,
+}
+
+/// A request which was sent, and whose response wasn't received yet.
+struct PendingRequest {
+    started: DateTime<Utc>,
+    method: Method,
+    uri: Uri,
+    request: json::Value,
+}
+
+impl HarRecorder {
+    /// An empty recording.
+    pub fn new() -> HarRecorder {
+        Default::default()
+    }
+
+    /// An empty recording, which also records the bodies of responses as text. This requires
+    /// buffering them, so it isn't suitable for large downloads.
+    pub fn with_bodies() -> HarRecorder {
+        HarRecorder {
+            bodies: true,
+            ..Default::default()
+        }
+    }
+
+    /// The amount of completed exchanges recorded so far.
+    pub fn len(&self) -> usize {
+        self.recording.lock().unwrap().entries.len()
+    }
+
+    /// Returns true if no exchange was completed yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all exchanges recorded so far.
+    pub fn clear(&self) {
+        self.recording.lock().unwrap().entries.clear();
+    }
+
+    /// Returns the HAR document of all exchanges recorded so far.
+    pub fn to_har(&self) -> json::Value {
+        json::json!({
+            "log": {
+                "version": "1.2",
+                "creator": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "pages": [],
+                "entries": self.recording.lock().unwrap().entries,
+            }
+        })
+    }
+
+    /// Write the HAR document of all exchanges recorded so far to `out`.
+    pub fn write(&self, out: impl Write) -> io::Result<()> {
+        json::to_writer_pretty(out, &self.to_har()).map_err(io::Error::from)
+    }
+
+    /// Write the HAR document of all exchanges recorded so far to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write(io::BufWriter::new(fs::File::create(path)?))
+    }
+
+    fn take_pending(&self, method: &Method, uri: &Uri) -> Option<PendingRequest> {
+        let mut recording = self.recording.lock().unwrap();
+        let index = recording
+            .pending
+            .iter()
+            .position(|p| p.method == *method && p.uri == *uri)?;
+        Some(recording.pending.remove(index))
+    }
+
+    fn complete(&self, pending: PendingRequest, elapsed: Duration, response: json::Value) {
+        let millis = elapsed.as_secs_f64() * 1000.0;
+        let entry = json::json!({
+            "startedDateTime": pending.started.to_rfc3339(),
+            "time": millis,
+            "request": pending.request,
+            "response": response,
+            "cache": {},
+            "timings": {
+                "send": 0,
+                "wait": millis,
+                "receive": 0,
+            },
+        });
+        self.recording.lock().unwrap().entries.push(entry);
+    }
+}
+
+impl HttpInterceptor for HarRecorder {
+    fn on_request(&self, parts: &Parts, body: &[u8]) {
+        let mut request = json::json!({
+            "method": parts.method.as_str(),
+            "url": redacted_uri(&parts.uri),
+            "httpVersion": format!("{:?}", parts.version),
+            "cookies": [],
+            "headers": har_headers(&parts.headers),
+            "queryString": query_string(&parts.uri),
+            "headersSize": -1,
+            "bodySize": body.len(),
+        });
+        if !body.is_empty() {
+            request["postData"] = json::json!({
+                "mimeType": header_str(&parts.headers, CONTENT_TYPE.as_str()).unwrap_or(""),
+                "text": String::from_utf8_lossy(body),
+            });
+        }
+        self.recording.lock().unwrap().pending.push(PendingRequest {
+            started: Utc::now(),
+            method: parts.method.clone(),
+            uri: parts.uri.clone(),
+            request,
+        });
+    }
+
+    fn on_response(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        response: &response::Parts,
+        elapsed: Duration,
+    ) {
+        let pending = match self.take_pending(method, uri) {
+            Some(pending) => pending,
+            None => return,
+        };
+        let size = header_str(&response.headers, CONTENT_LENGTH.as_str())
+            .and_then(|len| len.parse::<i64>().ok())
+            .unwrap_or(-1);
+        let response = json::json!({
+            "status": response.status.as_u16(),
+            "statusText": response.status.canonical_reason().unwrap_or(""),
+            "httpVersion": format!("{:?}", response.version),
+            "cookies": [],
+            "headers": har_headers(&response.headers),
+            "content": {
+                "size": size,
+                "mimeType": header_str(&response.headers, CONTENT_TYPE.as_str()).unwrap_or(""),
+            },
+            "redirectURL": header_str(&response.headers, LOCATION.as_str()).unwrap_or(""),
+            "headersSize": -1,
+            "bodySize": size,
+        });
+        self.complete(pending, elapsed, response);
+    }
+
+    fn wants_response_bodies(&self) -> bool {
+        self.bodies
+    }
+
+    fn on_response_body(&self, method: &Method, uri: &Uri, body: &[u8]) {
+        let url = redacted_uri(uri);
+        let mut recording = self.recording.lock().unwrap();
+        // the body follows the head of the latest response to the request
+        let entry = recording.entries.iter_mut().rev().find(|entry| {
+            entry["request"]["method"] == method.as_str()
+                && entry["request"]["url"] == url.as_str()
+                && entry["response"]["content"].get("text").is_none()
+        });
+        if let Some(entry) = entry {
+            entry["response"]["content"]["size"] = body.len().into();
+            entry["response"]["content"]["text"] = String::from_utf8_lossy(body).into();
+        }
+    }
+
+    fn on_error(&self, method: &Method, uri: &Uri, err: &hyper::Error) {
+        let pending = match self.take_pending(method, uri) {
+            Some(pending) => pending,
+            None => return,
+        };
+        let elapsed = (Utc::now() - pending.started).to_std().unwrap_or_default();
+        // browsers record requests without a response with status 0
+        let response = json::json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": [],
+            "content": {"size": 0, "mimeType": ""},
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+            "_error": err.to_string(),
+        });
+        self.complete(pending, elapsed, response);
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn har_headers(headers: &HeaderMap) -> json::Value {
+    redacted_headers(headers)
+        .iter()
+        .map(|(name, value)| {
+            json::json!({
+                "name": name.as_str(),
+                "value": String::from_utf8_lossy(value.as_bytes()),
+            })
+        })
+        .collect()
+}
+
+fn query_pairs(uri: &Uri) -> Vec<(String, String)> {
+    let query = uri.query().unwrap_or("");
+    url::form_urlencoded::parse(query.as_bytes())
+        .map(|(name, value)| {
+            let value = if REDACTED_PARAMS.contains(&name.as_ref()) {
+                "<redacted>".to_string()
+            } else {
+                value.into_owned()
+            };
+            (name.into_owned(), value)
+        })
+        .collect()
+}
+
+fn query_string(uri: &Uri) -> json::Value {
+    query_pairs(uri)
+        .into_iter()
+        .map(|(name, value)| json::json!({"name": name, "value": value}))
+        .collect()
+}
+
+pub(crate) fn redacted_uri(uri: &Uri) -> String {
+    let mut url = uri.to_string();
+    if let Some(query) = uri.query() {
+        let redacted = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(query_pairs(uri))
+            .finish();
+        url.truncate(url.len() - query.len());
+        url.push_str(&redacted);
+    }
+    url
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::StatusCode;
+
+    fn request(uri: &str) -> Parts {
+        let (parts, _) = http::Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header("Authorization", "Bearer secret")
+            .header("Content-Type", "application/json")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts
+    }
+
+    #[test]
+    fn records_exchanges_with_redaction() {
+        let recorder = HarRecorder::new();
+        let parts = request("https://example.com/v1/items?key=abc&pageSize=10");
+        recorder.on_request(&parts, b"{\"name\":\"a\"}");
+        assert!(recorder.is_empty());
+
+        let (response, _) = http::Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "application/json")
+            .header("Content-Length", "42")
+            .body(())
+            .unwrap()
+            .into_parts();
+        recorder.on_response(
+            &parts.method,
+            &parts.uri,
+            &response,
+            Duration::from_millis(250),
+        );
+        assert_eq!(recorder.len(), 1);
+
+        let har = recorder.to_har();
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(entry["time"], 250.0);
+        assert_eq!(
+            entry["request"]["url"],
+            "https://example.com/v1/items?key=%3Credacted%3E&pageSize=10"
+        );
+        assert_eq!(
+            entry["request"]["queryString"],
+            json::json!([
+                {"name": "key", "value": "<redacted>"},
+                {"name": "pageSize", "value": "10"},
+            ])
+        );
+        assert_eq!(
+            entry["request"]["headers"][0],
+            json::json!({"name": "authorization", "value": "<redacted>"})
+        );
+        assert_eq!(entry["request"]["postData"]["text"], "{\"name\":\"a\"}");
+        assert_eq!(entry["response"]["status"], 200);
+        assert_eq!(entry["response"]["content"]["size"], 42);
+
+        let mut out = Vec::new();
+        recorder.write(&mut out).unwrap();
+        assert_eq!(json::from_slice::<json::Value>(&out).unwrap(), har);
+
+        assert!(!recorder.wants_response_bodies());
+        let recorder = HarRecorder::with_bodies();
+        assert!(recorder.clone().wants_response_bodies());
+        recorder.on_request(&parts, b"");
+        recorder.on_response(&parts.method, &parts.uri, &response, Duration::ZERO);
+        recorder.on_response_body(&parts.method, &parts.uri, b"{\"items\":[]}");
+        let content = &recorder.to_har()["log"]["entries"][0]["response"]["content"];
+        assert_eq!(content["text"], "{\"items\":[]}");
+        assert_eq!(content["size"], 12);
+    }
+
+    #[test]
+    fn responses_without_request_are_ignored() {
+        let recorder = HarRecorder::new();
+        let (response, _) = http::Response::new(()).into_parts();
+        recorder.on_response(
+            &Method::GET,
+            &Uri::from_static("https://example.com/"),
+            &response,
+            Duration::ZERO,
+        );
+        assert!(recorder.is_empty());
+        assert_eq!(recorder.to_har()["log"]["entries"], json::json!([]));
+    }
+}
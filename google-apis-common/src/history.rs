@@ -0,0 +1,124 @@
+//! Incremental synchronization of mailboxes using their history, like that of Gmail.
+//!
+//! Gmail numbers the changes of a mailbox with increasing, but not contiguous, history ids. A client
+//! keeping a copy of a mailbox stores the history id it is synchronized with as checkpoint, and
+//! lists the history records after it with `users.history.list`, which fails with `404 Not Found`
+//! once the checkpoint is too old, see [`is_expired()`]. The mailbox must then be synchronized
+//! fully. The generated `sync::HistorySynchronizer` does so, and turns the history records into
+//! typed changes.
+//!
+//! Instead of polling, clients can be notified of changes with `users.watch`, which publishes a
+//! [`Notification`] holding the current history id of the mailbox to a Pub/Sub topic whenever it
+//! changes.
+use serde::de::Error as _;
+use serde::Deserialize;
+use serde_json as json;
+
+use crate::Error;
+
+/// Returns true if `err` indicates that the history id a history was listed from is too old, or
+/// invalid, so the mailbox must be synchronized fully.
+pub fn is_expired(err: &Error) -> bool {
+    let code = match err {
+        Error::Api(err) => err.code,
+        Error::Failure(response) => response.status().as_u16(),
+        _ => return false,
+    };
+    code == 404
+}
+
+/// The notification of a change to a mailbox, published to the topic of a `users.watch` call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+    /// The address of the mailbox which changed.
+    pub email_address: String,
+    /// The history id of the mailbox after the change.
+    pub history_id: u64,
+}
+
+impl Notification {
+    /// Returns the notification held by the `data` of a Pub/Sub message, like one received by a
+    /// subscriber, which is `{"emailAddress": "user@example.com", "historyId": "9876543210"}`.
+    pub fn from_data(data: &[u8]) -> json::Result<Notification> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            email_address: String,
+            history_id: json::Value,
+        }
+
+        let data: Data = json::from_slice(data)?;
+        let history_id = match &data.history_id {
+            json::Value::String(id) => id.parse().ok(),
+            json::Value::Number(id) => id.as_u64(),
+            _ => None,
+        }
+        .ok_or_else(|| json::Error::custom(format!("invalid historyId {}", data.history_id)))?;
+        Ok(Notification {
+            email_address: data.email_address,
+            history_id,
+        })
+    }
+
+    /// Returns the notification delivered by the body of a request a push subscription made to its
+    /// endpoint, which holds the Pub/Sub message with the notification as base64-encoded `data`.
+    pub fn from_push(body: &[u8]) -> json::Result<Notification> {
+        #[derive(Deserialize)]
+        struct Push {
+            message: Message,
+        }
+
+        #[derive(Deserialize)]
+        struct Message {
+            data: String,
+        }
+
+        let push: Push = json::from_slice(body)?;
+        let data = base64::decode(push.message.data.trim()).map_err(json::Error::custom)?;
+        Notification::from_data(&data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ApiError;
+
+    #[test]
+    fn notifications_of_pull_and_push_subscriptions() {
+        let notification = Notification {
+            email_address: "user@example.com".into(),
+            history_id: 9876543210,
+        };
+        let data = br#"{"emailAddress": "user@example.com", "historyId": "9876543210"}"#;
+        assert_eq!(Notification::from_data(data).unwrap(), notification);
+        let data = br#"{"emailAddress": "user@example.com", "historyId": 9876543210}"#;
+        assert_eq!(Notification::from_data(data).unwrap(), notification);
+        assert!(Notification::from_data(br#"{"emailAddress": "a", "historyId": "x"}"#).is_err());
+
+        let push = json::json!({
+            "message": {
+                "data": base64::encode(data),
+                "messageId": "2070443601311540",
+                "publishTime": "2021-02-26T19:13:55.749Z",
+            },
+            "subscription": "projects/myproject/subscriptions/mysubscription",
+        });
+        let push = json::to_vec(&push).unwrap();
+        assert_eq!(Notification::from_push(&push).unwrap(), notification);
+        assert!(Notification::from_push(br#"{"message": {"data": "%%"}}"#).is_err());
+    }
+
+    #[test]
+    fn only_not_found_expires_the_history() {
+        let api_error = |code| {
+            Error::Api(ApiError {
+                code,
+                ..Default::default()
+            })
+        };
+        assert!(is_expired(&api_error(404)));
+        assert!(!is_expired(&api_error(403)));
+        assert!(!is_expired(&Error::Cancelled));
+    }
+}
@@ -0,0 +1,359 @@
+//! Hooks which are run on all requests made by the generated APIs.
+//!
+//! Some organizations route Google API traffic through internal gateways, which require requests
+//! to carry additional authentication, like an HMAC signature or Identity-Aware Proxy headers.
+//! A [`RequestSigner`] added to the [`InterceptorChain`] of a Hub is run on each request after the
+//! regular authorization was added, right before it is sent. Requests following a redirect are
+//! signed again, as their uri changed.
+//!
+//! To observe the HTTP exchange, e.g. for logging, metrics or capturing requests as test fixtures,
+//! add an [`HttpInterceptor`]. It sees each request right before it is sent, and the head of the
+//! response received for it. Use [`redacted_headers()`] to avoid logging credentials.
+//!
+//! # Example
+//! ```rust
+//! use google_apis_common::interceptor::{redacted_headers, HttpInterceptor, InterceptorChain};
+//!
+//! struct Logger;
+//!
+//! impl HttpInterceptor for Logger {
+//!     fn on_request(&self, parts: &http::request::Parts, _body: &[u8]) {
+//!         eprintln!("{} {} {:?}", parts.method, parts.uri, redacted_headers(&parts.headers));
+//!     }
+//! }
+//!
+//! let mut interceptors = InterceptorChain::default();
+//! interceptors.add_signer(|parts: &mut http::request::Parts, body: &[u8]| {
+//!     let signature = format!("{}:{}", parts.uri.path(), body.len());
+//!     parts.headers.insert("X-Gateway-Signature", signature.parse()?);
+//!     Ok(())
+//! });
+//! interceptors.add_interceptor(Logger);
+//! ```
+use std::error::Error as StdError;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::header::{
+    HeaderMap, HeaderValue, AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, SET_COOKIE,
+};
+use http::request::Parts;
+use http::{response, Method, Uri};
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Signs requests before they are sent, by adding or changing headers or the uri.
+///
+/// It is implemented for closures taking the request parts and the complete request body, which
+/// suffices for signatures that can be computed synchronously.
+pub trait RequestSigner: Send + Sync {
+    fn sign<'a>(
+        &'a self,
+        parts: &'a mut Parts,
+        body: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send + 'a>>;
+}
+
+impl<F> RequestSigner for F
+where
+    F: Fn(&mut Parts, &[u8]) -> Result<(), BoxError> + Send + Sync,
+{
+    fn sign<'a>(
+        &'a self,
+        parts: &'a mut Parts,
+        body: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send + 'a>> {
+        let result = self(parts, body);
+        Box::pin(async move { result })
+    }
+}
+
+/// Observes the requests sent by a Hub, and the responses received for them.
+///
+/// All methods do nothing by default.
+pub trait HttpInterceptor: Send + Sync {
+    /// Called right before a request is sent, after it was signed.
+    fn on_request(&self, parts: &Parts, body: &[u8]) {
+        let _ = (parts, body);
+    }
+
+    /// Called once the head of the response to the request with the given method and uri was
+    /// received, `elapsed` after the request was sent.
+    fn on_response(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        response: &response::Parts,
+        elapsed: Duration,
+    ) {
+        let _ = (method, uri, response, elapsed);
+    }
+
+    /// Returns true if [`Self::on_response_body()`] should be called, which requires buffering
+    /// the bodies of all responses instead of streaming them.
+    fn wants_response_bodies(&self) -> bool {
+        false
+    }
+
+    /// Called with the complete body of the response to the request with the given method and
+    /// uri after [`Self::on_response()`], if [`Self::wants_response_bodies()`].
+    fn on_response_body(&self, method: &Method, uri: &Uri, body: &[u8]) {
+        let _ = (method, uri, body);
+    }
+
+    /// Called if no response could be received for the request with the given method and uri.
+    fn on_error(&self, method: &Method, uri: &Uri, err: &hyper::Error) {
+        let _ = (method, uri, err);
+    }
+
+    /// Called when the Hub is shut down, to export anything the interceptor buffered, like
+    /// telemetry.
+    fn flush(&self) {}
+}
+
+/// A [`RequestSigner`] adding fixed headers to each request, like a request reason or a header
+/// selecting a staging backend.
+///
+/// Headers which the request already carries, like `x-goog-user-project`, are replaced. All values
+/// of a header given multiple times are sent.
+#[derive(Clone, Debug, Default)]
+pub struct StaticHeaders {
+    headers: HeaderMap,
+}
+
+impl StaticHeaders {
+    /// Send `headers` with each request.
+    pub fn new(headers: HeaderMap) -> StaticHeaders {
+        StaticHeaders { headers }
+    }
+
+    /// Adds the headers to `headers`, replacing those of the same name.
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        headers.extend(self.headers.clone());
+    }
+}
+
+impl RequestSigner for StaticHeaders {
+    fn sign<'a>(
+        &'a self,
+        parts: &'a mut Parts,
+        _body: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send + 'a>> {
+        self.apply(&mut parts.headers);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Returns a copy of `headers` with the values of those carrying credentials replaced, so they
+/// can be logged safely.
+pub fn redacted_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    for name in [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE, SET_COOKIE] {
+        if let http::header::Entry::Occupied(mut entry) = headers.entry(name) {
+            entry.insert(HeaderValue::from_static("<redacted>"));
+        }
+    }
+    headers
+}
+
+/// The hooks run on all requests made by a Hub, in the order they were added.
+#[derive(Clone, Default)]
+pub struct InterceptorChain {
+    signers: Vec<Arc<dyn RequestSigner>>,
+    interceptors: Vec<Arc<dyn HttpInterceptor>>,
+}
+
+impl InterceptorChain {
+    /// Add a signer to be run after all previously added ones.
+    pub fn add_signer(&mut self, signer: impl RequestSigner + 'static) -> &mut Self {
+        self.signers.push(Arc::new(signer));
+        self
+    }
+
+    /// Add an interceptor to be notified after all previously added ones.
+    pub fn add_interceptor(&mut self, interceptor: impl HttpInterceptor + 'static) -> &mut Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Returns true if there are no hooks to run.
+    pub fn is_empty(&self) -> bool {
+        self.signers.is_empty() && self.interceptors.is_empty()
+    }
+
+    /// Run all signers on the given request, stopping at the first one to fail.
+    pub async fn sign(&self, parts: &mut Parts, body: &[u8]) -> Result<(), BoxError> {
+        for signer in &self.signers {
+            signer.sign(parts, body).await?;
+        }
+        Ok(())
+    }
+
+    /// Notify all interceptors about a request which is about to be sent.
+    pub fn on_request(&self, parts: &Parts, body: &[u8]) {
+        for interceptor in &self.interceptors {
+            interceptor.on_request(parts, body);
+        }
+    }
+
+    /// Notify all interceptors about the response to a request.
+    pub fn on_response(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        response: &response::Parts,
+        elapsed: Duration,
+    ) {
+        for interceptor in &self.interceptors {
+            interceptor.on_response(method, uri, response, elapsed);
+        }
+    }
+
+    /// Returns true if any interceptor wants to see the bodies of responses.
+    pub fn wants_response_bodies(&self) -> bool {
+        self.interceptors
+            .iter()
+            .any(|interceptor| interceptor.wants_response_bodies())
+    }
+
+    /// Notify all interceptors which want to see it about the body of a response.
+    pub fn on_response_body(&self, method: &Method, uri: &Uri, body: &[u8]) {
+        for interceptor in &self.interceptors {
+            if interceptor.wants_response_bodies() {
+                interceptor.on_response_body(method, uri, body);
+            }
+        }
+    }
+
+    /// Notify all interceptors about a request which failed.
+    pub fn on_error(&self, method: &Method, uri: &Uri, err: &hyper::Error) {
+        for interceptor in &self.interceptors {
+            interceptor.on_error(method, uri, err);
+        }
+    }
+
+    /// Let all interceptors export what they buffered.
+    pub fn flush(&self) {
+        for interceptor in &self.interceptors {
+            interceptor.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn signers_run_in_order() {
+        let mut chain = InterceptorChain::default();
+        assert!(chain.is_empty());
+        chain
+            .add_signer(|parts: &mut Parts, body: &[u8]| {
+                parts
+                    .headers
+                    .insert("X-Signature", body.len().to_string().parse()?);
+                Ok(())
+            })
+            .add_signer(|parts: &mut Parts, _: &[u8]| {
+                let previous = parts.headers["X-Signature"].to_str()?.to_string();
+                parts
+                    .headers
+                    .insert("X-Signature", format!("{}-signed", previous).parse()?);
+                Ok(())
+            });
+
+        let (mut parts, _) = http::Request::new(()).into_parts();
+        chain.sign(&mut parts, b"body").await.unwrap();
+        assert_eq!(parts.headers["X-Signature"], "4-signed");
+    }
+
+    #[tokio::test]
+    async fn static_headers_replace_existing_ones() {
+        let mut headers = HeaderMap::new();
+        headers.append("x-goog-user-project", "other".parse().unwrap());
+        headers.append("x-goog-request-reason", "a".parse().unwrap());
+        headers.append("x-goog-request-reason", "b".parse().unwrap());
+        let mut chain = InterceptorChain::default();
+        chain.add_signer(StaticHeaders::new(headers));
+
+        let (mut parts, _) = http::Request::builder()
+            .header("x-goog-user-project", "mine")
+            .header("content-type", "application/json")
+            .body(())
+            .unwrap()
+            .into_parts();
+        chain.sign(&mut parts, b"").await.unwrap();
+        assert_eq!(parts.headers["x-goog-user-project"], "other");
+        assert_eq!(parts.headers["content-type"], "application/json");
+        let reasons: Vec<_> = parts
+            .headers
+            .get_all("x-goog-request-reason")
+            .iter()
+            .collect();
+        assert_eq!(reasons, ["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn failing_signer_stops_chain() {
+        let mut chain = InterceptorChain::default();
+        chain
+            .add_signer(|_: &mut Parts, _: &[u8]| Err("no key".into()))
+            .add_signer(|_: &mut Parts, _: &[u8]| -> Result<(), BoxError> {
+                panic!("must not be called")
+            });
+
+        let (mut parts, _) = http::Request::new(()).into_parts();
+        assert_eq!(
+            chain.sign(&mut parts, b"").await.unwrap_err().to_string(),
+            "no key"
+        );
+    }
+
+    #[test]
+    fn interceptors_see_requests() {
+        use std::sync::Mutex;
+
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        impl HttpInterceptor for Capture {
+            fn on_request(&self, parts: &Parts, body: &[u8]) {
+                self.0.lock().unwrap().push(format!(
+                    "{} {} {}",
+                    parts.method,
+                    parts.uri,
+                    body.len()
+                ));
+            }
+        }
+
+        let capture = Capture::default();
+        let mut chain = InterceptorChain::default();
+        chain.add_interceptor(capture.clone());
+        assert!(!chain.is_empty());
+
+        let (parts, _) = http::Request::post("https://example.com/a")
+            .body(())
+            .unwrap()
+            .into_parts();
+        chain.on_request(&parts, b"body");
+        assert_eq!(
+            *capture.0.lock().unwrap(),
+            vec!["POST https://example.com/a 4"]
+        );
+    }
+
+    #[test]
+    fn credentials_are_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, "Bearer secret".parse().unwrap());
+        headers.insert("X-Goog-Request-Reason", "test".parse().unwrap());
+
+        let redacted = redacted_headers(&headers);
+        assert_eq!(redacted[AUTHORIZATION], "<redacted>");
+        assert_eq!(redacted["X-Goog-Request-Reason"], "test");
+    }
+}
@@ -0,0 +1,218 @@
+//! The labels attached to many Google Cloud resources.
+//!
+//! Label keys and values are restricted to short strings of lowercase letters, digits, underscores
+//! and dashes, and the server rejects resources violating these constraints with a `400 Bad Request`.
+//! The `labels` fields of the generated schemas which their API documents to follow these rules use
+//! [`Labels`], which can check them beforehand, and calls validate the labels of their request
+//! values before sending them. Other `labels`, like those of log entries, are plain maps.
+use std::collections::HashMap;
+use std::error;
+use std::fmt::{self, Display};
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of labels of a resource.
+pub const MAX_LABELS: usize = 64;
+
+/// The maximum length of label keys and values, in characters.
+pub const MAX_LENGTH: usize = 63;
+
+/// Why labels are invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LabelError {
+    /// There are more than [`MAX_LABELS`] labels.
+    TooMany(usize),
+    /// The key is empty, longer than [`MAX_LENGTH`], doesn't start with a lowercase letter, or
+    /// contains characters other than lowercase letters, digits, underscores and dashes.
+    InvalidKey(String),
+    /// The value of the given key is longer than [`MAX_LENGTH`], or contains characters other than
+    /// lowercase letters, digits, underscores and dashes.
+    InvalidValue { key: String, value: String },
+}
+
+impl Display for LabelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LabelError::TooMany(count) => write!(
+                f,
+                "A resource can't have {} labels, the maximum is {}",
+                count, MAX_LABELS
+            ),
+            LabelError::InvalidKey(key) => write!(
+                f,
+                "'{}' is not a valid label key: it must start with a lowercase letter and consist of \
+                 at most {} lowercase letters, digits, underscores and dashes",
+                key, MAX_LENGTH
+            ),
+            LabelError::InvalidValue { key, value } => write!(
+                f,
+                "'{}' is not a valid value of label '{}': it must consist of at most {} lowercase \
+                 letters, digits, underscores and dashes",
+                value, key, MAX_LENGTH
+            ),
+        }
+    }
+}
+
+impl error::Error for LabelError {}
+
+fn is_label_char(c: char) -> bool {
+    c.is_lowercase() || c.is_numeric() || c == '_' || c == '-'
+}
+
+fn is_valid_value(value: &str) -> bool {
+    value.chars().count() <= MAX_LENGTH && value.chars().all(is_label_char)
+}
+
+fn is_valid_key(key: &str) -> bool {
+    matches!(key.chars().next(), Some(c) if c.is_lowercase()) && is_valid_value(key)
+}
+
+/// The labels of a resource, which serialize like a map of strings.
+///
+/// Any map can be converted into labels, which are validated using [`Labels::validate()`] before
+/// a request value carrying them is sent. [`Labels::insert()`] validates each label right away.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Labels(HashMap<String, String>);
+
+impl Labels {
+    /// Create labels from `labels`, failing if they are invalid.
+    pub fn new(labels: HashMap<String, String>) -> Result<Labels, LabelError> {
+        let labels = Labels(labels);
+        labels.validate()?;
+        Ok(labels)
+    }
+
+    /// Check that there are at most [`MAX_LABELS`] labels, and that all of their keys and values
+    /// are valid.
+    pub fn validate(&self) -> Result<(), LabelError> {
+        if self.0.len() > MAX_LABELS {
+            return Err(LabelError::TooMany(self.0.len()));
+        }
+        for (key, value) in &self.0 {
+            Self::validate_label(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Add a label, failing if it is invalid or would be one too many. Returns the previous value
+    /// of the label.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<Option<String>, LabelError> {
+        let (key, value) = (key.into(), value.into());
+        Self::validate_label(&key, &value)?;
+        if self.0.len() == MAX_LABELS && !self.0.contains_key(&key) {
+            return Err(LabelError::TooMany(MAX_LABELS + 1));
+        }
+        Ok(self.0.insert(key, value))
+    }
+
+    /// Remove a label, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
+    /// Returns the labels as a map.
+    pub fn into_inner(self) -> HashMap<String, String> {
+        self.0
+    }
+
+    fn validate_label(key: &str, value: &str) -> Result<(), LabelError> {
+        if !is_valid_key(key) {
+            return Err(LabelError::InvalidKey(key.to_string()));
+        }
+        if !is_valid_value(value) {
+            return Err(LabelError::InvalidValue {
+                key: key.to_string(),
+                value: value.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Deref for Labels {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &HashMap<String, String> {
+        &self.0
+    }
+}
+
+impl From<HashMap<String, String>> for Labels {
+    fn from(labels: HashMap<String, String>) -> Labels {
+        Labels(labels)
+    }
+}
+
+impl From<Labels> for HashMap<String, String> {
+    fn from(labels: Labels) -> HashMap<String, String> {
+        labels.0
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Labels {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Labels {
+        Labels(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid_labels() {
+        let labels: Labels = [("env", "prod"), ("team-a_1", ""), ("größe", "groß")]
+            .into_iter()
+            .collect();
+        assert_eq!(labels.validate(), Ok(()));
+        assert_eq!(labels.get("env").map(String::as_str), Some("prod"));
+        assert_eq!(
+            serde_json::to_string(&Labels::from_iter([("env", "prod")])).unwrap(),
+            r#"{"env":"prod"}"#
+        );
+    }
+
+    #[test]
+    fn invalid_labels() {
+        let long = "a".repeat(MAX_LENGTH + 1);
+        for key in ["", "Env", "1env", "env.name", long.as_str()] {
+            assert_eq!(
+                Labels::from_iter([(key, "prod")]).validate(),
+                Err(LabelError::InvalidKey(key.to_string()))
+            );
+        }
+        for value in ["Prod", "prod env", long.as_str()] {
+            assert_eq!(
+                Labels::from_iter([("env", value)]).validate(),
+                Err(LabelError::InvalidValue {
+                    key: "env".to_string(),
+                    value: value.to_string()
+                })
+            );
+        }
+        let many: HashMap<_, _> = (0..=MAX_LABELS)
+            .map(|i| (format!("key{}", i), String::new()))
+            .collect();
+        assert_eq!(Labels::new(many), Err(LabelError::TooMany(MAX_LABELS + 1)));
+    }
+
+    #[test]
+    fn insert_validates() {
+        let mut labels = Labels::default();
+        assert_eq!(labels.insert("env", "prod"), Ok(None));
+        assert_eq!(labels.insert("env", "dev"), Ok(Some("prod".to_string())));
+        assert!(labels.insert("Env", "dev").is_err());
+        assert_eq!(labels.len(), 1);
+    }
+}
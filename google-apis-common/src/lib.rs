@@ -1,39 +1,195 @@
+#[cfg(feature = "yup-oauth2")]
+pub mod adc;
+#[cfg(feature = "client")]
 pub mod auth;
+#[cfg(feature = "client")]
+pub mod auth_info;
+#[cfg(feature = "client")]
+pub mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod builder;
+#[cfg(feature = "client")]
+pub mod bulk;
+#[cfg(feature = "client")]
+pub mod changes;
+#[cfg(feature = "client")]
+pub mod clock;
+#[cfg(feature = "client")]
+pub mod connector;
+pub mod decode;
+pub mod emulator;
+#[cfg(feature = "client")]
+pub mod etag;
+#[cfg(feature = "client")]
+pub mod external_account;
 pub mod field_mask;
+#[cfg(feature = "yup-oauth2")]
+pub mod flows;
+#[cfg(feature = "gzip")]
+pub mod gzip;
+#[cfg(feature = "client")]
+pub mod har;
+#[cfg(feature = "client")]
+pub mod history;
+#[cfg(feature = "client")]
+pub mod interceptor;
+pub mod labels;
+#[cfg(feature = "client")]
+pub mod metadata;
+pub mod method;
+#[cfg(feature = "client")]
+pub mod operations;
+#[cfg(feature = "client")]
+pub mod page;
+#[cfg(feature = "proto-interop")]
+pub mod proto;
+pub mod quota;
+#[cfg(feature = "client")]
+pub mod redirect;
+#[cfg(feature = "replay")]
+pub mod replay;
+pub mod resource_name;
+#[cfg(feature = "client")]
+pub mod retry;
+#[cfg(feature = "client")]
+pub mod rt;
 pub mod serde;
+#[cfg(feature = "client")]
+pub mod shared;
+#[cfg(feature = "client")]
+pub mod shutdown;
+#[cfg(feature = "client")]
+pub mod signed_url;
+#[cfg(feature = "client")]
+pub mod single_flight;
+#[cfg(feature = "client")]
+pub mod status;
+#[cfg(feature = "client")]
+pub mod stream;
+#[cfg(feature = "client")]
+pub mod subscriber;
+pub mod sunset;
+pub mod table_rows;
+pub mod tasks;
+#[cfg(feature = "client")]
+pub mod timeout;
+#[cfg(feature = "client")]
+pub mod trace;
+#[cfg(feature = "client")]
+pub mod transport;
+#[cfg(feature = "client")]
+pub mod upload;
+#[cfg(feature = "client")]
 pub mod url;
+pub mod validate;
+pub mod versions;
 
+#[cfg(feature = "client")]
 use std::error;
+#[cfg(feature = "client")]
 use std::error::Error as StdError;
+#[cfg(feature = "client")]
 use std::fmt::{self, Display};
+#[cfg(feature = "client")]
 use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+#[cfg(feature = "client")]
 use std::str::FromStr;
+#[cfg(feature = "client")]
 use std::time::Duration;
 
+#[cfg(feature = "client")]
 use itertools::Itertools;
 
+#[cfg(feature = "client")]
 use hyper::http::Uri;
 
+#[cfg(feature = "client")]
 use hyper::header::{HeaderMap, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT};
+#[cfg(feature = "client")]
 use hyper::Method;
+#[cfg(feature = "client")]
 use hyper::StatusCode;
 
+#[cfg(feature = "client")]
 use mime::Mime;
 
 use serde_json as json;
 
+#[cfg(feature = "client")]
 use tokio::io::{AsyncRead, AsyncWrite};
-use tokio::time::sleep;
-
-pub use auth::{GetToken, NoToken};
+#[cfg(feature = "client")]
+use rt::sleep;
+
+#[cfg(feature = "client")]
+pub use auth::{ApiKey, GetToken, NoToken, ServiceAccountImpersonation};
+#[cfg(feature = "client")]
+pub use auth_info::{AuthInfo, GrantedScopes};
+#[cfg(feature = "client")]
+pub use batch::{BatchEntry, BatchRequest, BatchResponse};
+pub use builder::MissingFieldsError;
+#[cfg(feature = "client")]
+pub use bulk::{BulkError, ItemError};
 pub use chrono;
-pub use field_mask::FieldMask;
+#[cfg(feature = "client")]
+pub use clock::ServerClock;
+#[cfg(feature = "client")]
+pub use external_account::{ExternalAccount, ExternalAccountCredentials};
+pub use field_mask::{FieldMask, MapFields};
+#[cfg(feature = "client")]
+pub use har::HarRecorder;
+#[cfg(feature = "client")]
+pub use interceptor::{HttpInterceptor, InterceptorChain, RequestSigner, StaticHeaders};
+pub use labels::{LabelError, Labels};
+#[cfg(feature = "client")]
+pub use metadata::{new_request_id, ResponseMetadata, SentRequestId};
+pub use method::MethodDescriptor;
+#[cfg(feature = "client")]
+pub use operations::{OperationFilter, Operations};
+#[cfg(feature = "client")]
+pub use page::{Page, PageFuture, PagedCall};
+pub use quota::{QuotaBudget, QuotaCost};
+#[cfg(feature = "client")]
+pub use redirect::RedirectPolicy;
+#[cfg(feature = "replay")]
+pub use replay::Replay;
+pub use resource_name::{parse_resource_name, ResourceNameError};
+#[cfg(feature = "client")]
+pub use retry::RetryPolicy;
 pub use serde_with;
+#[cfg(feature = "client")]
+pub use shared::GoogleClient;
+#[cfg(feature = "client")]
+pub use shutdown::CallTracker;
+#[cfg(feature = "client")]
+pub use signed_url::{IamUrlSigner, SignedUrlRequest, UrlSigner};
+#[cfg(feature = "client")]
+pub use single_flight::SingleFlight;
+#[cfg(feature = "client")]
+pub use status::{ApiError, BackoffHint, ErrorExt, HttpStatus};
+#[cfg(feature = "client")]
+pub use stream::JsonItems;
+#[cfg(feature = "client")]
+pub use subscriber::{Subscriber, SubscriberSettings, Subscription};
+pub use sunset::Sunset;
+pub use table_rows::RowDecoder;
+#[cfg(feature = "client")]
+pub use timeout::Timeouts;
+#[cfg(feature = "client")]
+pub use trace::CallSpan;
+#[cfg(feature = "client")]
+pub use transport::{Transport, TransportOptions};
+#[cfg(feature = "client")]
+pub use upload::{ResumableSession, UploadOptions};
+pub use validate::Divergence;
+pub use versions::{UpgradePlan, VersionNumber};
 #[cfg(feature = "yup-oauth2")]
 pub use yup_oauth2 as oauth2;
 
+#[cfg(feature = "client")]
 const LINE_ENDING: &str = "\r\n";
 
+#[cfg(feature = "client")]
 pub enum Retry {
     /// Signal you don't want to retry
     Abort,
@@ -41,6 +197,7 @@ pub enum Retry {
     After(Duration),
 }
 
+#[cfg(feature = "client")]
 #[derive(PartialEq, Eq)]
 pub enum UploadProtocol {
     Simple,
@@ -81,7 +238,9 @@ pub trait Part {}
 pub trait NestedType {}
 
 /// A utility to specify reader types which provide seeking capabilities too
+#[cfg(feature = "client")]
 pub trait ReadSeek: Seek + Read + Send {}
+#[cfg(feature = "client")]
 impl<T: Seek + Read + Send> ReadSeek for T {}
 
 /// A trait for all types that can convert themselves into a *parts* string
@@ -94,6 +253,7 @@ pub trait ToParts {
 ///
 /// It contains methods to deal with all common issues, as well with the ones related to
 /// uploading media
+#[cfg(feature = "client")]
 pub trait Delegate: Send {
     /// Called at the beginning of any API request. The delegate should store the method
     /// information if he is interesting in knowing more context when further calls to it
@@ -113,6 +273,14 @@ pub trait Delegate: Send {
         Retry::Abort
     }
 
+    /// Called whenever a request didn't complete within the attempt timeout of the call.
+    ///
+    /// A retry is only made if it can start before the deadline of the call, see
+    /// [`Timeouts`] for details.
+    fn attempt_timed_out(&mut self) -> Retry {
+        Retry::Abort
+    }
+
     /// Called whenever there is the need for your applications API key after
     /// the official authenticator implementation didn't provide one, for some reason.
     /// If this method returns None as well, the underlying operation will fail
@@ -207,6 +375,57 @@ pub trait Delegate: Send {
         false
     }
 
+    /// Called after each chunk of a resumable upload was accepted by the server, with the amount of
+    /// bytes it persisted so far out of `total_length`.
+    /// When resuming an upload, it is first called with the amount of bytes persisted previously.
+    fn upload_progress(&mut self, persisted: u64, total_length: u64) {
+        let _ = (persisted, total_length);
+    }
+
+    /// Called if the page size of a call exceeds the maximum documented by the API, with the
+    /// requested size and the maximum which is requested instead.
+    fn page_size_clamped(&mut self, requested: i64, max: i64) {
+        let _ = (requested, max);
+    }
+
+    /// Called with the metadata of the response of a successful call, like the id of the request,
+    /// before its body is decoded and `finished(true)` is called.
+    fn response_metadata(&mut self, metadata: &ResponseMetadata) {
+        let _ = metadata;
+    }
+
+    /// Called before a call is made if none of the `scopes` it requests is one of the
+    /// `required_scopes` of its method, as listed by the discovery document of the API. The call is
+    /// made anyway, as the server may accept other scopes. The default implementation logs it as a
+    /// warning with the `tracing` feature, and ignores it otherwise.
+    fn unlisted_scopes(&mut self, scopes: &[&str], required_scopes: &[&str]) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            ?scopes,
+            ?required_scopes,
+            "none of the scopes requested for the call is listed for its method"
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = (scopes, required_scopes);
+    }
+
+    /// Called with the ways the response of a call differs from the schema called `schema`, with
+    /// the `validate-responses` feature of the generated crate, see [`validate`]. The default
+    /// implementation logs each of them as a warning with the `tracing` feature, and ignores them
+    /// otherwise.
+    fn response_divergences(&mut self, schema: &str, divergences: &[Divergence]) {
+        #[cfg(feature = "tracing")]
+        for divergence in divergences {
+            tracing::warn!(
+                schema,
+                "the response diverges from its schema: {}",
+                divergence
+            );
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = (schema, divergences);
+    }
+
     /// Called before the API request method returns, in every case. It can be used to clean up
     /// internal state between calls to the API.
     /// This call always has a matching call to `begin(...)`.
@@ -222,11 +441,14 @@ pub trait Delegate: Send {
 
 /// A delegate with a conservative default implementation, which is used if no other delegate is
 /// set.
+#[cfg(feature = "client")]
 #[derive(Default)]
 pub struct DefaultDelegate;
 
+#[cfg(feature = "client")]
 impl Delegate for DefaultDelegate {}
 
+#[cfg(feature = "client")]
 #[derive(Debug)]
 pub enum Error {
     /// The http connection failed
@@ -240,6 +462,10 @@ pub enum Error {
     /// Details are included.
     BadRequest(serde_json::Value),
 
+    /// The server returned an error in the standard format of Google APIs, which was parsed
+    /// into its code, status, message and details.
+    Api(ApiError),
+
     /// We needed an API key for authentication, but didn't obtain one.
     /// Neither through the authenticator, nor through the Delegate.
     MissingAPIKey,
@@ -247,12 +473,22 @@ pub enum Error {
     /// We required a Token, but didn't get one from the Authenticator
     MissingToken(Box<dyn StdError + Send + Sync>),
 
+    /// The token of the call wasn't granted any of the scopes authorizing the method, which are
+    /// stored in field `.0`, as verified by [`GrantedScopes`].
+    InsufficientScopes(Vec<String>),
+
     /// The delgate instructed to cancel the operation
     Cancelled,
 
     /// An additional, free form field clashed with one of the built-in optional ones
     FieldClash(&'static str),
 
+    /// A header added to the call has an invalid name or value
+    InvalidHeader(http::Error),
+
+    /// The labels of the resource sent by the call violate the rules of resource labels
+    InvalidLabels(LabelError),
+
     /// Shows that we failed to decode the server response.
     /// This can happen if the protocol changes in conjunction with strict json decoding.
     JsonDecodeError(String, json::Error),
@@ -262,8 +498,12 @@ pub enum Error {
 
     /// An IO error occurred while reading a stream into memory
     Io(std::io::Error),
+
+    /// A hook of the Hub's interceptor chain failed to process the request, e.g. a signer
+    Interceptor(Box<dyn StdError + Send + Sync>),
 }
 
+#[cfg(feature = "client")]
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -285,31 +525,44 @@ impl Display for Error {
                 )
             }
             Error::BadRequest(message) => writeln!(f, "Bad Request: {}", message),
+            Error::Api(err) => writeln!(f, "Server error {}", err),
             Error::MissingToken(e) => writeln!(f, "Token retrieval failed: {}", e),
+            Error::InsufficientScopes(scopes) => writeln!(
+                f,
+                "The requested scopes don't authorize the method, which requires one of: {}",
+                scopes.join(", ")
+            ),
             Error::Cancelled => writeln!(f, "Operation cancelled by delegate"),
             Error::FieldClash(field) => writeln!(
                 f,
                 "The custom parameter '{}' is already provided natively by the CallBuilder.",
                 field
             ),
+            Error::InvalidHeader(err) => writeln!(f, "Invalid header added to the call: {}", err),
+            Error::InvalidLabels(err) => writeln!(f, "Invalid labels: {}", err),
             Error::JsonDecodeError(json_str, err) => writeln!(f, "{}: {}", err, json_str),
             Error::Failure(response) => {
                 writeln!(f, "Http status indicates failure: {:?}", response)
             }
+            Error::Interceptor(err) => writeln!(f, "Request interceptor failed: {}", err),
         }
     }
 }
 
+#[cfg(feature = "client")]
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Error::HttpError(ref err) => err.source(),
             Error::JsonDecodeError(_, ref err) => err.source(),
+            Error::InvalidHeader(ref err) => Some(err),
+            Error::InvalidLabels(ref err) => Some(err),
             _ => None,
         }
     }
 }
 
+#[cfg(feature = "client")]
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error::Io(err)
@@ -317,20 +570,31 @@ impl From<std::io::Error> for Error {
 }
 
 /// A universal result type used as return for all calls.
+#[cfg(feature = "client")]
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The future returned by the methods of the hub traits, like `StorageApi`, which keeps them object-safe.
+#[cfg(feature = "client")]
+pub type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
+
 /// Contains information about an API request.
+#[cfg(feature = "client")]
 pub struct MethodInfo {
     pub id: &'static str,
     pub http_method: Method,
+    /// Whether the call may be retried without duplicating its side effects, which is derived from
+    /// its method unless the call overrides it.
+    pub idempotent: bool,
 }
 
+#[cfg(feature = "client")]
 const BOUNDARY: &str = "MDuXWGyeE33QFXGchb2VFWc4Z7945d";
 
 /// Provides a `Read` interface that converts multiple parts into the protocol
 /// identified by [RFC2387](https://tools.ietf.org/html/rfc2387).
 /// **Note**: This implementation is just as rich as it needs to be to perform uploads
 /// to google APIs, and might not be a fully-featured implementation.
+#[cfg(feature = "client")]
 #[derive(Default)]
 pub struct MultiPartReader<'a> {
     raw_parts: Vec<(HeaderMap, &'a mut (dyn Read + Send))>,
@@ -338,6 +602,7 @@ pub struct MultiPartReader<'a> {
     last_part_boundary: Option<Cursor<Vec<u8>>>,
 }
 
+#[cfg(feature = "client")]
 impl<'a> MultiPartReader<'a> {
     // TODO: This should be an associated constant
     /// Returns the mime-type representing our multi-part message.
@@ -391,6 +656,7 @@ impl<'a> MultiPartReader<'a> {
     }
 }
 
+#[cfg(feature = "client")]
 impl<'a> Read for MultiPartReader<'a> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         match (
@@ -479,32 +745,38 @@ impl<'a> Read for MultiPartReader<'a> {
 ///
 /// Generated via rustc --pretty expanded -Z unstable-options, and manually
 /// processed to be more readable.
+#[cfg(feature = "client")]
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct XUploadContentType(pub Mime);
 
+#[cfg(feature = "client")]
 impl ::std::ops::Deref for XUploadContentType {
     type Target = Mime;
     fn deref(&self) -> &Mime {
         &self.0
     }
 }
+#[cfg(feature = "client")]
 impl ::std::ops::DerefMut for XUploadContentType {
     fn deref_mut(&mut self) -> &mut Mime {
         &mut self.0
     }
 }
+#[cfg(feature = "client")]
 impl Display for XUploadContentType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&**self, f)
     }
 }
 
+#[cfg(feature = "client")]
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Chunk {
     pub first: u64,
     pub last: u64,
 }
 
+#[cfg(feature = "client")]
 impl fmt::Display for Chunk {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         (write!(fmt, "{}-{}", self.first, self.last)).ok();
@@ -512,6 +784,7 @@ impl fmt::Display for Chunk {
     }
 }
 
+#[cfg(feature = "client")]
 impl FromStr for Chunk {
     type Err = &'static str;
 
@@ -535,12 +808,14 @@ impl FromStr for Chunk {
 }
 
 /// Implements the Content-Range header, for serialization only
+#[cfg(feature = "client")]
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ContentRange {
     pub range: Option<Chunk>,
     pub total_length: u64,
 }
 
+#[cfg(feature = "client")]
 impl ContentRange {
     pub fn header_value(&self) -> String {
         format!(
@@ -554,27 +829,22 @@ impl ContentRange {
     }
 }
 
+#[cfg(feature = "client")]
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct RangeResponseHeader(pub Chunk);
 
-impl RangeResponseHeader {
-    fn from_bytes(raw: &[u8]) -> Self {
-        if !raw.is_empty() {
-            if let Ok(s) = std::str::from_utf8(raw) {
-                const PREFIX: &str = "bytes ";
-                if let Some(stripped) = s.strip_prefix(PREFIX) {
-                    if let Ok(c) = <Chunk as FromStr>::from_str(stripped) {
-                        return RangeResponseHeader(c);
-                    }
-                }
-            }
-        }
-
-        panic!("Unable to parse Range header {:?}", raw)
-    }
+/// Returns the amount of bytes persisted by the server according to the `Range` header of an
+/// incomplete resumable upload.
+#[cfg(feature = "client")]
+fn persisted_range(res: &hyper::Response<hyper::body::Body>) -> Option<u64> {
+    res.headers()
+        .get("Range")
+        .and_then(|h| h.to_str().ok())
+        .and_then(upload::persisted_offset)
 }
 
 /// A utility type to perform a resumable upload from start to end.
+#[cfg(feature = "client")]
 pub struct ResumableUploadHelper<'a, A: 'a, S>
 where
     S: tower_service::Service<Uri> + Clone + Send + Sync + 'static,
@@ -593,7 +863,9 @@ where
     pub reader: &'a mut dyn ReadSeek,
     pub media_type: Mime,
     pub content_length: u64,
+    pub upload_options: &'a UploadOptions,
 }
+#[cfg(feature = "client")]
 impl<'a, A, S> ResumableUploadHelper<'a, A, S>
 where
     S: tower_service::Service<Uri> + Clone + Send + Sync + 'static,
@@ -602,6 +874,7 @@ where
     S::Future: Send + Unpin + 'static,
     S::Error: Into<Box<dyn StdError + Send + Sync>>,
 {
+    /// Returns the amount of bytes persisted by the server.
     async fn query_transfer_status(
         &mut self,
     ) -> std::result::Result<u64, hyper::Result<hyper::Response<hyper::body::Body>>> {
@@ -628,21 +901,15 @@ where
                 .await
             {
                 Ok(r) => {
-                    // 308 = resume-incomplete == PermanentRedirect
-                    let headers = r.headers().clone();
-                    let h: RangeResponseHeader = match headers.get("Range") {
-                        Some(hh) if r.status() == StatusCode::PERMANENT_REDIRECT => {
-                            RangeResponseHeader::from_bytes(hh.as_bytes())
-                        }
-                        None | Some(_) => {
-                            if let Retry::After(d) = self.delegate.http_failure(&r, None) {
-                                sleep(d).await;
-                                continue;
-                            }
-                            return Err(Ok(r));
-                        }
-                    };
-                    return Ok(h.0.last);
+                    // 308 = resume-incomplete == PermanentRedirect, without a range if nothing was persisted
+                    if r.status() == StatusCode::PERMANENT_REDIRECT {
+                        return Ok(persisted_range(&r).unwrap_or(0));
+                    }
+                    if let Retry::After(d) = self.delegate.http_failure(&r, None) {
+                        sleep(d).await;
+                        continue;
+                    }
+                    return Err(Ok(r));
                 }
                 Err(err) => {
                     if let Retry::After(d) = self.delegate.http_error(&err) {
@@ -662,7 +929,10 @@ where
         let mut start = match self.start_at {
             Some(s) => s,
             None => match self.query_transfer_status().await {
-                Ok(s) => s,
+                Ok(s) => {
+                    self.delegate.upload_progress(s, self.content_length);
+                    s
+                }
                 Err(result) => return Some(result),
             },
         };
@@ -702,18 +972,28 @@ where
                         .method(hyper::Method::POST)
                         .header("Content-Range", range_header.header_value())
                         .header(CONTENT_TYPE, format!("{}", self.media_type))
+                        .header(CONTENT_LENGTH, request_size)
                         .header(USER_AGENT, self.user_agent.to_string())
-                        .body(hyper::body::Body::from(req_bytes))
+                        .body(
+                            self.upload_options
+                                .body(req_bytes, start, self.content_length),
+                        )
                         .unwrap(),
                 )
                 .await;
             match res {
                 Ok(res) => {
-                    start += request_size;
-
                     if res.status() == StatusCode::PERMANENT_REDIRECT {
+                        // continue after the data the server persisted, which may be less than was sent,
+                        // and is nothing if the response has no Range header
+                        start = persisted_range(&res).unwrap_or(0);
+                        self.delegate.upload_progress(start, self.content_length);
                         continue;
                     }
+                    if res.status().is_success() {
+                        self.delegate
+                            .upload_progress(self.content_length, self.content_length);
+                    }
 
                     let (res_parts, res_body) = res.into_parts();
                     let res_body = match hyper::body::to_bytes(res_body).await {
@@ -763,13 +1043,14 @@ pub fn remove_json_null_values(value: &mut json::value::Value) {
 }
 
 // Borrowing the body object as mutable and converts it to a string
+#[cfg(feature = "client")]
 pub async fn get_body_as_string(res_body: &mut hyper::Body) -> String {
     let res_body_buf = hyper::body::to_bytes(res_body).await.unwrap();
     let res_body_string = String::from_utf8_lossy(&res_body_buf);
     res_body_string.to_string()
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "client"))]
 mod test_api {
     use super::*;
     use std::default::Default;
@@ -848,4 +1129,65 @@ mod test_api {
             mime.get_param("boundary").map(|x| x.as_str())
         );
     }
+
+    #[tokio::test]
+    async fn chunks_are_sent_again_if_nothing_was_persisted() {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/upload", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            let mut ranges = Vec::new();
+            loop {
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    stream.read_line(&mut line).await.unwrap();
+                    let line = line.trim_end().to_ascii_lowercase();
+                    if line.is_empty() {
+                        break;
+                    } else if let Some(value) = line.strip_prefix("content-length: ") {
+                        length = value.parse().unwrap();
+                    } else if let Some(value) = line.strip_prefix("content-range: ") {
+                        ranges.push(value.to_string());
+                    }
+                }
+                let mut body = vec![0; length];
+                stream.read_exact(&mut body).await.unwrap();
+                // the first chunk isn't persisted, which is answered without a Range header
+                let res = if ranges.len() == 1 {
+                    "HTTP/1.1 308 Resume Incomplete\r\ncontent-length: 0\r\n\r\n"
+                } else {
+                    "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n{}"
+                };
+                stream.get_mut().write_all(res.as_bytes()).await.unwrap();
+                if ranges.len() == 2 {
+                    return ranges;
+                }
+            }
+        });
+
+        let client = hyper::Client::new();
+        let mut delegate = DefaultDelegate;
+        let mut reader = Cursor::new(vec![1; 10]);
+        let options = UploadOptions::default();
+        let mut helper = ResumableUploadHelper {
+            client: &client,
+            delegate: &mut delegate,
+            start_at: Some(0),
+            auth: &(),
+            user_agent: "agent",
+            auth_header: String::new(),
+            url: &url,
+            reader: &mut reader,
+            media_type: mime::APPLICATION_OCTET_STREAM,
+            content_length: 10,
+            upload_options: &options,
+        };
+        let res = helper.upload().await.unwrap().unwrap();
+        assert!(res.status().is_success());
+        assert_eq!(server.await.unwrap(), ["bytes 0-9/10", "bytes 0-9/10"]);
+    }
 }
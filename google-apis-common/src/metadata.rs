@@ -0,0 +1,199 @@
+//! The metadata of responses, like the id the server assigned to a request.
+//!
+//! The generated `doit_with_metadata()` methods return a [`ResponseMetadata`] instead of the
+//! response alongside the decoded result, and each delegate receives one with
+//! [`Delegate::response_metadata()`](crate::Delegate::response_metadata) when a call succeeds.
+//!
+//! Calls of methods taking a `requestId` parameter, by which the server de-duplicates retried
+//! mutations, can have a request id generated for them, which the metadata of their response
+//! holds as [`ResponseMetadata::sent_request_id`].
+use std::fmt::Write;
+use std::time::Duration;
+
+use hyper::header::HeaderMap;
+use hyper::StatusCode;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// The headers which carry the id of a request, in the order of preference.
+pub const REQUEST_ID_HEADERS: [&str; 3] =
+    ["x-goog-request-id", "x-request-id", "x-guploader-uploadid"];
+
+/// An entry of the `Server-Timing` header, e.g. `gfet4t7; dur=43`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerTiming {
+    /// The name of the metric.
+    pub name: String,
+    /// The duration the server spent on it, if given.
+    pub duration: Option<Duration>,
+    /// Its description, if given.
+    pub description: Option<String>,
+}
+
+/// The metadata of a response: its status, headers, and the information extracted from them.
+#[derive(Clone, Debug)]
+pub struct ResponseMetadata {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// All headers of the response.
+    pub headers: HeaderMap,
+    /// The id of the request, taken from the first of the [`REQUEST_ID_HEADERS`] present. It
+    /// identifies the request when contacting support.
+    pub request_id: Option<String>,
+    /// The entries of the `Server-Timing` headers.
+    pub server_timing: Vec<ServerTiming>,
+    /// The request id the call sent as its `requestId` parameter, if any, which is the same for
+    /// all of its attempts.
+    pub sent_request_id: Option<String>,
+}
+
+/// The request id a call sent as its `requestId` parameter, which is added to the extensions of
+/// its response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SentRequestId(pub String);
+
+impl ResponseMetadata {
+    /// Extract the metadata of `response`, leaving its body untouched.
+    pub fn new<B>(response: &hyper::Response<B>) -> ResponseMetadata {
+        let mut metadata = ResponseMetadata::from_parts(response.status(), response.headers());
+        metadata.sent_request_id = response
+            .extensions()
+            .get::<SentRequestId>()
+            .map(|id| id.0.clone());
+        metadata
+    }
+
+    /// Extract the metadata of a response with the given status and headers.
+    pub fn from_parts(status: StatusCode, headers: &HeaderMap) -> ResponseMetadata {
+        let request_id = REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| headers.get(*name)?.to_str().ok())
+            .map(str::to_owned);
+        let server_timing = headers
+            .get_all("server-timing")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(parse_server_timing)
+            .collect();
+        ResponseMetadata {
+            status,
+            headers: headers.clone(),
+            request_id,
+            server_timing,
+            sent_request_id: None,
+        }
+    }
+}
+
+/// Returns a new random request id, a version 4 UUID like `1b4e28ba-2fa1-41d2-883f-0016d3cca427`,
+/// as the `requestId` parameters of APIs expect.
+pub fn new_request_id() -> String {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("the system to provide random bytes");
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let mut id = String::with_capacity(36);
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            id.push('-');
+        }
+        write!(id, "{:02x}", byte).expect("writing to a string to succeed");
+    }
+    id
+}
+
+/// Parse a single entry of a `Server-Timing` header, like `db;dur=53.2;desc="Query"`.
+fn parse_server_timing(entry: &str) -> Option<ServerTiming> {
+    let mut params = entry.split(';').map(str::trim);
+    let name = params.next().filter(|name| !name.is_empty())?;
+    let mut timing = ServerTiming {
+        name: name.to_owned(),
+        duration: None,
+        description: None,
+    };
+    for param in params {
+        let (key, value) = match param.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim().trim_matches('"')),
+            None => continue,
+        };
+        if key.eq_ignore_ascii_case("dur") {
+            timing.duration = value
+                .parse::<f64>()
+                .ok()
+                .filter(|millis| millis.is_finite() && *millis >= 0.0)
+                .map(|millis| Duration::from_secs_f64(millis / 1000.0));
+        } else if key.eq_ignore_ascii_case("desc") {
+            timing.description = Some(value.to_owned());
+        }
+    }
+    Some(timing)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn metadata_of_responses() {
+        let response = hyper::Response::builder()
+            .status(200)
+            .header("x-guploader-uploadid", "upload-1")
+            .header("x-goog-request-id", "request-1")
+            .header("server-timing", "gfet4t7; dur=43")
+            .header("server-timing", r#"db;dur=1.5;desc="Query", cache"#)
+            .body(())
+            .unwrap();
+        let metadata = ResponseMetadata::new(&response);
+        assert_eq!(metadata.status, StatusCode::OK);
+        assert_eq!(metadata.request_id.as_deref(), Some("request-1"));
+        assert_eq!(metadata.headers.len(), 4);
+        assert_eq!(
+            metadata.server_timing,
+            vec![
+                ServerTiming {
+                    name: "gfet4t7".into(),
+                    duration: Some(Duration::from_millis(43)),
+                    description: None,
+                },
+                ServerTiming {
+                    name: "db".into(),
+                    duration: Some(Duration::from_micros(1500)),
+                    description: Some("Query".into()),
+                },
+                ServerTiming {
+                    name: "cache".into(),
+                    duration: None,
+                    description: None,
+                },
+            ]
+        );
+
+        assert_eq!(metadata.sent_request_id, None);
+
+        let metadata = ResponseMetadata::from_parts(StatusCode::CREATED, &HeaderMap::new());
+        assert_eq!(metadata.request_id, None);
+        assert!(metadata.server_timing.is_empty());
+
+        let mut response = hyper::Response::new(());
+        response
+            .extensions_mut()
+            .insert(SentRequestId("id-1".to_string()));
+        assert_eq!(
+            ResponseMetadata::new(&response).sent_request_id.as_deref(),
+            Some("id-1")
+        );
+    }
+
+    #[test]
+    fn request_ids_are_uuids() {
+        let id = new_request_id();
+        assert_eq!(id.len(), 36);
+        let groups: Vec<_> = id.split('-').map(str::len).collect();
+        assert_eq!(groups, [8, 4, 4, 4, 12]);
+        assert_eq!(&id[14..15], "4");
+        assert!(matches!(&id[19..20], "8" | "9" | "a" | "b"));
+        assert_ne!(new_request_id(), id);
+    }
+}
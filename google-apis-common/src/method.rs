@@ -0,0 +1,69 @@
+//! Descriptions of the methods of an API, for tooling which handles them generically.
+//!
+//! Generated crates list all their methods in `methods::ALL`, sorted by their id, like
+//! `datafusion.projects.locations.instances.create`, which is what monitoring and quota
+//! dashboards key off. Call builders return their id with `method_id()` as well.
+
+/// A method of an API, as described by its discovery document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MethodDescriptor {
+    /// The id of the method, like `drive.files.list`.
+    pub id: &'static str,
+    /// The HTTP method of its requests, like `GET`.
+    pub http_method: &'static str,
+    /// The template of the path of its requests relative to the base url of the API, like
+    /// `files/{fileId}`, or `v1/{+name}` for parameters which may contain slashes.
+    pub path: &'static str,
+    /// The scopes of which any authorizes calls of the method.
+    pub scopes: &'static [&'static str],
+}
+
+impl MethodDescriptor {
+    /// Returns the names of the parameters in the path template, in order.
+    pub fn path_params(&self) -> impl Iterator<Item = &'static str> {
+        self.path.split('{').skip(1).filter_map(|part| {
+            let name = part.split('}').next()?;
+            Some(name.trim_start_matches(['+', '/']).trim_end_matches('*'))
+        })
+    }
+
+    /// Returns the descriptor of the method with the given id in `methods`, which are sorted by
+    /// their id like the `methods::ALL` of generated crates.
+    pub fn find(methods: &'static [MethodDescriptor], id: &str) -> Option<&'static Self> {
+        methods
+            .binary_search_by(|method| method.id.cmp(id))
+            .ok()
+            .map(|index| &methods[index])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static METHODS: &[MethodDescriptor] = &[
+        MethodDescriptor {
+            id: "drive.files.get",
+            http_method: "GET",
+            path: "files/{fileId}",
+            scopes: &["https://www.googleapis.com/auth/drive"],
+        },
+        MethodDescriptor {
+            id: "storage.objects.get",
+            http_method: "GET",
+            path: "b/{bucket}/o/{+object}",
+            scopes: &[],
+        },
+    ];
+
+    #[test]
+    fn methods_are_found_by_id() {
+        let method = MethodDescriptor::find(METHODS, "storage.objects.get").unwrap();
+        assert_eq!(
+            method.path_params().collect::<Vec<_>>(),
+            ["bucket", "object"]
+        );
+        assert_eq!(METHODS[0].path_params().collect::<Vec<_>>(), ["fileId"]);
+        assert!(MethodDescriptor::find(METHODS, "drive.files.list").is_none());
+    }
+}
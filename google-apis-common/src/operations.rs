@@ -0,0 +1,336 @@
+//! Listing and cancelling long-running operations.
+//!
+//! APIs with long-running operations, like Data Fusion or Translation, have an `operations.list`
+//! method returning them a page at a time. The generated `operations_stream()` methods, like
+//! `locations_operations_stream()`, return [`Operations`] instead, a stream of all operations
+//! matching an [`OperationFilter`] which lists further pages as needed. The
+//! `operations_cancel_all()` methods cancel all of them which aren't done yet, e.g. to clean up
+//! operations which are stuck.
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use serde::Serialize;
+use serde_json as json;
+
+use crate::Result;
+
+/// Selects operations by their state and the contents of their metadata. An empty filter selects
+/// all operations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperationFilter {
+    done: Option<bool>,
+    target: Option<String>,
+    metadata_type: Option<String>,
+}
+
+impl OperationFilter {
+    /// A filter selecting all operations.
+    pub fn new() -> OperationFilter {
+        OperationFilter::default()
+    }
+
+    /// Select only operations which are done, or only those which are still running.
+    pub fn done(mut self, done: bool) -> OperationFilter {
+        self.done = Some(done);
+        self
+    }
+
+    /// Select only operations on the given resource or the resources within it, according to the
+    /// `target` field of their metadata, like `projects/p/locations/l/instances/i`.
+    pub fn target(mut self, target: impl Into<String>) -> OperationFilter {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Select only operations whose metadata has the given type, which is either its fully
+    /// qualified name like `google.cloud.datafusion.v1.OperationMetadata`, or its last component.
+    pub fn metadata_type(mut self, metadata_type: impl Into<String>) -> OperationFilter {
+        self.metadata_type = Some(metadata_type.into());
+        self
+    }
+
+    /// Returns true if the given operation, like the `Operation` schema of an API, is selected.
+    pub fn matches<T: Serialize>(&self, operation: &T) -> bool {
+        match json::to_value(operation) {
+            Ok(value) => self.matches_value(&value),
+            Err(_) => false,
+        }
+    }
+
+    fn matches_value(&self, operation: &json::Value) -> bool {
+        let metadata_field = |field: &str| operation["metadata"][field].as_str();
+        if let Some(done) = self.done {
+            if operation["done"].as_bool().unwrap_or(false) != done {
+                return false;
+            }
+        }
+        if let Some(target) = &self.target {
+            // a prefix only selects the resources within the target, not those sharing a prefix
+            let within =
+                |t: &str| t.starts_with(target.as_str()) && t[target.len()..].starts_with('/');
+            if !matches!(metadata_field("target"), Some(t) if t == target || within(t)) {
+                return false;
+            }
+        }
+        if let Some(metadata_type) = &self.metadata_type {
+            let type_name = match metadata_field("@type") {
+                Some(url) => url.rsplit('/').next().unwrap_or(url),
+                None => return false,
+            };
+            if type_name != metadata_type && !type_name.ends_with(&format!(".{}", metadata_type)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A page of operations along with the token of the next one, as returned by `operations.list`.
+pub type OperationsPage<T> = (Vec<T>, Option<String>);
+
+type PageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<OperationsPage<T>>> + Send + 'a>>;
+
+/// A stream of the operations selected by an [`OperationFilter`], which lists their pages one at a
+/// time as the operations are taken from it.
+///
+/// The stream ends after the last page, or after yielding the error a page failed with.
+pub struct Operations<'a, T> {
+    filter: OperationFilter,
+    list: Box<dyn FnMut(Option<String>) -> PageFuture<'a, T> + Send + 'a>,
+    page: Option<PageFuture<'a, T>>,
+    // the token of the page to list next, or None after the last one
+    next_page: Option<Option<String>>,
+    operations: VecDeque<T>,
+}
+
+impl<T> Unpin for Operations<'_, T> {}
+
+impl<'a, T: Serialize> Operations<'a, T> {
+    /// Stream the operations selected by `filter` from the pages returned by `list`, which is
+    /// called with the token of each page, starting with `None` for the first one.
+    pub fn new<F, Fut>(filter: OperationFilter, mut list: F) -> Operations<'a, T>
+    where
+        F: FnMut(Option<String>) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<OperationsPage<T>>> + Send + 'a,
+    {
+        Operations {
+            filter,
+            list: Box::new(move |page_token| Box::pin(list(page_token))),
+            page: None,
+            next_page: Some(None),
+            operations: VecDeque::new(),
+        }
+    }
+
+    /// Returns the next selected operation, or `None` if there are no more.
+    pub async fn next_operation(&mut self) -> Option<Result<T>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Call `cancel` with the name of each selected operation which isn't done yet, returning the
+    /// number of cancelled operations. Stops at the first error.
+    pub async fn cancel_all<C, CF>(mut self, mut cancel: C) -> Result<usize>
+    where
+        C: FnMut(String) -> CF,
+        CF: Future<Output = Result<()>>,
+    {
+        let mut cancelled = 0;
+        while let Some(operation) = self.next_operation().await {
+            let operation = json::to_value(operation?).unwrap_or_default();
+            if operation["done"].as_bool().unwrap_or(false) {
+                continue;
+            }
+            if let Some(name) = operation["name"].as_str() {
+                cancel(name.to_owned()).await?;
+                cancelled += 1;
+            }
+        }
+        Ok(cancelled)
+    }
+}
+
+impl<'a, T: Serialize> Stream for Operations<'a, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(operation) = this.operations.pop_front() {
+                return Poll::Ready(Some(Ok(operation)));
+            }
+            let page = match &mut this.page {
+                Some(page) => page,
+                None => match this.next_page.take() {
+                    Some(page_token) => this.page.insert((this.list)(page_token)),
+                    None => return Poll::Ready(None),
+                },
+            };
+            let (operations, next_page_token) = match page.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(res) => {
+                    this.page = None;
+                    match res {
+                        Ok(page) => page,
+                        Err(err) => return Poll::Ready(Some(Err(err))),
+                    }
+                }
+            };
+            this.next_page = next_page_token.filter(|token| !token.is_empty()).map(Some);
+            let filter = &this.filter;
+            this.operations.extend(
+                operations
+                    .into_iter()
+                    .filter(|operation| filter.matches(operation)),
+            );
+        }
+    }
+}
+
+impl<T> fmt::Debug for Operations<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Operations")
+            .field("filter", &self.filter)
+            .field("next_page", &self.next_page)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ApiError, Error};
+    use serde_json::json;
+
+    fn operation(name: &str, done: bool, target: &str) -> json::Value {
+        json!({
+            "name": name,
+            "done": done,
+            "metadata": {
+                "@type": "type.googleapis.com/google.cloud.datafusion.v1.OperationMetadata",
+                "target": target,
+            },
+        })
+    }
+
+    fn pages() -> Vec<OperationsPage<json::Value>> {
+        vec![
+            (
+                vec![
+                    operation("operations/1", true, "projects/p/instances/a"),
+                    operation("operations/2", false, "projects/p/instances/a"),
+                ],
+                Some("2".into()),
+            ),
+            (vec![], Some("3".into())),
+            (
+                vec![
+                    operation("operations/3", false, "projects/p/instances/ab"),
+                    operation("operations/4", false, "projects/p/instances/a/backups/b"),
+                ],
+                Some("".into()),
+            ),
+        ]
+    }
+
+    fn list(
+        pages: Vec<OperationsPage<json::Value>>,
+    ) -> impl FnMut(Option<String>) -> std::future::Ready<Result<OperationsPage<json::Value>>> {
+        move |page_token| {
+            let index = page_token.map_or(0, |token| token.parse::<usize>().unwrap() - 1);
+            std::future::ready(Ok(pages[index].clone()))
+        }
+    }
+
+    async fn names(mut operations: Operations<'_, json::Value>) -> Vec<String> {
+        let mut names = Vec::new();
+        while let Some(operation) = operations.next_operation().await {
+            names.push(operation.unwrap()["name"].as_str().unwrap().to_owned());
+        }
+        names
+    }
+
+    #[test]
+    fn filters() {
+        let op = operation("operations/1", false, "projects/p/instances/a");
+        assert!(OperationFilter::new().matches(&op));
+        assert!(OperationFilter::new().done(false).matches(&op));
+        assert!(!OperationFilter::new().done(true).matches(&op));
+        assert!(OperationFilter::new().target("projects/p").matches(&op));
+        assert!(!OperationFilter::new()
+            .target("projects/p/instances/")
+            .matches(&op));
+        assert!(!OperationFilter::new()
+            .target("projects/p/inst")
+            .matches(&op));
+        assert!(OperationFilter::new()
+            .metadata_type("OperationMetadata")
+            .matches(&op));
+        assert!(OperationFilter::new()
+            .metadata_type("google.cloud.datafusion.v1.OperationMetadata")
+            .matches(&op));
+        assert!(!OperationFilter::new()
+            .metadata_type("Metadata")
+            .matches(&op));
+        assert!(!OperationFilter::new()
+            .metadata_type("OperationMetadata")
+            .matches(&json!({"name": "operations/1"})));
+    }
+
+    #[tokio::test]
+    async fn operations_are_streamed_from_all_pages() {
+        let all = Operations::new(OperationFilter::new(), list(pages()));
+        assert_eq!(
+            names(all).await,
+            [
+                "operations/1",
+                "operations/2",
+                "operations/3",
+                "operations/4"
+            ]
+        );
+        let filter = OperationFilter::new()
+            .done(false)
+            .target("projects/p/instances/a");
+        let running = Operations::new(filter, list(pages()));
+        assert_eq!(names(running).await, ["operations/2", "operations/4"]);
+    }
+
+    #[tokio::test]
+    async fn failed_pages_end_the_stream() {
+        let mut operations = Operations::new(OperationFilter::new(), |page_token| async move {
+            match page_token {
+                None => Ok((vec![json!({"name": "operations/1"})], Some("2".into()))),
+                Some(_) => Err(Error::Api(ApiError {
+                    code: 503,
+                    ..Default::default()
+                })),
+            }
+        });
+        assert!(operations.next_operation().await.unwrap().is_ok());
+        assert!(matches!(
+            operations.next_operation().await,
+            Some(Err(Error::Api(_)))
+        ));
+        assert!(operations.next_operation().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn running_operations_are_cancelled() {
+        let mut cancelled = Vec::new();
+        let operations =
+            Operations::new(OperationFilter::new().target("projects/p"), list(pages()));
+        let count = operations
+            .cancel_all(|name| {
+                cancelled.push(name);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(cancelled, ["operations/2", "operations/3", "operations/4"]);
+    }
+}
@@ -0,0 +1,133 @@
+//! Typed pages of list calls, for paging through the results of a call manually.
+//!
+//! The generated `doit_paged()` methods of calls whose responses are pages return a [`Page`], which
+//! holds the items of the response and the call fetching the next page with the same parameters,
+//! so callers don't need to pass page tokens on themselves.
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::{ResponseMetadata, Result};
+
+/// The future of a page returned by [`PagedCall::fetch_page()`].
+pub type PageFuture<'a, T, C> = Pin<Box<dyn Future<Output = Result<Page<T, C>>> + Send + 'a>>;
+
+/// A call builder whose responses are pages of items, implemented by the call builders having a
+/// `doit_paged()` method.
+pub trait PagedCall<'a>: Sized {
+    /// The type of the items of each page.
+    type Item;
+
+    /// Perform the call, returning its page.
+    fn fetch_page(self) -> PageFuture<'a, Self::Item, Self>;
+}
+
+/// The items of a page, along with the call fetching the next page, if there is one.
+pub struct Page<T, C> {
+    /// The items of this page, which may be empty even if there is a next page.
+    pub items: Vec<T>,
+    /// The token of the next page, or `None` if this is the last page.
+    pub next_token: Option<String>,
+    /// The metadata of the response of this page.
+    pub metadata: ResponseMetadata,
+    next: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    /// A page of `items`, followed by the page fetched by `next` if there is one.
+    pub fn new(
+        items: Vec<T>,
+        next_token: Option<String>,
+        metadata: ResponseMetadata,
+        next: Option<C>,
+    ) -> Page<T, C> {
+        Page {
+            items,
+            next_token,
+            metadata,
+            next,
+        }
+    }
+
+    /// Returns true if there is a next page.
+    pub fn has_next(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Returns the call fetching the next page, e.g. to change its parameters, or `None` if this
+    /// is the last page.
+    pub fn into_next_call(self) -> Option<C> {
+        self.next
+    }
+
+    /// Fetch the next page, or return `None` if this is the last page.
+    pub async fn fetch_next<'a>(self) -> Result<Option<Page<T, C>>>
+    where
+        C: PagedCall<'a, Item = T>,
+    {
+        match self.next {
+            Some(call) => call.fetch_page().await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T, C> IntoIterator for Page<T, C> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // pages of two numbers each, up to `end`
+    struct Numbers {
+        start: u32,
+        end: u32,
+    }
+
+    impl<'a> PagedCall<'a> for Numbers {
+        type Item = u32;
+
+        fn fetch_page(self) -> PageFuture<'a, u32, Numbers> {
+            Box::pin(async move {
+                let end = (self.start + 2).min(self.end);
+                let response = hyper::Response::new(hyper::body::Body::empty());
+                let next = (end < self.end).then_some(Numbers {
+                    start: end,
+                    end: self.end,
+                });
+                Ok(Page::new(
+                    (self.start..end).collect(),
+                    next.as_ref().map(|next| next.start.to_string()),
+                    ResponseMetadata::new(&response),
+                    next,
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn pages_are_fetched_until_the_last() {
+        let first = Numbers { start: 0, end: 5 }.fetch_page().await.unwrap();
+        assert!(first.has_next());
+        let mut items = Vec::new();
+        let mut tokens = Vec::new();
+        let mut page = Some(first);
+        while let Some(current) = page {
+            items.extend(current.items.iter().copied());
+            tokens.push(current.next_token.clone());
+            page = current.fetch_next().await.unwrap();
+        }
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+        assert_eq!(tokens, [Some("2".into()), Some("4".into()), None]);
+
+        let last = Numbers { start: 4, end: 5 }.fetch_page().await.unwrap();
+        assert!(!last.has_next());
+        assert_eq!(last.into_iter().collect::<Vec<_>>(), [4]);
+    }
+}
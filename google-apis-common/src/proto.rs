@@ -0,0 +1,305 @@
+//! Conversions between the schemas of generated crates and the types generated by `prost` from
+//! the protos of the same APIs.
+//!
+//! Teams calling an API through both its REST crate and a gRPC client like `tonic` need to pass
+//! messages between them. With the `proto-interop` feature, generated crates configured with the
+//! `prost` crate of their API implement [`Proto`] and `From` in both directions for their schemas,
+//! converting each field using the implementations of this module:
+//!
+//! * scalars, strings and bytes are moved, and `None` becomes the default value of the proto
+//!   field, which in turn becomes `None`, as proto3 doesn't distinguish them
+//! * messages, repeated fields and maps are converted element by element
+//! * times, durations, field masks and JSON values become their well-known types, like
+//!   [`prost_types::Timestamp`], and back
+//!
+//! Enum fields are converted by name if the prost enum to use is configured, and left unset
+//! otherwise, like fields the protos don't have.
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json as json;
+
+use crate::{FieldMask, Labels};
+
+pub use prost_types;
+
+/// A type which converts from and into the type `P` generated by `prost`.
+pub trait Proto<P>: Sized {
+    /// Convert into the prost type.
+    fn into_proto(self) -> P;
+
+    /// Convert from the prost type.
+    fn from_proto(proto: P) -> Self;
+}
+
+macro_rules! scalar_protos {
+    ($($t:ty),*) => {
+        $(
+            impl Proto<$t> for $t {
+                fn into_proto(self) -> $t {
+                    self
+                }
+
+                fn from_proto(proto: $t) -> $t {
+                    proto
+                }
+            }
+
+            impl Proto<$t> for Option<$t> {
+                fn into_proto(self) -> $t {
+                    self.unwrap_or_default()
+                }
+
+                fn from_proto(proto: $t) -> Option<$t> {
+                    Some(proto).filter(|value| *value != <$t>::default())
+                }
+            }
+        )*
+    };
+}
+
+scalar_protos!(bool, i32, i64, u32, u64, f32, f64, u8, String);
+
+/// Messages, and fields with explicit presence.
+impl<T: Proto<P>, P> Proto<Option<P>> for Option<T> {
+    fn into_proto(self) -> Option<P> {
+        self.map(T::into_proto)
+    }
+
+    fn from_proto(proto: Option<P>) -> Option<T> {
+        proto.map(T::from_proto)
+    }
+}
+
+/// Messages which contain themselves.
+impl<T: Proto<P>, P> Proto<P> for Box<T> {
+    fn into_proto(self) -> P {
+        (*self).into_proto()
+    }
+
+    fn from_proto(proto: P) -> Box<T> {
+        Box::new(T::from_proto(proto))
+    }
+}
+
+impl<T: Proto<P>, P> Proto<Vec<P>> for Vec<T> {
+    fn into_proto(self) -> Vec<P> {
+        self.into_iter().map(T::into_proto).collect()
+    }
+
+    fn from_proto(proto: Vec<P>) -> Vec<T> {
+        proto.into_iter().map(T::from_proto).collect()
+    }
+}
+
+impl<T: Proto<P>, P> Proto<Vec<P>> for Option<Vec<T>> {
+    fn into_proto(self) -> Vec<P> {
+        self.map(Proto::into_proto).unwrap_or_default()
+    }
+
+    fn from_proto(proto: Vec<P>) -> Option<Vec<T>> {
+        Some(proto)
+            .filter(|items| !items.is_empty())
+            .map(Proto::from_proto)
+    }
+}
+
+impl<T: Proto<P>, P> Proto<HashMap<String, P>> for Option<HashMap<String, T>> {
+    fn into_proto(self) -> HashMap<String, P> {
+        self.unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| (key, value.into_proto()))
+            .collect()
+    }
+
+    fn from_proto(proto: HashMap<String, P>) -> Option<HashMap<String, T>> {
+        Some(proto).filter(|map| !map.is_empty()).map(|map| {
+            map.into_iter()
+                .map(|(key, value)| (key, T::from_proto(value)))
+                .collect()
+        })
+    }
+}
+
+impl Proto<HashMap<String, String>> for Option<Labels> {
+    fn into_proto(self) -> HashMap<String, String> {
+        self.map(HashMap::from).unwrap_or_default()
+    }
+
+    fn from_proto(proto: HashMap<String, String>) -> Option<Labels> {
+        Some(proto).filter(|map| !map.is_empty()).map(Labels::from)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Proto<Vec<u8>> for Option<bytes::Bytes> {
+    fn into_proto(self) -> Vec<u8> {
+        self.map(Vec::from).unwrap_or_default()
+    }
+
+    fn from_proto(proto: Vec<u8>) -> Option<bytes::Bytes> {
+        Some(proto)
+            .filter(|bytes| !bytes.is_empty())
+            .map(bytes::Bytes::from)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Proto<bytes::Bytes> for Option<bytes::Bytes> {
+    fn into_proto(self) -> bytes::Bytes {
+        self.unwrap_or_default()
+    }
+
+    fn from_proto(proto: bytes::Bytes) -> Option<bytes::Bytes> {
+        Some(proto).filter(|bytes| !bytes.is_empty())
+    }
+}
+
+impl Proto<prost_types::Timestamp> for DateTime<Utc> {
+    fn into_proto(self) -> prost_types::Timestamp {
+        prost_types::Timestamp {
+            seconds: self.timestamp(),
+            nanos: self.timestamp_subsec_nanos() as i32,
+        }
+    }
+
+    /// Timestamps out of the range of [`DateTime`] become the Unix epoch.
+    fn from_proto(proto: prost_types::Timestamp) -> DateTime<Utc> {
+        Utc.timestamp_opt(proto.seconds, proto.nanos.max(0) as u32)
+            .single()
+            .unwrap_or_default()
+    }
+}
+
+impl Proto<prost_types::Duration> for chrono::Duration {
+    fn into_proto(self) -> prost_types::Duration {
+        let seconds = self.num_seconds();
+        let nanos = (self - chrono::Duration::seconds(seconds))
+            .num_nanoseconds()
+            .unwrap_or(0);
+        prost_types::Duration {
+            seconds,
+            nanos: nanos as i32,
+        }
+    }
+
+    fn from_proto(proto: prost_types::Duration) -> chrono::Duration {
+        chrono::Duration::seconds(proto.seconds) + chrono::Duration::nanoseconds(proto.nanos.into())
+    }
+}
+
+impl Proto<prost_types::FieldMask> for FieldMask {
+    fn into_proto(self) -> prost_types::FieldMask {
+        prost_types::FieldMask {
+            paths: self.into_paths(),
+        }
+    }
+
+    fn from_proto(proto: prost_types::FieldMask) -> FieldMask {
+        FieldMask::new(&proto.paths)
+    }
+}
+
+impl Proto<prost_types::Value> for json::Value {
+    fn into_proto(self) -> prost_types::Value {
+        use prost_types::value::Kind;
+        let kind = match self {
+            json::Value::Null => Kind::NullValue(prost_types::NullValue::NullValue as i32),
+            json::Value::Bool(value) => Kind::BoolValue(value),
+            json::Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+            json::Value::String(value) => Kind::StringValue(value),
+            json::Value::Array(values) => Kind::ListValue(prost_types::ListValue {
+                values: values.into_proto(),
+            }),
+            json::Value::Object(fields) => {
+                Kind::StructValue(fields.into_iter().collect::<HashMap<_, _>>().into_proto())
+            }
+        };
+        prost_types::Value { kind: Some(kind) }
+    }
+
+    fn from_proto(proto: prost_types::Value) -> json::Value {
+        use prost_types::value::Kind;
+        match proto.kind {
+            None | Some(Kind::NullValue(_)) => json::Value::Null,
+            Some(Kind::BoolValue(value)) => json::Value::Bool(value),
+            Some(Kind::NumberValue(value)) => json::Number::from_f64(value)
+                .map(json::Value::Number)
+                .unwrap_or(json::Value::Null),
+            Some(Kind::StringValue(value)) => json::Value::String(value),
+            Some(Kind::ListValue(list)) => json::Value::Array(Proto::from_proto(list.values)),
+            Some(Kind::StructValue(fields)) => {
+                json::Value::Object(HashMap::from_proto(fields).into_iter().collect())
+            }
+        }
+    }
+}
+
+/// Objects of JSON values, like `google.protobuf.Struct` fields.
+impl Proto<prost_types::Struct> for HashMap<String, json::Value> {
+    fn into_proto(self) -> prost_types::Struct {
+        prost_types::Struct {
+            fields: self
+                .into_iter()
+                .map(|(key, value)| (key, value.into_proto()))
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: prost_types::Struct) -> HashMap<String, json::Value> {
+        proto
+            .fields
+            .into_iter()
+            .map(|(key, value)| (key, json::Value::from_proto(value)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fields_round_trip() {
+        let proto: String = Some("a".to_string()).into_proto();
+        assert_eq!(proto, "a");
+        let proto: String = None::<String>.into_proto();
+        assert_eq!(proto, "");
+        assert_eq!(Option::<String>::from_proto(String::new()), None);
+        assert_eq!(Option::<i64>::from_proto(7), Some(7));
+        assert_eq!(
+            Option::<Vec<String>>::from_proto(vec!["a".to_string()]),
+            Some(vec!["a".to_string()])
+        );
+        assert_eq!(Option::<Vec<u8>>::from_proto(Vec::new()), None);
+        let map: HashMap<String, i32> = Some(HashMap::from([("a".to_string(), 1)])).into_proto();
+        assert_eq!(map["a"], 1);
+
+        let time = Utc.timestamp_opt(1_700_000_000, 5).unwrap();
+        let proto = Some(time).into_proto().unwrap();
+        assert_eq!((proto.seconds, proto.nanos), (1_700_000_000, 5));
+        assert_eq!(Option::<DateTime<Utc>>::from_proto(Some(proto)), Some(time));
+
+        let duration = chrono::Duration::milliseconds(-1500);
+        let proto = duration.into_proto();
+        assert_eq!((proto.seconds, proto.nanos), (-1, -500_000_000));
+        assert_eq!(chrono::Duration::from_proto(proto), duration);
+
+        let mask = FieldMask::from_proto(prost_types::FieldMask {
+            paths: vec!["display_name".to_string(), "labels".to_string()],
+        });
+        assert_eq!(mask.to_string(), "displayName,labels");
+        assert_eq!(mask.into_proto().paths, ["display_name", "labels"]);
+    }
+
+    #[test]
+    fn json_values() {
+        let value = json::json!({"a": [1.5, "b", null, true], "c": {"d": 2.0}});
+        let proto = value.clone().into_proto();
+        assert!(matches!(
+            proto.kind,
+            Some(prost_types::value::Kind::StructValue(_))
+        ));
+        assert_eq!(json::Value::from_proto(proto), value);
+    }
+}
@@ -0,0 +1,226 @@
+//! Estimation of the quota consumed by the calls made through a Hub.
+//!
+//! Some APIs charge a known amount of quota for each call, like the units of the YouTube Data API.
+//! The generated call builders of these APIs provide their cost as `QUOTA_COST` constant, and
+//! their Hubs accept a [`QuotaBudget`] which adds up the cost of all requests sent to the server.
+//! It can't know about calls made by other clients of the same project, so the consumption is
+//! an estimate.
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The quota charged by the server for a single call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuotaCost {
+    /// The name of the quota the cost is charged to, like `units`.
+    pub bucket: &'static str,
+    pub cost: u64,
+}
+
+/// The estimated consumption of a quota within its current period.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub bucket: String,
+    pub used: u64,
+    pub limit: u64,
+}
+
+impl QuotaUsage {
+    /// Returns true if the quota is used up.
+    pub fn is_exhausted(&self) -> bool {
+        self.used >= self.limit
+    }
+}
+
+struct Bucket {
+    limit: u64,
+    period: Duration,
+    period_start: Option<Instant>,
+    used: u64,
+    warned: bool,
+}
+
+type WarningFn = dyn Fn(&QuotaUsage) + Send + Sync;
+
+/// Tracks the quota consumed by a Hub and calls a function once a quota is nearly used up.
+///
+/// Clones share their consumption, so the budget may be shared by multiple Hubs.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use google_apis_common::quota::QuotaBudget;
+///
+/// let budget = QuotaBudget::new()
+///     .limit("units", 10_000, Duration::from_secs(24 * 60 * 60))
+///     .warn_at(90)
+///     .on_warning(|usage| eprintln!("Used {} of {} {}", usage.used, usage.limit, usage.bucket));
+/// ```
+#[derive(Clone)]
+pub struct QuotaBudget {
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+    warn_at_percent: u8,
+    on_warning: Option<Arc<WarningFn>>,
+}
+
+impl Default for QuotaBudget {
+    fn default() -> Self {
+        QuotaBudget {
+            buckets: Default::default(),
+            warn_at_percent: 80,
+            on_warning: None,
+        }
+    }
+}
+
+impl fmt::Debug for QuotaBudget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("QuotaBudget")
+            .field("warn_at_percent", &self.warn_at_percent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl QuotaBudget {
+    /// A budget without any limits, which warns once 80% of a limit are used.
+    pub fn new() -> QuotaBudget {
+        Default::default()
+    }
+
+    /// Set the amount of quota available in `bucket` per `period`, which starts with the first
+    /// call charged to it. Costs charged to buckets without a limit are ignored.
+    pub fn limit(self, bucket: impl Into<String>, limit: u64, period: Duration) -> QuotaBudget {
+        self.buckets.lock().unwrap().insert(
+            bucket.into(),
+            Bucket {
+                limit,
+                period,
+                period_start: None,
+                used: 0,
+                warned: false,
+            },
+        );
+        self
+    }
+
+    /// Set the percentage of a limit after which the warning function is called.
+    pub fn warn_at(mut self, percent: u8) -> QuotaBudget {
+        self.warn_at_percent = percent;
+        self
+    }
+
+    /// Set the function to call once per period when the usage of a bucket reaches the warning
+    /// threshold, and again if its limit is exceeded.
+    pub fn on_warning(mut self, f: impl Fn(&QuotaUsage) + Send + Sync + 'static) -> QuotaBudget {
+        self.on_warning = Some(Arc::new(f));
+        self
+    }
+
+    /// Returns the estimated usage of `bucket` in its current period, if it has a limit.
+    pub fn usage(&self, bucket: &str) -> Option<QuotaUsage> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let b = buckets.get_mut(bucket)?;
+        b.advance(Instant::now());
+        Some(QuotaUsage {
+            bucket: bucket.to_string(),
+            used: b.used,
+            limit: b.limit,
+        })
+    }
+
+    /// Charge the cost of a call to its bucket.
+    pub fn consume(&self, cost: QuotaCost) {
+        self.consume_at(cost, Instant::now())
+    }
+
+    fn consume_at(&self, cost: QuotaCost, now: Instant) {
+        let usage = {
+            let mut buckets = self.buckets.lock().unwrap();
+            let b = match buckets.get_mut(cost.bucket) {
+                Some(b) => b,
+                None => return,
+            };
+            b.advance(now);
+            let was_exhausted = b.used > b.limit;
+            b.used += cost.cost;
+
+            let threshold = b.limit * u64::from(self.warn_at_percent) / 100;
+            let warn = (!b.warned && b.used >= threshold) || (!was_exhausted && b.used > b.limit);
+            if !warn {
+                return;
+            }
+            b.warned = true;
+            QuotaUsage {
+                bucket: cost.bucket.to_string(),
+                used: b.used,
+                limit: b.limit,
+            }
+        };
+        if let Some(on_warning) = self.on_warning.as_ref() {
+            on_warning(&usage);
+        }
+    }
+}
+
+impl Bucket {
+    fn advance(&mut self, now: Instant) {
+        match self.period_start {
+            Some(start) if now.duration_since(start) < self.period => {}
+            _ => {
+                self.period_start = Some(now);
+                self.used = 0;
+                self.warned = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SEARCH: QuotaCost = QuotaCost {
+        bucket: "units",
+        cost: 40,
+    };
+
+    #[test]
+    fn warnings_at_threshold_and_exhaustion() {
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let budget = {
+            let warnings = warnings.clone();
+            QuotaBudget::new()
+                .limit("units", 100, Duration::from_secs(60))
+                .warn_at(75)
+                .on_warning(move |usage| warnings.lock().unwrap().push(usage.used))
+        };
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            budget.consume_at(SEARCH, start);
+        }
+        budget.consume_at(QuotaCost { bucket: "other", cost: 1000 }, start);
+        assert_eq!(*warnings.lock().unwrap(), vec![80, 120]);
+        assert!(budget.usage("units").unwrap().is_exhausted());
+        assert_eq!(budget.usage("other"), None);
+
+        budget.consume_at(SEARCH, start + Duration::from_secs(60));
+        assert_eq!(budget.usage("units").unwrap().used, 40);
+    }
+
+    #[test]
+    fn clones_share_usage() {
+        let budget = QuotaBudget::new().limit("units", 100, Duration::from_secs(60));
+        budget.clone().consume(SEARCH);
+        budget.consume(SEARCH);
+        assert_eq!(
+            budget.usage("units"),
+            Some(QuotaUsage {
+                bucket: "units".into(),
+                used: 80,
+                limit: 100
+            })
+        );
+    }
+}
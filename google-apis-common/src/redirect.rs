@@ -0,0 +1,361 @@
+//! Configuration of how redirects returned by the server are followed.
+//!
+//! [`hyper`] doesn't follow redirects by itself, which is why all calls made by the generated
+//! APIs are sent through a [`RedirectPolicy`]. It can be configured on the Hub.
+//!
+//! As it also sends all requests following a redirect, it runs the Hub's [`InterceptorChain`]
+//! right before each request is sent, and once its response was received.
+use std::error::Error as StdError;
+use std::time::Instant;
+
+use ::url::Url;
+use http::Uri;
+use hyper::body::HttpBody;
+use hyper::client::connect::Connection;
+use hyper::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, LOCATION};
+use hyper::{Method, StatusCode};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_service::Service;
+
+use crate::interceptor::InterceptorChain;
+use crate::Error;
+
+/// Determines which redirects are followed when performing a request.
+///
+/// By default, redirects aren't followed, and are returned like any other response. Policies
+/// following them, like [`RedirectPolicy::limited()`], follow them to any host, but send the
+/// `Authorization` header only to the host of the original request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RedirectPolicy {
+    /// The maximum amount of redirects to follow for a single request. `0` disables redirects.
+    pub max_redirects: usize,
+    /// If true, redirects to hosts other than the one of the original request are not followed.
+    pub same_host_only: bool,
+    /// If true, the `Authorization` header is also sent to hosts other than the one of the
+    /// original request.
+    pub forward_auth: bool,
+}
+
+impl RedirectPolicy {
+    /// A policy which never follows redirects, returning them as response instead, which is the
+    /// default.
+    pub fn none() -> RedirectPolicy {
+        RedirectPolicy::default()
+    }
+
+    /// A policy which follows up to `max_redirects` redirects for a single request.
+    pub fn limited(max_redirects: usize) -> RedirectPolicy {
+        RedirectPolicy {
+            max_redirects,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the uri to follow a redirect to, or `None` if `location` is invalid or not
+    /// permitted by this policy.
+    fn follow(&self, origin: &Uri, current: &Uri, location: &str) -> Option<Uri> {
+        let target: Uri = Url::parse(&current.to_string())
+            .and_then(|base| base.join(location))
+            .ok()?
+            .as_str()
+            .parse()
+            .ok()?;
+        if self.same_host_only && target.authority() != origin.authority() {
+            return None;
+        }
+        Some(target)
+    }
+
+    /// Send the given request, following redirects according to this policy and signing each
+    /// request using `interceptors`.
+    ///
+    /// The request body is buffered if `interceptors` sign it or inspect it. Otherwise, it is only
+    /// taken as is to send it again to the target of a redirect preserving the method if it is held in
+    /// memory, like JSON requests. Redirects which would send a streamed body, like media, again are
+    /// returned instead of being followed.
+    pub async fn send<S>(
+        &self,
+        client: &hyper::Client<S, hyper::body::Body>,
+        req: hyper::Request<hyper::body::Body>,
+        interceptors: &InterceptorChain,
+    ) -> crate::Result<hyper::Response<hyper::body::Body>>
+    where
+        S: Service<Uri> + Clone + Send + Sync + 'static,
+        S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        if self.max_redirects == 0 && interceptors.is_empty() {
+            return client.request(req).await.map_err(Error::HttpError);
+        }
+
+        let (mut parts, body) = req.into_parts();
+        // bodies held in memory have an exact size, and taking them as bytes doesn't copy them
+        let mut body = if interceptors.is_empty() && body.size_hint().exact().is_none() {
+            RequestBody::Streamed(Some(body))
+        } else {
+            RequestBody::Buffered(
+                hyper::body::to_bytes(body)
+                    .await
+                    .map_err(Error::HttpError)?,
+            )
+        };
+        let origin = parts.uri.clone();
+
+        let mut redirects = 0;
+        loop {
+            let (req_body, bytes) = match &mut body {
+                RequestBody::Buffered(bytes) => {
+                    (hyper::body::Body::from(bytes.clone()), &bytes[..])
+                }
+                RequestBody::Streamed(streamed) => (streamed.take().unwrap_or_default(), &[][..]),
+            };
+            let mut req = hyper::Request::new(req_body);
+            *req.method_mut() = parts.method.clone();
+            *req.uri_mut() = parts.uri.clone();
+            *req.version_mut() = parts.version;
+            *req.headers_mut() = parts.headers.clone();
+
+            let (mut req_parts, req_body) = req.into_parts();
+            interceptors
+                .sign(&mut req_parts, bytes)
+                .await
+                .map_err(Error::Interceptor)?;
+            interceptors.on_request(&req_parts, bytes);
+            let (method, uri) = (req_parts.method.clone(), req_parts.uri.clone());
+            let req = hyper::Request::from_parts(req_parts, req_body);
+
+            let sent_at = Instant::now();
+            let res = match client.request(req).await {
+                Ok(res) => res,
+                Err(err) => {
+                    interceptors.on_error(&method, &uri, &err);
+                    return Err(Error::HttpError(err));
+                }
+            };
+            let (res_parts, res_body) = res.into_parts();
+            interceptors.on_response(&method, &uri, &res_parts, sent_at.elapsed());
+            let res_body = if interceptors.wants_response_bodies() {
+                let bytes = hyper::body::to_bytes(res_body)
+                    .await
+                    .map_err(Error::HttpError)?;
+                interceptors.on_response_body(&method, &uri, &bytes);
+                hyper::body::Body::from(bytes)
+            } else {
+                res_body
+            };
+            let res = hyper::Response::from_parts(res_parts, res_body);
+            if redirects == self.max_redirects {
+                return Ok(res);
+            }
+            let target = match res.status() {
+                StatusCode::MOVED_PERMANENTLY
+                | StatusCode::FOUND
+                | StatusCode::SEE_OTHER
+                | StatusCode::TEMPORARY_REDIRECT
+                | StatusCode::PERMANENT_REDIRECT => res
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|l| l.to_str().ok())
+                    .and_then(|l| self.follow(&origin, &parts.uri, l)),
+                _ => None,
+            };
+            let target = match target {
+                Some(target) => target,
+                None => return Ok(res),
+            };
+
+            // Like browsers do, only 307 and 308 preserve the method and body
+            let status = res.status();
+            if (status == StatusCode::SEE_OTHER && parts.method != Method::HEAD)
+                || ((status == StatusCode::MOVED_PERMANENTLY || status == StatusCode::FOUND)
+                    && parts.method == Method::POST)
+            {
+                parts.method = Method::GET;
+                parts.headers.remove(CONTENT_TYPE);
+                parts.headers.remove(CONTENT_LENGTH);
+                body = RequestBody::Buffered(Default::default());
+            } else if let RequestBody::Streamed(None) = body {
+                // the streamed body was sent already, and can't be sent again
+                return Ok(res);
+            }
+            if !self.forward_auth && target.authority() != origin.authority() {
+                parts.headers.remove(AUTHORIZATION);
+            }
+            parts.uri = target;
+            redirects += 1;
+        }
+    }
+}
+
+/// The body of a request which is followed through redirects.
+enum RequestBody {
+    /// A body held in memory, which is sent again when following redirects.
+    Buffered(hyper::body::Bytes),
+    /// A streamed body, which is sent once, and taken when it is.
+    Streamed(Option<hyper::body::Body>),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn follow_resolves_relative_locations() {
+        let origin: Uri = "https://www.googleapis.com/drive/v3/files/abc"
+            .parse()
+            .unwrap();
+        let policy = RedirectPolicy::default();
+
+        assert_eq!(
+            policy.follow(&origin, &origin, "/download/abc?alt=media"),
+            Some(
+                "https://www.googleapis.com/download/abc?alt=media"
+                    .parse()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            policy.follow(&origin, &origin, "https://content.googleapis.com/abc"),
+            Some("https://content.googleapis.com/abc".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn follow_respects_same_host_only() {
+        let origin: Uri = "https://www.googleapis.com/drive/v3/files/abc"
+            .parse()
+            .unwrap();
+        let policy = RedirectPolicy {
+            same_host_only: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            policy.follow(&origin, &origin, "https://content.googleapis.com/abc"),
+            None
+        );
+        assert!(policy.follow(&origin, &origin, "other").is_some());
+    }
+
+    /// Serves redirects from `/from` to `/to`, returning its url and the requests it received as
+    /// their path and body.
+    async fn serve_redirect() -> (
+        String,
+        std::sync::Arc<std::sync::Mutex<Vec<(String, String)>>>,
+    ) {
+        use std::sync::{Arc, Mutex};
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let received = received.clone();
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    loop {
+                        let mut request_line = String::new();
+                        if stream.read_line(&mut request_line).await.unwrap() == 0 {
+                            return;
+                        }
+                        let path = request_line.split(' ').nth(1).unwrap().to_string();
+                        let (mut length, mut chunked) = (0, false);
+                        loop {
+                            let mut line = String::new();
+                            stream.read_line(&mut line).await.unwrap();
+                            let line = line.trim_end().to_ascii_lowercase();
+                            if line.is_empty() {
+                                break;
+                            } else if let Some(value) = line.strip_prefix("content-length: ") {
+                                length = value.parse().unwrap();
+                            } else if line == "transfer-encoding: chunked" {
+                                chunked = true;
+                            }
+                        }
+                        let mut body = Vec::new();
+                        if chunked {
+                            loop {
+                                let mut size = String::new();
+                                stream.read_line(&mut size).await.unwrap();
+                                let size = usize::from_str_radix(size.trim(), 16).unwrap();
+                                let mut chunk = vec![0; size + 2];
+                                stream.read_exact(&mut chunk).await.unwrap();
+                                if size == 0 {
+                                    break;
+                                }
+                                body.extend_from_slice(&chunk[..size]);
+                            }
+                        } else {
+                            body.resize(length, 0);
+                            stream.read_exact(&mut body).await.unwrap();
+                        }
+                        let res = if path == "/from" {
+                            "HTTP/1.1 307 Temporary Redirect\r\nLocation: /to\r\ncontent-length: 0\r\n\r\n"
+                        } else {
+                            "HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n"
+                        };
+                        received
+                            .lock()
+                            .unwrap()
+                            .push((path, String::from_utf8(body).unwrap()));
+                        stream.get_mut().write_all(res.as_bytes()).await.unwrap();
+                    }
+                });
+            }
+        });
+        (url, requests)
+    }
+
+    fn post(url: &str, body: hyper::body::Body) -> hyper::Request<hyper::body::Body> {
+        hyper::Request::post(format!("{}/from", url))
+            .body(body)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn redirects_are_followed_if_enabled() {
+        let (url, requests) = serve_redirect().await;
+        let client = hyper::Client::new();
+        let interceptors = InterceptorChain::default();
+
+        let res = RedirectPolicy::default()
+            .send(&client, post(&url, "data".into()), &interceptors)
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::TEMPORARY_REDIRECT);
+
+        let res = RedirectPolicy::limited(10)
+            .send(&client, post(&url, "data".into()), &interceptors)
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // a streamed body is sent once, and the redirect returned as it can't be sent again
+        let (mut sender, body) = hyper::body::Body::channel();
+        tokio::spawn(async move { sender.send_data("streamed".into()).await });
+        let res = RedirectPolicy::limited(10)
+            .send(&client, post(&url, body), &interceptors)
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::TEMPORARY_REDIRECT);
+
+        let requests = requests.lock().unwrap();
+        let requests: Vec<_> = requests
+            .iter()
+            .map(|(path, body)| (path.as_str(), body.as_str()))
+            .collect();
+        assert_eq!(
+            requests,
+            [
+                ("/from", "data"),
+                ("/from", "data"),
+                ("/to", "data"),
+                ("/from", "streamed")
+            ]
+        );
+    }
+}
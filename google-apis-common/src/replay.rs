@@ -0,0 +1,324 @@
+//! Replaying recorded exchanges instead of sending requests, to test calls without a network.
+//!
+//! A [`Replay`] is a connector answering the requests of a hub with the responses recorded by a
+//! [`HarRecorder`](crate::HarRecorder) created with `with_bodies()`. Each request is answered by
+//! the first unused exchange with the same method, path and query, ignoring redacted credentials,
+//! so calls are covered from building their url to decoding their response, including pagination.
+//! Requests without a recorded exchange are answered with `501 Not Implemented`, and can be
+//! listed with [`Replay::unmatched()`].
+//!
+//! The generated crates use it in their `replay` tests, which run with the `replay` feature, and
+//! their CLIs to serve a directory of fixtures in `--offline` mode, see [`Replay::load_dir()`].
+use std::convert::Infallible;
+use std::fs;
+use std::future::{ready, Ready};
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use http::{Request, Response, Uri};
+use hyper::body::Body;
+use hyper::client::connect::{Connected, Connection};
+use hyper::service::service_fn;
+use serde_json as json;
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+
+use crate::har::redacted_uri;
+
+// headers describing the recorded body, rather than the one replayed
+const SKIPPED_HEADERS: [&str; 3] = ["content-length", "content-encoding", "transfer-encoding"];
+
+/// A connector answering requests with recorded responses. Its clones share the recording, so
+/// exchanges are only replayed once.
+#[derive(Clone, Default)]
+pub struct Replay {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    exchanges: Vec<Exchange>,
+    unmatched: Vec<String>,
+}
+
+struct Exchange {
+    method: String,
+    path: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    used: bool,
+}
+
+impl Replay {
+    /// Replay the entries of a HAR document, as written by
+    /// [`HarRecorder::write()`](crate::HarRecorder::write()). Entries without a response are
+    /// skipped.
+    pub fn from_har(har: &json::Value) -> Replay {
+        Replay::from_exchanges(exchanges_of(har))
+    }
+
+    /// Load the HAR file at `path`, as saved by [`HarRecorder::save()`](crate::HarRecorder::save()).
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Replay> {
+        Ok(Replay::from_exchanges(exchanges_of(&read_har(
+            path.as_ref(),
+        )?)))
+    }
+
+    /// Load the `.har` files in the directory at `dir`, whose exchanges are replayed in the order
+    /// of their file names, like fixtures recorded for several calls of a runbook.
+    pub fn load_dir(dir: impl AsRef<Path>) -> io::Result<Replay> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension() == Some("har".as_ref()) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        let mut exchanges = Vec::new();
+        for path in paths {
+            exchanges.extend(exchanges_of(&read_har(&path)?));
+        }
+        Ok(Replay::from_exchanges(exchanges))
+    }
+
+    fn from_exchanges(exchanges: Vec<Exchange>) -> Replay {
+        Replay {
+            state: Arc::new(Mutex::new(State {
+                exchanges,
+                unmatched: Vec::new(),
+            })),
+        }
+    }
+
+    /// The amount of recorded exchanges which weren't replayed yet.
+    pub fn remaining(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state
+            .exchanges
+            .iter()
+            .filter(|exchange| !exchange.used)
+            .count()
+    }
+
+    /// The requests which had no recorded exchange, as method and redacted path.
+    pub fn unmatched(&self) -> Vec<String> {
+        self.state.lock().unwrap().unmatched.clone()
+    }
+
+    fn respond(&self, request: Request<Body>) -> Response<Body> {
+        let method = request.method().as_str();
+        let path = redacted_uri(request.uri());
+        let mut state = self.state.lock().unwrap();
+        let exchange = state
+            .exchanges
+            .iter_mut()
+            .find(|exchange| !exchange.used && exchange.method == method && exchange.path == path);
+        let exchange = match exchange {
+            Some(exchange) => exchange,
+            None => {
+                state.unmatched.push(format!("{} {}", method, path));
+                let error = json::json!({"error": {
+                    "code": 501,
+                    "message": format!("no recorded exchange for {} {}", method, path),
+                    "status": "UNIMPLEMENTED",
+                }});
+                return Response::builder()
+                    .status(501)
+                    .header("content-type", "application/json")
+                    .body(Body::from(error.to_string()))
+                    .unwrap();
+            }
+        };
+        exchange.used = true;
+        let mut response = Response::builder().status(exchange.status);
+        for (name, value) in &exchange.headers {
+            if !SKIPPED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                response = response.header(name.as_str(), value.as_str());
+            }
+        }
+        response
+            .body(Body::from(exchange.body.clone()))
+            .unwrap_or_else(|_| Response::new(Body::from(exchange.body.clone())))
+    }
+}
+
+fn read_har(path: &Path) -> io::Result<json::Value> {
+    json::from_slice(&fs::read(path)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+// the entries of a HAR document which have a response
+fn exchanges_of(har: &json::Value) -> Vec<Exchange> {
+    let entries = har["log"]["entries"].as_array().map(Vec::as_slice);
+    entries
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| {
+            let uri: Uri = entry["request"]["url"].as_str()?.parse().ok()?;
+            let response = &entry["response"];
+            Some(Exchange {
+                method: entry["request"]["method"].as_str()?.to_string(),
+                path: uri.path_and_query()?.to_string(),
+                status: response["status"].as_u64().filter(|&status| status > 0)? as u16,
+                headers: response["headers"]
+                    .as_array()
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|header| {
+                        let name = header["name"].as_str()?;
+                        let value = header["value"].as_str()?;
+                        Some((name.to_string(), value.to_string()))
+                    })
+                    .collect(),
+                body: response["content"]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                used: false,
+            })
+        })
+        .collect()
+}
+
+impl tower_service::Service<Uri> for Replay {
+    type Response = ReplayStream;
+    type Error = Infallible;
+    type Future = Ready<Result<ReplayStream, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let replay = self.clone();
+        let service =
+            service_fn(move |request| ready(Ok::<_, Infallible>(replay.respond(request))));
+        tokio::spawn(hyper::server::conn::Http::new().serve_connection(server, service));
+        ready(Ok(ReplayStream(client)))
+    }
+}
+
+/// The connection to a [`Replay`].
+pub struct ReplayStream(DuplexStream);
+
+impl Connection for ReplayStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+impl AsyncRead for ReplayStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ReplayStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn replays_recorded_exchanges() {
+        let har = json::json!({"log": {"entries": [
+            {
+                "request": {"method": "GET", "url": "https://example.googleapis.com/v1/items?key=%3Credacted%3E"},
+                "response": {
+                    "status": 200,
+                    "headers": [{"name": "Content-Length", "value": "99"}, {"name": "X-Page", "value": "1"}],
+                    "content": {"text": "{\"nextPageToken\":\"t\"}"},
+                },
+            },
+            {
+                "request": {"method": "GET", "url": "https://example.googleapis.com/v1/items?key=%3Credacted%3E"},
+                "response": {"status": 200, "headers": [], "content": {"text": "{}"}},
+            },
+            {
+                "request": {"method": "DELETE", "url": "https://example.googleapis.com/v1/items/1"},
+                "response": {"status": 0, "headers": [], "content": {}},
+            },
+        ]}});
+        let replay = Replay::from_har(&har);
+        assert_eq!(replay.remaining(), 2);
+        let client = hyper::Client::builder().build::<_, Body>(replay.clone());
+        let get = || {
+            client.get(
+                "https://example.googleapis.com/v1/items?key=other"
+                    .parse()
+                    .unwrap(),
+            )
+        };
+
+        let response = get().await.unwrap();
+        assert_eq!(response.headers()["x-page"], "1");
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], b"{\"nextPageToken\":\"t\"}");
+        let body = hyper::body::to_bytes(get().await.unwrap().into_body())
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"{}");
+        assert_eq!(replay.remaining(), 0);
+
+        assert_eq!(get().await.unwrap().status(), 501);
+        assert_eq!(replay.unmatched(), ["GET /v1/items?key=%3Credacted%3E"]);
+    }
+
+    #[tokio::test]
+    async fn loads_directories_of_fixtures() {
+        let dir = std::env::temp_dir().join(format!("replay-fixtures-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let fixture = |body: &str| {
+            json::json!({"log": {"entries": [{
+                "request": {"method": "GET", "url": "https://example.googleapis.com/v1/items/1"},
+                "response": {"status": 200, "headers": [], "content": {"text": body}},
+            }]}})
+            .to_string()
+        };
+        fs::write(dir.join("2-after.har"), fixture("{\"state\":\"DONE\"}")).unwrap();
+        fs::write(dir.join("1-before.har"), fixture("{\"state\":\"RUNNING\"}")).unwrap();
+        fs::write(dir.join("notes.txt"), "not a fixture").unwrap();
+        let replay = Replay::load_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        let replay = replay.unwrap();
+        assert_eq!(replay.remaining(), 2);
+
+        let client = hyper::Client::builder().build::<_, Body>(replay);
+        let mut bodies = Vec::new();
+        for _ in 0..2 {
+            let response = client
+                .get("https://example.googleapis.com/v1/items/1".parse().unwrap())
+                .await
+                .unwrap();
+            bodies.push(hyper::body::to_bytes(response.into_body()).await.unwrap());
+        }
+        assert_eq!(bodies, ["{\"state\":\"RUNNING\"}", "{\"state\":\"DONE\"}"]);
+        assert!(Replay::load_dir(std::env::temp_dir().join("replay-fixtures-missing")).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Support for the typed resource names, like `InstanceName`, which are generated for all
+//! resource name patterns of an API.
+use std::error;
+use std::fmt::{self, Display};
+
+/// A string couldn't be parsed as a resource name, as it doesn't match its template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceNameError {
+    /// The string which was parsed.
+    pub value: String,
+    /// The template of the resource name, like `projects/{project}/instances/{instance}`.
+    pub template: &'static str,
+}
+
+impl Display for ResourceNameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a resource name of the form '{}'",
+            self.value, self.template
+        )
+    }
+}
+
+impl error::Error for ResourceNameError {}
+
+/// Parses `value` according to `template`, returning the values of its `N` variables in order.
+///
+/// Templates consist of literal segments and variables like `{project}`, which match a single,
+/// non-empty segment.
+pub fn parse_resource_name<const N: usize>(
+    value: &str,
+    template: &'static str,
+) -> Result<[String; N], ResourceNameError> {
+    let err = || ResourceNameError {
+        value: value.to_string(),
+        template,
+    };
+    let mut values = Vec::with_capacity(N);
+    let mut segments = value.split('/');
+    for expected in template.split('/') {
+        let segment = segments.next().ok_or_else(err)?;
+        if expected.starts_with('{') && expected.ends_with('}') {
+            if segment.is_empty() {
+                return Err(err());
+            }
+            values.push(segment.to_string());
+        } else if segment != expected {
+            return Err(err());
+        }
+    }
+    if segments.next().is_some() {
+        return Err(err());
+    }
+    values.try_into().map_err(|_| err())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEMPLATE: &str = "projects/{project}/locations/{location}/instances/{instance}";
+
+    #[test]
+    fn names_are_parsed() {
+        assert_eq!(
+            parse_resource_name::<3>("projects/p/locations/us-east1/instances/i", TEMPLATE),
+            Ok(["p".to_string(), "us-east1".to_string(), "i".to_string()])
+        );
+    }
+
+    #[test]
+    fn malformed_names_are_rejected() {
+        for value in [
+            "",
+            "projects/p",
+            "projects/p/locations/l/instances/",
+            "projects/p/locations/l/instances/i/extra",
+            "projects/p/zones/l/instances/i",
+        ] {
+            assert_eq!(
+                parse_resource_name::<3>(value, TEMPLATE),
+                Err(ResourceNameError {
+                    value: value.to_string(),
+                    template: TEMPLATE
+                })
+            );
+        }
+        assert_eq!(
+            parse_resource_name::<3>("projects/p", "projects/{project}")
+                .unwrap_err()
+                .to_string(),
+            "'projects/p' is not a resource name of the form 'projects/{project}'"
+        );
+    }
+}
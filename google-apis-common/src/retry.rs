@@ -0,0 +1,212 @@
+//! Retrying calls which failed transiently, without duplicating their side effects.
+//!
+//! Each call tells its delegate whether it is idempotent, i.e. whether sending it twice has the
+//! same effect as sending it once, in the [`MethodInfo`] passed to [`Delegate::begin()`]. Methods
+//! using `GET`, `HEAD`, `PUT` or `DELETE` are, as are custom methods which only read, like
+//! `getIamPolicy`, while creating a resource using `POST` is not. Calls may override this using
+//! their `idempotent()` method, e.g. if they set a request id de-duplicating them.
+//!
+//! [`RetryPolicy`] retries idempotent calls after network errors, timed out attempts and responses
+//! with a status indicating a transient failure, using exponential backoff. Other calls are only
+//! retried if the server rejected them with `429 Too Many Requests`, as it then didn't handle them.
+//! Retries wait at least as long as the server asked to, see [`BackoffHint`].
+use std::time::Duration;
+
+use hyper::StatusCode;
+
+use crate::{BackoffHint, Delegate, MethodInfo, Retry};
+
+/// A delegate retrying calls which failed transiently, with exponential backoff.
+///
+/// It is passed to calls using their `delegate()` method, and may be used for many calls one after
+/// another, as each of them starts with a fresh count of retries.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    idempotent: bool,
+    retries: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(32),
+            idempotent: false,
+            retries: 0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy retrying up to 5 times, waiting 1s before the first retry and twice as long before
+    /// each further one, up to 32s.
+    pub fn new() -> RetryPolicy {
+        Default::default()
+    }
+
+    /// Set the maximum amount of retries of each call.
+    pub fn max_retries(mut self, max_retries: u32) -> RetryPolicy {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry, which is doubled before each further one.
+    pub fn initial_backoff(mut self, backoff: Duration) -> RetryPolicy {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the maximum delay before a retry.
+    pub fn max_backoff(mut self, backoff: Duration) -> RetryPolicy {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Returns the delay before the next retry, if another one is allowed, which is at least
+    /// `at_least`, like the delay asked for by the server.
+    fn next_retry(&mut self, at_least: Option<Duration>) -> Retry {
+        if self.retries >= self.max_retries {
+            return Retry::Abort;
+        }
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << self.retries.min(16))
+            .min(self.max_backoff);
+        self.retries += 1;
+        Retry::After(at_least.map_or(backoff, |at_least| backoff.max(at_least)))
+    }
+
+    fn retry_if_idempotent(&mut self) -> Retry {
+        if self.idempotent {
+            self.next_retry(None)
+        } else {
+            Retry::Abort
+        }
+    }
+}
+
+impl Delegate for RetryPolicy {
+    fn begin(&mut self, info: MethodInfo) {
+        self.idempotent = info.idempotent;
+        self.retries = 0;
+    }
+
+    fn http_error(&mut self, _err: &hyper::Error) -> Retry {
+        self.retry_if_idempotent()
+    }
+
+    fn attempt_timed_out(&mut self) -> Retry {
+        self.retry_if_idempotent()
+    }
+
+    fn http_failure(
+        &mut self,
+        res: &hyper::Response<hyper::body::Body>,
+        err: Option<serde_json::Value>,
+    ) -> Retry {
+        let retry_after = BackoffHint::from_response(res.headers(), err.as_ref())
+            .and_then(|hint| hint.retry_after);
+        match res.status() {
+            StatusCode::TOO_MANY_REQUESTS => self.next_retry(retry_after),
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+                if self.idempotent =>
+            {
+                self.next_retry(retry_after)
+            }
+            _ => Retry::Abort,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::header::RETRY_AFTER;
+    use hyper::Method;
+
+    fn delays(policy: &mut RetryPolicy, status: StatusCode, attempts: usize) -> Vec<Option<u64>> {
+        (0..attempts)
+            .map(|_| {
+                let res = hyper::Response::builder()
+                    .status(status)
+                    .body(hyper::body::Body::empty())
+                    .unwrap();
+                match policy.http_failure(&res, None) {
+                    Retry::After(d) => Some(d.as_secs()),
+                    Retry::Abort => None,
+                }
+            })
+            .collect()
+    }
+
+    fn begin(policy: &mut RetryPolicy, http_method: Method, idempotent: bool) {
+        policy.begin(MethodInfo {
+            id: "storage.objects.insert",
+            http_method,
+            idempotent,
+        });
+    }
+
+    #[test]
+    fn only_idempotent_calls_are_retried() {
+        let mut policy = RetryPolicy::new()
+            .max_retries(4)
+            .max_backoff(Duration::from_secs(4));
+        begin(&mut policy, Method::GET, true);
+        assert_eq!(
+            delays(&mut policy, StatusCode::SERVICE_UNAVAILABLE, 5),
+            [Some(1), Some(2), Some(4), Some(4), None]
+        );
+
+        begin(&mut policy, Method::POST, false);
+        assert_eq!(
+            delays(&mut policy, StatusCode::SERVICE_UNAVAILABLE, 1),
+            [None]
+        );
+        assert!(matches!(policy.attempt_timed_out(), Retry::Abort));
+        // the server didn't handle rejected calls
+        assert_eq!(
+            delays(&mut policy, StatusCode::TOO_MANY_REQUESTS, 2),
+            [Some(1), Some(2)]
+        );
+
+        begin(&mut policy, Method::POST, true);
+        assert!(matches!(policy.attempt_timed_out(), Retry::After(_)));
+        assert_eq!(delays(&mut policy, StatusCode::NOT_FOUND, 1), [None]);
+    }
+
+    #[test]
+    fn retry_after_is_honored() {
+        let mut policy = RetryPolicy::new();
+        begin(&mut policy, Method::POST, false);
+        let res = hyper::Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .header(RETRY_AFTER, "10")
+            .body(hyper::body::Body::empty())
+            .unwrap();
+        assert!(matches!(
+            policy.http_failure(&res, None),
+            Retry::After(d) if d == Duration::from_secs(10)
+        ));
+
+        let res = hyper::Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .body(hyper::body::Body::empty())
+            .unwrap();
+        let err = serde_json::json!({"error": {"code": 429, "details": [{
+            "@type": "type.googleapis.com/google.rpc.RetryInfo",
+            "retryDelay": "20s"
+        }]}});
+        assert!(matches!(
+            policy.http_failure(&res, Some(err)),
+            Retry::After(d) if d == Duration::from_secs(20)
+        ));
+    }
+}
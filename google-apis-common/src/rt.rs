@@ -0,0 +1,235 @@
+//! The timers and tasks of the async runtime calls are made on.
+//!
+//! Calls wait between their attempts and time out attempts, and some helpers run tasks in the
+//! background, like the upload of a body at a limited rate, or the leases of a `Subscriber`. They
+//! use tokio, unless the `async-std` feature is enabled, in which case they use async-std instead,
+//! whose reactor is also the one of smol.
+//!
+//! hyper still needs to be told so: its `Client` spawns the tasks of connections on the
+//! [`Executor`], and it mustn't expire idle connections, as it does with a tokio timer, nor connect
+//! with its `HttpConnector`, which uses tokio sockets. With the `async-std` feature, the
+//! [`TcpConnector`] connects with async-std sockets instead:
+//!
+//! ```ignore
+//! let connector = hyper_rustls::HttpsConnectorBuilder::new()
+//!     .with_native_roots()?
+//!     .https_or_http()
+//!     .enable_http1()
+//!     .wrap_connector(client::rt::TcpConnector);
+//! let client = hyper::Client::builder()
+//!     .executor(client::rt::Executor)
+//!     .pool_idle_timeout(None)
+//!     .build(connector);
+//! ```
+//!
+//! Authenticators making their own requests, like those of yup-oauth2, need to be given such a
+//! client too.
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+/// Waits until `duration` has elapsed.
+pub async fn sleep(duration: Duration) {
+    #[cfg(not(feature = "async-std"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(feature = "async-std")]
+    async_std::task::sleep(duration).await;
+}
+
+/// Returns the output of `future`, or fails with [`Elapsed`] if it isn't ready within `duration`.
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    #[cfg(not(feature = "async-std"))]
+    let output = tokio::time::timeout(duration, future).await.ok();
+    #[cfg(feature = "async-std")]
+    let output = async_std::future::timeout(duration, future).await.ok();
+    output.ok_or(Elapsed(()))
+}
+
+/// Runs `future` in the background, as a task of its own.
+pub fn spawn<F>(future: F)
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[cfg(not(feature = "async-std"))]
+    tokio::spawn(future);
+    #[cfg(feature = "async-std")]
+    async_std::task::spawn(future);
+}
+
+/// The error of a [`timeout()`] which elapsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl StdError for Elapsed {}
+
+/// Spawns the tasks of a `hyper::Client` with [`spawn()`], so they run on the runtime of the calls.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Executor;
+
+impl<F> hyper::rt::Executor<F> for Executor
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    fn execute(&self, future: F) {
+        spawn(future)
+    }
+}
+
+#[cfg(feature = "async-std")]
+pub use self::async_std_io::{TcpConnection, TcpConnector};
+
+#[cfg(feature = "async-std")]
+mod async_std_io {
+    use std::future::Future;
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use async_std::io::{Read, Write};
+    use async_std::net::TcpStream;
+    use hyper::client::connect::{Connected, Connection};
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use tower_service::Service;
+
+    /// Connects to the host of a URI with async-std, to its port or the default one of its scheme.
+    /// It is meant to be wrapped by a TLS connector, e.g.
+    /// `hyper_rustls::HttpsConnectorBuilder::wrap_connector()`.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct TcpConnector;
+
+    impl Service<hyper::Uri> for TcpConnector {
+        type Response = TcpConnection;
+        type Error = io::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, uri: hyper::Uri) -> Self::Future {
+            Box::pin(async move {
+                let host = uri
+                    .host()
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "the URI has no host")
+                    })?
+                    .trim_start_matches('[')
+                    .trim_end_matches(']');
+                let port = match (uri.port_u16(), uri.scheme_str()) {
+                    (Some(port), _) => port,
+                    (None, Some("https")) => 443,
+                    (None, _) => 80,
+                };
+                let stream = TcpStream::connect((host, port)).await.map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!("Failed to connect to '{}:{}': {}", host, port, err),
+                    )
+                })?;
+                stream.set_nodelay(true)?;
+                Ok(TcpConnection(stream))
+            })
+        }
+    }
+
+    /// A connection made by a [`TcpConnector`].
+    pub struct TcpConnection(TcpStream);
+
+    impl Connection for TcpConnection {
+        fn connected(&self) -> Connected {
+            Connected::new()
+        }
+    }
+
+    impl AsyncRead for TcpConnection {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let read = Pin::new(&mut self.0).poll_read(cx, buf.initialize_unfilled());
+            if let Poll::Ready(Ok(n)) = read {
+                buf.advance(n);
+            }
+            read.map_ok(|_| ())
+        }
+    }
+
+    impl AsyncWrite for TcpConnection {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.0).poll_write(cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.0).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.0).poll_close(cx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    async fn timers() {
+        assert_eq!(timeout(Duration::from_secs(5), async { 1 }).await, Ok(1));
+        let elapsed = timeout(Duration::from_millis(10), sleep(Duration::from_secs(5))).await;
+        assert_eq!(elapsed, Err(Elapsed(())));
+        assert_eq!(elapsed.unwrap_err().to_string(), "deadline has elapsed");
+    }
+
+    #[cfg(not(feature = "async-std"))]
+    #[tokio::test]
+    async fn timers_of_tokio() {
+        timers().await;
+    }
+
+    #[cfg(feature = "async-std")]
+    #[test]
+    fn timers_and_connections_of_async_std() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tower_service::Service;
+
+        async_std::task::block_on(async {
+            timers().await;
+
+            let listener = async_std::net::TcpListener::bind("127.0.0.1:0")
+                .await
+                .unwrap();
+            let uri: hyper::Uri = format!("http://{}/", listener.local_addr().unwrap())
+                .parse()
+                .unwrap();
+            let (sent, received) = tokio::sync::oneshot::channel();
+            spawn(async move {
+                use async_std::io::{ReadExt, WriteExt};
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 4];
+                stream.read_exact(&mut buf).await.unwrap();
+                stream.write_all(&buf).await.unwrap();
+                sent.send(()).unwrap();
+            });
+            let mut connection = TcpConnector.call(uri).await.unwrap();
+            connection.write_all(b"ping").await.unwrap();
+            let mut buf = [0; 4];
+            connection.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"ping");
+            received.await.unwrap();
+        });
+    }
+}
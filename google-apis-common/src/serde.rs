@@ -1,6 +1,7 @@
 pub mod duration {
     use serde::{Deserialize, Deserializer};
     use serde_with::{DeserializeAs, SerializeAs};
+    use std::borrow::Cow;
     use std::fmt::Formatter;
     use std::str::FromStr;
 
@@ -137,20 +138,95 @@ pub mod duration {
         where
             D: Deserializer<'de>,
         {
-            let s = Deserialize::deserialize(deserializer)?;
-            duration_from_str(s).map_err(serde::de::Error::custom)
+            let s: Cow<str> = Deserialize::deserialize(deserializer)?;
+            duration_from_str(&s).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// Base64 encoding and decoding of binary fields, without materializing more copies of their
+/// contents than needed.
+///
+/// Values are encoded directly into the output of the serializer, instead of into a string
+/// first. Strings owned by the deserializer, like those of a `serde_json::Value`, are decoded in
+/// place, so their memory holds the decoded contents afterwards.
+mod base64_codec {
+    use serde::de::{Error, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    /// The amount of characters decoded at once when decoding in place.
+    const CHUNK_LEN: usize = 1024;
+
+    pub fn serialize<S>(bytes: &[u8], config: base64::Config, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.collect_str(&base64::display::Base64Display::with_config(bytes, config))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D, config: base64::Config) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_string(Base64Visitor(config))
+    }
+
+    struct Base64Visitor(base64::Config);
+
+    impl<'de> Visitor<'de> for Base64Visitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a base64 encoded string")
+        }
+
+        fn visit_str<E: Error>(self, v: &str) -> Result<Vec<u8>, E> {
+            base64::decode_config(v, self.0).map_err(E::custom)
+        }
+
+        fn visit_string<E: Error>(self, v: String) -> Result<Vec<u8>, E> {
+            decode_in_place(v.into_bytes(), self.0).map_err(E::custom)
         }
     }
+
+    /// Decode the base64 characters in `buf`, writing the decoded bytes to its beginning. They
+    /// never overtake the characters still to be decoded, as 4 characters decode to 3 bytes.
+    pub fn decode_in_place(
+        mut buf: Vec<u8>,
+        config: base64::Config,
+    ) -> Result<Vec<u8>, base64::DecodeError> {
+        let mut decoded = [0; CHUNK_LEN / 4 * 3];
+        let mut len = 0;
+        for start in (0..buf.len()).step_by(CHUNK_LEN) {
+            let end = buf.len().min(start + CHUNK_LEN);
+            let n = base64::decode_config_slice(&buf[start..end], config, &mut decoded).map_err(
+                |err| match err {
+                    base64::DecodeError::InvalidByte(offset, byte) => {
+                        base64::DecodeError::InvalidByte(start + offset, byte)
+                    }
+                    base64::DecodeError::InvalidLastSymbol(offset, byte) => {
+                        base64::DecodeError::InvalidLastSymbol(start + offset, byte)
+                    }
+                    err => err,
+                },
+            )?;
+            buf[len..len + n].copy_from_slice(&decoded[..n]);
+            len += n;
+        }
+        buf.truncate(len);
+        Ok(buf)
+    }
 }
 
 pub mod standard_base64 {
-    use serde::{Deserialize, Deserializer, Serializer};
+    use super::base64_codec;
+    use serde::{Deserializer, Serializer};
     use serde_with::{DeserializeAs, SerializeAs};
-    use std::borrow::Cow;
 
     pub struct Wrapper;
 
-    pub fn to_string(bytes: &Vec<u8>) -> String {
+    pub fn to_string(bytes: &[u8]) -> String {
         base64::encode_config(bytes, base64::STANDARD)
     }
 
@@ -159,7 +235,7 @@ pub mod standard_base64 {
         where
             S: Serializer,
         {
-            s.serialize_str(&to_string(value))
+            base64_codec::serialize(value, base64::STANDARD, s)
         }
     }
 
@@ -168,20 +244,39 @@ pub mod standard_base64 {
         where
             D: Deserializer<'de>,
         {
-            let s: Cow<str> = Deserialize::deserialize(deserializer)?;
-            base64::decode_config(s.as_ref(), base64::STANDARD).map_err(serde::de::Error::custom)
+            base64_codec::deserialize(deserializer, base64::STANDARD)
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    impl SerializeAs<bytes::Bytes> for Wrapper {
+        fn serialize_as<S>(value: &bytes::Bytes, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            base64_codec::serialize(value, base64::STANDARD, s)
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    impl<'de> DeserializeAs<'de, bytes::Bytes> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<bytes::Bytes, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            base64_codec::deserialize(deserializer, base64::STANDARD).map(bytes::Bytes::from)
         }
     }
 }
 
 pub mod urlsafe_base64 {
-    use serde::{Deserialize, Deserializer, Serializer};
+    use super::base64_codec;
+    use serde::{Deserializer, Serializer};
     use serde_with::{DeserializeAs, SerializeAs};
-    use std::borrow::Cow;
 
     pub struct Wrapper;
 
-    pub fn to_string(bytes: &Vec<u8>) -> String {
+    pub fn to_string(bytes: &[u8]) -> String {
         base64::encode_config(bytes, base64::URL_SAFE)
     }
 
@@ -190,7 +285,7 @@ pub mod urlsafe_base64 {
         where
             S: Serializer,
         {
-            s.serialize_str(&to_string(value))
+            base64_codec::serialize(value, base64::URL_SAFE, s)
         }
     }
 
@@ -199,8 +294,69 @@ pub mod urlsafe_base64 {
         where
             D: Deserializer<'de>,
         {
-            let s: Cow<str> = Deserialize::deserialize(deserializer)?;
-            base64::decode_config(s.as_ref(), base64::URL_SAFE).map_err(serde::de::Error::custom)
+            base64_codec::deserialize(deserializer, base64::URL_SAFE)
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    impl SerializeAs<bytes::Bytes> for Wrapper {
+        fn serialize_as<S>(value: &bytes::Bytes, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            base64_codec::serialize(value, base64::URL_SAFE, s)
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    impl<'de> DeserializeAs<'de, bytes::Bytes> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<bytes::Bytes, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            base64_codec::deserialize(deserializer, base64::URL_SAFE).map(bytes::Bytes::from)
+        }
+    }
+}
+
+/// Deserialization of fields which are documented to always be present in responses.
+///
+/// Values which are `null` fail to deserialize with an error naming the broken invariant, instead
+/// of the generic type mismatch reported by serde. Serialization is left to the inner type `U`.
+pub mod required {
+    use serde::{de::Error, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, Same, SerializeAs};
+    use std::marker::PhantomData;
+
+    pub struct Wrapper<U = Same>(PhantomData<U>);
+
+    impl<'de, T, U> DeserializeAs<'de, T> for Wrapper<U>
+    where
+        U: DeserializeAs<'de, T>,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match <Option<U> as DeserializeAs<'de, Option<T>>>::deserialize_as(deserializer)? {
+                Some(value) => Ok(value),
+                None => Err(D::Error::custom(
+                    "a field documented to be always present is null, \
+                     disable the 'strict-required' feature to accept such responses",
+                )),
+            }
+        }
+    }
+
+    impl<T, U> SerializeAs<T> for Wrapper<U>
+    where
+        U: SerializeAs<T>,
+    {
+        fn serialize_as<S>(value: &T, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            U::serialize_as(value, s)
         }
     }
 }
@@ -211,7 +367,7 @@ pub fn datetime_to_string(datetime: &chrono::DateTime<chrono::offset::Utc>) -> S
 
 #[cfg(test)]
 mod test {
-    use super::{duration, standard_base64, urlsafe_base64};
+    use super::{base64_codec, duration, required, standard_base64, urlsafe_base64};
     use serde::{Deserialize, Serialize};
     use serde_with::{serde_as, DisplayFromStr};
 
@@ -243,6 +399,38 @@ mod test {
         num: Option<i64>,
     }
 
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct RequiredWrapper {
+        #[serde_as(as = "required::Wrapper")]
+        name: String,
+        #[serde_as(as = "required::Wrapper<DisplayFromStr>")]
+        num: i64,
+    }
+
+    #[test]
+    fn test_required() {
+        let wrapper: RequiredWrapper =
+            serde_json::from_str(r#"{"name": "a", "num": "7"}"#).unwrap();
+        assert_eq!(
+            wrapper,
+            RequiredWrapper {
+                name: "a".to_string(),
+                num: 7
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&wrapper).unwrap(),
+            r#"{"name":"a","num":"7"}"#
+        );
+
+        let err = serde_json::from_str::<RequiredWrapper>(r#"{"name": null, "num": "7"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("documented to be always present"), "{}", err);
+        assert!(serde_json::from_str::<RequiredWrapper>(r#"{"name": "a"}"#).is_err());
+    }
+
     #[test]
     fn test_duration_de_success_cases() {
         let durations = [
@@ -340,6 +528,69 @@ mod test {
         assert_eq!(Some(b"hello world".as_slice()), wrapper.bytes.as_deref());
     }
 
+    #[test]
+    fn large_base64_values() {
+        let bytes: Vec<u8> = (0..10_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let wrapper = Base64StandardWrapper {
+            bytes: Some(bytes.clone()),
+        };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(
+            json,
+            format!(r#"{{"bytes":"{}"}}"#, standard_base64::to_string(&bytes))
+        );
+        // a Value owns its strings, which are decoded in place
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Base64StandardWrapper>(value).unwrap(),
+            wrapper
+        );
+        assert_eq!(
+            serde_json::from_reader::<_, Base64StandardWrapper>(json.as_bytes()).unwrap(),
+            wrapper
+        );
+
+        for len in [0, 1, 2, 3, 767, 768, 769, 5000] {
+            for config in [base64::STANDARD, base64::URL_SAFE_NO_PAD] {
+                let encoded = base64::encode_config(&bytes[..len], config);
+                assert_eq!(
+                    base64_codec::decode_in_place(encoded.into_bytes(), config).unwrap(),
+                    &bytes[..len],
+                    "{} bytes",
+                    len
+                );
+            }
+        }
+        let mut encoded = standard_base64::to_string(&bytes).into_bytes();
+        encoded[2000] = b'*';
+        assert_eq!(
+            base64_codec::decode_in_place(encoded, base64::STANDARD),
+            Err(base64::DecodeError::InvalidByte(2000, b'*'))
+        );
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_fields() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct BytesWrapper {
+            #[serde_as(as = "Option<urlsafe_base64::Wrapper>")]
+            bytes: Option<bytes::Bytes>,
+        }
+
+        let wrapper: BytesWrapper =
+            serde_json::from_str(r#"{"bytes": "aGVsbG8gd29ybGQ="}"#).unwrap();
+        assert_eq!(
+            wrapper.bytes,
+            Some(bytes::Bytes::from_static(b"hello world"))
+        );
+        assert_eq!(
+            serde_json::to_string(&wrapper).unwrap(),
+            r#"{"bytes":"aGVsbG8gd29ybGQ="}"#
+        );
+    }
+
     #[test]
     fn urlsafe_base64_de_failure_cases() {
         assert!(
@@ -416,4 +667,30 @@ mod test {
             serde_json::from_str("{}").unwrap()
         );
     }
+
+    #[test]
+    fn test_owned_strings() {
+        // values built as `serde_json::Value`, like the requests of the CLI, can't lend their strings
+        let wrapper: DurationWrapper =
+            serde_json::from_value(serde_json::json!({"duration": "1.5s"})).unwrap();
+        assert_eq!(wrapper.duration.unwrap().num_milliseconds(), 1500);
+        let wrapper: DurationWrapper =
+            serde_json::from_reader(r#"{"duration": "2s"}"#.as_bytes()).unwrap();
+        assert_eq!(wrapper.duration.unwrap().num_seconds(), 2);
+        let wrapper: I64Wrapper = serde_json::from_value(serde_json::json!({"num": "7"})).unwrap();
+        assert_eq!(wrapper.num, Some(7));
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    fn test_arbitrary_precision() {
+        let json = r#"{"float":0.10000000000000000001,"int":12345678901234567890123}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(value.to_string(), json);
+        let wrapper = I64Wrapper {
+            num: Some(i64::MIN),
+        };
+        let value = serde_json::to_value(&wrapper).unwrap();
+        assert_eq!(wrapper, serde_json::from_value(value).unwrap());
+    }
 }
@@ -0,0 +1,111 @@
+//! Sharing a connection pool and token cache between the hubs of many APIs.
+//!
+//! Each hub created with `new()` owns the client and authenticator it is given. Programs using
+//! dozens of APIs would either duplicate them, opening separate connections and obtaining separate
+//! tokens for each API, or have to clone them carefully. A [`GoogleClient`] holds them once, and
+//! the generated `from_shared()` constructors, like `Translate::from_shared(&google_client)`,
+//! create hubs using its connection pool, token cache and [`ServerClock`].
+use std::sync::Arc;
+
+use crate::auth::GetTokenOutput;
+use crate::{GetToken, ServerClock};
+
+/// The client, authenticator and server clock shared by the hubs created from it. Its clones
+/// share them as well.
+#[derive(Clone)]
+pub struct GoogleClient<S> {
+    client: hyper::Client<S, hyper::body::Body>,
+    auth: SharedToken,
+    clock: ServerClock,
+}
+
+impl<S> GoogleClient<S> {
+    /// Share `client` and the tokens obtained from `auth` between all hubs created from it.
+    pub fn new<A: 'static + GetToken>(
+        client: hyper::Client<S, hyper::body::Body>,
+        auth: A,
+    ) -> GoogleClient<S> {
+        GoogleClient {
+            client,
+            auth: SharedToken(Arc::new(auth)),
+            clock: ServerClock::default(),
+        }
+    }
+
+    /// Set the clock estimating the time of the server, which is shared by all hubs created from
+    /// this client afterwards.
+    pub fn server_clock(mut self, clock: ServerClock) -> GoogleClient<S> {
+        self.clock = clock;
+        self
+    }
+
+    /// The client whose connection pool is used by all hubs.
+    pub fn client(&self) -> &hyper::Client<S, hyper::body::Body> {
+        &self.client
+    }
+
+    /// An authenticator using the shared one, and thus its token cache.
+    pub fn auth(&self) -> Box<dyn GetToken> {
+        Box::new(self.auth.clone())
+    }
+
+    /// The clock shared by all hubs.
+    pub fn clock(&self) -> &ServerClock {
+        &self.clock
+    }
+}
+
+/// A [`GetToken`] implementation whose clones use the same authenticator, instead of a copy of it.
+#[derive(Clone)]
+struct SharedToken(Arc<dyn GetToken>);
+
+impl GetToken for SharedToken {
+    fn get_token<'a>(&'a self, scopes: &'a [&str]) -> GetTokenOutput<'a> {
+        self.0.get_token(scopes)
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        self.0.api_key()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Mutex;
+
+    // clones obtain their tokens independently of the original
+    #[derive(Default)]
+    struct CountingToken(Mutex<usize>);
+
+    impl Clone for CountingToken {
+        fn clone(&self) -> Self {
+            CountingToken(Mutex::new(*self.0.lock().unwrap()))
+        }
+    }
+
+    impl GetToken for CountingToken {
+        fn get_token<'a>(&'a self, _scopes: &'a [&str]) -> GetTokenOutput<'a> {
+            Box::pin(async move {
+                let mut count = self.0.lock().unwrap();
+                *count += 1;
+                Ok(Some(format!("token-{}", count)))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn hubs_share_the_authenticator() {
+        let client = hyper::Client::builder().build_http::<hyper::body::Body>();
+        let shared = GoogleClient::new(client, CountingToken::default());
+        let (first, second) = (shared.auth(), shared.clone().auth().clone());
+        assert_eq!(
+            first.get_token(&[]).await.unwrap().as_deref(),
+            Some("token-1")
+        );
+        assert_eq!(
+            second.get_token(&[]).await.unwrap().as_deref(),
+            Some("token-2")
+        );
+    }
+}
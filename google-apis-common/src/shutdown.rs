@@ -0,0 +1,118 @@
+//! Shutting down Hubs gracefully.
+//!
+//! Each Hub tracks its calls in flight with a [`CallTracker`], which is shared by its clones. Once
+//! it is shut down, e.g. during a rolling restart of a service, new calls fail immediately while
+//! those in flight, including resumable uploads, are waited for. The bodies of media downloads
+//! aren't tracked once the call returned them.
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+#[derive(Default)]
+struct State {
+    shut_down: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+/// Tracks the calls in flight of a Hub, to wait for them when shutting it down.
+#[derive(Clone, Default)]
+pub struct CallTracker {
+    state: Arc<State>,
+}
+
+impl CallTracker {
+    /// Register the start of a call, which is in flight until the returned guard is dropped.
+    ///
+    /// Fails if the tracker was shut down.
+    pub fn start(&self) -> io::Result<CallGuard> {
+        self.state.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = CallGuard {
+            state: self.state.clone(),
+        };
+        if self.is_shut_down() {
+            return Err(io::Error::other("The hub was shut down"));
+        }
+        Ok(guard)
+    }
+
+    /// Returns the number of calls in flight.
+    pub fn in_flight(&self) -> usize {
+        self.state.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Returns true if the tracker was shut down.
+    pub fn is_shut_down(&self) -> bool {
+        self.state.shut_down.load(Ordering::SeqCst)
+    }
+
+    /// Stop accepting calls, and wait for the calls in flight to complete for up to `timeout`.
+    ///
+    /// Returns true if all calls completed in time.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.state.shut_down.store(true, Ordering::SeqCst);
+        let drained = async {
+            loop {
+                // register for the notification before checking, to not miss it
+                let idle = self.state.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        };
+        crate::rt::timeout(timeout, drained).await.is_ok()
+    }
+}
+
+/// A call in flight, which completes when dropped.
+pub struct CallGuard {
+    state: Arc<State>,
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        if self.state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn shutdown_waits_for_calls_in_flight() {
+        let tracker = CallTracker::default();
+        let call = tracker.start().unwrap();
+        assert_eq!(tracker.in_flight(), 1);
+
+        let shutdown = tokio::spawn({
+            let tracker = tracker.clone();
+            async move { tracker.shutdown(Duration::from_secs(10)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(tracker.is_shut_down());
+        match tracker.start() {
+            Err(err) => assert_eq!(err.to_string(), "The hub was shut down"),
+            _ => panic!("calls must fail after the shutdown"),
+        }
+        assert!(!shutdown.is_finished());
+
+        drop(call);
+        assert!(shutdown.await.unwrap());
+        assert_eq!(tracker.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn shutdown_times_out() {
+        let tracker = CallTracker::default();
+        let _call = tracker.start().unwrap();
+        assert!(!tracker.shutdown(Duration::from_millis(10)).await);
+        assert!(CallTracker::default().shutdown(Duration::ZERO).await);
+    }
+}
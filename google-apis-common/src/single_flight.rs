@@ -0,0 +1,271 @@
+//! Coalescing identical concurrent GET requests into a single request.
+//!
+//! Bursts of identical reads, like many tasks resolving the same queue configuration at once, send
+//! one request each. A hub with a [`SingleFlight`], set using its `single_flight()` method, sends a
+//! GET request only if no identical one is in flight, and otherwise waits for the response to that
+//! one, which is shared by all requests it was coalesced with. Requests are identical if they have
+//! the same url and headers, like the `Authorization` header of the principal they are made on
+//! behalf of, the `Accept-Encoding` of calls asking for compressed responses, and the headers set
+//! by the caller. Only the time left until the deadline of a call, which it asks the server to
+//! respect, may differ.
+//!
+//! Responses of coalesced requests are buffered, which is why media downloads are never coalesced.
+//! If the request in flight fails or is cancelled, each waiting request is sent on its own.
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use http::header::HeaderMap;
+use http::{Method, StatusCode, Version};
+use hyper::body::{Body, Bytes};
+use ring::digest;
+use tokio::sync::oneshot;
+
+use crate::{Error, Result};
+
+type Waiters = Vec<oneshot::Sender<SharedResponse>>;
+
+/// The requests in flight, shared by its clones, e.g. to coalesce the requests of several hubs.
+#[derive(Clone, Default)]
+pub struct SingleFlight {
+    flights: Arc<Mutex<HashMap<FlightKey, Waiters>>>,
+}
+
+// the url and a hash of the headers of a request
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct FlightKey {
+    uri: String,
+    headers: Vec<u8>,
+}
+
+/// The header holding the time left until the deadline of a call, which differs between
+/// identical calls.
+const SERVER_TIMEOUT: &str = "x-server-timeout";
+
+#[derive(Clone)]
+struct SharedResponse {
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl SingleFlight {
+    /// No requests in flight.
+    pub fn new() -> SingleFlight {
+        Default::default()
+    }
+
+    /// The amount of distinct requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.flights.lock().unwrap().len()
+    }
+
+    /// Send `req` using `send` if it can't be coalesced with an identical request in flight, and
+    /// return the response to that request otherwise.
+    pub async fn send<F, Fut>(
+        &self,
+        req: hyper::Request<Body>,
+        send: F,
+    ) -> Result<hyper::Response<Body>>
+    where
+        F: FnOnce(hyper::Request<Body>) -> Fut,
+        Fut: Future<Output = Result<hyper::Response<Body>>>,
+    {
+        let key = match FlightKey::of(&req) {
+            Some(key) => key,
+            None => return send(req).await,
+        };
+        let waiter = {
+            let mut flights = self.flights.lock().unwrap();
+            match flights.get_mut(&key) {
+                Some(waiters) => {
+                    let (sender, receiver) = oneshot::channel();
+                    waiters.push(sender);
+                    Some(receiver)
+                }
+                None => {
+                    flights.insert(key.clone(), Vec::new());
+                    None
+                }
+            }
+        };
+        if let Some(waiter) = waiter {
+            return match waiter.await {
+                Ok(shared) => Ok(shared.into_response()),
+                // the request in flight failed or was cancelled
+                Err(_) => send(req).await,
+            };
+        }
+
+        // lands the flight if the request fails or this future is dropped, letting waiters go
+        let mut flight = Flight {
+            flights: &self.flights,
+            key: Some(key),
+        };
+        let (parts, body) = send(req).await?.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .map_err(Error::HttpError)?;
+        let shared = SharedResponse {
+            status: parts.status,
+            version: parts.version,
+            headers: parts.headers.clone(),
+            body: body.clone(),
+        };
+        for waiter in flight.land() {
+            waiter.send(shared.clone()).ok();
+        }
+        Ok(hyper::Response::from_parts(parts, Body::from(body)))
+    }
+}
+
+impl FlightKey {
+    // Returns the key of requests which may be coalesced, which are GET requests without media.
+    fn of(req: &hyper::Request<Body>) -> Option<FlightKey> {
+        let query = req.uri().query().unwrap_or_default();
+        if req.method() != Method::GET || query.split('&').any(|param| param == "alt=media") {
+            return None;
+        }
+        let mut names: Vec<_> = req
+            .headers()
+            .keys()
+            .filter(|name| name.as_str() != SERVER_TIMEOUT)
+            .collect();
+        names.sort_by_key(|name| name.as_str());
+        let mut headers = digest::Context::new(&digest::SHA256);
+        for name in names {
+            for value in req.headers().get_all(name) {
+                headers.update(name.as_str().as_bytes());
+                headers.update(b":");
+                headers.update(value.as_bytes());
+                headers.update(b"\n");
+            }
+        }
+        Some(FlightKey {
+            uri: req.uri().to_string(),
+            headers: headers.finish().as_ref().to_vec(),
+        })
+    }
+}
+
+impl SharedResponse {
+    fn into_response(self) -> hyper::Response<Body> {
+        let mut res = hyper::Response::new(Body::from(self.body));
+        *res.status_mut() = self.status;
+        *res.version_mut() = self.version;
+        *res.headers_mut() = self.headers;
+        res
+    }
+}
+
+// A request in flight, which is removed from the flights once it landed or was dropped.
+struct Flight<'a> {
+    flights: &'a Mutex<HashMap<FlightKey, Waiters>>,
+    key: Option<FlightKey>,
+}
+
+impl Flight<'_> {
+    // Remove the flight, returning the requests waiting for its response.
+    fn land(&mut self) -> Waiters {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Vec::new(),
+        };
+        self.flights
+            .lock()
+            .unwrap()
+            .remove(&key)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        // dropping the senders makes the waiters send their requests on their own
+        self.land();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::{ACCEPT_ENCODING, AUTHORIZATION};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn request(uri: &str, token: &str) -> hyper::Request<Body> {
+        hyper::Request::get(uri)
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(SERVER_TIMEOUT, token.len().to_string())
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    async fn body(res: Result<hyper::Response<Body>>) -> Bytes {
+        hyper::body::to_bytes(res.unwrap().into_body())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn identical_requests_are_coalesced() {
+        let flights = SingleFlight::new();
+        let sent = AtomicUsize::new(0);
+        let send = |req: hyper::Request<Body>| {
+            let n = sent.fetch_add(1, Ordering::SeqCst);
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                let body = format!("{} {}", n, req.uri());
+                Ok(hyper::Response::new(Body::from(body)))
+            }
+        };
+        let uri = "https://example.googleapis.com/v1/queues/q";
+        let mut later = request(uri, "a");
+        later
+            .headers_mut()
+            .insert(SERVER_TIMEOUT, "0.5".parse().unwrap());
+        let mut compressed = request(uri, "a");
+        compressed
+            .headers_mut()
+            .insert(ACCEPT_ENCODING, "gzip".parse().unwrap());
+        let (first, second, other_principal, other_encoding, media) = tokio::join!(
+            flights.send(request(uri, "a"), send),
+            flights.send(later, send),
+            flights.send(request(uri, "b"), send),
+            flights.send(compressed, send),
+            flights.send(request(&format!("{}?alt=media", uri), "a"), send),
+        );
+        assert_eq!(sent.load(Ordering::SeqCst), 4);
+        let first = body(first).await;
+        assert_eq!(first, body(second).await);
+        assert_ne!(first, body(other_principal).await);
+        assert_ne!(first, body(other_encoding).await);
+        assert!(body(media).await.ends_with(b"?alt=media"));
+        assert_eq!(flights.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn waiters_send_their_own_request_if_the_flight_fails() {
+        let flights = SingleFlight::new();
+        let sent = AtomicUsize::new(0);
+        let send = |_req: hyper::Request<Body>| {
+            let n = sent.fetch_add(1, Ordering::SeqCst);
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                match n {
+                    0 => Err(Error::Cancelled),
+                    _ => Ok(hyper::Response::new(Body::from("ok"))),
+                }
+            }
+        };
+        let uri = "https://example.googleapis.com/v1/queues/q";
+        let (first, second) = tokio::join!(
+            flights.send(request(uri, "a"), send),
+            flights.send(request(uri, "a"), send),
+        );
+        assert!(matches!(first, Err(Error::Cancelled)));
+        assert_eq!(body(second).await, "ok");
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,589 @@
+//! The errors returned by Google APIs, as described by [`google.rpc.Status`][status].
+//!
+//! [status]: https://cloud.google.com/apis/design/errors
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::io;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use http::header::{HeaderMap, DATE, RETRY_AFTER};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use crate::Error;
+
+const ERROR_INFO_TYPE: &str = "type.googleapis.com/google.rpc.ErrorInfo";
+const QUOTA_FAILURE_TYPE: &str = "type.googleapis.com/google.rpc.QuotaFailure";
+const BAD_REQUEST_TYPE: &str = "type.googleapis.com/google.rpc.BadRequest";
+const RETRY_INFO_TYPE: &str = "type.googleapis.com/google.rpc.RetryInfo";
+
+/// An error reported by the server, parsed from the body of a failed request.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ApiError {
+    /// The HTTP status code, like `404`.
+    pub code: u16,
+    /// The canonical error code, like `NOT_FOUND`, derived from the `code` for APIs not providing it.
+    pub status: String,
+    /// A description of the error for developers.
+    pub message: String,
+    /// Additional, machine-readable information about the error.
+    pub details: Vec<ErrorDetail>,
+    /// The delay of the `Retry-After` header of the response, see [`ApiError::backoff_hint()`].
+    pub retry_after: Option<Duration>,
+}
+
+/// Additional information about an [`ApiError`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ErrorDetail {
+    ErrorInfo(ErrorInfo),
+    QuotaFailure(QuotaFailure),
+    BadRequest(BadRequest),
+    /// Any other detail, including its `@type` field.
+    Other(json::Value),
+}
+
+/// The reason of an error, see [`google.rpc.ErrorInfo`][info].
+///
+/// [info]: https://cloud.google.com/apis/design/errors#error_info
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorInfo {
+    /// A constant identifying the error within its domain, like `API_DISABLED`.
+    pub reason: String,
+    /// The service which generated the error, like `googleapis.com`.
+    pub domain: String,
+    pub metadata: HashMap<String, String>,
+}
+
+/// Describes how a quota check failed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaFailure {
+    pub violations: Vec<QuotaViolation>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaViolation {
+    /// The subject on which the quota check failed, like `project:my-project`.
+    pub subject: String,
+    pub description: String,
+}
+
+/// Describes violations in a client request.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BadRequest {
+    pub field_violations: Vec<FieldViolation>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldViolation {
+    /// The path to the invalid field of the request, like `instance.name`.
+    pub field: String,
+    pub description: String,
+}
+
+/// What the server told about retrying a call it rejected, e.g. with `429 Too Many Requests`, see
+/// [`Error::backoff_hint()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BackoffHint {
+    /// How long to wait before retrying, the longer of the delays of the `Retry-After` header of
+    /// the response and the `RetryInfo` detail of the error.
+    pub retry_after: Option<Duration>,
+    /// The quota checks which failed, from the `QuotaFailure` details of the error.
+    pub quota_violations: Vec<QuotaViolation>,
+}
+
+impl BackoffHint {
+    /// Parse the hint of a failed response from its headers and the error in its body, like
+    /// delegates are given them. Returns `None` if they tell neither.
+    pub fn from_response(headers: &HeaderMap, error: Option<&json::Value>) -> Option<BackoffHint> {
+        let mut err = error.and_then(ApiError::from_value).unwrap_or_default();
+        err.retry_after = retry_after(headers);
+        err.backoff_hint()
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: Status,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Status {
+    code: u16,
+    status: String,
+    message: String,
+    details: Vec<json::Value>,
+    /// Only sent in the v1 error format, which lacks the `status` and `details`.
+    errors: Vec<LegacyError>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct LegacyError {
+    domain: String,
+    reason: String,
+}
+
+/// Returns the canonical error code the server uses for the HTTP status `code`, see
+/// [`google.rpc.Code`][code].
+///
+/// [code]: https://github.com/googleapis/googleapis/blob/master/google/rpc/code.proto
+fn canonical_status(code: u16) -> &'static str {
+    match code {
+        400 => "INVALID_ARGUMENT",
+        401 => "UNAUTHENTICATED",
+        403 => "PERMISSION_DENIED",
+        404 => "NOT_FOUND",
+        409 => "ABORTED",
+        412 => "FAILED_PRECONDITION",
+        416 => "OUT_OF_RANGE",
+        429 => "RESOURCE_EXHAUSTED",
+        499 => "CANCELLED",
+        501 => "UNIMPLEMENTED",
+        503 => "UNAVAILABLE",
+        504 => "DEADLINE_EXCEEDED",
+        500..=599 => "INTERNAL",
+        _ => "UNKNOWN",
+    }
+}
+
+impl ErrorDetail {
+    fn from_value(value: json::Value) -> ErrorDetail {
+        fn parse<T: for<'de> Deserialize<'de>>(value: &json::Value) -> Option<T> {
+            json::from_value(value.clone()).ok()
+        }
+        let parsed = match value.get("@type").and_then(|t| t.as_str()) {
+            Some(ERROR_INFO_TYPE) => parse(&value).map(ErrorDetail::ErrorInfo),
+            Some(QUOTA_FAILURE_TYPE) => parse(&value).map(ErrorDetail::QuotaFailure),
+            Some(BAD_REQUEST_TYPE) => parse(&value).map(ErrorDetail::BadRequest),
+            _ => None,
+        };
+        parsed.unwrap_or(ErrorDetail::Other(value))
+    }
+}
+
+impl ApiError {
+    /// Parse the standard error response of Google APIs, `{"error": {"code": ..., ...}}`.
+    /// Returns `None` if `value` has a different structure.
+    ///
+    /// The calls of the hub request the v2 error format, but some APIs still respond using the
+    /// v1 format. Its errors are parsed alike: the `status` is derived from the `code`, and the
+    /// reason and domain of the first entry of `errors` become an [`ErrorInfo`].
+    pub fn from_value(value: &json::Value) -> Option<ApiError> {
+        let response: ErrorResponse = json::from_value(value.clone()).ok()?;
+        let status = response.error;
+        let mut err = ApiError {
+            code: status.code,
+            status: status.status,
+            message: status.message,
+            details: status
+                .details
+                .into_iter()
+                .map(ErrorDetail::from_value)
+                .collect(),
+            retry_after: None,
+        };
+        if err.status.is_empty() {
+            err.status = canonical_status(err.code).to_string();
+        }
+        if err.error_info().is_none() {
+            if let Some(legacy) = status.errors.into_iter().find(|e| !e.reason.is_empty()) {
+                err.details.push(ErrorDetail::ErrorInfo(ErrorInfo {
+                    reason: legacy.reason,
+                    domain: legacy.domain,
+                    metadata: HashMap::new(),
+                }));
+            }
+        }
+        Some(err)
+    }
+
+    /// Parse the error in the body of a failed response like [`ApiError::from_value()`], along
+    /// with the delay of its `Retry-After` header.
+    pub fn from_response(headers: &HeaderMap, value: &json::Value) -> Option<ApiError> {
+        let mut err = ApiError::from_value(value)?;
+        err.retry_after = retry_after(headers);
+        Some(err)
+    }
+
+    /// Returns the reason of the error, if the server provided one.
+    pub fn error_info(&self) -> Option<&ErrorInfo> {
+        self.details.iter().find_map(|d| match d {
+            ErrorDetail::ErrorInfo(info) => Some(info),
+            _ => None,
+        })
+    }
+
+    /// Returns all quota violations which caused the error.
+    pub fn quota_violations(&self) -> impl Iterator<Item = &QuotaViolation> {
+        self.details
+            .iter()
+            .filter_map(|d| match d {
+                ErrorDetail::QuotaFailure(failure) => Some(&failure.violations),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// Returns all invalid fields of the request which caused the error.
+    pub fn field_violations(&self) -> impl Iterator<Item = &FieldViolation> {
+        self.details
+            .iter()
+            .filter_map(|d| match d {
+                ErrorDetail::BadRequest(bad_request) => Some(&bad_request.field_violations),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// Returns how long the server asked to wait before retrying, from its `RetryInfo` detail.
+    pub fn retry_delay(&self) -> Option<Duration> {
+        self.details.iter().find_map(|d| match d {
+            ErrorDetail::Other(value) if value["@type"] == RETRY_INFO_TYPE => {
+                value["retryDelay"].as_str().and_then(parse_duration)
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns when to retry the call and which quotas it exhausted, if the server told either.
+    pub fn backoff_hint(&self) -> Option<BackoffHint> {
+        let hint = BackoffHint {
+            retry_after: self.retry_after.max(self.retry_delay()),
+            quota_violations: self.quota_violations().cloned().collect(),
+        };
+        (hint != BackoffHint::default()).then_some(hint)
+    }
+}
+
+impl Error {
+    /// Returns what the server told about retrying the call, if it was rejected by the server and
+    /// told when to retry it or which quotas it exhausted.
+    pub fn backoff_hint(&self) -> Option<BackoffHint> {
+        match self {
+            Error::Api(err) => err.backoff_hint(),
+            Error::Failure(response) => BackoffHint::from_response(response.headers(), None),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the delay of the `Retry-After` header, which is either in seconds or an HTTP date. The
+/// latter is relative to the `Date` header, if there is one, so the clocks needn't be in sync.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let date = |name| {
+        let value = headers.get(name)?.to_str().ok()?.trim();
+        Some((value, DateTime::parse_from_rfc2822(value).ok()))
+    };
+    match date(RETRY_AFTER)? {
+        (_, Some(retry_at)) => {
+            let now = date(DATE).and_then(|(_, date)| date);
+            let now = now.map_or_else(Utc::now, |now| now.with_timezone(&Utc));
+            let delay = retry_at.with_timezone(&Utc) - now;
+            Some(delay.to_std().unwrap_or_default())
+        }
+        (secs, None) => secs.parse().ok().map(Duration::from_secs),
+    }
+}
+
+/// Parses a duration in the JSON format of `google.protobuf.Duration`, like `"1.5s"`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let secs: f64 = value.strip_suffix('s')?.parse().ok()?;
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.status.is_empty() {
+            write!(f, "{}: {}", self.code, self.message)?;
+        } else {
+            write!(f, "{} {}: {}", self.code, self.status, self.message)?;
+        }
+        for violation in self.field_violations() {
+            write!(f, "\n  {}: {}", violation.field, violation.description)?;
+        }
+        for violation in self.quota_violations() {
+            write!(f, "\n  {}: {}", violation.subject, violation.description)?;
+        }
+        Ok(())
+    }
+}
+
+/// The status a service proxying a Google API should respond with when a call failed, see
+/// [`ErrorExt::to_http_status()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HttpStatus {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// How long the client should wait before retrying, to be sent as the `Retry-After` header.
+    pub retry_after: Option<Duration>,
+}
+
+impl HttpStatus {
+    fn new(status: StatusCode) -> HttpStatus {
+        HttpStatus {
+            status,
+            retry_after: None,
+        }
+    }
+
+    /// Returns the value of the `Retry-After` header, in whole seconds rounded up.
+    pub fn retry_after_header(&self) -> Option<http::HeaderValue> {
+        self.retry_after.map(|delay| {
+            let secs = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+            http::HeaderValue::from(secs)
+        })
+    }
+}
+
+/// Extensions of [`Error`] for services built on top of Google APIs.
+pub trait ErrorExt {
+    /// Returns the status to respond with when proxying a call which failed with this error.
+    ///
+    /// Client errors reported by the server are passed through, like `400`, `403`, `404` and
+    /// `429`, the latter along with the delay the server asked to wait before retrying. As they
+    /// concern the credentials of the proxy rather than those of its clients, `401` and `407`
+    /// become `502 Bad Gateway`, like server errors, failed connections and responses which
+    /// couldn't be decoded. Timeouts become `504 Gateway Timeout`, an upload which is too large
+    /// `413 Payload Too Large`, and all other errors, which are local to the proxy,
+    /// `500 Internal Server Error`.
+    fn to_http_status(&self) -> HttpStatus;
+}
+
+impl ErrorExt for Error {
+    fn to_http_status(&self) -> HttpStatus {
+        match self {
+            Error::Api(err) => upstream_status(err.code, self),
+            Error::Failure(response) => upstream_status(response.status().as_u16(), self),
+            Error::Io(err) if err.kind() == io::ErrorKind::TimedOut => {
+                HttpStatus::new(StatusCode::GATEWAY_TIMEOUT)
+            }
+            Error::HttpError(err) if err.is_timeout() => {
+                HttpStatus::new(StatusCode::GATEWAY_TIMEOUT)
+            }
+            Error::HttpError(_)
+            | Error::BadRequest(_)
+            | Error::MissingToken(_)
+            | Error::JsonDecodeError(..) => HttpStatus::new(StatusCode::BAD_GATEWAY),
+            Error::UploadSizeLimitExceeded(..) => HttpStatus::new(StatusCode::PAYLOAD_TOO_LARGE),
+            Error::MissingAPIKey
+            | Error::InsufficientScopes(_)
+            | Error::Cancelled
+            | Error::FieldClash(_)
+            | Error::InvalidHeader(_)
+            | Error::InvalidLabels(_)
+            | Error::Io(_)
+            | Error::Interceptor(_) => HttpStatus::new(StatusCode::INTERNAL_SERVER_ERROR),
+        }
+    }
+}
+
+/// Maps the status code returned by the server along with `err` to the one to respond with.
+fn upstream_status(code: u16, err: &Error) -> HttpStatus {
+    match StatusCode::from_u16(code) {
+        Ok(StatusCode::UNAUTHORIZED | StatusCode::PROXY_AUTHENTICATION_REQUIRED) => {
+            HttpStatus::new(StatusCode::BAD_GATEWAY)
+        }
+        Ok(StatusCode::TOO_MANY_REQUESTS) => HttpStatus {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            retry_after: err.backoff_hint().and_then(|hint| hint.retry_after),
+        },
+        Ok(status) if status.is_client_error() => HttpStatus::new(status),
+        _ => HttpStatus::new(StatusCode::BAD_GATEWAY),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_status_with_details() {
+        let value = json::json!({
+            "error": {
+                "code": 400,
+                "message": "Invalid instance name.",
+                "status": "INVALID_ARGUMENT",
+                "details": [
+                    {
+                        "@type": "type.googleapis.com/google.rpc.ErrorInfo",
+                        "reason": "INVALID_NAME",
+                        "domain": "datafusion.googleapis.com",
+                        "metadata": {"service": "datafusion.googleapis.com"}
+                    },
+                    {
+                        "@type": "type.googleapis.com/google.rpc.BadRequest",
+                        "fieldViolations": [{"field": "instance.name", "description": "Too long"}]
+                    },
+                    {
+                        "@type": "type.googleapis.com/google.rpc.QuotaFailure",
+                        "violations": [{"subject": "project:p", "description": "Limit reached"}]
+                    },
+                    {"@type": "type.googleapis.com/google.rpc.Help", "links": []}
+                ]
+            }
+        });
+        let err = ApiError::from_value(&value).unwrap();
+
+        assert_eq!(err.code, 400);
+        assert_eq!(err.status, "INVALID_ARGUMENT");
+        assert_eq!(err.error_info().unwrap().reason, "INVALID_NAME");
+        assert_eq!(
+            err.field_violations().next(),
+            Some(&FieldViolation {
+                field: "instance.name".into(),
+                description: "Too long".into()
+            })
+        );
+        assert_eq!(err.quota_violations().count(), 1);
+        assert!(matches!(err.details[3], ErrorDetail::Other(_)));
+        assert_eq!(
+            err.to_string(),
+            "400 INVALID_ARGUMENT: Invalid instance name.\n  instance.name: Too long\n  project:p: Limit reached"
+        );
+    }
+
+    #[test]
+    fn parse_legacy_and_unknown_errors() {
+        let legacy = json::json!({
+            "error": {
+                "code": 404,
+                "message": "File not found: abc.",
+                "errors": [{"domain": "global", "reason": "notFound", "message": "File not found: abc."}]
+            }
+        });
+        let err = ApiError::from_value(&legacy).unwrap();
+        assert_eq!(err.to_string(), "404 NOT_FOUND: File not found: abc.");
+        assert_eq!(
+            err.error_info(),
+            Some(&ErrorInfo {
+                reason: "notFound".into(),
+                domain: "global".into(),
+                metadata: HashMap::new(),
+            })
+        );
+
+        let err = ApiError::from_value(&json::json!({"error": {"code": 502, "message": "Bad"}}))
+            .unwrap();
+        assert_eq!(err.status, "INTERNAL");
+        assert!(err.details.is_empty());
+
+        assert_eq!(
+            ApiError::from_value(&json::json!({"message": "nope"})),
+            None
+        );
+        assert_eq!(ApiError::from_value(&json::json!({"error": "nope"})), None);
+    }
+
+    fn api_error(code: u16, details: Vec<ErrorDetail>) -> Error {
+        Error::Api(ApiError {
+            code,
+            details,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn errors_map_to_proxy_statuses() {
+        let status = |err: Error| err.to_http_status().status;
+        assert_eq!(status(api_error(400, vec![])), StatusCode::BAD_REQUEST);
+        assert_eq!(status(api_error(403, vec![])), StatusCode::FORBIDDEN);
+        assert_eq!(status(api_error(404, vec![])), StatusCode::NOT_FOUND);
+        assert_eq!(status(api_error(401, vec![])), StatusCode::BAD_GATEWAY);
+        assert_eq!(status(api_error(500, vec![])), StatusCode::BAD_GATEWAY);
+        assert_eq!(status(api_error(503, vec![])), StatusCode::BAD_GATEWAY);
+        assert_eq!(
+            status(Error::Io(io::Error::new(io::ErrorKind::TimedOut, "slow"))),
+            StatusCode::GATEWAY_TIMEOUT
+        );
+        assert_eq!(
+            status(Error::UploadSizeLimitExceeded(10, 5)),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        assert_eq!(
+            status(Error::BadRequest(json::json!({"error": "nope"}))),
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(
+            status(Error::MissingAPIKey),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn retry_after_is_propagated() {
+        let retry_info = ErrorDetail::Other(json::json!({
+            "@type": "type.googleapis.com/google.rpc.RetryInfo",
+            "retryDelay": "1.5s"
+        }));
+        let status = api_error(429, vec![retry_info.clone()]).to_http_status();
+        assert_eq!(status.status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(status.retry_after, Some(Duration::from_millis(1500)));
+        assert_eq!(status.retry_after_header().unwrap(), "2");
+        assert_eq!(
+            api_error(503, vec![retry_info])
+                .to_http_status()
+                .retry_after,
+            None
+        );
+
+        let response = hyper::Response::builder()
+            .status(429)
+            .header("Retry-After", "30")
+            .body(hyper::Body::empty())
+            .unwrap();
+        let status = Error::Failure(response).to_http_status();
+        assert_eq!(status.status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(status.retry_after, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn backoff_hints_are_parsed() {
+        let value = json::json!({
+            "error": {
+                "code": 429,
+                "status": "RESOURCE_EXHAUSTED",
+                "details": [
+                    {
+                        "@type": "type.googleapis.com/google.rpc.QuotaFailure",
+                        "violations": [{"subject": "project:p", "description": "Reads per minute"}]
+                    },
+                    {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "5s"}
+                ]
+            }
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "12".parse().unwrap());
+        let err = Error::Api(ApiError::from_response(&headers, &value).unwrap());
+        let hint = err.backoff_hint().unwrap();
+        assert_eq!(hint.retry_after, Some(Duration::from_secs(12)));
+        assert_eq!(hint.quota_violations[0].subject, "project:p");
+        assert_eq!(
+            err.to_http_status().retry_after,
+            Some(Duration::from_secs(12))
+        );
+
+        // dates are relative to the date of the response
+        headers.insert(
+            RETRY_AFTER,
+            "Sun, 06 Nov 1994 08:50:07 GMT".parse().unwrap(),
+        );
+        headers.insert(DATE, "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap());
+        let hint = BackoffHint::from_response(&headers, None).unwrap();
+        assert_eq!(hint.retry_after, Some(Duration::from_secs(30)));
+        headers.remove(DATE);
+        let hint = BackoffHint::from_response(&headers, None).unwrap();
+        assert_eq!(hint.retry_after, Some(Duration::ZERO));
+
+        assert_eq!(BackoffHint::from_response(&HeaderMap::new(), None), None);
+        assert_eq!(api_error(429, vec![]).backoff_hint(), None);
+        assert_eq!(Error::Cancelled.backoff_hint(), None);
+    }
+}
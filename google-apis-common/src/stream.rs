@@ -0,0 +1,398 @@
+//! Streaming the items of large list responses.
+//!
+//! Responses like the rows of a BigQuery table or the entries of Cloud Logging can be huge, and
+//! decoding them at once requires buffering the complete body. The `doit_streaming()` method of
+//! such calls instead returns [`JsonItems`], which decodes the items of the response one at a
+//! time while its body is received.
+//!
+//! Calls of methods which stream their responses, like the `listen` and `write` methods of
+//! Firestore, return [`JsonItems`] as well. Over REST, the server sends the messages of the
+//! stream as a JSON array, whose elements are received while the call is open.
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use hyper::body::HttpBody;
+use serde::de::DeserializeOwned;
+use serde_json as json;
+
+use crate::{ApiError, Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Start,
+    Key,
+    Colon { items: bool },
+    Value,
+    ItemsStart,
+    FirstItem,
+    Item,
+    AfterItem,
+    AfterMember,
+    Done,
+}
+
+enum Step<T> {
+    Item(T),
+    Incomplete,
+    Done,
+}
+
+/// A stream of the items of a list response, which are decoded one at a time while the body is
+/// received.
+///
+/// The items are taken from an array member of the JSON object sent as body, like `items`. All
+/// other members, like the `nextPageToken`, are available through [`JsonItems::rest()`] once the
+/// stream ended. For streamed responses, the items are the elements of the array sent as body,
+/// see [`JsonItems::array()`].
+pub struct JsonItems<T> {
+    head: http::response::Parts,
+    body: hyper::Body,
+    field: &'static str,
+    array: bool,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    state: State,
+    key: String,
+    rest: json::Map<String, json::Value>,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> JsonItems<T> {
+    /// Stream the items of the array member `field` of the body of `response`.
+    pub fn new(response: hyper::Response<hyper::Body>, field: &'static str) -> JsonItems<T> {
+        let (head, body) = response.into_parts();
+        JsonItems {
+            head,
+            body,
+            field,
+            array: false,
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+            state: State::Start,
+            key: String::new(),
+            rest: json::Map::new(),
+            _item: PhantomData,
+        }
+    }
+
+    /// Stream the messages of a streamed response, the elements of the array sent as body of
+    /// `response`.
+    ///
+    /// An element reporting an error, like `{"error": {"code": 503, ...}}`, which the server sends
+    /// if the stream fails after the response started, yields [`Error::Api`] and ends the stream.
+    pub fn array(response: hyper::Response<hyper::Body>) -> JsonItems<T> {
+        let mut items = JsonItems::new(response, "");
+        items.array = true;
+        items.state = State::ItemsStart;
+        items
+    }
+
+    /// Returns the next item, or `None` once all items were received.
+    ///
+    /// This allows consuming the stream without the combinators of the `futures` crate.
+    pub async fn next_item(&mut self) -> Option<Result<T>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<T> JsonItems<T> {
+    /// Returns the status and headers of the response.
+    pub fn head(&self) -> &http::response::Parts {
+        &self.head
+    }
+
+    /// Returns the members of the response besides its items, which are complete once the stream
+    /// ended.
+    pub fn rest(&self) -> &json::Map<String, json::Value> {
+        &self.rest
+    }
+
+    fn skip_whitespace(&mut self) -> Option<u8> {
+        while let Some(b' ' | b'\t' | b'\r' | b'\n') = self.buf.get(self.pos) {
+            self.pos += 1;
+        }
+        self.buf.get(self.pos).copied()
+    }
+
+    /// Parses the value at the current position, returning `None` if it is incomplete.
+    #[allow(clippy::result_large_err)]
+    fn parse<V: DeserializeOwned>(&mut self) -> Result<Option<V>> {
+        let rest = &self.buf[self.pos..];
+        let mut values = json::Deserializer::from_slice(rest).into_iter::<V>();
+        match values.next() {
+            Some(Ok(value)) => {
+                let end = values.byte_offset();
+                // a number at the end of the buffer may continue in the next chunk
+                if end == rest.len() && !self.eof {
+                    return Ok(None);
+                }
+                self.pos += end;
+                Ok(Some(value))
+            }
+            Some(Err(err)) if err.is_eof() => Ok(None),
+            Some(Err(err)) => Err(self.decode_error(err)),
+            None => Ok(None),
+        }
+    }
+
+    fn decode_error(&self, err: json::Error) -> Error {
+        let rest = String::from_utf8_lossy(&self.buf[self.pos..]).into_owned();
+        Error::JsonDecodeError(rest, err)
+    }
+
+    fn syntax_error(&self, expected: &str) -> Error {
+        self.decode_error(<json::Error as serde::de::Error>::custom(format!(
+            "expected {}",
+            expected
+        )))
+    }
+
+    fn advance(&mut self, state: State) {
+        self.pos += 1;
+        self.state = state;
+    }
+
+    /// Returns the state after the closing bracket of the items.
+    fn after_items(&self) -> State {
+        if self.array {
+            State::Done
+        } else {
+            State::AfterMember
+        }
+    }
+}
+
+impl<T: DeserializeOwned> JsonItems<T> {
+    /// Parses as much of the buffered body as possible, until an item was decoded.
+    #[allow(clippy::result_large_err)]
+    fn step(&mut self) -> Result<Step<T>> {
+        loop {
+            let next = self.skip_whitespace();
+            if next.is_none() && self.state != State::Done {
+                return Ok(Step::Incomplete);
+            }
+            match (self.state, next) {
+                (State::Done, _) => return Ok(Step::Done),
+                (State::Start, Some(b'{')) => self.advance(State::Key),
+                (State::Start, _) => return Err(self.syntax_error("an object")),
+                (State::Key, Some(b'}')) => self.advance(State::Done),
+                (State::Key, Some(b'"')) => match self.parse::<String>()? {
+                    Some(key) => {
+                        self.state = State::Colon {
+                            items: key == self.field,
+                        };
+                        self.key = key;
+                    }
+                    None => return Ok(Step::Incomplete),
+                },
+                (State::Key, _) => return Err(self.syntax_error("a member name")),
+                (State::Colon { items }, Some(b':')) => self.advance(if items {
+                    State::ItemsStart
+                } else {
+                    State::Value
+                }),
+                (State::Colon { .. }, _) => return Err(self.syntax_error("':'")),
+                (State::Value, _) => match self.parse::<json::Value>()? {
+                    Some(value) => {
+                        self.rest.insert(mem::take(&mut self.key), value);
+                        self.state = State::AfterMember;
+                    }
+                    None => return Ok(Step::Incomplete),
+                },
+                (State::ItemsStart, Some(b'[')) => self.advance(State::FirstItem),
+                (State::ItemsStart, _) if self.array => return Err(self.syntax_error("an array")),
+                // like `null`, which is kept among the other members
+                (State::ItemsStart, _) => self.state = State::Value,
+                (State::FirstItem, Some(b']')) => self.advance(self.after_items()),
+                (State::FirstItem, _) => self.state = State::Item,
+                (State::Item, _) if self.array => match self.parse::<json::Value>()? {
+                    Some(value) => {
+                        self.state = State::AfterItem;
+                        return self.message(value).map(Step::Item);
+                    }
+                    None => return Ok(Step::Incomplete),
+                },
+                (State::Item, _) => match self.parse::<T>()? {
+                    Some(item) => {
+                        self.state = State::AfterItem;
+                        return Ok(Step::Item(item));
+                    }
+                    None => return Ok(Step::Incomplete),
+                },
+                (State::AfterItem, Some(b',')) => self.advance(State::Item),
+                (State::AfterItem, Some(b']')) => self.advance(self.after_items()),
+                (State::AfterItem, _) => return Err(self.syntax_error("',' or ']'")),
+                (State::AfterMember, Some(b',')) => self.advance(State::Key),
+                (State::AfterMember, Some(b'}')) => self.advance(State::Done),
+                (State::AfterMember, _) => return Err(self.syntax_error("',' or '}'")),
+            }
+        }
+    }
+
+    /// Decodes a message of a streamed response, which may report that the stream failed.
+    #[allow(clippy::result_large_err)]
+    fn message(&self, value: json::Value) -> Result<T> {
+        if matches!(value.as_object(), Some(members) if members.len() == 1) {
+            if let Some(err) = ApiError::from_value(&value) {
+                return Err(Error::Api(err));
+            }
+        }
+        T::deserialize(&value).map_err(|err| Error::JsonDecodeError(value.to_string(), err))
+    }
+}
+
+impl<T> fmt::Debug for JsonItems<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonItems")
+            .field("head", &self.head)
+            .field("rest", &self.rest)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for JsonItems<T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        let this = self.get_mut();
+        loop {
+            let err = match this.step() {
+                Ok(Step::Item(item)) => return Poll::Ready(Some(Ok(item))),
+                Ok(Step::Done) => return Poll::Ready(None),
+                Ok(Step::Incomplete) if this.eof => Error::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "The response ended before its body was complete",
+                )),
+                Ok(Step::Incomplete) => match Pin::new(&mut this.body).poll_data(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Some(Ok(chunk))) => {
+                        this.buf.drain(..this.pos);
+                        this.pos = 0;
+                        this.buf.extend_from_slice(&chunk);
+                        continue;
+                    }
+                    Poll::Ready(Some(Err(err))) => Error::HttpError(err),
+                    Poll::Ready(None) => {
+                        this.eof = true;
+                        continue;
+                    }
+                },
+                Err(err) => err,
+            };
+            this.state = State::Done;
+            return Poll::Ready(Some(Err(err)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        id: u32,
+    }
+
+    /// Returns a response whose body is received in the given chunks.
+    fn response(chunks: &[&str]) -> hyper::Response<hyper::Body> {
+        let (mut sender, body) = hyper::Body::channel();
+        let chunks: Vec<String> = chunks.iter().map(|c| c.to_string()).collect();
+        tokio::spawn(async move {
+            for chunk in chunks {
+                sender.send_data(chunk.into()).await.unwrap();
+            }
+        });
+        hyper::Response::new(body)
+    }
+
+    /// Returns the items of a response whose body is received in the given chunks.
+    async fn stream(chunks: &[&str]) -> (Vec<Result<Row>>, json::Map<String, json::Value>) {
+        let mut items = JsonItems::<Row>::new(response(chunks), "rows");
+        let mut res = Vec::new();
+        while let Some(item) = items.next_item().await {
+            res.push(item);
+        }
+        (res, items.rest().clone())
+    }
+
+    #[tokio::test]
+    async fn items_are_streamed() {
+        let (items, rest) = stream(&[
+            r#"{"kind": "list", "rows": [{"id": 1"#,
+            r#"2}, {"id""#,
+            r#": 3} ], "pageToken": "ne"#,
+            r#"xt", "totalRows": 4"#,
+            "2}",
+        ])
+        .await;
+        let ids: Vec<u32> = items.into_iter().map(|item| item.unwrap().id).collect();
+        assert_eq!(ids, vec![12, 3]);
+        assert_eq!(rest.get("pageToken"), Some(&json::json!("next")));
+        assert_eq!(rest.get("totalRows"), Some(&json::json!(42)));
+        assert_eq!(rest.get("kind"), Some(&json::json!("list")));
+    }
+
+    #[tokio::test]
+    async fn responses_without_items() {
+        for body in [r#"{}"#, r#"{"rows": []}"#, r#"{"rows": null}"#] {
+            let (items, _) = stream(&[body]).await;
+            assert!(items.is_empty(), "{}", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_responses_fail() {
+        let (items, _) = stream(&[r#"{"rows": [{"id": 1}, {"id": "#]).await;
+        assert_eq!(items.len(), 2);
+        assert!(
+            matches!(items[1], Err(Error::Io(ref err)) if err.kind() == io::ErrorKind::UnexpectedEof)
+        );
+
+        let (items, _) = stream(&[r#"{"rows": [{"id": 1} {"id": 2}]}"#]).await;
+        assert!(matches!(items[1], Err(Error::JsonDecodeError(..))));
+
+        let (items, _) = stream(&[r#"{"rows": [{"name": "a"}]}"#]).await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+
+    async fn messages(chunks: &[&str]) -> Vec<Result<Row>> {
+        let mut items = JsonItems::<Row>::array(response(chunks));
+        let mut res = Vec::new();
+        while let Some(item) = items.next_item().await {
+            res.push(item);
+        }
+        res
+    }
+
+    #[tokio::test]
+    async fn messages_are_streamed() {
+        let items = messages(&["[{\"id\": 1}\n", ",{\"id\": 2", "}\n", "]"]).await;
+        let ids: Vec<u32> = items.into_iter().map(|item| item.unwrap().id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(messages(&["[]"]).await.is_empty());
+
+        let items = messages(&[r#"{"id": 1}"#]).await;
+        assert!(matches!(items[..], [Err(Error::JsonDecodeError(..))]));
+    }
+
+    #[tokio::test]
+    async fn failed_streams_yield_api_errors() {
+        let items = messages(&[
+            r#"[{"id": 1}, {"error": {"code": 503, "message": "The service is unavailable.", "#,
+            r#""status": "UNAVAILABLE"}}]"#,
+        ])
+        .await;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[1], Err(Error::Api(ref err)) if err.status == "UNAVAILABLE"));
+    }
+}
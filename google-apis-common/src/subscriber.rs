@@ -0,0 +1,531 @@
+//! Receiving the messages of a Pub/Sub subscription continuously, like the official client libraries.
+//!
+//! A [`Subscriber`] pulls messages of a [`Subscription`] in the background, and hands them out as
+//! a stream of [`Message`]s using [`Subscriber::messages()`], or to a handler using
+//! [`Subscriber::run()`]. While a message is handled, its lease is extended by modifying its ack
+//! deadline, up to [`SubscriberSettings::max_lease_extension`]. Acknowledgements are sent in
+//! batches, and messages which are dropped without being acknowledged are nacked, so they are
+//! redelivered right away.
+//!
+//! Flow control limits the amount of messages and bytes being handled at once: no messages are
+//! pulled while [`SubscriberSettings::max_outstanding_messages`] or
+//! [`SubscriberSettings::max_outstanding_bytes`] are reached.
+//!
+//! The Pub/Sub crate implements [`Subscription`] for its hub, whose `subscriptions_subscriber()`
+//! method creates a subscriber for a subscription.
+use std::collections::HashMap;
+use std::future::{poll_fn, Future};
+use std::io;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_core::Stream;
+use tokio::sync::{mpsc, Notify};
+
+use crate::{Error, Result};
+
+/// The interval at which acknowledgements are sent and leases are extended.
+pub const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The maximum amount of ack ids sent in a single request.
+pub const MAX_ACK_IDS: usize = 2500;
+
+const MIN_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The future of a call made by a [`Subscription`].
+pub type SubscriptionFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// A message pulled from a subscription, along with the id acknowledging it.
+#[derive(Clone, Debug)]
+pub struct Received<M> {
+    /// The id acknowledging the message.
+    pub ack_id: String,
+    /// The message.
+    pub message: M,
+    /// The number of times the message was delivered, if the subscription tracks it.
+    pub delivery_attempt: Option<i32>,
+    /// The size of the message, which counts towards the outstanding bytes.
+    pub size: usize,
+}
+
+/// The calls a [`Subscriber`] makes on a subscription.
+pub trait Subscription: Send + Sync + 'static {
+    /// The type of the messages.
+    type Message: Send + 'static;
+
+    /// Pull at most `max_messages` messages, waiting for messages to arrive if there are none.
+    fn pull(&self, max_messages: i32) -> SubscriptionFuture<'_, Vec<Received<Self::Message>>>;
+
+    /// Acknowledge the messages with the given ids.
+    fn acknowledge(&self, ack_ids: Vec<String>) -> SubscriptionFuture<'_, ()>;
+
+    /// Set the ack deadline of the messages with the given ids to `seconds` from now, where zero
+    /// makes them available for redelivery right away.
+    fn modify_ack_deadline(&self, ack_ids: Vec<String>, seconds: i32)
+        -> SubscriptionFuture<'_, ()>;
+}
+
+/// The settings of a [`Subscriber`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubscriberSettings {
+    /// The maximum amount of messages pulled at once. Defaults to 100.
+    pub max_messages: i32,
+    /// The maximum amount of messages handed out but not yet acknowledged or nacked. Defaults to
+    /// 1000.
+    pub max_outstanding_messages: usize,
+    /// The maximum size of messages handed out but not yet acknowledged or nacked. Defaults to
+    /// 100 MiB.
+    pub max_outstanding_bytes: usize,
+    /// The ack deadline outstanding messages are extended to, which should exceed the time their
+    /// handling takes. Defaults to 60 seconds, and is at most 600 seconds.
+    pub ack_deadline: Duration,
+    /// The time after which the leases of outstanding messages aren't extended anymore, so they
+    /// are redelivered if they are still not acknowledged. Defaults to 60 minutes.
+    pub max_lease_extension: Duration,
+}
+
+impl Default for SubscriberSettings {
+    fn default() -> SubscriberSettings {
+        SubscriberSettings {
+            max_messages: 100,
+            max_outstanding_messages: 1000,
+            max_outstanding_bytes: 100 << 20,
+            ack_deadline: Duration::from_secs(60),
+            max_lease_extension: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Receives the messages of a subscription, see the [module documentation](self).
+pub struct Subscriber<S> {
+    subscription: Arc<S>,
+    settings: SubscriberSettings,
+}
+
+impl<S: Subscription> Subscriber<S> {
+    /// A subscriber receiving the messages of `subscription`.
+    pub fn new(subscription: S, settings: SubscriberSettings) -> Subscriber<S> {
+        Subscriber {
+            subscription: Arc::new(subscription),
+            settings,
+        }
+    }
+
+    /// Start pulling messages, returning the stream of received messages. The stream yields an
+    /// error and ends if pulling failed with an error which isn't transient. Dropping it stops
+    /// pulling, and nacks the messages it didn't yield yet.
+    pub fn messages(self) -> Messages<S::Message> {
+        let leases = Arc::new(Leases::default());
+        let (sender, receiver) = mpsc::channel(self.settings.max_messages.max(1) as usize);
+        crate::rt::spawn(pull(
+            self.subscription.clone(),
+            self.settings,
+            leases.clone(),
+            sender,
+        ));
+        crate::rt::spawn(manage_leases(
+            self.subscription,
+            self.settings,
+            leases.clone(),
+        ));
+        Messages { receiver, leases }
+    }
+
+    /// Handle each received message with `handler`, which runs concurrently for as many messages as
+    /// flow control allows. It returns once pulling failed with an error which isn't transient.
+    pub async fn run<F, Fut>(self, mut handler: F) -> Result<()>
+    where
+        F: FnMut(Message<S::Message>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut messages = self.messages();
+        while let Some(message) = poll_fn(|cx| Pin::new(&mut messages).poll_next(cx)).await {
+            crate::rt::spawn(handler(message?));
+        }
+        Ok(())
+    }
+}
+
+/// The stream of messages returned by [`Subscriber::messages()`].
+pub struct Messages<M> {
+    receiver: mpsc::Receiver<Result<Message<M>>>,
+    leases: Arc<Leases>,
+}
+
+impl<M> Stream for Messages<M> {
+    type Item = Result<Message<M>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl<M> Drop for Messages<M> {
+    fn drop(&mut self) {
+        self.leases.state.lock().unwrap().closed = true;
+        self.leases.released.notify_one();
+    }
+}
+
+/// A received message, which is nacked when it is dropped without being acknowledged.
+pub struct Message<M> {
+    message: Option<M>,
+    ack_id: String,
+    delivery_attempt: Option<i32>,
+    leases: Arc<Leases>,
+}
+
+impl<M> Message<M> {
+    /// The id acknowledging the message.
+    pub fn ack_id(&self) -> &str {
+        &self.ack_id
+    }
+
+    /// The number of times the message was delivered, if the subscription tracks it.
+    pub fn delivery_attempt(&self) -> Option<i32> {
+        self.delivery_attempt
+    }
+
+    /// Acknowledge the message, returning it.
+    pub fn ack(mut self) -> M {
+        self.leases.settle(&self.ack_id, true);
+        self.message.take().expect("only taken when settled")
+    }
+
+    /// Make the message available for redelivery right away, returning it.
+    pub fn nack(mut self) -> M {
+        self.leases.settle(&self.ack_id, false);
+        self.message.take().expect("only taken when settled")
+    }
+}
+
+impl<M> Deref for Message<M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        self.message.as_ref().expect("only taken when settled")
+    }
+}
+
+impl<M> Drop for Message<M> {
+    fn drop(&mut self) {
+        if self.message.is_some() {
+            self.leases.settle(&self.ack_id, false);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Leases {
+    state: Mutex<LeaseState>,
+    // notified when messages are settled or the stream of messages is dropped
+    released: Notify,
+}
+
+#[derive(Default)]
+struct LeaseState {
+    leases: HashMap<String, Lease>,
+    bytes: usize,
+    acks: Vec<String>,
+    nacks: Vec<String>,
+    closed: bool,
+}
+
+struct Lease {
+    received: Instant,
+    // when the ack deadline was last extended, or `None` if it wasn't yet
+    extended: Option<Instant>,
+    size: usize,
+}
+
+impl Leases {
+    fn settle(&self, ack_id: &str, ack: bool) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(lease) = state.leases.remove(ack_id) {
+            state.bytes -= lease.size;
+            match ack {
+                true => state.acks.push(ack_id.to_string()),
+                false => state.nacks.push(ack_id.to_string()),
+            }
+        }
+        drop(state);
+        self.released.notify_one();
+    }
+
+    // Returns the amount of messages which may be pulled without exceeding the outstanding
+    // limits, or `None` if pulling should stop.
+    fn capacity(&self, settings: &SubscriberSettings) -> Option<usize> {
+        let state = self.state.lock().unwrap();
+        if state.closed {
+            return None;
+        }
+        if state.bytes >= settings.max_outstanding_bytes {
+            return Some(0);
+        }
+        Some(
+            settings
+                .max_outstanding_messages
+                .saturating_sub(state.leases.len()),
+        )
+    }
+}
+
+// Returns true if calls failing with `err` should be retried.
+fn is_transient(err: &Error) -> bool {
+    let is_transient_status = |status: u16| matches!(status, 429 | 500 | 502 | 503 | 504);
+    match err {
+        Error::HttpError(_) => true,
+        Error::Io(err) => err.kind() == io::ErrorKind::TimedOut,
+        Error::Api(err) => is_transient_status(err.code),
+        Error::Failure(res) => is_transient_status(res.status().as_u16()),
+        _ => false,
+    }
+}
+
+async fn pull<S: Subscription>(
+    subscription: Arc<S>,
+    settings: SubscriberSettings,
+    leases: Arc<Leases>,
+    sender: mpsc::Sender<Result<Message<S::Message>>>,
+) {
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        let capacity = match leases.capacity(&settings) {
+            Some(0) => {
+                leases.released.notified().await;
+                continue;
+            }
+            Some(capacity) => capacity,
+            None => return,
+        };
+        let max_messages = settings.max_messages.max(1).min(capacity as i32);
+        let received = match subscription.pull(max_messages).await {
+            Ok(received) => received,
+            Err(err) if is_transient(&err) => {
+                crate::rt::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                continue;
+            }
+            Err(err) => {
+                sender.send(Err(err)).await.ok();
+                return;
+            }
+        };
+        backoff = MIN_BACKOFF;
+        for received in received {
+            {
+                let mut state = leases.state.lock().unwrap();
+                state.bytes += received.size;
+                let lease = Lease {
+                    received: Instant::now(),
+                    extended: None,
+                    size: received.size,
+                };
+                state.leases.insert(received.ack_id.clone(), lease);
+            }
+            let message = Message {
+                message: Some(received.message),
+                ack_id: received.ack_id,
+                delivery_attempt: received.delivery_attempt,
+                leases: leases.clone(),
+            };
+            // the message is nacked when it is dropped along with the error
+            if sender.send(Ok(message)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+async fn manage_leases<S: Subscription>(
+    subscription: Arc<S>,
+    settings: SubscriberSettings,
+    leases: Arc<Leases>,
+) {
+    let ack_deadline = settings.ack_deadline.min(Duration::from_secs(600));
+    loop {
+        crate::rt::sleep(FLUSH_INTERVAL).await;
+        let now = Instant::now();
+        let (acks, nacks, extensions, done) = {
+            let mut state = leases.state.lock().unwrap();
+            let mut expired = Vec::new();
+            let mut extensions = Vec::new();
+            for (ack_id, lease) in state.leases.iter_mut() {
+                if now.duration_since(lease.received) >= settings.max_lease_extension {
+                    expired.push(ack_id.clone());
+                    continue;
+                }
+                let is_due = match lease.extended {
+                    Some(extended) => now.duration_since(extended) >= ack_deadline / 2,
+                    None => true,
+                };
+                if is_due {
+                    lease.extended = Some(now);
+                    extensions.push(ack_id.clone());
+                }
+            }
+            // expired messages are redelivered once their deadline passes
+            for ack_id in expired {
+                if let Some(lease) = state.leases.remove(&ack_id) {
+                    state.bytes -= lease.size;
+                }
+            }
+            let acks = std::mem::take(&mut state.acks);
+            let nacks = std::mem::take(&mut state.nacks);
+            (
+                acks,
+                nacks,
+                extensions,
+                state.closed && state.leases.is_empty(),
+            )
+        };
+        if !extensions.is_empty() || done {
+            leases.released.notify_one();
+        }
+
+        // failures are ignored, as the messages are redelivered then
+        for ack_ids in acks.chunks(MAX_ACK_IDS) {
+            subscription.acknowledge(ack_ids.to_vec()).await.ok();
+        }
+        for ack_ids in nacks.chunks(MAX_ACK_IDS) {
+            subscription
+                .modify_ack_deadline(ack_ids.to_vec(), 0)
+                .await
+                .ok();
+        }
+        for ack_ids in extensions.chunks(MAX_ACK_IDS) {
+            subscription
+                .modify_ack_deadline(ack_ids.to_vec(), ack_deadline.as_secs() as i32)
+                .await
+                .ok();
+        }
+        if done {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a subscription of numbered messages, recording the calls made on it
+    #[derive(Default)]
+    struct Numbers {
+        next: Mutex<u32>,
+        end: u32,
+        acks: Mutex<Vec<String>>,
+        deadlines: Mutex<Vec<(String, i32)>>,
+    }
+
+    impl Subscription for Numbers {
+        type Message = u32;
+
+        fn pull(&self, max_messages: i32) -> SubscriptionFuture<'_, Vec<Received<u32>>> {
+            Box::pin(async move {
+                let mut next = self.next.lock().unwrap();
+                let start = *next;
+                *next = (start + max_messages as u32).min(self.end);
+                if start == self.end {
+                    return Err(Error::Cancelled);
+                }
+                Ok((start..*next)
+                    .map(|n| Received {
+                        ack_id: n.to_string(),
+                        message: n,
+                        delivery_attempt: None,
+                        size: 1,
+                    })
+                    .collect())
+            })
+        }
+
+        fn acknowledge(&self, ack_ids: Vec<String>) -> SubscriptionFuture<'_, ()> {
+            self.acks.lock().unwrap().extend(ack_ids);
+            Box::pin(async { Ok(()) })
+        }
+
+        fn modify_ack_deadline(
+            &self,
+            ack_ids: Vec<String>,
+            seconds: i32,
+        ) -> SubscriptionFuture<'_, ()> {
+            let mut deadlines = self.deadlines.lock().unwrap();
+            deadlines.extend(ack_ids.into_iter().map(|ack_id| (ack_id, seconds)));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    async fn next<M>(messages: &mut Messages<M>) -> Option<Result<Message<M>>> {
+        poll_fn(|cx| Pin::new(&mut *messages).poll_next(cx)).await
+    }
+
+    #[tokio::test]
+    async fn messages_are_leased_until_they_are_settled() {
+        let numbers = Arc::new(Numbers {
+            end: 4,
+            ..Default::default()
+        });
+        let settings = SubscriberSettings {
+            max_outstanding_messages: 2,
+            ack_deadline: Duration::from_secs(30),
+            ..Default::default()
+        };
+        let subscriber = Subscriber {
+            subscription: numbers.clone(),
+            settings,
+        };
+        let mut messages = subscriber.messages();
+
+        let first = next(&mut messages).await.unwrap().unwrap();
+        let second = next(&mut messages).await.unwrap().unwrap();
+        assert_eq!((*first, second.ack_id()), (0, "1"));
+        tokio::time::sleep(FLUSH_INTERVAL * 3).await;
+        // flow control holds back further messages until outstanding ones are settled
+        assert_eq!(*numbers.next.lock().unwrap(), 2);
+        let mut extensions = numbers.deadlines.lock().unwrap().clone();
+        extensions.sort();
+        assert_eq!(extensions, [("0".to_string(), 30), ("1".to_string(), 30)]);
+
+        assert_eq!(first.ack(), 0);
+        drop(second);
+        let third = next(&mut messages).await.unwrap().unwrap();
+        assert_eq!(*third, 2);
+        third.ack();
+        let fourth = next(&mut messages).await.unwrap().unwrap();
+        assert!(matches!(
+            next(&mut messages).await,
+            Some(Err(Error::Cancelled))
+        ));
+        assert!(next(&mut messages).await.is_none());
+        drop(fourth);
+        drop(messages);
+
+        tokio::time::sleep(FLUSH_INTERVAL * 3).await;
+        assert_eq!(*numbers.acks.lock().unwrap(), ["0", "2"]);
+        let deadlines = numbers.deadlines.lock().unwrap();
+        assert!(deadlines.contains(&("1".to_string(), 0)));
+        assert!(deadlines.contains(&("3".to_string(), 0)));
+    }
+
+    #[test]
+    fn transient_errors_are_retried() {
+        let timeout = io::Error::new(io::ErrorKind::TimedOut, "timeout");
+        assert!(is_transient(&Error::Io(timeout)));
+        let unavailable = hyper::Response::builder()
+            .status(503)
+            .body(hyper::Body::empty())
+            .unwrap();
+        assert!(is_transient(&Error::Failure(unavailable)));
+        let not_found = hyper::Response::builder()
+            .status(404)
+            .body(hyper::Body::empty())
+            .unwrap();
+        assert!(!is_transient(&Error::Failure(not_found)));
+        assert!(!is_transient(&Error::Cancelled));
+    }
+}
@@ -0,0 +1,147 @@
+//! The shutdown of API versions announced by Google.
+//!
+//! API versions are shut down regularly once their successors are available. Crates of API
+//! versions whose shutdown was announced hold its date and how to migrate as their `SUNSET`, and
+//! their hubs warn about it once per process when they are created, from [`WARNING_DAYS`] days
+//! before the shutdown on. Operators thus learn about it from their own logs, rather than on the
+//! day calls start failing.
+//!
+//! The warning is a `tracing` event, which is only emitted with the `tracing` feature. Without
+//! it, [`Sunset::warning()`] returns the warning to report it otherwise. Setting
+//! [`SUNSET_WARNINGS_ENV`] to `0` turns it off.
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// The amount of days before a shutdown from which on hubs warn about it.
+pub const WARNING_DAYS: i64 = 180;
+
+/// The environment variable turning the warnings about shutdowns off if it is `0`.
+pub const SUNSET_WARNINGS_ENV: &str = "GOOGLE_API_SUNSET_WARNINGS";
+
+/// The announced shutdown of an API version.
+#[derive(Debug)]
+pub struct Sunset {
+    /// The name of the API, like `analyticsreporting`.
+    pub api: &'static str,
+    /// The version which is shut down, like `v4`.
+    pub version: &'static str,
+    /// The day of the shutdown, like `2024-07-01`.
+    pub date: &'static str,
+    /// How to migrate, like the API version to use instead.
+    pub migration: &'static str,
+    warned: AtomicBool,
+}
+
+impl Sunset {
+    /// The shutdown of `version` of `api` on `date`, formatted as `YYYY-MM-DD`.
+    pub const fn new(
+        api: &'static str,
+        version: &'static str,
+        date: &'static str,
+        migration: &'static str,
+    ) -> Sunset {
+        Sunset {
+            api,
+            version,
+            date,
+            migration,
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the day of the shutdown.
+    ///
+    /// # Panics
+    ///
+    /// If the date isn't formatted as `YYYY-MM-DD`.
+    pub fn day(&self) -> NaiveDate {
+        NaiveDate::parse_from_str(self.date, "%Y-%m-%d")
+            .unwrap_or_else(|_| panic!("{:?} is not a date like 2024-07-01", self.date))
+    }
+
+    /// Returns the amount of days left at `now` until the shutdown, which is negative once it passed.
+    pub fn days_left(&self, now: DateTime<Utc>) -> i64 {
+        (self.day() - now.date_naive()).num_days()
+    }
+
+    /// Returns the warning about the shutdown at `now`, if it is at most [`WARNING_DAYS`] away or
+    /// passed already.
+    pub fn warning(&self, now: DateTime<Utc>) -> Option<String> {
+        let days_left = self.days_left(now);
+        let when = match days_left {
+            days if days > WARNING_DAYS => return None,
+            days if days > 0 => format!("will be shut down on {}, in {} days", self.date, days),
+            0 => format!("is shut down today, on {}", self.date),
+            _ => format!("was shut down on {}", self.date),
+        };
+        Some(format!(
+            "the {} {} API {}. {}",
+            self.api, self.version, when, self.migration
+        ))
+    }
+
+    /// Emit the warning about the shutdown at `now` as a `tracing` event, unless it was emitted by
+    /// this process already or warnings are turned off using [`SUNSET_WARNINGS_ENV`]. Nothing is
+    /// emitted without the `tracing` feature.
+    pub fn warn_once(&self, now: DateTime<Utc>) {
+        if env::var(SUNSET_WARNINGS_ENV).ok().as_deref().map(str::trim) == Some("0") {
+            return;
+        }
+        let warning = match self.warning(now) {
+            Some(warning) => warning,
+            None => return,
+        };
+        if self.warned.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            api = self.api,
+            version = self.version,
+            sunset = self.date,
+            "{}",
+            warning
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = warning;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn warnings_start_before_the_shutdown() {
+        let sunset = Sunset::new(
+            "analyticsreporting",
+            "v4",
+            "2024-07-01",
+            "Use analyticsdata.",
+        );
+        let at = |y, m, d| Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap();
+
+        assert_eq!(sunset.days_left(at(2024, 6, 1)), 30);
+        assert_eq!(sunset.warning(at(2023, 12, 1)), None);
+        assert_eq!(
+            sunset.warning(at(2024, 6, 1)).unwrap(),
+            "the analyticsreporting v4 API will be shut down on 2024-07-01, in 30 days. Use analyticsdata."
+        );
+        assert_eq!(
+            sunset.warning(at(2024, 7, 1)).unwrap(),
+            "the analyticsreporting v4 API is shut down today, on 2024-07-01. Use analyticsdata."
+        );
+        assert_eq!(
+            sunset.warning(at(2024, 8, 1)).unwrap(),
+            "the analyticsreporting v4 API was shut down on 2024-07-01. Use analyticsdata."
+        );
+
+        sunset.warn_once(at(2023, 12, 1));
+        assert!(!sunset.warned.load(Ordering::Relaxed));
+        sunset.warn_once(at(2024, 6, 1));
+        assert!(sunset.warned.load(Ordering::Relaxed));
+    }
+}
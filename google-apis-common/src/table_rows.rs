@@ -0,0 +1,223 @@
+//! Decoding of the rows of BigQuery tables and query results into your own types.
+//!
+//! The API returns each row as `{"f": [{"v": ...}, ...]}`, holding the values of its cells in the
+//! order of the fields of the table's schema, as strings whichever their type. A [`RowDecoder`]
+//! turns a row into a JSON object keyed by the names of the fields instead, converting the values
+//! according to the types of the fields, and deserializes it into a type using serde:
+//!
+//! * `INTEGER` and `FLOAT` values become numbers, except for `NaN` and infinite floats, which
+//!   remain strings, and `BOOLEAN` values become booleans.
+//! * `TIMESTAMP` values become RFC 3339 strings, which deserialize into `chrono::DateTime<Utc>`.
+//! * `RECORD` values become objects, `REPEATED` fields arrays, and `JSON` values are parsed.
+//! * All other values remain strings, including `NUMERIC` and `BIGNUMERIC` values, which would
+//!   lose precision as numbers, and `BYTES` values, which are encoded with base64.
+use std::fmt::Display;
+
+use serde::de::{DeserializeOwned, Error as _};
+use serde_json as json;
+
+/// Decodes the rows of a table with a given schema.
+#[derive(Clone, Debug)]
+pub struct RowDecoder {
+    fields: Vec<Field>,
+}
+
+/// A field of a schema, and the fields of its records.
+#[derive(Clone, Debug)]
+struct Field {
+    name: String,
+    kind: String,
+    repeated: bool,
+    fields: Vec<Field>,
+}
+
+impl RowDecoder {
+    /// The decoder of the rows of a table whose schema is `schema`, like the `schema` of query
+    /// results, as JSON: `{"fields": [{"name": ..., "type": ..., "mode": ..., "fields": [...]}]}`.
+    pub fn new(schema: &json::Value) -> json::Result<RowDecoder> {
+        Ok(RowDecoder {
+            fields: fields(schema)?,
+        })
+    }
+
+    /// Returns `row`, which is `{"f": [{"v": ...}, ...]}`, as an object of the values of its cells
+    /// keyed by the names of their fields.
+    pub fn to_object(&self, row: &json::Value) -> json::Result<json::Value> {
+        record(&self.fields, row)
+    }
+
+    /// Returns `row` deserialized into `T`, whose members are named like the fields of the schema.
+    pub fn decode<T: DeserializeOwned>(&self, row: &json::Value) -> json::Result<T> {
+        json::from_value(self.to_object(row)?)
+    }
+}
+
+fn fields(schema: &json::Value) -> json::Result<Vec<Field>> {
+    let items = match schema.get("fields") {
+        None | Some(json::Value::Null) => return Ok(Vec::new()),
+        Some(json::Value::Array(items)) => items,
+        Some(_) => return Err(custom("the fields of the schema aren't an array")),
+    };
+    items
+        .iter()
+        .map(|field| {
+            let text = |key| field.get(key).and_then(json::Value::as_str);
+            Ok(Field {
+                name: text("name")
+                    .ok_or_else(|| custom("a field of the schema has no name"))?
+                    .to_string(),
+                kind: text("type").unwrap_or("STRING").to_ascii_uppercase(),
+                repeated: text("mode").is_some_and(|mode| mode.eq_ignore_ascii_case("REPEATED")),
+                fields: fields(field)?,
+            })
+        })
+        .collect()
+}
+
+fn record(fields: &[Field], row: &json::Value) -> json::Result<json::Value> {
+    let cells = match row.get("f") {
+        Some(json::Value::Array(cells)) => cells.as_slice(),
+        _ => &[],
+    };
+    if cells.len() != fields.len() {
+        return Err(custom(format!(
+            "the row has {} cells instead of the {} fields of the schema",
+            cells.len(),
+            fields.len()
+        )));
+    }
+    let mut object = json::Map::new();
+    for (field, cell) in fields.iter().zip(cells) {
+        let value = field
+            .value(cell.get("v").unwrap_or(&json::Value::Null))
+            .map_err(|err| custom(format!("{}: {}", field.name, err)))?;
+        object.insert(field.name.clone(), value);
+    }
+    Ok(json::Value::Object(object))
+}
+
+impl Field {
+    fn value(&self, value: &json::Value) -> json::Result<json::Value> {
+        match value {
+            json::Value::Array(items) if self.repeated => items
+                .iter()
+                .map(|item| self.scalar(item.get("v").unwrap_or(&json::Value::Null)))
+                .collect::<json::Result<_>>()
+                .map(json::Value::Array),
+            _ => self.scalar(value),
+        }
+    }
+
+    fn scalar(&self, value: &json::Value) -> json::Result<json::Value> {
+        let text = match value {
+            json::Value::String(text) => text,
+            json::Value::Object(_) if matches!(self.kind.as_str(), "RECORD" | "STRUCT") => {
+                return record(&self.fields, value)
+            }
+            _ => return Ok(value.clone()),
+        };
+        match self.kind.as_str() {
+            "INTEGER" | "INT64" => text.parse::<i64>().map(json::Value::from).map_err(custom),
+            "FLOAT" | "FLOAT64" => {
+                let number = text.parse::<f64>().map_err(custom)?;
+                Ok(json::Number::from_f64(number)
+                    .map_or_else(|| json::Value::String(text.clone()), json::Value::Number))
+            }
+            "BOOLEAN" | "BOOL" => text.parse::<bool>().map(json::Value::Bool).map_err(custom),
+            "TIMESTAMP" => timestamp(text),
+            "JSON" => json::from_str(text),
+            _ => Ok(json::Value::String(text.clone())),
+        }
+    }
+}
+
+/// Returns the timestamp `text`, which is in seconds since the epoch, like `1.7E9`, as RFC 3339.
+fn timestamp(text: &str) -> json::Result<json::Value> {
+    let seconds = text.parse::<f64>().map_err(custom)?;
+    let micros = (seconds * 1e6).round() as i64;
+    let time = chrono::DateTime::from_timestamp_micros(micros)
+        .ok_or_else(|| custom(format!("{} is out of range", text)))?;
+    Ok(json::Value::String(
+        time.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+    ))
+}
+
+fn custom(message: impl Display) -> json::Error {
+    json::Error::custom(message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use chrono::{DateTime, Utc};
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Visit {
+        user: String,
+        count: i64,
+        score: Option<f64>,
+        active: bool,
+        at: DateTime<Utc>,
+        pages: Vec<Page>,
+        attributes: json::Value,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Page {
+        path: String,
+        seconds: i64,
+    }
+
+    #[test]
+    fn rows_are_decoded_by_the_types_of_their_fields() {
+        let schema = json::json!({"fields": [
+            {"name": "user", "type": "STRING", "mode": "REQUIRED"},
+            {"name": "count", "type": "INTEGER"},
+            {"name": "score", "type": "FLOAT"},
+            {"name": "active", "type": "BOOLEAN"},
+            {"name": "at", "type": "TIMESTAMP"},
+            {"name": "pages", "type": "RECORD", "mode": "REPEATED", "fields": [
+                {"name": "path", "type": "STRING"},
+                {"name": "seconds", "type": "INT64"},
+            ]},
+            {"name": "attributes", "type": "JSON"},
+        ]});
+        let row = json::json!({"f": [
+            {"v": "ada"},
+            {"v": "9007199254740993"},
+            {"v": null},
+            {"v": "true"},
+            {"v": "1.7000000001234E9"},
+            {"v": [{"v": {"f": [{"v": "/"}, {"v": "12"}]}}]},
+            {"v": "{\"beta\":true}"},
+        ]});
+        let decoder = RowDecoder::new(&schema).unwrap();
+        let visit: Visit = decoder.decode(&row).unwrap();
+        assert_eq!(
+            visit,
+            Visit {
+                user: "ada".to_string(),
+                count: 9007199254740993,
+                score: None,
+                active: true,
+                at: "2023-11-14T22:13:20.1234Z".parse().unwrap(),
+                pages: vec![Page {
+                    path: "/".to_string(),
+                    seconds: 12
+                }],
+                attributes: json::json!({"beta": true}),
+            }
+        );
+
+        let err = decoder
+            .decode::<Visit>(&json::json!({"f": []}))
+            .unwrap_err();
+        assert!(err.to_string().contains("0 cells instead of the 7 fields"));
+        let mut row = row;
+        row["f"][1]["v"] = json::json!("many");
+        let err = decoder.decode::<Visit>(&row).unwrap_err();
+        assert!(err.to_string().starts_with("count: "));
+    }
+}
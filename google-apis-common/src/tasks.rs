@@ -0,0 +1,88 @@
+//! Deterministic ids of Cloud Tasks, to create each task at most once.
+//!
+//! Cloud Tasks rejects the creation of a task whose name is used by an existing or recently
+//! deleted task with `ALREADY_EXISTS`. Naming tasks by a hash of their payload thus de-duplicates
+//! them, e.g. when a request creating one is retried. The generated `Task::deduplicated()` methods
+//! name tasks using [`task_id()`], which follows the documented rules for task ids.
+#[cfg(feature = "client")]
+use ring::digest;
+#[cfg(feature = "client")]
+use serde::Serialize;
+#[cfg(feature = "client")]
+use serde_json as json;
+
+/// The maximum length of a task id.
+pub const TASK_ID_MAX_LEN: usize = 500;
+
+/// Derive the id of a task from `namespace`, which separates the tasks of different uses of the
+/// same payload, and the payload, like the `Task` itself with its name unset.
+///
+/// The id starts with the hex-encoded SHA-256 hash of both, so ids are well distributed as
+/// recommended for task names, followed by the namespace with all characters not allowed in ids
+/// replaced by `_`, for readability. Maps within the payload are hashed in the order of their keys,
+/// so equal payloads always have the same id. It's only available with the `client` feature.
+#[cfg(feature = "client")]
+pub fn task_id<T: Serialize>(namespace: &str, payload: &T) -> String {
+    // converting to a value first sorts the keys of maps
+    let payload = json::to_value(payload)
+        .and_then(|value| json::to_vec(&value))
+        .unwrap_or_default();
+    let mut context = digest::Context::new(&digest::SHA256);
+    context.update(&(namespace.len() as u64).to_be_bytes());
+    context.update(namespace.as_bytes());
+    context.update(&payload);
+    let mut id: String = context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    if !namespace.is_empty() {
+        id.push('-');
+        id.extend(
+            namespace
+                .chars()
+                .map(|c| if is_task_id_char(c) { c } else { '_' }),
+        );
+        id.truncate(TASK_ID_MAX_LEN);
+    }
+    id
+}
+
+/// Returns true if `id` may be used as the id of a task, i.e. as last segment of its name.
+pub fn is_valid_task_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= TASK_ID_MAX_LEN && id.chars().all(is_task_id_char)
+}
+
+fn is_task_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+#[cfg(all(test, feature = "client"))]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn ids_of_tasks() {
+        let payload = json!({"httpRequest": {"url": "https://example.com/work", "body": "e30="}});
+        let id = task_id("orders/émile", &payload);
+        assert!(is_valid_task_id(&id));
+        assert_eq!(id.len(), 64 + 1 + 12);
+        assert!(id.ends_with("-orders__mile"));
+        assert_eq!(id, task_id("orders/émile", &payload));
+        assert_ne!(&id[..64], &task_id("orders/emile", &payload)[..64]);
+        assert_ne!(&id[..64], &task_id("orders/émile", &json!({}))[..64]);
+        assert_eq!(task_id("", &payload).len(), 64);
+        assert_eq!(task_id(&"x".repeat(1000), &payload).len(), TASK_ID_MAX_LEN);
+
+        let headers: HashMap<_, _> = (0..16).map(|i| (i.to_string(), i)).collect();
+        let reordered: HashMap<_, _> = (0..16).rev().map(|i| (i.to_string(), i)).collect();
+        assert_eq!(task_id("n", &headers), task_id("n", &reordered));
+
+        assert!(!is_valid_task_id(""));
+        assert!(!is_valid_task_id("a/b"));
+        assert!(!is_valid_task_id(&"a".repeat(TASK_ID_MAX_LEN + 1)));
+    }
+}
@@ -0,0 +1,281 @@
+//! Bounding how long calls take.
+//!
+//! The *attempt timeout* bounds each request sent for a call, so a request which hangs fails and
+//! may be retried. The *deadline* bounds the call as a whole, including all of its retries and the
+//! delays between them, so retries never exceed the time budget of the caller. Like in gax-based
+//! clients, each attempt is further bounded by the time remaining until the deadline, and retries
+//! which couldn't start before the deadline aren't made.
+//!
+//! Both are unset by default. The chunks of resumable uploads aren't bounded by the attempt timeout.
+//!
+//! Services calling Google APIs while handling a request of their own can propagate the deadline
+//! of that request using [`with_deadline()`]: all calls made within are bounded by it, in
+//! addition to their own deadline. The time remaining for each attempt is sent to the server as
+//! the `X-Server-Timeout` header, so it stops working on requests whose caller gave up.
+//!
+//! ```rust
+//! # async fn handle(_: &str) {}
+//! use std::time::{Duration, Instant};
+//! use google_apis_common::timeout::with_deadline;
+//!
+//! # async fn serve() {
+//! // the deadline the client of this service sent along with its request
+//! let deadline = Instant::now() + Duration::from_secs(5);
+//! with_deadline(deadline, handle("request")).await;
+//! # }
+//! ```
+use std::future::Future;
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::{Error, Result};
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+/// Run `f`, bounding all calls it makes by `deadline`.
+///
+/// Nested scopes can only shorten the deadline, so a callee can't extend the budget of its caller.
+pub async fn with_deadline<F: Future>(deadline: Instant, f: F) -> F::Output {
+    let deadline = current_deadline().map_or(deadline, |outer| outer.min(deadline));
+    DEADLINE.scope(deadline, f).await
+}
+
+/// Returns the deadline set using [`with_deadline()`] for the current task, if any.
+pub fn current_deadline() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+/// The timeouts of a call, each of which is unbounded if unset.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// The maximum duration of each request sent for a call.
+    pub attempt: Option<Duration>,
+    /// The maximum duration of a call, including all retries.
+    pub deadline: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Returns these timeouts, with those which are unset taken from `defaults`.
+    pub fn or(self, defaults: Timeouts) -> Timeouts {
+        Timeouts {
+            attempt: self.attempt.or(defaults.attempt),
+            deadline: self.deadline.or(defaults.deadline),
+        }
+    }
+
+    /// Start timing a call, whose deadline is counted from now, and bounded by the deadline of
+    /// the current task, see [`with_deadline()`].
+    pub fn start(self) -> CallTimer {
+        self.start_at(Instant::now(), current_deadline())
+    }
+
+    fn start_at(self, now: Instant, scoped: Option<Instant>) -> CallTimer {
+        let deadline = match (self.deadline.map(|deadline| now + deadline), scoped) {
+            (Some(own), Some(scoped)) => Some(own.min(scoped)),
+            (own, scoped) => own.or(scoped),
+        };
+        CallTimer {
+            attempt: self.attempt,
+            deadline,
+        }
+    }
+}
+
+/// Times the attempts of a call against its [`Timeouts`].
+#[derive(Clone, Copy, Debug)]
+pub struct CallTimer {
+    attempt: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+impl CallTimer {
+    /// Returns the time remaining until the deadline, if there is one.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining_at(Instant::now())
+    }
+
+    fn remaining_at(&self, now: Instant) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// Returns the timeout of the next attempt, which is the attempt timeout bounded by the time
+    /// remaining until the deadline.
+    pub fn attempt_timeout(&self) -> Option<Duration> {
+        self.attempt_timeout_at(Instant::now())
+    }
+
+    fn attempt_timeout_at(&self, now: Instant) -> Option<Duration> {
+        match (self.attempt, self.remaining_at(now)) {
+            (Some(attempt), Some(remaining)) => Some(attempt.min(remaining)),
+            (attempt, remaining) => attempt.or(remaining),
+        }
+    }
+
+    /// Returns the value of the `X-Server-Timeout` header telling the server how long the next
+    /// attempt may take, in seconds with millisecond precision, if it is bounded.
+    pub fn server_timeout(&self) -> Option<http::HeaderValue> {
+        self.attempt_timeout().and_then(|timeout| {
+            http::HeaderValue::from_str(&format!("{:.3}", timeout.as_secs_f64())).ok()
+        })
+    }
+
+    /// Returns true if another attempt can be made after waiting for `delay`.
+    pub fn allows_retry_after(&self, delay: Duration) -> bool {
+        !matches!(self.remaining(), Some(remaining) if remaining <= delay)
+    }
+
+    /// Make an attempt, which fails with an [`io::ErrorKind::TimedOut`] error if it doesn't
+    /// complete within the timeout of the attempt.
+    pub async fn attempt<T>(&self, attempt: impl Future<Output = Result<T>>) -> Result<T> {
+        let timeout = match self.attempt_timeout() {
+            Some(timeout) => timeout,
+            None => return attempt.await,
+        };
+        if timeout.is_zero() {
+            return Err(self.timed_out(timeout));
+        }
+        match crate::rt::timeout(timeout, attempt).await {
+            Ok(res) => res,
+            Err(_) => Err(self.timed_out(timeout)),
+        }
+    }
+
+    fn timed_out(&self, timeout: Duration) -> Error {
+        let msg = if !matches!(self.attempt, Some(attempt) if attempt <= timeout) {
+            "The deadline of the call was exceeded".to_string()
+        } else {
+            format!("The attempt timed out after {:?}", timeout)
+        };
+        Error::Io(io::Error::new(io::ErrorKind::TimedOut, msg))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn attempts_are_bounded_by_deadline() {
+        let now = Instant::now();
+        let timer = Timeouts {
+            attempt: Some(secs(10)),
+            deadline: Some(secs(25)),
+        }
+        .start_at(now, None);
+        assert_eq!(timer.attempt_timeout_at(now), Some(secs(10)));
+        assert_eq!(timer.attempt_timeout_at(now + secs(20)), Some(secs(5)));
+        assert_eq!(timer.attempt_timeout_at(now + secs(30)), Some(secs(0)));
+        assert_eq!(timer.remaining_at(now + secs(5)), Some(secs(20)));
+
+        let timer = Timeouts {
+            attempt: None,
+            deadline: Some(secs(25)),
+        }
+        .start_at(now, None);
+        assert_eq!(timer.attempt_timeout_at(now + secs(5)), Some(secs(20)));
+        assert_eq!(Timeouts::default().start().attempt_timeout(), None);
+        assert!(Timeouts::default().start().allows_retry_after(secs(3600)));
+    }
+
+    #[test]
+    fn scoped_deadlines_bound_calls() {
+        let now = Instant::now();
+        let timeouts = Timeouts {
+            attempt: None,
+            deadline: Some(secs(10)),
+        };
+        let timer = timeouts.start_at(now, Some(now + secs(3)));
+        assert_eq!(timer.remaining_at(now), Some(secs(3)));
+        let timer = timeouts.start_at(now, Some(now + secs(30)));
+        assert_eq!(timer.remaining_at(now), Some(secs(10)));
+        let timer = Timeouts::default().start_at(now, Some(now + secs(30)));
+        assert_eq!(timer.remaining_at(now), Some(secs(30)));
+    }
+
+    #[tokio::test]
+    async fn deadlines_are_propagated_to_tasks() {
+        assert_eq!(current_deadline(), None);
+        let outer = Instant::now() + secs(5);
+        with_deadline(outer, async {
+            assert_eq!(current_deadline(), Some(outer));
+            with_deadline(outer + secs(60), async {
+                assert_eq!(current_deadline(), Some(outer));
+            })
+            .await;
+            with_deadline(outer - secs(1), async {
+                assert_eq!(current_deadline(), Some(outer - secs(1)));
+            })
+            .await;
+
+            let timer = Timeouts::default().start();
+            assert!(timer.remaining().unwrap() <= secs(5));
+            let header = timer.server_timeout().unwrap();
+            let value: f64 = header.to_str().unwrap().parse().unwrap();
+            assert!(value > 4.0 && value <= 5.0);
+        })
+        .await;
+        assert_eq!(Timeouts::default().start().server_timeout(), None);
+    }
+
+    #[test]
+    fn call_timeouts_override_defaults() {
+        let defaults = Timeouts {
+            attempt: Some(secs(10)),
+            deadline: Some(secs(60)),
+        };
+        let call = Timeouts {
+            attempt: None,
+            deadline: Some(secs(5)),
+        };
+        assert_eq!(
+            call.or(defaults),
+            Timeouts {
+                attempt: Some(secs(10)),
+                deadline: Some(secs(5)),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn attempts_time_out() {
+        let timer = Timeouts {
+            attempt: Some(Duration::from_millis(10)),
+            deadline: None,
+        }
+        .start();
+        let res = timer
+            .attempt(async {
+                tokio::time::sleep(secs(10)).await;
+                Ok(())
+            })
+            .await;
+        match res {
+            Err(Error::Io(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+                assert!(err.to_string().starts_with("The attempt timed out"));
+            }
+            _ => panic!("expected a timeout"),
+        }
+        assert_eq!(timer.attempt(async { Ok(42) }).await.unwrap(), 42);
+
+        let timer = Timeouts {
+            attempt: Some(secs(10)),
+            deadline: Some(Duration::ZERO),
+        }
+        .start();
+        assert!(!timer.allows_retry_after(Duration::ZERO));
+        match timer.attempt(async { Ok(()) }).await {
+            Err(Error::Io(err)) => {
+                assert_eq!(err.to_string(), "The deadline of the call was exceeded")
+            }
+            _ => panic!("expected the deadline to be exceeded"),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Tracing spans around calls, following the OpenTelemetry semantic conventions for HTTP clients.
+//!
+//! Each generated `doit()` creates a [`CallSpan`], which records the status of the last response,
+//! the number of retries and the latency of the call. With the `tracing` feature, it is a
+//! [`tracing`] span named like `GET v1/{+name}`, which `tracing-opentelemetry` exports as span of
+//! kind `client`, so calls appear in distributed traces without wrapping them. Without the feature,
+//! it doesn't do anything.
+use std::future::Future;
+use std::time::Instant;
+
+use hyper::StatusCode;
+
+#[cfg(feature = "tracing")]
+pub use tracing;
+
+/// The span of a single call, which may consist of several requests if it is retried. It ends
+/// when it is dropped.
+pub struct CallSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    start: Instant,
+    status: Option<StatusCode>,
+    resend_count: u32,
+}
+
+impl CallSpan {
+    /// Start the span of a call of the method with the given id, like
+    /// `translate.projects.detectLanguage`, to `url_template`, the path of the method relative to
+    /// the base url of `api`.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn new(api: &str, method_id: &str, http_method: &str, url_template: &str) -> CallSpan {
+        CallSpan {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "google_api_call",
+                "otel.name" = %format_args!("{} {}", http_method, url_template),
+                "otel.kind" = "client",
+                "otel.status_code" = tracing::field::Empty,
+                "rpc.system" = "google_api",
+                "rpc.service" = api,
+                "rpc.method" = method_id,
+                "http.request.method" = http_method,
+                "url.template" = url_template,
+                "server.address" = tracing::field::Empty,
+                "http.response.status_code" = tracing::field::Empty,
+                "http.request.resend_count" = tracing::field::Empty,
+                "error.type" = tracing::field::Empty,
+                "latency_ms" = tracing::field::Empty,
+            ),
+            start: Instant::now(),
+            status: None,
+            resend_count: 0,
+        }
+    }
+
+    /// Record the host the requests are sent to, taken from their url.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn url(&self, url: &str) {
+        #[cfg(feature = "tracing")]
+        if let Some(host) = url
+            .parse::<hyper::Uri>()
+            .ok()
+            .as_ref()
+            .and_then(|uri| uri.host())
+        {
+            self.span.record("server.address", host);
+        }
+    }
+
+    /// Record that the request is sent again, after the previous attempt failed.
+    pub fn resend(&mut self) {
+        self.resend_count += 1;
+    }
+
+    /// Record the status of a response, replacing that of the previous attempt.
+    pub fn response(&mut self, status: StatusCode) {
+        self.status = Some(status);
+        #[cfg(feature = "tracing")]
+        self.span
+            .record("http.response.status_code", status.as_u16());
+    }
+
+    /// Run `future`, like sending a request, within the span, so the events it emits belong to it.
+    pub fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(future, self.span.clone());
+        #[cfg(not(feature = "tracing"))]
+        future
+    }
+
+    // The status code of an unsuccessful last response, or `_OTHER` if there was none.
+    fn error_type(&self) -> Option<String> {
+        match self.status {
+            Some(status) if status.is_client_error() || status.is_server_error() => {
+                Some(status.as_u16().to_string())
+            }
+            Some(_) => None,
+            None => Some("_OTHER".to_string()),
+        }
+    }
+}
+
+impl Drop for CallSpan {
+    fn drop(&mut self) {
+        let latency = self.start.elapsed();
+        let error_type = self.error_type();
+        #[cfg(feature = "tracing")]
+        {
+            if self.resend_count > 0 {
+                self.span
+                    .record("http.request.resend_count", self.resend_count);
+            }
+            if let Some(error_type) = error_type {
+                self.span.record("error.type", error_type.as_str());
+                self.span.record("otel.status_code", "ERROR");
+            }
+            self.span
+                .record("latency_ms", latency.as_secs_f64() * 1000.0);
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = (latency, error_type);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn errors_of_calls() {
+        let mut span = CallSpan::new("translate", "translate.projects.get", "GET", "v3/{+name}");
+        span.url("https://translation.googleapis.com/v3/projects/p");
+        assert_eq!(span.error_type().as_deref(), Some("_OTHER"));
+        span.response(StatusCode::SERVICE_UNAVAILABLE);
+        span.resend();
+        assert_eq!(span.error_type().as_deref(), Some("503"));
+        span.response(StatusCode::OK);
+        assert_eq!(span.error_type(), None);
+        assert_eq!(span.resend_count, 1);
+    }
+}
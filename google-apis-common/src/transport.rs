@@ -0,0 +1,213 @@
+//! Spreading calls over several connections, for bulk workloads.
+//!
+//! By default, a Hub sends all its calls through its client, which multiplexes them over a single
+//! HTTP/2 connection per host. For thousands of concurrent calls, the number of streams the server
+//! allows per connection then becomes a bottleneck, and a slow response delays the frames of all
+//! others. A [`Transport`] set with `hub.transport(...)` instead spreads the calls over several
+//! clients with a connection each, picking the least loaded one for each call, and optionally caps
+//! the number of concurrent streams on each of them.
+//!
+//! The transport applies to the requests of calls, while resumable uploads and token requests
+//! keep using the client of the Hub.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use hyper::client::connect::Connect;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// The options of a [`Transport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransportOptions {
+    /// The number of clients calls are spread over, each of which has its own connection to a
+    /// host. Defaults to 1.
+    pub connections: usize,
+    /// The maximum number of concurrent calls on each connection. Calls beyond it wait for a call
+    /// on their connection to complete. If unset, only the limit advertised by the server applies.
+    pub max_concurrent_streams: Option<usize>,
+    /// Whether the HTTP/2 flow control windows adapt to the bandwidth of the connection, instead
+    /// of using the default window sizes. Defaults to false.
+    pub adaptive_window: bool,
+}
+
+impl Default for TransportOptions {
+    fn default() -> TransportOptions {
+        TransportOptions {
+            connections: 1,
+            max_concurrent_streams: None,
+            adaptive_window: false,
+        }
+    }
+}
+
+impl TransportOptions {
+    /// Returns a builder for clients configured according to these options.
+    pub fn client_builder(&self) -> hyper::client::Builder {
+        let mut builder = hyper::Client::builder();
+        builder.http2_adaptive_window(self.adaptive_window);
+        builder
+    }
+}
+
+struct Channel<S> {
+    client: hyper::Client<S, hyper::body::Body>,
+    streams: Option<Arc<Semaphore>>,
+}
+
+/// Spreads calls over several clients, see the [module documentation](self).
+///
+/// The default transport has no clients of its own, so all calls use the client of the Hub.
+pub struct Transport<S> {
+    channels: Arc<Vec<Channel<S>>>,
+    next: Arc<AtomicUsize>,
+}
+
+impl<S> Default for Transport<S> {
+    fn default() -> Transport<S> {
+        Transport {
+            channels: Arc::new(Vec::new()),
+            next: Default::default(),
+        }
+    }
+}
+
+impl<S> Clone for Transport<S> {
+    fn clone(&self) -> Transport<S> {
+        Transport {
+            channels: self.channels.clone(),
+            next: self.next.clone(),
+        }
+    }
+}
+
+impl<S> Transport<S>
+where
+    S: Connect + Clone + Send + Sync + 'static,
+{
+    /// Create a transport with clients connecting through `connector`.
+    pub fn new(connector: S, options: TransportOptions) -> Transport<S> {
+        let channels = (0..options.connections.max(1))
+            .map(|_| Channel {
+                client: options.client_builder().build(connector.clone()),
+                streams: options
+                    .max_concurrent_streams
+                    .map(|max| Arc::new(Semaphore::new(max.max(1)))),
+            })
+            .collect();
+        Transport {
+            channels: Arc::new(channels),
+            next: Default::default(),
+        }
+    }
+}
+
+impl<S> Transport<S> {
+    /// Returns the number of clients of this transport, which is 0 for the default transport.
+    pub fn connections(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Returns a stream to make a call on, waiting for one to become available if all connections
+    /// are at their maximum of concurrent streams. Without clients of its own, `fallback` is used.
+    pub async fn stream<'a>(
+        &'a self,
+        fallback: &'a hyper::Client<S, hyper::body::Body>,
+    ) -> Stream<'a, S> {
+        let channel = match self.pick() {
+            Some(channel) => channel,
+            None => {
+                return Stream {
+                    client: fallback,
+                    _permit: None,
+                }
+            }
+        };
+        let permit = match &channel.streams {
+            Some(streams) => streams.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        Stream {
+            client: &channel.client,
+            _permit: permit,
+        }
+    }
+
+    /// Returns the channel with the most available streams, starting the search at the channel
+    /// after the previously picked one to spread calls evenly.
+    fn pick(&self) -> Option<&Channel<S>> {
+        let count = self.channels.len();
+        if count == 0 {
+            return None;
+        }
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % count;
+        let available = |channel: &Channel<S>| {
+            channel
+                .streams
+                .as_ref()
+                .map_or(usize::MAX, |streams| streams.available_permits())
+        };
+        (0..count)
+            .map(|i| &self.channels[(start + i) % count])
+            .fold(None, |best: Option<&Channel<S>>, channel| match best {
+                Some(best) if available(best) >= available(channel) => Some(best),
+                _ => Some(channel),
+            })
+    }
+}
+
+/// A stream a call is made on, which is released when dropped.
+pub struct Stream<'a, S> {
+    client: &'a hyper::Client<S, hyper::body::Body>,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl<'a, S> Stream<'a, S> {
+    /// Returns the client to make the call with.
+    pub fn client(&self) -> &'a hyper::Client<S, hyper::body::Body> {
+        self.client
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::client::HttpConnector;
+
+    #[tokio::test]
+    async fn default_transport_uses_fallback() {
+        let fallback = hyper::Client::new();
+        let transport = Transport::<HttpConnector>::default();
+        assert_eq!(transport.connections(), 0);
+        let stream = transport.stream(&fallback).await;
+        assert!(std::ptr::eq(stream.client(), &fallback));
+    }
+
+    #[tokio::test]
+    async fn calls_go_to_least_loaded_connection() {
+        let fallback = hyper::Client::new();
+        let transport = Transport::new(
+            HttpConnector::new(),
+            TransportOptions {
+                connections: 2,
+                max_concurrent_streams: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(transport.connections(), 2);
+
+        let first = transport.stream(&fallback).await;
+        let second = transport.stream(&fallback).await;
+        assert!(!std::ptr::eq(first.client(), second.client()));
+        let third = transport.stream(&fallback).await;
+        let fourth = transport.stream(&fallback).await;
+        let streams = |channel: &Channel<HttpConnector>| {
+            channel.streams.as_ref().unwrap().available_permits()
+        };
+        assert!(transport.channels.iter().all(|c| streams(c) == 0));
+
+        drop(third);
+        let fifth = transport.stream(&fallback).await;
+        assert!(transport.channels.iter().all(|c| streams(c) == 0));
+        drop((first, second, fourth, fifth));
+        assert!(transport.channels.iter().all(|c| streams(c) == 2));
+    }
+}
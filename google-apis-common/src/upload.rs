@@ -0,0 +1,548 @@
+//! Resumable upload sessions which are fed with data as it is produced.
+//!
+//! The `doit()` method of call builders supporting resumable uploads needs a reader of known size.
+//! Their `upload_resumable_session()` method instead only starts the upload, returning a
+//! [`ResumableSession`] to which data can be appended over any period of time, without knowing the
+//! total size in advance. The upload completes once the session is finalized.
+//!
+//! The url of the session may be persisted along with its [offset](ResumableSession::offset), to
+//! [resume](ResumableSession::resume) the upload after the process was restarted.
+//!
+//! Media which is read from an [`AsyncRead`], like a download streamed from another server, is
+//! uploaded by `upload_resumable_stream()`, which feeds it to a session using
+//! [`ResumableSession::finalize_from()`], so that only one chunk is buffered at a time.
+//!
+//! The media sent by the `upload()` and `upload_resumable()` methods may be reported and throttled
+//! using the [`UploadOptions`] of the call, which sends it in small pieces for that purpose.
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::poll_fn;
+use std::io;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+use http::Uri;
+use hyper::body::Bytes;
+use hyper::client::connect::Connection;
+use hyper::header::{CONTENT_LENGTH, USER_AGENT};
+use hyper::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json as json;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tower_service::Service;
+
+use crate::{get_body_as_string, ApiError, Error};
+
+/// The granularity of all but the last chunk sent to the server.
+const CHUNK_GRANULARITY: u64 = 1 << 18;
+
+/// The size of the pieces in which media is sent if its progress is reported or its rate limited.
+const PIECE_SIZE: usize = 1 << 14;
+
+/// Media of at most this size is uploaded in a single request by `upload_resumable_stream()` if
+/// its size is known, and the method supports simple uploads.
+pub const SINGLE_REQUEST_LIMIT: u64 = 5 << 20;
+
+/// An upload session to which data is appended, until it is finalized.
+///
+/// Data is buffered until a full chunk can be sent, which keeps at most one chunk in memory.
+/// `T` is the resource returned by the server once the upload is complete.
+pub struct ResumableSession<S, T> {
+    client: hyper::Client<S, hyper::body::Body>,
+    url: String,
+    user_agent: String,
+    chunk_size: u64,
+    offset: u64,
+    buffer: Vec<u8>,
+    _response: PhantomData<fn() -> T>,
+}
+
+impl<S, T> ResumableSession<S, T>
+where
+    S: Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    T: DeserializeOwned,
+{
+    /// A session at the beginning of the upload with the given url, as returned in the `Location`
+    /// header when starting it.
+    pub fn new(
+        client: hyper::Client<S, hyper::body::Body>,
+        url: impl Into<String>,
+        user_agent: impl Into<String>,
+    ) -> Self {
+        ResumableSession {
+            client,
+            url: url.into(),
+            user_agent: user_agent.into(),
+            chunk_size: 1 << 23,
+            offset: 0,
+            buffer: Vec::new(),
+            _response: PhantomData,
+        }
+    }
+
+    /// Continue the upload with the given url at the offset persisted by the server.
+    /// Appending must continue with the data at the returned session's [`offset()`](Self::offset).
+    pub async fn resume(
+        client: hyper::Client<S, hyper::body::Body>,
+        url: impl Into<String>,
+        user_agent: impl Into<String>,
+    ) -> crate::Result<Self> {
+        let mut session = Self::new(client, url, user_agent);
+        session.offset = session.query_offset().await?;
+        Ok(session)
+    }
+
+    /// Set the amount of data sent to the server at once, which is rounded up to a multiple of
+    /// 256 KiB. It defaults to 8 MiB.
+    pub fn chunk_size(mut self, size: u64) -> Self {
+        self.chunk_size = size.max(1).div_ceil(CHUNK_GRANULARITY) * CHUNK_GRANULARITY;
+        self
+    }
+
+    /// The url of the session, which may be used to resume it later.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The amount of data persisted by the server.
+    ///
+    /// Data appended after it is buffered, and lost if the session is dropped.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Ask the server for the amount of data it persisted.
+    pub async fn query_offset(&mut self) -> crate::Result<u64> {
+        let res = self.put(content_range(0, 0, None), Vec::new()).await?;
+        self.expect_incomplete(res).await
+    }
+
+    /// Append `data` to the upload, sending all full chunks to the server.
+    ///
+    /// If this fails, the data which wasn't sent remains buffered, and is sent with the next call.
+    /// It fails if the server persists none of a chunk, instead of sending it again.
+    pub async fn append(&mut self, data: &[u8]) -> crate::Result<()> {
+        self.buffer.extend_from_slice(data);
+        while self.buffer.len() as u64 >= self.chunk_size {
+            let chunk = self.buffer[..self.chunk_size as usize].to_vec();
+            let range = content_range(self.offset, chunk.len() as u64, None);
+            let res = self.put(range, chunk).await?;
+            let previous_offset = self.offset;
+            let offset = self.expect_incomplete(res).await?;
+            self.consume_until(offset);
+            if self.offset == previous_offset {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "the server didn't persist any of the chunk",
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Send `data` along with all buffered data as the end of the upload, and return the resource
+    /// created by the server.
+    pub async fn finalize(
+        mut self,
+        data: &[u8],
+    ) -> crate::Result<(hyper::Response<hyper::body::Body>, T)> {
+        self.buffer.extend_from_slice(data);
+        loop {
+            let total = self.offset + self.buffer.len() as u64;
+            let range = content_range(self.offset, self.buffer.len() as u64, Some(total));
+            let mut res = self.put(range, self.buffer.clone()).await?;
+            if res.status() == StatusCode::PERMANENT_REDIRECT {
+                // the server didn't persist everything, send the remainder again
+                let previous_offset = self.offset;
+                let offset = self.expect_incomplete(res).await?;
+                self.consume_until(offset);
+                if self.offset > previous_offset {
+                    continue;
+                }
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "the server didn't accept the end of the upload",
+                )));
+            }
+            let res_body_string = get_body_as_string(res.body_mut()).await;
+            if !res.status().is_success() {
+                return Err(failure(res, res_body_string));
+            }
+            return match json::from_str(&res_body_string) {
+                Ok(decoded) => Ok((res, decoded)),
+                Err(err) => Err(Error::JsonDecodeError(res_body_string, err)),
+            };
+        }
+    }
+
+    /// Append all data read from `reader` and finalize the session once it ended, buffering one
+    /// chunk at a time, so that data of any size can be streamed from another source.
+    ///
+    /// If the `size` of the data is given, the session isn't finalized if `reader` has more or
+    /// less data.
+    pub async fn finalize_from<R>(
+        mut self,
+        mut reader: R,
+        size: Option<u64>,
+    ) -> crate::Result<(hyper::Response<hyper::body::Body>, T)>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut piece = vec![0; CHUNK_GRANULARITY as usize];
+        let mut read_total = 0;
+        loop {
+            let len = read(&mut reader, &mut piece).await.map_err(Error::Io)?;
+            if len == 0 {
+                break;
+            }
+            read_total += len as u64;
+            if size.is_some_and(|size| read_total > size) {
+                return Err(size_mismatch(size));
+            }
+            self.append(&piece[..len]).await?;
+        }
+        if size.is_some_and(|size| read_total != size) {
+            return Err(size_mismatch(size));
+        }
+        self.finalize(&[]).await
+    }
+
+    async fn put(
+        &self,
+        content_range: String,
+        body: Vec<u8>,
+    ) -> crate::Result<hyper::Response<hyper::body::Body>> {
+        let req = hyper::Request::builder()
+            .method(Method::PUT)
+            .uri(self.url.as_str())
+            .header(USER_AGENT, self.user_agent.as_str())
+            .header("Content-Range", content_range)
+            .header(CONTENT_LENGTH, body.len())
+            .body(hyper::body::Body::from(body))
+            .unwrap();
+        self.client.request(req).await.map_err(Error::HttpError)
+    }
+
+    /// Returns the offset of the response to an incomplete upload, or the error the server
+    /// responded with.
+    async fn expect_incomplete(
+        &self,
+        mut res: hyper::Response<hyper::body::Body>,
+    ) -> crate::Result<u64> {
+        // 308 = resume-incomplete == PermanentRedirect
+        if res.status() == StatusCode::PERMANENT_REDIRECT {
+            return Ok(res
+                .headers()
+                .get("Range")
+                .and_then(|h| h.to_str().ok())
+                .and_then(persisted_offset)
+                .unwrap_or(0));
+        }
+        let res_body_string = get_body_as_string(res.body_mut()).await;
+        Err(failure(res, res_body_string))
+    }
+
+    fn consume_until(&mut self, offset: u64) {
+        let persisted = offset
+            .saturating_sub(self.offset)
+            .min(self.buffer.len() as u64);
+        self.buffer.drain(..persisted as usize);
+        self.offset += persisted;
+    }
+}
+
+/// Called with the amount of bytes of media sent so far and its total size.
+type ProgressFn = dyn FnMut(u64, u64) + Send;
+
+/// How the media of an upload is sent, set using the `upload_progress()` and
+/// `upload_rate_limit()` methods of call builders.
+#[derive(Clone, Default)]
+pub struct UploadOptions {
+    progress: Option<Arc<Mutex<ProgressFn>>>,
+    rate_limit: Option<u64>,
+}
+
+impl fmt::Debug for UploadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadOptions")
+            .field("progress", &self.progress.is_some())
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+impl UploadOptions {
+    /// Call `progress` with the amount of bytes sent so far and the total size of the media, after
+    /// each piece of it was sent. Retried requests send their media again, from the start of the
+    /// media or of the retried chunk.
+    pub fn set_progress(&mut self, progress: impl FnMut(u64, u64) + Send + 'static) {
+        self.progress = Some(Arc::new(Mutex::new(progress)));
+    }
+
+    /// Send media at no more than `bytes_per_second`, or as fast as possible if it is 0.
+    pub fn set_rate_limit(&mut self, bytes_per_second: u64) {
+        self.rate_limit = Some(bytes_per_second).filter(|&limit| limit > 0);
+    }
+
+    /// Returns the body sending `bytes`, which are found at `offset` of media of `total` bytes.
+    pub fn body(&self, bytes: Vec<u8>, offset: u64, total: u64) -> hyper::Body {
+        if self.progress.is_none() && self.rate_limit.is_none() {
+            return hyper::Body::from(bytes);
+        }
+        let (mut sender, body) = hyper::Body::channel();
+        let options = self.clone();
+        crate::rt::spawn(async move {
+            let bytes = Bytes::from(bytes);
+            let started = Instant::now();
+            let mut sent = 0;
+            while sent < bytes.len() {
+                let piece = bytes.slice(sent..(sent + PIECE_SIZE).min(bytes.len()));
+                if let Some(limit) = options.rate_limit {
+                    let due = Duration::from_secs_f64(sent as f64 / limit as f64);
+                    if let Some(wait) = due.checked_sub(started.elapsed()) {
+                        crate::rt::sleep(wait).await;
+                    }
+                }
+                sent += piece.len();
+                // the request was abandoned
+                if sender.send_data(piece).await.is_err() {
+                    return;
+                }
+                if let Some(progress) = options.progress.as_ref() {
+                    if let Ok(mut progress) = progress.lock() {
+                        (*progress)(offset + sent as u64, total);
+                    }
+                }
+            }
+        });
+        body
+    }
+}
+
+/// Reads all data of `reader`, which is expected to be `size` bytes, like media uploaded in a
+/// single request.
+pub async fn read_all<R>(mut reader: R, size: u64) -> io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut data = Vec::with_capacity(size as usize);
+    let mut piece = vec![0; PIECE_SIZE];
+    loop {
+        match read(&mut reader, &mut piece).await? {
+            0 => return Ok(data),
+            len => data.extend_from_slice(&piece[..len]),
+        }
+    }
+}
+
+/// Reads from `reader` into `buf`, returning the amount of bytes read, or 0 at its end.
+async fn read<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize>
+where
+    R: AsyncRead + Unpin,
+{
+    poll_fn(|cx| {
+        let mut read_buf = ReadBuf::new(buf);
+        match Pin::new(&mut *reader).poll_read(cx, &mut read_buf) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(read_buf.filled().len())),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
+}
+
+fn size_mismatch(size: Option<u64>) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "the media to upload doesn't have the {} bytes given as its size",
+            size.unwrap_or_default()
+        ),
+    ))
+}
+
+pub(crate) fn failure(res: hyper::Response<hyper::body::Body>, body: String) -> Error {
+    match json::from_str::<json::Value>(&body) {
+        Ok(value) => match ApiError::from_response(res.headers(), &value) {
+            Some(api_error) => Error::Api(api_error),
+            None => Error::BadRequest(value),
+        },
+        Err(_) => {
+            let (parts, _) = res.into_parts();
+            Error::Failure(hyper::Response::from_parts(parts, body.into()))
+        }
+    }
+}
+
+/// The `Content-Range` header for `len` bytes at `first`, with `total` being unknown until the
+/// last chunk is sent.
+fn content_range(first: u64, len: u64, total: Option<u64>) -> String {
+    let total = total.map_or_else(|| "*".to_string(), |t| t.to_string());
+    if len == 0 {
+        format!("bytes */{}", total)
+    } else {
+        format!("bytes {}-{}/{}", first, first + len - 1, total)
+    }
+}
+
+/// Parses the `Range` header of an incomplete upload, like `bytes=0-1023`, into the amount of
+/// bytes persisted by the server.
+pub(crate) fn persisted_offset(range: &str) -> Option<u64> {
+    let range = range
+        .strip_prefix("bytes=")
+        .or_else(|| range.strip_prefix("bytes "))?;
+    let (_, last) = range.split_once('-')?;
+    last.trim().parse::<u64>().ok().map(|last| last + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn content_range_with_unknown_total() {
+        assert_eq!(content_range(0, 0, None), "bytes */*");
+        assert_eq!(content_range(0, 1 << 18, None), "bytes 0-262143/*");
+        assert_eq!(
+            content_range(1 << 18, 10, Some(262154)),
+            "bytes 262144-262153/262154"
+        );
+        assert_eq!(content_range(1 << 18, 0, Some(1 << 18)), "bytes */262144");
+    }
+
+    #[test]
+    fn persisted_offset_from_range() {
+        assert_eq!(persisted_offset("bytes=0-262143"), Some(262144));
+        assert_eq!(persisted_offset("bytes 0-9"), Some(10));
+        assert_eq!(persisted_offset("0-9"), None);
+        assert_eq!(persisted_offset("bytes=0-"), None);
+    }
+
+    #[tokio::test]
+    async fn bodies_report_progress_and_are_throttled() {
+        let mut options = UploadOptions::default();
+        let body = options.body(vec![1; 10], 0, 10);
+        assert_eq!(hyper::body::to_bytes(body).await.unwrap().len(), 10);
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        options.set_progress(move |sent, total| sink.lock().unwrap().push((sent, total)));
+        options.set_rate_limit(PIECE_SIZE as u64 * 10);
+        let media = vec![7; PIECE_SIZE * 2 + 1];
+        let started = Instant::now();
+        let body = options.body(media.clone(), 100, 100 + media.len() as u64);
+        assert_eq!(hyper::body::to_bytes(body).await.unwrap(), media);
+        // the last piece is due after two pieces were sent, at a tenth of a second each
+        assert!(started.elapsed() >= Duration::from_millis(190));
+
+        let total = 100 + media.len() as u64;
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                (100 + PIECE_SIZE as u64, total),
+                (100 + PIECE_SIZE as u64 * 2, total),
+                (total, total)
+            ]
+        );
+    }
+
+    /// Serves an upload session which persists all chunks, or none of them unless `persist` is
+    /// set, returning its url and the amount of bytes received.
+    async fn serve_session(persist: bool) -> (String, Arc<Mutex<usize>>) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/upload", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(0));
+        let counter = received.clone();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            loop {
+                let (mut length, mut range) = (0, String::new());
+                loop {
+                    let mut line = String::new();
+                    if stream.read_line(&mut line).await.unwrap() == 0 {
+                        return;
+                    }
+                    let line = line.trim_end().to_ascii_lowercase();
+                    if line.is_empty() {
+                        break;
+                    } else if let Some(value) = line.strip_prefix("content-length: ") {
+                        length = value.parse().unwrap();
+                    } else if let Some(value) = line.strip_prefix("content-range: ") {
+                        range = value.to_string();
+                    }
+                }
+                let mut body = vec![0; length];
+                stream.read_exact(&mut body).await.unwrap();
+                let total = {
+                    let mut total = counter.lock().unwrap();
+                    *total += length;
+                    *total
+                };
+                let res = if !persist {
+                    "HTTP/1.1 308 Resume Incomplete\r\ncontent-length: 0\r\n\r\n".to_string()
+                } else if range.ends_with("/*") {
+                    format!(
+                        "HTTP/1.1 308 Resume Incomplete\r\nRange: bytes=0-{}\r\ncontent-length: 0\r\n\r\n",
+                        total - 1
+                    )
+                } else {
+                    let body = format!("{{\"size\":{}}}", total);
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                stream.get_mut().write_all(res.as_bytes()).await.unwrap();
+            }
+        });
+        (url, received)
+    }
+
+    #[tokio::test]
+    async fn sessions_are_finalized_from_readers() {
+        let media = vec![3; CHUNK_GRANULARITY as usize + 1000];
+        let (url, received) = serve_session(true).await;
+        let session = ResumableSession::<_, json::Value>::new(hyper::Client::new(), url, "agent")
+            .chunk_size(CHUNK_GRANULARITY);
+        let (_, resource) = session
+            .finalize_from(&media[..], Some(media.len() as u64))
+            .await
+            .unwrap();
+        assert_eq!(resource["size"], media.len());
+        assert_eq!(*received.lock().unwrap(), media.len());
+
+        let (url, _) = serve_session(true).await;
+        let session = ResumableSession::<_, json::Value>::new(hyper::Client::new(), url, "agent");
+        match session.finalize_from(&media[..], Some(100)).await {
+            Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::InvalidData),
+            _ => panic!("the size of the media wasn't checked"),
+        }
+
+        assert_eq!(read_all(&media[..], 0).await.unwrap(), media);
+    }
+
+    #[tokio::test]
+    async fn appending_fails_if_nothing_is_persisted() {
+        let (url, received) = serve_session(false).await;
+        let mut session =
+            ResumableSession::<_, json::Value>::new(hyper::Client::new(), url, "agent")
+                .chunk_size(CHUNK_GRANULARITY);
+        let data = vec![7; CHUNK_GRANULARITY as usize];
+        match session.append(&data).await {
+            Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof),
+            _ => panic!("the chunk wasn't refused"),
+        }
+        assert_eq!(session.offset(), 0);
+        assert_eq!(*received.lock().unwrap(), data.len());
+    }
+}
@@ -0,0 +1,309 @@
+//! Validation of responses against the schemas of the discovery document of their API.
+//!
+//! With the `validate-responses` feature, generated crates embed the schemas of their responses,
+//! and check the body of each response against them before decoding it: the types of its values, the
+//! values of enums, the presence of fields required in responses, and fields the schemas don't
+//! have. The [`Divergence`]s found are passed to `Delegate::response_divergences()`, which logs
+//! them with the `tracing` feature unless overridden. They hint at changes of the API the crate
+//! wasn't generated for yet, or at bugs of the generator, so the feature is meant for testing and
+//! staging environments, as the validation costs about as much as decoding the response.
+//!
+//! Responses to calls selecting their fields with the `fields` parameter miss the others, which
+//! are then reported as missing if they are required.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde_json as json;
+
+/// The schemas of the responses of an API, as embedded into its crate.
+pub struct Schemas {
+    json: &'static str,
+    parsed: OnceLock<BTreeMap<String, Schema>>,
+}
+
+/// A schema of the discovery document, with only the properties needed for validation.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Schema {
+    /// The JSON type, like `object` or `string`, which is `any` or missing for any value.
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    /// The format of strings and numbers, like `int64`.
+    pub format: Option<String>,
+    /// The values of a string enum.
+    #[serde(rename = "enum")]
+    pub enum_: Option<Vec<String>>,
+    /// The name of the schema this one refers to.
+    #[serde(rename = "$ref")]
+    pub ref_: Option<String>,
+    /// The schema of the items of an array.
+    pub items: Option<Box<Schema>>,
+    /// The schemas of the fields of an object.
+    pub properties: Option<BTreeMap<String, Schema>>,
+    /// The schema of the values of an object used as map.
+    pub additional_properties: Option<Box<Schema>>,
+    /// The fields of an object which are present in each response.
+    pub required: Option<Vec<String>>,
+}
+
+/// The way a value of a response differs from its schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The value has the JSON type `found`, instead of the type `expected` by the schema.
+    Type {
+        expected: String,
+        found: &'static str,
+    },
+    /// The string is not one of the values of its enum.
+    EnumValue(String),
+    /// The string doesn't have the `format` of the schema, like a 64 bit integer.
+    Format { format: String, value: String },
+    /// The field is required in responses, but missing.
+    MissingField,
+    /// The schema has no such field.
+    UnknownField,
+    /// The schema refers to a schema which doesn't exist, which is a bug of the generator.
+    UnknownSchema(String),
+}
+
+/// A difference between a response and its schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The path of the value within the response, like `files[3].owners[0].kind`, which is empty
+    /// for the response itself.
+    pub path: String,
+    pub kind: DivergenceKind,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "the response"
+        } else {
+            self.path.as_str()
+        };
+        match &self.kind {
+            DivergenceKind::Type { expected, found } => {
+                write!(f, "{} is of type {} instead of {}", path, found, expected)
+            }
+            DivergenceKind::EnumValue(value) => {
+                write!(f, "{} has the unknown enum value {:?}", path, value)
+            }
+            DivergenceKind::Format { format, value } => {
+                write!(f, "{} is {:?}, which is no {}", path, value, format)
+            }
+            DivergenceKind::MissingField => write!(f, "{} is required, but missing", path),
+            DivergenceKind::UnknownField => write!(f, "{} is not a field of the schema", path),
+            DivergenceKind::UnknownSchema(name) => {
+                write!(f, "{} refers to the unknown schema {}", path, name)
+            }
+        }
+    }
+}
+
+impl Schemas {
+    /// The schemas of the JSON object `json`, which maps the names of schemas to their discovery
+    /// schemas. It is parsed when the first response is validated.
+    pub const fn new(json: &'static str) -> Schemas {
+        Schemas {
+            json,
+            parsed: OnceLock::new(),
+        }
+    }
+
+    /// Returns the divergences of the JSON response `body` from the schema called `schema`. Bodies
+    /// which aren't JSON aren't validated, as they fail to decode anyway.
+    pub fn validate(&self, schema: &str, body: &str) -> Vec<Divergence> {
+        let schemas = self.parsed.get_or_init(|| {
+            json::from_str(self.json).expect("the embedded schemas are valid JSON")
+        });
+        let value = match json::from_str::<json::Value>(body) {
+            Ok(value) => value,
+            Err(_) => return Vec::new(),
+        };
+        let mut validator = Validator {
+            schemas,
+            divergences: Vec::new(),
+        };
+        let root = Schema {
+            ref_: Some(schema.to_string()),
+            ..Default::default()
+        };
+        validator.validate(&root, &value, String::new());
+        validator.divergences
+    }
+}
+
+struct Validator<'a> {
+    schemas: &'a BTreeMap<String, Schema>,
+    divergences: Vec<Divergence>,
+}
+
+impl<'a> Validator<'a> {
+    fn diverges(&mut self, path: String, kind: DivergenceKind) {
+        self.divergences.push(Divergence { path, kind });
+    }
+
+    fn validate(&mut self, schema: &Schema, value: &json::Value, path: String) {
+        let schema = match &schema.ref_ {
+            Some(name) => match self.schemas.get(name) {
+                Some(schema) => schema,
+                None => return self.diverges(path, DivergenceKind::UnknownSchema(name.clone())),
+            },
+            None => schema,
+        };
+        let type_ = schema.type_.as_deref().unwrap_or("any");
+        match (type_, value) {
+            (_, json::Value::Null) | ("any", _) => {}
+            ("string", json::Value::String(s)) => self.validate_string(schema, s, path),
+            ("boolean", json::Value::Bool(_)) => {}
+            ("integer", json::Value::Number(n)) if n.is_i64() || n.is_u64() => {}
+            ("number", json::Value::Number(_)) => {}
+            ("array", json::Value::Array(items)) => {
+                if let Some(items_schema) = &schema.items {
+                    for (i, item) in items.iter().enumerate() {
+                        self.validate(items_schema, item, format!("{}[{}]", path, i));
+                    }
+                }
+            }
+            ("object", json::Value::Object(fields)) => self.validate_object(schema, fields, path),
+            (expected, value) => self.diverges(
+                path,
+                DivergenceKind::Type {
+                    expected: expected.to_string(),
+                    found: type_name(value),
+                },
+            ),
+        }
+    }
+
+    fn validate_string(&mut self, schema: &Schema, s: &str, path: String) {
+        if let Some(values) = &schema.enum_ {
+            if !values.iter().any(|value| value == s) {
+                return self.diverges(path, DivergenceKind::EnumValue(s.to_string()));
+            }
+        }
+        let valid = match schema.format.as_deref() {
+            Some("int64") => s.parse::<i64>().is_ok(),
+            Some("uint64") => s.parse::<u64>().is_ok(),
+            _ => true,
+        };
+        if !valid {
+            let format = schema.format.clone().unwrap_or_default();
+            self.diverges(
+                path,
+                DivergenceKind::Format {
+                    format,
+                    value: s.to_string(),
+                },
+            );
+        }
+    }
+
+    fn validate_object(
+        &mut self,
+        schema: &Schema,
+        fields: &json::Map<String, json::Value>,
+        path: String,
+    ) {
+        let field_path = |name: &str| {
+            if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", path, name)
+            }
+        };
+        for name in schema.required.iter().flatten() {
+            let missing = match fields.get(name) {
+                Some(value) => value.is_null(),
+                None => true,
+            };
+            if missing {
+                self.diverges(field_path(name), DivergenceKind::MissingField);
+            }
+        }
+        for (name, value) in fields {
+            let field_schema = schema
+                .properties
+                .as_ref()
+                .and_then(|properties| properties.get(name))
+                .or(schema.additional_properties.as_deref());
+            match field_schema {
+                Some(field_schema) => self.validate(field_schema, value, field_path(name)),
+                // objects without properties hold any fields
+                None if schema.properties.is_none() => {}
+                None => self.diverges(field_path(name), DivergenceKind::UnknownField),
+            }
+        }
+    }
+}
+
+fn type_name(value: &json::Value) -> &'static str {
+    match value {
+        json::Value::Null => "null",
+        json::Value::Bool(_) => "boolean",
+        json::Value::Number(n) if n.is_f64() => "number",
+        json::Value::Number(_) => "integer",
+        json::Value::String(_) => "string",
+        json::Value::Array(_) => "array",
+        json::Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SCHEMAS: Schemas = Schemas::new(
+        r#"{
+            "FileList": {"type": "object", "properties": {
+                "files": {"type": "array", "items": {"$ref": "File"}},
+                "nextPageToken": {"type": "string"}
+            }},
+            "File": {"type": "object", "required": ["id"], "properties": {
+                "id": {"type": "string"},
+                "size": {"type": "string", "format": "int64"},
+                "starred": {"type": "boolean"},
+                "state": {"type": "string", "enum": ["ACTIVE", "DELETED"]},
+                "labels": {"type": "object", "additionalProperties": {"type": "string"}},
+                "owner": {"$ref": "User"},
+                "metadata": {"type": "object"}
+            }}
+        }"#,
+    );
+
+    #[test]
+    fn divergences_of_responses() {
+        let body = r#"{"files": [
+            {"id": "a", "size": "12", "starred": true, "state": "ACTIVE", "labels": {"k": "v"},
+             "metadata": {"anything": [1]}, "extra": 1},
+            {"size": "12.5", "starred": "yes", "state": "TRASHED", "labels": {"k": 1}, "owner": {}}
+        ], "nextPageToken": null}"#;
+        let divergences = SCHEMAS.validate("FileList", body);
+        let divergences = divergences
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            divergences,
+            [
+                "files[0].extra is not a field of the schema",
+                "files[1].id is required, but missing",
+                "files[1].labels.k is of type integer instead of string",
+                "files[1].owner refers to the unknown schema User",
+                "files[1].size is \"12.5\", which is no int64",
+                "files[1].starred is of type string instead of boolean",
+                "files[1].state has the unknown enum value \"TRASHED\"",
+            ]
+        );
+
+        assert_eq!(SCHEMAS.validate("File", r#"{"id": "a"}"#), []);
+        assert_eq!(SCHEMAS.validate("File", "not json"), []);
+        assert_eq!(
+            SCHEMAS.validate("File", "[]")[0].to_string(),
+            "the response is of type array instead of object"
+        );
+    }
+}
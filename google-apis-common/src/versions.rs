@@ -0,0 +1,266 @@
+//! Planning upgrades of instances to the versions available for them, like those of Data Fusion.
+//!
+//! Data Fusion lists the versions instances can run with `locations.versions.list`, optionally only
+//! the latest patch of each minor version. The generated `locations_instances_upgrade_plan()`
+//! methods combine these with the current version of an instance into an [`UpgradePlan`], which
+//! compares version numbers numerically instead of as strings, so `6.10.0` is newer than `6.9.2`.
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json as json;
+
+/// The error of invalid version numbers, or of instances without a version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionError {
+    /// The invalid version number, or `None` if there was none.
+    pub value: Option<String>,
+}
+
+impl fmt::Display for VersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "'{}' is not a version number like '6.9.1'", value),
+            None => f.write_str("the instance has no version"),
+        }
+    }
+}
+
+impl error::Error for VersionError {}
+
+/// A version number of dot-separated numeric components, like `6.9.1.1`. Missing trailing
+/// components are zero, so `6.9` and `6.9.0` are equal.
+#[derive(Clone, Debug)]
+pub struct VersionNumber(Vec<u64>);
+
+impl VersionNumber {
+    /// Returns the numeric components of the version.
+    pub fn components(&self) -> &[u64] {
+        &self.0
+    }
+
+    /// Returns true if both versions have the same major and minor version, so upgrading from one
+    /// to the other only applies patches.
+    pub fn same_minor(&self, other: &VersionNumber) -> bool {
+        self.minor_key() == other.minor_key()
+    }
+
+    fn component(&self, index: usize) -> u64 {
+        self.0.get(index).copied().unwrap_or(0)
+    }
+
+    fn minor_key(&self) -> (u64, u64) {
+        (self.component(0), self.component(1))
+    }
+}
+
+impl FromStr for VersionNumber {
+    type Err = VersionError;
+
+    fn from_str(value: &str) -> Result<VersionNumber, VersionError> {
+        value
+            .split('.')
+            .map(|component| component.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map(VersionNumber)
+            .map_err(|_| VersionError {
+                value: Some(value.to_string()),
+            })
+    }
+}
+
+impl fmt::Display for VersionNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, component) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{}", component)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for VersionNumber {
+    fn cmp(&self, other: &VersionNumber) -> Ordering {
+        (0..self.0.len().max(other.0.len()))
+            .map(|index| self.component(index).cmp(&other.component(index)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for VersionNumber {
+    fn partial_cmp(&self, other: &VersionNumber) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for VersionNumber {
+    fn eq(&self, other: &VersionNumber) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for VersionNumber {}
+
+/// A version an instance can be upgraded to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeTarget {
+    /// The number of the version.
+    pub version: VersionNumber,
+    /// Whether it is the default version of new instances.
+    pub default: bool,
+    /// Whether it is a preview, which isn't considered stable.
+    pub preview: bool,
+    /// Whether upgrading to it only applies patches, as it has the current minor version.
+    pub patch: bool,
+    /// The names of the features available in this version.
+    pub features: Vec<String>,
+}
+
+/// The versions an instance can be upgraded to, newer than its current version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradePlan {
+    /// The current version of the instance.
+    pub current: VersionNumber,
+    /// Whether only the latest patch of each minor version is a valid target.
+    pub latest_patch_only: bool,
+    /// The valid targets, oldest first.
+    pub targets: Vec<UpgradeTarget>,
+}
+
+impl UpgradePlan {
+    /// Plan the upgrade of `instance`, whose current version is its `version` field, to one of
+    /// the `versions` returned by `locations.versions.list`. With `latest_patch_only`, only the
+    /// latest patch of each minor version is a target, even if others are listed.
+    ///
+    /// Fails if the instance has no valid version. Versions without a valid number are ignored.
+    pub fn new<I, V>(
+        instance: &I,
+        versions: impl IntoIterator<Item = V>,
+        latest_patch_only: bool,
+    ) -> Result<UpgradePlan, VersionError>
+    where
+        I: Serialize,
+        V: Serialize,
+    {
+        let current: VersionNumber = json::to_value(instance)
+            .ok()
+            .and_then(|instance| instance["version"].as_str().map(str::to_owned))
+            .ok_or(VersionError { value: None })?
+            .parse()?;
+        let mut targets: Vec<UpgradeTarget> = versions
+            .into_iter()
+            .filter_map(|version| json::to_value(version).ok())
+            .filter_map(|version| {
+                let number: VersionNumber = version["versionNumber"].as_str()?.parse().ok()?;
+                Some(UpgradeTarget {
+                    patch: number.same_minor(&current),
+                    version: number,
+                    default: version["defaultVersion"].as_bool().unwrap_or(false),
+                    preview: version["type"].as_str() == Some("TYPE_PREVIEW"),
+                    features: json::from_value(version["availableFeatures"].clone())
+                        .unwrap_or_default(),
+                })
+            })
+            .filter(|target| target.version > current)
+            .collect();
+        targets.sort_by(|a, b| a.version.cmp(&b.version));
+        targets.dedup_by(|a, b| a.version == b.version);
+        if latest_patch_only {
+            let mut latest = BTreeMap::new();
+            for target in targets {
+                latest.insert(target.version.minor_key(), target);
+            }
+            targets = latest.into_values().collect();
+        }
+        Ok(UpgradePlan {
+            current,
+            latest_patch_only,
+            targets,
+        })
+    }
+
+    /// Returns true if the instance may be upgraded to the given version.
+    pub fn allows(&self, version: &VersionNumber) -> bool {
+        self.targets.iter().any(|target| target.version == *version)
+    }
+
+    /// Returns the newest target which isn't a preview.
+    pub fn latest(&self) -> Option<&UpgradeTarget> {
+        self.targets.iter().rev().find(|target| !target.preview)
+    }
+
+    /// Returns the newest target which only applies patches.
+    pub fn latest_patch(&self) -> Option<&UpgradeTarget> {
+        self.targets.iter().rev().find(|target| target.patch)
+    }
+
+    /// Returns true if there is no target the instance can be upgraded to, except for previews.
+    pub fn is_up_to_date(&self) -> bool {
+        self.latest().is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn version(s: &str) -> VersionNumber {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn version_numbers() {
+        assert!(version("6.10.0") > version("6.9.2"));
+        assert_eq!(version("6.9"), version("6.9.0.0"));
+        assert!(version("6.9.0.1") > version("6.9"));
+        assert!(version("6.9.1").same_minor(&version("6.9")));
+        assert_eq!(version("6.10.0.1").to_string(), "6.10.0.1");
+        assert!("6.x".parse::<VersionNumber>().is_err());
+        assert!("".parse::<VersionNumber>().is_err());
+    }
+
+    #[test]
+    fn upgrade_plans() {
+        let instance = json!({"name": "projects/p/locations/l/instances/i", "version": "6.9.1"});
+        let versions = [
+            json!({"versionNumber": "6.8.0", "type": "TYPE_GENERAL_AVAILABILITY"}),
+            json!({"versionNumber": "6.9.1"}),
+            json!({"versionNumber": "6.9.2", "type": "TYPE_GENERAL_AVAILABILITY"}),
+            json!({"versionNumber": "6.9.3", "availableFeatures": ["a"]}),
+            json!({"versionNumber": "6.10.0", "defaultVersion": true}),
+            json!({"versionNumber": "6.10.1"}),
+            json!({"versionNumber": "7.0.0", "type": "TYPE_PREVIEW"}),
+            json!({"versionNumber": "invalid"}),
+        ];
+        let plan = UpgradePlan::new(&instance, &versions, false).unwrap();
+        let numbers: Vec<_> = plan.targets.iter().map(|t| t.version.to_string()).collect();
+        assert_eq!(numbers, ["6.9.2", "6.9.3", "6.10.0", "6.10.1", "7.0.0"]);
+        assert!(plan.allows(&version("6.10.0")));
+        assert!(!plan.allows(&version("6.8.0")));
+        assert_eq!(plan.latest().unwrap().version, version("6.10.1"));
+        assert_eq!(plan.latest_patch().unwrap().features, ["a"]);
+        assert!(plan.targets[2].default && !plan.targets[2].patch);
+        assert!(plan.targets[4].preview);
+
+        let plan = UpgradePlan::new(&instance, &versions, true).unwrap();
+        let numbers: Vec<_> = plan.targets.iter().map(|t| t.version.to_string()).collect();
+        assert_eq!(numbers, ["6.9.3", "6.10.1", "7.0.0"]);
+        assert!(!plan.allows(&version("6.9.2")));
+
+        let latest = json!({"version": "6.10.1"});
+        assert!(UpgradePlan::new(&latest, &versions, true)
+            .unwrap()
+            .is_up_to_date());
+        assert_eq!(
+            UpgradePlan::new(&json!({}), &versions, true),
+            Err(VersionError { value: None })
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! The registry of the crates generated for Google APIs, like `google-storage1`.
+//!
+//! Each generated API version is described by an [`ApiInfo`], naming its crates and their version,
+//! the revision of the discovery document they were generated from, and the urls calls are sent
+//! to. It lets tools like dashboards or dependency audits reason about all generated crates
+//! without downloading them:
+//!
+//! ```ignore
+//! let storage = google_apis_meta::find("storage", "v1").unwrap();
+//! assert_eq!(storage.crate_name, "google-storage1");
+//! ```
+//!
+//! The registry is generated along with the crates, from `src/generator/templates/registry.rs.mako`.
+mod registry;
+
+/// A generated version of an API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApiInfo {
+    /// The name of the API, like `storage`.
+    pub name: &'static str,
+    /// The version of the API, like `v1`.
+    pub version: &'static str,
+    /// The title of the API, like `Cloud Storage JSON API`.
+    pub title: &'static str,
+    /// The crate of the library, like `google-storage1`.
+    pub crate_name: &'static str,
+    /// The crate of the command line program, like `google-storage1-cli`.
+    pub cli_crate_name: &'static str,
+    /// The version of both crates, whose build metadata is the revision, like `5.0.5+20240229`.
+    pub crate_version: &'static str,
+    /// The revision of the discovery document the crates were generated from, like `20240229`.
+    pub revision: &'static str,
+    /// The url of the service, like `https://storage.googleapis.com/`.
+    pub root_url: &'static str,
+    /// The url the paths of methods are relative to, which hubs use by default, like
+    /// `https://storage.googleapis.com/storage/v1/`.
+    pub base_url: &'static str,
+    /// The documentation of the API, if it has any.
+    pub documentation_link: Option<&'static str>,
+}
+
+impl ApiInfo {
+    /// Returns the name of the library crate in Rust code, like `google_storage1`.
+    pub fn extern_crate_name(&self) -> String {
+        self.crate_name.replace('-', "_")
+    }
+
+    /// Returns the documentation of the library crate on docs.rs.
+    pub fn docs_url(&self) -> String {
+        format!("https://docs.rs/{}/{}", self.crate_name, self.crate_version)
+    }
+}
+
+/// Returns all generated API versions, sorted by the name of their API.
+pub fn apis() -> &'static [ApiInfo] {
+    registry::APIS
+}
+
+/// Returns the generated versions of the API called `name`.
+pub fn versions(name: &str) -> impl Iterator<Item = &'static ApiInfo> + '_ {
+    apis().iter().filter(move |api| api.name == name)
+}
+
+/// Returns the version `version` of the API called `name`, if it is generated.
+pub fn find(name: &str, version: &str) -> Option<&'static ApiInfo> {
+    versions(name).find(|api| api.version == version)
+}
+
+/// Returns the API version whose library or command line program is the crate called `crate_name`,
+/// which may be written as in Rust code, like `google_storage1`.
+pub fn by_crate_name(crate_name: &str) -> Option<&'static ApiInfo> {
+    let crate_name = crate_name.replace('_', "-");
+    apis()
+        .iter()
+        .find(|api| api.crate_name == crate_name || api.cli_crate_name == crate_name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lookups() {
+        let storage = find("storage", "v1").unwrap();
+        assert_eq!(storage.crate_name, "google-storage1");
+        assert_eq!(storage.cli_crate_name, "google-storage1-cli");
+        assert_eq!(
+            storage.base_url,
+            "https://storage.googleapis.com/storage/v1/"
+        );
+        assert!(storage
+            .crate_version
+            .ends_with(&format!("+{}", storage.revision)));
+        assert_eq!(storage.extern_crate_name(), "google_storage1");
+
+        assert_eq!(by_crate_name("google_storage1"), Some(storage));
+        assert_eq!(by_crate_name("google-storage1-cli"), Some(storage));
+        assert_eq!(by_crate_name("google-storage2"), None);
+        assert!(versions("pubsub").any(|api| api.version == "v1beta2"));
+        // blacklisted APIs are not generated
+        assert_eq!(find("dataflow", "v1b3"), None);
+    }
+
+    #[test]
+    fn registry_is_sorted() {
+        assert!(apis().windows(2).all(|w| w[0].name <= w[1].name));
+    }
+}
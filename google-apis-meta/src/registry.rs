@@ -0,0 +1,3801 @@
+// DO NOT EDIT !
+// This file was generated automatically from 'src/generator/templates/registry.rs.mako'
+// DO NOT EDIT !
+
+use crate::ApiInfo;
+
+/// All generated APIs, sorted by name.
+pub(crate) static APIS: &[ApiInfo] = &[
+    ApiInfo {
+        name: "abusiveexperiencereport",
+        version: "v1",
+        title: "Abusive Experience Report API",
+        crate_name: "google-abusiveexperiencereport1",
+        cli_crate_name: "google-abusiveexperiencereport1-cli",
+        crate_version: "5.0.5+20240207",
+        revision: "20240207",
+        root_url: "https://abusiveexperiencereport.googleapis.com/",
+        base_url: "https://abusiveexperiencereport.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/abusive-experience-report/"),
+    },
+    ApiInfo {
+        name: "acceleratedmobilepageurl",
+        version: "v1",
+        title: "Accelerated Mobile Pages (AMP) URL API",
+        crate_name: "google-acceleratedmobilepageurl1",
+        cli_crate_name: "google-acceleratedmobilepageurl1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://acceleratedmobilepageurl.googleapis.com/",
+        base_url: "https://acceleratedmobilepageurl.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/amp/cache/"),
+    },
+    ApiInfo {
+        name: "accessapproval",
+        version: "v1",
+        title: "Access Approval API",
+        crate_name: "google-accessapproval1",
+        cli_crate_name: "google-accessapproval1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://accessapproval.googleapis.com/",
+        base_url: "https://accessapproval.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/assured-workloads/access-approval/docs"),
+    },
+    ApiInfo {
+        name: "accessapproval",
+        version: "v1beta1",
+        title: "Access Approval API",
+        crate_name: "google-accessapproval1_beta1",
+        cli_crate_name: "google-accessapproval1_beta1-cli",
+        crate_version: "5.0.5+20200708",
+        revision: "20200708",
+        root_url: "https://accessapproval.googleapis.com/",
+        base_url: "https://accessapproval.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/access-approval/docs"),
+    },
+    ApiInfo {
+        name: "accesscontextmanager",
+        version: "v1",
+        title: "Access Context Manager API",
+        crate_name: "google-accesscontextmanager1",
+        cli_crate_name: "google-accesscontextmanager1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://accesscontextmanager.googleapis.com/",
+        base_url: "https://accesscontextmanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/access-context-manager/docs/reference/rest/"),
+    },
+    ApiInfo {
+        name: "accesscontextmanager",
+        version: "v1beta",
+        title: "Access Context Manager API",
+        crate_name: "google-accesscontextmanager1_beta",
+        cli_crate_name: "google-accesscontextmanager1_beta-cli",
+        crate_version: "5.0.5+20230123",
+        revision: "20230123",
+        root_url: "https://accesscontextmanager.googleapis.com/",
+        base_url: "https://accesscontextmanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/access-context-manager/docs/reference/rest/"),
+    },
+    ApiInfo {
+        name: "adexchangebuyer",
+        version: "v1.3",
+        title: "Ad Exchange Buyer API",
+        crate_name: "google-adexchangebuyer1d3",
+        cli_crate_name: "google-adexchangebuyer1d3-cli",
+        crate_version: "5.0.5+20210330",
+        revision: "20210330",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/adexchangebuyer/v1.3/",
+        documentation_link: Some("https://developers.google.com/ad-exchange/buyer-rest"),
+    },
+    ApiInfo {
+        name: "adexchangebuyer",
+        version: "v1.4",
+        title: "Ad Exchange Buyer API",
+        crate_name: "google-adexchangebuyer1d4",
+        cli_crate_name: "google-adexchangebuyer1d4-cli",
+        crate_version: "5.0.5+20210330",
+        revision: "20210330",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/adexchangebuyer/v1.4/",
+        documentation_link: Some("https://developers.google.com/ad-exchange/buyer-rest"),
+    },
+    ApiInfo {
+        name: "adexchangebuyer2",
+        version: "v2beta1",
+        title: "Ad Exchange Buyer API II",
+        crate_name: "google-adexchangebuyer2_v2_beta1",
+        cli_crate_name: "google-adexchangebuyer2_v2_beta1-cli",
+        crate_version: "5.0.5+20240305",
+        revision: "20240305",
+        root_url: "https://adexchangebuyer.googleapis.com/",
+        base_url: "https://adexchangebuyer.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/authorized-buyers/apis/reference/rest/"),
+    },
+    ApiInfo {
+        name: "adexchangeseller",
+        version: "v2.0",
+        title: "Ad Exchange Seller API",
+        crate_name: "google-adexchangeseller2",
+        cli_crate_name: "google-adexchangeseller2-cli",
+        crate_version: "5.0.5+20171101",
+        revision: "20171101",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/adexchangeseller/v2.0/",
+        documentation_link: Some("https://developers.google.com/ad-exchange/seller-rest/"),
+    },
+    ApiInfo {
+        name: "adexperiencereport",
+        version: "v1",
+        title: "Ad Experience Report API",
+        crate_name: "google-adexperiencereport1",
+        cli_crate_name: "google-adexperiencereport1-cli",
+        crate_version: "5.0.5+20240207",
+        revision: "20240207",
+        root_url: "https://adexperiencereport.googleapis.com/",
+        base_url: "https://adexperiencereport.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/ad-experience-report/"),
+    },
+    ApiInfo {
+        name: "admob",
+        version: "v1",
+        title: "AdMob API",
+        crate_name: "google-admob1",
+        cli_crate_name: "google-admob1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://admob.googleapis.com/",
+        base_url: "https://admob.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/admob/api/"),
+    },
+    ApiInfo {
+        name: "adsense",
+        version: "v1.4",
+        title: "AdSense Management API",
+        crate_name: "google-adsense1d4",
+        cli_crate_name: "google-adsense1d4-cli",
+        crate_version: "5.0.5+20201002",
+        revision: "20201002",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/adsense/v1.4/",
+        documentation_link: Some("https://developers.google.com/adsense/management/"),
+    },
+    ApiInfo {
+        name: "adsense",
+        version: "v2",
+        title: "AdSense Management API",
+        crate_name: "google-adsense2",
+        cli_crate_name: "google-adsense2-cli",
+        crate_version: "5.0.5+20240305",
+        revision: "20240305",
+        root_url: "https://adsense.googleapis.com/",
+        base_url: "https://adsense.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/adsense/management/"),
+    },
+    ApiInfo {
+        name: "adsensehost",
+        version: "v4.1",
+        title: "AdSense Host API",
+        crate_name: "google-adsensehost4d1",
+        cli_crate_name: "google-adsensehost4d1-cli",
+        crate_version: "5.0.5+20200930",
+        revision: "20200930",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/adsensehost/v4.1/",
+        documentation_link: Some("https://developers.google.com/adsense/host/"),
+    },
+    ApiInfo {
+        name: "alertcenter",
+        version: "v1beta1",
+        title: "Google Workspace Alert Center API",
+        crate_name: "google-alertcenter1_beta1",
+        cli_crate_name: "google-alertcenter1_beta1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://alertcenter.googleapis.com/",
+        base_url: "https://alertcenter.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/admin-sdk/alertcenter/"),
+    },
+    ApiInfo {
+        name: "analytics",
+        version: "v3",
+        title: "Google Analytics API",
+        crate_name: "google-analytics3",
+        cli_crate_name: "google-analytics3-cli",
+        crate_version: "5.0.5+20190807",
+        revision: "20190807",
+        root_url: "https://analytics.googleapis.com/",
+        base_url: "https://www.googleapis.com/analytics/v3/",
+        documentation_link: Some("https://developers.google.com/analytics/"),
+    },
+    ApiInfo {
+        name: "analyticsdata",
+        version: "v1beta",
+        title: "Google Analytics Data API",
+        crate_name: "google-analyticsdata1_beta",
+        cli_crate_name: "google-analyticsdata1_beta-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://analyticsdata.googleapis.com/",
+        base_url: "https://analyticsdata.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/analytics/devguides/reporting/data/v1/"),
+    },
+    ApiInfo {
+        name: "analyticsreporting",
+        version: "v4",
+        title: "Analytics Reporting API",
+        crate_name: "google-analyticsreporting4",
+        cli_crate_name: "google-analyticsreporting4-cli",
+        crate_version: "5.0.5+20240123",
+        revision: "20240123",
+        root_url: "https://analyticsreporting.googleapis.com/",
+        base_url: "https://analyticsreporting.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/analytics/devguides/reporting/core/v4/"),
+    },
+    ApiInfo {
+        name: "androiddeviceprovisioning",
+        version: "v1",
+        title: "Android Device Provisioning Partner API",
+        crate_name: "google-androiddeviceprovisioning1",
+        cli_crate_name: "google-androiddeviceprovisioning1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://androiddeviceprovisioning.googleapis.com/",
+        base_url: "https://androiddeviceprovisioning.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/zero-touch/"),
+    },
+    ApiInfo {
+        name: "androidenterprise",
+        version: "v1",
+        title: "Google Play EMM API",
+        crate_name: "google-androidenterprise1",
+        cli_crate_name: "google-androidenterprise1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://androidenterprise.googleapis.com/",
+        base_url: "https://androidenterprise.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/android/work/play/emm-api"),
+    },
+    ApiInfo {
+        name: "androidmanagement",
+        version: "v1",
+        title: "Android Management API",
+        crate_name: "google-androidmanagement1",
+        cli_crate_name: "google-androidmanagement1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://androidmanagement.googleapis.com/",
+        base_url: "https://androidmanagement.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/android/management"),
+    },
+    ApiInfo {
+        name: "androidpublisher",
+        version: "v2",
+        title: "Google Play Developer API",
+        crate_name: "google-androidpublisher2",
+        cli_crate_name: "google-androidpublisher2-cli",
+        crate_version: "5.0.5+20200331",
+        revision: "20200331",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/androidpublisher/v2/applications/",
+        documentation_link: Some("https://developers.google.com/android-publisher"),
+    },
+    ApiInfo {
+        name: "androidpublisher",
+        version: "v3",
+        title: "Google Play Android Developer API",
+        crate_name: "google-androidpublisher3",
+        cli_crate_name: "google-androidpublisher3-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://androidpublisher.googleapis.com/",
+        base_url: "https://androidpublisher.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/android-publisher"),
+    },
+    ApiInfo {
+        name: "apigateway",
+        version: "v1",
+        title: "API Gateway API",
+        crate_name: "google-apigateway1",
+        cli_crate_name: "google-apigateway1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://apigateway.googleapis.com/",
+        base_url: "https://apigateway.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/api-gateway/docs"),
+    },
+    ApiInfo {
+        name: "apigee",
+        version: "v1",
+        title: "Apigee API",
+        crate_name: "google-apigee1",
+        cli_crate_name: "google-apigee1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://apigee.googleapis.com/",
+        base_url: "https://apigee.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/apigee-api-management/"),
+    },
+    ApiInfo {
+        name: "apikeys",
+        version: "v2",
+        title: "API Keys API",
+        crate_name: "google-apikeys2",
+        cli_crate_name: "google-apikeys2-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://apikeys.googleapis.com/",
+        base_url: "https://apikeys.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/api-keys/docs"),
+    },
+    ApiInfo {
+        name: "appengine",
+        version: "v1",
+        title: "App Engine Admin API",
+        crate_name: "google-appengine1",
+        cli_crate_name: "google-appengine1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://appengine.googleapis.com/",
+        base_url: "https://appengine.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/appengine/docs/admin-api/"),
+    },
+    ApiInfo {
+        name: "appengine",
+        version: "v1beta4",
+        title: "App Engine Admin API",
+        crate_name: "google-appengine1_beta4",
+        cli_crate_name: "google-appengine1_beta4-cli",
+        crate_version: "5.0.5+20181005",
+        revision: "20181005",
+        root_url: "https://appengine.googleapis.com/",
+        base_url: "https://appengine.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/appengine/docs/admin-api/"),
+    },
+    ApiInfo {
+        name: "appengine",
+        version: "v1beta5",
+        title: "App Engine Admin API",
+        crate_name: "google-appengine1_beta5",
+        cli_crate_name: "google-appengine1_beta5-cli",
+        crate_version: "5.0.5+20181005",
+        revision: "20181005",
+        root_url: "https://appengine.googleapis.com/",
+        base_url: "https://appengine.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/appengine/docs/admin-api/"),
+    },
+    ApiInfo {
+        name: "appsactivity",
+        version: "v1",
+        title: "Drive Activity API",
+        crate_name: "google-appsactivity1",
+        cli_crate_name: "google-appsactivity1-cli",
+        crate_version: "5.0.5+20200628",
+        revision: "20200628",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/appsactivity/v1/",
+        documentation_link: Some("https://developers.google.com/google-apps/activity/"),
+    },
+    ApiInfo {
+        name: "appstate",
+        version: "v1",
+        title: "Google App State API",
+        crate_name: "google-appstate1",
+        cli_crate_name: "google-appstate1-cli",
+        crate_version: "5.0.5+20190627",
+        revision: "20190627",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/appstate/v1/",
+        documentation_link: Some("https://developers.google.com/games/services/web/api/states"),
+    },
+    ApiInfo {
+        name: "area120tables",
+        version: "v1alpha1",
+        title: "Area120 Tables API",
+        crate_name: "google-area120tables1_alpha1",
+        cli_crate_name: "google-area120tables1_alpha1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://area120tables.googleapis.com/",
+        base_url: "https://area120tables.googleapis.com/",
+        documentation_link: Some("https://support.google.com/area120-tables/answer/10011390"),
+    },
+    ApiInfo {
+        name: "artifactregistry",
+        version: "v1",
+        title: "Artifact Registry API",
+        crate_name: "google-artifactregistry1",
+        cli_crate_name: "google-artifactregistry1-cli",
+        crate_version: "5.0.5+20240213",
+        revision: "20240213",
+        root_url: "https://artifactregistry.googleapis.com/",
+        base_url: "https://artifactregistry.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/artifacts/docs/"),
+    },
+    ApiInfo {
+        name: "artifactregistry",
+        version: "v1beta1",
+        title: "Artifact Registry API",
+        crate_name: "google-artifactregistry1_beta1",
+        cli_crate_name: "google-artifactregistry1_beta1-cli",
+        crate_version: "5.0.5+20240213",
+        revision: "20240213",
+        root_url: "https://artifactregistry.googleapis.com/",
+        base_url: "https://artifactregistry.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/artifacts/docs/"),
+    },
+    ApiInfo {
+        name: "assuredworkloads",
+        version: "v1",
+        title: "Assured Workloads API",
+        crate_name: "google-assuredworkloads1",
+        cli_crate_name: "google-assuredworkloads1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://assuredworkloads.googleapis.com/",
+        base_url: "https://assuredworkloads.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/learnmoreurl"),
+    },
+    ApiInfo {
+        name: "authorizedbuyersmarketplace",
+        version: "v1",
+        title: "Authorized Buyers Marketplace API",
+        crate_name: "google-authorizedbuyersmarketplace1",
+        cli_crate_name: "google-authorizedbuyersmarketplace1-cli",
+        crate_version: "5.0.5+20240305",
+        revision: "20240305",
+        root_url: "https://authorizedbuyersmarketplace.googleapis.com/",
+        base_url: "https://authorizedbuyersmarketplace.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/authorized-buyers/apis/marketplace/reference/rest/"),
+    },
+    ApiInfo {
+        name: "autoscaler",
+        version: "v1beta2",
+        title: "Google Compute Engine Autoscaler API",
+        crate_name: "google-autoscaler1_beta2",
+        cli_crate_name: "google-autoscaler1_beta2-cli",
+        crate_version: "5.0.5+20150629",
+        revision: "20150629",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/autoscaler/v1beta2/",
+        documentation_link: Some("http://developers.google.com/compute/docs/autoscaler"),
+    },
+    ApiInfo {
+        name: "baremetalsolution",
+        version: "v2",
+        title: "Bare Metal Solution API",
+        crate_name: "google-baremetalsolution2",
+        cli_crate_name: "google-baremetalsolution2-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://baremetalsolution.googleapis.com/",
+        base_url: "https://baremetalsolution.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/bare-metal"),
+    },
+    ApiInfo {
+        name: "bigqueryconnection",
+        version: "v1beta1",
+        title: "BigQuery Connection API",
+        crate_name: "google-bigqueryconnection1_beta1",
+        cli_crate_name: "google-bigqueryconnection1_beta1-cli",
+        crate_version: "5.0.5+20240214",
+        revision: "20240214",
+        root_url: "https://bigqueryconnection.googleapis.com/",
+        base_url: "https://bigqueryconnection.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/bigquery/docs/connections-api-intro"),
+    },
+    ApiInfo {
+        name: "bigquerydatatransfer",
+        version: "v1",
+        title: "BigQuery Data Transfer API",
+        crate_name: "google-bigquerydatatransfer1",
+        cli_crate_name: "google-bigquerydatatransfer1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://bigquerydatatransfer.googleapis.com/",
+        base_url: "https://bigquerydatatransfer.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/bigquery-transfer/"),
+    },
+    ApiInfo {
+        name: "bigqueryreservation",
+        version: "v1",
+        title: "BigQuery Reservation API",
+        crate_name: "google-bigqueryreservation1",
+        cli_crate_name: "google-bigqueryreservation1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://bigqueryreservation.googleapis.com/",
+        base_url: "https://bigqueryreservation.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/bigquery/"),
+    },
+    ApiInfo {
+        name: "bigtableadmin",
+        version: "v2",
+        title: "Cloud Bigtable Admin API",
+        crate_name: "google-bigtableadmin2",
+        cli_crate_name: "google-bigtableadmin2-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://bigtableadmin.googleapis.com/",
+        base_url: "https://bigtableadmin.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/bigtable/"),
+    },
+    ApiInfo {
+        name: "billingbudgets",
+        version: "v1",
+        title: "Cloud Billing Budget API",
+        crate_name: "google-billingbudgets1",
+        cli_crate_name: "google-billingbudgets1-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://billingbudgets.googleapis.com/",
+        base_url: "https://billingbudgets.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/billing/docs/how-to/budget-api-overview"),
+    },
+    ApiInfo {
+        name: "billingbudgets",
+        version: "v1beta1",
+        title: "Cloud Billing Budget API",
+        crate_name: "google-billingbudgets1_beta1",
+        cli_crate_name: "google-billingbudgets1_beta1-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://billingbudgets.googleapis.com/",
+        base_url: "https://billingbudgets.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/billing/docs/how-to/budget-api-overview"),
+    },
+    ApiInfo {
+        name: "blogger",
+        version: "v3",
+        title: "Blogger API",
+        crate_name: "google-blogger3",
+        cli_crate_name: "google-blogger3-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://blogger.googleapis.com/",
+        base_url: "https://blogger.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/blogger/docs/3.0/getting_started"),
+    },
+    ApiInfo {
+        name: "books",
+        version: "v1",
+        title: "Books API",
+        crate_name: "google-books1",
+        cli_crate_name: "google-books1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://books.googleapis.com/",
+        base_url: "https://books.googleapis.com/",
+        documentation_link: Some("https://code.google.com/apis/books/docs/v1/getting_started.html"),
+    },
+    ApiInfo {
+        name: "calendar",
+        version: "v3",
+        title: "Calendar API",
+        crate_name: "google-calendar3",
+        cli_crate_name: "google-calendar3-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/calendar/v3/",
+        documentation_link: Some("https://developers.google.com/google-apps/calendar/firstapp"),
+    },
+    ApiInfo {
+        name: "certificatemanager",
+        version: "v1",
+        title: "Certificate Manager API",
+        crate_name: "google-certificatemanager1",
+        cli_crate_name: "google-certificatemanager1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://certificatemanager.googleapis.com/",
+        base_url: "https://certificatemanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/certificate-manager"),
+    },
+    ApiInfo {
+        name: "chromemanagement",
+        version: "v1",
+        title: "Chrome Management API",
+        crate_name: "google-chromemanagement1",
+        cli_crate_name: "google-chromemanagement1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://chromemanagement.googleapis.com/",
+        base_url: "https://chromemanagement.googleapis.com/",
+        documentation_link: Some("http://developers.google.com/chrome/management/"),
+    },
+    ApiInfo {
+        name: "chromepolicy",
+        version: "v1",
+        title: "Chrome Policy API",
+        crate_name: "google-chromepolicy1",
+        cli_crate_name: "google-chromepolicy1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://chromepolicy.googleapis.com/",
+        base_url: "https://chromepolicy.googleapis.com/",
+        documentation_link: Some("http://developers.google.com/chrome/policy"),
+    },
+    ApiInfo {
+        name: "chromeuxreport",
+        version: "v1",
+        title: "Chrome UX Report API",
+        crate_name: "google-chromeuxreport1",
+        cli_crate_name: "google-chromeuxreport1-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://chromeuxreport.googleapis.com/",
+        base_url: "https://chromeuxreport.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/web/tools/chrome-user-experience-report/api/reference"),
+    },
+    ApiInfo {
+        name: "classroom",
+        version: "v1",
+        title: "Google Classroom API",
+        crate_name: "google-classroom1",
+        cli_crate_name: "google-classroom1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://classroom.googleapis.com/",
+        base_url: "https://classroom.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/classroom/"),
+    },
+    ApiInfo {
+        name: "cloudasset",
+        version: "v1",
+        title: "Cloud Asset API",
+        crate_name: "google-cloudasset1",
+        cli_crate_name: "google-cloudasset1-cli",
+        crate_version: "5.0.5+20240302",
+        revision: "20240302",
+        root_url: "https://cloudasset.googleapis.com/",
+        base_url: "https://cloudasset.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/asset-inventory/docs/quickstart"),
+    },
+    ApiInfo {
+        name: "cloudasset",
+        version: "v1beta1",
+        title: "Cloud Asset API",
+        crate_name: "google-cloudasset1_beta1",
+        cli_crate_name: "google-cloudasset1_beta1-cli",
+        crate_version: "5.0.5+20240302",
+        revision: "20240302",
+        root_url: "https://cloudasset.googleapis.com/",
+        base_url: "https://cloudasset.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/asset-inventory/docs/quickstart"),
+    },
+    ApiInfo {
+        name: "cloudbilling",
+        version: "v1",
+        title: "Cloud Billing API",
+        crate_name: "google-cloudbilling1",
+        cli_crate_name: "google-cloudbilling1-cli",
+        crate_version: "5.0.5+20240214",
+        revision: "20240214",
+        root_url: "https://cloudbilling.googleapis.com/",
+        base_url: "https://cloudbilling.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/billing/docs/apis"),
+    },
+    ApiInfo {
+        name: "cloudbuild",
+        version: "v1",
+        title: "Cloud Build API",
+        crate_name: "google-cloudbuild1",
+        cli_crate_name: "google-cloudbuild1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://cloudbuild.googleapis.com/",
+        base_url: "https://cloudbuild.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/cloud-build/docs/"),
+    },
+    ApiInfo {
+        name: "cloudchannel",
+        version: "v1",
+        title: "Cloud Channel API",
+        crate_name: "google-cloudchannel1",
+        cli_crate_name: "google-cloudchannel1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://cloudchannel.googleapis.com/",
+        base_url: "https://cloudchannel.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/channel"),
+    },
+    ApiInfo {
+        name: "clouddebugger",
+        version: "v2",
+        title: "Cloud Debugger API",
+        crate_name: "google-clouddebugger2",
+        cli_crate_name: "google-clouddebugger2-cli",
+        crate_version: "5.0.5+20230113",
+        revision: "20230113",
+        root_url: "https://clouddebugger.googleapis.com/",
+        base_url: "https://clouddebugger.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/debugger"),
+    },
+    ApiInfo {
+        name: "clouddeploy",
+        version: "v1",
+        title: "Cloud Deploy API",
+        crate_name: "google-clouddeploy1",
+        cli_crate_name: "google-clouddeploy1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://clouddeploy.googleapis.com/",
+        base_url: "https://clouddeploy.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/deploy/"),
+    },
+    ApiInfo {
+        name: "clouderrorreporting",
+        version: "v1beta1",
+        title: "Error Reporting API",
+        crate_name: "google-clouderrorreporting1_beta1",
+        cli_crate_name: "google-clouderrorreporting1_beta1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://clouderrorreporting.googleapis.com/",
+        base_url: "https://clouderrorreporting.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/error-reporting/"),
+    },
+    ApiInfo {
+        name: "cloudfunctions",
+        version: "v1",
+        title: "Cloud Functions API",
+        crate_name: "google-cloudfunctions1",
+        cli_crate_name: "google-cloudfunctions1-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://cloudfunctions.googleapis.com/",
+        base_url: "https://cloudfunctions.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/functions"),
+    },
+    ApiInfo {
+        name: "cloudidentity",
+        version: "v1",
+        title: "Cloud Identity API",
+        crate_name: "google-cloudidentity1",
+        cli_crate_name: "google-cloudidentity1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://cloudidentity.googleapis.com/",
+        base_url: "https://cloudidentity.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/identity/"),
+    },
+    ApiInfo {
+        name: "cloudiot",
+        version: "v1",
+        title: "Cloud IoT API",
+        crate_name: "google-cloudiot1",
+        cli_crate_name: "google-cloudiot1-cli",
+        crate_version: "5.0.5+0",
+        revision: "0",
+        root_url: "https://cloudiot.googleapis.com/",
+        base_url: "https://cloudiot.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/iot"),
+    },
+    ApiInfo {
+        name: "cloudkms",
+        version: "v1",
+        title: "Cloud Key Management Service (KMS) API",
+        crate_name: "google-cloudkms1",
+        cli_crate_name: "google-cloudkms1-cli",
+        crate_version: "5.0.5+20240219",
+        revision: "20240219",
+        root_url: "https://cloudkms.googleapis.com/",
+        base_url: "https://cloudkms.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/kms/"),
+    },
+    ApiInfo {
+        name: "cloudkms",
+        version: "v1beta1",
+        title: "Google Cloud Key Management Service (KMS) API",
+        crate_name: "google-cloudkms1_beta1",
+        cli_crate_name: "google-cloudkms1_beta1-cli",
+        crate_version: "5.0.5+20170515",
+        revision: "20170515",
+        root_url: "https://cloudkms.googleapis.com/",
+        base_url: "https://cloudkms.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/kms/"),
+    },
+    ApiInfo {
+        name: "cloudlatencytest",
+        version: "v2",
+        title: "Google Cloud Network Performance Monitoring API",
+        crate_name: "google-cloudlatencytest2",
+        cli_crate_name: "google-cloudlatencytest2-cli",
+        crate_version: "5.0.5+20160309",
+        revision: "20160309",
+        root_url: "https://cloudlatencytest-pa.googleapis.com/",
+        base_url: "https://cloudlatencytest-pa.googleapis.com/v2/statscollection/",
+        documentation_link: None,
+    },
+    ApiInfo {
+        name: "cloudmonitoring",
+        version: "v2beta2",
+        title: "Cloud Monitoring API",
+        crate_name: "google-cloudmonitoring2_beta2",
+        cli_crate_name: "google-cloudmonitoring2_beta2-cli",
+        crate_version: "5.0.5+20170501",
+        revision: "20170501",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/cloudmonitoring/v2beta2/projects/",
+        documentation_link: Some("https://cloud.google.com/monitoring/v2beta2/"),
+    },
+    ApiInfo {
+        name: "cloudprivatecatalog",
+        version: "v1beta1",
+        title: "Cloud Private Catalog API",
+        crate_name: "google-cloudprivatecatalog1_beta1",
+        cli_crate_name: "google-cloudprivatecatalog1_beta1-cli",
+        crate_version: "5.0.5+20200405",
+        revision: "20200405",
+        root_url: "https://cloudprivatecatalog.googleapis.com/",
+        base_url: "https://cloudprivatecatalog.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/private-catalog/"),
+    },
+    ApiInfo {
+        name: "cloudprivatecatalogproducer",
+        version: "v1beta1",
+        title: "Cloud Private Catalog Producer API",
+        crate_name: "google-cloudprivatecatalogproducer1_beta1",
+        cli_crate_name: "google-cloudprivatecatalogproducer1_beta1-cli",
+        crate_version: "5.0.5+20200405",
+        revision: "20200405",
+        root_url: "https://cloudprivatecatalogproducer.googleapis.com/",
+        base_url: "https://cloudprivatecatalogproducer.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/private-catalog/"),
+    },
+    ApiInfo {
+        name: "cloudprofiler",
+        version: "v2",
+        title: "Cloud Profiler API",
+        crate_name: "google-cloudprofiler2",
+        cli_crate_name: "google-cloudprofiler2-cli",
+        crate_version: "5.0.5+20240219",
+        revision: "20240219",
+        root_url: "https://cloudprofiler.googleapis.com/",
+        base_url: "https://cloudprofiler.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/profiler/"),
+    },
+    ApiInfo {
+        name: "cloudresourcemanager",
+        version: "v1",
+        title: "Cloud Resource Manager API",
+        crate_name: "google-cloudresourcemanager1",
+        cli_crate_name: "google-cloudresourcemanager1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://cloudresourcemanager.googleapis.com/",
+        base_url: "https://cloudresourcemanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/resource-manager"),
+    },
+    ApiInfo {
+        name: "cloudresourcemanager",
+        version: "v1beta1",
+        title: "Cloud Resource Manager API",
+        crate_name: "google-cloudresourcemanager1_beta1",
+        cli_crate_name: "google-cloudresourcemanager1_beta1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://cloudresourcemanager.googleapis.com/",
+        base_url: "https://cloudresourcemanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/resource-manager"),
+    },
+    ApiInfo {
+        name: "cloudresourcemanager",
+        version: "v2",
+        title: "Cloud Resource Manager API",
+        crate_name: "google-cloudresourcemanager2",
+        cli_crate_name: "google-cloudresourcemanager2-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://cloudresourcemanager.googleapis.com/",
+        base_url: "https://cloudresourcemanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/resource-manager"),
+    },
+    ApiInfo {
+        name: "cloudresourcemanager",
+        version: "v3",
+        title: "Cloud Resource Manager API",
+        crate_name: "google-cloudresourcemanager3",
+        cli_crate_name: "google-cloudresourcemanager3-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://cloudresourcemanager.googleapis.com/",
+        base_url: "https://cloudresourcemanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/resource-manager"),
+    },
+    ApiInfo {
+        name: "cloudscheduler",
+        version: "v1",
+        title: "Cloud Scheduler API",
+        crate_name: "google-cloudscheduler1",
+        cli_crate_name: "google-cloudscheduler1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://cloudscheduler.googleapis.com/",
+        base_url: "https://cloudscheduler.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/scheduler/"),
+    },
+    ApiInfo {
+        name: "cloudscheduler",
+        version: "v1beta1",
+        title: "Cloud Scheduler API",
+        crate_name: "google-cloudscheduler1_beta1",
+        cli_crate_name: "google-cloudscheduler1_beta1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://cloudscheduler.googleapis.com/",
+        base_url: "https://cloudscheduler.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/scheduler/"),
+    },
+    ApiInfo {
+        name: "cloudshell",
+        version: "v1",
+        title: "Cloud Shell API",
+        crate_name: "google-cloudshell1",
+        cli_crate_name: "google-cloudshell1-cli",
+        crate_version: "5.0.5+20240212",
+        revision: "20240212",
+        root_url: "https://cloudshell.googleapis.com/",
+        base_url: "https://cloudshell.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/shell/docs/"),
+    },
+    ApiInfo {
+        name: "cloudsupport",
+        version: "v2beta",
+        title: "Google Cloud Support API",
+        crate_name: "google-cloudsupport2_beta",
+        cli_crate_name: "google-cloudsupport2_beta-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://cloudsupport.googleapis.com/",
+        base_url: "https://cloudsupport.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/support/docs/apis"),
+    },
+    ApiInfo {
+        name: "cloudtasks",
+        version: "v2",
+        title: "Cloud Tasks API",
+        crate_name: "google-cloudtasks2",
+        cli_crate_name: "google-cloudtasks2-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://cloudtasks.googleapis.com/",
+        base_url: "https://cloudtasks.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/tasks/"),
+    },
+    ApiInfo {
+        name: "cloudtasks",
+        version: "v2beta2",
+        title: "Cloud Tasks API",
+        crate_name: "google-cloudtasks2_beta2",
+        cli_crate_name: "google-cloudtasks2_beta2-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://cloudtasks.googleapis.com/",
+        base_url: "https://cloudtasks.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/tasks/"),
+    },
+    ApiInfo {
+        name: "cloudtasks",
+        version: "v2beta3",
+        title: "Cloud Tasks API",
+        crate_name: "google-cloudtasks2_beta3",
+        cli_crate_name: "google-cloudtasks2_beta3-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://cloudtasks.googleapis.com/",
+        base_url: "https://cloudtasks.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/tasks/"),
+    },
+    ApiInfo {
+        name: "cloudtrace",
+        version: "v1",
+        title: "Cloud Trace API",
+        crate_name: "google-cloudtrace1",
+        cli_crate_name: "google-cloudtrace1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://cloudtrace.googleapis.com/",
+        base_url: "https://cloudtrace.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/trace"),
+    },
+    ApiInfo {
+        name: "cloudtrace",
+        version: "v2",
+        title: "Cloud Trace API",
+        crate_name: "google-cloudtrace2",
+        cli_crate_name: "google-cloudtrace2-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://cloudtrace.googleapis.com/",
+        base_url: "https://cloudtrace.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/trace"),
+    },
+    ApiInfo {
+        name: "clouduseraccounts",
+        version: "vm_beta",
+        title: "Cloud User Accounts API",
+        crate_name: "google-clouduseraccountsvm_beta",
+        cli_crate_name: "google-clouduseraccountsvm_beta-cli",
+        crate_version: "5.0.5+20160316",
+        revision: "20160316",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/clouduseraccounts/vm_beta/projects/",
+        documentation_link: Some("https://cloud.google.com/compute/docs/access/user-accounts/api/latest/"),
+    },
+    ApiInfo {
+        name: "commentanalyzer",
+        version: "v1alpha1",
+        title: "Perspective Comment Analyzer API",
+        crate_name: "google-commentanalyzer1_alpha1",
+        cli_crate_name: "google-commentanalyzer1_alpha1-cli",
+        crate_version: "5.0.5+20200405",
+        revision: "20200405",
+        root_url: "https://commentanalyzer.googleapis.com/",
+        base_url: "https://commentanalyzer.googleapis.com/",
+        documentation_link: Some("https://github.com/conversationai/perspectiveapi/blob/master/README.md"),
+    },
+    ApiInfo {
+        name: "composer",
+        version: "v1",
+        title: "Cloud Composer API",
+        crate_name: "google-composer1",
+        cli_crate_name: "google-composer1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://composer.googleapis.com/",
+        base_url: "https://composer.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/composer/"),
+    },
+    ApiInfo {
+        name: "compute",
+        version: "v1",
+        title: "Compute Engine API",
+        crate_name: "google-compute1",
+        cli_crate_name: "google-compute1-cli",
+        crate_version: "5.0.5+20240218",
+        revision: "20240218",
+        root_url: "https://compute.googleapis.com/",
+        base_url: "https://compute.googleapis.com/compute/v1/",
+        documentation_link: Some("https://cloud.google.com/compute/"),
+    },
+    ApiInfo {
+        name: "connectors",
+        version: "v1",
+        title: "Connectors API",
+        crate_name: "google-connectors1",
+        cli_crate_name: "google-connectors1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://connectors.googleapis.com/",
+        base_url: "https://connectors.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/apigee/docs/api-platform/connectors/about-connectors"),
+    },
+    ApiInfo {
+        name: "consumersurveys",
+        version: "v2",
+        title: "Consumer Surveys API",
+        crate_name: "google-consumersurveys2",
+        cli_crate_name: "google-consumersurveys2-cli",
+        crate_version: "5.0.5+20170407",
+        revision: "20170407",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/consumersurveys/v2/",
+        documentation_link: None,
+    },
+    ApiInfo {
+        name: "contactcenterinsights",
+        version: "v1",
+        title: "Contact Center AI Insights API",
+        crate_name: "google-contactcenterinsights1",
+        cli_crate_name: "google-contactcenterinsights1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://contactcenterinsights.googleapis.com/",
+        base_url: "https://contactcenterinsights.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/contact-center/insights/docs"),
+    },
+    ApiInfo {
+        name: "container",
+        version: "v1",
+        title: "Kubernetes Engine API",
+        crate_name: "google-container1",
+        cli_crate_name: "google-container1-cli",
+        crate_version: "5.0.5+20240214",
+        revision: "20240214",
+        root_url: "https://container.googleapis.com/",
+        base_url: "https://container.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/container-engine/"),
+    },
+    ApiInfo {
+        name: "containeranalysis",
+        version: "v1beta1",
+        title: "Container Analysis API",
+        crate_name: "google-containeranalysis1_beta1",
+        cli_crate_name: "google-containeranalysis1_beta1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://containeranalysis.googleapis.com/",
+        base_url: "https://containeranalysis.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/container-analysis/api/reference/rest/"),
+    },
+    ApiInfo {
+        name: "containeranalysis",
+        version: "v1",
+        title: "Container Analysis API",
+        crate_name: "google-containeranalysis1",
+        cli_crate_name: "google-containeranalysis1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://containeranalysis.googleapis.com/",
+        base_url: "https://containeranalysis.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/container-analysis/api/reference/rest/"),
+    },
+    ApiInfo {
+        name: "content",
+        version: "v2",
+        title: "Content API for Shopping",
+        crate_name: "google-content2",
+        cli_crate_name: "google-content2-cli",
+        crate_version: "5.0.5+20220303",
+        revision: "20220303",
+        root_url: "https://shoppingcontent.googleapis.com/",
+        base_url: "https://shoppingcontent.googleapis.com/content/v2/",
+        documentation_link: Some("https://developers.google.com/shopping-content/v2/"),
+    },
+    ApiInfo {
+        name: "content",
+        version: "v2sandbox",
+        title: "Content API for Shopping",
+        crate_name: "google-content2_sandbox",
+        cli_crate_name: "google-content2_sandbox-cli",
+        crate_version: "5.0.5+20181009",
+        revision: "20181009",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/content/v2sandbox/",
+        documentation_link: Some("https://developers.google.com/shopping-content"),
+    },
+    ApiInfo {
+        name: "coordinate",
+        version: "v1",
+        title: "Google Maps Coordinate API",
+        crate_name: "google-coordinate1",
+        cli_crate_name: "google-coordinate1-cli",
+        crate_version: "5.0.5+20150811",
+        revision: "20150811",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/coordinate/v1/",
+        documentation_link: Some("https://developers.google.com/coordinate/"),
+    },
+    ApiInfo {
+        name: "customsearch",
+        version: "v1",
+        title: "Custom Search API",
+        crate_name: "google-customsearch1",
+        cli_crate_name: "google-customsearch1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://customsearch.googleapis.com/",
+        base_url: "https://customsearch.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/custom-search/v1/introduction"),
+    },
+    ApiInfo {
+        name: "datacatalog",
+        version: "v1beta1",
+        title: "Google Cloud Data Catalog API",
+        crate_name: "google-datacatalog1_beta1",
+        cli_crate_name: "google-datacatalog1_beta1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://datacatalog.googleapis.com/",
+        base_url: "https://datacatalog.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/data-catalog/docs/"),
+    },
+    ApiInfo {
+        name: "datacatalog",
+        version: "v1",
+        title: "Google Cloud Data Catalog API",
+        crate_name: "google-datacatalog1",
+        cli_crate_name: "google-datacatalog1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://datacatalog.googleapis.com/",
+        base_url: "https://datacatalog.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/data-catalog/docs/"),
+    },
+    ApiInfo {
+        name: "datafusion",
+        version: "v1",
+        title: "Cloud Data Fusion API",
+        crate_name: "google-datafusion1",
+        cli_crate_name: "google-datafusion1-cli",
+        crate_version: "5.0.5+20240111",
+        revision: "20240111",
+        root_url: "https://datafusion.googleapis.com/",
+        base_url: "https://datafusion.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/data-fusion/docs"),
+    },
+    ApiInfo {
+        name: "datafusion",
+        version: "v1beta1",
+        title: "Cloud Data Fusion API",
+        crate_name: "google-datafusion1_beta1",
+        cli_crate_name: "google-datafusion1_beta1-cli",
+        crate_version: "5.0.5+20240111",
+        revision: "20240111",
+        root_url: "https://datafusion.googleapis.com/",
+        base_url: "https://datafusion.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/data-fusion/docs"),
+    },
+    ApiInfo {
+        name: "datalabeling",
+        version: "v1beta1",
+        title: "Data Labeling API",
+        crate_name: "google-datalabeling1_beta1",
+        cli_crate_name: "google-datalabeling1_beta1-cli",
+        crate_version: "5.0.5+20240207",
+        revision: "20240207",
+        root_url: "https://datalabeling.googleapis.com/",
+        base_url: "https://datalabeling.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/data-labeling/docs/"),
+    },
+    ApiInfo {
+        name: "datamigration",
+        version: "v1",
+        title: "Database Migration API",
+        crate_name: "google-datamigration1",
+        cli_crate_name: "google-datamigration1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://datamigration.googleapis.com/",
+        base_url: "https://datamigration.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/database-migration/"),
+    },
+    ApiInfo {
+        name: "datapipelines",
+        version: "v1",
+        title: "Data pipelines API",
+        crate_name: "google-datapipelines1",
+        cli_crate_name: "google-datapipelines1-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://datapipelines.googleapis.com/",
+        base_url: "https://datapipelines.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dataflow/docs/guides/data-pipelines"),
+    },
+    ApiInfo {
+        name: "dataplex",
+        version: "v1",
+        title: "Cloud Dataplex API",
+        crate_name: "google-dataplex1",
+        cli_crate_name: "google-dataplex1-cli",
+        crate_version: "5.0.5+20240224",
+        revision: "20240224",
+        root_url: "https://dataplex.googleapis.com/",
+        base_url: "https://dataplex.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dataplex/docs"),
+    },
+    ApiInfo {
+        name: "dataproc",
+        version: "v1",
+        title: "Cloud Dataproc API",
+        crate_name: "google-dataproc1",
+        cli_crate_name: "google-dataproc1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://dataproc.googleapis.com/",
+        base_url: "https://dataproc.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dataproc/"),
+    },
+    ApiInfo {
+        name: "datastore",
+        version: "v1",
+        title: "Cloud Datastore API",
+        crate_name: "google-datastore1",
+        cli_crate_name: "google-datastore1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://datastore.googleapis.com/",
+        base_url: "https://datastore.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/datastore/"),
+    },
+    ApiInfo {
+        name: "datastore",
+        version: "v1beta3",
+        title: "Cloud Datastore API",
+        crate_name: "google-datastore1_beta3",
+        cli_crate_name: "google-datastore1_beta3-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://datastore.googleapis.com/",
+        base_url: "https://datastore.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/datastore/"),
+    },
+    ApiInfo {
+        name: "datastream",
+        version: "v1",
+        title: "Datastream API",
+        crate_name: "google-datastream1",
+        cli_crate_name: "google-datastream1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://datastream.googleapis.com/",
+        base_url: "https://datastream.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/datastream/"),
+    },
+    ApiInfo {
+        name: "deploymentmanager",
+        version: "v2",
+        title: "Cloud Deployment Manager V2 API",
+        crate_name: "google-deploymentmanager2",
+        cli_crate_name: "google-deploymentmanager2-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://deploymentmanager.googleapis.com/",
+        base_url: "https://deploymentmanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/deployment-manager"),
+    },
+    ApiInfo {
+        name: "deploymentmanager",
+        version: "v2beta2",
+        title: "Google Cloud Deployment Manager API",
+        crate_name: "google-deploymentmanager2_beta2",
+        cli_crate_name: "google-deploymentmanager2_beta2-cli",
+        crate_version: "5.0.5+20160201",
+        revision: "20160201",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/deploymentmanager/v2beta2/projects/",
+        documentation_link: Some("https://developers.google.com/deployment-manager/"),
+    },
+    ApiInfo {
+        name: "dfareporting",
+        version: "v2.8",
+        title: "DCM/DFA Reporting And Trafficking API",
+        crate_name: "google-dfareporting2d8",
+        cli_crate_name: "google-dfareporting2d8-cli",
+        crate_version: "5.0.5+20180830",
+        revision: "20180830",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/dfareporting/v2.8/",
+        documentation_link: Some("https://developers.google.com/doubleclick-advertisers/"),
+    },
+    ApiInfo {
+        name: "dfareporting",
+        version: "v3.0",
+        title: "DCM/DFA Reporting And Trafficking API",
+        crate_name: "google-dfareporting3",
+        cli_crate_name: "google-dfareporting3-cli",
+        crate_version: "5.0.5+20180830",
+        revision: "20180830",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/dfareporting/v3.0/",
+        documentation_link: Some("https://developers.google.com/doubleclick-advertisers/"),
+    },
+    ApiInfo {
+        name: "dfareporting",
+        version: "v3.2",
+        title: "DCM/DFA Reporting And Trafficking API",
+        crate_name: "google-dfareporting3d2",
+        cli_crate_name: "google-dfareporting3d2-cli",
+        crate_version: "5.0.5+20190531",
+        revision: "20190531",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/dfareporting/v3.2/",
+        documentation_link: Some("https://developers.google.com/doubleclick-advertisers/"),
+    },
+    ApiInfo {
+        name: "dfareporting",
+        version: "v3.3",
+        title: "Campaign Manager 360 API",
+        crate_name: "google-dfareporting3d3",
+        cli_crate_name: "google-dfareporting3d3-cli",
+        crate_version: "5.0.5+20220104",
+        revision: "20220104",
+        root_url: "https://dfareporting.googleapis.com/",
+        base_url: "https://dfareporting.googleapis.com/dfareporting/v3.3/",
+        documentation_link: Some("https://developers.google.com/doubleclick-advertisers/"),
+    },
+    ApiInfo {
+        name: "dfareporting",
+        version: "v3.4",
+        title: "Campaign Manager 360 API",
+        crate_name: "google-dfareporting3d4",
+        cli_crate_name: "google-dfareporting3d4-cli",
+        crate_version: "5.0.5+20230118",
+        revision: "20230118",
+        root_url: "https://dfareporting.googleapis.com/",
+        base_url: "https://dfareporting.googleapis.com/dfareporting/v3.4/",
+        documentation_link: Some("https://developers.google.com/doubleclick-advertisers/"),
+    },
+    ApiInfo {
+        name: "dfareporting",
+        version: "v3.5",
+        title: "Campaign Manager 360 API",
+        crate_name: "google-dfareporting3d5",
+        cli_crate_name: "google-dfareporting3d5-cli",
+        crate_version: "5.0.5+20240201",
+        revision: "20240201",
+        root_url: "https://dfareporting.googleapis.com/",
+        base_url: "https://dfareporting.googleapis.com/dfareporting/v3.5/",
+        documentation_link: Some("https://developers.google.com/doubleclick-advertisers/"),
+    },
+    ApiInfo {
+        name: "dialogflow",
+        version: "v2",
+        title: "Dialogflow API",
+        crate_name: "google-dialogflow2",
+        cli_crate_name: "google-dialogflow2-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://dialogflow.googleapis.com/",
+        base_url: "https://dialogflow.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dialogflow/"),
+    },
+    ApiInfo {
+        name: "dialogflow",
+        version: "v2beta1",
+        title: "Dialogflow API",
+        crate_name: "google-dialogflow2_beta1",
+        cli_crate_name: "google-dialogflow2_beta1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://dialogflow.googleapis.com/",
+        base_url: "https://dialogflow.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dialogflow/"),
+    },
+    ApiInfo {
+        name: "dialogflow",
+        version: "v3",
+        title: "Dialogflow API",
+        crate_name: "google-dialogflow3",
+        cli_crate_name: "google-dialogflow3-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://dialogflow.googleapis.com/",
+        base_url: "https://dialogflow.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dialogflow/"),
+    },
+    ApiInfo {
+        name: "digitalassetlinks",
+        version: "v1",
+        title: "Digital Asset Links API",
+        crate_name: "google-digitalassetlinks1",
+        cli_crate_name: "google-digitalassetlinks1-cli",
+        crate_version: "5.0.5+20240224",
+        revision: "20240224",
+        root_url: "https://digitalassetlinks.googleapis.com/",
+        base_url: "https://digitalassetlinks.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/digital-asset-links/"),
+    },
+    ApiInfo {
+        name: "discovery",
+        version: "v1",
+        title: "API Discovery Service",
+        crate_name: "google-discovery1",
+        cli_crate_name: "google-discovery1-cli",
+        crate_version: "5.0.5+20200806",
+        revision: "20200806",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/discovery/v1/",
+        documentation_link: Some("https://developers.google.com/discovery/"),
+    },
+    ApiInfo {
+        name: "displayvideo",
+        version: "v1",
+        title: "Display & Video 360 API",
+        crate_name: "google-displayvideo1",
+        cli_crate_name: "google-displayvideo1-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://displayvideo.googleapis.com/",
+        base_url: "https://displayvideo.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/display-video/"),
+    },
+    ApiInfo {
+        name: "dlp",
+        version: "v2",
+        title: "Sensitive Data Protection (DLP)",
+        crate_name: "google-dlp2",
+        cli_crate_name: "google-dlp2-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://dlp.googleapis.com/",
+        base_url: "https://dlp.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/sensitive-data-protection/docs/"),
+    },
+    ApiInfo {
+        name: "dlp",
+        version: "v2beta1",
+        title: "DLP API",
+        crate_name: "google-dlp2_beta1",
+        cli_crate_name: "google-dlp2_beta1-cli",
+        crate_version: "5.0.5+20171205",
+        revision: "20171205",
+        root_url: "https://dlp.googleapis.com/",
+        base_url: "https://dlp.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dlp/docs/"),
+    },
+    ApiInfo {
+        name: "dns",
+        version: "v1",
+        title: "Cloud DNS API",
+        crate_name: "google-dns1",
+        cli_crate_name: "google-dns1-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://dns.googleapis.com/",
+        base_url: "https://dns.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dns/docs"),
+    },
+    ApiInfo {
+        name: "dns",
+        version: "v2",
+        title: "Cloud DNS API",
+        crate_name: "google-dns2",
+        cli_crate_name: "google-dns2-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://dns.googleapis.com/",
+        base_url: "https://dns.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dns/docs"),
+    },
+    ApiInfo {
+        name: "docs",
+        version: "v1",
+        title: "Google Docs API",
+        crate_name: "google-docs1",
+        cli_crate_name: "google-docs1-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://docs.googleapis.com/",
+        base_url: "https://docs.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/docs/"),
+    },
+    ApiInfo {
+        name: "documentai",
+        version: "v1",
+        title: "Cloud Document AI API",
+        crate_name: "google-documentai1",
+        cli_crate_name: "google-documentai1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://documentai.googleapis.com/",
+        base_url: "https://documentai.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/document-ai/docs/"),
+    },
+    ApiInfo {
+        name: "documentai",
+        version: "v1beta2",
+        title: "Cloud Document AI API",
+        crate_name: "google-documentai1_beta2",
+        cli_crate_name: "google-documentai1_beta2-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://documentai.googleapis.com/",
+        base_url: "https://documentai.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/document-ai/docs/"),
+    },
+    ApiInfo {
+        name: "domains",
+        version: "v1beta1",
+        title: "Cloud Domains API",
+        crate_name: "google-domains1_beta1",
+        cli_crate_name: "google-domains1_beta1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://domains.googleapis.com/",
+        base_url: "https://domains.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/domains/"),
+    },
+    ApiInfo {
+        name: "domains",
+        version: "v1",
+        title: "Cloud Domains API",
+        crate_name: "google-domains1",
+        cli_crate_name: "google-domains1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://domains.googleapis.com/",
+        base_url: "https://domains.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/domains/"),
+    },
+    ApiInfo {
+        name: "domainsrdap",
+        version: "v1",
+        title: "Domains RDAP API",
+        crate_name: "google-domainsrdap1",
+        cli_crate_name: "google-domainsrdap1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://domainsrdap.googleapis.com/",
+        base_url: "https://domainsrdap.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/domains/rdap/"),
+    },
+    ApiInfo {
+        name: "doubleclickbidmanager",
+        version: "v1",
+        title: "DoubleClick Bid Manager API",
+        crate_name: "google-doubleclickbidmanager1",
+        cli_crate_name: "google-doubleclickbidmanager1-cli",
+        crate_version: "5.0.5+20210323",
+        revision: "20210323",
+        root_url: "https://doubleclickbidmanager.googleapis.com/",
+        base_url: "https://doubleclickbidmanager.googleapis.com/doubleclickbidmanager/v1/",
+        documentation_link: Some("https://developers.google.com/bid-manager/"),
+    },
+    ApiInfo {
+        name: "doubleclickbidmanager",
+        version: "v1.1",
+        title: "DoubleClick Bid Manager API",
+        crate_name: "google-doubleclickbidmanager1d1",
+        cli_crate_name: "google-doubleclickbidmanager1d1-cli",
+        crate_version: "5.0.5+20230117",
+        revision: "20230117",
+        root_url: "https://doubleclickbidmanager.googleapis.com/",
+        base_url: "https://doubleclickbidmanager.googleapis.com/doubleclickbidmanager/v1.1/",
+        documentation_link: Some("https://developers.google.com/bid-manager/"),
+    },
+    ApiInfo {
+        name: "doubleclicksearch",
+        version: "v2",
+        title: "Search Ads 360 API",
+        crate_name: "google-doubleclicksearch2",
+        cli_crate_name: "google-doubleclicksearch2-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://doubleclicksearch.googleapis.com/",
+        base_url: "https://doubleclicksearch.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/search-ads"),
+    },
+    ApiInfo {
+        name: "drive",
+        version: "v2",
+        title: "Google Drive API",
+        crate_name: "google-drive2",
+        cli_crate_name: "google-drive2-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/drive/v2/",
+        documentation_link: Some("https://developers.google.com/drive/"),
+    },
+    ApiInfo {
+        name: "drive",
+        version: "v3",
+        title: "Google Drive API",
+        crate_name: "google-drive3",
+        cli_crate_name: "google-drive3-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/drive/v3/",
+        documentation_link: Some("https://developers.google.com/drive/"),
+    },
+    ApiInfo {
+        name: "driveactivity",
+        version: "v2",
+        title: "Drive Activity API",
+        crate_name: "google-driveactivity2",
+        cli_crate_name: "google-driveactivity2-cli",
+        crate_version: "5.0.5+20240302",
+        revision: "20240302",
+        root_url: "https://driveactivity.googleapis.com/",
+        base_url: "https://driveactivity.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/drive/activity/"),
+    },
+    ApiInfo {
+        name: "essentialcontacts",
+        version: "v1",
+        title: "Essential Contacts API",
+        crate_name: "google-essentialcontacts1",
+        cli_crate_name: "google-essentialcontacts1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://essentialcontacts.googleapis.com/",
+        base_url: "https://essentialcontacts.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/essentialcontacts/docs/"),
+    },
+    ApiInfo {
+        name: "eventarc",
+        version: "v1",
+        title: "Eventarc API",
+        crate_name: "google-eventarc1",
+        cli_crate_name: "google-eventarc1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://eventarc.googleapis.com/",
+        base_url: "https://eventarc.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/eventarc"),
+    },
+    ApiInfo {
+        name: "factchecktools",
+        version: "v1alpha1",
+        title: "Fact Check Tools API",
+        crate_name: "google-factchecktools1_alpha1",
+        cli_crate_name: "google-factchecktools1_alpha1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://factchecktools.googleapis.com/",
+        base_url: "https://factchecktools.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/fact-check/tools/api/"),
+    },
+    ApiInfo {
+        name: "fcm",
+        version: "v1",
+        title: "Firebase Cloud Messaging API",
+        crate_name: "google-fcm1",
+        cli_crate_name: "google-fcm1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://fcm.googleapis.com/",
+        base_url: "https://fcm.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/cloud-messaging"),
+    },
+    ApiInfo {
+        name: "fcmdata",
+        version: "v1beta1",
+        title: "Firebase Cloud Messaging Data API",
+        crate_name: "google-fcmdata1_beta1",
+        cli_crate_name: "google-fcmdata1_beta1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://fcmdata.googleapis.com/",
+        base_url: "https://fcmdata.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/cloud-messaging"),
+    },
+    ApiInfo {
+        name: "file",
+        version: "v1",
+        title: "Cloud Filestore API",
+        crate_name: "google-file1",
+        cli_crate_name: "google-file1-cli",
+        crate_version: "5.0.5+20240126",
+        revision: "20240126",
+        root_url: "https://file.googleapis.com/",
+        base_url: "https://file.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/filestore/"),
+    },
+    ApiInfo {
+        name: "file",
+        version: "v1beta1",
+        title: "Cloud Filestore API",
+        crate_name: "google-file1_beta1",
+        cli_crate_name: "google-file1_beta1-cli",
+        crate_version: "5.0.5+20240126",
+        revision: "20240126",
+        root_url: "https://file.googleapis.com/",
+        base_url: "https://file.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/filestore/"),
+    },
+    ApiInfo {
+        name: "firebase",
+        version: "v1beta1",
+        title: "Firebase Management API",
+        crate_name: "google-firebase1_beta1",
+        cli_crate_name: "google-firebase1_beta1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://firebase.googleapis.com/",
+        base_url: "https://firebase.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com"),
+    },
+    ApiInfo {
+        name: "firebaseappcheck",
+        version: "v1beta",
+        title: "Firebase App Check API",
+        crate_name: "google-firebaseappcheck1_beta",
+        cli_crate_name: "google-firebaseappcheck1_beta-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://firebaseappcheck.googleapis.com/",
+        base_url: "https://firebaseappcheck.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/app-check"),
+    },
+    ApiInfo {
+        name: "firebasedatabase",
+        version: "v1beta",
+        title: "Firebase Realtime Database API",
+        crate_name: "google-firebasedatabase1_beta",
+        cli_crate_name: "google-firebasedatabase1_beta-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://firebasedatabase.googleapis.com/",
+        base_url: "https://firebasedatabase.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/reference/rest/database/database-management/rest/"),
+    },
+    ApiInfo {
+        name: "firebasedynamiclinks",
+        version: "v1",
+        title: "Firebase Dynamic Links API",
+        crate_name: "google-firebasedynamiclinks1",
+        cli_crate_name: "google-firebasedynamiclinks1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://firebasedynamiclinks.googleapis.com/",
+        base_url: "https://firebasedynamiclinks.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/dynamic-links/"),
+    },
+    ApiInfo {
+        name: "firebasehosting",
+        version: "v1",
+        title: "Firebase Hosting API",
+        crate_name: "google-firebasehosting1",
+        cli_crate_name: "google-firebasehosting1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://firebasehosting.googleapis.com/",
+        base_url: "https://firebasehosting.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/hosting/"),
+    },
+    ApiInfo {
+        name: "firebasehosting",
+        version: "v1beta1",
+        title: "Firebase Hosting API",
+        crate_name: "google-firebasehosting1_beta1",
+        cli_crate_name: "google-firebasehosting1_beta1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://firebasehosting.googleapis.com/",
+        base_url: "https://firebasehosting.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/hosting/"),
+    },
+    ApiInfo {
+        name: "firebaseml",
+        version: "v1",
+        title: "Firebase ML API",
+        crate_name: "google-firebaseml1",
+        cli_crate_name: "google-firebaseml1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://firebaseml.googleapis.com/",
+        base_url: "https://firebaseml.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com"),
+    },
+    ApiInfo {
+        name: "firebaseremoteconfig",
+        version: "v1",
+        title: "Firebase Remote Config API",
+        crate_name: "google-firebaseremoteconfig1",
+        cli_crate_name: "google-firebaseremoteconfig1-cli",
+        crate_version: "5.0.5+20171129",
+        revision: "20171129",
+        root_url: "https://firebaseremoteconfig.googleapis.com/",
+        base_url: "https://firebaseremoteconfig.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/remote-config/"),
+    },
+    ApiInfo {
+        name: "firebasestorage",
+        version: "v1beta",
+        title: "Cloud Storage for Firebase API",
+        crate_name: "google-firebasestorage1_beta",
+        cli_crate_name: "google-firebasestorage1_beta-cli",
+        crate_version: "5.0.5+20240301",
+        revision: "20240301",
+        root_url: "https://firebasestorage.googleapis.com/",
+        base_url: "https://firebasestorage.googleapis.com/",
+        documentation_link: Some("https://firebase.google.com/docs/storage"),
+    },
+    ApiInfo {
+        name: "firestore",
+        version: "v1",
+        title: "Cloud Firestore API",
+        crate_name: "google-firestore1",
+        cli_crate_name: "google-firestore1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://firestore.googleapis.com/",
+        base_url: "https://firestore.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/firestore"),
+    },
+    ApiInfo {
+        name: "firestore",
+        version: "v1beta1",
+        title: "Cloud Firestore API",
+        crate_name: "google-firestore1_beta1",
+        cli_crate_name: "google-firestore1_beta1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://firestore.googleapis.com/",
+        base_url: "https://firestore.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/firestore"),
+    },
+    ApiInfo {
+        name: "fitness",
+        version: "v1",
+        title: "Fitness API",
+        crate_name: "google-fitness1",
+        cli_crate_name: "google-fitness1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://fitness.googleapis.com/",
+        base_url: "https://fitness.googleapis.com/fitness/v1/users/",
+        documentation_link: Some("https://developers.google.com/fit/rest/v1/get-started"),
+    },
+    ApiInfo {
+        name: "fusiontables",
+        version: "v2",
+        title: "Fusion Tables API",
+        crate_name: "google-fusiontables2",
+        cli_crate_name: "google-fusiontables2-cli",
+        crate_version: "5.0.5+20171117",
+        revision: "20171117",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/fusiontables/v2/",
+        documentation_link: Some("https://developers.google.com/fusiontables"),
+    },
+    ApiInfo {
+        name: "games",
+        version: "v1",
+        title: "Google Play Game Services",
+        crate_name: "google-games1",
+        cli_crate_name: "google-games1-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://games.googleapis.com/",
+        base_url: "https://games.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/games/"),
+    },
+    ApiInfo {
+        name: "gamesconfiguration",
+        version: "v1configuration",
+        title: "Google Play Game Services Publishing API",
+        crate_name: "google-gamesconfiguration1_configuration",
+        cli_crate_name: "google-gamesconfiguration1_configuration-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://gamesconfiguration.googleapis.com/",
+        base_url: "https://gamesconfiguration.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/games/"),
+    },
+    ApiInfo {
+        name: "gameservices",
+        version: "v1",
+        title: "Game Services API",
+        crate_name: "google-gameservices1",
+        cli_crate_name: "google-gameservices1-cli",
+        crate_version: "5.0.5+20230105",
+        revision: "20230105",
+        root_url: "https://gameservices.googleapis.com/",
+        base_url: "https://gameservices.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/solutions/gaming/"),
+    },
+    ApiInfo {
+        name: "gamesmanagement",
+        version: "v1management",
+        title: "Google Play Game Management",
+        crate_name: "google-gamesmanagement1_management",
+        cli_crate_name: "google-gamesmanagement1_management-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://gamesmanagement.googleapis.com/",
+        base_url: "https://gamesmanagement.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/games/"),
+    },
+    ApiInfo {
+        name: "gan",
+        version: "v1beta1",
+        title: "Google Affiliate Network API",
+        crate_name: "google-gan1_beta1",
+        cli_crate_name: "google-gan1_beta1-cli",
+        crate_version: "5.0.5+20130205",
+        revision: "20130205",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/gan/v1beta1/",
+        documentation_link: Some("https://developers.google.com/affiliate-network/"),
+    },
+    ApiInfo {
+        name: "genomics",
+        version: "v1",
+        title: "Genomics API",
+        crate_name: "google-genomics1",
+        cli_crate_name: "google-genomics1-cli",
+        crate_version: "5.0.5+20210324",
+        revision: "20210324",
+        root_url: "https://genomics.googleapis.com/",
+        base_url: "https://genomics.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/genomics"),
+    },
+    ApiInfo {
+        name: "gmail",
+        version: "v1",
+        title: "Gmail API",
+        crate_name: "google-gmail1",
+        cli_crate_name: "google-gmail1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://gmail.googleapis.com/",
+        base_url: "https://gmail.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/gmail/api/"),
+    },
+    ApiInfo {
+        name: "gmailpostmastertools",
+        version: "v1",
+        title: "Gmail Postmaster Tools API",
+        crate_name: "google-gmailpostmastertools1",
+        cli_crate_name: "google-gmailpostmastertools1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://gmailpostmastertools.googleapis.com/",
+        base_url: "https://gmailpostmastertools.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/gmail/postmaster"),
+    },
+    ApiInfo {
+        name: "gmailpostmastertools",
+        version: "v1beta1",
+        title: "Gmail Postmaster Tools API",
+        crate_name: "google-gmailpostmastertools1_beta1",
+        cli_crate_name: "google-gmailpostmastertools1_beta1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://gmailpostmastertools.googleapis.com/",
+        base_url: "https://gmailpostmastertools.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/gmail/postmaster"),
+    },
+    ApiInfo {
+        name: "groupsmigration",
+        version: "v1",
+        title: "Groups Migration API",
+        crate_name: "google-groupsmigration1",
+        cli_crate_name: "google-groupsmigration1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://groupsmigration.googleapis.com/",
+        base_url: "https://groupsmigration.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/google-apps/groups-migration/"),
+    },
+    ApiInfo {
+        name: "groupssettings",
+        version: "v1",
+        title: "Groups Settings API",
+        crate_name: "google-groupssettings1",
+        cli_crate_name: "google-groupssettings1-cli",
+        crate_version: "5.0.5+20220614",
+        revision: "20220614",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/groups/v1/groups/",
+        documentation_link: Some("https://developers.google.com/google-apps/groups-settings/get_started"),
+    },
+    ApiInfo {
+        name: "healthcare",
+        version: "v1",
+        title: "Cloud Healthcare API",
+        crate_name: "google-healthcare1",
+        cli_crate_name: "google-healthcare1-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://healthcare.googleapis.com/",
+        base_url: "https://healthcare.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/healthcare"),
+    },
+    ApiInfo {
+        name: "healthcare",
+        version: "v1beta1",
+        title: "Cloud Healthcare API",
+        crate_name: "google-healthcare1_beta1",
+        cli_crate_name: "google-healthcare1_beta1-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://healthcare.googleapis.com/",
+        base_url: "https://healthcare.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/healthcare"),
+    },
+    ApiInfo {
+        name: "iam",
+        version: "v1",
+        title: "Identity and Access Management (IAM) API",
+        crate_name: "google-iam1",
+        cli_crate_name: "google-iam1-cli",
+        crate_version: "5.0.5+20240220",
+        revision: "20240220",
+        root_url: "https://iam.googleapis.com/",
+        base_url: "https://iam.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/iam/"),
+    },
+    ApiInfo {
+        name: "iamcredentials",
+        version: "v1",
+        title: "IAM Service Account Credentials API",
+        crate_name: "google-iamcredentials1",
+        cli_crate_name: "google-iamcredentials1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://iamcredentials.googleapis.com/",
+        base_url: "https://iamcredentials.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/iam/docs/creating-short-lived-service-account-credentials"),
+    },
+    ApiInfo {
+        name: "iap",
+        version: "v1",
+        title: "Cloud Identity-Aware Proxy API",
+        crate_name: "google-iap1",
+        cli_crate_name: "google-iap1-cli",
+        crate_version: "5.0.5+20240224",
+        revision: "20240224",
+        root_url: "https://iap.googleapis.com/",
+        base_url: "https://iap.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/iap"),
+    },
+    ApiInfo {
+        name: "iap",
+        version: "v1beta1",
+        title: "Cloud Identity-Aware Proxy API",
+        crate_name: "google-iap1_beta1",
+        cli_crate_name: "google-iap1_beta1-cli",
+        crate_version: "5.0.5+20240224",
+        revision: "20240224",
+        root_url: "https://iap.googleapis.com/",
+        base_url: "https://iap.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/iap"),
+    },
+    ApiInfo {
+        name: "ideahub",
+        version: "v1beta",
+        title: "Idea Hub API",
+        crate_name: "google-ideahub1_beta",
+        cli_crate_name: "google-ideahub1_beta-cli",
+        crate_version: "5.0.5+20220305",
+        revision: "20220305",
+        root_url: "https://ideahub.googleapis.com/",
+        base_url: "https://ideahub.googleapis.com/",
+        documentation_link: Some("https://console.cloud.google.com/apis/library/ideahub.googleapis.com"),
+    },
+    ApiInfo {
+        name: "identitytoolkit",
+        version: "v3",
+        title: "Google Identity Toolkit API",
+        crate_name: "google-identitytoolkit3",
+        cli_crate_name: "google-identitytoolkit3-cli",
+        crate_version: "5.0.5+20180723",
+        revision: "20180723",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/identitytoolkit/v3/relyingparty/",
+        documentation_link: Some("https://developers.google.com/identity-toolkit/v3/"),
+    },
+    ApiInfo {
+        name: "ids",
+        version: "v1",
+        title: "Cloud IDS API",
+        crate_name: "google-ids1",
+        cli_crate_name: "google-ids1-cli",
+        crate_version: "5.0.5+20231203",
+        revision: "20231203",
+        root_url: "https://ids.googleapis.com/",
+        base_url: "https://ids.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/"),
+    },
+    ApiInfo {
+        name: "indexing",
+        version: "v3",
+        title: "Web Search Indexing API",
+        crate_name: "google-indexing3",
+        cli_crate_name: "google-indexing3-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://indexing.googleapis.com/",
+        base_url: "https://indexing.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/search/apis/indexing-api/"),
+    },
+    ApiInfo {
+        name: "jobs",
+        version: "v3",
+        title: "Cloud Talent Solution API",
+        crate_name: "google-jobs3",
+        cli_crate_name: "google-jobs3-cli",
+        crate_version: "5.0.5+20240202",
+        revision: "20240202",
+        root_url: "https://jobs.googleapis.com/",
+        base_url: "https://jobs.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/talent-solution/job-search/docs/"),
+    },
+    ApiInfo {
+        name: "jobs",
+        version: "v4",
+        title: "Cloud Talent Solution API",
+        crate_name: "google-jobs4",
+        cli_crate_name: "google-jobs4-cli",
+        crate_version: "5.0.5+20240202",
+        revision: "20240202",
+        root_url: "https://jobs.googleapis.com/",
+        base_url: "https://jobs.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/talent-solution/job-search/docs/"),
+    },
+    ApiInfo {
+        name: "keep",
+        version: "v1",
+        title: "Google Keep API",
+        crate_name: "google-keep1",
+        cli_crate_name: "google-keep1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://keep.googleapis.com/",
+        base_url: "https://keep.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/keep/api"),
+    },
+    ApiInfo {
+        name: "language",
+        version: "v1",
+        title: "Cloud Natural Language API",
+        crate_name: "google-language1",
+        cli_crate_name: "google-language1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://language.googleapis.com/",
+        base_url: "https://language.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/natural-language/"),
+    },
+    ApiInfo {
+        name: "language",
+        version: "v1beta1",
+        title: "Cloud Natural Language API",
+        crate_name: "google-language1_beta1",
+        cli_crate_name: "google-language1_beta1-cli",
+        crate_version: "5.0.5+20220218",
+        revision: "20220218",
+        root_url: "https://language.googleapis.com/",
+        base_url: "https://language.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/natural-language/"),
+    },
+    ApiInfo {
+        name: "libraryagent",
+        version: "v1",
+        title: "Library Agent API",
+        crate_name: "google-libraryagent1",
+        cli_crate_name: "google-libraryagent1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://libraryagent.googleapis.com/",
+        base_url: "https://libraryagent.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/docs/quota"),
+    },
+    ApiInfo {
+        name: "licensing",
+        version: "v1",
+        title: "Enterprise License Manager API",
+        crate_name: "google-licensing1",
+        cli_crate_name: "google-licensing1-cli",
+        crate_version: "5.0.5+20240301",
+        revision: "20240301",
+        root_url: "https://licensing.googleapis.com/",
+        base_url: "https://licensing.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/admin-sdk/licensing/"),
+    },
+    ApiInfo {
+        name: "lifesciences",
+        version: "v2beta",
+        title: "Cloud Life Sciences API",
+        crate_name: "google-lifesciences2_beta",
+        cli_crate_name: "google-lifesciences2_beta-cli",
+        crate_version: "5.0.5+20240220",
+        revision: "20240220",
+        root_url: "https://lifesciences.googleapis.com/",
+        base_url: "https://lifesciences.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/life-sciences"),
+    },
+    ApiInfo {
+        name: "localservices",
+        version: "v1",
+        title: "Local Services API",
+        crate_name: "google-localservices1",
+        cli_crate_name: "google-localservices1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://localservices.googleapis.com/",
+        base_url: "https://localservices.googleapis.com/",
+        documentation_link: Some("https://ads.google.com/local-services-ads/"),
+    },
+    ApiInfo {
+        name: "logging",
+        version: "v2",
+        title: "Cloud Logging API",
+        crate_name: "google-logging2",
+        cli_crate_name: "google-logging2-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://logging.googleapis.com/",
+        base_url: "https://logging.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/logging/docs/"),
+    },
+    ApiInfo {
+        name: "logging",
+        version: "v2beta1",
+        title: "Stackdriver Logging API",
+        crate_name: "google-logging2_beta1",
+        cli_crate_name: "google-logging2_beta1-cli",
+        crate_version: "5.0.5+20190325",
+        revision: "20190325",
+        root_url: "https://logging.googleapis.com/",
+        base_url: "https://logging.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/logging/docs/"),
+    },
+    ApiInfo {
+        name: "managedidentities",
+        version: "v1",
+        title: "Managed Service for Microsoft Active Directory API",
+        crate_name: "google-managedidentities1",
+        cli_crate_name: "google-managedidentities1-cli",
+        crate_version: "5.0.5+20240112",
+        revision: "20240112",
+        root_url: "https://managedidentities.googleapis.com/",
+        base_url: "https://managedidentities.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/managed-microsoft-ad/"),
+    },
+    ApiInfo {
+        name: "manager",
+        version: "v1beta2",
+        title: "Deployment Manager API",
+        crate_name: "google-manager1_beta2",
+        cli_crate_name: "google-manager1_beta2-cli",
+        crate_version: "5.0.5+20140915",
+        revision: "20140915",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/manager/v1beta2/projects/",
+        documentation_link: Some("https://developers.google.com/deployment-manager/"),
+    },
+    ApiInfo {
+        name: "manufacturers",
+        version: "v1",
+        title: "Manufacturer Center API",
+        crate_name: "google-manufacturers1",
+        cli_crate_name: "google-manufacturers1-cli",
+        crate_version: "5.0.5+20240205",
+        revision: "20240205",
+        root_url: "https://manufacturers.googleapis.com/",
+        base_url: "https://manufacturers.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/manufacturers/"),
+    },
+    ApiInfo {
+        name: "memcache",
+        version: "v1",
+        title: "Cloud Memorystore for Memcached API",
+        crate_name: "google-memcache1",
+        cli_crate_name: "google-memcache1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://memcache.googleapis.com/",
+        base_url: "https://memcache.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/memorystore/"),
+    },
+    ApiInfo {
+        name: "memcache",
+        version: "v1beta2",
+        title: "Cloud Memorystore for Memcached API",
+        crate_name: "google-memcache1_beta2",
+        cli_crate_name: "google-memcache1_beta2-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://memcache.googleapis.com/",
+        base_url: "https://memcache.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/memorystore/"),
+    },
+    ApiInfo {
+        name: "metastore",
+        version: "v1beta",
+        title: "Dataproc Metastore API",
+        crate_name: "google-metastore1_beta",
+        cli_crate_name: "google-metastore1_beta-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://metastore.googleapis.com/",
+        base_url: "https://metastore.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/dataproc-metastore/docs"),
+    },
+    ApiInfo {
+        name: "mirror",
+        version: "v1",
+        title: "Google Mirror API",
+        crate_name: "google-mirror1",
+        cli_crate_name: "google-mirror1-cli",
+        crate_version: "5.0.5+20190424",
+        revision: "20190424",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/mirror/v1/",
+        documentation_link: Some("https://developers.google.com/glass"),
+    },
+    ApiInfo {
+        name: "ml",
+        version: "v1",
+        title: "AI Platform Training & Prediction API",
+        crate_name: "google-ml1",
+        cli_crate_name: "google-ml1-cli",
+        crate_version: "5.0.5+20240127",
+        revision: "20240127",
+        root_url: "https://ml.googleapis.com/",
+        base_url: "https://ml.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/ml/"),
+    },
+    ApiInfo {
+        name: "monitoring",
+        version: "v3",
+        title: "Cloud Monitoring API",
+        crate_name: "google-monitoring3",
+        cli_crate_name: "google-monitoring3-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://monitoring.googleapis.com/",
+        base_url: "https://monitoring.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/monitoring/api/"),
+    },
+    ApiInfo {
+        name: "mybusiness",
+        version: "v4",
+        title: "Google My Business API",
+        crate_name: "google-mybusiness4",
+        cli_crate_name: "google-mybusiness4-cli",
+        crate_version: "5.0.5+0",
+        revision: "0",
+        root_url: "https://mybusiness.googleapis.com/",
+        base_url: "https://mybusiness.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "mybusinessaccountmanagement",
+        version: "v1",
+        title: "My Business Account Management API",
+        crate_name: "google-mybusinessaccountmanagement1",
+        cli_crate_name: "google-mybusinessaccountmanagement1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://mybusinessaccountmanagement.googleapis.com/",
+        base_url: "https://mybusinessaccountmanagement.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "mybusinessbusinesscalls",
+        version: "v1",
+        title: "My Business Business Calls API",
+        crate_name: "google-mybusinessbusinesscalls1",
+        cli_crate_name: "google-mybusinessbusinesscalls1-cli",
+        crate_version: "5.0.5+20230124",
+        revision: "20230124",
+        root_url: "https://mybusinessbusinesscalls.googleapis.com/",
+        base_url: "https://mybusinessbusinesscalls.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "mybusinessbusinessinformation",
+        version: "v1",
+        title: "My Business Business Information API",
+        crate_name: "google-mybusinessbusinessinformation1",
+        cli_crate_name: "google-mybusinessbusinessinformation1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://mybusinessbusinessinformation.googleapis.com/",
+        base_url: "https://mybusinessbusinessinformation.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "mybusinesslodging",
+        version: "v1",
+        title: "My Business Lodging API",
+        crate_name: "google-mybusinesslodging1",
+        cli_crate_name: "google-mybusinesslodging1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://mybusinesslodging.googleapis.com/",
+        base_url: "https://mybusinesslodging.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "mybusinessnotifications",
+        version: "v1",
+        title: "My Business Notifications API",
+        crate_name: "google-mybusinessnotifications1",
+        cli_crate_name: "google-mybusinessnotifications1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://mybusinessnotifications.googleapis.com/",
+        base_url: "https://mybusinessnotifications.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "mybusinessplaceactions",
+        version: "v1",
+        title: "My Business Place Actions API",
+        crate_name: "google-mybusinessplaceactions1",
+        cli_crate_name: "google-mybusinessplaceactions1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://mybusinessplaceactions.googleapis.com/",
+        base_url: "https://mybusinessplaceactions.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "mybusinessverifications",
+        version: "v1",
+        title: "My Business Verifications API",
+        crate_name: "google-mybusinessverifications1",
+        cli_crate_name: "google-mybusinessverifications1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://mybusinessverifications.googleapis.com/",
+        base_url: "https://mybusinessverifications.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/my-business/"),
+    },
+    ApiInfo {
+        name: "networkconnectivity",
+        version: "v1alpha1",
+        title: "Network Connectivity API",
+        crate_name: "google-networkconnectivity1_alpha1",
+        cli_crate_name: "google-networkconnectivity1_alpha1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://networkconnectivity.googleapis.com/",
+        base_url: "https://networkconnectivity.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/network-connectivity/docs/reference/networkconnectivity/rest"),
+    },
+    ApiInfo {
+        name: "networkconnectivity",
+        version: "v1",
+        title: "Network Connectivity API",
+        crate_name: "google-networkconnectivity1",
+        cli_crate_name: "google-networkconnectivity1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://networkconnectivity.googleapis.com/",
+        base_url: "https://networkconnectivity.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/network-connectivity/docs/reference/networkconnectivity/rest"),
+    },
+    ApiInfo {
+        name: "networkmanagement",
+        version: "v1",
+        title: "Network Management API",
+        crate_name: "google-networkmanagement1",
+        cli_crate_name: "google-networkmanagement1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://networkmanagement.googleapis.com/",
+        base_url: "https://networkmanagement.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/"),
+    },
+    ApiInfo {
+        name: "networksecurity",
+        version: "v1",
+        title: "Network Security API",
+        crate_name: "google-networksecurity1",
+        cli_crate_name: "google-networksecurity1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://networksecurity.googleapis.com/",
+        base_url: "https://networksecurity.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/networking"),
+    },
+    ApiInfo {
+        name: "networkservices",
+        version: "v1",
+        title: "Network Services API",
+        crate_name: "google-networkservices1",
+        cli_crate_name: "google-networkservices1-cli",
+        crate_version: "5.0.5+20240224",
+        revision: "20240224",
+        root_url: "https://networkservices.googleapis.com/",
+        base_url: "https://networkservices.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/networking"),
+    },
+    ApiInfo {
+        name: "notebooks",
+        version: "v1",
+        title: "Notebooks API",
+        crate_name: "google-notebooks1",
+        cli_crate_name: "google-notebooks1-cli",
+        crate_version: "5.0.5+20231227",
+        revision: "20231227",
+        root_url: "https://notebooks.googleapis.com/",
+        base_url: "https://notebooks.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/notebooks/docs/"),
+    },
+    ApiInfo {
+        name: "ondemandscanning",
+        version: "v1",
+        title: "On-Demand Scanning API",
+        crate_name: "google-ondemandscanning1",
+        cli_crate_name: "google-ondemandscanning1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://ondemandscanning.googleapis.com/",
+        base_url: "https://ondemandscanning.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/container-analysis/docs/on-demand-scanning/"),
+    },
+    ApiInfo {
+        name: "orgpolicy",
+        version: "v2",
+        title: "Organization Policy API",
+        crate_name: "google-orgpolicy2",
+        cli_crate_name: "google-orgpolicy2-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://orgpolicy.googleapis.com/",
+        base_url: "https://orgpolicy.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/orgpolicy/docs/reference/rest/index.html"),
+    },
+    ApiInfo {
+        name: "oslogin",
+        version: "v1",
+        title: "Cloud OS Login API",
+        crate_name: "google-oslogin1",
+        cli_crate_name: "google-oslogin1-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://oslogin.googleapis.com/",
+        base_url: "https://oslogin.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/compute/docs/oslogin/"),
+    },
+    ApiInfo {
+        name: "oslogin",
+        version: "v1beta",
+        title: "Cloud OS Login API",
+        crate_name: "google-oslogin1_beta",
+        cli_crate_name: "google-oslogin1_beta-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://oslogin.googleapis.com/",
+        base_url: "https://oslogin.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/compute/docs/oslogin/"),
+    },
+    ApiInfo {
+        name: "pagespeedonline",
+        version: "v2",
+        title: "PageSpeed Insights API",
+        crate_name: "google-pagespeedonline2",
+        cli_crate_name: "google-pagespeedonline2-cli",
+        crate_version: "5.0.5+20191206",
+        revision: "20191206",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/pagespeedonline/v2/",
+        documentation_link: Some("https://developers.google.com/speed/docs/insights/v2/getting-started"),
+    },
+    ApiInfo {
+        name: "pagespeedonline",
+        version: "v4",
+        title: "PageSpeed Insights API",
+        crate_name: "google-pagespeedonline4",
+        cli_crate_name: "google-pagespeedonline4-cli",
+        crate_version: "5.0.5+20191206",
+        revision: "20191206",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/pagespeedonline/v4/",
+        documentation_link: Some("https://developers.google.com/speed/docs/insights/v4/getting-started"),
+    },
+    ApiInfo {
+        name: "pagespeedonline",
+        version: "v5",
+        title: "PageSpeed Insights API",
+        crate_name: "google-pagespeedonline5",
+        cli_crate_name: "google-pagespeedonline5-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://pagespeedonline.googleapis.com/",
+        base_url: "https://pagespeedonline.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/speed/docs/insights/v5/about"),
+    },
+    ApiInfo {
+        name: "partners",
+        version: "v2",
+        title: "Google Partners API",
+        crate_name: "google-partners2",
+        cli_crate_name: "google-partners2-cli",
+        crate_version: "5.0.5+20180925",
+        revision: "20180925",
+        root_url: "https://partners.googleapis.com/",
+        base_url: "https://partners.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/partners/"),
+    },
+    ApiInfo {
+        name: "paymentsresellersubscription",
+        version: "v1",
+        title: "Payments Reseller Subscription API",
+        crate_name: "google-paymentsresellersubscription1",
+        cli_crate_name: "google-paymentsresellersubscription1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://paymentsresellersubscription.googleapis.com/",
+        base_url: "https://paymentsresellersubscription.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/payments/reseller/subscription/"),
+    },
+    ApiInfo {
+        name: "people",
+        version: "v1",
+        title: "People API",
+        crate_name: "google-people1",
+        cli_crate_name: "google-people1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://people.googleapis.com/",
+        base_url: "https://people.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/people/"),
+    },
+    ApiInfo {
+        name: "photoslibrary",
+        version: "v1",
+        title: "Photos Library API",
+        crate_name: "google-photoslibrary1",
+        cli_crate_name: "google-photoslibrary1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://photoslibrary.googleapis.com/",
+        base_url: "https://photoslibrary.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/photos/"),
+    },
+    ApiInfo {
+        name: "playablelocations",
+        version: "v3",
+        title: "Playable Locations API",
+        crate_name: "google-playablelocations3",
+        cli_crate_name: "google-playablelocations3-cli",
+        crate_version: "5.0.5+20200707",
+        revision: "20200707",
+        root_url: "https://playablelocations.googleapis.com/",
+        base_url: "https://playablelocations.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/maps/contact-sales/"),
+    },
+    ApiInfo {
+        name: "playcustomapp",
+        version: "v1",
+        title: "Google Play Custom App Publishing API",
+        crate_name: "google-playcustomapp1",
+        cli_crate_name: "google-playcustomapp1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://playcustomapp.googleapis.com/",
+        base_url: "https://playcustomapp.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/android/work/play/custom-app-api/"),
+    },
+    ApiInfo {
+        name: "playintegrity",
+        version: "v1",
+        title: "Google Play Integrity API",
+        crate_name: "google-playintegrity1",
+        cli_crate_name: "google-playintegrity1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://playintegrity.googleapis.com/",
+        base_url: "https://playintegrity.googleapis.com/",
+        documentation_link: Some("https://developer.android.com/google/play/integrity"),
+    },
+    ApiInfo {
+        name: "playmoviespartner",
+        version: "v1",
+        title: "Google Play Movies Partner API",
+        crate_name: "google-playmoviespartner1",
+        cli_crate_name: "google-playmoviespartner1-cli",
+        crate_version: "5.0.5+20170919",
+        revision: "20170919",
+        root_url: "https://playmoviespartner.googleapis.com/",
+        base_url: "https://playmoviespartner.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/playmoviespartner/"),
+    },
+    ApiInfo {
+        name: "plus",
+        version: "v1",
+        title: "Google+ API",
+        crate_name: "google-plus1",
+        cli_crate_name: "google-plus1-cli",
+        crate_version: "5.0.5+20190616",
+        revision: "20190616",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/plus/v1/",
+        documentation_link: Some("https://developers.google.com/+/api/"),
+    },
+    ApiInfo {
+        name: "plusdomains",
+        version: "v1",
+        title: "Google+ Domains API",
+        crate_name: "google-plusdomains1",
+        cli_crate_name: "google-plusdomains1-cli",
+        crate_version: "5.0.5+20190616",
+        revision: "20190616",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/plusDomains/v1/",
+        documentation_link: Some("https://developers.google.com/+/domains/"),
+    },
+    ApiInfo {
+        name: "policyanalyzer",
+        version: "v1",
+        title: "Policy Analyzer API",
+        crate_name: "google-policyanalyzer1",
+        cli_crate_name: "google-policyanalyzer1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://policyanalyzer.googleapis.com/",
+        base_url: "https://policyanalyzer.googleapis.com/",
+        documentation_link: Some("https://www.google.com"),
+    },
+    ApiInfo {
+        name: "policysimulator",
+        version: "v1",
+        title: "Policy Simulator API",
+        crate_name: "google-policysimulator1",
+        cli_crate_name: "google-policysimulator1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://policysimulator.googleapis.com/",
+        base_url: "https://policysimulator.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/iam/docs/simulating-access"),
+    },
+    ApiInfo {
+        name: "policytroubleshooter",
+        version: "v1",
+        title: "Policy Troubleshooter API",
+        crate_name: "google-policytroubleshooter1",
+        cli_crate_name: "google-policytroubleshooter1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://policytroubleshooter.googleapis.com/",
+        base_url: "https://policytroubleshooter.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/iam/"),
+    },
+    ApiInfo {
+        name: "prediction",
+        version: "v1.6",
+        title: "Prediction API",
+        crate_name: "google-prediction1d6",
+        cli_crate_name: "google-prediction1d6-cli",
+        crate_version: "5.0.5+20160511",
+        revision: "20160511",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/prediction/v1.6/projects/",
+        documentation_link: Some("https://developers.google.com/prediction/docs/developer-guide"),
+    },
+    ApiInfo {
+        name: "privateca",
+        version: "v1beta1",
+        title: "Certificate Authority API",
+        crate_name: "google-privateca1_beta1",
+        cli_crate_name: "google-privateca1_beta1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://privateca.googleapis.com/",
+        base_url: "https://privateca.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/"),
+    },
+    ApiInfo {
+        name: "privateca",
+        version: "v1",
+        title: "Certificate Authority API",
+        crate_name: "google-privateca1",
+        cli_crate_name: "google-privateca1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://privateca.googleapis.com/",
+        base_url: "https://privateca.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/"),
+    },
+    ApiInfo {
+        name: "prod_tt_sasportal",
+        version: "v1alpha1",
+        title: "SAS Portal API (Testing)",
+        crate_name: "google-prod_tt_sasportal1_alpha1",
+        cli_crate_name: "google-prod_tt_sasportal1_alpha1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://prod-tt-sasportal.googleapis.com/",
+        base_url: "https://prod-tt-sasportal.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/spectrum-access-system/"),
+    },
+    ApiInfo {
+        name: "proximitybeacon",
+        version: "v1beta1",
+        title: "Proximity Beacon API",
+        crate_name: "google-proximitybeacon1_beta1",
+        cli_crate_name: "google-proximitybeacon1_beta1-cli",
+        crate_version: "5.0.5+20200127",
+        revision: "20200127",
+        root_url: "https://proximitybeacon.googleapis.com/",
+        base_url: "https://proximitybeacon.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/beacons/proximity/"),
+    },
+    ApiInfo {
+        name: "pubsub",
+        version: "v1",
+        title: "Cloud Pub/Sub API",
+        crate_name: "google-pubsub1",
+        cli_crate_name: "google-pubsub1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://pubsub.googleapis.com/",
+        base_url: "https://pubsub.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/pubsub/docs"),
+    },
+    ApiInfo {
+        name: "pubsub",
+        version: "v1beta2",
+        title: "Cloud Pub/Sub API",
+        crate_name: "google-pubsub1_beta2",
+        cli_crate_name: "google-pubsub1_beta2-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://pubsub.googleapis.com/",
+        base_url: "https://pubsub.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/pubsub/docs"),
+    },
+    ApiInfo {
+        name: "pubsublite",
+        version: "v1",
+        title: "Pub/Sub Lite API",
+        crate_name: "google-pubsublite1",
+        cli_crate_name: "google-pubsublite1-cli",
+        crate_version: "5.0.5+20240224",
+        revision: "20240224",
+        root_url: "https://pubsublite.googleapis.com/",
+        base_url: "https://pubsublite.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/pubsub/lite/docs"),
+    },
+    ApiInfo {
+        name: "qpxexpress",
+        version: "v1",
+        title: "QPX Express API",
+        crate_name: "google-qpxexpress1",
+        cli_crate_name: "google-qpxexpress1-cli",
+        crate_version: "5.0.5+20160708",
+        revision: "20160708",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/qpxExpress/v1/trips/",
+        documentation_link: Some("http://developers.google.com/qpx-express"),
+    },
+    ApiInfo {
+        name: "realtimebidding",
+        version: "v1",
+        title: "Real-time Bidding API",
+        crate_name: "google-realtimebidding1",
+        cli_crate_name: "google-realtimebidding1-cli",
+        crate_version: "5.0.5+20240305",
+        revision: "20240305",
+        root_url: "https://realtimebidding.googleapis.com/",
+        base_url: "https://realtimebidding.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/authorized-buyers/apis/realtimebidding/reference/rest/"),
+    },
+    ApiInfo {
+        name: "recaptchaenterprise",
+        version: "v1",
+        title: "reCAPTCHA Enterprise API",
+        crate_name: "google-recaptchaenterprise1",
+        cli_crate_name: "google-recaptchaenterprise1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://recaptchaenterprise.googleapis.com/",
+        base_url: "https://recaptchaenterprise.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/recaptcha-enterprise/"),
+    },
+    ApiInfo {
+        name: "recommendationengine",
+        version: "v1beta1",
+        title: "Recommendations AI (Beta)",
+        crate_name: "google-recommendationengine1_beta1",
+        cli_crate_name: "google-recommendationengine1_beta1-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://recommendationengine.googleapis.com/",
+        base_url: "https://recommendationengine.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/recommendations-ai/docs"),
+    },
+    ApiInfo {
+        name: "recommender",
+        version: "v1",
+        title: "Recommender API",
+        crate_name: "google-recommender1",
+        cli_crate_name: "google-recommender1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://recommender.googleapis.com/",
+        base_url: "https://recommender.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/recommender/docs/"),
+    },
+    ApiInfo {
+        name: "recommender",
+        version: "v1beta1",
+        title: "Recommender API",
+        crate_name: "google-recommender1_beta1",
+        cli_crate_name: "google-recommender1_beta1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://recommender.googleapis.com/",
+        base_url: "https://recommender.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/recommender/docs/"),
+    },
+    ApiInfo {
+        name: "redis",
+        version: "v1",
+        title: "Google Cloud Memorystore for Redis API",
+        crate_name: "google-redis1",
+        cli_crate_name: "google-redis1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://redis.googleapis.com/",
+        base_url: "https://redis.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/memorystore/docs/redis/"),
+    },
+    ApiInfo {
+        name: "remotebuildexecution",
+        version: "v2",
+        title: "Remote Build Execution API",
+        crate_name: "google-remotebuildexecution2",
+        cli_crate_name: "google-remotebuildexecution2-cli",
+        crate_version: "5.0.5+20210329",
+        revision: "20210329",
+        root_url: "https://remotebuildexecution.googleapis.com/",
+        base_url: "https://remotebuildexecution.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/remote-build-execution/docs/"),
+    },
+    ApiInfo {
+        name: "replicapool",
+        version: "v1beta2",
+        title: "Google Compute Engine Instance Group Manager API",
+        crate_name: "google-replicapool1_beta2",
+        cli_crate_name: "google-replicapool1_beta2-cli",
+        crate_version: "5.0.5+20160512",
+        revision: "20160512",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/replicapool/v1beta2/projects/",
+        documentation_link: Some("https://developers.google.com/compute/docs/instance-groups/manager/v1beta2"),
+    },
+    ApiInfo {
+        name: "replicapoolupdater",
+        version: "v1beta1",
+        title: "Google Compute Engine Instance Group Updater API",
+        crate_name: "google-replicapoolupdater1_beta1",
+        cli_crate_name: "google-replicapoolupdater1_beta1-cli",
+        crate_version: "5.0.5+20161003",
+        revision: "20161003",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/replicapoolupdater/v1beta1/projects/",
+        documentation_link: Some("https://cloud.google.com/compute/docs/instance-groups/manager/#applying_rolling_updates_using_the_updater_service"),
+    },
+    ApiInfo {
+        name: "reseller",
+        version: "v1sandbox",
+        title: "Enterprise Apps Reseller API",
+        crate_name: "google-reseller1_sandbox",
+        cli_crate_name: "google-reseller1_sandbox-cli",
+        crate_version: "5.0.5+20160329",
+        revision: "20160329",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/apps/reseller/v1sandbox/",
+        documentation_link: Some("https://developers.google.com/google-apps/reseller/"),
+    },
+    ApiInfo {
+        name: "resourcesettings",
+        version: "v1",
+        title: "Resource Settings API",
+        crate_name: "google-resourcesettings1",
+        cli_crate_name: "google-resourcesettings1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://resourcesettings.googleapis.com/",
+        base_url: "https://resourcesettings.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/resource-manager/docs/resource-settings/overview"),
+    },
+    ApiInfo {
+        name: "resourceviews",
+        version: "v1beta2",
+        title: "Google Compute Engine Instance Groups API",
+        crate_name: "google-resourceviews1_beta2",
+        cli_crate_name: "google-resourceviews1_beta2-cli",
+        crate_version: "5.0.5+20160512",
+        revision: "20160512",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/resourceviews/v1beta2/projects/",
+        documentation_link: Some("https://developers.google.com/compute/"),
+    },
+    ApiInfo {
+        name: "retail",
+        version: "v2",
+        title: "Vertex AI Search for Retail API",
+        crate_name: "google-retail2",
+        cli_crate_name: "google-retail2-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://retail.googleapis.com/",
+        base_url: "https://retail.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/recommendations"),
+    },
+    ApiInfo {
+        name: "run",
+        version: "v1",
+        title: "Cloud Run Admin API",
+        crate_name: "google-run1",
+        cli_crate_name: "google-run1-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://run.googleapis.com/",
+        base_url: "https://run.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/run/"),
+    },
+    ApiInfo {
+        name: "run",
+        version: "v2",
+        title: "Cloud Run Admin API",
+        crate_name: "google-run2",
+        cli_crate_name: "google-run2-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://run.googleapis.com/",
+        base_url: "https://run.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/run/"),
+    },
+    ApiInfo {
+        name: "runtimeconfig",
+        version: "v1",
+        title: "Cloud Runtime Configuration API",
+        crate_name: "google-runtimeconfig1",
+        cli_crate_name: "google-runtimeconfig1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://runtimeconfig.googleapis.com/",
+        base_url: "https://runtimeconfig.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/deployment-manager/runtime-configurator/"),
+    },
+    ApiInfo {
+        name: "runtimeconfig",
+        version: "v1beta1",
+        title: "Cloud Runtime Configuration API",
+        crate_name: "google-runtimeconfig1_beta1",
+        cli_crate_name: "google-runtimeconfig1_beta1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://runtimeconfig.googleapis.com/",
+        base_url: "https://runtimeconfig.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/deployment-manager/runtime-configurator/"),
+    },
+    ApiInfo {
+        name: "safebrowsing",
+        version: "v4",
+        title: "Safe Browsing API",
+        crate_name: "google-safebrowsing4",
+        cli_crate_name: "google-safebrowsing4-cli",
+        crate_version: "5.0.5+20240225",
+        revision: "20240225",
+        root_url: "https://safebrowsing.googleapis.com/",
+        base_url: "https://safebrowsing.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/safe-browsing/"),
+    },
+    ApiInfo {
+        name: "sasportal",
+        version: "v1alpha1",
+        title: "SAS Portal API",
+        crate_name: "google-sasportal1_alpha1",
+        cli_crate_name: "google-sasportal1_alpha1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://sasportal.googleapis.com/",
+        base_url: "https://sasportal.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/spectrum-access-system/"),
+    },
+    ApiInfo {
+        name: "searchconsole",
+        version: "v1",
+        title: "Google Search Console API",
+        crate_name: "google-searchconsole1",
+        cli_crate_name: "google-searchconsole1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://searchconsole.googleapis.com/",
+        base_url: "https://searchconsole.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/webmaster-tools/search-console-api/"),
+    },
+    ApiInfo {
+        name: "secretmanager",
+        version: "v1",
+        title: "Secret Manager API",
+        crate_name: "google-secretmanager1",
+        cli_crate_name: "google-secretmanager1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://secretmanager.googleapis.com/",
+        base_url: "https://secretmanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/secret-manager/"),
+    },
+    ApiInfo {
+        name: "secretmanager",
+        version: "v1beta1",
+        title: "Secret Manager API",
+        crate_name: "google-secretmanager1_beta1",
+        cli_crate_name: "google-secretmanager1_beta1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://secretmanager.googleapis.com/",
+        base_url: "https://secretmanager.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/secret-manager/"),
+    },
+    ApiInfo {
+        name: "securitycenter",
+        version: "v1",
+        title: "Security Command Center API",
+        crate_name: "google-securitycenter1",
+        cli_crate_name: "google-securitycenter1-cli",
+        crate_version: "5.0.5+20240302",
+        revision: "20240302",
+        root_url: "https://securitycenter.googleapis.com/",
+        base_url: "https://securitycenter.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/security-command-center"),
+    },
+    ApiInfo {
+        name: "servicebroker",
+        version: "v1",
+        title: "Service Broker API",
+        crate_name: "google-servicebroker1",
+        cli_crate_name: "google-servicebroker1-cli",
+        crate_version: "5.0.5+20190624",
+        revision: "20190624",
+        root_url: "https://servicebroker.googleapis.com/",
+        base_url: "https://servicebroker.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/kubernetes-engine/docs/concepts/add-on/service-broker"),
+    },
+    ApiInfo {
+        name: "servicecontrol",
+        version: "v1",
+        title: "Service Control API",
+        crate_name: "google-servicecontrol1",
+        cli_crate_name: "google-servicecontrol1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://servicecontrol.googleapis.com/",
+        base_url: "https://servicecontrol.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/service-control/"),
+    },
+    ApiInfo {
+        name: "servicecontrol",
+        version: "v2",
+        title: "Service Control API",
+        crate_name: "google-servicecontrol2",
+        cli_crate_name: "google-servicecontrol2-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://servicecontrol.googleapis.com/",
+        base_url: "https://servicecontrol.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/service-control/"),
+    },
+    ApiInfo {
+        name: "servicedirectory",
+        version: "v1",
+        title: "Service Directory API",
+        crate_name: "google-servicedirectory1",
+        cli_crate_name: "google-servicedirectory1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://servicedirectory.googleapis.com/",
+        base_url: "https://servicedirectory.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/service-directory"),
+    },
+    ApiInfo {
+        name: "servicedirectory",
+        version: "v1beta1",
+        title: "Service Directory API",
+        crate_name: "google-servicedirectory1_beta1",
+        cli_crate_name: "google-servicedirectory1_beta1-cli",
+        crate_version: "5.0.5+20240226",
+        revision: "20240226",
+        root_url: "https://servicedirectory.googleapis.com/",
+        base_url: "https://servicedirectory.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/service-directory"),
+    },
+    ApiInfo {
+        name: "serviceregistry",
+        version: "alpha",
+        title: "Google Cloud Service Registry API",
+        crate_name: "google-serviceregistryalpha",
+        cli_crate_name: "google-serviceregistryalpha-cli",
+        crate_version: "5.0.5+20160401",
+        revision: "20160401",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/serviceregistry/alpha/projects/",
+        documentation_link: Some("https://developers.google.com/cloud-serviceregistry/"),
+    },
+    ApiInfo {
+        name: "sheets",
+        version: "v4",
+        title: "Google Sheets API",
+        crate_name: "google-sheets4",
+        cli_crate_name: "google-sheets4-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://sheets.googleapis.com/",
+        base_url: "https://sheets.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/sheets/"),
+    },
+    ApiInfo {
+        name: "siteverification",
+        version: "v1",
+        title: "Google Site Verification API",
+        crate_name: "google-siteverification1",
+        cli_crate_name: "google-siteverification1-cli",
+        crate_version: "5.0.5+20191119",
+        revision: "20191119",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/siteVerification/v1/",
+        documentation_link: Some("https://developers.google.com/site-verification/"),
+    },
+    ApiInfo {
+        name: "smartdevicemanagement",
+        version: "v1",
+        title: "Smart Device Management API",
+        crate_name: "google-smartdevicemanagement1",
+        cli_crate_name: "google-smartdevicemanagement1-cli",
+        crate_version: "5.0.5+20240218",
+        revision: "20240218",
+        root_url: "https://smartdevicemanagement.googleapis.com/",
+        base_url: "https://smartdevicemanagement.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/nest/device-access"),
+    },
+    ApiInfo {
+        name: "sourcerepo",
+        version: "v1",
+        title: "Cloud Source Repositories API",
+        crate_name: "google-sourcerepo1",
+        cli_crate_name: "google-sourcerepo1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://sourcerepo.googleapis.com/",
+        base_url: "https://sourcerepo.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/source-repositories/docs/apis"),
+    },
+    ApiInfo {
+        name: "spectrum",
+        version: "v1explorer",
+        title: "Google Spectrum Database API",
+        crate_name: "google-spectrum1_explorer",
+        cli_crate_name: "google-spectrum1_explorer-cli",
+        crate_version: "5.0.5+20170306",
+        revision: "20170306",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/spectrum/v1explorer/paws/",
+        documentation_link: Some("http://developers.google.com/spectrum"),
+    },
+    ApiInfo {
+        name: "speech",
+        version: "v1",
+        title: "Cloud Speech-to-Text API",
+        crate_name: "google-speech1",
+        cli_crate_name: "google-speech1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://speech.googleapis.com/",
+        base_url: "https://speech.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/speech-to-text/docs/quickstart-protocol"),
+    },
+    ApiInfo {
+        name: "speech",
+        version: "v1beta1",
+        title: "Cloud Speech API",
+        crate_name: "google-speech1_beta1",
+        cli_crate_name: "google-speech1_beta1-cli",
+        crate_version: "5.0.5+20181005",
+        revision: "20181005",
+        root_url: "https://speech.googleapis.com/",
+        base_url: "https://speech.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/speech-to-text/docs/quickstart-protocol"),
+    },
+    ApiInfo {
+        name: "sql",
+        version: "v1beta4",
+        title: "Cloud SQL Admin API",
+        crate_name: "google-sql1_beta4",
+        cli_crate_name: "google-sql1_beta4-cli",
+        crate_version: "5.0.5+20200331",
+        revision: "20200331",
+        root_url: "https://sqladmin.googleapis.com/",
+        base_url: "https://sqladmin.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/cloud-sql/"),
+    },
+    ApiInfo {
+        name: "sqladmin",
+        version: "v1beta4",
+        title: "Cloud SQL Admin API",
+        crate_name: "google-sqladmin1_beta4",
+        cli_crate_name: "google-sqladmin1_beta4-cli",
+        crate_version: "5.0.5+20240219",
+        revision: "20240219",
+        root_url: "https://sqladmin.googleapis.com/",
+        base_url: "https://sqladmin.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/cloud-sql/"),
+    },
+    ApiInfo {
+        name: "sqladmin",
+        version: "v1",
+        title: "Cloud SQL Admin API",
+        crate_name: "google-sqladmin1",
+        cli_crate_name: "google-sqladmin1-cli",
+        crate_version: "5.0.5+20240219",
+        revision: "20240219",
+        root_url: "https://sqladmin.googleapis.com/",
+        base_url: "https://sqladmin.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/cloud-sql/"),
+    },
+    ApiInfo {
+        name: "storage",
+        version: "v1",
+        title: "Cloud Storage JSON API",
+        crate_name: "google-storage1",
+        cli_crate_name: "google-storage1-cli",
+        crate_version: "5.0.5+20240229",
+        revision: "20240229",
+        root_url: "https://storage.googleapis.com/",
+        base_url: "https://storage.googleapis.com/storage/v1/",
+        documentation_link: Some("https://developers.google.com/storage/docs/json_api/"),
+    },
+    ApiInfo {
+        name: "storagetransfer",
+        version: "v1",
+        title: "Storage Transfer API",
+        crate_name: "google-storagetransfer1",
+        cli_crate_name: "google-storagetransfer1-cli",
+        crate_version: "5.0.5+20240224",
+        revision: "20240224",
+        root_url: "https://storagetransfer.googleapis.com/",
+        base_url: "https://storagetransfer.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/storage-transfer/docs"),
+    },
+    ApiInfo {
+        name: "sts",
+        version: "v1",
+        title: "Security Token Service API",
+        crate_name: "google-sts1",
+        cli_crate_name: "google-sts1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://sts.googleapis.com/",
+        base_url: "https://sts.googleapis.com/",
+        documentation_link: Some("http://cloud.google.com/iam/docs/workload-identity-federation"),
+    },
+    ApiInfo {
+        name: "surveys",
+        version: "v2",
+        title: "Surveys API",
+        crate_name: "google-surveys2",
+        cli_crate_name: "google-surveys2-cli",
+        crate_version: "5.0.5+20180508",
+        revision: "20180508",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/surveys/v2/",
+        documentation_link: None,
+    },
+    ApiInfo {
+        name: "tagmanager",
+        version: "v1",
+        title: "Tag Manager API",
+        crate_name: "google-tagmanager1",
+        cli_crate_name: "google-tagmanager1-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://tagmanager.googleapis.com/",
+        base_url: "https://tagmanager.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/tag-manager"),
+    },
+    ApiInfo {
+        name: "tagmanager",
+        version: "v2",
+        title: "Tag Manager API",
+        crate_name: "google-tagmanager2",
+        cli_crate_name: "google-tagmanager2-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://tagmanager.googleapis.com/",
+        base_url: "https://tagmanager.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/tag-manager"),
+    },
+    ApiInfo {
+        name: "taskqueue",
+        version: "v1beta2",
+        title: "TaskQueue API",
+        crate_name: "google-taskqueue1_beta2",
+        cli_crate_name: "google-taskqueue1_beta2-cli",
+        crate_version: "5.0.5+20160428",
+        revision: "20160428",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/taskqueue/v1beta2/projects/",
+        documentation_link: Some("https://developers.google.com/appengine/docs/python/taskqueue/rest"),
+    },
+    ApiInfo {
+        name: "tasks",
+        version: "v1",
+        title: "Google Tasks API",
+        crate_name: "google-tasks1",
+        cli_crate_name: "google-tasks1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://tasks.googleapis.com/",
+        base_url: "https://tasks.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/tasks/"),
+    },
+    ApiInfo {
+        name: "testing",
+        version: "v1",
+        title: "Cloud Testing API",
+        crate_name: "google-testing1",
+        cli_crate_name: "google-testing1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://testing.googleapis.com/",
+        base_url: "https://testing.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/cloud-test-lab/"),
+    },
+    ApiInfo {
+        name: "texttospeech",
+        version: "v1",
+        title: "Cloud Text-to-Speech API",
+        crate_name: "google-texttospeech1",
+        cli_crate_name: "google-texttospeech1-cli",
+        crate_version: "5.0.5+20240228",
+        revision: "20240228",
+        root_url: "https://texttospeech.googleapis.com/",
+        base_url: "https://texttospeech.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/text-to-speech/"),
+    },
+    ApiInfo {
+        name: "tpu",
+        version: "v1",
+        title: "Cloud TPU API",
+        crate_name: "google-tpu1",
+        cli_crate_name: "google-tpu1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://tpu.googleapis.com/",
+        base_url: "https://tpu.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/tpu/"),
+    },
+    ApiInfo {
+        name: "tpu",
+        version: "v1alpha1",
+        title: "Cloud TPU API",
+        crate_name: "google-tpu1_alpha1",
+        cli_crate_name: "google-tpu1_alpha1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://tpu.googleapis.com/",
+        base_url: "https://tpu.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/tpu/"),
+    },
+    ApiInfo {
+        name: "transcoder",
+        version: "v1beta1",
+        title: "Transcoder API",
+        crate_name: "google-transcoder1_beta1",
+        cli_crate_name: "google-transcoder1_beta1-cli",
+        crate_version: "5.0.5+20210323",
+        revision: "20210323",
+        root_url: "https://transcoder.googleapis.com/",
+        base_url: "https://transcoder.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/transcoder/docs/"),
+    },
+    ApiInfo {
+        name: "transcoder",
+        version: "v1",
+        title: "Transcoder API",
+        crate_name: "google-transcoder1",
+        cli_crate_name: "google-transcoder1-cli",
+        crate_version: "5.0.5+20240221",
+        revision: "20240221",
+        root_url: "https://transcoder.googleapis.com/",
+        base_url: "https://transcoder.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/transcoder/docs/"),
+    },
+    ApiInfo {
+        name: "translate",
+        version: "v2",
+        title: "Google Cloud Translation API",
+        crate_name: "google-translate2",
+        cli_crate_name: "google-translate2-cli",
+        crate_version: "5.0.5+20170525",
+        revision: "20170525",
+        root_url: "https://translation.googleapis.com/",
+        base_url: "https://translation.googleapis.com/language/translate/",
+        documentation_link: Some("https://code.google.com/apis/language/translate/v2/getting_started.html"),
+    },
+    ApiInfo {
+        name: "translate",
+        version: "v3",
+        title: "Cloud Translation API",
+        crate_name: "google-translate3",
+        cli_crate_name: "google-translate3-cli",
+        crate_version: "5.0.5+20240131",
+        revision: "20240131",
+        root_url: "https://translation.googleapis.com/",
+        base_url: "https://translation.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/translate/docs/quickstarts"),
+    },
+    ApiInfo {
+        name: "urlshortener",
+        version: "v1",
+        title: "URL Shortener API",
+        crate_name: "google-urlshortener1",
+        cli_crate_name: "google-urlshortener1-cli",
+        crate_version: "5.0.5+20150519",
+        revision: "20150519",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/urlshortener/v1/",
+        documentation_link: Some("https://developers.google.com/url-shortener/v1/getting_started"),
+    },
+    ApiInfo {
+        name: "vault",
+        version: "v1",
+        title: "Google Vault API",
+        crate_name: "google-vault1",
+        cli_crate_name: "google-vault1-cli",
+        crate_version: "5.0.5+20240220",
+        revision: "20240220",
+        root_url: "https://vault.googleapis.com/",
+        base_url: "https://vault.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/vault"),
+    },
+    ApiInfo {
+        name: "vectortile",
+        version: "v1",
+        title: "Semantic Tile API",
+        crate_name: "google-vectortile1",
+        cli_crate_name: "google-vectortile1-cli",
+        crate_version: "5.0.5+20210331",
+        revision: "20210331",
+        root_url: "https://vectortile.googleapis.com/",
+        base_url: "https://vectortile.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/maps/contact-sales/"),
+    },
+    ApiInfo {
+        name: "verifiedaccess",
+        version: "v1",
+        title: "Chrome Verified Access API",
+        crate_name: "google-verifiedaccess1",
+        cli_crate_name: "google-verifiedaccess1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://verifiedaccess.googleapis.com/",
+        base_url: "https://verifiedaccess.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/chrome/verified-access"),
+    },
+    ApiInfo {
+        name: "versionhistory",
+        version: "v1",
+        title: "versionhistory.googleapis.com API",
+        crate_name: "google-versionhistory1",
+        cli_crate_name: "google-versionhistory1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://versionhistory.googleapis.com/",
+        base_url: "https://versionhistory.googleapis.com/",
+        documentation_link: Some("https://developers.chrome.com/versionhistory"),
+    },
+    ApiInfo {
+        name: "videointelligence",
+        version: "v1",
+        title: "Cloud Video Intelligence API",
+        crate_name: "google-videointelligence1",
+        cli_crate_name: "google-videointelligence1-cli",
+        crate_version: "5.0.5+20240118",
+        revision: "20240118",
+        root_url: "https://videointelligence.googleapis.com/",
+        base_url: "https://videointelligence.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/video-intelligence/docs/"),
+    },
+    ApiInfo {
+        name: "videointelligence",
+        version: "v1beta1",
+        title: "Cloud Video Intelligence API",
+        crate_name: "google-videointelligence1_beta1",
+        cli_crate_name: "google-videointelligence1_beta1-cli",
+        crate_version: "5.0.5+20171122",
+        revision: "20171122",
+        root_url: "https://videointelligence.googleapis.com/",
+        base_url: "https://videointelligence.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/video-intelligence/docs/"),
+    },
+    ApiInfo {
+        name: "vision",
+        version: "v1",
+        title: "Cloud Vision API",
+        crate_name: "google-vision1",
+        cli_crate_name: "google-vision1-cli",
+        crate_version: "5.0.5+20240223",
+        revision: "20240223",
+        root_url: "https://vision.googleapis.com/",
+        base_url: "https://vision.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/vision/"),
+    },
+    ApiInfo {
+        name: "vmmigration",
+        version: "v1",
+        title: "VM Migration API",
+        crate_name: "google-vmmigration1",
+        cli_crate_name: "google-vmmigration1-cli",
+        crate_version: "5.0.5+20240222",
+        revision: "20240222",
+        root_url: "https://vmmigration.googleapis.com/",
+        base_url: "https://vmmigration.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/migrate/virtual-machines"),
+    },
+    ApiInfo {
+        name: "walletobjects",
+        version: "v1",
+        title: "Google Wallet API",
+        crate_name: "google-walletobjects1",
+        cli_crate_name: "google-walletobjects1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://walletobjects.googleapis.com/",
+        base_url: "https://walletobjects.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/pay/passes"),
+    },
+    ApiInfo {
+        name: "webfonts",
+        version: "v1",
+        title: "Web Fonts Developer API",
+        crate_name: "google-webfonts1",
+        cli_crate_name: "google-webfonts1-cli",
+        crate_version: "5.0.5+20240227",
+        revision: "20240227",
+        root_url: "https://webfonts.googleapis.com/",
+        base_url: "https://webfonts.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/fonts/docs/developer_api"),
+    },
+    ApiInfo {
+        name: "webmasters",
+        version: "v3",
+        title: "Search Console API",
+        crate_name: "google-webmasters3",
+        cli_crate_name: "google-webmasters3-cli",
+        crate_version: "5.0.5+20190428",
+        revision: "20190428",
+        root_url: "https://www.googleapis.com/",
+        base_url: "https://www.googleapis.com/webmasters/v3/",
+        documentation_link: Some("https://developers.google.com/webmaster-tools/"),
+    },
+    ApiInfo {
+        name: "webrisk",
+        version: "v1",
+        title: "Web Risk API",
+        crate_name: "google-webrisk1",
+        cli_crate_name: "google-webrisk1-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://webrisk.googleapis.com/",
+        base_url: "https://webrisk.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/web-risk/"),
+    },
+    ApiInfo {
+        name: "workflowexecutions",
+        version: "v1",
+        title: "Workflow Executions API",
+        crate_name: "google-workflowexecutions1",
+        cli_crate_name: "google-workflowexecutions1-cli",
+        crate_version: "5.0.5+20240220",
+        revision: "20240220",
+        root_url: "https://workflowexecutions.googleapis.com/",
+        base_url: "https://workflowexecutions.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/workflows"),
+    },
+    ApiInfo {
+        name: "workflows",
+        version: "v1",
+        title: "Workflows API",
+        crate_name: "google-workflows1",
+        cli_crate_name: "google-workflows1-cli",
+        crate_version: "5.0.5+20240207",
+        revision: "20240207",
+        root_url: "https://workflows.googleapis.com/",
+        base_url: "https://workflows.googleapis.com/",
+        documentation_link: Some("https://cloud.google.com/workflows"),
+    },
+    ApiInfo {
+        name: "youtube",
+        version: "v3",
+        title: "YouTube Data API v3",
+        crate_name: "google-youtube3",
+        cli_crate_name: "google-youtube3-cli",
+        crate_version: "5.0.5+20240303",
+        revision: "20240303",
+        root_url: "https://youtube.googleapis.com/",
+        base_url: "https://youtube.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/youtube/"),
+    },
+    ApiInfo {
+        name: "youtubereporting",
+        version: "v1",
+        title: "YouTube Reporting API",
+        crate_name: "google-youtubereporting1",
+        cli_crate_name: "google-youtubereporting1-cli",
+        crate_version: "5.0.5+20240304",
+        revision: "20240304",
+        root_url: "https://youtubereporting.googleapis.com/",
+        base_url: "https://youtubereporting.googleapis.com/",
+        documentation_link: Some("https://developers.google.com/youtube/reporting/v1/reports/"),
+    },
+];
@@ -0,0 +1,435 @@
+//! The client of the `google.cloud.bigquery.storage.v1.BigQueryWrite` service.
+use std::mem;
+
+use futures_core::Stream;
+use google_apis_common::GetToken;
+use tonic::codec::{ProstCodec, Streaming};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Channel, ClientTlsConfig};
+
+use crate::storage::{
+    AppendRowsRequest, AppendRowsResponse, BatchCommitWriteStreamsRequest,
+    BatchCommitWriteStreamsResponse, CreateWriteStreamRequest, FinalizeWriteStreamRequest,
+    FinalizeWriteStreamResponse, FlushRowsRequest, FlushRowsResponse, GetWriteStreamRequest,
+    WriteStream,
+};
+use crate::{Error, Result};
+
+const SERVICE: &str = "google.cloud.bigquery.storage.v1.BigQueryWrite";
+
+/// Identifies an OAuth2 authorization scope, one of which is requested for the token of each call.
+#[derive(PartialEq, Eq, Ord, PartialOrd, Hash, Debug, Clone, Copy, Default)]
+pub enum Scope {
+    /// View and manage your data in Google BigQuery and see the email address for your Google Account
+    #[default]
+    Full,
+
+    /// Insert data into Google BigQuery
+    Insertdata,
+
+    /// See, edit, configure, and delete your Google Cloud data and see the email address for your Google Account.
+    CloudPlatform,
+}
+
+impl AsRef<str> for Scope {
+    fn as_ref(&self) -> &str {
+        match *self {
+            Scope::Full => "https://www.googleapis.com/auth/bigquery",
+            Scope::Insertdata => "https://www.googleapis.com/auth/bigquery.insertdata",
+            Scope::CloudPlatform => "https://www.googleapis.com/auth/cloud-platform",
+        }
+    }
+}
+
+/// A client of the Write API, which authorizes each call with a token of its authenticator.
+///
+/// It is cheap to clone, and clones share the connection to the server, over which calls are
+/// multiplexed.
+#[derive(Clone)]
+pub struct BigQueryWrite {
+    channel: Channel,
+    auth: Box<dyn GetToken>,
+    scopes: Vec<String>,
+}
+
+impl BigQueryWrite {
+    /// The endpoint of the service.
+    pub const ENDPOINT: &'static str = "https://bigquerystorage.googleapis.com";
+
+    /// A client making calls over `channel`, authorized with tokens of `auth`.
+    pub fn new<A: 'static + GetToken>(channel: Channel, auth: A) -> BigQueryWrite {
+        BigQueryWrite {
+            channel,
+            auth: Box::new(auth),
+            scopes: vec![Scope::default().as_ref().to_string()],
+        }
+    }
+
+    /// Connect to [`Self::ENDPOINT`] using TLS with the root certificates of the system, and make
+    /// calls authorized with tokens of `auth`.
+    pub async fn connect<A: 'static + GetToken>(auth: A) -> Result<BigQueryWrite> {
+        let channel = Channel::from_static(Self::ENDPOINT)
+            .tls_config(ClientTlsConfig::new().with_native_roots())?
+            .connect()
+            .await?;
+        Ok(BigQueryWrite::new(channel, auth))
+    }
+
+    /// Set the scopes requested for the tokens of calls, which are [`Scope::Full`] by default.
+    ///
+    /// Returns the previously set scopes.
+    pub fn scopes<I, St>(&mut self, scopes: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = St>,
+        St: AsRef<str>,
+    {
+        let scopes = scopes.into_iter().map(|s| s.as_ref().to_string()).collect();
+        mem::replace(&mut self.scopes, scopes)
+    }
+
+    /// Create a stream to write rows to, which is done using the default stream of the table if
+    /// rows should be visible as soon as they are appended, and there is no need for offsets.
+    pub async fn create_write_stream(
+        &self,
+        request: CreateWriteStreamRequest,
+    ) -> Result<WriteStream> {
+        let parent = request.parent.clone();
+        self.unary("CreateWriteStream", ("parent", &parent), request)
+            .await
+    }
+
+    /// Append the rows of `requests` to `write_stream`, returning the responses of the server,
+    /// one for each request in the same order.
+    ///
+    /// Requests are sent while responses are received, so rows are written as fast as the
+    /// connection allows. The call ends once all requests were sent and answered, or when the
+    /// server fails it, like after ten minutes without requests, in which case rows of requests
+    /// which weren't answered have to be appended again.
+    pub async fn append_rows<S>(
+        &self,
+        write_stream: &str,
+        requests: S,
+    ) -> Result<Streaming<AppendRowsResponse>>
+    where
+        S: Stream<Item = AppendRowsRequest> + Send + 'static,
+    {
+        let request = self
+            .request(("write_stream", write_stream), requests)
+            .await?;
+        let mut grpc = self.ready().await?;
+        let responses = grpc
+            .streaming(request, path("AppendRows"), ProstCodec::default())
+            .await?;
+        Ok(responses.into_inner())
+    }
+
+    /// Get the stream called `request.name`.
+    pub async fn get_write_stream(&self, request: GetWriteStreamRequest) -> Result<WriteStream> {
+        let name = request.name.clone();
+        self.unary("GetWriteStream", ("name", &name), request).await
+    }
+
+    /// Finalize the stream called `request.name`, after which no rows can be appended to it.
+    pub async fn finalize_write_stream(
+        &self,
+        request: FinalizeWriteStreamRequest,
+    ) -> Result<FinalizeWriteStreamResponse> {
+        let name = request.name.clone();
+        self.unary("FinalizeWriteStream", ("name", &name), request)
+            .await
+    }
+
+    /// Commit the rows of finalized pending streams atomically, which makes them visible.
+    pub async fn batch_commit_write_streams(
+        &self,
+        request: BatchCommitWriteStreamsRequest,
+    ) -> Result<BatchCommitWriteStreamsResponse> {
+        let parent = request.parent.clone();
+        self.unary("BatchCommitWriteStreams", ("parent", &parent), request)
+            .await
+    }
+
+    /// Make the rows of a buffered stream visible, up to the offset of `request`.
+    pub async fn flush_rows(&self, request: FlushRowsRequest) -> Result<FlushRowsResponse> {
+        let write_stream = request.write_stream.clone();
+        self.unary("FlushRows", ("write_stream", &write_stream), request)
+            .await
+    }
+
+    async fn unary<Req, Res>(&self, method: &str, param: (&str, &str), message: Req) -> Result<Res>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Res: prost::Message + Default + Send + Sync + 'static,
+    {
+        let request = self.request(param, message).await?;
+        let mut grpc = self.ready().await?;
+        let response = grpc
+            .unary(request, path(method), ProstCodec::default())
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// A request carrying `message`, with the token of the call and the routing parameter `param`,
+    /// which the service requires to find the table the call is about.
+    async fn request<T>(&self, param: (&str, &str), message: T) -> Result<tonic::Request<T>> {
+        let mut request = tonic::Request::new(message);
+        let scopes: Vec<&str> = self.scopes.iter().map(String::as_str).collect();
+        let token = self
+            .auth
+            .get_token(&scopes)
+            .await
+            .map_err(Error::MissingToken)?;
+        let metadata = request.metadata_mut();
+        if let Some(token) = token {
+            let value = MetadataValue::try_from(format!("Bearer {}", token))
+                .map_err(|err| Error::MissingToken(Box::new(err)))?;
+            metadata.insert("authorization", value);
+        }
+        let params = format!("{}={}", param.0, percent_encode(param.1));
+        // only characters which are valid in headers remain after encoding
+        metadata.insert(
+            "x-goog-request-params",
+            MetadataValue::try_from(params).unwrap(),
+        );
+        Ok(request)
+    }
+
+    async fn ready(&self) -> Result<tonic::client::Grpc<Channel>> {
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready().await?;
+        Ok(grpc)
+    }
+}
+
+fn path(method: &str) -> PathAndQuery {
+    PathAndQuery::try_from(format!("/{}/{}", SERVICE, method)).unwrap()
+}
+
+/// Encode all but the unreserved characters of urls, and `/`, which separates the parts of names.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    use tokio_stream::StreamExt;
+    use tonic::body::BoxBody;
+    use tonic::codegen::http;
+    use tonic::server::{Grpc, NamedService, StreamingService, UnaryService};
+    use tonic::{Request, Response, Status};
+
+    use super::*;
+    use crate::storage::{append_rows_response, ProtoRows};
+
+    type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+    /// A `BigQueryWrite` service recording the metadata of calls, which appends rows to streams
+    /// at offsets following each other, and fails requests without rows.
+    #[derive(Clone, Default)]
+    struct Server {
+        metadata: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl Server {
+        fn record<T>(&self, request: &Request<T>) {
+            let header = |name| {
+                let value = request.metadata().get(name).unwrap();
+                value.to_str().unwrap().to_string()
+            };
+            self.metadata
+                .lock()
+                .unwrap()
+                .push((header("authorization"), header("x-goog-request-params")));
+        }
+    }
+
+    impl NamedService for Server {
+        const NAME: &'static str = SERVICE;
+    }
+
+    impl UnaryService<CreateWriteStreamRequest> for Server {
+        type Response = WriteStream;
+        type Future = BoxFuture<std::result::Result<Response<WriteStream>, Status>>;
+
+        fn call(&mut self, request: Request<CreateWriteStreamRequest>) -> Self::Future {
+            self.record(&request);
+            let request = request.into_inner();
+            let stream = WriteStream {
+                name: format!("{}/streams/s", request.parent),
+                ..request.write_stream.unwrap()
+            };
+            Box::pin(async move { Ok(Response::new(stream)) })
+        }
+    }
+
+    impl StreamingService<AppendRowsRequest> for Server {
+        type Response = AppendRowsResponse;
+        type ResponseStream =
+            Pin<Box<dyn Stream<Item = std::result::Result<AppendRowsResponse, Status>> + Send>>;
+        type Future = BoxFuture<std::result::Result<Response<Self::ResponseStream>, Status>>;
+
+        // the responses fail with the status tonic decodes requests with
+        #[allow(clippy::result_large_err)]
+        fn call(&mut self, request: Request<Streaming<AppendRowsRequest>>) -> Self::Future {
+            self.record(&request);
+            let mut offset = 0;
+            let responses = request.into_inner().map(move |request| {
+                let request = request?;
+                let response = match request.rows {
+                    Some(crate::storage::append_rows_request::Rows::ProtoRows(data)) => {
+                        let result = append_rows_response::AppendResult {
+                            offset: Some(offset),
+                        };
+                        offset += data.rows.unwrap().serialized_rows.len() as i64;
+                        append_rows_response::Response::AppendResult(result)
+                    }
+                    None => append_rows_response::Response::Error(crate::rpc::Status {
+                        code: tonic::Code::InvalidArgument as i32,
+                        message: "no rows".into(),
+                        details: Vec::new(),
+                    }),
+                };
+                Ok(AppendRowsResponse {
+                    response: Some(response),
+                    write_stream: request.write_stream,
+                    ..Default::default()
+                })
+            });
+            Box::pin(async move { Ok(Response::new(Box::pin(responses) as Self::ResponseStream)) })
+        }
+    }
+
+    impl tower_service::Service<http::Request<BoxBody>> for Server {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<std::result::Result<http::Response<BoxBody>, Infallible>>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+            let server = self.clone();
+            Box::pin(async move {
+                Ok(match request.uri().path().rsplit('/').next().unwrap() {
+                    "CreateWriteStream" => {
+                        Grpc::new(ProstCodec::default())
+                            .unary(server, request)
+                            .await
+                    }
+                    "AppendRows" => {
+                        Grpc::new(ProstCodec::default())
+                            .streaming(server, request)
+                            .await
+                    }
+                    _ => Status::unimplemented("").into_http(),
+                })
+            })
+        }
+    }
+
+    async fn serve() -> (BigQueryWrite, Server) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = Server::default();
+        let service = server.clone();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+        let channel = Channel::from_shared(url).unwrap().connect().await.unwrap();
+        (BigQueryWrite::new(channel, "token".to_string()), server)
+    }
+
+    #[tokio::test]
+    async fn rows_are_appended_while_streaming() {
+        let (client, server) = serve().await;
+        let table = "projects/p/datasets/d/tables/t";
+        let stream = client
+            .create_write_stream(CreateWriteStreamRequest {
+                parent: table.into(),
+                write_stream: Some(WriteStream {
+                    r#type: crate::storage::write_stream::Type::Committed as i32,
+                    ..Default::default()
+                }),
+            })
+            .await
+            .unwrap();
+        assert_eq!(stream.name, "projects/p/datasets/d/tables/t/streams/s");
+        assert_eq!(
+            stream.r#type(),
+            crate::storage::write_stream::Type::Committed
+        );
+
+        let rows = ProtoRows::encode(&[String::from("a"), String::from("b")]);
+        let requests = vec![
+            AppendRowsRequest::new(&stream.name, None, rows.clone()),
+            AppendRowsRequest {
+                write_stream: stream.name.clone(),
+                ..Default::default()
+            },
+            AppendRowsRequest::new(&stream.name, None, rows),
+        ];
+        let mut responses = client
+            .append_rows(&stream.name, tokio_stream::iter(requests))
+            .await
+            .unwrap();
+        let mut results = Vec::new();
+        while let Some(response) = responses.message().await.unwrap() {
+            assert_eq!(response.write_stream, stream.name);
+            results.push(response.result().map_err(|status| status.code));
+        }
+        assert_eq!(
+            results,
+            [
+                Ok(Some(0)),
+                Err(tonic::Code::InvalidArgument as i32),
+                Ok(Some(2))
+            ]
+        );
+
+        assert_eq!(
+            *server.metadata.lock().unwrap(),
+            [
+                (
+                    "Bearer token".to_string(),
+                    "parent=projects/p/datasets/d/tables/t".to_string()
+                ),
+                (
+                    "Bearer token".to_string(),
+                    "write_stream=projects/p/datasets/d/tables/t/streams/s".to_string()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn routing_parameters_are_encoded() {
+        assert_eq!(
+            percent_encode("projects/p-1/datasets/d_2/tables/t é"),
+            "projects/p-1/datasets/d_2/tables/t%20%C3%A9"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! A client of the [BigQuery Storage Write API](https://cloud.google.com/bigquery/docs/write-api)
+//! (v1), which streams rows into BigQuery tables over gRPC.
+//!
+//! Unlike the other crates of this family, it isn't generated from a discovery document, as the
+//! Write API is only served over gRPC. Its messages are declared by hand in [`storage`], as
+//! `prost` generates them from the protos of the API, and calls are made by `tonic`. Calls are
+//! authorized with the same authenticators as the generated crates, anything implementing
+//! [`GetToken`], like those of `yup-oauth2`.
+//!
+//! Rows are serialized as protocol buffers, described by the writer schema of the first request
+//! sent by [`BigQueryWrite::append_rows()`], which streams requests while responses are received:
+//!
+//! ```ignore
+//! use google_bigquerystorage1::storage::{AppendRowsRequest, ProtoRows, ProtoSchema};
+//! use google_bigquerystorage1::BigQueryWrite;
+//!
+//! let client = BigQueryWrite::connect(auth).await?;
+//! let stream = "projects/p/datasets/d/tables/t/streams/_default";
+//! let schema = ProtoSchema { proto_descriptor: Some(row_descriptor) };
+//! let requests = batches.enumerate().map(move |(index, rows)| {
+//!     let schema = (index == 0).then(|| schema.clone());
+//!     AppendRowsRequest::new(stream, schema, ProtoRows::encode(&rows))
+//! });
+//! let mut responses = client.append_rows(stream, requests).await?;
+//! while let Some(response) = responses.message().await? {
+//!     if let Err(status) = response.result() {
+//!         eprintln!("rows weren't appended: {}", status.message);
+//!     }
+//! }
+//! ```
+//!
+//! Streams other than the default stream of a table are created with
+//! [`BigQueryWrite::create_write_stream()`], and then finalized and committed, or flushed,
+//! depending on their type.
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+
+mod client;
+pub mod rpc;
+pub mod storage;
+
+pub use client::{BigQueryWrite, Scope};
+pub use google_apis_common::GetToken;
+pub use tonic::Streaming;
+
+/// A universal result type used as return for all calls.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Identifies the kind of error of a call.
+#[derive(Debug)]
+pub enum Error {
+    /// The authenticator didn't provide a token, or one which can't be sent.
+    MissingToken(Box<dyn StdError + Send + Sync>),
+
+    /// The connection to the server couldn't be established.
+    Transport(tonic::transport::Error),
+
+    /// The server failed the call, or the connection broke during it.
+    Status(tonic::Status),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingToken(err) => write!(f, "Token retrieval failed: {}", err),
+            Error::Transport(err) => write!(f, "Transport error: {}", err),
+            Error::Status(status) => write!(
+                f,
+                "The call failed with {:?}: {}",
+                status.code(),
+                status.message()
+            ),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::MissingToken(err) => Some(err.as_ref()),
+            Error::Transport(err) => Some(err),
+            Error::Status(status) => Some(status),
+        }
+    }
+}
+
+impl From<tonic::transport::Error> for Error {
+    fn from(err: tonic::transport::Error) -> Self {
+        Error::Transport(err)
+    }
+}
+
+impl From<tonic::Status> for Error {
+    fn from(status: tonic::Status) -> Self {
+        Error::Status(status)
+    }
+}
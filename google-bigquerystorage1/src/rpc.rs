@@ -0,0 +1,22 @@
+//! The `google.rpc` messages used by the Write API.
+
+/// An error of an RPC, as the server returns it within responses, like in
+/// [`AppendRowsResponse`](crate::storage::AppendRowsResponse).
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Status {
+    /// The gRPC status code, see [`tonic::Code`].
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+    /// A description of the error.
+    #[prost(string, tag = "2")]
+    pub message: String,
+    /// Details about the error.
+    #[prost(message, repeated, tag = "3")]
+    pub details: Vec<prost_types::Any>,
+}
+
+impl From<Status> for tonic::Status {
+    fn from(status: Status) -> tonic::Status {
+        tonic::Status::new(tonic::Code::from(status.code), status.message)
+    }
+}
@@ -0,0 +1,458 @@
+//! The messages of the `google.cloud.bigquery.storage.v1` package used by the Write API, as
+//! `prost` would generate them from `storage.proto`, `stream.proto`, `protobuf.proto` and
+//! `table.proto`.
+//!
+//! Only the fields used by the `BigQueryWrite` service are declared. Fields the server sends which
+//! aren't declared here are skipped when decoding.
+use crate::rpc;
+
+/// Request message for `CreateWriteStream`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CreateWriteStreamRequest {
+    /// The table the stream writes to, like `projects/{project}/datasets/{dataset}/tables/{table}`.
+    #[prost(string, tag = "1")]
+    pub parent: String,
+    /// The stream to create, of which only the type is set.
+    #[prost(message, optional, tag = "2")]
+    pub write_stream: Option<WriteStream>,
+}
+
+/// Request message for `AppendRows`.
+///
+/// The first request sent on a connection must set the `write_stream` and the writer schema of
+/// its rows, which later requests may leave unset to use the same ones.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AppendRowsRequest {
+    /// The stream the rows are appended to, like
+    /// `projects/{project}/datasets/{dataset}/tables/{table}/streams/{id}`, or
+    /// `projects/{project}/datasets/{dataset}/tables/{table}/streams/_default` for the default
+    /// stream of the table.
+    #[prost(string, tag = "1")]
+    pub write_stream: String,
+    /// The offset at which the rows must be appended, which makes appends exactly once. It may
+    /// only be set for streams which aren't the default stream.
+    #[prost(message, optional, tag = "2")]
+    pub offset: Option<i64>,
+    /// The rows to append.
+    #[prost(oneof = "append_rows_request::Rows", tags = "4")]
+    pub rows: Option<append_rows_request::Rows>,
+    /// An id identifying the writer in the logs of the server.
+    #[prost(string, tag = "6")]
+    pub trace_id: String,
+    /// How fields missing from the rows are filled in, by the name of the field.
+    #[prost(
+        map = "string, enumeration(append_rows_request::MissingValueInterpretation)",
+        tag = "7"
+    )]
+    pub missing_value_interpretations: std::collections::HashMap<String, i32>,
+    /// How fields missing from the rows and `missing_value_interpretations` are filled in.
+    #[prost(
+        enumeration = "append_rows_request::MissingValueInterpretation",
+        tag = "8"
+    )]
+    pub default_missing_value_interpretation: i32,
+}
+
+/// The nested types of [`AppendRowsRequest`].
+pub mod append_rows_request {
+    /// Rows serialized as protocol buffers.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ProtoData {
+        /// The schema of the rows, which is only needed in the first request of a connection.
+        #[prost(message, optional, tag = "1")]
+        pub writer_schema: Option<super::ProtoSchema>,
+        /// The rows.
+        #[prost(message, optional, tag = "2")]
+        pub rows: Option<super::ProtoRows>,
+    }
+
+    /// The rows of an [`AppendRowsRequest`](super::AppendRowsRequest).
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Rows {
+        /// Rows serialized as protocol buffers.
+        #[prost(message, tag = "4")]
+        ProtoRows(ProtoData),
+    }
+
+    /// How a missing field is filled in.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum MissingValueInterpretation {
+        /// Like `NullValue`.
+        Unspecified = 0,
+        /// The field is set to NULL.
+        NullValue = 1,
+        /// The field is set to its default value, or NULL if it has none.
+        DefaultValue = 2,
+    }
+}
+
+/// Response message for `AppendRows`, one of which is sent for each request.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AppendRowsResponse {
+    /// Whether the rows were appended.
+    #[prost(oneof = "append_rows_response::Response", tags = "1, 2")]
+    pub response: Option<append_rows_response::Response>,
+    /// The new schema of the table if it changed, after which rows may use it.
+    #[prost(message, optional, tag = "3")]
+    pub updated_schema: Option<TableSchema>,
+    /// The rows which couldn't be appended, in which case none of the rows of the request were.
+    #[prost(message, repeated, tag = "4")]
+    pub row_errors: Vec<RowError>,
+    /// The stream the rows were appended to.
+    #[prost(string, tag = "5")]
+    pub write_stream: String,
+}
+
+/// The nested types of [`AppendRowsResponse`].
+pub mod append_rows_response {
+    /// The result of appending rows.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct AppendResult {
+        /// The offset the rows were appended at, if the stream has offsets.
+        #[prost(message, optional, tag = "1")]
+        pub offset: Option<i64>,
+    }
+
+    /// Whether the rows of an [`AppendRowsRequest`](super::AppendRowsRequest) were appended.
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Response {
+        /// The rows were appended.
+        #[prost(message, tag = "1")]
+        AppendResult(AppendResult),
+        /// The rows weren't appended. The connection can still be used if the error is
+        /// `INVALID_ARGUMENT`, `ALREADY_EXISTS` or `OUT_OF_RANGE`, as with bad rows or offsets.
+        #[prost(message, tag = "2")]
+        Error(crate::rpc::Status),
+    }
+}
+
+/// Request message for `GetWriteStream`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetWriteStreamRequest {
+    /// The name of the stream.
+    #[prost(string, tag = "1")]
+    pub name: String,
+    /// Which fields of the stream are returned.
+    #[prost(enumeration = "WriteStreamView", tag = "3")]
+    pub view: i32,
+}
+
+/// Request message for `BatchCommitWriteStreams`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BatchCommitWriteStreamsRequest {
+    /// The table the streams write to.
+    #[prost(string, tag = "1")]
+    pub parent: String,
+    /// The names of the finalized pending streams to commit atomically.
+    #[prost(string, repeated, tag = "2")]
+    pub write_streams: Vec<String>,
+}
+
+/// Response message for `BatchCommitWriteStreams`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BatchCommitWriteStreamsResponse {
+    /// When the streams were committed, which is unset if they weren't.
+    #[prost(message, optional, tag = "1")]
+    pub commit_time: Option<prost_types::Timestamp>,
+    /// Why the streams weren't committed.
+    #[prost(message, repeated, tag = "2")]
+    pub stream_errors: Vec<StorageError>,
+}
+
+/// Request message for `FinalizeWriteStream`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FinalizeWriteStreamRequest {
+    /// The name of the stream to finalize, after which no rows can be appended to it.
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+/// Response message for `FinalizeWriteStream`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FinalizeWriteStreamResponse {
+    /// The amount of rows in the finalized stream.
+    #[prost(int64, tag = "1")]
+    pub row_count: i64,
+}
+
+/// Request message for `FlushRows`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FlushRowsRequest {
+    /// The buffered stream to flush.
+    #[prost(string, tag = "1")]
+    pub write_stream: String,
+    /// The offset up to which rows are flushed, including it.
+    #[prost(message, optional, tag = "2")]
+    pub offset: Option<i64>,
+}
+
+/// Response message for `FlushRows`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FlushRowsResponse {
+    /// The offset up to which rows were flushed.
+    #[prost(int64, tag = "1")]
+    pub offset: i64,
+}
+
+/// Why a stream couldn't be committed.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StorageError {
+    /// The kind of error.
+    #[prost(enumeration = "storage_error::StorageErrorCode", tag = "1")]
+    pub code: i32,
+    /// The name of the stream or table the error is about.
+    #[prost(string, tag = "2")]
+    pub entity: String,
+    /// A description of the error.
+    #[prost(string, tag = "3")]
+    pub error_message: String,
+}
+
+/// The nested types of [`StorageError`].
+pub mod storage_error {
+    /// The kind of a [`StorageError`](super::StorageError).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum StorageErrorCode {
+        Unspecified = 0,
+        TableNotFound = 1,
+        StreamAlreadyCommitted = 2,
+        StreamNotFound = 3,
+        InvalidStreamType = 4,
+        InvalidStreamState = 5,
+        StreamFinalized = 6,
+        SchemaMismatchExtraFields = 7,
+        OffsetAlreadyExists = 8,
+        OffsetOutOfRange = 9,
+        CmekNotProvided = 10,
+        InvalidCmekProvided = 11,
+        CmekEncryptionError = 12,
+        KmsServiceError = 13,
+        KmsPermissionDenied = 14,
+    }
+}
+
+/// Why a row couldn't be appended.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RowError {
+    /// The index of the row in the request.
+    #[prost(int64, tag = "1")]
+    pub index: i64,
+    /// The kind of error.
+    #[prost(enumeration = "row_error::RowErrorCode", tag = "2")]
+    pub code: i32,
+    /// A description of the error.
+    #[prost(string, tag = "3")]
+    pub message: String,
+}
+
+/// The nested types of [`RowError`].
+pub mod row_error {
+    /// The kind of a [`RowError`](super::RowError).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum RowErrorCode {
+        Unspecified = 0,
+        /// One of the fields of the row is invalid.
+        FieldsError = 1,
+    }
+}
+
+/// A stream rows are written to.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WriteStream {
+    /// The name of the stream, which is set by the server.
+    #[prost(string, tag = "1")]
+    pub name: String,
+    /// When rows appended to the stream become visible.
+    #[prost(enumeration = "write_stream::Type", tag = "2")]
+    pub r#type: i32,
+    /// When the stream was created.
+    #[prost(message, optional, tag = "3")]
+    pub create_time: Option<prost_types::Timestamp>,
+    /// When the stream was committed, if it was.
+    #[prost(message, optional, tag = "4")]
+    pub commit_time: Option<prost_types::Timestamp>,
+    /// The schema of the table when the stream was created, with the `Full` view.
+    #[prost(message, optional, tag = "5")]
+    pub table_schema: Option<TableSchema>,
+    /// How rows are written.
+    #[prost(enumeration = "write_stream::WriteMode", tag = "7")]
+    pub write_mode: i32,
+    /// The location of the table.
+    #[prost(string, tag = "8")]
+    pub location: String,
+}
+
+/// The nested types of [`WriteStream`].
+pub mod write_stream {
+    /// When rows appended to a [`WriteStream`](super::WriteStream) become visible.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Type {
+        Unspecified = 0,
+        /// Rows are visible as soon as they are appended.
+        Committed = 1,
+        /// Rows are visible once the stream is finalized and committed.
+        Pending = 2,
+        /// Rows are visible once they are flushed.
+        Buffered = 3,
+    }
+
+    /// How rows are written to a [`WriteStream`](super::WriteStream).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum WriteMode {
+        Unspecified = 0,
+        /// Rows are inserted.
+        Insert = 1,
+    }
+}
+
+/// Which fields of a [`WriteStream`] are returned by `GetWriteStream`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum WriteStreamView {
+    /// Like `Basic`.
+    Unspecified = 0,
+    /// All fields but the schema of the table.
+    Basic = 1,
+    /// All fields.
+    Full = 2,
+}
+
+/// The schema of rows serialized as protocol buffers.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ProtoSchema {
+    /// The message of the rows, which must be self-contained, so nested messages are declared
+    /// within it.
+    #[prost(message, optional, tag = "1")]
+    pub proto_descriptor: Option<prost_types::DescriptorProto>,
+}
+
+/// Rows serialized as protocol buffers.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ProtoRows {
+    /// Each row, serialized as the message of the writer schema.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub serialized_rows: Vec<Vec<u8>>,
+}
+
+/// The schema of a table.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TableSchema {
+    /// The fields of the table.
+    #[prost(message, repeated, tag = "1")]
+    pub fields: Vec<TableFieldSchema>,
+}
+
+/// The schema of a field of a table.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TableFieldSchema {
+    /// The name of the field.
+    #[prost(string, tag = "1")]
+    pub name: String,
+    /// The type of the field.
+    #[prost(enumeration = "table_field_schema::Type", tag = "2")]
+    pub r#type: i32,
+    /// Whether the field is nullable, required or repeated.
+    #[prost(enumeration = "table_field_schema::Mode", tag = "3")]
+    pub mode: i32,
+    /// The fields of a `Struct` field.
+    #[prost(message, repeated, tag = "4")]
+    pub fields: Vec<TableFieldSchema>,
+    /// The description of the field.
+    #[prost(string, tag = "6")]
+    pub description: String,
+    /// The maximum length of `String` and `Bytes` fields.
+    #[prost(int64, tag = "7")]
+    pub max_length: i64,
+    /// The precision of `Numeric` and `Bignumeric` fields.
+    #[prost(int64, tag = "8")]
+    pub precision: i64,
+    /// The scale of `Numeric` and `Bignumeric` fields.
+    #[prost(int64, tag = "9")]
+    pub scale: i64,
+    /// The SQL expression of the default value of the field.
+    #[prost(string, tag = "10")]
+    pub default_value_expression: String,
+}
+
+/// The nested types of [`TableFieldSchema`].
+pub mod table_field_schema {
+    /// The type of a [`TableFieldSchema`](super::TableFieldSchema).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Type {
+        Unspecified = 0,
+        String = 1,
+        Int64 = 2,
+        Double = 3,
+        Struct = 4,
+        Bytes = 5,
+        Bool = 6,
+        Timestamp = 7,
+        Date = 8,
+        Time = 9,
+        Datetime = 10,
+        Geography = 11,
+        Numeric = 12,
+        Bignumeric = 13,
+        Interval = 14,
+        Json = 15,
+        Range = 16,
+    }
+
+    /// Whether a [`TableFieldSchema`](super::TableFieldSchema) is nullable, required or repeated.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Mode {
+        Unspecified = 0,
+        Nullable = 1,
+        Required = 2,
+        Repeated = 3,
+    }
+}
+
+impl ProtoRows {
+    /// Serialize each of `rows`, which are messages described by the writer schema.
+    pub fn encode<M: prost::Message>(rows: &[M]) -> ProtoRows {
+        ProtoRows {
+            serialized_rows: rows.iter().map(prost::Message::encode_to_vec).collect(),
+        }
+    }
+}
+
+impl AppendRowsRequest {
+    /// A request appending `rows` to `write_stream`. The `writer_schema` is only needed in the
+    /// first request sent on a connection.
+    pub fn new(
+        write_stream: impl Into<String>,
+        writer_schema: Option<ProtoSchema>,
+        rows: ProtoRows,
+    ) -> AppendRowsRequest {
+        AppendRowsRequest {
+            write_stream: write_stream.into(),
+            rows: Some(append_rows_request::Rows::ProtoRows(
+                append_rows_request::ProtoData {
+                    writer_schema,
+                    rows: Some(rows),
+                },
+            )),
+            ..Default::default()
+        }
+    }
+}
+
+impl AppendRowsResponse {
+    /// The offset the rows were appended at, which is `None` for streams without offsets, or the
+    /// error of the server if they weren't appended.
+    pub fn result(&self) -> Result<Option<i64>, &rpc::Status> {
+        match &self.response {
+            Some(append_rows_response::Response::AppendResult(result)) => Ok(result.offset),
+            Some(append_rows_response::Response::Error(status)) => Err(status),
+            None => Ok(None),
+        }
+    }
+}
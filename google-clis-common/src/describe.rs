@@ -0,0 +1,257 @@
+use serde_json as json;
+use serde_json::value::Value;
+
+use std::io::Write;
+
+use crate::output::{write_output, OutputError, OutputFormat, TableOptions};
+
+/// The descriptions of the methods of a CLI, as listed by its `list-methods` command and shown by its
+/// `describe` command.
+///
+/// The generator embeds them as a JSON array holding an object per method, like
+/// `{"resource": "files", "method": "list", "description": "Lists files.", "arguments": [...],
+/// "parameters": [...], "request": "File", "request_fields": [...], "response": "FileList",
+/// "upload": ["simple"]}`. Arguments and parameters are objects with a `name`, a `type` and a
+/// `description`, and request fields have a `path` instead of a `name`.
+pub struct Methods(Vec<Value>);
+
+impl Methods {
+    /// Parse the descriptions embedded into a CLI.
+    pub fn parse(methods: &str) -> Methods {
+        match json::from_str(methods) {
+            Ok(Value::Array(methods)) => Methods(methods),
+            _ => panic!("the embedded method descriptions are a JSON array"),
+        }
+    }
+
+    /// Returns the description of `method` of `resource`, using the names of their sub-commands.
+    pub fn find(&self, resource: &str, method: &str) -> Option<&Value> {
+        self.0
+            .iter()
+            .find(|m| str_field(m, "resource") == resource && str_field(m, "method") == method)
+    }
+
+    /// Write the resource, method and summary of each method, in columns or in `format`.
+    pub fn write_list(
+        &self,
+        out: &mut dyn Write,
+        format: Option<OutputFormat>,
+    ) -> Result<(), OutputError> {
+        let list: Vec<Value> = self
+            .0
+            .iter()
+            .map(|m| {
+                json::json!({
+                    "resource": m["resource"],
+                    "method": m["method"],
+                    "summary": summary(str_field(m, "description")),
+                })
+            })
+            .collect();
+        if let Some(format) = format {
+            let table = TableOptions {
+                columns: "resource,method,summary".parse().ok(),
+                ..Default::default()
+            };
+            return write_output(out, &Value::Array(list), format, None, &table);
+        }
+        let command =
+            |m: &Value| format!("{} {}", str_field(m, "resource"), str_field(m, "method"));
+        let width = list.iter().map(|m| command(m).len()).max().unwrap_or(0);
+        for m in &list {
+            writeln!(
+                out,
+                "{:width$}  {}",
+                command(m),
+                str_field(m, "summary"),
+                width = width
+            )?;
+        }
+        Ok(out.flush()?)
+    }
+}
+
+/// Write the arguments, parameters and request fields of `method`, as text or in `format`.
+pub fn write_method(
+    out: &mut dyn Write,
+    method: &Value,
+    format: Option<OutputFormat>,
+) -> Result<(), OutputError> {
+    if let Some(format) = format {
+        return write_output(out, method, format, None, &TableOptions::default());
+    }
+    writeln!(
+        out,
+        "{} {}",
+        str_field(method, "resource"),
+        str_field(method, "method")
+    )?;
+    let description = str_field(method, "description");
+    if !description.is_empty() {
+        writeln!(out, "\n{}", description.trim_end())?;
+    }
+    write_entries(out, "Arguments:", "name", &method["arguments"], true)?;
+    write_entries(
+        out,
+        "Parameters, set with -p <name>=<value>:",
+        "name",
+        &method["parameters"],
+        false,
+    )?;
+    if let Some(request) = method["request"].as_str() {
+        write_entries(
+            out,
+            &format!(
+                "Fields of the {} request, set with -r <path>=<value>:",
+                request
+            ),
+            "path",
+            &method["request_fields"],
+            false,
+        )?;
+    }
+    if let Some(Value::Array(protocols)) = method.get("upload") {
+        let protocols: Vec<&str> = protocols.iter().filter_map(Value::as_str).collect();
+        if !protocols.is_empty() {
+            writeln!(
+                out,
+                "\nUploads a file with -u ({}) <file>.",
+                protocols.join("|")
+            )?;
+        }
+    }
+    if let Some(response) = method["response"].as_str() {
+        writeln!(out, "\nResponse: {}", response)?;
+    }
+    Ok(out.flush()?)
+}
+
+fn write_entries(
+    out: &mut dyn Write,
+    title: &str,
+    key: &str,
+    entries: &Value,
+    positional: bool,
+) -> Result<(), OutputError> {
+    let entries = match entries {
+        Value::Array(entries) if !entries.is_empty() => entries,
+        _ => return Ok(()),
+    };
+    let name = |e: &Value| {
+        if positional {
+            format!("<{}>", str_field(e, key))
+        } else {
+            str_field(e, key).to_string()
+        }
+    };
+    let width = entries.iter().map(|e| name(e).len()).max().unwrap_or(0);
+    let type_width = entries
+        .iter()
+        .map(|e| str_field(e, "type").len())
+        .max()
+        .unwrap_or(0);
+    writeln!(out, "\n{}", title)?;
+    for e in entries {
+        let line = format!(
+            "  {:width$}  {:type_width$}  {}",
+            name(e),
+            str_field(e, "type"),
+            summary(str_field(e, "description")),
+            width = width,
+            type_width = type_width
+        );
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+/// Returns the first sentence of `description`, or its first line if that is shorter.
+fn summary(description: &str) -> &str {
+    let line = description.trim_start().lines().next().unwrap_or("");
+    match line.find(". ") {
+        Some(end) => &line[..=end],
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const METHODS: &str = r##"[
+        {"resource": "files", "method": "get", "description": "Gets a file. Or its content.\nSee the guide.",
+         "arguments": [{"name": "file-id", "type": "string", "description": "The ID of the file."}],
+         "parameters": [{"name": "fields", "type": "string"}],
+         "request": null, "request_fields": [], "response": "File", "upload": []},
+        {"resource": "files", "method": "update", "description": "Updates a file.",
+         "arguments": [{"name": "file-id", "type": "string", "description": "The ID of the file."}],
+         "parameters": [],
+         "request": "File", "request_fields": [{"path": "name", "type": "string"},
+                                              {"path": "app-properties", "type": "{ string: string }"}],
+         "response": "File", "upload": ["simple"]}
+    ]"##;
+
+    fn written(write: impl FnOnce(&mut Vec<u8>) -> Result<(), OutputError>) -> String {
+        let mut out = Vec::new();
+        write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn methods_are_listed() {
+        let methods = Methods::parse(METHODS);
+        assert_eq!(
+            written(|out| methods.write_list(out, None)),
+            "files get     Gets a file.\nfiles update  Updates a file.\n"
+        );
+        let listed: Value = json::from_str(&written(|out| {
+            methods.write_list(out, Some(OutputFormat::JsonCompact))
+        }))
+        .unwrap();
+        assert_eq!(
+            listed[1],
+            json::json!({"resource": "files", "method": "update", "summary": "Updates a file."})
+        );
+        let table = written(|out| methods.write_list(out, Some(OutputFormat::Table)));
+        assert!(table.starts_with("resource"), "{}", table);
+    }
+
+    #[test]
+    fn methods_are_described() {
+        let methods = Methods::parse(METHODS);
+        assert!(methods.find("files", "delete").is_none());
+        assert_eq!(
+            written(|out| write_method(out, methods.find("files", "update").unwrap(), None)),
+            "files update\n\
+             \n\
+             Updates a file.\n\
+             \n\
+             Arguments:\n  \
+             <file-id>  string  The ID of the file.\n\
+             \n\
+             Fields of the File request, set with -r <path>=<value>:\n  \
+             name            string\n  \
+             app-properties  { string: string }\n\
+             \n\
+             Uploads a file with -u (simple) <file>.\n\
+             \n\
+             Response: File\n"
+        );
+        let get = written(|out| write_method(out, methods.find("files", "get").unwrap(), None));
+        assert!(get.contains("Gets a file. Or its content.\nSee the guide.\n"));
+        assert!(get.contains("Parameters, set with -p <name>=<value>:\n  fields  string\n"));
+        let described: Value = json::from_str(&written(|out| {
+            write_method(
+                out,
+                methods.find("files", "get").unwrap(),
+                Some(OutputFormat::Json),
+            )
+        }))
+        .unwrap();
+        assert_eq!(described["arguments"][0]["name"], "file-id");
+    }
+}
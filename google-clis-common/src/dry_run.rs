@@ -0,0 +1,151 @@
+use http::header::HeaderName;
+use http::request::Parts;
+use serde_json as json;
+
+use std::io::{self, Write};
+
+/// Headers whose values are never written, as they carry credentials.
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-goog-api-key",
+    "cookie",
+];
+
+/// Query parameters whose values are never written, as they carry credentials.
+const REDACTED_PARAMS: &[&str] = &["key", "access_token"];
+
+const REDACTED: &str = "<redacted>";
+
+fn is_redacted_header(name: &HeaderName) -> bool {
+    REDACTED_HEADERS.contains(&name.as_str())
+}
+
+fn redacted_query(query: &str) -> Vec<(&str, &str)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            if REDACTED_PARAMS.contains(&name) {
+                (name, REDACTED)
+            } else {
+                (name, value)
+            }
+        })
+        .collect()
+}
+
+/// Write the request a call would send instead of sending it, as requested by `--dry-run`.
+///
+/// The method and url are followed by the query parameters, headers and body of the request,
+/// with credentials redacted. JSON bodies are pretty-printed.
+pub fn write_request(out: &mut dyn Write, parts: &Parts, body: &[u8]) -> io::Result<()> {
+    let query = parts.uri.query().map(redacted_query).unwrap_or_default();
+    let mut url = parts.uri.to_string();
+    if let Some(pos) = url.find('?') {
+        url.truncate(pos);
+    }
+    if !query.is_empty() {
+        let pairs: Vec<_> = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        url = format!("{}?{}", url, pairs.join("&"));
+    }
+    writeln!(out, "{} {}", parts.method, url)?;
+
+    if !query.is_empty() {
+        writeln!(out, "\nQuery parameters:")?;
+        for (name, value) in &query {
+            writeln!(out, "  {} = {}", name, value)?;
+        }
+    }
+
+    writeln!(out, "\nHeaders:")?;
+    for (name, value) in &parts.headers {
+        let value = if is_redacted_header(name) {
+            REDACTED.into()
+        } else {
+            String::from_utf8_lossy(value.as_bytes())
+        };
+        writeln!(out, "  {}: {}", name, value)?;
+    }
+
+    if !body.is_empty() {
+        writeln!(out, "\nBody:")?;
+        match json::from_slice::<json::Value>(body) {
+            Ok(value) => {
+                json::to_writer_pretty(&mut *out, &value)?;
+                writeln!(out)?;
+            }
+            Err(_) => match std::str::from_utf8(body) {
+                Ok(text) => writeln!(out, "{}", text)?,
+                Err(_) => writeln!(out, "<{} bytes of binary data>", body.len())?,
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parts(uri: &str) -> Parts {
+        let (parts, _) = http::Request::builder()
+            .method(http::Method::POST)
+            .uri(uri)
+            .header("Authorization", "Bearer secret")
+            .header("Content-Type", "application/json")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts
+    }
+
+    fn written(parts: &Parts, body: &[u8]) -> String {
+        let mut out = Vec::new();
+        write_request(&mut out, parts, body).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn requests_are_written_with_redaction() {
+        let parts = parts("https://example.com/v1/items?alt=json&key=abc&pageSize=10");
+        assert_eq!(
+            written(&parts, br#"{"name":"a"}"#),
+            "POST https://example.com/v1/items?alt=json&key=<redacted>&pageSize=10
+
+Query parameters:
+  alt = json
+  key = <redacted>
+  pageSize = 10
+
+Headers:
+  authorization: <redacted>
+  content-type: application/json
+
+Body:
+{
+  \"name\": \"a\"
+}
+"
+        );
+    }
+
+    #[test]
+    fn requests_without_query_and_body() {
+        let parts = parts("https://example.com/v1/items");
+        assert_eq!(
+            written(&parts, b""),
+            "POST https://example.com/v1/items
+
+Headers:
+  authorization: <redacted>
+  content-type: application/json
+"
+        );
+        assert!(written(&parts, &[0xff, 0xfe]).ends_with("Body:\n<2 bytes of binary data>\n"));
+    }
+}
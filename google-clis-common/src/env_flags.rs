@@ -0,0 +1,243 @@
+//! Environment variables standing in for global flags.
+//!
+//! Each global flag which isn't given on the command line is taken from the environment variable
+//! named after it, which is its name in upper case with dashes replaced by underscores and
+//! prefixed by `GOOGLE_CLI_`, like `GOOGLE_CLI_CONFIG_DIR` for `--config-dir`. This makes the
+//! programs easy to configure in containers and other automation, where passing flags is awkward.
+//!
+//! Flags taking multiple values, like `--scope`, take them from their variable separated by
+//! commas or whitespace, except for those whose values may contain whitespace, like `--header`,
+//! which take them from separate lines. Flags without a value, like `--debug`, are set unless their variable
+//! is empty, `0`, `false` or `no`.
+use clap::ArgMatches;
+
+use std::env;
+
+/// The prefix of the environment variables standing in for global flags.
+pub const ENV_PREFIX: &str = "GOOGLE_CLI_";
+
+/// Returns the name of the environment variable standing in for the global flag `flag`, like
+/// `GOOGLE_CLI_CONFIG_DIR` for `config-dir`.
+pub fn flag_env_var(flag: &str) -> String {
+    format!("{}{}", ENV_PREFIX, flag.to_uppercase().replace('-', "_"))
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok()
+}
+
+/// Returns the value of the global flag `flag` whose argument is called `arg_name`, either as given
+/// on the command line or from its environment variable.
+pub fn flag_value(matches: &ArgMatches, arg_name: &str, flag: &str) -> Option<String> {
+    flag_value_from(matches, arg_name, flag, env_var)
+}
+
+fn flag_value_from(
+    matches: &ArgMatches,
+    arg_name: &str,
+    flag: &str,
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    match matches.value_of(arg_name) {
+        Some(value) => Some(value.to_string()),
+        None => var(&flag_env_var(flag)).filter(|value| !value.is_empty()),
+    }
+}
+
+/// Returns the values of the global flag `flag` whose argument is called `arg_name`, either as
+/// given on the command line or from its environment variable.
+pub fn flag_values(matches: &ArgMatches, arg_name: &str, flag: &str) -> Vec<String> {
+    flag_values_from(matches, arg_name, flag, env_var)
+}
+
+fn flag_values_from(
+    matches: &ArgMatches,
+    arg_name: &str,
+    flag: &str,
+    var: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    match matches.values_of(arg_name) {
+        Some(values) => values.map(str::to_string).collect(),
+        None => var(&flag_env_var(flag))
+            .map(|value| {
+                value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns the values of the global flag `flag` whose argument is called `arg_name`, like
+/// [`flag_values()`], but taking them from separate lines of its environment variable.
+pub fn flag_lines(matches: &ArgMatches, arg_name: &str, flag: &str) -> Vec<String> {
+    flag_lines_from(matches, arg_name, flag, env_var)
+}
+
+fn flag_lines_from(
+    matches: &ArgMatches,
+    arg_name: &str,
+    flag: &str,
+    var: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    match matches.values_of(arg_name) {
+        Some(values) => values.map(str::to_string).collect(),
+        None => var(&flag_env_var(flag))
+            .map(|value| {
+                value
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns true if the global flag `flag`, which takes no value, was given on the command line or
+/// is set by its environment variable.
+pub fn flag_is_present(matches: &ArgMatches, flag: &str) -> bool {
+    flag_is_present_from(matches, flag, env_var)
+}
+
+fn flag_is_present_from(
+    matches: &ArgMatches,
+    flag: &str,
+    var: impl Fn(&str) -> Option<String>,
+) -> bool {
+    if matches.is_present(flag) {
+        return true;
+    }
+    match var(&flag_env_var(flag)) {
+        Some(value) => !matches!(
+            value.trim().to_lowercase().as_str(),
+            "" | "0" | "false" | "no"
+        ),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use clap::{App, Arg};
+    use std::collections::HashMap;
+
+    fn vars(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn matches(args: &[&str]) -> ArgMatches<'static> {
+        App::new("test")
+            .arg(
+                Arg::with_name("folder")
+                    .long("config-dir")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("url")
+                    .long("scope")
+                    .multiple(true)
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("name-value")
+                    .long("header")
+                    .multiple(true)
+                    .number_of_values(1)
+                    .takes_value(true),
+            )
+            .arg(Arg::with_name("debug").long("debug"))
+            .get_matches_from(std::iter::once("test").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn env_var_names() {
+        assert_eq!(flag_env_var("config-dir"), "GOOGLE_CLI_CONFIG_DIR");
+        assert_eq!(flag_env_var("format"), "GOOGLE_CLI_FORMAT");
+    }
+
+    #[test]
+    fn flags_take_precedence_over_env_vars() {
+        let env = vars(&[
+            ("GOOGLE_CLI_CONFIG_DIR", "/etc/cli"),
+            ("GOOGLE_CLI_SCOPE", "https://a, https://b"),
+            ("GOOGLE_CLI_DEBUG", "1"),
+        ]);
+
+        let given = matches(&["--config-dir", "/tmp", "--scope", "https://c"]);
+        assert_eq!(
+            flag_value_from(&given, "folder", "config-dir", &env).as_deref(),
+            Some("/tmp")
+        );
+        assert_eq!(
+            flag_values_from(&given, "url", "scope", &env),
+            vec!["https://c"]
+        );
+
+        let none = matches(&[]);
+        assert_eq!(
+            flag_value_from(&none, "folder", "config-dir", &env).as_deref(),
+            Some("/etc/cli")
+        );
+        assert_eq!(
+            flag_values_from(&none, "url", "scope", &env),
+            vec!["https://a", "https://b"]
+        );
+        assert!(flag_is_present_from(&none, "debug", &env));
+        assert_eq!(
+            flag_value_from(&none, "folder", "config-dir", vars(&[])),
+            None
+        );
+    }
+
+    #[test]
+    fn lines_of_env_vars() {
+        let env = vars(&[(
+            "GOOGLE_CLI_HEADER",
+            "X-Goog-Request-Reason: audit, weekly\n\n  X-Debug: 1\n",
+        )]);
+        assert_eq!(
+            flag_lines_from(&matches(&[]), "name-value", "header", &env),
+            vec!["X-Goog-Request-Reason: audit, weekly", "X-Debug: 1"]
+        );
+        assert_eq!(
+            flag_lines_from(
+                &matches(&["--header", "A: b c", "--header", "D: e"]),
+                "name-value",
+                "header",
+                &env
+            ),
+            vec!["A: b c", "D: e"]
+        );
+    }
+
+    #[test]
+    fn boolean_env_vars() {
+        let none = matches(&[]);
+        for value in ["", "0", "false", "No"] {
+            assert!(!flag_is_present_from(
+                &none,
+                "debug",
+                vars(&[("GOOGLE_CLI_DEBUG", value)])
+            ));
+        }
+        assert!(flag_is_present_from(
+            &none,
+            "debug",
+            vars(&[("GOOGLE_CLI_DEBUG", "true")])
+        ));
+        assert!(flag_is_present_from(
+            &matches(&["--debug"]),
+            "debug",
+            vars(&[])
+        ));
+    }
+}
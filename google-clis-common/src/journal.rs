@@ -0,0 +1,161 @@
+//! Journals of the items a bulk run completed, allowing an interrupted run to resume.
+//!
+//! A bulk run makes a call for each of many items, like the lines of a file, which may take long
+//! enough to be interrupted. Recording each item once its call succeeded, and skipping the items
+//! recorded already, lets the run be started again with the same journal, e.g. given with
+//! `--resume journal.json`, without applying any mutation twice. Items whose call failed aren't
+//! recorded, so they are retried by the next run.
+//!
+//! Items are identified by a key chosen by the caller, which should identify the mutation made
+//! rather than just the position of the item, like the request summary the failures of a
+//! `BulkError` are reported with.
+//! The journal is a file holding one JSON object per line, `{"item": "<key>"}`, which is appended
+//! to as items complete, so it stays valid if the run is killed at any time.
+use serde_json as json;
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{CLIError, ConfigurationError};
+
+/// The items completed by the runs using the same journal file.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    file: fs::File,
+    completed: HashSet<String>,
+}
+
+impl Journal {
+    /// Open the journal at `path`, reading the items completed by previous runs, or creating it if
+    /// it doesn't exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Journal, CLIError> {
+        let path = path.as_ref();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(io_error(path, err)),
+        };
+        // the last line is incomplete if a run was killed while writing it, losing just that item
+        let complete = text.rfind('\n').map_or(0, |end| end + 1);
+        let completed = parse(&text[..complete]).map_err(|err| io_error(path, err))?;
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| io_error(path, err))?;
+        if complete < text.len() {
+            file.set_len(complete as u64)
+                .map_err(|err| io_error(path, err))?;
+        }
+        Ok(Journal {
+            path: path.to_path_buf(),
+            file,
+            completed,
+        })
+    }
+
+    /// The file the journal is kept in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The amount of items completed so far.
+    pub fn len(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Returns true if no item was completed yet.
+    pub fn is_empty(&self) -> bool {
+        self.completed.is_empty()
+    }
+
+    /// Returns true if the item identified by `item` was completed, and is thus to be skipped.
+    pub fn is_completed(&self, item: &str) -> bool {
+        self.completed.contains(item)
+    }
+
+    /// Record that the call made for the item identified by `item` succeeded, writing it to the
+    /// journal file before returning.
+    pub fn record(&mut self, item: &str) -> Result<(), CLIError> {
+        if self.completed.contains(item) {
+            return Ok(());
+        }
+        let mut line = json::json!({ "item": item }).to_string();
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .and_then(|_| self.file.flush())
+            .map_err(|err| io_error(&self.path, err))?;
+        self.completed.insert(item.to_string());
+        Ok(())
+    }
+}
+
+fn parse(text: &str) -> io::Result<HashSet<String>> {
+    text.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            json::from_str::<json::Value>(line)
+                .ok()
+                .and_then(|entry| entry.get("item")?.as_str().map(str::to_string))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("'{}' is not a journal entry", line),
+                    )
+                })
+        })
+        .collect()
+}
+
+fn io_error(path: &Path, err: io::Error) -> CLIError {
+    CLIError::Configuration(ConfigurationError::Io((
+        path.to_string_lossy().into_owned(),
+        err,
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resumes_after_completed_items() {
+        let path = std::env::temp_dir().join(format!("journal-{}.json", std::process::id()));
+        fs::remove_file(&path).ok();
+
+        let mut journal = Journal::open(&path).unwrap();
+        assert!(journal.is_empty());
+        journal.record("DELETE /b/bucket/o/a").unwrap();
+        journal.record("DELETE /b/bucket/o/b").unwrap();
+        journal.record("DELETE /b/bucket/o/a").unwrap();
+        assert_eq!(journal.len(), 2);
+        drop(journal);
+
+        // a run killed while writing an entry
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"item": "DELETE /b/bu"#).unwrap();
+        drop(file);
+
+        let mut journal = Journal::open(&path).unwrap();
+        assert!(journal.is_completed("DELETE /b/bucket/o/a"));
+        assert!(journal.is_completed("DELETE /b/bucket/o/b"));
+        assert!(!journal.is_completed("DELETE /b/bucket/o/c"));
+        journal.record("DELETE /b/bucket/o/c").unwrap();
+        drop(journal);
+
+        let journal = Journal::open(&path).unwrap();
+        assert_eq!(journal.len(), 3);
+        assert!(journal.is_completed("DELETE /b/bucket/o/c"));
+
+        fs::write(&path, "not a journal\n{\"item\": \"x\"}\n").unwrap();
+        assert!(matches!(
+            Journal::open(&path),
+            Err(CLIError::Configuration(ConfigurationError::Io(_)))
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+}
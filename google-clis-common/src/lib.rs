@@ -1,3 +1,11 @@
+pub mod describe;
+pub mod dry_run;
+pub mod env_flags;
+pub mod journal;
+pub mod output;
+pub mod profile;
+pub mod progress;
+
 use mime::Mime;
 use yup_oauth2::{ApplicationSecret, ConsoleApplicationSecret};
 use serde_json as json;
@@ -8,15 +16,28 @@ use std::env;
 use std::fmt;
 use std::fs;
 use std::io;
-use std::io::{stdout, Write};
+use std::io::{stdout, Read, Seek, Write};
 use std::path::Path;
 use std::str::FromStr;
 use std::string::ToString;
 
 use std::default::Default;
 
+pub use dry_run::write_request;
+pub use env_flags::{flag_env_var, flag_is_present, flag_lines, flag_value, flag_values};
+pub use journal::Journal;
+pub use output::{write_output, Columns, FieldPath, OutputFormat, SortKey, TableOptions};
+pub use profile::{list_profiles, Profile, DEFAULT_PROFILE};
+pub use progress::{Progress, ProgressEvent, ProgressFormat};
+
 const FIELD_SEP: char = '.';
 
+/// The path standing for standard input, as given to `--request-file` or as the file to upload.
+pub const STDIN_PATH: &str = "-";
+
+/// The size of the pieces in which standard input is read when it is uploaded as a stream.
+pub const STDIN_CHUNK_SIZE: usize = 1 << 18;
+
 pub enum ComplexType {
     Pod,
     Vec,
@@ -77,6 +98,24 @@ pub fn remove_json_null_values(value: &mut Value) {
     }
 }
 
+/// Merge `overrides` into `base`, recursing into objects present in both.
+/// All other values in `base` are replaced by the ones in `overrides`.
+pub fn merge_json_values(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(ref mut base_map), Value::Object(overrides_map)) => {
+            for (key, value) in overrides_map {
+                match base_map.get_mut(&key) {
+                    Some(base_value) => merge_json_values(base_value, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
 fn did_you_mean<'a>(v: &str, possible_values: &[&'a str]) -> Option<&'a str> {
     let mut candidate: Option<(f64, &str)> = None;
     for pv in possible_values {
@@ -100,7 +139,7 @@ pub enum CallType {
 arg_enum! {
     pub enum UploadProtocol {
         Simple,
-        // Resumable // This seems to be lost during the async conversion
+        Resumable,
     }
 }
 
@@ -108,7 +147,7 @@ impl AsRef<str> for UploadProtocol {
     fn as_ref(&self) -> &str {
         match *self {
             UploadProtocol::Simple => "simple",
-            // UploadProtocol::Resumable => "resumable",
+            UploadProtocol::Resumable => "resumable",
         }
     }
 }
@@ -278,13 +317,13 @@ impl FieldCursor {
                             JsonType::Boolean => {
                                 Value::Bool(arg_from_str(value, err, field, "boolean"))
                             }
+                            // integers stay integral, so they decode into integer fields,
+                            // with or without serde_json's `arbitrary_precision`
                             JsonType::Int => Value::Number(
-                                json::Number::from_f64(arg_from_str(value, err, field, "int"))
-                                    .expect("valid f64"),
+                                arg_from_str::<i64>(value, err, field, "int").into(),
                             ),
                             JsonType::Uint => Value::Number(
-                                json::Number::from_f64(arg_from_str(value, err, field, "uint"))
-                                    .expect("valid f64"),
+                                arg_from_str::<u64>(value, err, field, "uint").into(),
                             ),
                             JsonType::Float => Value::Number(
                                 json::Number::from_f64(arg_from_str(value, err, field, "float"))
@@ -362,6 +401,49 @@ pub fn parse_kv_arg<'a>(
     }
 }
 
+/// Parses the headers given as `Name: value`, like with `--header`, into the headers to send with
+/// each request. All values of a header given multiple times are sent.
+pub fn parse_headers(args: &[String], err: &mut InvalidOptionsError) -> http::HeaderMap {
+    let mut headers = http::HeaderMap::new();
+    for arg in args {
+        let parsed = arg.split_once(':').and_then(|(name, value)| {
+            let name = http::header::HeaderName::from_bytes(name.trim().as_bytes()).ok()?;
+            let value = http::HeaderValue::from_str(value.trim()).ok()?;
+            Some((name, value))
+        });
+        match parsed {
+            Some((name, value)) => {
+                headers.append(name, value);
+            }
+            None => err
+                .issues
+                .push(CLIError::InvalidHeaderSyntax(arg.to_string())),
+        }
+    }
+    headers
+}
+
+/// Parses the service accounts given as comma-separated email addresses, like with
+/// `--impersonate-service-account`, into the one to impersonate, which is the last one, and the
+/// chain of service accounts the impersonation is delegated through.
+pub fn parse_impersonation_chain(
+    arg: &str,
+    err: &mut InvalidOptionsError,
+) -> Option<(String, Vec<String>)> {
+    let mut accounts: Vec<String> = arg.split(',').map(|a| a.trim().to_string()).collect();
+    let is_email = |account: &String| match account.split_once('@') {
+        Some((name, domain)) => !name.is_empty() && !domain.is_empty(),
+        None => false,
+    };
+    if !accounts.iter().all(is_email) {
+        err.issues
+            .push(CLIError::InvalidServiceAccount(arg.to_string()));
+        return None;
+    }
+    let service_account = accounts.pop()?;
+    Some((service_account, accounts))
+}
+
 pub fn calltype_from_str(
     name: &str,
     valid_protocols: Vec<String>,
@@ -379,9 +461,43 @@ pub fn calltype_from_str(
     })
 }
 
-pub fn input_file_from_opts(file_path: &str, err: &mut InvalidOptionsError) -> Option<fs::File> {
+/// A reader which can seek, as needed by uploads whose size is known in advance.
+pub trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// The media to upload, read from a file or from standard input.
+pub enum InputFile {
+    File(fs::File),
+    Stdin,
+}
+
+impl InputFile {
+    pub fn is_stdin(&self) -> bool {
+        matches!(self, InputFile::Stdin)
+    }
+
+    /// Returns a reader of the whole input which can seek. Standard input can't, so it is read
+    /// into memory for this.
+    pub fn into_seekable(self) -> io::Result<Box<dyn ReadSeek>> {
+        match self {
+            InputFile::File(f) => Ok(Box::new(f)),
+            InputFile::Stdin => {
+                let mut buffer = Vec::new();
+                io::stdin().lock().read_to_end(&mut buffer)?;
+                Ok(Box::new(io::Cursor::new(buffer)))
+            }
+        }
+    }
+}
+
+/// Opens the file to upload, or returns standard input if `file_path` is [`STDIN_PATH`].
+pub fn input_file_from_opts(file_path: &str, err: &mut InvalidOptionsError) -> Option<InputFile> {
+    if file_path == STDIN_PATH {
+        return Some(InputFile::Stdin);
+    }
     match fs::File::open(file_path) {
-        Ok(f) => Some(f),
+        Ok(f) => Some(InputFile::File(f)),
         Err(io_err) => {
             err.issues.push(CLIError::Input(InputError::Io((
                 file_path.to_string(),
@@ -392,6 +508,66 @@ pub fn input_file_from_opts(file_path: &str, err: &mut InvalidOptionsError) -> O
     }
 }
 
+/// Returns the request value given by the `--request-file` or `--request-json` options as a
+/// JSON object, or an empty object if neither was set.
+///
+/// Files are parsed as JSON if they have a `.json` extension, and as YAML otherwise, which
+/// includes standard input, read if the file is [`STDIN_PATH`].
+pub fn request_value_from_opts(
+    file_path: Option<&str>,
+    inline_json: Option<&str>,
+    err: &mut InvalidOptionsError,
+) -> Value {
+    let (source, parsed) = match (file_path, inline_json) {
+        (Some(file_path), _) => {
+            let contents = if file_path == STDIN_PATH {
+                io::read_to_string(io::stdin().lock())
+            } else {
+                fs::read_to_string(file_path)
+            };
+            let contents = match contents {
+                Ok(contents) => contents,
+                Err(io_err) => {
+                    err.issues.push(CLIError::Input(InputError::Io((
+                        file_path.to_string(),
+                        io_err,
+                    ))));
+                    return Value::Object(Default::default());
+                }
+            };
+            let parsed = if file_path.ends_with(".json") {
+                json::from_str(&contents).map_err(|e| e.to_string())
+            } else {
+                serde_yaml::from_str(&contents).map_err(|e| e.to_string())
+            };
+            (file_path, parsed)
+        }
+        (None, Some(inline_json)) => (
+            "--request-json",
+            json::from_str(inline_json).map_err(|e| e.to_string()),
+        ),
+        (None, None) => return Value::Object(Default::default()),
+    };
+
+    match parsed {
+        Ok(value @ Value::Object(_)) => value,
+        Ok(_) => {
+            err.issues.push(CLIError::Input(InputError::RequestValue(
+                source.to_string(),
+                "the request value must be an object".to_string(),
+            )));
+            Value::Object(Default::default())
+        }
+        Err(desc) => {
+            err.issues.push(CLIError::Input(InputError::RequestValue(
+                source.to_string(),
+                desc,
+            )));
+            Value::Object(Default::default())
+        }
+    }
+}
+
 pub fn input_mime_from_opts(mime: &str, err: &mut InvalidOptionsError) -> Option<Mime> {
     match mime.parse() {
         Ok(m) => Some(m),
@@ -503,6 +679,8 @@ impl fmt::Display for ConfigurationError {
 pub enum InputError {
     Io((String, io::Error)),
     Mime(String),
+    RequestValue(String, String),
+    StdinUsedTwice,
 }
 
 impl fmt::Display for InputError {
@@ -514,6 +692,15 @@ impl fmt::Display for InputError {
                 file_path, io_err
             ),
             InputError::Mime(ref mime) => writeln!(f, "'{}' is not a known mime-type.", mime),
+            InputError::RequestValue(ref source, ref desc) => writeln!(
+                f,
+                "Failed to read the request value from '{}' with error: {}.",
+                source, desc
+            ),
+            InputError::StdinUsedTwice => writeln!(
+                f,
+                "Standard input can be read once only, but was given as request file and as file to upload."
+            ),
         }
     }
 }
@@ -570,6 +757,14 @@ pub enum CLIError {
     Field(FieldError),
     MissingCommandError,
     MissingMethodError(String),
+    UnknownRequestSchema(String, String),
+    UnknownMethod(String, String),
+    InvalidHeaderSyntax(String),
+    InvalidServiceAccount(String),
+    InvalidProfileName(String),
+    UnknownProfileKey(String),
+    MissingFixtures,
+    MissingKeyFile,
 }
 
 impl fmt::Display for CLIError {
@@ -610,6 +805,46 @@ impl fmt::Display for CLIError {
                 "Please specify the method to call on the '{}' command.",
                 cmd
             ),
+            CLIError::UnknownRequestSchema(ref resource, ref method) => writeln!(
+                f,
+                "'{} {}' is not a known method taking a request value.",
+                resource, method
+            ),
+            CLIError::UnknownMethod(ref resource, ref method) => writeln!(
+                f,
+                "'{} {}' is not a known method.",
+                resource, method
+            ),
+            CLIError::InvalidHeaderSyntax(ref header) => writeln!(
+                f,
+                "'{}' is not a valid header of the form '<name>: <value>'.",
+                header
+            ),
+            CLIError::InvalidServiceAccount(ref accounts) => writeln!(
+                f,
+                "'{}' is not a comma-separated list of service account email addresses.",
+                accounts
+            ),
+            CLIError::InvalidProfileName(ref name) => writeln!(
+                f,
+                "'{}' is not a valid profile name, which consists of letters, digits, '-' and '_'.",
+                name
+            ),
+            CLIError::UnknownProfileKey(ref key) => {
+                let suffix = match did_you_mean(key, profile::PROFILE_KEYS) {
+                    Some(v) => format!(" Did you mean '{}' ?", v),
+                    None => String::new(),
+                };
+                writeln!(f, "Profiles have no setting '{}'.{}", key, suffix)
+            }
+            CLIError::MissingFixtures => writeln!(
+                f,
+                "Offline mode requires the directory of the fixtures to serve, given with --fixtures."
+            ),
+            CLIError::MissingKeyFile => writeln!(
+                f,
+                "Acting as a user with --subject requires the key of a service account, given with --key-file."
+            ),
         }
     }
 }
@@ -757,6 +992,53 @@ mod test_cli {
 
     use std::default::Default;
 
+    #[test]
+    fn headers() {
+        let mut err = InvalidOptionsError::new();
+        let args: Vec<String> = [
+            "X-Goog-Request-Reason: audit: weekly",
+            "x-debug:1",
+            "X-Debug: 2",
+            "no colon",
+            "Bad Name: value",
+        ]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+        let headers = parse_headers(&args, &mut err);
+
+        assert_eq!(headers["x-goog-request-reason"], "audit: weekly");
+        let debug: Vec<_> = headers.get_all("x-debug").iter().collect();
+        assert_eq!(debug, ["1", "2"]);
+        assert_eq!(headers.len(), 3);
+        assert_eq!(err.issues.len(), 2);
+        assert!(matches!(err.issues[0], CLIError::InvalidHeaderSyntax(ref h) if h == "no colon"));
+    }
+
+    #[test]
+    fn impersonation_chains() {
+        let mut err = InvalidOptionsError::new();
+        let target = "sa@p.iam.gserviceaccount.com";
+        assert_eq!(
+            parse_impersonation_chain(target, &mut err),
+            Some((target.to_string(), vec![]))
+        );
+        assert_eq!(
+            parse_impersonation_chain(
+                "a@p.iam.gserviceaccount.com, sa@p.iam.gserviceaccount.com",
+                &mut err
+            ),
+            Some((
+                target.to_string(),
+                vec!["a@p.iam.gserviceaccount.com".to_string()]
+            ))
+        );
+        assert!(err.issues.is_empty());
+        assert_eq!(parse_impersonation_chain("sa@p,", &mut err), None);
+        assert_eq!(parse_impersonation_chain("@p", &mut err), None);
+        assert_eq!(err.issues.len(), 2);
+    }
+
     #[test]
     fn cursor() {
         let mut c: FieldCursor = Default::default();
@@ -793,4 +1075,88 @@ mod test_cli {
         assert_eq!(c.num_fields(), 3);
         assert_eq!(c.to_string(), "one.beer.one");
     }
+    #[test]
+    fn request_value() {
+        let mut err = InvalidOptionsError::new();
+
+        assert_eq!(
+            request_value_from_opts(None, Some(r#"{"name": "foo"}"#), &mut err),
+            json::json!({"name": "foo"})
+        );
+        assert_eq!(
+            request_value_from_opts(None, None, &mut err),
+            json::json!({})
+        );
+        assert!(err.issues.is_empty());
+
+        request_value_from_opts(None, Some("[1]"), &mut err);
+        request_value_from_opts(None, Some("{"), &mut err);
+        request_value_from_opts(Some("/does/not/exist.yaml"), None, &mut err);
+        assert_eq!(err.issues.len(), 3);
+    }
+
+    #[test]
+    fn input_files() {
+        let mut err = InvalidOptionsError::new();
+        assert!(input_file_from_opts(STDIN_PATH, &mut err)
+            .unwrap()
+            .is_stdin());
+
+        let path = env::temp_dir().join("google-clis-common-input-test");
+        fs::write(&path, "media").unwrap();
+        let file = input_file_from_opts(path.to_str().unwrap(), &mut err).unwrap();
+        assert!(!file.is_stdin());
+        let mut reader = file.into_seekable().unwrap();
+        assert_eq!(reader.seek(io::SeekFrom::End(0)).unwrap(), 5);
+        fs::remove_file(&path).unwrap();
+        assert!(err.issues.is_empty());
+
+        assert!(input_file_from_opts("/does/not/exist", &mut err).is_none());
+        assert_eq!(err.issues.len(), 1);
+    }
+
+    #[test]
+    fn merge_values() {
+        let mut base = json::json!({"name": "foo", "labels": {"a": "1", "b": "2"}, "tags": [1]});
+        merge_json_values(
+            &mut base,
+            json::json!({"labels": {"b": "3", "c": "4"}, "tags": [2], "size": 5}),
+        );
+        assert_eq!(
+            base,
+            json::json!({
+                "name": "foo",
+                "labels": {"a": "1", "b": "3", "c": "4"},
+                "tags": [2],
+                "size": 5,
+            })
+        );
+    }
+
+    #[test]
+    fn json_values() {
+        let mut err = InvalidOptionsError::new();
+        let mut value = json::json!({});
+        for (field, arg, jtype) in [
+            ("count", "3", JsonType::Int),
+            ("size", "18446744073709551615", JsonType::Uint),
+            ("ratio", "0.5", JsonType::Float),
+        ] {
+            let mut cursor: FieldCursor = Default::default();
+            cursor.set(field).unwrap();
+            let type_info = JsonTypeInfo {
+                jtype,
+                ctype: ComplexType::Pod,
+            };
+            cursor.set_json_value(&mut value, arg, type_info, &mut err, &cursor);
+        }
+        assert!(err.issues.is_empty());
+        let (count, size, ratio): (i32, u64, f64) = json::from_value(json::json!([
+            value["count"],
+            value["size"],
+            value["ratio"]
+        ]))
+        .unwrap();
+        assert_eq!((count, size, ratio), (3, u64::MAX, 0.5));
+    }
 }
@@ -0,0 +1,442 @@
+use serde_json as json;
+use serde_json::value::Value;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// The format in which the server's result is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    JsonCompact,
+    Yaml,
+    Table,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "json-compact" => Ok(OutputFormat::JsonCompact),
+            "yaml" => Ok(OutputFormat::Yaml),
+            "table" => Ok(OutputFormat::Table),
+            _ => Err(format!(
+                "'{}' is not an output format, choose one of 'json', 'json-compact', 'yaml' or 'table'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+    All,
+}
+
+/// A path to the values to extract from the server's result, like `items[*].name` or `labels.env`.
+///
+/// Fields are separated by `.`, arrays are indexed using `[<index>]`, and `[*]` applies the rest of
+/// the path to all items of an array.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldPath(Vec<PathSegment>);
+
+impl FromStr for FieldPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{}' is not a valid field path", s);
+        let mut segments = Vec::new();
+        for part in s.split('.') {
+            let (name, mut indices) = match part.find('[') {
+                Some(pos) => part.split_at(pos),
+                None => (part, ""),
+            };
+            if name.is_empty() && indices.is_empty() {
+                return Err(invalid());
+            }
+            if !name.is_empty() {
+                segments.push(PathSegment::Key(name.to_string()));
+            }
+            while !indices.is_empty() {
+                let end = indices.find(']').ok_or_else(invalid)?;
+                let index = &indices[1..end];
+                segments.push(match index {
+                    "*" => PathSegment::All,
+                    _ => PathSegment::Index(index.parse().map_err(|_| invalid())?),
+                });
+                indices = &indices[end + 1..];
+                if !indices.is_empty() && !indices.starts_with('[') {
+                    return Err(invalid());
+                }
+            }
+        }
+        Ok(FieldPath(segments))
+    }
+}
+
+impl FieldPath {
+    /// Returns the value at this path, or `Value::Null` if there is none.
+    /// Paths containing `[*]` produce an array of all values found.
+    pub fn select(&self, value: &Value) -> Value {
+        select(value, &self.0)
+    }
+}
+
+fn select(value: &Value, path: &[PathSegment]) -> Value {
+    match path.split_first() {
+        None => value.clone(),
+        Some((PathSegment::Key(key), rest)) => value
+            .get(key)
+            .map(|v| select(v, rest))
+            .unwrap_or(Value::Null),
+        Some((PathSegment::Index(index), rest)) => value
+            .get(index)
+            .map(|v| select(v, rest))
+            .unwrap_or(Value::Null),
+        Some((PathSegment::All, rest)) => match value {
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|v| select(v, rest))
+                    .filter(|v| !v.is_null())
+                    .collect(),
+            ),
+            _ => Value::Null,
+        },
+    }
+}
+
+/// The columns of a table, given as comma-separated field paths like `name,state,metadata.updateTime`.
+/// Each path is applied to the rows of the table, and used as header of its column.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Columns(Vec<(String, FieldPath)>);
+
+impl FromStr for Columns {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .map(|name| Ok((name.to_string(), name.parse()?)))
+            .collect::<Result<_, _>>()
+            .map(Columns)
+    }
+}
+
+/// The field path by which the rows of a table are sorted, in descending order if prefixed with `~`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SortKey {
+    pub path: FieldPath,
+    pub descending: bool,
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, descending) = match s.strip_prefix('~') {
+            Some(path) => (path, true),
+            None => (s, false),
+        };
+        Ok(SortKey {
+            path: path.parse()?,
+            descending,
+        })
+    }
+}
+
+impl SortKey {
+    fn compare(&self, a: &Value, b: &Value) -> Ordering {
+        let ordering = compare_values(&self.path.select(a), &self.path.select(b));
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Orders numbers numerically and strings lexicographically, which includes RFC 3339 timestamps.
+/// Missing values come last, and values of different types are compared by their JSON text.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+/// Options affecting the output in the `table` format only.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableOptions {
+    /// The columns to show, or all top-level fields of the rows if unset.
+    pub columns: Option<Columns>,
+    /// The key to sort rows by, or `None` to keep the order of the server.
+    pub sort_by: Option<SortKey>,
+}
+
+#[derive(Debug)]
+pub enum OutputError {
+    Io(io::Error),
+    Json(json::Error),
+    Yaml(serde_yaml::Error),
+}
+
+impl fmt::Display for OutputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            OutputError::Io(ref err) => writeln!(f, "Failed to write output: {}.", err),
+            OutputError::Json(ref err) => writeln!(f, "Failed to encode output as JSON: {}.", err),
+            OutputError::Yaml(ref err) => writeln!(f, "Failed to encode output as YAML: {}.", err),
+        }
+    }
+}
+
+impl From<io::Error> for OutputError {
+    fn from(err: io::Error) -> Self {
+        OutputError::Io(err)
+    }
+}
+
+/// Write `value` in the given format, after extracting the value at `field` if set.
+///
+/// If the extracted value is a string, it is written as is to be easily consumable by scripts.
+/// Tables are written using the given `table` options.
+pub fn write_output(
+    out: &mut dyn Write,
+    value: &Value,
+    format: OutputFormat,
+    field: Option<&FieldPath>,
+    table: &TableOptions,
+) -> Result<(), OutputError> {
+    let selected;
+    let value = match field {
+        Some(path) => {
+            selected = path.select(value);
+            if let Value::String(ref s) = selected {
+                writeln!(out, "{}", s)?;
+                return Ok(out.flush()?);
+            }
+            &selected
+        }
+        None => value,
+    };
+
+    match format {
+        OutputFormat::Json => {
+            json::to_writer_pretty(&mut *out, value).map_err(OutputError::Json)?;
+            writeln!(out)?;
+        }
+        OutputFormat::JsonCompact => {
+            json::to_writer(&mut *out, value).map_err(OutputError::Json)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Yaml => serde_yaml::to_writer(&mut *out, value).map_err(OutputError::Yaml)?,
+        OutputFormat::Table => write_table(out, value, table)?,
+    }
+    Ok(out.flush()?)
+}
+
+/// Returns the rows of a table representing `value`. For list responses, these are the items of
+/// the first field holding an array, otherwise `value` is the only row.
+fn table_rows(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(fields) => match fields.values().find(|v| v.is_array()) {
+            Some(Value::Array(items)) => items.iter().collect(),
+            _ => vec![value],
+        },
+        Value::Null => Vec::new(),
+        _ => vec![value],
+    }
+}
+
+fn table_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+    }
+}
+
+fn write_table(out: &mut dyn Write, value: &Value, options: &TableOptions) -> io::Result<()> {
+    let mut rows = table_rows(value);
+    if let Some(ref key) = options.sort_by {
+        rows.sort_by(|a, b| key.compare(a, b));
+    }
+
+    let (header, cells): (Vec<String>, Vec<Vec<String>>) = match options.columns {
+        Some(Columns(ref columns)) => (
+            columns.iter().map(|(name, _)| name.clone()).collect(),
+            rows.iter()
+                .map(|row| {
+                    columns
+                        .iter()
+                        .map(|(_, path)| table_cell(Some(&path.select(row))))
+                        .collect()
+                })
+                .collect(),
+        ),
+        None => {
+            let mut columns: Vec<&str> = Vec::new();
+            for row in &rows {
+                if let Value::Object(fields) = row {
+                    for key in fields.keys() {
+                        if !columns.contains(&key.as_str()) {
+                            columns.push(key);
+                        }
+                    }
+                }
+            }
+
+            let cells = rows
+                .iter()
+                .map(|row| match row {
+                    Value::Object(fields) => {
+                        columns.iter().map(|c| table_cell(fields.get(*c))).collect()
+                    }
+                    _ => vec![table_cell(Some(row))],
+                })
+                .collect();
+            let header = if columns.is_empty() {
+                vec!["value".to_string()]
+            } else {
+                columns.iter().map(|c| c.to_string()).collect()
+            };
+            (header, cells)
+        }
+    };
+
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in std::iter::once(&header).chain(cells.iter()) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn field_path_parsing() {
+        assert_eq!(
+            "items[*].name".parse(),
+            Ok(FieldPath(vec![
+                PathSegment::Key("items".to_string()),
+                PathSegment::All,
+                PathSegment::Key("name".to_string()),
+            ]))
+        );
+        assert_eq!(
+            "a[1][2]".parse(),
+            Ok(FieldPath(vec![
+                PathSegment::Key("a".to_string()),
+                PathSegment::Index(1),
+                PathSegment::Index(2),
+            ]))
+        );
+        for invalid in &["", "a..b", "a[", "a[x]", "a[1]b"] {
+            assert!(invalid.parse::<FieldPath>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn field_path_selection() {
+        let value = json::json!({"items": [{"name": "a"}, {"name": "b"}, {}], "count": 3});
+        let select = |path: &str| path.parse::<FieldPath>().unwrap().select(&value);
+
+        assert_eq!(select("count"), json::json!(3));
+        assert_eq!(select("items[1].name"), json::json!("b"));
+        assert_eq!(select("items[*].name"), json::json!(["a", "b"]));
+        assert_eq!(select("missing.field"), Value::Null);
+    }
+
+    #[test]
+    fn output_formats() {
+        let value =
+            json::json!({"instances": [{"name": "a", "size": 1}, {"name": "bb"}], "token": "t"});
+        let output = |format: OutputFormat, field: Option<&str>| {
+            let mut out = Vec::new();
+            let field = field.map(|f| f.parse().unwrap());
+            write_output(
+                &mut out,
+                &value,
+                format,
+                field.as_ref(),
+                &Default::default(),
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(
+            output(OutputFormat::JsonCompact, Some("instances[0]")),
+            "{\"name\":\"a\",\"size\":1}\n"
+        );
+        assert_eq!(output(OutputFormat::Json, Some("token")), "t\n");
+        assert_eq!(
+            output(OutputFormat::Table, None),
+            "name  size\na     1\nbb\n"
+        );
+    }
+
+    #[test]
+    fn table_columns_and_sorting() {
+        let value = json::json!({"items": [
+            {"name": "b", "state": "ON", "meta": {"updateTime": "2024-01-02T00:00:00Z"}},
+            {"name": "c", "state": "OFF"},
+            {"name": "a", "meta": {"updateTime": "2024-01-01T00:00:00Z"}, "size": 10},
+        ]});
+        let output = |columns: &str, sort_by: Option<&str>| {
+            let mut out = Vec::new();
+            let options = TableOptions {
+                columns: Some(columns.parse().unwrap()),
+                sort_by: sort_by.map(|s| s.parse().unwrap()),
+            };
+            write_output(&mut out, &value, OutputFormat::Table, None, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(
+            output("name,state", None),
+            "name  state\nb     ON\nc     OFF\na\n"
+        );
+        assert_eq!(
+            output("name, meta.updateTime", Some("meta.updateTime")),
+            "name  meta.updateTime\n\
+             a     2024-01-01T00:00:00Z\n\
+             b     2024-01-02T00:00:00Z\n\
+             c\n"
+        );
+        assert_eq!(output("name", Some("~name")), "name\nc\nb\na\n");
+        assert!("name,".parse::<Columns>().is_err());
+        assert!("~".parse::<SortKey>().is_err());
+    }
+}
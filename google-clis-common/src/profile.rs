@@ -0,0 +1,210 @@
+//! Profiles keeping the credentials and defaults of several accounts or projects apart.
+//!
+//! Each profile stores its tokens in its own directory within the configuration directory, named
+//! `profiles/<name>`. The `default` profile, used unless `--profile` is given, uses the
+//! configuration directory itself, where tokens were stored before there were profiles. The
+//! application secret is shared by all profiles.
+//!
+//! A profile may set defaults for the calls made with it, which are stored in the `profile.json`
+//! file of its directory, and listed and changed using the `config` sub-command:
+//!
+//! * `project` - the project the quota and billing of calls are attributed to
+//! * `format` - the output format used unless `--format` is given
+use serde_json as json;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::output::OutputFormat;
+use crate::{CLIError, ConfigurationError};
+
+/// The name of the profile used unless another one is selected.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The defaults a profile may set.
+pub const PROFILE_KEYS: &[&str] = &["project", "format"];
+
+const PROFILES_DIR: &str = "profiles";
+const SETTINGS_FILE: &str = "profile.json";
+
+/// A profile within a configuration directory, along with its defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+    dir: PathBuf,
+    settings: BTreeMap<String, String>,
+}
+
+impl Profile {
+    /// Open the profile called `name` within `config_dir`, creating its directory if it doesn't
+    /// exist yet.
+    pub fn open(config_dir: &str, name: &str) -> Result<Profile, CLIError> {
+        if !is_valid_name(name) {
+            return Err(CLIError::InvalidProfileName(name.to_string()));
+        }
+        let dir = match name {
+            DEFAULT_PROFILE => PathBuf::from(config_dir),
+            _ => Path::new(config_dir).join(PROFILES_DIR).join(name),
+        };
+        fs::create_dir_all(&dir).map_err(|err| io_error(&dir, err))?;
+
+        let path = dir.join(SETTINGS_FILE);
+        let settings = match fs::read_to_string(&path) {
+            Ok(text) => json::from_str(&text)
+                .map_err(|err| io_error(&path, io::Error::new(io::ErrorKind::InvalidData, err)))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(io_error(&path, err)),
+        };
+        Ok(Profile {
+            name: name.to_string(),
+            dir,
+            settings,
+        })
+    }
+
+    /// The name of the profile.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The directory holding the tokens and defaults of the profile.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The file the tokens of the program called `program_name` are stored in.
+    pub fn token_path(&self, program_name: &str) -> String {
+        self.dir.join(program_name).to_string_lossy().into_owned()
+    }
+
+    /// Returns the default set for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.settings.get(key).map(String::as_str)
+    }
+
+    /// Returns the defaults set by the profile, ordered by their key.
+    pub fn settings(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.settings
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Set the default for `key` to `value`, or remove it if `value` is `None`, and store the
+    /// defaults of the profile.
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<(), CLIError> {
+        if !PROFILE_KEYS.contains(&key) {
+            return Err(CLIError::UnknownProfileKey(key.to_string()));
+        }
+        match value {
+            Some(value) => {
+                if key == "format" {
+                    OutputFormat::from_str(value).map_err(|err| {
+                        CLIError::ParseError(
+                            key.to_string(),
+                            "output format".to_string(),
+                            value.to_string(),
+                            err,
+                        )
+                    })?;
+                }
+                self.settings.insert(key.to_string(), value.to_string());
+            }
+            None => {
+                self.settings.remove(key);
+            }
+        }
+
+        let path = self.dir.join(SETTINGS_FILE);
+        let text = json::to_string_pretty(&self.settings).expect("strings serialize");
+        fs::write(&path, text).map_err(|err| io_error(&path, err))
+    }
+}
+
+/// Returns the names of the profiles within `config_dir`, starting with the default profile.
+pub fn list_profiles(config_dir: &str) -> Result<Vec<String>, CLIError> {
+    let mut names = Vec::new();
+    let dir = Path::new(config_dir).join(PROFILES_DIR);
+    match fs::read_dir(&dir) {
+        Ok(entries) => {
+            for entry in entries {
+                let entry = entry.map_err(|err| io_error(&dir, err))?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if entry.path().is_dir() && is_valid_name(&name) && name != DEFAULT_PROFILE {
+                    names.push(name);
+                }
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(io_error(&dir, err)),
+    }
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(names)
+}
+
+// Names become directory names, so only plain ones are allowed.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn io_error(path: &Path, err: io::Error) -> CLIError {
+    CLIError::Configuration(ConfigurationError::Io((
+        path.to_string_lossy().into_owned(),
+        err,
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn profiles_keep_their_own_defaults() {
+        let config_dir = std::env::temp_dir().join(format!("profiles-{}", std::process::id()));
+        let config_dir = config_dir.to_str().unwrap();
+
+        let mut default = Profile::open(config_dir, DEFAULT_PROFILE).unwrap();
+        assert_eq!(default.dir(), Path::new(config_dir));
+        default.set("project", Some("p1")).unwrap();
+        let mut work = Profile::open(config_dir, "work").unwrap();
+        assert_eq!(
+            work.token_path("cli"),
+            Path::new(config_dir)
+                .join("profiles/work/cli")
+                .to_string_lossy()
+        );
+        work.set("project", Some("p2")).unwrap();
+        work.set("format", Some("yaml")).unwrap();
+        assert!(work.set("format", Some("xml")).is_err());
+        assert!(matches!(
+            work.set("region", Some("x")),
+            Err(CLIError::UnknownProfileKey(_))
+        ));
+
+        let work = Profile::open(config_dir, "work").unwrap();
+        let settings: Vec<_> = work.settings().collect();
+        assert_eq!(settings, [("format", "yaml"), ("project", "p2")]);
+        let mut default = Profile::open(config_dir, DEFAULT_PROFILE).unwrap();
+        assert_eq!(default.get("project"), Some("p1"));
+        default.set("project", None).unwrap();
+        assert_eq!(
+            Profile::open(config_dir, DEFAULT_PROFILE)
+                .unwrap()
+                .get("project"),
+            None
+        );
+
+        assert_eq!(list_profiles(config_dir).unwrap(), ["default", "work"]);
+        assert!(matches!(
+            Profile::open(config_dir, "../x"),
+            Err(CLIError::InvalidProfileName(_))
+        ));
+        fs::remove_dir_all(config_dir).unwrap();
+    }
+}
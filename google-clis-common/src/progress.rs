@@ -0,0 +1,167 @@
+//! Machine-readable progress of long operations.
+//!
+//! With `--progress json`, uploads, waits for operations and the listing of all pages report their
+//! progress on standard error, as one JSON object per line, which is known as NDJSON. Tools and CI
+//! systems wrapping the programs can display it without parsing text meant for humans. Each event
+//! names its kind in the `event` field:
+//!
+//! * `upload` - `bytes` of `total_bytes` were uploaded
+//! * `operation` - the operation `name` was polled after `elapsed_secs`, and is `done` or not
+//! * `page` - `pages` pages holding `items` items were listed so far
+use serde_json as json;
+
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// How progress is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressFormat {
+    /// Progress isn't reported.
+    #[default]
+    None,
+    /// Each event is written as a line of JSON.
+    Json,
+}
+
+impl FromStr for ProgressFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(ProgressFormat::None),
+            "json" => Ok(ProgressFormat::Json),
+            _ => Err(format!(
+                "'{}' is not a progress format, choose one of 'none' or 'json'",
+                s
+            )),
+        }
+    }
+}
+
+/// A step made by a long operation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent<'a> {
+    /// `bytes` of `total_bytes` of an upload were persisted by the server.
+    Upload { bytes: u64, total_bytes: u64 },
+    /// The operation `name` was polled `elapsed_secs` after the wait for it began.
+    Operation {
+        name: &'a str,
+        done: bool,
+        elapsed_secs: f64,
+    },
+    /// `pages` pages holding `items` items in total were listed so far.
+    Page { pages: usize, items: usize },
+}
+
+impl ProgressEvent<'_> {
+    /// The event as JSON object.
+    pub fn to_json(&self) -> json::Value {
+        match *self {
+            ProgressEvent::Upload { bytes, total_bytes } => json::json!({
+                "event": "upload",
+                "bytes": bytes,
+                "total_bytes": total_bytes,
+            }),
+            ProgressEvent::Operation {
+                name,
+                done,
+                elapsed_secs,
+            } => json::json!({
+                "event": "operation",
+                "name": name,
+                "done": done,
+                "elapsed_secs": elapsed_secs,
+            }),
+            ProgressEvent::Page { pages, items } => json::json!({
+                "event": "page",
+                "pages": pages,
+                "items": items,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ProgressEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+/// Reports the progress of long operations on standard error, in the given format.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Progress {
+    format: ProgressFormat,
+}
+
+impl Progress {
+    pub fn new(format: ProgressFormat) -> Progress {
+        Progress { format }
+    }
+
+    /// Returns true if progress is reported.
+    pub fn is_enabled(&self) -> bool {
+        self.format != ProgressFormat::None
+    }
+
+    /// Report `event`, unless progress isn't reported. Failures to write it are ignored, as they
+    /// mustn't fail the operation.
+    pub fn emit(&self, event: ProgressEvent) {
+        if self.is_enabled() {
+            write_event(&mut io::stderr().lock(), &event).ok();
+        }
+    }
+}
+
+/// Write `event` to `out` as a line of JSON, flushing it so it is seen right away.
+pub fn write_event(out: &mut impl Write, event: &ProgressEvent) -> io::Result<()> {
+    writeln!(out, "{}", event)?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn events_are_written_as_json_lines() {
+        let mut out = Vec::new();
+        let events = [
+            ProgressEvent::Upload {
+                bytes: 512,
+                total_bytes: 1024,
+            },
+            ProgressEvent::Operation {
+                name: "operations/o1",
+                done: false,
+                elapsed_secs: 1.5,
+            },
+            ProgressEvent::Page { pages: 2, items: 7 },
+        ];
+        for event in &events {
+            write_event(&mut out, event).unwrap();
+        }
+        let lines: Vec<json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                json::json!({"event": "upload", "bytes": 512, "total_bytes": 1024}),
+                json::json!({"event": "operation", "name": "operations/o1", "done": false, "elapsed_secs": 1.5}),
+                json::json!({"event": "page", "pages": 2, "items": 7}),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("json".parse(), Ok(ProgressFormat::Json));
+        assert_eq!("none".parse(), Ok(ProgressFormat::None));
+        assert!("text".parse::<ProgressFormat>().is_err());
+        assert!(!Progress::default().is_enabled());
+        assert!(Progress::new(ProgressFormat::Json).is_enabled());
+    }
+}
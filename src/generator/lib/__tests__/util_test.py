@@ -1,9 +1,18 @@
 #!/usr/bin/env python
 
+import datetime
+import os
+import tempfile
 import unittest
 import json
 
-from generator.lib.util import to_api_version, library_name, re_find_replacements, to_rust_type
+from generator.lib.util import (to_api_version, library_name, re_find_replacements, to_rust_type, to_serde_type, mtls_root_url,
+                                method_quota_cost, deprecation_note, list_items_property, page_size_limit,
+                                strict_required_properties, required_request_properties, resource_name_from_pattern, resource_names,
+                                is_labels_property, streamed_items_property, is_server_streaming_method, is_idempotent_method,
+                                request_id_param, api_sunset, proto_interop_fields, registered_apis, validation_schemas,
+                                library_dependencies, map_fields, mark_resource_labels,
+                                REQUEST_MARKER_TRAIT, RESPONSE_MARKER_TRAIT)
 from .test_data.discovery_document import DISCOVERY_DOC
 
 
@@ -41,6 +50,245 @@ class UtilsTest(unittest.TestCase):
             res = library_name('oauth2', v)
             self.assertEqual(res, want)
 
+    def test_library_dependencies(self):
+        self.assertEqual(library_dependencies(['hyper = { version = "^ 0.14", optional = true }', 'url = "= 1.7"']),
+                         ['hyper', 'url'])
+        for dep in ('clap = "^2.0"', 'google-clis-common = { path = "../../google-clis-common" }'):
+            with self.assertRaises(AssertionError):
+                library_dependencies(['url = "= 1.7"', dep])
+
+    def test_mtls_root_url(self):
+        for url, want in (('https://www.googleapis.com/', 'https://www.mtls.googleapis.com/'),
+                          ('https://pubsub.googleapis.com/', 'https://pubsub.mtls.googleapis.com/'),
+                          ('https://foo.sandbox.googleapis.com/', 'https://foo.mtls.sandbox.googleapis.com/'),
+                          ('https://pubsub.mtls.googleapis.com/', 'https://pubsub.mtls.googleapis.com/'),
+                          ('https://example.com/', 'https://example.com/')):
+            self.assertEqual(mtls_root_url(url), want)
+
+    def test_method_quota_cost(self):
+        class Method(dict):
+            id = property(lambda self: self['id'])
+        quota = {'read': {'bucket': 'units', 'cost': 1},
+                 'write': {'bucket': 'units', 'cost': 50},
+                 'methods': {'youtube.search.list': 100,
+                             'youtube.videos.getRating': {'bucket': 'ratings', 'cost': 2}}}
+        for id, http_method, want in (('youtube.videos.list', 'GET', ('units', 1)),
+                                      ('youtube.search.list', 'GET', ('units', 100)),
+                                      ('youtube.videos.delete', 'DELETE', ('units', 50)),
+                                      ('youtube.videos.getRating', 'GET', ('ratings', 2))):
+            self.assertEqual(method_quota_cost(quota, Method(id=id, httpMethod=http_method)), want)
+        self.assertIsNone(method_quota_cost(None, Method(id='a', httpMethod='GET')))
+        self.assertIsNone(method_quota_cost({'read': quota['read']}, Method(id='a', httpMethod='POST')))
+
+    def test_is_server_streaming_method(self):
+        class Method(dict):
+            id = property(lambda self: self['id'])
+        listen = Method(id='firestore.projects.databases.documents.listen')
+        self.assertTrue(is_server_streaming_method(['firestore.projects.databases.documents.listen'], listen))
+        self.assertFalse(is_server_streaming_method(None, listen))
+        self.assertTrue(is_server_streaming_method(None, Method(id='a', streamingType='SERVER_STREAMING')))
+        self.assertFalse(is_server_streaming_method(None, Method(id='a', streamingType='NONE')))
+
+    def test_is_idempotent_method(self):
+        class Method(dict):
+            id = property(lambda self: self['id'])
+        for id, http_method, want in (('storage.objects.get', 'GET', True),
+                                      ('storage.objects.delete', 'DELETE', True),
+                                      ('storage.objects.update', 'PUT', True),
+                                      ('storage.objects.patch', 'PATCH', False),
+                                      ('storage.objects.insert', 'POST', False),
+                                      ('pubsub.projects.topics.getIamPolicy', 'POST', True),
+                                      ('firestore.projects.databases.documents.batchGet', 'POST', True),
+                                      ('pubsub.projects.topics.listing', 'POST', False),
+                                      ('cloudtasks.projects.locations.queues.tasks.create', 'POST', False)):
+            self.assertEqual(is_idempotent_method(None, Method(id=id, httpMethod=http_method)), want, id)
+        create = Method(id='compute.instances.insert', httpMethod='POST')
+        self.assertTrue(is_idempotent_method({'compute.instances.insert': True}, create))
+        get = Method(id='a.get', httpMethod='GET')
+        self.assertFalse(is_idempotent_method({'a.get': False}, get))
+
+    def test_request_id_param(self):
+        request_id = {'location': 'query', 'type': 'string'}
+        self.assertEqual(request_id_param({'parameters': {'requestId': request_id}}), 'requestId')
+        self.assertIsNone(request_id_param({}))
+        self.assertIsNone(request_id_param({'parameters': {'requestId': dict(request_id, location='path')}}))
+        self.assertIsNone(request_id_param({'parameters': {'requestId': dict(request_id, required=True)}}))
+        self.assertIsNone(request_id_param({'parameters': {'requestId': dict(request_id, repeated=True)}}))
+
+    def test_api_sunset(self):
+        self.assertIsNone(api_sunset(None))
+        for date in ('2024-07-01', datetime.date(2024, 7, 1)):
+            self.assertEqual(api_sunset({'date': date, 'migration': 'Use v5.'}), ('2024-07-01', 'Use v5.'))
+        with self.assertRaises(AssertionError):
+            api_sunset({'date': 'July 2024', 'migration': 'Use v5.'})
+
+    def test_validation_schemas(self):
+        schemas = {
+            'FileList': {'id': 'FileList', 'type': 'object', 'description': 'A list of files.',
+                         'properties': {'files': {'type': 'array', 'items': {'$ref': 'File'}}}},
+            'File': {'id': 'File', 'type': 'object', 'properties': {
+                'id': {'type': 'string', 'description': 'The id.'},
+                'labels': {'type': 'object', 'additionalProperties': {'$ref': 'Label'}},
+                'state': {'type': 'string', 'enum': ['ACTIVE'], 'enumDescriptions': ['Active.']}}},
+            'Label': {'id': 'Label', 'type': 'object', 'properties': {'name': {'type': 'string'}}},
+            'Unused': {'id': 'Unused', 'type': 'object'},
+        }
+        validated = json.loads(validation_schemas(schemas, ['FileList'], {'File': ['id']}))
+        self.assertEqual(sorted(validated), ['File', 'FileList', 'Label'])
+        self.assertEqual(validated['File'], {'type': 'object', 'required': ['id'], 'properties': {
+            'id': {'type': 'string'},
+            'labels': {'type': 'object', 'additionalProperties': {'$ref': 'Label'}},
+            'state': {'type': 'string', 'enum': ['ACTIVE']}}})
+        self.assertEqual(json.loads(validation_schemas(schemas, [], None)), {})
+
+    def test_deprecation_note(self):
+        self.assertIsNone(deprecation_note({'description': 'Use `driveId` instead.'}))
+        for description, want in (('Deprecated: Use `driveId` instead.', '"Deprecated: Use `driveId` instead."'),
+                                  ('Deprecated. Use language_codes instead. Optional.', '"Use language_codes instead."'),
+                                  ('Deprecated.', '"The parameter is deprecated and may be ignored by the server."'),
+                                  ('Deprecated. Use "a" instead', '"Use \\"a\\" instead."')):
+            self.assertEqual(deprecation_note({'deprecated': True, 'description': description}), want)
+
+    def test_list_items_property(self):
+        schemas = json.loads(DISCOVERY_DOC)['schemas']
+        self.assertEqual(list_items_property(schemas, schemas['ListAlbumsResponse']), ('albums', 'Album'))
+        self.assertEqual(list_items_property(schemas, schemas['SearchMediaItemsResponse']), ('mediaItems', 'MediaItem'))
+        self.assertIsNone(list_items_property(schemas, schemas['BatchCreateMediaItemsResponse']))
+        self.assertIsNone(list_items_property(schemas, schemas['Album']))
+
+        # like the rows of BigQuery tables
+        rows = {'id': 'TableDataList', 'properties': {
+            'rows': {'type': 'array', 'items': {'$ref': 'TableRow'}},
+            'pageToken': {'type': 'string'},
+            'totalRows': {'type': 'string', 'format': 'int64'},
+        }}
+        self.assertEqual(list_items_property(schemas, rows), ('rows', 'TableRow'))
+
+    def test_streamed_items_property(self):
+        schemas = json.loads(DISCOVERY_DOC)['schemas']
+        self.assertEqual(streamed_items_property(schemas, schemas['ListAlbumsResponse']), ('albums', 'Album'))
+        self.assertIsNone(streamed_items_property(schemas, schemas['Album']))
+        ids = {'id': 'ListIdsResponse', 'properties': {
+            'ids': {'type': 'array', 'items': {'type': 'string', 'format': 'int64'}},
+            'nextPageToken': {'type': 'string'},
+        }}
+        self.assertEqual(list_items_property(schemas, ids), ('ids', 'i64'))
+        self.assertIsNone(streamed_items_property(schemas, ids))
+
+    def test_strict_required_properties(self):
+        schemas = json.loads(DISCOVERY_DOC)['schemas']
+        required_fields = {'ListAlbumsResponse': ['nextPageToken']}
+        s = schemas['ListAlbumsResponse']
+        self.assertEqual(strict_required_properties(required_fields, s, [RESPONSE_MARKER_TRAIT]), {'nextPageToken'})
+        self.assertEqual(strict_required_properties(None, s, [RESPONSE_MARKER_TRAIT]), set())
+        self.assertEqual(strict_required_properties(required_fields, schemas['Album'], [REQUEST_MARKER_TRAIT]), set())
+        with self.assertRaises(AssertionError):
+            strict_required_properties(required_fields, s, [REQUEST_MARKER_TRAIT, RESPONSE_MARKER_TRAIT])
+        with self.assertRaises(AssertionError):
+            strict_required_properties({'ListAlbumsResponse': ['missing']}, s, [RESPONSE_MARKER_TRAIT])
+        with self.assertRaises(AssertionError):
+            strict_required_properties({'ListAlbumsResponse': ['albums']}, s, [RESPONSE_MARKER_TRAIT])
+
+    def test_required_request_properties(self):
+        s = {'properties': {
+            'name': {'type': 'string', 'description': 'Required. The name of the queue.'},
+            'state': {'type': 'string', 'description': 'Output only. The state of the queue.'},
+            'labels': {'type': 'object', 'description': 'Optional. Required labels are validated by the server.'},
+            'rateLimits': {'$ref': 'RateLimits', 'description': ' Required: how fast tasks are dispatched.'},
+            'retryConfig': {'$ref': 'RetryConfig'},
+        }}
+        self.assertEqual(required_request_properties(s), ['name', 'rateLimits'])
+        self.assertEqual(required_request_properties({}), [])
+
+    def test_proto_interop_fields(self):
+        s = {'id': 'Subscription', 'properties': {
+            'ackDeadlineSeconds': {'type': 'integer', 'format': 'int32'},
+            'type': {'type': 'string'},
+            'state': {'type': 'string', 'enum': ['STATE_UNSPECIFIED', 'ACTIVE']},
+            'kinds': {'type': 'array', 'items': {'type': 'string', 'enum': ['A', 'B']}},
+            'pushConfig': {'$ref': 'PushConfig'},
+            'oidcToken': {'$ref': 'OidcToken'},
+        }}
+        config = {'messages': ['Subscription', 'PushConfig'], 'skip': ['Subscription.oidcToken'],
+                  'enums': {'Subscription.state': 'subscription::State'}}
+        self.assertEqual(proto_interop_fields(config, s), [
+            ('ack_deadline_seconds', 'ack_deadline_seconds', None),
+            ('type_', 'r#type', None),
+            ('state', 'state', 'subscription::State'),
+            ('push_config', 'push_config', None),
+        ])
+        # enum fields without a prost enum are left unset
+        self.assertEqual([f[0] for f in proto_interop_fields({'messages': ['PushConfig', 'OidcToken']}, s)],
+                         ['ack_deadline_seconds', 'type_', 'push_config', 'oidc_token'])
+        with self.assertRaises(AssertionError):
+            proto_interop_fields({'messages': ['Subscription']}, s)
+        with self.assertRaises(AssertionError):
+            proto_interop_fields(dict(config, enums={'Subscription.kinds': 'Kind'}), s)
+
+    def test_registered_apis(self):
+        with tempfile.TemporaryDirectory() as api_base:
+            for name, version, doc in (('storage', 'v1', {'title': 'Cloud Storage JSON API', 'revision': '20240229',
+                                                          'rootUrl': 'https://storage.googleapis.com/',
+                                                          'servicePath': 'storage/v1/'}),
+                                       ('pubsub', 'v1beta2', {'rootUrl': 'https://pubsub.googleapis.com/'})):
+                os.makedirs(os.path.join(api_base, name, version))
+                with open(os.path.join(api_base, name, version, name + '-api.json'), 'w') as fp:
+                    json.dump(doc, fp)
+            api_list = {'storage': ['v1'], 'pubsub': ['v1', 'v1beta2'], 'dataflow': ['v1b3']}
+            apis = registered_apis(api_base, api_list, ['dataflow'], '5.0.5')
+        self.assertEqual([(a.name, a.version) for a in apis], [('pubsub', 'v1beta2'), ('storage', 'v1')])
+        pubsub, storage = apis
+        self.assertEqual((pubsub.crate_name, pubsub.crate_version, pubsub.title),
+                         ('google-pubsub1_beta2', '5.0.5+00000000', 'pubsub'))
+        self.assertEqual((storage.cli_crate_name, storage.crate_version, storage.base_url),
+                         ('google-storage1-cli', '5.0.5+20240229', 'https://storage.googleapis.com/storage/v1/'))
+
+    def test_page_size_limit(self):
+        class Method(dict):
+            id = property(lambda self: self['id'])
+
+        def method(page_size, page_token=True):
+            m = Method(id='cloudtasks.projects.locations.queues.list', parameters={'pageSize': page_size})
+            if page_token:
+                m['parameters']['pageToken'] = {'type': 'string'}
+            return m
+
+        documented = {'type': 'integer', 'description': 'The maximum value is 1,000; values above 1,000 are coerced.'}
+        self.assertEqual(page_size_limit(None, method(documented)), ('pageSize', 1000))
+        self.assertEqual(page_size_limit(None, method({'type': 'integer', 'maximum': '500'})), ('pageSize', 500))
+        limits = {'cloudtasks.projects.locations.queues.list': 100}
+        self.assertEqual(page_size_limit(limits, method(documented)), ('pageSize', 100))
+        # maxima depending on other parameters are ambiguous
+        ambiguous = {'type': 'integer', 'description': 'The maximum page size is 1000 with ids only, otherwise the '
+                                                       'maximum page size is 100.'}
+        self.assertIsNone(page_size_limit(None, method(ambiguous)))
+        self.assertIsNone(page_size_limit(None, method({'type': 'integer', 'description': 'The page size.'})))
+        self.assertIsNone(page_size_limit(None, method(documented, page_token=False)))
+        self.assertIsNone(page_size_limit(None, method({'type': 'string', 'maximum': '10'})))
+
+    def test_resource_name_from_pattern(self):
+        rn = resource_name_from_pattern('^projects/[^/]+/locations/[^/]+/instances/[^/]+/dnsPeerings/[^/]+$')
+        self.assertEqual(rn.type_name, 'DnsPeeringName')
+        self.assertEqual(rn.template(),
+                         'projects/{project}/locations/{location}/instances/{instance}/dnsPeerings/{dns_peering}')
+        self.assertEqual(rn.format_str(), 'projects/{}/locations/{}/instances/{}/dnsPeerings/{}')
+        self.assertEqual(resource_name_from_pattern('^projects/[^/]+/types/[^/]+$').segments,
+                         [('projects', 'project'), ('types', 'type_')])
+        for pattern in ('^projects/[^/]+/.*$', '^[^/]+$', '^projects/[^/]+/settings$', '^folders/[^/]+/folders/[^/]+$',
+                        'projects/[^/]+'):
+            self.assertIsNone(resource_name_from_pattern(pattern))
+
+    def test_resource_names(self):
+        def method(*patterns):
+            return {'parameters': dict(('p%d' % i, {'location': 'path', 'pattern': p})
+                                       for i, p in enumerate(patterns))}
+        instance = '^projects/[^/]+/locations/[^/]+/instances/[^/]+$'
+        names = resource_names({}, [method(instance, '^projects/[^/]+$'),
+                                    method('^projects/[^/]+/zones/[^/]+$', '^projects/[^/]+/regions/[^/]+/zones/[^/]+$'),
+                                    method(instance)])
+        self.assertEqual(sorted(rn.type_name for rn in names.values()), ['InstanceName', 'ProjectName'])
+        self.assertEqual(list(resource_names({'InstanceName': {}}, [method(instance)])), [])
+
     def test_url_substitution(self):
         url = "https://www.googleapis.com/resumable/upload/groups/v1/groups/{groupId}/{foo}/archive"
         ms = list(re_find_replacements.finditer(url))
@@ -106,6 +354,55 @@ class UtilsTest(unittest.TestCase):
         rust_type = to_rust_type(schemas, class_name, property_name, property_value, allow_optionals=True)
         self.assertEqual(rust_type, 'Option<Vec<HashMap<String, json::Value>>>')
 
+    def test_map_fields(self):
+        schemas = {
+            'Metadata': {'id': 'Metadata', 'type': 'object', 'additionalProperties': {'type': 'string'}},
+            'Config': {'id': 'Config', 'type': 'object', 'properties': {'name': {'type': 'string'}}},
+            'Zones': {'id': 'Zones', 'type': 'array', 'items': {'type': 'string'}},
+        }
+        s = {'id': 'Instance', 'type': 'object', 'properties': {
+            'name': {'type': 'string'},
+            'labels': {'type': 'object', 'additionalProperties': {'type': 'string'}},
+            'metadata': {'$ref': 'Metadata'},
+            'config': {'$ref': 'Config'},
+            'zones': {'$ref': 'Zones'},
+            'payload': {'type': 'any'},
+            'status': {'type': 'object', 'properties': {'code': {'type': 'integer'}}},
+            'nodes': {'type': 'array', 'items': {'$ref': 'Config'}},
+        }}
+        self.assertEqual(map_fields(schemas, s), [
+            ('labels', None), ('metadata', None), ('config', 'Config'), ('payload', None), ('status', 'InstanceStatus'),
+        ])
+        self.assertEqual(map_fields(schemas, schemas['Metadata']), [])
+
+    def test_to_rust_type_labels(self):
+        labels = {'type': 'object', 'additionalProperties': {'type': 'string'}}
+        # only labels marked to follow the rules of resource labels are validated
+        self.assertFalse(is_labels_property('labels', labels))
+        self.assertEqual(to_rust_type({}, 'LogEntry', 'labels', labels), 'Option<HashMap<String, String>>')
+        schemas = {'Instance': {'id': 'Instance', 'properties': {'labels': labels}}}
+        mark_resource_labels(schemas, ['Instance'])
+        self.assertTrue(is_labels_property('labels', labels))
+        self.assertEqual(to_rust_type({}, 'Instance', 'labels', labels), 'Option<client::Labels>')
+        self.assertEqual(to_rust_type({}, 'Instance', 'labels', labels, allow_optionals=False), 'client::Labels')
+
+        # other maps of strings, and labels of other types, remain maps
+        self.assertFalse(is_labels_property('tags', labels))
+        self.assertEqual(to_rust_type({}, 'Instance', 'tags', labels), 'Option<HashMap<String, String>>')
+        typed_labels = {'type': 'object', 'additionalProperties': {'$ref': 'Label'}}
+        self.assertFalse(is_labels_property('labels', typed_labels))
+        self.assertEqual(to_rust_type({}, 'Instance', 'labels', typed_labels), 'Option<HashMap<String, Label>>')
+
+
+    def test_to_rust_type_binary(self):
+        body = {'type': 'string', 'format': 'byte'}
+        self.assertEqual(to_rust_type({}, 'Task', 'body', body), 'Option<Binary>')
+        serde_type, changed = to_serde_type({}, 'Task', 'body', body)
+        self.assertTrue(changed)
+        self.assertEqual(str(serde_type), 'Option<::client::serde::standard_base64::Wrapper>')
+        chunks = {'type': 'array', 'items': body}
+        self.assertEqual(str(to_serde_type({}, 'Task', 'chunks', chunks)[0]),
+                         'Option<Vec<::client::serde::standard_base64::Wrapper>>')
 
 def main():
     unittest.main()
@@ -2,6 +2,7 @@ import generator.lib.util as util
 
 import os
 import re
+import json
 import collections
 from copy import deepcopy
 from random import (randint, random, choice)
@@ -19,7 +20,32 @@ VALUE_ARG = 'v'
 KEY_VALUE_ARG = 'kv'
 SCOPE_FLAG = 'scope'
 CONFIG_DIR_FLAG = 'config-dir'
+PROFILE_FLAG = 'profile'
 DEBUG_FLAG = 'debug'
+DRY_RUN_FLAG = 'dry-run'
+OFFLINE_FLAG = 'offline'
+FIXTURES_FLAG = 'fixtures'
+HEADER_FLAG = 'header'
+IMPERSONATE_FLAG = 'impersonate-service-account'
+KEY_FILE_FLAG = 'key-file'
+SUBJECT_FLAG = 'subject'
+DEVICE_FLOW_FLAG = 'device-flow'
+ALL_PAGES_FLAG = 'all-pages'
+MAX_ITEMS_FLAG = 'max-items'
+WAIT_TIMEOUT_FLAG = 'timeout'
+POLL_FLAG = 'poll'
+ADDRESS_FAMILY_FLAG = 'address-family'
+FORMAT_FLAG = 'format'
+PROGRESS_FLAG = 'progress'
+FIELD_FLAG = 'field'
+COLUMNS_FLAG = 'columns'
+SORT_BY_FLAG = 'sort-by'
+REQUEST_FILE_FLAG = 'request-file'
+REQUEST_JSON_FLAG = 'request-json'
+SCHEMA_CMD = 'schema'
+METHODS_CMD = 'list-methods'
+DESCRIBE_CMD = 'describe'
+CONFIG_CMD = 'config'
 DEFAULT_MIME = 'application/octet-stream'
 
 MODE_ARG = 'mode'
@@ -28,9 +54,29 @@ FILE_FLAG = 'f'
 MIME_ARG = 'mime'
 MIME_FLAG = 'm'
 OUT_ARG = 'out'
+WAIT_TIMEOUT_ARG = 'seconds'
+POLL_ARG = 'interval'
 
 SCOPE_ARG = 'url'
+ADDRESS_FAMILY_ARG = 'family'
+FORMAT_ARG = 'output-format'
+PROGRESS_ARG = 'progress-format'
+FIELD_ARG = 'field-path'
+COLUMNS_ARG = 'column-paths'
+SORT_BY_ARG = 'sort-path'
+HEADER_ARG = 'name-value'
+IMPERSONATE_ARG = 'email'
+KEY_FILE_ARG = 'key-path'
+SUBJECT_ARG = 'user-email'
+REQUEST_FILE_ARG = 'path'
+REQUEST_JSON_ARG = 'json'
 CONFIG_DIR_ARG = 'folder'
+PROFILE_ARG = 'profile-name'
+FIXTURES_ARG = 'fixtures-dir'
+SCHEMA_RESOURCE_ARG = 'resource'
+SCHEMA_METHOD_ARG = 'method'
+CONFIG_KEY_ARG = 'key'
+CONFIG_VALUE_ARG = 'value'
 
 FIELD_SEP = '.'
 
@@ -72,6 +118,14 @@ JSON_TYPE_TO_ENUM_MAP = {'boolean' : 'Boolean',
                          'uint64'  : 'Uint',
                          'string'  : 'String'}
 
+# Maps discovery formats to the type they have in the JSON representation
+JSON_SCHEMA_FORMAT_TYPE_MAP = {'int32'  : 'integer',
+                               'uint32' : 'integer',
+                               'int64'  : 'string',
+                               'uint64' : 'string',
+                               'float'  : 'number',
+                               'double' : 'number'}
+
 CTYPE_TO_ENUM_MAP = {CTYPE_POD:   'Pod',
                      CTYPE_ARRAY: 'Vec',
                      CTYPE_MAP:   'Map'}
@@ -82,13 +136,87 @@ def new_method_context(resource, method, c):
     m = c.fqan_map[util.to_fqan(c.rtc_map[resource], resource, method)]
     response_schema = util.method_response(c, m)
     params, request_value = util.build_all_params(c, m)
-    # let CLIs not support resumable downloads or uploads for now, but don't affect the APIs
-    media_params = list(filter(lambda mp: mp.protocol == "simple", util.method_media_params(m)))
+    media_params = util.method_media_params(m)
     required_props, optional_props, part_prop = util.organize_params(params, request_value)
 
     return MethodContext(m, response_schema, params, request_value, media_params,
                          required_props, optional_props, part_prop)
 
+# Returns the name of the property holding the items of the responses of a paged list method, whose pages the
+# CLI can fetch one after another to output all items at once, or None for other methods.
+def paged_items_property(c, mc):
+    if not mc.response_schema or mc.media_params or mc.m.get('supportsMediaDownload', False):
+        return None
+    if 'nextPageToken' not in (mc.response_schema.get('properties') or dict()):
+        return None
+    if not any(p.name == 'pageToken' for p in mc.optional_props):
+        return None
+    items = util.list_items_property(c.schemas, mc.response_schema)
+    return items and items[0]
+
+# Returns the parameter naming the operation to get, if the given method is an 'operations.get' method the CLI can poll
+# operations with, i.e. one taking the name of the operation as its only parameter, and returning it.
+def _operation_get_name_param(c, resource, method):
+    if not (resource + '.' + method).endswith('operations.get'):
+        return None
+    mc = new_method_context(resource, method, c)
+    if not mc.response_schema or 'done' not in (mc.response_schema.get('properties') or dict()):
+        return None
+    if mc.request_value or len(mc.required_props) != 1:
+        return None
+    name_param = mc.required_props[0]
+    if name_param.get('type') != 'string' or name_param.get('repeated', False):
+        return None
+    return name_param
+
+# Returns (wait_method, get_method, name_param) for each 'operations.get' method of the given resource. The CLI waits
+# for the operation of the given name using wait_method, like 'locations.operations.wait', by polling it with
+# get_method until it is done, unless the API has a wait method of its own.
+def operation_waits(c, resource):
+    methods = c.rta_map.get(resource, list())
+    res = list()
+    for method in sorted(methods):
+        name_param = _operation_get_name_param(c, resource, method)
+        if name_param is None:
+            continue
+        wait_method = method[:-len('get')] + 'wait'
+        if wait_method in methods:
+            continue
+        res.append((wait_method, method, name_param))
+    # end for each method
+    return res
+
+# Returns (get_resource, get_method, name_param) of the 'operations.get' method the CLI polls the operation returned by
+# the given method with when it is called with --poll, or None if it doesn't return an operation. Of all methods
+# getting the same type of operation, the one of the resource sharing the longest prefix with the given one is used,
+# like 'projects.locations.operations.get' for 'projects.locations.instances.create'.
+def operation_poll(c, resource, method):
+    mc = new_method_context(resource, method, c)
+    if (not mc.response_schema or mc.media_params or mc.m.get('supportsMediaDownload', False)
+            or paged_items_property(c, mc) or (resource + '.' + method).endswith(('operations.get', 'operations.wait'))):
+        return None
+    properties = mc.response_schema.get('properties') or dict()
+    if 'done' not in properties or 'name' not in properties:
+        return None
+    parts = (resource + '.' + method).split('.')
+    best = None
+    for get_resource in sorted(c.rta_map.keys()):
+        for get_method in sorted(c.rta_map[get_resource]):
+            name_param = _operation_get_name_param(c, get_resource, get_method)
+            if name_param is None:
+                continue
+            if new_method_context(get_resource, get_method, c).response_schema.id != mc.response_schema.id:
+                continue
+            shared = 0
+            for a, b in zip(parts, (get_resource + '.' + get_method).split('.')):
+                if a != b:
+                    break
+                shared += 1
+            if best is None or shared > best[0]:
+                best = (shared, (get_resource, get_method, name_param))
+    # end for each get method
+    return best and best[1]
+
 def comma_sep_fields(fields):
     return ', '.join('"%s"' % mangle_subcommand(f) for f in sorted(fields))
 
@@ -222,6 +350,109 @@ def cli_schema_to_yaml(schema, prefix=''):
     return o
 
 
+# Convert the given cli-schema (result from to_cli_schema(schema)) into a JSON Schema document (as dict).
+# It only describes the fields which can be set using the field cursor.
+def cli_schema_to_json_schema(schema, root=True):
+    res = dict()
+    if root:
+        res['$schema'] = 'http://json-schema.org/draft-07/schema#'
+        res['title'] = schema.id
+    if 'description' in schema:
+        res['description'] = schema.description
+    res['type'] = 'object'
+    properties = dict()
+    for fn in sorted(schema.fields.keys()):
+        f = schema.fields[fn]
+        if not isinstance(f, SchemaEntry):
+            properties[fn] = cli_schema_to_json_schema(f, root=False)
+            continue
+        entry = _json_schema_pod(f.actual_property)
+        if f.container_type == CTYPE_ARRAY:
+            entry = {'type': 'array', 'items': entry}
+        elif f.container_type == CTYPE_MAP:
+            entry = {'type': 'object', 'additionalProperties': entry}
+        if 'description' in f.property:
+            entry['description'] = f.property.description
+        properties[fn] = entry
+    # end for each field
+    res['properties'] = properties
+    return res
+
+
+def _json_schema_pod(p):
+    fmt = p.get('format')
+    res = {'type': JSON_SCHEMA_FORMAT_TYPE_MAP.get(fmt) or JSON_SCHEMA_FORMAT_TYPE_MAP.get(p.type, p.type)}
+    if fmt:
+        res['format'] = fmt
+    if 'enum' in p:
+        res['enum'] = list(p.enum)
+    return res
+
+
+# Return the JSON Schema of the given cli-schema as compact string, suitable for embedding into a raw string literal
+def cli_schema_to_json_schema_s(schema):
+    return json.dumps(cli_schema_to_json_schema(schema), sort_keys=True, separators=(',', ':'))
+
+
+# Return the type of a parameter or request field as shown to users, like 'int64', '[string]' or '{ string: string }'
+def _described_type(p, container_type=CTYPE_POD):
+    t = p.get('format', p.type)
+    if container_type == CTYPE_ARRAY or p.get('repeated', False):
+        t = '[%s]' % t
+    elif container_type == CTYPE_MAP:
+        t = '{ string: %s }' % t
+    return t
+
+
+# Return the fields of the given cli-schema as list of dicts with the path used with the field cursor, their type and
+# description
+def _cli_schema_fields(schema, prefix=''):
+    res = list()
+    for fn in sorted(schema.fields.keys()):
+        f = schema.fields[fn]
+        path = prefix + mangle_subcommand(fn)
+        if not isinstance(f, SchemaEntry):
+            res.extend(_cli_schema_fields(f, path + FIELD_SEP))
+            continue
+        res.append({'path': path,
+                    'type': _described_type(f.actual_property, f.container_type),
+                    'description': f.property.get('description', '').strip()})
+    # end for each field
+    return res
+
+
+# Return a dict describing the given method as shown by the `describe` subcommand: its required arguments, optional
+# parameters and the fields of its request value.
+def method_description(c, resource, method):
+    mc = new_method_context(resource, method, c)
+
+    def describe_param(p):
+        return {'name': mangle_subcommand(p.name),
+                'type': _described_type(p),
+                'description': p.get('description', '').strip()}
+
+    return {'resource': mangle_subcommand(resource),
+            'method': mangle_subcommand(method),
+            'id': mc.m.id,
+            'description': mc.m.get('description', '').strip(),
+            'arguments': [describe_param(p) for p in mc.required_props if not is_request_value_property(mc, p)],
+            'parameters': [describe_param(p) for p in sorted(mc.optional_props, key=lambda p: p.name)
+                           if not p.get('skip_example', False)],
+            'request': mc.request_value and mc.request_value.id or None,
+            'request_fields': mc.request_value and _cli_schema_fields(to_cli_schema(c, mc.request_value)) or [],
+            'response': mc.response_schema and mc.response_schema.id or None,
+            'upload': [mp.protocol for mp in mc.media_params]}
+
+
+# Return the descriptions of all methods as compact JSON array, suitable for embedding into a raw string literal
+def method_descriptions_s(c):
+    descriptions = [method_description(c, resource, method)
+                    for resource in sorted(c.rta_map.keys()) for method in sorted(c.rta_map[resource])]
+    s = json.dumps(descriptions, sort_keys=True, separators=(',', ':'))
+    assert '"##' not in s
+    return s
+
+
 # Return a value string suitable for the given field.
 def field_to_value(f):
     v = JSON_TYPE_RND_MAP[f.actual_property.type]()
@@ -21,6 +21,7 @@ CHRONO_DATETIME = f"{CHRONO_PATH}::DateTime<{CHRONO_PATH}::offset::Utc>"
 CHRONO_DATE = f"{CHRONO_PATH}::NaiveDate"
 USE_FORMAT = 'use_format_field'
 CHRONO_UTC_NOW = "chrono::Utc::now()"
+BINARY = "Binary"
 
 RUST_TYPE_MAP = {
     'boolean': Base("bool"),
@@ -47,7 +48,8 @@ RUST_TYPE_MAP = {
     # https://github.com/protocolbuffers/protobuf/blob/ec1a70913e5793a7d0a7b5fbf7e0e4f75409dd41/src/google/protobuf/duration.proto
     'google-duration': Base(f"{CHRONO_PATH}::Duration"),
     # guessing bytes is universally url-safe b64
-    "byte": Vec(Base("u8")),
+    # Vec<u8>, or bytes::Bytes with the `bytes` feature
+    "byte": Base(BINARY),
     # https://github.com/protocolbuffers/protobuf/blob/ec1a70913e5793a7d0a7b5fbf7e0e4f75409dd41/src/google/protobuf/field_mask.proto
     "google-fieldmask": Base("client::FieldMask")
 }
@@ -66,6 +68,8 @@ RUST_TYPE_RND_MAP = {
     "Vec<u8>": lambda: f"vec![0, 1, 2, 3]",
     # why a reference to Vec? Because it works. Should be slice, but who knows how typing works here.
     "&Vec<u8>": lambda: f"&vec![0, 1, 2, 3]",
+    BINARY: lambda: "vec![0, 1, 2, 3].into()",
+    f"&{BINARY}": lambda: "&vec![0, 1, 2, 3].into()",
     # TODO: styling this
     f"{CHRONO_PATH}::Duration": lambda: f"chrono::Duration::seconds({randint(0, 9999999)})",
     CHRONO_DATE: chrono_date,
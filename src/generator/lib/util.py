@@ -1,3 +1,5 @@
+import datetime
+import json
 import os
 import re
 import subprocess
@@ -5,7 +7,7 @@ import urllib
 
 import inflect
 from dataclasses import dataclass
-from typing import Any, Dict, List, Mapping, Tuple
+from typing import Any, Dict, List, Mapping, Optional, Tuple
 from copy import deepcopy
 from .rust_type import Base, Box, HashMap, Vec, Option, RustType
 from .types import RUST_TYPE_MAP, RUST_TYPE_RND_MAP
@@ -19,6 +21,8 @@ re_desc_parts = re.compile(
 
 re_find_replacements = re.compile(r"\{[/\+]?\w+\*?\}")
 re_relative_links = re.compile(r"\]\s*\([^h]")
+re_sentence_end = re.compile(r"(?<=\.)\s+")
+re_use_instead = re.compile(r"\buse\b.+\binstead\b", flags=re.IGNORECASE)
 
 HTTP_METHODS = set(("OPTIONS", "GET", "POST", "PUT", "DELETE", "HEAD", "TRACE", "CONNECT", "PATCH"))
 
@@ -62,6 +66,9 @@ REQUEST_VALUE_PROPERTY_NAME = 'request'
 DELEGATE_PROPERTY_NAME = 'delegate'
 TO_PARTS_MARKER = 'client::ToParts'
 UNUSED_TYPE_MARKER = 'client::UnusedType'
+LABELS_TYPE = 'client::Labels'
+# set on `labels` properties which follow the rules of resource labels, see mark_resource_labels()
+RESOURCE_LABELS_MARKER = 'resource_labels'
 
 PROTOCOL_TYPE_INFO = {
     'simple': {
@@ -381,6 +388,23 @@ def is_map_prop(p):
     return 'additionalProperties' in p
 
 
+# return True if the property holds the labels of a resource, which are typed as client::Labels to be validated.
+# Only the labels marked by mark_resource_labels() are, as others may hold any pairs, like system keys with dots
+def is_labels_property(property_name, p):
+    return (property_name == 'labels' and p.get(RESOURCE_LABELS_MARKER, False) and p.get('type') == 'object'
+            and p.get('additionalProperties', {}).get('type') == 'string')
+
+
+# Mark the `labels` of the schemas with the given ids, which the API documents to follow the rules of resource labels,
+# so they are typed as client::Labels. `schema_ids` is the optional 'api.resource_labels' overrides entry. It must be
+# called before the context of the schemas is created.
+def mark_resource_labels(schemas, schema_ids):
+    for sid in schema_ids or ():
+        p = schemas[sid]['properties']['labels']
+        assert p.get('additionalProperties', {}).get('type') == 'string', "%s.labels is not a map of strings" % sid
+        p[RESOURCE_LABELS_MARKER] = True
+
+
 def _assure_unique_type_name(schemas, tn):
     if tn in schemas:
         tn += 'Nested'
@@ -447,6 +471,8 @@ def to_rust_type_inner(
         if not _is_recursive and tn == schema_name:
             rt = Option(Box(rt))
         return wrap_type(rt)
+    if not _is_recursive and is_labels_property(property_name, t):
+        return wrap_type(Base(LABELS_TYPE))
     try:
         # prefer format if present
         rust_type = RUST_TYPE_MAP[t.get("format", t["type"])]
@@ -556,6 +582,93 @@ def schema_markers(s, c, transitive=True):
     return sorted(res)
 
 
+# Returns the name of the property holding the items of a list response, along with the rust type of
+# its items. It is the `items` array if there is one, or the only array of a paged response.
+# Returns None for schemas which are no lists.
+def list_items_property(schemas, s):
+    properties = s.get('properties') or dict()
+    arrays = [pn for pn, p in items(properties) if p.get('type') == 'array']
+    if 'items' in arrays:
+        pn = 'items'
+    elif len(arrays) == 1 and ('nextPageToken' in properties or 'pageToken' in properties):
+        pn = arrays[0]
+    else:
+        return None
+    # nested types of the context's schemas would make the name of the items type seem taken
+    schemas = {sn: ns for sn, ns in items(schemas) if not ns.get('parents')}
+    vec = to_rust_type_inner(schemas, s['id'], pn, properties[pn], allow_optionals=False)
+    return pn, str(vec.members[0])
+
+
+# Returns the name and item type of the list items property of the response of method `m`, along with its optional
+# page token parameter, if the call can be paged through by passing on the 'nextPageToken' of each response.
+# Returns None otherwise, or if the method is server-streaming or has media.
+def paged_items(c, m, server_streaming):
+    response_schema = method_response(c, m)
+    if not response_schema or is_server_streaming_method(server_streaming, m) or method_media_params(m) \
+            or m.get('supportsMediaDownload', False):
+        return None
+    if 'nextPageToken' not in response_schema.get('properties', dict()):
+        return None
+    list_items = list_items_property(c.schemas, response_schema)
+    if list_items is None:
+        return None
+    optional_params = organize_params(*build_all_params(c, m))[1]
+    page_token_param = next((p for p in optional_params if p.name == 'pageToken' and p.get('type') == 'string'), None)
+    if page_token_param is None:
+        return None
+    return list_items[0], list_items[1], page_token_param
+
+
+# Returns the name of the property of the list response schema `s` whose items can be streamed, along with their
+# type, or None. Only items which are schemas themselves are streamed, as others may need custom deserialization.
+def streamed_items_property(schemas, s):
+    list_items = list_items_property(schemas, s)
+    if list_items is None or TREF not in s['properties'][list_items[0]].get('items', dict()):
+        return None
+    return list_items
+
+
+# Returns the names of the properties of schema `s` which the 'required_response_fields' api override
+# documents to be always present, and which are non-optional with the 'strict-required' feature.
+# Only schemas which are never sent to the server may have such properties, as their default values
+# would be sent otherwise. Arrays can't be required either, as the server omits them if they are empty.
+def strict_required_properties(required_fields, s, markers):
+    names = (required_fields or dict()).get(s['id'], list())
+    if names:
+        assert REQUEST_MARKER_TRAIT not in markers, \
+            "Schema '%s' is part of requests and can't have required response fields" % s['id']
+        for pn in names:
+            p = s.get('properties', dict()).get(pn)
+            assert p is not None, "Schema '%s' has no property '%s'" % (s['id'], pn)
+            assert p.get('type') != 'array', "Property '%s' of schema '%s' is omitted if empty" % (pn, s['id'])
+    return set(names)
+
+
+
+# Return the sorted names of the properties of a request schema which the server requires, as their description
+# says. The builders of the schema fail to build values missing them.
+def required_request_properties(s):
+    return sorted(pn for pn, p in items(s.get('properties') or dict())
+                  if p.get('description', '').lstrip().startswith('Required'))
+
+# Return the properties of the object schema `s` which client::FieldMask::diff() compares as a whole, as (name, None),
+# and those holding objects whose fields it compares, as (name, type of the object), see client::MapFields.
+def map_fields(schemas, s):
+    fields = list()
+    for pn, p in items(s.get('properties') or dict()):
+        if TREF in p:
+            ref = schemas.get(p[TREF], dict())
+            if ref.get('type') == 'any' or 'additionalProperties' in ref and not ref.get('properties'):
+                fields.append((pn, None))
+            elif ref.get('type') == 'object' and ref.get('properties'):
+                fields.append((pn, p[TREF]))
+        elif p.get('type') == 'any' or is_map_prop(p):
+            fields.append((pn, None))
+        elif p.get('type') == 'object' and p.get('properties'):
+            fields.append((pn, to_rust_type(schemas, s['id'], pn, p, allow_optionals=False)))
+    return fields
+
 ## -- End Rust TypeSystem -- @}
 
 # NOTE: unfortunately, it turned out that sometimes fields are missing. The only way to handle this is to
@@ -674,6 +787,19 @@ def setter_fn_name(p):
     return fn_name
 
 
+# Returns the note of the #[deprecated] attribute of the setter of parameter `p`, or None if it isn't deprecated.
+# It's the sentence of the description naming the replacement, if there is one, as a rust string literal.
+def deprecation_note(p):
+    if not p.get('deprecated', False):
+        return None
+    note = 'The parameter is deprecated and may be ignored by the server.'
+    for sentence in re_sentence_end.split(p.get('description', '')):
+        if re_use_instead.search(sentence):
+            note = sentence.strip().rstrip('.') + '.'
+            break
+    return '"%s"' % note.replace('\\', '\\\\').replace('"', '\\"').replace('\n', ' ')
+
+
 # _method_params(...), request_value|None -> (required_properties, optional_properties, part_prop|None)
 def organize_params(params, request_value):
     part_prop = None
@@ -746,12 +872,281 @@ def build_all_params(c, m):
                       
                       It should be used to handle progress information, and to implement a certain level of resilience."""})
     params.append(dp)
+    # path parameters with a typed resource name are passed as such
+    for p in params:
+        rn = p.get('location') == 'path' and c.resource_names.get(p.get('pattern'))
+        if rn:
+            p.resource_name = rn
+            p.input_type = 'impl Into<%s>' % rn.type_name
+            p.clone_value = '{}.into().to_string()'
+    # end for each param
     return params, request_value
 
 
 ## -- End Activity Utilities -- @}
 
 
+## -- IAM Policy Utilities -- @{
+
+IAM_POLICY_VERSION_PARAMS = ('options.requestedPolicyVersion', 'optionsRequestedPolicyVersion')
+
+
+@dataclass
+class IamPolicyCycle:
+    # the activity of the generated method, like 'locations.instances.modifyIamPolicy'
+    activity: str
+    get_activity: str
+    set_activity: str
+    policy_type: str
+    set_request_type: str
+    # the required parameters of both methods, except for their requests
+    params: List[Any]
+    get_request_type: Optional[str]
+    # the optional parameter of the get method requesting the policy version, if it has one
+    version_param: Optional[Any]
+    # the (property, type) of the options of the get request requesting the policy version, if it has them
+    version_options: Optional[Tuple[str, str]]
+
+
+# Returns the read-modify-write cycles of IAM policies for all pairs of getIamPolicy and setIamPolicy
+# activities of the given resource, like 'locations.instances.getIamPolicy'.
+def iam_policy_cycles(c, resource) -> List[IamPolicyCycle]:
+    activities = c.rta_map.get(resource, list())
+    cycles = list()
+    for get_activity in activities:
+        if get_activity.split('.')[-1] != 'getIamPolicy':
+            continue
+        prefix = get_activity[:-len('getIamPolicy')]
+        set_activity = prefix + 'setIamPolicy'
+        activity = prefix + 'modifyIamPolicy'
+        if set_activity not in activities or activity in activities:
+            continue
+        category = c.rtc_map[resource]
+        get_m = c.fqan_map[to_fqan(category, resource, get_activity)]
+        set_m = c.fqan_map[to_fqan(category, resource, set_activity)]
+        policy = method_response(c, get_m)
+        set_request = method_request(c, set_m)
+        if policy is None or (method_response(c, set_m) or dict()).get('id') != policy.id or set_request is None:
+            continue
+        if set_request.get('properties', dict()).get('policy', dict()).get(TREF) != policy.id:
+            continue
+
+        get_params, get_request = build_all_params(c, get_m)
+        set_params, _ = build_all_params(c, set_m)
+        get_required, get_optional, _ = organize_params(get_params, get_request)
+        set_required, _, _ = organize_params(set_params, set_request)
+        params = [p for p in get_required if p.name != REQUEST_VALUE_PROPERTY_NAME]
+        if [p.name for p in params] != [p.name for p in set_required if p.name != REQUEST_VALUE_PROPERTY_NAME]:
+            continue
+        # each argument is passed to both methods on each attempt, which requires them to be cheap to copy
+        if any(p.get('repeated') or not (p.get('resource_name') or p.get('type') == 'string' or is_pod_property(p))
+               for p in params):
+            continue
+
+        version_param = next((p for p in get_optional if p.name in IAM_POLICY_VERSION_PARAMS), None)
+        version_options = None
+        if get_request:
+            options = get_request.get('properties', dict()).get('options', dict())
+            options_schema = c.schemas.get(options.get(TREF))
+            if options_schema and 'requestedPolicyVersion' in options_schema.get('properties', dict()):
+                version_options = ('options', options_schema.id)
+        cycles.append(IamPolicyCycle(activity, get_activity, set_activity, policy.id, set_request.id, params,
+                                     get_request and get_request.id or None, version_param, version_options))
+    # end for each activity
+    return cycles
+
+## -- End IAM Policy Utilities -- @}
+
+
+## -- Operation Utilities -- @{
+
+@dataclass
+class OperationListing:
+    # the activities of the generated methods, like 'locations.operations.stream' and 'locations.operations.cancelAll'
+    stream_activity: str
+    cancel_all_activity: str
+    list_activity: str
+    cancel_activity: str
+    operation_type: str
+    # the required parameters of the list method
+    params: List[Any]
+    # the required parameters of the cancel method, one of which takes the name of the operation
+    cancel_params: List[Any]
+    cancel_request_type: Optional[str]
+
+
+# Returns the listings of long-running operations for all pairs of 'operations.list' and 'operations.cancel'
+# activities of the given resource, whose responses are pages of operations.
+def operation_listings(c, resource) -> List[OperationListing]:
+    activities = c.rta_map.get(resource, list())
+    listings = list()
+    for list_activity in activities:
+        if not (resource + '.' + list_activity).endswith('operations.list'):
+            continue
+        prefix = list_activity[:-len('list')]
+        cancel_activity = prefix + 'cancel'
+        stream_activity = prefix + 'stream'
+        cancel_all_activity = prefix + 'cancelAll'
+        if cancel_activity not in activities or stream_activity in activities or cancel_all_activity in activities:
+            continue
+        category = c.rtc_map[resource]
+        list_m = c.fqan_map[to_fqan(category, resource, list_activity)]
+        cancel_m = c.fqan_map[to_fqan(category, resource, cancel_activity)]
+        page = method_response(c, list_m)
+        if page is None or 'nextPageToken' not in page.get('properties', dict()):
+            continue
+        items = list_items_property(c.schemas, page)
+        if items is None or items[0] != 'operations':
+            continue
+        operation = c.schemas.get(items[1])
+        if operation is None or not {'name', 'done'} <= set(operation.get('properties', dict())):
+            continue
+
+        list_params, list_request = build_all_params(c, list_m)
+        list_required, list_optional, _ = organize_params(list_params, list_request)
+        if list_request or 'pageToken' not in [p.name for p in list_optional]:
+            continue
+        # each argument is passed to the list method for each page
+        if any(p.get('repeated') or not (p.get('resource_name') or p.get('type') == 'string' or is_pod_property(p))
+               for p in list_required):
+            continue
+        cancel_params, cancel_request = build_all_params(c, cancel_m)
+        cancel_required = organize_params(cancel_params, cancel_request)[0]
+        names = [p for p in cancel_required if p.name != REQUEST_VALUE_PROPERTY_NAME]
+        if len(names) != 1 or not (names[0].get('resource_name') or names[0].get('type') == 'string'):
+            continue
+        listings.append(OperationListing(stream_activity, cancel_all_activity, list_activity, cancel_activity,
+                                         operation.id, list_required, cancel_required,
+                                         cancel_request and cancel_request.id or None))
+    # end for each activity
+    return listings
+
+## -- End Operation Utilities -- @}
+
+
+## -- Example Utilities -- @{
+
+@dataclass
+class ExampleCall:
+    resource: str
+    activity: str
+    # the rust expressions of the placeholder arguments of the required parameters
+    args: List[str]
+    # the resource name types used by the arguments, which need to be imported
+    resource_name_types: List[str]
+    # the setter of the page token if the call is paged, or None
+    page_token_setter: Optional[str]
+    response_type: str
+
+
+# Returns the simplest call to show in examples and tests, or None: a GET without media, request or streamed
+# response, taking the fewest required parameters, which are passed placeholder values.
+def example_call(c, server_streaming) -> Optional[ExampleCall]:
+    call = None
+    for resource in sorted(c.rta_map.keys()):
+        for activity in sorted(c.rta_map[resource]):
+            m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, activity)]
+            if m.get('httpMethod') != 'GET' or method_media_params(m) or not method_response(c, m) \
+                    or is_server_streaming_method(server_streaming, m):
+                continue
+            params, request_value = build_all_params(c, m)
+            if request_value:
+                continue
+            required_props, optional_props, _ = organize_params(params, request_value)
+            if call is None or len(required_props) < len(call[3]):
+                call = (resource, activity, m, required_props, optional_props)
+        # end for each activity
+    # end for each resource
+    if call is None:
+        return None
+
+    resource, activity, m, required_props, optional_props = call
+    resource_name_types = list()
+    args = list()
+    for p in required_props:
+        if p.get('resource_name'):
+            rn = p.resource_name
+            if rn.type_name not in resource_name_types:
+                resource_name_types.append(rn.type_name)
+            args.append('%s::new(%s)' % (rn.type_name, ', '.join('"%s"' % f.rstrip('_') for _, f in rn.segments)))
+            continue
+        v = rnd_arg_val_for_type(activity_input_type(c.schemas, p))
+        if v.endswith('"'):
+            v = '"%s"' % p.name
+        args.append(v)
+    # end for each required property
+    response = method_response(c, m)
+    page_token_setter = None
+    if 'nextPageToken' in response.get('properties', dict()):
+        page_token_setter = next((mangle_ident(setter_fn_name(p)) for p in optional_props if p.name == 'pageToken'),
+                                 None)
+    return ExampleCall(resource, activity, args, resource_name_types, page_token_setter, response.id)
+
+## -- End Example Utilities -- @}
+
+
+## -- Resource Name Utilities -- @{
+
+re_resource_collection = re.compile(r"^[a-zA-Z][a-zA-Z0-9]*$")
+
+
+@dataclass
+class ResourceName:
+    type_name: str
+    # (collection, field) for each collection of the name, like ('instances', 'instance')
+    segments: List[Tuple[str, str]]
+
+    # Returns the template of the name, like 'projects/{project}/instances/{instance}'
+    def template(self) -> str:
+        return '/'.join('%s/{%s}' % (collection, field.rstrip('_')) for collection, field in self.segments)
+
+    # Returns the format string of the name, like 'projects/{}/instances/{}'
+    def format_str(self) -> str:
+        return '/'.join('%s/{}' % collection for collection, _ in self.segments)
+
+
+# Returns the resource name matched by the given pattern of a path parameter, like
+# '^projects/[^/]+/instances/[^/]+$', or None if it doesn't match names of the form
+# 'collection/{id}/collection/{id}'.
+def resource_name_from_pattern(pattern: str) -> Optional[ResourceName]:
+    if not (pattern.startswith('^') and pattern.endswith('$')):
+        return None
+    parts = pattern[1:-1].replace('[^/]+', '*').split('/')
+    if len(parts) % 2:
+        return None
+    segments = list()
+    for collection, id_pattern in zip(parts[::2], parts[1::2]):
+        if id_pattern != '*' or not re_resource_collection.match(collection):
+            return None
+        segments.append((collection, mangle_ident(singular(collection))))
+    # end for each segment
+    if len(set(field for _, field in segments)) != len(segments):
+        return None
+    return ResourceName(canonical_type_name(singular(segments[-1][0])) + 'Name', segments)
+
+
+# Returns a map of pattern -> ResourceName for the path parameters of all given methods.
+# Names whose type name is used by several patterns, or by a schema, are omitted.
+def resource_names(schemas, methods) -> Dict[str, ResourceName]:
+    by_pattern = dict()
+    for m in methods:
+        for p in m.get('parameters', dict()).values():
+            if p.get('location') != 'path' or p.get('pattern') in by_pattern:
+                continue
+            rn = p.get('pattern') and resource_name_from_pattern(p['pattern'])
+            if rn:
+                by_pattern[p['pattern']] = rn
+        # end for each parameter
+    # end for each method
+    patterns_by_type = dict()
+    for pattern, rn in by_pattern.items():
+        patterns_by_type.setdefault(rn.type_name, list()).append(pattern)
+    return dict((pattern, rn) for pattern, rn in by_pattern.items()
+                if len(patterns_by_type[rn.type_name]) == 1 and rn.type_name not in schemas)
+
+## -- End Resource Name Utilities -- @}
+
+
 @dataclass
 class Context:
     sta_map: Dict[str, Any]
@@ -759,6 +1154,7 @@ class Context:
     rta_map: Dict[str, Any]
     rtc_map: Dict[str, Any]
     schemas: Dict[str, Any]
+    resource_names: Dict[str, ResourceName]
 
 
 # return a newly build context from the given data
@@ -884,7 +1280,7 @@ def new_context(schemas: Dict[str, Dict[str, Any]], resources: Dict[str, Any]) -
 
     all_schemas = schemas and build_schema_map() or dict()
     if not resources:
-        return Context(dict(), dict(), dict(), dict(), all_schemas)
+        return Context(dict(), dict(), dict(), dict(), all_schemas, dict())
 
     rta_map: Dict[str, Any] = {}
     rtc_map: Dict[str, Any] = {}
@@ -899,7 +1295,7 @@ def new_context(schemas: Dict[str, Dict[str, Any]], resources: Dict[str, Any]) -
     # end for each fqan
     sta_map.update(_sta_map)
     fqan_map.update(_fqan_map)
-    return Context(sta_map, fqan_map, rta_map, rtc_map, all_schemas)
+    return Context(sta_map, fqan_map, rta_map, rtc_map, all_schemas, resource_names(all_schemas, fqan_map.values()))
 
 
 def _is_special_version(v):
@@ -928,6 +1324,201 @@ def to_api_version(v):
     return version
 
 
+# Returns the (bucket, cost) charged for calling method `m`, or None if unknown.
+# `quota` is the optional 'api.quota' overrides entry, with 'read' and 'write' costs for GET and
+# other methods respectively, and the 'methods' overriding the cost, or bucket and cost, of
+# particular method ids.
+def method_quota_cost(quota, m):
+    if not quota:
+        return None
+    cost = quota.get('methods', dict()).get(m.id)
+    if cost is not None and not isinstance(cost, int):
+        return cost['bucket'], cost['cost']
+    base = quota.get(m.get('httpMethod') == 'GET' and 'read' or 'write')
+    if not base:
+        return None
+    return base['bucket'], cost if cost is not None else base['cost']
+
+
+# Returns True if the server streams the responses of method `m`, as a JSON array whose elements are received
+# while the call is open. Discovery documents rarely mark such methods with their 'streamingType', so
+# `server_streaming` is the optional 'api.server_streaming' overrides entry, listing the ids of further methods.
+def is_server_streaming_method(server_streaming, m):
+    return m.get('streamingType') == 'SERVER_STREAMING' or m.id in (server_streaming or list())
+
+
+# The HTTP methods whose requests have the same effect when they are sent more than once.
+IDEMPOTENT_HTTP_METHODS = ('GET', 'HEAD', 'PUT', 'DELETE')
+# The names of custom methods sent using POST which only read, like 'projects.getIamPolicy' or 'documents.batchGet'.
+_READ_ONLY_METHOD_RE = re.compile(r'^(get|list|search|lookup|batchGet|testIamPermissions)([A-Z]|$)')
+
+
+# Returns True if method `m` may be retried without duplicating its side effects, judging by its HTTP method and by
+# the name of custom methods which only read. `idempotency` is the optional 'api.idempotency' overrides entry, mapping
+# method ids to True or False, e.g. for methods de-duplicating their calls by a request id.
+def is_idempotent_method(idempotency, m):
+    if m.id in (idempotency or dict()):
+        return bool(idempotency[m.id])
+    http_method = m.get('httpMethod')
+    if http_method in IDEMPOTENT_HTTP_METHODS:
+        return True
+    return http_method == 'POST' and bool(_READ_ONLY_METHOD_RE.match(m.id.split('.')[-1]))
+
+
+# The names of the query parameters taking a request id chosen by the client, by which servers de-duplicate retried
+# calls of mutating methods, like those of compute.
+REQUEST_ID_PARAMS = ('requestId',)
+
+
+# Returns the name of the optional query parameter of method `m` taking a request id, or None if it has none.
+def request_id_param(m):
+    params = m.get('parameters') or dict()
+    for name in REQUEST_ID_PARAMS:
+        p = params.get(name)
+        if (p and p.get('location') == 'query' and p.get('type') == 'string'
+                and not p.get('required', False) and not p.get('repeated', False)):
+            return name
+    return None
+
+
+# Returns the day of the shutdown of an API version, formatted like '2024-07-01', and how to migrate, from `sunset`, the
+# optional 'api.sunset' overrides entry with its 'date' and 'migration', or None if no shutdown was announced.
+def api_sunset(sunset):
+    if not sunset:
+        return None
+    # yaml parses unquoted dates
+    day = str(sunset['date'])
+    try:
+        datetime.date.fromisoformat(day)
+    except ValueError:
+        raise AssertionError("api.sunset.date %r is not a date like 2024-07-01" % day)
+    return day, sunset['migration']
+
+
+# The keys of discovery schemas which responses are validated against with the `validate-responses` feature.
+_VALIDATION_KEYS = ('type', 'format', 'enum', '$ref', 'items', 'properties', 'additionalProperties')
+
+
+# Returns the JSON of the discovery schemas the responses of the schemas named `response_ids` are validated against,
+# which are these and all schemas they refer to, with only their _VALIDATION_KEYS. The fields listed in `required_fields`,
+# the optional 'api.required_response_fields' overrides entry, are required.
+def validation_schemas(schemas, response_ids, required_fields):
+    def strip(s):
+        stripped = dict()
+        for k in _VALIDATION_KEYS:
+            v = s.get(k)
+            if v is None:
+                continue
+            if k in ('items', 'additionalProperties'):
+                # additionalProperties may be a boolean
+                if isinstance(v, bool):
+                    continue
+                v = strip(v)
+            elif k == 'properties':
+                # properties may be called 'items', so it's no method to call
+                v = dict((pn, strip(p)) for pn, p in items(v))
+            stripped[k] = v
+        return stripped
+
+    def refs(s):
+        if '$ref' in s:
+            yield s['$ref']
+        for k in ('items', 'additionalProperties'):
+            if k in s:
+                yield from refs(s[k])
+        for p in s.get('properties', dict()).values():
+            yield from refs(p)
+
+    required_fields = required_fields or dict()
+    validated = dict()
+    todo = list(response_ids)
+    while todo:
+        sid = todo.pop()
+        if sid in validated or sid not in schemas:
+            continue
+        s = strip(schemas[sid])
+        if required_fields.get(sid):
+            s['required'] = list(required_fields[sid])
+        validated[sid] = s
+        todo.extend(refs(s))
+    text = json.dumps(validated, sort_keys=True, separators=(',', ':'))
+    assert '"#' not in text, "the schemas can't be embedded as raw string"
+    return text
+
+
+# The names of the parameters setting the amount of items in each page of a list method.
+PAGE_SIZE_PARAMS = ('pageSize', 'maxResults', 'maxPageSize')
+# The phrases documenting the maximum page size in the description of a page size parameter.
+_PAGE_SIZE_LIMIT_RE = re.compile(
+    r"maximum (?:allowed |permitted )?(?:value |page size |number |size |limit )?(?:is|of) (\d[\d,]*)"
+    r"|(?:cannot|can't|must not|may not|should not) (?:be (?:greater|larger|more) than|exceed) (\d[\d,]*)"
+    r"|values? (?:above|greater than|larger than|over) (\d[\d,]*) (?:will be|are|is) "
+    r"(?:coerced|capped|truncated|clamped|reduced|changed)"
+    r"|max(?:imum)? (?:allowed )?(?:value|page size)(?: is)?:? (\d[\d,]*)", re.IGNORECASE)
+
+
+# Returns the name of the page size parameter of list method `m`, and the maximum page size the API documents for it,
+# or None if it has no such parameter or the maximum is unknown. The maximum is taken from `limits`, the optional
+# 'api.page_size_limits' overrides entry mapping method ids to their maximum, the 'maximum' of the parameter, or its
+# description, as long as the description mentions a single maximum.
+def page_size_limit(limits, m):
+    params = m.get('parameters') or dict()
+    if 'pageToken' not in params:
+        return None
+    for pn in PAGE_SIZE_PARAMS:
+        p = params.get(pn)
+        if p is None or p.get('type') != 'integer' or p.get('required', False):
+            continue
+        if m.id in (limits or dict()):
+            return pn, int(limits[m.id])
+        if 'maximum' in p:
+            return pn, int(p['maximum'])
+        found = {int(n.replace(',', '')) for groups in _PAGE_SIZE_LIMIT_RE.findall(p.get('description', ''))
+                 for n in groups if n}
+        if len(found) == 1:
+            return pn, found.pop()
+        return None
+    return None
+
+
+# Returns the fields of schema `s` which are converted to and from the message of the same name generated by prost, as
+# tuples of the field name in the schema, the field name in the message, and the prost enum the value of an enum field
+# is converted to by name, or None. `proto_interop` is the 'api.proto_interop' overrides entry, listing the schemas
+# which have a message in its 'messages', the fields the messages don't have, like the members of oneofs, in its
+# 'skip', and the prost enums of enum fields, like 'Schema.field: path::to::Enum', in its 'enums'. Enum fields without
+# a prost enum are skipped.
+def proto_interop_fields(proto_interop, s):
+    messages = set(proto_interop['messages'])
+    skip = set(proto_interop.get('skip') or list())
+    enums = proto_interop.get('enums') or dict()
+    fields = list()
+    for pn, p in items(s.get('properties') or dict()):
+        fqn = s['id'] + '.' + pn
+        if fqn in skip:
+            continue
+        for ref in (p, p.get('items') or dict(), p.get('additionalProperties') or dict()):
+            assert TREF not in ref or ref[TREF] in messages, \
+                "%s refers to %s, which must be one of the proto_interop messages, or skipped" % (fqn, ref[TREF])
+        enum = enums.get(fqn)
+        if 'enum' in (p.get('items') or dict()):
+            assert enum is None, "%s: only enum fields which aren't repeated can be converted" % fqn
+            continue
+        if 'enum' in p and enum is None:
+            continue
+        name = camel_to_under(pn)
+        fields.append((mangle_ident(pn), name in RESERVED_WORDS and 'r#' + name or name, enum))
+    return fields
+
+
+# Returns the mTLS variant of the given root url, following the naming scheme of googleapis.com.
+# Urls of other domains are returned unchanged.
+def mtls_root_url(root_url):
+    m = re.match(r"^(\w+://[^/]+?)((\.sandbox)?\.googleapis\.com)(/.*)?$", root_url)
+    if not m or '.mtls.' in m.group(1) + '.':
+        return root_url
+    return m.group(1) + '.mtls' + m.group(2) + (m.group(4) or '')
+
+
 def normalize_library_name(name):
     return name.lower()
 
@@ -952,6 +1543,23 @@ def library_to_crate_name(name, suffix=''):
     return 'google-' + name + suffix
 
 
+# The crates only the CLIs depend on, which library crates must not pull in, so users of the libraries never build them
+CLI_ONLY_CRATES = ('clap', 'strsim', 'google-clis-common')
+
+
+# return the name of the crate of a dependency line of a Cargo.toml, like `hyper` of `hyper = { version = "^ 0.14" }`
+def dependency_name(dependency):
+    return dependency.split('=')[0].strip()
+
+
+# return the names of the crates of the dependency lines of a library crate, failing if one is only needed by CLIs
+def library_dependencies(dependencies):
+    names = [dependency_name(dep) for dep in dependencies]
+    cli_only = [name for name in names if name in CLI_ONLY_CRATES]
+    assert not cli_only, "library crates must not depend on %s, which only the CLIs need" % ', '.join(cli_only)
+    return names
+
+
 # return version like 0.1.0+2014031421
 def crate_version(build_version, revision):
     return '%s+%s' % (build_version, isinstance(revision, str) and revision or '00000000')
@@ -999,6 +1607,51 @@ def api_index(DOC_ROOT, name, version, ti, cargo, revision, check_exists=True):
         return None
 
 
+@dataclass
+class RegisteredApi:
+    name: str
+    version: str
+    title: str
+    # the crate names of the library and its command line program, like 'google-storage1' and 'google-storage1-cli'
+    crate_name: str
+    cli_crate_name: str
+    crate_version: str
+    revision: str
+    root_url: str
+    base_url: str
+    documentation_link: Optional[str]
+
+
+# Returns the APIs which are generated, sorted by name, with the versions of each API in the order of `api_list`, the
+# 'api.list' of the API list mapping names to versions. APIs and versions in `blacklist`, like 'dataflow' or
+# 'websecurityscanner-v1beta', are left out, as are versions whose discovery document is missing below `api_base`.
+def registered_apis(api_base, api_list, blacklist, build_version) -> List[RegisteredApi]:
+    apis = list()
+    for name in sorted(api_list.keys()):
+        if name in blacklist:
+            continue
+        for version in api_list[name]:
+            json_path = api_json_path(api_base, name, version)
+            if name + '-' + version in blacklist or not os.path.isfile(json_path):
+                continue
+            with open(json_path) as fp:
+                doc = json.load(fp)
+            crate = library_to_crate_name(library_name(name, version))
+            revision = doc.get('revision')
+            apis.append(RegisteredApi(
+                name=name,
+                version=version,
+                title=doc.get('title', name),
+                crate_name=crate,
+                cli_crate_name=crate + '-cli',
+                crate_version=crate_version(build_version, revision),
+                revision=isinstance(revision, str) and revision or '00000000',
+                root_url=doc['rootUrl'],
+                base_url=doc.get('baseUrl') or doc['rootUrl'] + doc.get('servicePath', ''),
+                documentation_link=doc.get('documentationLink') or None))
+    return apis
+
+
 # return type name of a resource method builder, from a resource name
 def rb_type(r):
     return "%sMethods" % singular(canonical_type_name(r))
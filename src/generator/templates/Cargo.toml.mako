@@ -1,4 +1,5 @@
-<%! from generator.lib.util import (estr, enclose_in, hash_comment, library_to_crate_name, to_extern_crate_name) %>\
+<%! from generator.lib.util import (estr, enclose_in, hash_comment, library_to_crate_name, to_extern_crate_name,
+                                   library_dependencies) %>\
 <%namespace name="util" file="../lib/util.mako"/>\
 <%block filter="hash_comment">\
 <%util:gen_info source="${self.uri}" />\
@@ -26,6 +27,7 @@ path = "src/main.rs"
 % endif
 
 [dependencies]
+% if cargo.get('is_executable', False):
 anyhow = "^ 1.0"
 hyper-rustls = "0.25.0"
 ## Must match the one hyper uses, otherwise there are duplicate similarly named `Mime` structs
@@ -33,14 +35,26 @@ mime = "^ 0.3.0"
 serde = { version = "^ 1.0", features = ["derive"] }
 serde_json = "^ 1.0"
 itertools = "^ 0.10"
-% if cargo.get('is_executable', False):
 google-clis-common = { path = "../../google-clis-common", version = "6.0" }
 % else:
-google-apis-common = { path = "../../google-apis-common", version = "6.0.3" }
+## All but the dependencies of the schemas are only needed by the `client` feature
+anyhow = { version = "^ 1.0", optional = true }
+hyper-rustls = { version = "0.25.0", optional = true }
+## Must match the one hyper uses, otherwise there are duplicate similarly named `Mime` structs
+mime = { version = "^ 0.3.0", optional = true }
+serde = { version = "^ 1.0", features = ["derive"] }
+serde_json = "^ 1.0"
+itertools = { version = "^ 0.10", optional = true }
+google-apis-common = { path = "../../google-apis-common", version = "6.0.3", default-features = false }
+bytes = { version = "^1.0", optional = true }
 % endif
 % for dep in cargo.get('dependencies', list()):
 ${dep}
 % endfor
+% if api.get('proto_interop'):
+## The crate of the messages generated by prost, which must be optional
+${api.proto_interop.dependency}
+% endif
 
 <%
   api_name = util.library_name()
@@ -55,10 +69,68 @@ ${dep}
 [dependencies.${crate_name_we_depend_on}]
 path = "../${api_name}"
 version = "${util.crate_version()}"
+% if cargo.get('is_executable', False):
+## --offline serves fixtures using the replay connector
+features = ["replay"]
+% endif
 % endif
 
 % if not cargo.get("is_executable", False):
+## The quickstart example is compiled and run by trybuild in the crate's tests
+[dev-dependencies]
+tokio = { version = "^1.0", features = ["macros", "rt-multi-thread"] }
+trybuild = "^1.0"
+
+[[example]]
+name = "quickstart"
+required-features = ["yup-oauth2"]
+
+[[test]]
+name = "quickstart"
+required-features = ["yup-oauth2"]
+
+[[test]]
+name = "replay"
+required-features = ["replay"]
+
 [features]
-yup-oauth2 = ["google-apis-common/yup-oauth2"]
-default = ["yup-oauth2"]
+<%
+  client_deps = ['anyhow', 'hyper-rustls', 'mime', 'itertools'] + library_dependencies(cargo.get('dependencies', list()))
+%># The hub and the calls of the API. Without it, only the schemas are compiled, to be used with another HTTP stack,
+# which doesn't depend on hyper or yup-oauth2.
+client = ["google-apis-common/client", ${', '.join('"dep:%s"' % dep for dep in client_deps)}]
+yup-oauth2 = ["client", "google-apis-common/yup-oauth2"]
+default = ["client", "yup-oauth2"]
+# Enables creating the hub from the Application Default Credentials.
+adc = ["yup-oauth2"]
+# Makes each call within a `tracing` span following the OpenTelemetry conventions for HTTP clients.
+tracing = ["client", "google-apis-common/tracing"]
+# Enables replaying recorded exchanges instead of sending requests, as done by the `replay` test.
+replay = ["client", "google-apis-common/replay"]
+# Compresses the responses and large request bodies of calls with gzip.
+gzip = ["client", "google-apis-common/gzip"]
+# Binary fields are `bytes::Bytes`, whose clones share their contents, instead of `Vec<u8>`. It only changes the
+# fields of this crate, not those of other generated crates in the same build.
+bytes = ["dep:bytes", "google-apis-common/bytes"]
+# Keeps the fields of responses which the schemas don't have in their `extras`, and sends them again in requests.
+extras = []
+# Keeps the exact digits of numbers in `serde_json::Value` fields, like high-precision numeric values. It changes
+# `serde_json` for all crates of a build, so it is never enabled by default.
+arbitrary-precision = ["google-apis-common/arbitrary-precision"]
+# Decodes large responses, like long lists, with `simd-json`, which is faster on CPUs with SIMD instructions.
+simd-json = ["google-apis-common/simd-json"]
+# Waits and runs background tasks with async-std instead of tokio, for applications which don't run tokio.
+async-std = ["client", "google-apis-common/async-std"]
+# Adds the `blocking` module, whose hub makes calls synchronously on a runtime of its own.
+blocking = ["client", "google-apis-common/blocking"]
+# Validates responses against the schemas of the API, and reports how they differ to the delegate, to find changes of the API early.
+validate-responses = ["client"]
+% if api.get('proto_interop'):
+# Implements `From` between the schemas and the messages generated by prost from the protos of the API.
+proto-interop = ["google-apis-common/proto-interop", "dep:${library_dependencies([api.proto_interop.dependency])[0]}"]
+% endif
+% if api.get('required_response_fields'):
+# Fields of responses documented to be always present are not wrapped in `Option`.
+strict-required = []
+% endif
 % endif
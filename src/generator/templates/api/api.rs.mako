@@ -3,17 +3,34 @@
 <%namespace name="rbuild" file="lib/rbuild.mako"/>\
 <%namespace name="mbuild" file="lib/mbuild.mako"/>\
 <%namespace name="schema" file="lib/schema.mako"/>\
+<%namespace name="names" file="lib/names.mako"/>\
+<%namespace name="hub_trait" file="lib/hub_trait.mako"/>\
+<%namespace name="blocking" file="lib/blocking.mako"/>\
+<%namespace name="objects" file="lib/objects.mako"/>\
+<%namespace name="batcher" file="lib/batcher.mako"/>\
+<%namespace name="sync" file="lib/sync.mako"/>\
+<%namespace name="changes" file="lib/changes.mako"/>\
+<%namespace name="query" file="lib/query.mako"/>\
+<%namespace name="proto" file="lib/proto.mako"/>\
 <%
     from generator.lib.util import (new_context, rust_comment, rust_doc_comment, rust_module_doc_comment,
                       rb_type, hub_type, mangle_ident, hub_type_params_s,
                       rb_type_params_s, find_fattest_resource, HUB_TYPE_PARAMETERS, METHODS_RESOURCE,
-                      UNUSED_TYPE_MARKER, schema_markers)
+                      UNUSED_TYPE_MARKER, schema_markers, mtls_root_url, supports_scopes, api_sunset,
+                      escape_rust_string, method_response, validation_schemas, mark_resource_labels)
 
+    mark_resource_labels(schemas, api.get('resource_labels'))
     c = new_context(schemas, resources)
     hub_type = hub_type(c.schemas, util.canonical_name())
     ht_params = hub_type_params_s()
 
     default_user_agent = "google-api-rust-client/" + cargo.build_version
+    mtls_root = mtlsRootUrl if mtlsRootUrl is not UNDEFINED else mtls_root_url(rootUrl)
+    mtls_base = baseUrl.startswith(rootUrl) and mtls_root + baseUrl[len(rootUrl):] or baseUrl
+    service_path = baseUrl.startswith(rootUrl) and baseUrl[len(rootUrl):] or servicePath
+    emulator_host_env = api.get('emulator_host_env') and '"%s"' % api.emulator_host_env
+    sunset = api_sunset(api.get('sunset'))
+    response_ids = sorted(set(r.id for r in (method_response(c, m) for m in c.fqan_map.values()) if r))
 %>\
 use std::collections::HashMap;
 use std::cell::RefCell;
@@ -25,19 +42,68 @@ use std::io;
 use std::fs;
 use std::mem;
 
+#[cfg(feature = "client")]
 use hyper::client::connect;
+#[cfg(feature = "client")]
 use tokio::io::{AsyncRead, AsyncWrite};
-use tokio::time::sleep;
+#[cfg(feature = "client")]
+use crate::client::rt::sleep;
+#[cfg(feature = "client")]
 use tower_service;
 use serde::{Serialize, Deserialize};
 
-use crate::{client, client::GetToken, client::serde_with};
+use crate::{client, client::serde_with};
+#[cfg(feature = "client")]
+use crate::client::GetToken;
+
+/// The type of binary fields, which are transferred as base64 strings. It is `bytes::Bytes` if the `bytes` feature of
+/// this crate is enabled, whose clones share their contents, and `Vec<u8>` otherwise.
+#[cfg(not(feature = "bytes"))]
+pub type Binary = Vec<u8>;
+/// The type of binary fields, which are transferred as base64 strings. It is `bytes::Bytes` if the `bytes` feature of
+/// this crate is enabled, whose clones share their contents, and `Vec<u8>` otherwise.
+#[cfg(feature = "bytes")]
+pub type Binary = bytes::Bytes;
 
 // ##############
 // UTILITIES ###
 // ############
 
 ${lib.scope_enum()}
+% if sunset:
+
+/// The announced shutdown of this API version on ${sunset[0]}, which hubs warn about once when they are created
+/// with the `tracing` feature, see [`client::sunset`].
+pub static SUNSET: client::Sunset = client::Sunset::new("${name}", "${version}", "${sunset[0]}", "${sunset[1] | escape_rust_string}");
+% endif
+
+/// The methods of this API, for tooling which handles them generically, like dashboards keyed by method id.
+pub mod methods {
+    use crate::client::MethodDescriptor;
+
+    /// All methods of this API, sorted by their id.
+    pub static ALL: &[MethodDescriptor] = &[
+    % for m in sorted(c.fqan_map.values(), key=lambda m: m.id):
+        MethodDescriptor {
+            id: "${m.id}",
+            http_method: "${m.httpMethod}",
+            path: "${m.path}",
+            scopes: &[${', '.join('"%s"' % scope for scope in m.get('scopes', []))}],
+        },
+    % endfor
+    ];
+
+    /// Returns the method with the given id, like `${min(m.id for m in c.fqan_map.values())}`.
+    pub fn find(id: &str) -> ${"Option<&'static MethodDescriptor>"} {
+        MethodDescriptor::find(ALL, id)
+    }
+}
+% if response_ids:
+
+/// The discovery schemas of the responses, which they are validated against with the `validate-responses` feature.
+#[cfg(feature = "validate-responses")]
+static RESPONSE_SCHEMAS: client::validate::Schemas = client::validate::Schemas::new(r#"${validation_schemas(schemas or dict(), response_ids, api.get('required_response_fields'))}"#);
+% endif
 
 
 // ########
@@ -53,6 +119,7 @@ ${lib.scope_enum()}
 <%block filter="rust_doc_comment">\
 ${lib.hub_usage_example(c)}\
 </%block>
+#[cfg(feature = "client")]
 #[derive(Clone)]
 pub struct ${hub_type}${ht_params} {
     pub client: hyper::Client<S, hyper::body::Body>,
@@ -60,20 +127,122 @@ pub struct ${hub_type}${ht_params} {
     _user_agent: String,
     _base_url: String,
     _root_url: String,
+    _redirect_policy: client::RedirectPolicy,
+    _interceptors: client::InterceptorChain,
+    _default_scopes: Vec<String>,
+    _clock: client::ServerClock,
+    _timeouts: client::Timeouts,
+    _transport: client::Transport<S>,
+    _quota_project: Option<String>,
+    _gzip: bool,
+    _calls: client::CallTracker,
+    _single_flight: Option<client::SingleFlight>,
+    _granted_scopes: Option<client::GrantedScopes>,
+% if api.get('quota'):
+    _quota_budget: Option<client::QuotaBudget>,
+% endif
 }
 
+#[cfg(feature = "client")]
 impl<'a, ${', '.join(HUB_TYPE_PARAMETERS)}> client::Hub for ${hub_type}${ht_params} {}
 
+#[cfg(feature = "client")]
 impl<'a, ${', '.join(HUB_TYPE_PARAMETERS)}> ${hub_type}${ht_params} {
 
     pub fn new<A: 'static + client::GetToken>(client: hyper::Client<S, hyper::body::Body>, auth: A) -> ${hub_type}${ht_params} {
-        ${hub_type} {
+        let mut hub = ${hub_type} {
             client,
             auth: Box::new(auth),
             _user_agent: "${default_user_agent}".to_string(),
             _base_url: "${baseUrl}".to_string(),
             _root_url: "${rootUrl}".to_string(),
+            _redirect_policy: Default::default(),
+            _interceptors: Default::default(),
+            _default_scopes: Vec::new(),
+            _clock: Default::default(),
+            _timeouts: Default::default(),
+            _transport: Default::default(),
+            _quota_project: None,
+            _gzip: true,
+            _calls: Default::default(),
+            _single_flight: None,
+            _granted_scopes: None,
+% if api.get('quota'):
+            _quota_budget: None,
+% endif
+        };
+        hub.use_mtls_endpoint(client::connector::MtlsEndpoint::from_env() == client::connector::MtlsEndpoint::Always);
+        if let Some(root_url) = client::emulator::emulator_from_env("${name}", ${emulator_host_env and 'Some(%s)' % emulator_host_env or 'None'}) {
+            hub.use_emulator(root_url);
         }
+% if sunset:
+        SUNSET.warn_once(client::chrono::Utc::now());
+% endif
+        hub
+    }
+
+    /// Create a hub which acts as the given service account, identified by its email address.
+    /// The tokens obtained from `auth` are exchanged for tokens of the service account as needed,
+    /// see [`client::ServiceAccountImpersonation`] for details.
+    pub fn new_with_impersonation<A: 'static + client::GetToken>(client: hyper::Client<S, hyper::body::Body>, auth: A, service_account: impl Into<String>) -> ${hub_type}${ht_params}
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let clock = client::ServerClock::default();
+        let auth = client::ServiceAccountImpersonation::new(client.clone(), auth, service_account).clock(clock.clone());
+        let mut hub = ${hub_type}::new(client, auth);
+        hub._clock = clock;
+        hub
+    }
+
+    /// Create a hub which acts as the user with the email address `subject`, using the domain-wide delegation
+    /// granted to the service account of `key` by an administrator of the user's domain.
+    /// See [`client::flows::domain_wide_delegation()`] for details.
+    #[cfg(feature = "yup-oauth2")]
+    pub async fn with_domain_wide_delegation(client: hyper::Client<S, hyper::body::Body>, key: client::oauth2::ServiceAccountKey, subject: &str) -> client::Result<${hub_type}${ht_params}>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let auth = client::flows::domain_wide_delegation(client.clone(), key, subject).await.map_err(client::Error::Io)?;
+        Ok(${hub_type}::new(client, auth))
+    }
+
+    /// Create a hub authorizing the user with the device flow of the client `secret`, for programs without a
+    /// browser. Tokens are kept in the file at `token_path` if given. See [`client::flows::device_flow()`] for
+    /// details.
+    #[cfg(feature = "yup-oauth2")]
+    pub async fn with_device_flow(client: hyper::Client<S, hyper::body::Body>, secret: client::oauth2::ApplicationSecret, token_path: Option<std::path::PathBuf>) -> client::Result<${hub_type}${ht_params}>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let auth = client::flows::device_flow(client.clone(), secret, token_path).await.map_err(client::Error::Io)?;
+        Ok(${hub_type}::new(client, auth))
+    }
+
+    /// Create a hub authorizing its calls with the given API key, which is sent as `key` parameter
+    /// instead of an `Authorization` header. See [`client::ApiKey`] for details.
+    pub fn with_api_key(client: hyper::Client<S, hyper::body::Body>, key: impl Into<String>) -> ${hub_type}${ht_params} {
+        ${hub_type}::new(client, client::ApiKey::new(key))
+    }
+
+    /// Create a hub using the connection pool, authenticator and server clock of the given
+    /// [`client::GoogleClient`], which may be shared by the hubs of many APIs.
+    pub fn from_shared(shared: &client::GoogleClient<S>) -> ${hub_type}${ht_params}
+    where
+        S: Clone,
+    {
+        let mut hub = ${hub_type}::new(shared.client().clone(), shared.auth());
+        hub._clock = shared.clock().clone();
+        hub
     }
 
     % for resource in sorted(c.rta_map.keys()):
@@ -105,8 +274,236 @@ impl<'a, ${', '.join(HUB_TYPE_PARAMETERS)}> ${hub_type}${ht_params} {
     pub fn root_url(&mut self, new_root_url: String) -> String {
         mem::replace(&mut self._root_url, new_root_url)
     }
+
+    /// Set whether to send all requests to the mTLS endpoint `${mtls_root}`, which requires the
+    /// connector to present a client certificate. This sets the root and base url of the chosen endpoint.
+    /// It defaults to `true` only if the `GOOGLE_API_USE_MTLS_ENDPOINT` environment variable is `always`.
+    ///
+    /// When using a client certificate, call
+    /// `hub.use_mtls_endpoint(client::connector::MtlsEndpoint::from_env().use_mtls(true))` to also honor its default, `auto`.
+    pub fn use_mtls_endpoint(&mut self, use_mtls: bool) {
+        let (root_url, base_url) = if use_mtls {
+            ("${mtls_root}", "${mtls_base}")
+        } else {
+            ("${rootUrl}", "${baseUrl}")
+        };
+        self._root_url = root_url.to_string();
+        self._base_url = base_url.to_string();
+    }
+
+    /// Send all requests to the emulator with the given root url, like `http://localhost:8085/`, without
+    /// authorization. Hubs do so when created if an emulator is named in the environment, see `client::emulator`.
+    pub fn use_emulator(&mut self, root_url: String) {
+        self._base_url = root_url.clone() + "${service_path}";
+        self._root_url = root_url;
+        self.auth = Box::new(client::NoToken);
+    }
+
+    /// Set the policy determining which redirects are followed in all requests to the server.
+    /// By default, redirects aren't followed, and are returned like other responses.
+    ///
+    /// Returns the previously set redirect policy.
+    pub fn redirect_policy(&mut self, new_redirect_policy: client::RedirectPolicy) -> client::RedirectPolicy {
+        mem::replace(&mut self._redirect_policy, new_redirect_policy)
+    }
+
+    /// Set the clock which is updated with the time of the server on each response, to be shared with
+    /// authenticators and signers computing deadlines, as they may be off due to a drifting local clock.
+    /// Hubs created with [`Self::new_with_impersonation()`] share it with their authenticator.
+    ///
+    /// Returns the previously set clock.
+    pub fn server_clock(&mut self, new_clock: client::ServerClock) -> client::ServerClock {
+        mem::replace(&mut self._clock, new_clock)
+    }
+
+    /// Set the attempt timeout and deadline of all calls which don't set their own, see [`client::Timeouts`].
+    /// Both are unset by default.
+    ///
+    /// Returns the previously set timeouts.
+    pub fn timeouts(&mut self, new_timeouts: client::Timeouts) -> client::Timeouts {
+        mem::replace(&mut self._timeouts, new_timeouts)
+    }
+
+    /// Set the transport spreading the requests of all calls over several connections, and capping the
+    /// concurrent streams on each of them, see [`client::Transport`]. By default, all requests are sent
+    /// through the client of this hub.
+    ///
+    /// Returns the previously set transport.
+    pub fn transport(&mut self, new_transport: client::Transport<S>) -> client::Transport<S> {
+        mem::replace(&mut self._transport, new_transport)
+    }
+
+    /// Set the project to which the quota and billing of all calls are attributed, sent as the
+    /// `x-goog-user-project` header. Many APIs require it when using user credentials, as they don't
+    /// belong to a project. It is unset by default, and calls may set their own.
+    ///
+    /// Returns the previously set quota project.
+    pub fn quota_project(&mut self, project: String) -> Option<String> {
+        mem::replace(&mut self._quota_project, Some(project))
+    }
+
+    /// Set whether calls ask for responses compressed with gzip and compress large request bodies, which
+    /// only applies with the `gzip` feature, see `client::gzip`. It is enabled by default, and calls
+    /// may disable it for themselves.
+    ///
+    /// Returns the previous setting.
+    pub fn gzip(&mut self, enabled: bool) -> bool {
+        mem::replace(&mut self._gzip, enabled)
+    }
+
+    /// Set the scopes to request for all calls which don't have a scope added explicitly.
+    /// Each call uses the first of these scopes which authorizes its method, and its own default scope
+    /// if there is none.
+    ///
+    /// Returns self, to allow chaining further configuration.
+    pub fn with_scopes<I, St>(&mut self, scopes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = St>,
+        St: AsRef<str>,
+    {
+        self._default_scopes = scopes.into_iter().map(|s| s.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Returns the scopes set using [`Self::with_scopes()`], which are empty by default.
+    pub fn default_scopes(&self) -> &[String] {
+        &self._default_scopes
+    }
+
+    /// Set the requests in flight with which identical GET requests of this hub are coalesced, so only one of them
+    /// is sent and its response is shared, see [`client::SingleFlight`]. Share it between hubs to coalesce their
+    /// requests as well. It is unset by default.
+    ///
+    /// Returns the previously set requests in flight.
+    pub fn single_flight(&mut self, new_flights: Option<client::SingleFlight>) -> Option<client::SingleFlight> {
+        mem::replace(&mut self._single_flight, new_flights)
+    }
+
+    /// Set the scopes granted to the tokens of calls, which are verified to include one of the scopes authorizing
+    /// the method before each call, failing it with [`client::Error::InsufficientScopes`] otherwise. This asks
+    /// Google's `tokeninfo` endpoint about each new token, see [`client::GrantedScopes`]. It is unset by default,
+    /// in which case calls requesting none of the scopes listed for their method are only reported to
+    /// [`client::Delegate::unlisted_scopes()`].
+    ///
+    /// Returns the previously set granted scopes.
+    pub fn granted_scopes(&mut self, new_scopes: Option<client::GrantedScopes>) -> Option<client::GrantedScopes> {
+        mem::replace(&mut self._granted_scopes, new_scopes)
+    }
+
+% if api.get('quota'):
+    /// Set the budget which estimates the quota consumed by all calls made through this hub, using the
+    /// `QUOTA_COST` of each call builder. It is unset by default.
+    ///
+    /// Returns the previously set budget.
+    pub fn quota_budget(&mut self, new_budget: Option<client::QuotaBudget>) -> Option<client::QuotaBudget> {
+        mem::replace(&mut self._quota_budget, new_budget)
+    }
+
+% endif
+% if batchPath is not UNDEFINED:
+    /// Send all calls added to `batch` to the server in a single request, which is subject to the
+    /// redirect policy and interceptors of this hub. At most [`client::BatchRequest::MAX_CALLS`] calls may be batched.
+    ///
+    /// Fails only if the batch as a whole failed, the results of the individual calls are taken from the
+    /// returned response using the entries obtained when adding them.
+    pub async fn execute_batch(&self, batch: client::BatchRequest) -> client::Result<client::BatchResponse>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let _call = self._calls.start()?;
+        let url = self._root_url.clone() + "${batchPath}";
+        let request = batch.into_request(&url, &self._user_agent);
+        let stream = self._transport.stream(&self.client).await;
+        let res = self._redirect_policy.send(stream.client(), request, &self._interceptors).await?;
+        self._clock.observe(res.headers());
+        client::BatchResponse::from_response(res).await
+    }
+
+% endif
+    /// Returns the principal, the granted scopes and the expiry of the token calls are authorized with, as described by
+    /// Google's `tokeninfo` endpoint, to debug calls which are denied. The token is obtained for the scopes set using
+% if supports_scopes(auth):
+    /// [`Self::with_scopes()`], or the default [`Scope`]. Returns `None` if calls aren't authorized with tokens, like
+    /// when using an API key.
+% else:
+    /// [`Self::with_scopes()`]. Returns `None` if calls aren't authorized with tokens, like when using an API key.
+% endif
+    pub async fn auth_info(&self) -> client::Result<Option<client::AuthInfo>>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+% if supports_scopes(auth):
+        let default_scope = Scope::default();
+        let mut scopes: ${"Vec<&str>"} = self._default_scopes.iter().map(String::as_str).collect();
+        if scopes.is_empty() {
+            scopes.push(default_scope.as_ref());
+        }
+% else:
+        let scopes: ${"Vec<&str>"} = self._default_scopes.iter().map(String::as_str).collect();
+% endif
+        client::auth_info::auth_info(&self.client, &*self.auth, &scopes, &self._user_agent).await
+    }
+
+    /// Shut this hub and its clones down gracefully, e.g. before a rolling restart: calls started from now
+    /// on fail, while those in flight, including resumable uploads, are waited for up to `timeout`.
+    /// Afterwards, the interceptors are flushed, so they can export buffered telemetry.
+    ///
+    /// Returns true if all calls in flight completed in time.
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> bool {
+        let drained = self._calls.shutdown(timeout).await;
+        self._interceptors.flush();
+        drained
+    }
+
+    /// Add a signer which is run on all requests to the server after they were authorized,
+    /// e.g. to add the headers required by an internal gateway.
+    /// Signers run in the order they were added, and again for each redirect that is followed.
+    ///
+    /// Returns self, to allow adding more signers.
+    pub fn add_request_signer(&mut self, signer: impl client::RequestSigner + 'static) -> &mut Self {
+        self._interceptors.add_signer(signer);
+        self
+    }
+
+    /// Add an interceptor which is notified about all requests sent to the server and their responses,
+    /// e.g. to log them using [`client::interceptor::redacted_headers()`] or to collect metrics.
+    /// A [`client::HarRecorder`] records them as HTTP Archive, to be inspected with browser tooling.
+    ///
+    /// Returns self, to allow adding more interceptors.
+    pub fn with_interceptor(&mut self, interceptor: impl client::HttpInterceptor + 'static) -> &mut Self {
+        self._interceptors.add_interceptor(interceptor);
+        self
+    }
+}
+
+#[cfg(feature = "adc")]
+impl ${hub_type}<hyper_rustls::HttpsConnector<connect::HttpConnector>> {
+    /// Create a hub using the Application Default Credentials, sending all requests through a rustls connector
+    /// which trusts the native root certificates.
+    ///
+    /// The credentials are discovered like in the Google Cloud SDKs, see [`client::adc`] for details.
+    pub async fn with_adc() -> client::Result<Self> {
+        let connector = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .map_err(client::Error::Io)?
+            .https_or_http()
+            .enable_http1()
+            .build();
+        let client = hyper::Client::builder().build(connector);
+        let auth = client::adc::application_default_credentials(client.clone()).await.map_err(client::Error::Io)?;
+        Ok(${hub_type}::new(client, auth))
+    }
 }
 
+${hub_trait.new(c, hub_type)}
+
+${blocking.new(c, hub_type)}
 
 % if c.schemas:
 // ############
@@ -117,6 +514,19 @@ impl<'a, ${', '.join(HUB_TYPE_PARAMETERS)}> ${hub_type}${ht_params} {
 ${schema.new(s, c)}
 % endif
 % endfor
+% if api.get('proto_interop'):
+${proto.new(c)}
+% endif
+% endif
+
+% if c.resource_names:
+// ###################
+// ResourceNames  ###
+// #################
+
+% for rn in sorted(c.resource_names.values(), key=lambda rn: rn.type_name):
+${names.new(rn)}
+% endfor
 % endif
 
 // ###################
@@ -140,3 +550,48 @@ ${mbuild.new(resource, method, c)}
 
 % endfor ## method in methods
 % endfor ## resource, methods
+% if api.get('object_storage'):
+
+
+// ###################
+// Objects        ###
+// #################
+
+${objects.new(c, hub_type)}
+% endif
+% if api.get('task_batcher'):
+
+
+// ###################
+// Batcher        ###
+// #################
+
+${batcher.new(c, hub_type)}
+% endif
+% if api.get('history_sync'):
+
+
+// ###################
+// Sync           ###
+// #################
+
+${sync.new(c, hub_type)}
+% endif
+% if api.get('changes_tracker'):
+
+
+// ###################
+// Changes        ###
+// #################
+
+${changes.new(c, hub_type)}
+% endif
+% if api.get('query_rows'):
+
+
+// ###################
+// Query          ###
+// #################
+
+${query.new(c, hub_type)}
+% endif
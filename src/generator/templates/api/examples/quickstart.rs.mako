@@ -0,0 +1,48 @@
+<%namespace name="util" file="../../../lib/util.mako"/>\
+<%
+    from generator.lib.util import new_context, rust_comment, hub_type, mangle_ident, example_call
+
+    c = new_context(schemas, resources)
+    hub_type_name = hub_type(c.schemas, util.canonical_name())
+    call = example_call(c, api.get('server_streaming'))
+%>\
+<%block filter="rust_comment">\
+<%util:gen_info source="${self.uri}" />\
+</%block>
+//! Authorizes a ${hub_type_name} hub with the client secret of an installed application
+% if call:
+//! and makes a call with it.
+% else:
+//! ready to make calls.
+% endif
+//!
+//! Run it with `cargo run --example quickstart -- <client-secret.json>`. Without a client secret, it
+//! exits right away, which is how the crate's tests check that it keeps compiling.
+use ${util.library_name()}::{${hub_type_name}, oauth2, hyper, hyper_rustls, client::connector};
+% for type_name in (call.resource_name_types if call else []):
+use ${util.library_name()}::api::${type_name};
+% endfor
+
+#[tokio::main]
+async fn main() {
+    let Some(secret_path) = std::env::args().nth(1) else {
+        eprintln!("usage: quickstart <client-secret.json>");
+        return;
+    };
+    let secret = oauth2::read_application_secret(secret_path).await.expect("a readable client secret");
+    let auth = oauth2::InstalledFlowAuthenticator::builder(
+            secret,
+            oauth2::InstalledFlowReturnMethod::HTTPRedirect,
+        ).build().await.unwrap();
+    let connector = connector::http_connector(connector::AddressFamily::Any);
+    let hub = ${hub_type_name}::new(hyper::Client::builder().build(hyper_rustls::HttpsConnectorBuilder::new().with_native_roots().unwrap().https_or_http().enable_http1().wrap_connector(connector)), auth);
+% if call:
+
+    match hub.${mangle_ident(call.resource)}().${mangle_ident(call.activity)}(${', '.join(call.args)}).${api.terms.action}().await {
+        Ok((_, response)) => println!("{:?}", response),
+        Err(e) => eprintln!("{}", e),
+    }
+% else:
+    let _ = hub;
+% endif
+}
@@ -41,15 +41,29 @@ ${lib.docs(c)}
 </%block>
 
 // Re-export the hyper and hyper_rustls crate, they are required to build the hub
+#[cfg(feature = "client")]
 pub use hyper;
+#[cfg(feature = "client")]
 pub use hyper_rustls;
 pub extern crate google_apis_common as client;
 pub use client::chrono;
 pub mod api;
 
 // Re-export the hub type and some basic client structs
+#[cfg(feature = "client")]
 pub use api::${hub_type};
-pub use client::{Result, Error, Delegate, FieldMask};
+% if api.get('object_storage'):
+#[cfg(feature = "client")]
+pub use api::objects;
+% endif
+% if api.get('query_rows'):
+#[cfg(feature = "client")]
+pub use api::query;
+% endif
+#[cfg(feature = "client")]
+pub use client::{Result, Error, Delegate};
+pub use client::FieldMask;
+pub use api::methods;
 
 // Re-export the yup_oauth2 crate, that is required to call some methods of the hub and the client
 #[cfg(feature = "yup-oauth2")]
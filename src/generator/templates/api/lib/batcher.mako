@@ -0,0 +1,119 @@
+<%!
+    from generator.lib.util import (mangle_ident, to_fqan, build_all_params, organize_params, rb_type, method_response,
+                      activity_input_type, REQUEST_VALUE_PROPERTY_NAME)
+%>\
+## Build the batcher creating many tasks of a Cloud Tasks queue concurrently, on top of the create call of its tasks
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c, hub_type)">\
+<%
+    config = api.task_batcher
+    resource = config.resource
+    create_m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, config.create)]
+    params, request_value = build_all_params(c, create_m)
+    parent_param = [p for p in organize_params(params, request_value)[0] if p.name != REQUEST_VALUE_PROPERTY_NAME][0]
+    assert parent_param.name == 'parent', "task_batcher requires a create method taking the parent queue"
+    assert 'TaskBatcher' not in c.schemas, "TaskBatcher clashes with a schema"
+    assert request_value.id == 'CreateTaskRequest' and method_response(c, create_m).id == 'Task', \
+        "task_batcher requires a create method taking a CreateTaskRequest"
+    assert 'Task' in (api.get('deduplicated_names') or dict()), "task_batcher requires deduplicated_names of Task"
+    create = '%s().%s' % (mangle_ident(resource), mangle_ident(config.create))
+    if parent_param.get('resource_name'):
+        parent_type = parent_param.resource_name.type_name
+        parent_arg = 'self.parent.clone()'
+        parent_value = 'parent.into()'
+    else:
+        parent_type = 'String'
+        parent_arg = '&self.parent'
+        parent_value = 'parent.to_string()'
+%>\
+/// Creates many tasks of a queue concurrently, each using [`${rb_type(resource)}::${mangle_ident(config.create)}()`].
+///
+/// At most [`Self::concurrency()`] tasks are created at once, and each call is retried as decided by a clone of the
+/// [`client::RetryPolicy`] set using [`Self::retry_policy()`]. Creating a task isn't idempotent unless it is named, so
+/// calls of unnamed tasks are only retried if the server rejected them with `429 Too Many Requests`, unless they are
+/// named using [`Self::deduplicated()`]. The tasks which couldn't be created are reported together as a
+/// [`client::BulkError`].
+#[cfg(feature = "client")]
+pub struct TaskBatcher<'a, S> {
+    hub: &'a ${hub_type}<S>,
+    parent: ${parent_type},
+    concurrency: usize,
+    retry_policy: Option<client::RetryPolicy>,
+    namespace: Option<String>,
+}
+
+#[cfg(feature = "client")]
+impl<'a, S> TaskBatcher<'a, S>
+where
+    S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+    S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    /// The amount of tasks created at once unless set otherwise.
+    pub const DEFAULT_CONCURRENCY: usize = 32;
+
+    /// Create tasks of the queue `parent`, like `projects/PROJECT_ID/locations/LOCATION_ID/queues/QUEUE_ID`.
+    pub fn new(hub: &'a ${hub_type}<S>, parent: ${activity_input_type(schemas, parent_param)}) -> TaskBatcher<'a, S> {
+        TaskBatcher {
+            hub,
+            parent: ${parent_value},
+            concurrency: Self::DEFAULT_CONCURRENCY,
+            retry_policy: Some(client::RetryPolicy::new()),
+            namespace: None,
+        }
+    }
+
+    /// Set the maximum amount of tasks created at once, which is at least one.
+    pub fn concurrency(mut self, concurrency: usize) -> TaskBatcher<'a, S> {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the policy by which the calls creating tasks are retried, or `None` to not retry them.
+    /// It defaults to [`client::RetryPolicy::new()`].
+    pub fn retry_policy(mut self, retry_policy: Option<client::RetryPolicy>) -> TaskBatcher<'a, S> {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Name the tasks which have no name by a hash of `namespace` and their fields, see [`Task::deduplicated()`],
+    /// so their calls are retried after any transient failure. Creating a task which exists already, like one created
+    /// by an earlier attempt or batch, then fails with `ALREADY_EXISTS` instead of creating a duplicate.
+    pub fn deduplicated(mut self, namespace: impl Into<String>) -> TaskBatcher<'a, S> {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Create `tasks`, and return the created tasks along with their position among `tasks`, and the failures of
+    /// the others.
+    pub async fn create(&self, tasks: impl IntoIterator<Item = Task>) -> (Vec<(usize, Task)>, client::BulkError) {
+        client::bulk::call_concurrently(tasks, self.concurrency, |mut task| {
+            if let (Some(namespace), None) = (self.namespace.as_ref(), task.name.as_ref()) {
+                task = task.deduplicated(&self.parent, namespace);
+            }
+            let request = match task.name.as_ref() {
+                Some(name) => format!("POST {}", name),
+                None => format!("POST {}/tasks", self.parent),
+            };
+            // the server rejects named tasks which exist already
+            let idempotent = task.name.is_some();
+            let mut retry_policy = self.retry_policy.clone();
+            let call = async move {
+                let request = CreateTaskRequest {
+                    task: Some(task),
+                    ..Default::default()
+                };
+                let mut call = self.hub.${create}(request, ${parent_arg}).idempotent(idempotent);
+                if let Some(retry_policy) = retry_policy.as_mut() {
+                    call = call.delegate(retry_policy);
+                }
+                let (_, task) = call.${api.terms.action}().await?;
+                Ok(task)
+            };
+            (request, call)
+        }).await
+    }
+}
+</%def>
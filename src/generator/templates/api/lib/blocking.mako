@@ -0,0 +1,122 @@
+<%!
+    from generator.lib.util import (mangle_ident, to_fqan, mb_type, rb_type, build_all_params, organize_params,
+                      activity_input_type, method_media_params, method_response, is_server_streaming_method,
+                      hub_type_params_s, rb_type_params_s)
+%>\
+## Build the blocking module, wrapping the hub and its methods builders to make calls synchronously
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c, hub_type)">\
+<%
+    resources = list()
+    skipped_calls = False
+    for resource in sorted(c.rta_map.keys()):
+        calls = list()
+        for activity in c.rta_map[resource]:
+            m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, activity)]
+            # calls uploading media have no doit(), and streams are consumed asynchronously
+            if method_media_params(m) or is_server_streaming_method(api.get('server_streaming'), m):
+                skipped_calls = True
+                continue
+            params, request_value = build_all_params(c, m)
+            required_props = organize_params(params, request_value)[0]
+            args = [(mangle_ident(p.name), activity_input_type(schemas, p)) for p in required_props]
+            response_schema = method_response(c, m)
+            rtype = 'hyper::Response<hyper::body::Body>'
+            if response_schema:
+                rtype = '(%s, %s)' % (rtype, response_schema.id)
+            calls.append((mangle_ident(activity), mb_type(resource, activity), args, rtype))
+        if calls:
+            resources.append((resource, rb_type(resource), calls))
+    ht_params = hub_type_params_s()
+    rb_params = rb_type_params_s(None, c)
+%>\
+/// Synchronous calls of the [`${hub_type}`](crate::api::${hub_type}) hub, for programs which don't run an async runtime.
+///
+/// Like `reqwest::blocking`, the [`blocking::${hub_type}`](crate::api::blocking::${hub_type}) wraps the hub along with a
+/// [`client::blocking::Runtime`] which its calls are made on. Its methods builders create
+/// [`client::blocking::Call`]s wrapping the call builders of the hub, whose `${api.terms.action}()` blocks until the call
+/// is done. Calls must not be made from within an async context.
+% if skipped_calls:
+/// Calls uploading media or streaming their response can only be made using the hub.
+% endif
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    use super::*;
+    use crate::client::blocking::{Call, Perform, Runtime};
+
+    /// The [`super::${hub_type}`] along with the runtime its calls are made on.
+    pub struct ${hub_type}${ht_params} {
+        hub: super::${hub_type}${ht_params},
+        runtime: Runtime,
+    }
+
+    impl<'a, S> ${hub_type}${ht_params} {
+        /// Make the calls of `hub` on a runtime of its own.
+        ///
+        /// # Panics
+        ///
+        /// If the runtime can't be created, see [`Runtime::new()`].
+        pub fn new(hub: super::${hub_type}${ht_params}) -> ${hub_type}${ht_params} {
+            let runtime = Runtime::new().expect("the runtime of blocking calls to be created");
+            ${hub_type}::with_runtime(hub, runtime)
+        }
+
+        /// Make the calls of `hub` on `runtime`, e.g. the one its authenticator was created on.
+        pub fn with_runtime(hub: super::${hub_type}${ht_params}, runtime: Runtime) -> ${hub_type}${ht_params} {
+            ${hub_type} { hub, runtime }
+        }
+
+        /// The hub, e.g. to make calls asynchronously.
+        pub fn hub(&self) -> &super::${hub_type}${ht_params} {
+            &self.hub
+        }
+
+        /// The runtime the calls are made on.
+        pub fn runtime(&self) -> &Runtime {
+            &self.runtime
+        }
+    % for resource, rb, calls in resources:
+
+        pub fn ${mangle_ident(resource)}(&'a self) -> ${rb}${rb_params} {
+            ${rb} { methods: self.hub.${mangle_ident(resource)}(), runtime: &self.runtime }
+        }
+    % endfor
+    }
+    % for resource, rb, calls in resources:
+
+    /// The calls of [`super::${rb}`], made on the runtime of the [`${hub_type}`].
+    pub struct ${rb}${rb_params} {
+        methods: super::${rb}${rb_params},
+        runtime: &'a Runtime,
+    }
+
+    impl${rb_params} ${rb}${rb_params} {
+    % for fn_name, call_type, args, rtype in calls:
+        /// Create the call built by [`super::${call_type}`].
+        pub fn ${fn_name}(&self${''.join(', %s: %s' % arg for arg in args)}) -> Call<'a, super::${call_type}<'a, S>> {
+            Call::new(self.methods.${fn_name}(${', '.join(name for name, _ in args)}), self.runtime)
+        }
+    % endfor
+    }
+    % endfor
+    % for resource, rb, calls in resources:
+    % for fn_name, call_type, args, rtype in calls:
+
+    impl<'a, S> Perform<'a> for super::${call_type}<'a, S>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        type Output = ${rtype};
+
+        fn perform(self) -> client::BoxFuture<'a, client::Result<${rtype}>> {
+            Box::pin(self.${api.terms.action}())
+        }
+    }
+    % endfor
+    % endfor
+}
+</%def>
@@ -0,0 +1,168 @@
+<%!
+    from generator.lib.util import (mangle_ident, to_fqan, method_response)
+%>\
+## Build the tracker streaming the changes of files, on top of the calls listing changes with page tokens
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c, hub_type)">\
+<%
+    config = api.changes_tracker
+    resource = config.resource
+    changes = mangle_ident(resource)
+    responses = dict()
+    for activity in ('start', 'list'):
+        fqan = to_fqan(c.rtc_map[resource], resource, config[activity])
+        assert fqan in c.fqan_map, "changes_tracker requires %s.%s" % (resource, config[activity])
+        responses[activity] = method_response(c, c.fqan_map[fqan]).id
+    assert responses == dict(start='StartPageToken', list='ChangeList'), \
+        "changes_tracker requires calls returning a StartPageToken and a ChangeList"
+    assert set(('changeType', 'removed', 'file', 'fileId', 'drive', 'driveId')) <= set(c.schemas['Change'].properties)
+    for name in ('ChangesTracker', 'ChangeEvent'):
+        assert name not in c.schemas, "%s clashes with a schema" % name
+    start = mangle_ident(config.start)
+    list_changes = mangle_ident(config.list)
+%>\
+/// A change of a file or a shared drive, as listed by [`ChangesTracker::changes()`].
+#[cfg(feature = "client")]
+#[derive(Clone, Debug)]
+pub enum ChangeEvent {
+    /// The file was created or modified, which includes being trashed or shared.
+    FileChanged { file_id: String, file: File },
+    /// The file was deleted, or can't be accessed anymore.
+    FileRemoved { file_id: String },
+    /// The shared drive was created or modified.
+    DriveChanged { drive_id: String, drive: Drive },
+    /// The shared drive was deleted, or can't be accessed anymore.
+    DriveRemoved { drive_id: String },
+    /// A change of a kind this crate doesn't know.
+    Other(Change),
+}
+
+#[cfg(feature = "client")]
+impl From<Change> for ChangeEvent {
+    fn from(change: Change) -> ChangeEvent {
+        let removed = change.removed.unwrap_or(false);
+        match change.change_type.as_deref() {
+            Some("file") | None => {
+                let file_id = change.file_id.unwrap_or_default();
+                match change.file {
+                    Some(file) if !removed => ChangeEvent::FileChanged { file_id, file },
+                    _ => ChangeEvent::FileRemoved { file_id },
+                }
+            }
+            Some("drive") => {
+                let drive_id = change.drive_id.unwrap_or_default();
+                match change.drive {
+                    Some(drive) if !removed => ChangeEvent::DriveChanged { drive_id, drive },
+                    _ => ChangeEvent::DriveRemoved { drive_id },
+                }
+            }
+            Some(_) => ChangeEvent::Other(change),
+        }
+    }
+}
+
+/// Tracks the changes of files incrementally, using [`${hub_type}::${changes}()`]`.${list_changes}()`.
+///
+/// The page token the changes are listed from is kept in a [`client::changes::PageTokenStore`], like a
+/// [`client::changes::FileTokenStore`], so each call of [`Self::changes()`] continues where the previous one stopped,
+/// also after a restart. The first call only stores the token of the current state, see [`client::changes`].
+#[cfg(feature = "client")]
+pub struct ChangesTracker<'a, S, P> {
+    hub: &'a ${hub_type}<S>,
+    store: P,
+    drive_id: Option<String>,
+    page_size: Option<i32>,
+    fields: Option<String>,
+}
+
+#[cfg(feature = "client")]
+impl<'a, S, P> ChangesTracker<'a, S, P>
+where
+    S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+    S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    P: client::changes::PageTokenStore,
+{
+    /// Track the changes of the files of the user, with the page token kept in `store`.
+    pub fn new(hub: &'a ${hub_type}<S>, store: P) -> ChangesTracker<'a, S, P> {
+        ChangesTracker {
+            hub,
+            store,
+            drive_id: None,
+            page_size: None,
+            fields: None,
+        }
+    }
+
+    /// Track the changes of the files of the shared drive `drive_id` instead. A page token of the files of the user
+    /// can't be used for those of a shared drive, so each needs its own store.
+    pub fn drive_id(mut self, drive_id: impl Into<String>) -> ChangesTracker<'a, S, P> {
+        self.drive_id = Some(drive_id.into());
+        self
+    }
+
+    /// Set the maximum amount of changes listed per page.
+    pub fn page_size(mut self, page_size: i32) -> ChangesTracker<'a, S, P> {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Set the fields of the changed files to return, like `id,name,parents,modifiedTime`, instead of only their id,
+    /// name and type.
+    pub fn file_fields(mut self, file_fields: &str) -> ChangesTracker<'a, S, P> {
+        self.fields = Some(format!(
+            "nextPageToken,newStartPageToken,changes(changeType,removed,time,fileId,driveId,drive,file({}))",
+            file_fields
+        ));
+        self
+    }
+
+    /// Returns the store of the page token.
+    pub fn store(&self) -> &P {
+        &self.store
+    }
+
+    /// Returns the stream of the changes made after the stored page token, which stores the token of the next page
+    /// once all changes of a page were taken from it, and ends after the last page.
+    pub fn changes(&mut self) -> client::changes::Changes<'_, ChangeEvent> {
+        let hub = self.hub;
+        let drive_id = self.drive_id.clone();
+        let start = move || {
+            let mut call = hub.${changes}().${start}();
+            if let Some(drive_id) = drive_id.as_ref() {
+                call = call.drive_id(drive_id).supports_all_drives(true);
+            }
+            async move {
+                let (_, token) = call.${api.terms.action}().await?;
+                token.start_page_token.ok_or_else(|| {
+                    client::Error::Io(io::Error::new(io::ErrorKind::InvalidData, "the response has no start page token"))
+                })
+            }
+        };
+        let (drive_id, page_size, fields) = (self.drive_id.clone(), self.page_size, self.fields.clone());
+        let list = move |page_token: String| {
+            let mut call = hub.${changes}().${list_changes}(&page_token).include_removed(true);
+            if let Some(drive_id) = drive_id.as_ref() {
+                call = call.drive_id(drive_id).supports_all_drives(true).include_items_from_all_drives(true);
+            }
+            if let Some(page_size) = page_size {
+                call = call.page_size(page_size);
+            }
+            if let Some(fields) = fields.as_ref() {
+                call = call.param("fields", fields.as_str());
+            }
+            async move {
+                let (_, list) = call.${api.terms.action}().await?;
+                Ok(client::changes::ChangesPage {
+                    changes: list.changes.unwrap_or_default().into_iter().map(ChangeEvent::from).collect(),
+                    next_page_token: list.next_page_token,
+                    new_start_page_token: list.new_start_page_token,
+                })
+            }
+        };
+        client::changes::Changes::new(&mut self.store, start, list)
+    }
+}
+</%def>
@@ -0,0 +1,66 @@
+<%!
+    from generator.lib.util import (mangle_ident, to_fqan, mb_type, build_all_params, organize_params,
+                      activity_input_type, method_media_params, method_response, hub_type_params_s,
+                      is_server_streaming_method)
+%>\
+## Build the object-safe trait with a method for each call of the hub, and its implementation by the hub
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c, hub_type)">\
+<%
+    calls = list()
+    skipped_uploads = False
+    for resource in sorted(c.rta_map.keys()):
+        for activity in c.rta_map[resource]:
+            m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, activity)]
+            # calls uploading media take a generic reader, which isn't object-safe
+            if method_media_params(m):
+                skipped_uploads = True
+                continue
+            params, request_value = build_all_params(c, m)
+            required_props = organize_params(params, request_value)[0]
+            args = list()
+            for p in required_props:
+                arg_type = activity_input_type(schemas, p)
+                if p.get('resource_name'):
+                    arg_type = p.resource_name.type_name
+                args.append((mangle_ident(p.name), arg_type))
+            response_schema = method_response(c, m)
+            rtype = 'hyper::Response<hyper::body::Body>'
+            if response_schema and is_server_streaming_method(api.get('server_streaming'), m):
+                rtype = 'client::JsonItems<%s>' % response_schema.id
+            elif response_schema:
+                rtype = '(%s, %s)' % (rtype, response_schema.id)
+            calls.append((resource, activity, mangle_ident(resource + '_' + activity), args, rtype))
+%>\
+/// The calls of the [`${hub_type}`] hub as an object-safe trait, so code using the API can be tested with a
+/// mock implementation, e.g. one generated by `mockall`, and take a `&dyn ${hub_type}Api` instead of the hub.
+///
+/// Each method makes a call with the given required parameters, like `${api.terms.action}()` on the respective
+/// call builder. Optional parameters, delegates and scopes can only be set using the call builders of the hub.
+% if skipped_uploads:
+/// Calls uploading media are not part of this trait.
+% endif
+#[cfg(feature = "client")]
+pub trait ${hub_type}Api: Send + Sync {
+% for resource, activity, fn_name, args, rtype in calls:
+    /// Perform the call built by [`${mb_type(resource, activity)}`].
+    fn ${fn_name}(&self${''.join(', %s: %s' % arg for arg in args)}) -> client::BoxFuture<'_, client::Result<${rtype}>>;
+% endfor
+}
+
+#[cfg(feature = "client")]
+impl${hub_type_params_s()} ${hub_type}Api for ${hub_type}${hub_type_params_s()}
+where
+    S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+    S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+% for resource, activity, fn_name, args, rtype in calls:
+    fn ${fn_name}(&self${''.join(', %s: %s' % arg for arg in args)}) -> client::BoxFuture<'_, client::Result<${rtype}>> {
+        Box::pin(self.${mangle_ident(resource)}().${mangle_ident(activity)}(${', '.join(name for name, _ in args)}).${api.terms.action}())
+    }
+% endfor
+}
+</%def>
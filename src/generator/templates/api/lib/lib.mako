@@ -7,7 +7,8 @@
                       REQUEST_MARKER_TRAIT, RESPONSE_MARKER_TRAIT, supports_scopes, to_api_version,
                       to_fqan, METHODS_RESOURCE, ADD_PARAM_MEDIA_EXAMPLE, PROTOCOL_TYPE_INFO, enclose_in,
                       upload_action_fn, METHODS_BUILDER_MARKER_TRAIT, DELEGATE_TYPE,
-                      to_extern_crate_name, rust_doc_sanitize)  
+                      to_extern_crate_name, rust_doc_sanitize, iam_policy_cycles, operation_listings, request_id_param,
+                      api_sunset)  
 
     def pretty_name(name):
         return ' '.join(split_camelcase_s(name).split('.'))
@@ -56,6 +57,8 @@
                 array.append(m)
     # end for each method
     header_methods = (('Upload', upload_methods), ('Download', download_methods), ('Subscription', subscription_methods))
+    request_id_methods = [m for m in c.fqan_map.values() if request_id_param(m)]
+    sunset = api_sunset(api.get('sunset'))
 %>\
 This documentation was generated from *${util.canonical_name()}* crate version *${util.crate_version()}*, where *${revision is UNDEFINED and '00000000' or revision}* is the exact revision of the *${id}* schema built by the [mako](http://www.makotemplates.org/) code generator *v${cargo.build_version}*.
 % if documentationLink:
@@ -66,6 +69,11 @@ Everything else about the *${util.canonical_name()}* *${api_version}* API can be
 % if rust_doc:
 The original source code is [on github](${util.github_source_root_url()}).
 % endif
+% if sunset:
+
+**This API version is shut down on ${sunset[0]}.** ${sunset[1]} With the `tracing` feature, hubs warn about the
+shutdown once when they are created, from half a year before it on, see ${link('sunset', 'client::sunset')}.
+% endif
 # Features
 
 % if len(c.rta_map) > 0 + (METHODS_RESOURCE in c.rta_map):
@@ -184,6 +192,11 @@ ${'##'} A complete example
 
 ${self.hub_usage_example(c, rust_doc, fr=fr)}\
 
+A minimal program authorizing the hub with the client secret of an installed application and making a single call
+is the `quickstart` example of this crate. Run it using `cargo run --example quickstart -- <client-secret.json>`.
+The crate's tests compile and run it using [trybuild](https://docs.rs/trybuild), which keeps it working with the
+versions of its dependencies.
+
 ${'##'} Handling Errors
 
 All errors produced by the system are provided either as ${link('Result', 'client::Result')} enumeration as return value of
@@ -193,6 +206,197 @@ ${link('Hub Delegate', delegate_url)}, or the ${link('Authenticator Delegate', u
 When delegates handle errors or intermediate values, they may have a chance to instruct the system to retry. This 
 makes the system potentially resilient to all kinds of errors.
 
+Each call tells its delegate whether it is idempotent, i.e. whether it may be retried without duplicating its side effects,
+which is derived from its HTTP method and name, and may be overridden using `.idempotent(...)` on its builder. The
+${link('RetryPolicy', 'client::RetryPolicy')} delegate retries transient failures with exponential backoff, but only
+of idempotent calls, so creating a resource isn't repeated after the server may have handled it. It waits at least as long
+as the server asked to, in the `Retry-After` header or the `RetryInfo` of the error, which is available as the
+${link('BackoffHint', 'client::BackoffHint')} of a failed call, along with the quotas it exhausted.
+% if request_id_methods:
+
+Methods taking a `requestId` parameter, like `${sorted(request_id_methods, key=lambda m: m.id)[0].id}`, are de-duplicated by
+the server. Using `.auto_request_id(true)` on their builders generates a request id which is sent with all attempts of
+the call, making it idempotent, and which is available as `sent_request_id` of the
+${link('ResponseMetadata', 'client::ResponseMetadata')} returned by `${api.terms.action}_with_metadata()`.
+% endif
+
+Errors reported by the server are parsed into an ${link('ApiError', 'client::ApiError')}. Calls request the v2 error
+format by setting the `$.xgafv` parameter to `2` unless it was set explicitly, and errors still sent in the v1 format
+are given the same shape, with a canonical `status` and an `ErrorInfo` detail holding their reason.
+
+To bound how long calls take, set an attempt timeout and a deadline for all calls using `hub.timeouts(...)`, or for
+individual calls using `.attempt_timeout(...)` and `.deadline(...)` on their builders. Attempts which timed out are retried
+if the delegate allows it, but no retry is made past the deadline. See ${link('Timeouts', 'client::Timeouts')} for details.
+
+Services calling this API while handling requests of their own can propagate the deadlines of these requests by running
+their handlers within ${link('with_deadline()', 'client::timeout::with_deadline')}, which bounds all calls made within.
+The time remaining for each attempt is sent to the server as `X-Server-Timeout` header.
+
+With the `gzip` feature enabled, calls ask for responses compressed with gzip and compress request bodies of at least
+a kilobyte, which considerably reduces the size of large JSON documents like long lists. Calls streaming their response
+or transferring media are sent as they are. Compression is turned off for all calls using `hub.gzip(false)`, or for a
+single call using `.gzip(false)` on its builder.
+
+Headers which an API expects without a parameter setting them, like `X-Goog-FieldMask`, are added to a call using
+`.header(name, value)` on its builder, which sends them with each attempt of the call. A name or value which isn't valid
+fails the call with `client::Error::InvalidHeader` instead.
+
+With the `arbitrary-precision` feature numbers within `serde_json::Value` fields, which hold values of any type, keep their
+exact digits instead of being rounded to `f64`. It enables the `arbitrary_precision` feature of `serde_json`, which applies
+to all crates of a build, so it is opt-in, and this crate works with or without it.
+% if api.get('arbitrary_precision'):
+It is recommended for this API, whose values hold numbers exceeding the precision of `f64`.
+% endif
+
+With the `simd-json` feature, large responses like long lists are decoded by `simd-json` instead of `serde_json`,
+which is faster on CPUs with SIMD instructions, see ${link('decode', 'client::decode')}. It has no effect along with
+the `arbitrary-precision` feature, which only `serde_json` supports.
+
+With the `validate-responses` feature, meant for testing and staging environments, the body of each response is validated
+against the schemas of the API this crate was generated from: the types of values, the values of enums, and fields which
+are unknown or, if required, missing. The ${link('divergences', 'client::validate::Divergence')} are passed to
+`${delegate_url}::response_divergences()`, to find changes of the API early. By default, they are logged as warnings
+with the `tracing` feature, and ignored otherwise.
+
+Calls wait between attempts and time them out with the timers of tokio. With the `async-std` feature, they use those of
+async-std instead, so applications running on async-std or smol don't need a tokio runtime. The `hyper::Client` of the hub
+then needs to be built with the ${link('Executor', 'client::rt::Executor')} and the ${link('TcpConnector', 'client::rt::TcpConnector')},
+see ${link('rt', 'client::rt')}.
+
+Programs which don't run an async runtime, like build scripts, can make calls synchronously with the `blocking` feature.
+Like `reqwest::blocking`, its `blocking::${hub_url}` wraps the hub along with a ${link('Runtime', 'client::blocking::Runtime')},
+and its methods builders create ${link('Calls', 'client::blocking::Call')}. Their call builders are configured using `with()`,
+and `${api.terms.action}()` blocks until the call is done.
+
+Binary fields, which are sent as base64 strings, are encoded directly into the JSON of requests, and decoded without
+an intermediate copy. With the `bytes` feature enabled, their type ${link('Binary', 'api::Binary')} is
+`bytes::Bytes` instead of `Vec<u8>`, so they can be cloned and sliced without copying large payloads. The feature only
+changes the fields of this crate, not those of other generated crates used along with it.
+
+Fields which the schemas don't have, like those added to the API after this crate was generated, are dropped when
+decoding a response. With the `extras` feature enabled, they are kept in the `extras` map of each schema instead, and
+encoded again along with the other fields, so reading a resource and sending it back as part of an update doesn't
+clear them.
+
+Crates which only exchange the schemas of this API, like services receiving its resources from another service,
+depend on it with `default-features = false`, which turns off the `client` feature. Only the schemas, resource names
+and scopes are compiled then, without the hub, the call builders and their dependencies like `hyper` and `yup-oauth2`.
+
+Services proxying calls to this API can use ${link('ErrorExt::to_http_status()', 'client::ErrorExt')} to determine
+the status to respond with when a call failed, including the `Retry-After` header for rate limited calls.
+
+Tooling which handles methods generically, like monitoring and quota dashboards keyed by method id, finds all methods
+of this API in `methods::ALL`, with their id, HTTP method, path template and scopes. Call builders return their id
+with `method_id()` and their HTTP method with `http_method()`.
+
+${'##'} Application Default Credentials
+
+With the `adc` feature enabled, `${hub_url}::with_adc().await?` creates a hub which is ready to use, using the
+credentials named by the `GOOGLE_APPLICATION_CREDENTIALS` environment variable, those of `gcloud auth application-default login`
+or the metadata server, in this order. See ${link('adc', 'client::adc')} for details.
+
+When using user credentials, many APIs require a project to attribute the quota and billing of calls to, which is set using
+`hub.quota_project(...)` for all calls, or `.quota_project(...)` on the builder of a single call.
+
+${'##'} API Keys
+
+Methods which accept an API key, like those reading public data, can be called without obtaining tokens by creating
+the hub with `${hub_url}::with_api_key(client, "key")`. The key is sent as `key` parameter of every call instead of an
+`Authorization` header, unless a call sets this parameter itself. See ${link('ApiKey', 'client::ApiKey')} for details.
+
+${'##'} Acting as a Service Account
+
+To make all calls as a service account, create the hub with `${hub_url}::new_with_impersonation(client, auth, "name@project.iam.gserviceaccount.com")`.
+The tokens provided by `auth` will then be exchanged for tokens of the service account, which requires the
+*Service Account Token Creator* role on it.
+
+To act as a user of a Google Workspace domain instead, create the hub with
+`${hub_url}::with_domain_wide_delegation(client, key, "user@example.com").await?`, given the key of a service account
+which an administrator of the domain granted domain-wide delegation of the scopes used.
+
+Programs without a browser, like CLIs run over SSH, can authorize the user on another device with
+`${hub_url}::with_device_flow(client, secret, Some(token_path)).await?`, which writes the code to enter to standard
+error. See ${link('flows', 'client::flows')} for details.
+
+If your requests pass through a gateway requiring additional authentication, use `hub.add_request_signer(...)` to
+add the required headers to each request after it was authorized. See ${link('RequestSigner', 'client::RequestSigner')} for details.
+
+${'##'} Sharing Connections and Tokens between APIs
+
+Hubs are cheap to clone, as their clones share the connection pool and authenticator. To use the same ones for the hubs
+of many APIs, wrap them into a ${link('GoogleClient', 'client::GoogleClient')} once and create each hub from it:
+
+```ignore
+let shared = client::GoogleClient::new(client, auth);
+let hub = ${hub_url}::from_shared(&shared);
+```
+
+All hubs created this way reuse the same connections and cached tokens, and share their ${link('ServerClock', 'client::ServerClock')}.
+
+${'##'} Workload Identity Federation
+
+Workloads running on AWS or in CI systems like GitHub Actions can call the API without any long-lived keys,
+using the external account credential file of their workload identity pool provider:
+
+```ignore
+let credentials = client::ExternalAccountCredentials::from_file("credentials.json")?;
+let auth = client::ExternalAccount::new(client.clone(), credentials);
+let hub = ${hub_url}::new(client, auth);
+```
+
+Subject tokens are read from files, urls or the AWS environment, see ${link('ExternalAccount', 'client::ExternalAccount')} for details.
+If the credentials name a service account, its tokens are obtained through impersonation as well.
+
+${'##'} Mutual TLS
+
+To authenticate with a client certificate, configure it on the TLS connector and send all calls to the mTLS endpoint
+of the API using `hub.use_mtls_endpoint(true)`. Like the Google Cloud SDKs, the hub uses the mTLS endpoint by default
+if the `GOOGLE_API_USE_MTLS_ENDPOINT` environment variable is `always`, see ${link('MtlsEndpoint', 'client::connector::MtlsEndpoint')}.
+
+```ignore
+let tls = rustls::ClientConfig::builder()
+    .with_native_roots()?
+    .with_client_auth_cert(certificate_chain, private_key)?;
+let https = hyper_rustls::HttpsConnectorBuilder::new()
+    .with_tls_config(tls)
+    .https_only()
+    .enable_http1()
+    .wrap_connector(connector::http_connector(connector::AddressFamily::Any));
+let mut hub = ${hub_url}::new(hyper::Client::builder().build(https), auth);
+hub.use_mtls_endpoint(client::connector::MtlsEndpoint::from_env().use_mtls(true));
+```
+
+${'##'} Emulators
+
+% if api.get('emulator_host_env'):
+If the `${api.emulator_host_env}` environment variable names the host of a local emulator, like `localhost:8085`, hubs
+send all calls to it when created, without authorization, so tests run without credentials.
+% else:
+To send all calls to a local emulator or fake of the API, name its host in the `GOOGLE_API_ENDPOINT_OVERRIDES` environment
+variable, like `${name}=localhost:9020`. Hubs then send all calls to it when created, without authorization.
+% endif
+The connector has to support plain HTTP, like `HttpsConnectorBuilder::new().with_native_roots()?.https_or_http()`,
+and `hub.use_emulator(root_url)` routes the calls of a hub explicitly. See ${link('emulator', 'client::emulator')} for details.
+
+${'##'} Proxies on Unix Domain Sockets
+
+To send all calls through a local proxy listening on a unix domain socket, like the sidecar brokering the egress of a pod,
+create the hub's client with a ${link('UnixConnector', 'client::connector::UnixConnector')}. The urls of calls are kept,
+so the `Host` header names the API for proxies forwarding plain HTTP, and a TLS connector wrapping it performs the handshake
+with the API through the proxy, using its host for SNI:
+
+```ignore
+let https = hyper_rustls::HttpsConnectorBuilder::new()
+    .with_native_roots()?
+    .https_only()
+    .enable_http1()
+    .wrap_connector(client::connector::UnixConnector::new("/var/run/egress.sock"));
+let hub = ${hub_url}::new(hyper::Client::builder().build(https), auth);
+```
+
+A hub's ${link('Transport', 'client::Transport')} can spread calls over several connections to the socket as well, using
+`client::Transport::new(connector, options)` with the same connector.
+
 ${'##'} Uploads and Downloads
 If a method supports downloads, the response body, which is part of the ${link('Result', 'client::Result')}, should be
 read by you to obtain the media.
@@ -204,6 +408,92 @@ Methods supporting uploads can do so using up to ${len(PROTOCOL_TYPE_INFO)} diff
 ${put_and(md_italic(PROTOCOL_TYPE_INFO.keys()))}. The distinctiveness of each is represented by customized 
 `${api.terms.action}(...)` methods, which are then named ${put_and(enclose_in('`', ("%s(...)" % upload_action_fn(api.terms.upload_action, v['suffix']) for v in PROTOCOL_TYPE_INFO.values())))} respectively.
 
+The url of each resumable upload is handed to the `store_upload_url()` method of the ${link('delegate', delegate_url)}.
+If it was persisted, an interrupted upload can be continued later, even by another process, by returning it from
+`upload_url()` when calling the same method with the same media again. The upload then continues after the bytes
+the server received previously, and `upload_progress()` is called after each chunk.
+
+The `upload_progress(callback)` method of a call builder reports the bytes of media sent while they are being sent,
+with either protocol, like for a progress bar, and `upload_rate_limit(bytes_per_second)` throttles the upload.
+
+Media which can't be seeked, like a download streamed from another server, is uploaded from any
+`tokio::io::AsyncRead` by `${upload_action_fn(api.terms.upload_action, PROTOCOL_TYPE_INFO['resumable']['suffix'])}_stream(stream, size, mime_type)`,
+which keeps only one chunk of it in memory. If its size is known and small, it is sent in a single request instead.
+% if api.get('object_storage'):
+
+The most common operations on objects are made by the ${link('objects', 'api::objects')} module, without assembling calls:
+
+```ignore
+let bucket = objects::Bucket::new(&hub, "my-bucket");
+bucket.upload_file("report.csv", "text/csv".parse().unwrap()).await?;
+bucket.object("report.csv").download_to_file("/tmp/report.csv").await?;
+bucket.compose(&["part-1", "part-2"], "whole", None).await?;
+let signer = client::UrlSigner::from_service_account_file("service-account.json")?;
+let url = bucket.object("whole").signed_url(&signer, "GET", Duration::from_secs(3600))?;
+```
+
+`read_stream()` returns the contents of an object as a stream of chunks instead, and signed urls, which grant anyone
+holding them access to an object until they expire, are signed locally by a ${link('UrlSigner', 'client::UrlSigner')}
+using the key of a service account. Without a key, an ${link('IamUrlSigner', 'client::IamUrlSigner')} signs them using
+the IAM Credentials API instead. Both sign a ${link('SignedUrlRequest', 'client::SignedUrlRequest')}, as returned by
+`signed_url_request()`, which may require headers like the `content_type()` of an upload to be sent with the url.
+% endif
+% if api.get('query_rows'):
+
+Queries are run and their rows decoded into your own types by the ${link('query', 'api::query')} module, which waits for
+them to complete and fetches all pages of their results:
+
+```ignore
+#[derive(Deserialize)]
+struct Visits {
+    country: String,
+    visits: i64,
+    last_visit: DateTime<Utc>,
+}
+
+let project = query::Project::new(&hub, "my-project");
+let rows: Vec<Visits> = project
+    .run_query("SELECT country, COUNT(*) AS visits, MAX(time) AS last_visit FROM web.visits GROUP BY country")
+    .rows()
+    .await?;
+```
+
+The cells of each row are converted according to the types of the fields of the results, like `INTEGER` cells into
+numbers and `TIMESTAMP` cells into RFC 3339 strings, by a ${link('RowDecoder', 'client::RowDecoder')}, which decodes
+the rows of tables read by other calls as well.
+% endif
+% if batchPath is not UNDEFINED:
+
+${'##'} Batching Calls
+
+Up to 100 calls can be sent in a single request by adding them to a ${link('BatchRequest', 'client::BatchRequest')}
+using `add_to_batch(&mut batch)` instead of calling `${api.terms.action}()`, and sending it with `hub.execute_batch(batch)`.
+The result of each call is then taken from the ${link('BatchResponse', 'client::BatchResponse')} using the entry
+returned when adding it, or the results of all calls at once using `take_all()`, which reports the calls that failed
+together as ${link('BulkError', 'client::BulkError')}, along with their position and a summary of their request.
+% endif
+
+<% iam_cycles = [(resource, cycle) for resource in sorted(c.rta_map.keys()) for cycle in iam_policy_cycles(c, resource)] %>\
+% if iam_cycles:
+${'##'} Modifying IAM Policies
+
+Methods like `hub.${mangle_ident(iam_cycles[0][0])}().${mangle_ident(iam_cycles[0][1].activity)}(..., |policy| ...)` modify the IAM
+policy of a resource in a read-modify-write cycle, which sets the modified policy along with the etag it was read with.
+If the policy was modified concurrently, the cycle is restarted with the current policy instead of overwriting the
+concurrent modification.
+
+% endif
+<% listings = [(resource, listing) for resource in sorted(c.rta_map.keys()) for listing in operation_listings(c, resource)] %>\
+% if listings:
+${'##'} Long-running Operations
+
+Methods like `hub.${mangle_ident(listings[0][0])}().${mangle_ident(listings[0][1].stream_activity)}(..., filter)` return a
+${link('stream', 'client::Operations')} of the long-running operations selected by an ${link('OperationFilter', 'client::OperationFilter')},
+which selects them by whether they are done, their target and the type of their metadata. Their pages are listed as the
+operations are taken from the stream. `${mangle_ident(listings[0][1].cancel_all_activity)}(..., filter)` cancels all selected
+operations which aren't done yet, e.g. to clean up operations which are stuck.
+
+% endif
 ${'##'} Customization and Callbacks
 
 You may alter the way an `${api.terms.action}()` method is called by providing a ${link('delegate', delegate_url)} to the 
@@ -213,6 +503,43 @@ retry on failure.
 
 The ${link('delegate trait', delegate_url)} is default-implemented, allowing you to customize it with minimal effort.
 
+The ${link('ResponseMetadata', 'client::ResponseMetadata')} of successful calls, holding the status, headers, request id
+and server timing of the response, is handed to the delegate, and returned alongside the result by
+`${api.terms.action}_with_metadata()` instead of the response.
+
+Calls whose responses are pages of items also have a `${api.terms.action}_paged()` method, returning a
+${link('Page', 'client::Page')} of the items along with the token and metadata of the response. Its `fetch_next()`
+fetches the next page with the same parameters, so page tokens don't need to be passed on by hand.
+If the API documents the maximum page size of a call, it is available as the `MAX_PAGE_SIZE` constant of its builder,
+and larger page sizes are reduced to it instead of failing, which is reported to the `page_size_clamped()` method of the delegate.
+
+With the `tracing` feature enabled, each call is made within a `tracing` span named like `GET v1/{+name}`, which records
+the API, method id, status code, retry count and latency of the call following the OpenTelemetry semantic conventions
+for HTTP clients. Using `tracing-opentelemetry`, calls thus appear in distributed traces, see ${link('CallSpan', 'client::CallSpan')}.
+
+Calls can be tested without a network by creating the hub with a ${link('Replay', 'client::Replay')} connector, available
+with the `replay` feature, which answers requests with the responses recorded by a `client::HarRecorder::with_bodies()`
+interceptor. The `replay` test of this crate replays the quickstart call from `tests/fixtures/replay.har` this way.
+
+For bulk workloads with many concurrent calls, `hub.transport(client::Transport::new(connector, options))` spreads
+the calls over several connections and caps the concurrent streams on each of them.
+See ${link('TransportOptions', 'client::TransportOptions')} for details.
+
+If many tasks read the same resources at once, `hub.single_flight(Some(client::SingleFlight::new()))` coalesces
+GET requests with the same url and headers, like those of the principal and the compression of the response, while one
+of them is in flight, so only that one is sent and its response is shared. See ${link('SingleFlight', 'client::SingleFlight')} for details.
+
+To stop a service cleanly, e.g. during a rolling restart, `hub.shutdown(timeout).await` makes new calls fail while
+waiting for those in flight, including uploads, and then flushes the interceptors of the hub.
+
+If calls are denied, `hub.auth_info().await` tells the principal they are made as, the scopes granted to its token and
+when it expires, see ${link('AuthInfo', 'client::AuthInfo')}. Calls requesting none of the scopes listed for their method
+are still made, and reported to `Delegate::unlisted_scopes()`. To fail them before they are sent if their token wasn't granted
+any of these scopes, set `hub.granted_scopes(Some(Default::default()))`, see ${link('GrantedScopes', 'client::GrantedScopes')}.
+
+Code using the API can take a `&dyn ${hub_url}Api` instead of the hub. The ${link(hub_url + 'Api', hub_url + 'Api')} trait
+has a method for each call except media uploads, so such code can be tested with a mock implementation of it.
+
 ${'##'} Optional Parts in Server-Requests
 
 All structures provided by this library are made to be ${link('encodable', request_trait_url)} and 
@@ -221,6 +548,87 @@ are valid.
 Most optionals are are considered ${link('Parts', part_trait_url)} which are identifiable by name, which will be sent to 
 the server to indicate either the set parts of the request or the desired parts in the response.
 
+Calls returning large lists also have a `${api.terms.action}_streaming()` method, whose ${link('JsonItems', 'client::JsonItems')}
+stream decodes the items of the response one at a time while it is received, instead of buffering all of them.
+% if api.get('server_streaming'):
+
+Methods whose responses are streamed by the server, like ${', '.join('`%s`' % id.split('.')[-1] for id in api.server_streaming)},
+return a ${link('JsonItems', 'client::JsonItems')} stream from `${api.terms.action}()`, which yields each message as soon
+as it was received, and ends when the server closes the call.
+% endif
+% if api.get('resource_labels'):
+
+The `labels` of ${', '.join('`%s`' % sid for sid in api.resource_labels)}, which follow the rules of resource labels, are
+${link('Labels', 'client::Labels')}, which are created from any map of strings, and validated before a request carrying them is sent, so invalid labels fail with
+`client::Error::InvalidLabels` without a round-trip to the server. The `labels` of other schemas are plain maps.
+% endif
+
+Instead of filling a `Default::default()` value field by field, ${link('request values', request_trait_url)} can be
+built fluently with their `builder()`, which has a setter for each field taking its plain value. Its `build()` fails
+with a ${link('MissingFieldsError', 'client::MissingFieldsError')} if fields documented as required weren't set.
+
+Field masks like the `updateMask` of `patch` calls can be derived from the changes made to a resource with
+${link('FieldMask::diff()', 'client::FieldMask')}, which call builders taking the resource also offer as
+`update_mask_from_diff(&old)?`, for example. Maps like the `labels` of resources are replaced as a whole, as known from
+their schema.
+
+Responses only hold the fields selected by the `fields` parameter, which is set from a ${link('FieldMask', 'client::FieldMask')}
+using `.fields_mask(FieldMask::new(&["items.id", "next_page_token"]))` on call builders. The fields which weren't selected are
+`None`, and as such fields are left out of request values, a partial resource can be modified and sent back, e.g. with the
+same mask as update mask, without clearing the fields which weren't selected.
+% for schema_name in sorted(api.get('deduplicated_names') or dict()):
+
+A `${schema_name}` may be named by a hash of its fields with `${schema_name.lower()}.deduplicated(parent, namespace)` before creating it,
+so creating it twice, e.g. when retrying, fails with `ALREADY_EXISTS` instead of creating a duplicate.
+See ${link('task_id()', 'client::tasks')} for how the names are derived.
+% endfor
+% if api.get('upgrade_plan'):
+
+To find the versions an instance can be upgraded to, `hub.${mangle_ident(api.upgrade_plan.resource)}().${mangle_ident(api.upgrade_plan.get[:-len('get')] + 'upgrade_plan')}(name, latest_patch_only)`
+combines its current version with the versions available in its location into an ${link('UpgradePlan', 'client::UpgradePlan')},
+which compares version numbers numerically and tells whether upgrading to a version only applies patches.
+% endif
+% if api.get('subscriber'):
+
+To receive the messages of a subscription continuously, `hub.${mangle_ident(api.subscriber.resource)}().${mangle_ident(api.subscriber.pull[:-len('pull')] + 'subscriber')}(subscription, settings)`
+returns a ${link('Subscriber', 'client::Subscriber')}, which pulls messages in the background and hands them out as a stream,
+or to a handler using `run()`. The ack deadlines of messages are extended while they are handled, and the
+${link('SubscriberSettings', 'client::SubscriberSettings')} limit the amount of messages and bytes handled at once.
+% endif
+% if api.get('history_sync'):
+
+To keep a copy of a mailbox in sync, a ${link('HistorySynchronizer', 'api::sync::HistorySynchronizer')} stores the history id
+it is synchronized with as checkpoint, and its `sync()` returns the messages added and deleted, and the labels changed since,
+or tells to synchronize the mailbox fully once the checkpoint expired. Its `watch()` publishes the changes of the mailbox
+to a Pub/Sub topic, whose messages are parsed by ${link('Notification', 'client::history::Notification')}.
+% endif
+% if api.get('changes_tracker'):
+
+To keep track of the changes of files, a ${link('ChangesTracker', 'api::ChangesTracker')} keeps the page token they are listed
+from in a ${link('PageTokenStore', 'client::changes::PageTokenStore')}, like a file, and its `changes()` streams the changes made
+since as ${link('ChangeEvent', 'api::ChangeEvent')}s, storing the next token once the changes of each page were taken.
+% endif
+% if api.get('task_batcher'):
+
+To create many tasks at once, `TaskBatcher::new(&hub, queue).create(tasks)` makes a ${link('TaskBatcher', 'api::TaskBatcher')}
+create them with a bounded amount of concurrent calls, which are retried by a ${link('RetryPolicy', 'client::RetryPolicy')}.
+It returns the created tasks, and a ${link('BulkError', 'client::BulkError')} holding the failure of each task which wasn't created.
+With `deduplicated(namespace)`, unnamed tasks are named by a hash of their fields first, so their calls are retried safely.
+% endif
+% if api.get('required_response_fields'):
+
+Fields of responses which are documented to be always present are not optional if the `strict-required` feature
+is enabled. Responses missing them fail to decode with a descriptive error, so this feature should not be combined
+with partial responses selecting other fields.
+% endif
+% if api.get('proto_interop'):
+
+To pass messages between this crate and a gRPC client of the same API, the `proto-interop` feature implements `From`
+in both directions between ${', '.join('`%s`' % m for m in api.proto_interop.messages)} and the
+messages generated by prost in `${api.proto_interop.module}`. Fields the messages don't have are left unset, see
+${link('proto', 'client::proto')} for how the others are converted.
+% endif
+
 ${'##'} Builder Arguments
 
 Using ${link('method builders', call_builder_url)}, you are able to prepare an action call by repeatedly calling it's methods.
@@ -228,6 +636,10 @@ These will always take a single argument, for which the following statements are
 
 * [PODs][wiki-pod] are handed by copy
 * strings are passed as `&str`
+% if c.resource_names:
+* resource names are passed as their typed name, like `${sorted(c.resource_names.values(), key=lambda rn: rn.type_name)[0].type_name}`,
+  which can be created from its ids using `new(...)` or parsed from a string using `parse()`
+% endif
 * ${link('request values', request_trait_url)} are moved
 
 Arguments will always be copied or cloned into the builder, to make them independent of their original life times.
@@ -243,7 +655,7 @@ Arguments will always be copied or cloned into the builder, to make them indepen
 ###############################################################################################
 <%def name="test_hub(hub_type, comments=True)">\
 use std::default::Default;
-use ${util.library_name()}::{${hub_type}, oauth2, hyper, hyper_rustls, chrono, FieldMask};
+use ${util.library_name()}::{${hub_type}, oauth2, hyper, hyper_rustls, chrono, FieldMask, client::connector};
 
 % if comments:
 // Get an ApplicationSecret instance by some means. It contains the `client_id` and 
@@ -261,7 +673,11 @@ let auth = oauth2::InstalledFlowAuthenticator::builder(
         secret,
         oauth2::InstalledFlowReturnMethod::HTTPRedirect,
     ).build().await.unwrap();
-let mut hub = ${hub_type}::new(hyper::Client::builder().build(hyper_rustls::HttpsConnectorBuilder::new().with_native_roots().unwrap().https_or_http().enable_http1().build()), auth);\
+% if comments:
+// The connector tries both IPv4 and IPv6 addresses of a host, choose an `AddressFamily` to prefer one of them.
+% endif
+let connector = connector::http_connector(connector::AddressFamily::Any);
+let mut hub = ${hub_type}::new(hyper::Client::builder().build(hyper_rustls::HttpsConnectorBuilder::new().with_native_roots().unwrap().https_or_http().enable_http1().wrap_connector(connector)), auth);\
 </%def>
 
 ## You will still have to set the filter for your comment type - either nothing, or rust_doc_comment !
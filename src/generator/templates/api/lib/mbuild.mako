@@ -12,7 +12,9 @@
                       re_find_replacements, ADD_PARAM_FN, ADD_PARAM_MEDIA_EXAMPLE, upload_action_fn, METHODS_RESOURCE,
                       method_name_to_variant, size_to_bytes, method_default_scope,
                       is_repeated_property, setter_fn_name, ADD_SCOPE_FN, ADD_SCOPES_FN, rust_doc_sanitize,
-                      CLEAR_SCOPES_FN, items, string_impl)
+                      CLEAR_SCOPES_FN, items, string_impl, method_quota_cost, is_idempotent_method, deprecation_note,
+                      is_labels_property, streamed_items_property, is_server_streaming_method, paged_items,
+                      page_size_limit, request_id_param)
 
     SIMPLE = "simple"
     RESUMABLE = "resumable"
@@ -53,6 +55,26 @@
     ThisType = mb_type(resource, method) + mb_tparams
 
     params, request_value = build_all_params(c, m)
+    quota_cost = method_quota_cost(api.get('quota'), m)
+    idempotent = is_idempotent_method(api.get('idempotency'), m)
+    request_id = request_id_param(m)
+    page_size = page_size_limit(api.get('page_size_limits'), m)
+    if page_size:
+        page_size_type = activity_rust_type(schemas, next(p for p in params if p.name == page_size[0]), allow_optionals=False)
+    server_streaming = is_server_streaming_method(api.get('server_streaming'), m)
+    streamed_items = (response_schema and not server_streaming and not m.get('supportsMediaDownload', False)
+                      and not method_media_params(m) and streamed_items_property(schemas, response_schema))
+    paged = paged_items(c, m, api.get('server_streaming'))
+    if paged:
+        required_fields = (api.get('required_response_fields') or dict()).get(response_schema.id, list())
+        # required fields are non-optional with the strict-required feature
+        page_items = 'page.' + mangle_ident(paged[0])
+        if paged[0] in required_fields:
+            page_items = 'Option::from(%s)' % page_items
+        page_items += '.unwrap_or_default()'
+        next_page_token = 'page.next_page_token'
+        if 'nextPageToken' in required_fields:
+            next_page_token += '.into()'
     alt_param = None
     for p in params:
         if p.name == 'alt':
@@ -117,6 +139,7 @@ the *${m.scopes[0]}* scope to make a valid call.
 <%block filter="rust_doc_comment">\
 ${self.usage(resource, method, m, params, request_value, parts)}\
 </%block>
+#[cfg(feature = "client")]
 pub struct ${ThisType}
     where ${struct_type_bounds_s()} {
 
@@ -132,14 +155,28 @@ pub struct ${ThisType}
 % endfor
 ## A generic map for additinal parameters. Sometimes you can set some that are documented online only
     ${api.properties.params}: HashMap<String, String>,
+    ${api.properties.timeouts}: client::Timeouts,
+    ${api.properties.quota_project}: Option<String>,
+    ${api.properties.gzip}: Option<bool>,
+    ${api.properties.idempotent}: Option<bool>,
+    ${api.properties.headers}: hyper::HeaderMap,
+    ${api.properties.header_error}: Option<http::Error>,
+    % if method_media_params(m):
+    ${api.properties.upload}: client::UploadOptions,
+    % endif
+    % if request_id:
+    ${api.properties.auto_request_id}: bool,
+    % endif
     % if method_default_scope(m):
 ## We need the scopes sorted, to not unnecessarily query new tokens
     ${api.properties.scopes}: BTreeSet<String>
     % endif
 }
 
+#[cfg(feature = "client")]
 impl${mb_tparams} ${CALL_BUILDER_MARKERT_TRAIT} for ${ThisType} {}
 
+#[cfg(feature = "client")]
 impl${mb_tparams} ${ThisType}
 where
     S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
@@ -147,15 +184,134 @@ where
     S::Future: Send + Unpin + 'static,
     S::Error: Into<Box<dyn StdError + Send + Sync>>,
 {
+% if quota_cost:
+    /// The quota charged by the server for each call, which is tracked by the hub's quota budget, if set.
+    pub const QUOTA_COST: client::QuotaCost = client::QuotaCost { bucket: "${quota_cost[0]}", cost: ${quota_cost[1]} };
+
+% endif
+    /// The id of this method, which monitoring and quota dashboards key off.
+    pub const METHOD_ID: &'static str = "${m.id}";
+
+    /// The template of the path of this method's requests, relative to the base url of the API.
+    pub const PATH: &'static str = "${m.path}";
+
+    /// Whether calls of this method may be retried without duplicating their side effects, judging by its HTTP method
+    /// and name. Calls override it using `idempotent()`.
+    pub const IDEMPOTENT: bool = ${idempotent and 'true' or 'false'};
+
+    /// The id of this method, `${m.id}`.
+    pub fn method_id(&self) -> &'static str {
+        Self::METHOD_ID
+    }
+
+    /// The HTTP method of this method's requests.
+    pub fn http_method(&self) -> hyper::Method {
+        ${method_name_to_variant(m.httpMethod)}
+    }
+
+% if page_size:
+    /// The maximum page size documented by the API. Larger values of the *${split_camelcase_s(page_size[0])}* property are
+    /// reduced to it when the call is made, which is reported to the delegate.
+    pub const MAX_PAGE_SIZE: ${page_size_type} = ${page_size[1]};
+
+% endif
 % if api.get('no_upload_prefix') is not None and ThisType.startswith(api.no_upload_prefix):
 ${self._action_fn(c, resource, method, m, params, request_value, parts, doit_without_upload = True)}\
 % endif
 
 ${self._action_fn(c, resource, method, m, params, request_value, parts)}\
+% if response_schema and not server_streaming and not m.get('supportsMediaDownload', False) and not method_media_params(m):
+
+    /// Perform the operation you have built so far, like `${api.terms.action}()`, but return the metadata of the
+    /// response, like the id the server assigned to the request, instead of the response itself.
+    pub async fn ${api.terms.action}_with_metadata(self) -> client::Result<(client::ResponseMetadata, ${response_schema.id})> {
+        let (res, result) = self.${api.terms.action}().await?;
+        Ok((client::ResponseMetadata::new(&res), result))
+    }
+% endif
+% if paged:
+
+    /// Perform the operation you have built so far, returning the `${paged[0]}` of the response as a page, whose
+    /// [`client::Page::fetch_next()`] fetches the next page with the same parameters and delegate.
+    pub async fn ${api.terms.action}_paged(mut self) -> client::Result<client::Page<${paged[1]}, ${ThisType}>> {
+        let mut delegate = self.${property(DELEGATE_PROPERTY_NAME)}.take();
+        // the call of this page borrows the delegate, which is passed on to the call of the next page
+        let call = ${mb_type(resource, method)} {
+            hub: self.hub,
+        % for p in params:
+            % if p.name == DELEGATE_PROPERTY_NAME:
+            ${property(p.name)}: match delegate {
+                Some(ref mut delegate) => Some(&mut **delegate),
+                None => None,
+            },
+            % else:
+            ${property(p.name)}: self.${property(p.name)}.clone(),
+            % endif
+        % endfor
+            ${api.properties.params}: self.${api.properties.params}.clone(),
+            ${api.properties.timeouts}: self.${api.properties.timeouts},
+            ${api.properties.quota_project}: self.${api.properties.quota_project}.clone(),
+            ${api.properties.gzip}: self.${api.properties.gzip},
+            ${api.properties.idempotent}: self.${api.properties.idempotent},
+            ${api.properties.headers}: self.${api.properties.headers}.clone(),
+            ${api.properties.header_error}: self.${api.properties.header_error}.take(),
+        % if method_media_params(m):
+            ${api.properties.upload}: self.${api.properties.upload}.clone(),
+        % endif
+        % if request_id:
+            ${api.properties.auto_request_id}: self.${api.properties.auto_request_id},
+        % endif
+        % if method_default_scope(m):
+            ${api.properties.scopes}: self.${api.properties.scopes}.clone(),
+        % endif
+        };
+        let (res, page) = call.${api.terms.action}().await?;
+        self.${property(DELEGATE_PROPERTY_NAME)} = delegate;
+        let next_token: Option<String> = ${next_page_token};
+        let next_token = next_token.filter(|token| !token.is_empty());
+        let next = next_token.clone().map(|token| {
+            self.${property(paged[2].name)} = Some(token);
+            self
+        });
+        Ok(client::Page::new(${page_items}, next_token, client::ResponseMetadata::new(&res), next))
+    }
+% endif
+% if streamed_items:
+
+${self._action_fn(c, resource, method, m, params, request_value, parts, streaming = True)}\
+% endif
+% if any(p.protocol == RESUMABLE for p in method_media_params(m)):
+
+${self._action_fn(c, resource, method, m, params, request_value, parts, start_session = True)}\
+
+${self._upload_stream_fn(c, m)}\
+% endif
+
+${self._action_fn(c, resource, method, m, params, request_value, parts, build_request = True)}\
+% if batchPath is not UNDEFINED:
+
+    /// Add the operation you have built so far to `batch`, to be sent along with other calls using
+    /// [`${hub_type_name}::execute_batch()`].
+    ///
+    /// The returned entry takes the result of this call from the response to the batch.
+    pub async fn add_to_batch(self, batch: &mut client::BatchRequest) -> client::Result<client::BatchEntry<${response_schema and response_schema.id or 'json::Value'}>> {
+        let request = self.build_request().await?;
+        batch.add(request).await
+    }
+% endif
 
 ## SETTERS ###############
 % for p in params:
 ${self._setter_fn(resource, method, m, p, part_prop, ThisType, c)}\
+% if request_value and p.get('format') == 'google-fieldmask' and not is_required_property(p) and not is_repeated_property(p):
+    /// Sets the *${split_camelcase_s(p.name)}* ${get_word(p, 'location')}property to the fields in which the request differs
+    /// from `old`, the current state of the resource, see [`client::FieldMask::diff()`], which fails if the resources
+    /// can't be serialized to JSON.
+    pub fn ${mangle_ident(setter_fn_name(p))}_from_diff(mut self, old: &${request_value.id}) -> json::Result<${ThisType}> {
+        self.${property(p.name)} = Some(client::FieldMask::diff(old, &self.${property(REQUEST_VALUE_PROPERTY_NAME)})?);
+        Ok(self)
+    }
+% endif
 % endfor
 
     /// Set any additional parameter of the query string used in the request.
@@ -177,6 +333,104 @@ ${self._setter_fn(resource, method, m, p, part_prop, ThisType, c)}\
         self.${api.properties.params}.insert(name.as_ref().to_string(), value.as_ref().to_string());
         self
     }
+    % if parameters and 'fields' in parameters and 'fields' not in [p.name for p in params]:
+<% assert 'fields_mask' not in [mangle_ident(setter_fn_name(p)) for p in params], "fields_mask() clashes with a setter" %>\
+
+    /// Select the fields of the response to return, sent as the *fields* parameter. Fields which weren't selected are
+    /// `None` in the response, and as fields which are `None` aren't sent, a partial resource can be sent back as
+    /// request value, e.g. along with the same mask as update mask. See [`client::FieldMask::to_fields_param()`].
+    pub fn fields_mask(self, mask: client::FieldMask) -> ${ThisType} {
+        self.${ADD_PARAM_FN}("fields", mask.to_fields_param().as_str())
+    }
+    % endif
+
+    /// Set the maximum duration of each request sent for this call, after which it is retried if the delegate
+    /// allows it. It defaults to the attempt timeout of the hub.
+    pub fn attempt_timeout(mut self, timeout: std::time::Duration) -> ${ThisType} {
+        self.${api.properties.timeouts}.attempt = Some(timeout);
+        self
+    }
+
+    /// Set the maximum duration of this call, including all retries and the delays between them.
+    /// It defaults to the deadline of the hub.
+    pub fn deadline(mut self, deadline: std::time::Duration) -> ${ThisType} {
+        self.${api.properties.timeouts}.deadline = Some(deadline);
+        self
+    }
+
+    /// Set the project to which the quota and billing of this call are attributed, sent as the
+    /// `x-goog-user-project` header. It defaults to the quota project of the hub.
+    pub fn quota_project(mut self, project: impl Into<String>) -> ${ThisType} {
+        self.${api.properties.quota_project} = Some(project.into());
+        self
+    }
+
+    /// Set whether this call asks for a response compressed with gzip and compresses a large request body,
+    /// which only applies with the `gzip` feature. It defaults to the setting of the hub.
+    pub fn gzip(mut self, enabled: bool) -> ${ThisType} {
+        self.${api.properties.gzip} = Some(enabled);
+        self
+    }
+
+    /// Set whether this call may be retried without duplicating its side effects, like if it sets a request id the
+    /// server de-duplicates calls by. The delegate learns it in [`client::MethodInfo`], and [`client::RetryPolicy`]
+    /// only retries failed calls which are idempotent. It defaults to [`Self::IDEMPOTENT`].
+    pub fn idempotent(mut self, idempotent: bool) -> ${ThisType} {
+        self.${api.properties.idempotent} = Some(idempotent);
+        self
+    }
+    % if request_id:
+
+    /// Set whether this call generates a random *${split_camelcase_s(request_id)}* unless one was set, which is sent with all
+    /// of its attempts, so the server doesn't apply the call twice when it is retried. Calls sending a request id are
+    /// idempotent unless set otherwise using `idempotent()`, and the id sent is available as
+    /// [`client::ResponseMetadata::sent_request_id`].
+    pub fn auto_request_id(mut self, enabled: bool) -> ${ThisType} {
+        self.${api.properties.auto_request_id} = enabled;
+        self
+    }
+    % endif
+
+    /// Add a header to the request of this call, like one the API documents without a parameter for it,
+    /// e.g. `X-Goog-FieldMask`. Adding a header more than once sends all of its values.
+    ///
+    /// Like with `http::request::Builder::header()`, the name and value are converted into a header's, and
+    /// the call fails with [`client::Error::InvalidHeader`] if one of them isn't valid.
+    pub fn header<K, V>(mut self, name: K, value: V) -> ${ThisType}
+                                                        where hyper::header::HeaderName: std::convert::TryFrom<K>,
+                                                              <hyper::header::HeaderName as std::convert::TryFrom<K>>::Error: Into<http::Error>,
+                                                              hyper::header::HeaderValue: std::convert::TryFrom<V>,
+                                                              <hyper::header::HeaderValue as std::convert::TryFrom<V>>::Error: Into<http::Error> {
+        use std::convert::TryFrom;
+        let header = hyper::header::HeaderName::try_from(name).map_err(Into::into)
+            .and_then(|name| Ok((name, hyper::header::HeaderValue::try_from(value).map_err(Into::into)?)));
+        match header {
+            Ok((name, value)) => {
+                self.${api.properties.headers}.append(name, value);
+            }
+            Err(err) => {
+                self.${api.properties.header_error}.get_or_insert(err);
+            }
+        }
+        self
+    }
+    % if method_media_params(m):
+
+    /// Call `progress` with the amount of bytes sent so far and the total size of the media while uploading it,
+    /// like for a progress bar. Unlike the `upload_progress()` of the delegate, which reports the bytes persisted
+    /// by the server after each chunk of a resumable upload, it is called while sending each chunk.
+    pub fn upload_progress(mut self, progress: impl FnMut(u64, u64) + Send + 'static) -> ${ThisType} {
+        self.${api.properties.upload}.set_progress(progress);
+        self
+    }
+
+    /// Send the media of this call at no more than `bytes_per_second`, to leave bandwidth to other uses of the
+    /// network. It is sent as fast as possible by default, or if it is 0.
+    pub fn upload_rate_limit(mut self, bytes_per_second: u64) -> ${ThisType} {
+        self.${api.properties.upload}.set_rate_limit(bytes_per_second);
+        self
+    }
+    % endif
 
     % if method_default_scope(m):
     /// Identifies the authorization scope for the method you are building.
@@ -213,8 +467,34 @@ ${self._setter_fn(resource, method, m, p, part_prop, ThisType, c)}\
         self.${api.properties.scopes}.clear();
         self
     }
+
+    /// The scopes authorizing this method, of which at least one should be requested. Calls requesting none of
+    /// them are reported to the delegate, and fail if the hub verifies the scopes granted to their token.
+    ///
+    /// If no scope was added, the first of the hub's default scopes which is one of them is used,
+    /// and [`${scope_url_to_variant(name, method_default_scope(m), fully_qualified=True)}`] if there is none.
+    pub fn required_scopes(&self) -> &'static [Scope] {
+        &[${', '.join(scope_url_to_variant(name, s, fully_qualified=True) for s in m.scopes)}]
+    }
     % endif
 }
+% if paged:
+
+#[cfg(feature = "client")]
+impl${mb_tparams} client::PagedCall<'a> for ${ThisType}
+where
+    S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+    S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Item = ${paged[1]};
+
+    fn fetch_page(self) -> client::PageFuture<'a, ${paged[1]}, Self> {
+        Box::pin(self.${api.terms.action}_paged())
+    }
+}
+% endif
 </%def>
 
 
@@ -274,6 +554,9 @@ ${self._setter_fn(resource, method, m, p, part_prop, ThisType, c)}\
     ///
     ${part_desc | rust_doc_sanitize(documentationLink), rust_doc_comment, indent_all_but_first_by(1)}
     % endif
+    % if deprecation_note(p):
+    #[deprecated(note = ${deprecation_note(p)})]
+    % endif
     pub fn ${mangle_ident(setter_fn_name(p))}(mut self, ${value_name}: ${InType}) -> ${ThisType} {
         % if p.get('repeated', False):
         self.${property(p.name)}.push(${new_value_copied});
@@ -308,10 +591,17 @@ ${self._setter_fn(resource, method, m, p, part_prop, ThisType, c)}\
 
     rb_name = 'req'   # name of request binding
     required_args = request_value and [rb_name] or []
+    resource_name_types = list()
     for p in required_props:
         # could also just skip the first element, but ... let's be safe
         if request_value and request_value.id == p.get(TREF):
             continue
+        if p.get('resource_name'):
+            rn = p.resource_name
+            if rn.type_name not in resource_name_types:
+                resource_name_types.append(rn.type_name)
+            required_args.append('%s::new(%s)' % (rn.type_name, ', '.join('"%s"' % f.rstrip('_') for _, f in rn.segments)))
+            continue
         v = rnd_arg_val_for_type(activity_input_type(schemas, p))
         # we chose to replace random strings with their meaning, as indicated by the name !
         if is_string_value(v):
@@ -348,6 +638,9 @@ ${capture(util.test_prelude) | hide_filter}\
 % if request_value:
 use ${util.library_name()}::api::${request_value_type};
 % endif
+% for type_name in resource_name_types:
+use ${util.library_name()}::api::${type_name};
+% endfor
 % if handle_result:
 use ${util.library_name()}::{Result, Error};
 % endif
@@ -387,7 +680,7 @@ ${rb_name}.${mangle_ident(spn)} = ${assignment}
 % endif
 let result = hub.${mangle_ident(resource)}().${mangle_ident(method)}(${required_args})\
 % for p in optional_props:
-% if p.get('skip_example', False):
+% if p.get('skip_example', False) or deprecation_note(p):
 <% continue %>
 % endif
 
@@ -411,8 +704,13 @@ match result {
         |Error::UploadSizeLimitExceeded(_, _)
         |Error::Failure(_)
         |Error::BadRequest(_)
+        |Error::Api(_)
+        |Error::InsufficientScopes(_)
         |Error::FieldClash(_)
-        |Error::JsonDecodeError(_, _) => println!("{}", e),
+        |Error::InvalidHeader(_)
+        |Error::InvalidLabels(_)
+        |Error::JsonDecodeError(_, _)
+        |Error::Interceptor(_) => println!("{}", e),
     },
     Ok(res) => println!("Success: {:?}", res),
 }
@@ -425,12 +723,63 @@ match result {
 ## create an entire 'api.terms.action' method
 ###############################################################################################
 ###############################################################################################
-<%def name="_action_fn(c, resource, method, m, params, request_value, parts, doit_without_upload = False)">\
+<%def name="_upload_stream_fn(c, m)">\
+<%
+    response_schema = method_response(c, m)
+    rtype = 'client::Result<hyper::Response<hyper::body::Body>>'
+    if response_schema:
+        rtype = 'client::Result<(hyper::Response<hyper::body::Body>, %s)>' % response_schema.id
+    media_params = method_media_params(m)
+    resumable_param = [p for p in media_params if p.protocol == RESUMABLE][0]
+    simple_params = [p for p in media_params if p.protocol == SIMPLE]
+    max_size = media_params[0].max_size
+    upload_fn = upload_action_fn(api.terms.upload_action, resumable_param.type.suffix)
+%>\
+    /// Upload media read from `stream`, like a download streamed from another server, buffering only one chunk of
+    /// it at a time. It is sent with a resumable upload, unless its `size` is known and
+    % if simple_params:
+    /// at most [`client::upload::SINGLE_REQUEST_LIMIT`], in which case it is sent in a single request.
+    % else:
+    /// checked against the size limit of the method.
+    % endif
+    ///
+    /// Fails with an error of the kind [`std::io::ErrorKind::InvalidData`] if `stream` doesn't have `size` bytes.
+    pub async fn ${upload_fn}_stream<R>(self, stream: R, size: Option<u64>, mime_type: mime::Mime) -> ${rtype}
+                where R: AsyncRead + Unpin {
+        % if max_size > 0:
+        if let Some(size) = size {
+            if size > ${max_size} {
+                return Err(client::Error::UploadSizeLimitExceeded(size, ${max_size}));
+            }
+        }
+        % endif
+        % if simple_params:
+        if let Some(size) = size.filter(|size| *size <= client::upload::SINGLE_REQUEST_LIMIT) {
+            let media = client::upload::read_all(stream, size).await.map_err(client::Error::Io)?;
+            if media.len() as u64 != size {
+                return Err(client::Error::Io(io::Error::new(io::ErrorKind::InvalidData,
+                    format!("the media to upload doesn't have the {} bytes given as its size", size))));
+            }
+            return self.${upload_action_fn(api.terms.upload_action, simple_params[0].type.suffix)}(io::Cursor::new(media), mime_type).await;
+        }
+        % endif
+        let session = self.${upload_fn}_session(mime_type).await?;
+        % if response_schema:
+        session.finalize_from(stream, size).await
+        % else:
+        session.finalize_from(stream, size).await.map(|(res, _)| res)
+        % endif
+    }
+</%def>
+
+<%def name="_action_fn(c, resource, method, m, params, request_value, parts, doit_without_upload = False, start_session = False, build_request = False, streaming = False)">\
 <%
     import os.path
     join_url = lambda b, e: b.strip('/') + e
-    if doit_without_upload:
+    if doit_without_upload or build_request or streaming:
         media_params = []
+    elif start_session:
+        media_params = [p for p in method_media_params(m) if p.protocol == RESUMABLE]
     else:
         media_params = method_media_params(m)
 
@@ -440,13 +789,32 @@ match result {
     add_args = ''
     rtype = 'client::Result<hyper::Response<hyper::body::Body>>'
     response_schema = method_response(c, m)
-
-    supports_download = m.get('supportsMediaDownload', False);
+    quota_cost = method_quota_cost(api.get('quota'), m)
+    page_size = page_size_limit(api.get('page_size_limits'), m)
+    request_id = request_id_param(m)
+    # calls sending a request id are de-duplicated by the server
+    idempotent = 'Self::IDEMPOTENT'
+    if request_id:
+        idempotent += ' || self.%s.is_some()' % property(request_id)
+
+    supports_download = m.get('supportsMediaDownload', False) and not start_session
     reserved_params = []
     if response_schema:
         if not supports_download:
             reserved_params = ['alt']
         rtype = 'client::Result<(hyper::Response<hyper::body::Body>, %s)>' % (response_schema.id)
+    if start_session:
+        rtype = 'client::Result<client::ResumableSession<S, %s>>' % (response_schema and response_schema.id or 'json::Value')
+    elif build_request:
+        rtype = 'client::Result<hyper::Request<hyper::body::Body>>'
+    elif streaming:
+        streamed_items = streamed_items_property(schemas, response_schema)
+        rtype = 'client::Result<client::JsonItems<%s>>' % streamed_items[1]
+    server_streaming = response_schema and not start_session and is_server_streaming_method(api.get('server_streaming'), m)
+    if server_streaming and not build_request:
+        rtype = 'client::Result<client::JsonItems<%s>>' % response_schema.id
+    # only calls buffering their JSON response are compressed, unlike those streaming it or transferring media
+    gzip = not (build_request or streaming or start_session or server_streaming or supports_download or method_media_params(m))
 
     mtype_param = 'RS'
 
@@ -467,6 +835,12 @@ match result {
 
     if doit_without_upload:
         action_fn = qualifier + 'async fn ' + "doit_without_upload" + type_params + '(mut self)' + ' -> ' + rtype + where
+    elif build_request:
+        action_fn = 'pub async fn build_request(mut self) -> ' + rtype
+    elif streaming:
+        action_fn = 'pub async fn ' + api.terms.action + '_streaming(mut self) -> ' + rtype
+    elif start_session:
+        action_fn = 'pub async fn ' + upload_action_fn(api.terms.upload_action, resumable_media_param.type.suffix) + '_session(mut self, reader_mime_type: mime::Mime) -> ' + rtype
     else:
         action_fn = qualifier + 'async fn ' + api.terms.action + type_params + ('(mut self%s)' % add_args) + ' -> ' + rtype + where
 
@@ -522,6 +896,30 @@ match result {
 %>
     % if doit_without_upload:
     /// Perform the operation you have build so far, but without uploading. This is used to e.g. renaming or updating the description for a file
+    % elif streaming:
+    /// Perform the operation you have build so far, decoding the `${streamed_items[0]}` of the response one at a time
+    /// while it is received, instead of buffering the complete response like [`Self::${api.terms.action}()`].
+    ///
+    /// The other members of the response are available through [`client::JsonItems::rest()`] once all items were
+    /// taken from the returned stream.
+    % elif start_session:
+    /// Start a resumable upload of media with the given mime type, without knowing its size in advance.
+    ///
+    /// The returned session is fed with data as it is produced, and creates the resource once it is finalized.
+    % elif build_request:
+    /// Build the request of the operation you have built so far, including its authorization, without sending it.
+    /// This is useful for testing, or to sign it before it is sent by other means.
+    ///
+    /// The url, query, headers and body are the same as those sent by [`Self::${api.terms.action}()`]\
+    % if method_media_params(m):
+, without uploading media\
+    % endif
+.
+    % elif server_streaming:
+    /// Perform the operation you have build so far, decoding the messages the server streams as response one at a time
+    /// while they are received.
+    ///
+    /// The returned stream ends when the server closes it, and yields an error if the stream failed.
     % else:
     /// Perform the operation you have build so far.
     % endif
@@ -531,10 +929,46 @@ match result {
         use client::{ToParts, url::Params};
         use std::borrow::Cow;
 
+        % if start_session:
+        let protocol = client::UploadProtocol::Resumable;
+        % endif
+        % if default_scope:
+        let required_scopes = self.required_scopes();
+        % endif
+        % if request_id:
+        ## the id is generated once, and sent with each attempt
+        if self.${api.properties.auto_request_id} && self.${property(request_id)}.is_none() {
+            self.${property(request_id)} = Some(client::new_request_id());
+        }
+        % endif
         let mut dd = client::DefaultDelegate;
         let mut dlg: &mut dyn client::Delegate = ${delegate}.unwrap_or(&mut dd);
         dlg.begin(client::MethodInfo { id: "${m.id}",
-                               http_method: ${method_name_to_variant(m.httpMethod)} });
+                               http_method: ${method_name_to_variant(m.httpMethod)},
+                               idempotent: self.${api.properties.idempotent}.unwrap_or(${idempotent}) });
+        % if page_size:
+        if let Some(page_size) = self.${property(page_size[0])} {
+            if page_size > Self::MAX_PAGE_SIZE {
+                dlg.page_size_clamped(page_size.into(), Self::MAX_PAGE_SIZE.into());
+                self.${property(page_size[0])} = Some(Self::MAX_PAGE_SIZE);
+            }
+        }
+        % endif
+        % if not build_request:
+        let _call = match self.hub._calls.start() {
+            Ok(call) => call,
+            Err(err) => {
+                ${delegate_finish}(false);
+                return Err(client::Error::Io(err));
+            }
+        };
+        let timer = self.${api.properties.timeouts}.or(self.hub._timeouts).start();
+        % if gzip:
+        #[cfg(feature = "gzip")]
+        let gzip = self.${api.properties.gzip}.unwrap_or(self.hub._gzip);
+        % endif
+        let mut span = client::CallSpan::new("${name}", "${m.id}", "${m.httpMethod}", "${m.path}");
+        % endif
 
         ## TODO: Should go into validation function?
         ## Additional params - may not overlap with optional params
@@ -544,6 +978,19 @@ match result {
                 return Err(client::Error::FieldClash(field));
             }
         }
+        if let Some(err) = self.${api.properties.header_error}.take() {
+            ${delegate_finish}(false);
+            return Err(client::Error::InvalidHeader(err));
+        }
+        % if request_value and is_labels_property('labels', request_value.get('properties', dict()).get('labels', dict())):
+
+        if let Some(labels) = self.${property(REQUEST_VALUE_PROPERTY_NAME)}.labels.as_ref() {
+            if let Err(err) = labels.validate() {
+                ${delegate_finish}(false);
+                return Err(client::Error::InvalidLabels(err));
+            }
+        }
+        % endif
 
         let mut params = Params::with_capacity(${len(params) + len(reserved_params)} + ${paddfields}.len());
 <%
@@ -595,9 +1042,19 @@ match result {
         % endfor
 
         params.extend(${paddfields}.iter());
+        % if parameters and '$.xgafv' in parameters:
+        ## all errors are parsed alike, but only the v2 format carries their details
+        if params.get("$.xgafv").is_none() {
+            params.push("$.xgafv", "2");
+        }
+        % endif
 
         % if response_schema:
-        % if supports_download:
+        % if supports_download and build_request:
+        if params.get("alt").is_none() {
+            params.push("alt", "json");
+        }
+        % elif supports_download:
         let (alt_field_missing, enable_resource_parsing) = {
             if let Some(value) = params.get("alt") {
                 (false, value == "json")
@@ -636,7 +1093,7 @@ else {
         <%
             assert 'key' in parameters, "Expected 'key' parameter if there are no scopes"
         %>
-        match dlg.api_key() {
+        match self.hub.auth.api_key().map(str::to_string).or_else(|| dlg.api_key()) {
             Some(value) => params.push("key", value),
             None => {
                 ${delegate_finish}(false);
@@ -645,8 +1102,21 @@ else {
         }
         % endif
         % else:
+        if let Some(key) = self.hub.auth.api_key() {
+            if params.get("key").is_none() {
+                params.push("key", key.to_string());
+            }
+        }
         if self.${api.properties.scopes}.is_empty() {
-            self.${api.properties.scopes}.insert(${scope_url_to_variant(name, default_scope, fully_qualified=True)}.as_ref().to_string());
+            let scope = self.hub._default_scopes.iter()
+                .find(|scope| required_scopes.iter().any(|s| s.as_ref() == scope.as_str()))
+                .cloned()
+                .unwrap_or_else(|| ${scope_url_to_variant(name, default_scope, fully_qualified=True)}.as_ref().to_string());
+            self.${api.properties.scopes}.insert(scope);
+        }
+        if !self.${api.properties.scopes}.iter().any(|scope| required_scopes.iter().any(|s| s.as_ref() == scope.as_str())) {
+            dlg.unlisted_scopes(&self.${api.properties.scopes}.iter().map(String::as_str).collect::<Vec<_>>(),
+                                &required_scopes.iter().map(|s| s.as_ref()).collect::<Vec<_>>());
         }
         % endif
 
@@ -663,6 +1133,9 @@ else {
         % endif
 
         let url = params.parse_with_url(&url);
+        % if not build_request:
+        span.url(url.as_str());
+        % endif
 
         % if request_value:
         let mut json_mime_type = mime::APPLICATION_JSON;
@@ -679,12 +1152,14 @@ else {
         % endif
 
         % if resumable_media_param:
-        let mut should_ask_dlg_for_url = false;
+        let mut should_ask_dlg_for_url = protocol == ${PROTOCOL_TYPE_MAP[resumable_media_param.protocol]};
         let mut upload_url_from_server;
         let mut upload_url: Option<String> = None;
         % endif
 
+        % if not build_request:
         loop {
+        % endif
             % if default_scope:
             let token = match ${auth_call}.get_token(&self.${api.properties.scopes}.iter().map(String::as_str).collect::<Vec<_>>()[..]).await {
                 Ok(token) => token,
@@ -698,11 +1173,20 @@ else {
                     }
                 }
             };
+            if let (Some(granted_scopes), Some(token)) = (self.hub._granted_scopes.as_ref(), token.as_ref()) {
+                let required_scopes: ${"Vec<&str>"} = required_scopes.iter().map(|s| s.as_ref()).collect();
+                if let Err(err) = granted_scopes.verify(&self.hub.client, token, &required_scopes, &self.hub._user_agent).await {
+                    ${delegate_finish}(false);
+                    return Err(err);
+                }
+            }
             % endif
             % if request_value:
             request_value_reader.seek(io::SeekFrom::Start(0)).unwrap();
             % endif
+            % if not build_request:
             let mut req_result = {
+            % endif
             % if resumable_media_param:
                 if should_ask_dlg_for_url && (upload_url = dlg.upload_url()) == () && upload_url.is_some() {
                     should_ask_dlg_for_url = false;
@@ -728,12 +1212,37 @@ else {
                     _ => (&mut request_value_reader as &mut (dyn io::Read + Send), json_mime_type.clone()),
                 };
             % endif
-                let client = &self.hub.client;
+                % if not build_request:
+                let stream = self.hub._transport.stream(&self.hub.client).await;
+                let client = stream.client();
                 dlg.pre_request();
+                % if quota_cost:
+                if let Some(budget) = self.hub._quota_budget.as_ref() {
+                    budget.consume(Self::QUOTA_COST);
+                }
+                % endif
+                % endif
                 let mut req_builder = hyper::Request::builder()
                     .method(${method_name_to_variant(m.httpMethod)})
                     .uri(url.as_str())
                     .header(USER_AGENT, self.hub._user_agent.clone());
+                if let Some(project) = self.${api.properties.quota_project}.as_ref().or(self.hub._quota_project.as_ref()) {
+                    req_builder = req_builder.header("x-goog-user-project", project.as_str());
+                }
+                for (name, value) in self.${api.properties.headers}.iter() {
+                    req_builder = req_builder.header(name, value.clone());
+                }
+                % if not build_request:
+                if let Some(timeout) = timer.server_timeout() {
+                    req_builder = req_builder.header("x-server-timeout", timeout);
+                }
+                % endif
+                % if gzip:
+                #[cfg(feature = "gzip")]
+                if gzip {
+                    req_builder = client::gzip::accept(req_builder);
+                }
+                % endif
 
                 % if default_scope:
                 if let Some(token) = token.as_ref() {
@@ -749,7 +1258,19 @@ else {
                 % endif
 
                 % if request_value:
-                    % if not simple_media_param:
+                    % if gzip:
+                        let req_builder = req_builder
+                        .header(CONTENT_TYPE, json_mime_type.to_string())
+                        .header(CONTENT_LENGTH, request_size as u64);
+                        let body = request_value_reader.get_ref().clone();
+                        #[cfg(feature = "gzip")]
+                        let (req_builder, body) = if gzip {
+                            client::gzip::compress_request(req_builder, body)
+                        } else {
+                            (req_builder, body)
+                        };
+                        let request = req_builder.body(hyper::body::Body::from(body))\
+                    % elif not simple_media_param:
                         let request = req_builder
                         .header(CONTENT_TYPE, json_mime_type.to_string())
                         .header(CONTENT_LENGTH, request_size as u64)
@@ -757,9 +1278,16 @@ else {
                     % else:
                         let mut body_reader_bytes = vec![];
                         body_reader.read_to_end(&mut body_reader_bytes).unwrap();
+                        let body_size = body_reader_bytes.len() as u64;
+                        let body = if protocol == ${PROTOCOL_TYPE_MAP[simple_media_param.protocol]} {
+                            self.${api.properties.upload}.body(body_reader_bytes, 0, body_size)
+                        } else {
+                            hyper::body::Body::from(body_reader_bytes)
+                        };
                         let request = req_builder
                             .header(CONTENT_TYPE, content_type.to_string())
-                            .body(hyper::body::Body::from(body_reader_bytes))\
+                            .header(CONTENT_LENGTH, body_size)
+                            .body(body)\
                     % endif ## not simple_media_param
                 % else:
                     % if simple_media_param:
@@ -769,7 +1297,7 @@ else {
                             reader.read_to_end(&mut bytes)?;
                             req_builder.header(CONTENT_TYPE, reader_mime_type.to_string())
                                      .header(CONTENT_LENGTH, size)
-                                     .body(hyper::body::Body::from(bytes))
+                                     .body(self.${api.properties.upload}.body(bytes, 0, size))
                         } else {
                             req_builder.body(hyper::body::Body::from(Vec::new()))
                         }\
@@ -781,24 +1309,68 @@ else {
                 % endif
 ;
 
-                client.request(request.unwrap()).await
+                % if build_request:
+                ${delegate_finish}(true);
+                Ok(request.unwrap())
+                % elif m.httpMethod == 'GET':
+                let request = request.unwrap();
+                match self.hub._single_flight.as_ref() {
+                    Some(flights) => {
+                        // the fields of self were partially moved, so the closure only borrows the hub
+                        let hub = self.hub;
+                        let send = |request| hub._redirect_policy.send(client, request, &hub._interceptors);
+                        span.instrument(timer.attempt(flights.send(request, send))).await
+                    }
+                    None => span.instrument(timer.attempt(self.hub._redirect_policy.send(client, request, &self.hub._interceptors))).await,
+                }
+                % else:
+                span.instrument(timer.attempt(self.hub._redirect_policy.send(client, request.unwrap(), &self.hub._interceptors))).await
+                % endif
 
 </%block>\
                 % if resumable_media_param:
             }
                 % endif
+            % if not build_request:
             };
+            % if gzip:
+            #[cfg(feature = "gzip")]
+            let req_result = match req_result {
+                Ok(res) if gzip => client::gzip::decode(res).await,
+                req_result => req_result,
+            };
+            % endif
 
             match req_result {
-                Err(err) => {
+                Err(client::Error::HttpError(err)) => {
                     if let client::Retry::After(d) = dlg.http_error(&err) {
-                        sleep(d).await;
-                        continue;
+                        if timer.allows_retry_after(d) {
+                            span.resend();
+                            sleep(d).await;
+                            continue;
+                        }
                     }
                     ${delegate_finish}(false);
                     return Err(client::Error::HttpError(err))
                 }
+                Err(client::Error::Io(err)) if err.kind() == io::ErrorKind::TimedOut => {
+                    if let client::Retry::After(d) = dlg.attempt_timed_out() {
+                        if timer.allows_retry_after(d) {
+                            span.resend();
+                            sleep(d).await;
+                            continue;
+                        }
+                    }
+                    ${delegate_finish}(false);
+                    return Err(client::Error::Io(err))
+                }
+                Err(err) => {
+                    ${delegate_finish}(false);
+                    return Err(err)
+                }
                 Ok(mut res) => {
+                    span.response(res.status());
+                    self.hub._clock.observe(res.headers());
                     if !res.status().is_success() {
                         let res_body_string = client::get_body_as_string(res.body_mut()).await;
                         let (parts, _) = res.into_parts();
@@ -808,17 +1380,40 @@ else {
                         let server_response = json::from_str::<serde_json::Value>(&res_body_string).ok();
 
                         if let client::Retry::After(d) = dlg.http_failure(&restored_response, server_response.clone()) {
-                            sleep(d).await;
-                            continue;
+                            if timer.allows_retry_after(d) {
+                                span.resend();
+                                sleep(d).await;
+                                continue;
+                            }
                         }
 
                         ${delegate_finish}(false);
 
                         return match server_response {
-                            Some(error_value) => Err(client::Error::BadRequest(error_value)),
+                            Some(error_value) => match client::ApiError::from_response(restored_response.headers(), &error_value) {
+                                Some(api_error) => Err(client::Error::Api(api_error)),
+                                None => Err(client::Error::BadRequest(error_value)),
+                            },
                             None => Err(client::Error::Failure(restored_response)),
                         }
                     }
+                    % if start_session:
+                    let url_str = res.headers().get("Location").expect("LOCATION header is part of protocol").to_str().unwrap();
+                    let session = if upload_url_from_server {
+                        client::ResumableSession::new(self.hub.client.clone(), url_str, self.hub._user_agent.as_str())
+                    } else {
+                        match client::ResumableSession::resume(self.hub.client.clone(), url_str, self.hub._user_agent.as_str()).await {
+                            Ok(session) => session,
+                            Err(err) => {
+                                ${delegate_finish}(false);
+                                return Err(err)
+                            }
+                        }
+                    };
+
+                    ${delegate_finish}(true);
+                    return Ok(session)
+                    % else:
                     % if resumable_media_param:
                     if protocol == ${PROTOCOL_TYPE_MAP[resumable_media_param.protocol]} {
                         ${READER_SEEK | indent_all_but_first_by(6)}
@@ -839,7 +1434,8 @@ else {
                                 url: url_str,
                                 reader: &mut reader,
                                 media_type: reader_mime_type.clone(),
-                                content_length: size
+                                content_length: size,
+                                upload_options: &self.${api.properties.upload},
                             }.upload().await
                         };
                         match upload_result {
@@ -856,9 +1452,10 @@ else {
                             ## decoded next
                             Some(Ok(upload_result)) => {
                                 res = upload_result;
+                                ## the session is complete or failed permanently, it can't be resumed anymore
+                                dlg.store_upload_url(None);
                                 if !res.status().is_success() {
                                     ## delegate was called in upload() already - don't tell him again
-                                    dlg.store_upload_url(None);
                                     ${delegate_finish}(false);
                                     return Err(client::Error::Failure(res))
                                 }
@@ -866,7 +1463,17 @@ else {
                         }
                     }
                     % endif
-                % if response_schema:
+                    % if request_id:
+                    if let Some(id) = self.${property(request_id)}.as_ref() {
+                        res.extensions_mut().insert(client::SentRequestId(id.clone()));
+                    }
+                    % endif
+                    dlg.response_metadata(&client::ResponseMetadata::new(&res));
+                % if streaming:
+                    let result_value = client::JsonItems::new(res, "${streamed_items[0]}");
+                % elif server_streaming:
+                    let result_value = client::JsonItems::array(res);
+                % elif response_schema:
                     ## If 'alt' is not json, we cannot attempt to decode the response
                     let result_value = \
                     % if supports_download:
@@ -874,8 +1481,15 @@ if enable_resource_parsing \
                     % endif
 {
                         let res_body_string = client::get_body_as_string(res.body_mut()).await;
+                        #[cfg(feature = "validate-responses")]
+                        {
+                            let divergences = RESPONSE_SCHEMAS.validate("${response_schema.id}", &res_body_string);
+                            if !divergences.is_empty() {
+                                dlg.response_divergences("${response_schema.id}", &divergences);
+                            }
+                        }
 
-                        match json::from_str(&res_body_string) {
+                        match client::decode::from_str(&res_body_string) {
                             Ok(decoded) => (res, decoded),
                             Err(err) => {
                                 dlg.response_json_decode_error(&res_body_string, &err);
@@ -893,11 +1507,14 @@ if enable_resource_parsing \
 
                     ${delegate_finish}(true);
                     return Ok(result_value)
+                    % endif ## start_session
                 }
             }
         }
+            % endif ## not build_request
     }
 
+    % if not start_session and not build_request:
     % for p in media_params:
     ${p.description | rust_doc_sanitize(documentationLink), rust_doc_comment, indent_all_but_first_by(1)}
     ///
@@ -909,4 +1526,5 @@ if enable_resource_parsing \
         self.${api.terms.action}(${p.type.arg_name}, mime_type, ${PROTOCOL_TYPE_MAP[p.protocol]}).await
     }
     % endfor
+    % endif
 </%def>
@@ -0,0 +1,51 @@
+<%!
+    from generator.lib.util import singular
+%>\
+## Build the typed resource name of the given ResourceName
+###################################################################################################################
+###################################################################################################################
+<%def name="new(rn)">\
+<%
+    fields = [field for _, field in rn.segments]
+%>\
+/// The name of a *${singular(rn.segments[-1][0])}* resource, of the form `${rn.template()}`.
+///
+/// It is accepted by all methods taking such a name, and can be parsed from its string form
+/// using `str::parse()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ${rn.type_name} {
+% for collection, field in rn.segments:
+    /// The id of the *${singular(collection)}*.
+    pub ${field}: String,
+% endfor
+}
+
+impl ${rn.type_name} {
+    /// The template of these names, in which each variable is a single path segment.
+    pub const TEMPLATE: &'static str = "${rn.template()}";
+
+    /// Create the name of the resource with the given ids.
+    pub fn new(${', '.join('%s: impl Into<String>' % field for field in fields)}) -> ${rn.type_name} {
+        ${rn.type_name} {
+        % for field in fields:
+            ${field}: ${field}.into(),
+        % endfor
+        }
+    }
+}
+
+impl std::fmt::Display for ${rn.type_name} {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "${rn.format_str()}", ${', '.join('self.' + field for field in fields)})
+    }
+}
+
+impl std::str::FromStr for ${rn.type_name} {
+    type Err = client::ResourceNameError;
+
+    fn from_str(value: &str) -> std::result::Result<${rn.type_name}, client::ResourceNameError> {
+        let [${', '.join(fields)}] = client::parse_resource_name(value, Self::TEMPLATE)?;
+        Ok(${rn.type_name} { ${', '.join(fields)} })
+    }
+}
+</%def>
@@ -0,0 +1,195 @@
+<%!
+    from generator.lib.util import (mangle_ident, to_fqan, method_media_params)
+%>\
+## Build the convenience layer for the objects of a storage API, on top of the calls of its objects resource
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c, hub_type)">\
+<%
+    resource = api.object_storage.resource
+    objects = mangle_ident(resource)
+    for activity in ('insert', 'get', 'compose'):
+        assert to_fqan(c.rtc_map[resource], resource, activity) in c.fqan_map, \
+            "object_storage requires %s.%s" % (resource, activity)
+    assert method_media_params(c.fqan_map[to_fqan(c.rtc_map[resource], resource, 'insert')])
+%>\
+/// Convenient handling of buckets and their objects, built on the calls of [`${hub_type}::${objects}()`].
+///
+/// A [`Bucket`] names a bucket, and an [`Object`] names an object within it, without fetching either.
+/// Their methods make the calls needed for the most common operations, like uploading a file, and fail
+/// like the calls do.
+#[cfg(feature = "client")]
+pub mod objects {
+    use std::error::Error as StdError;
+    use std::fs;
+    use std::io::{self, Write};
+    use std::path::Path;
+    use std::time::Duration;
+
+    use hyper::body::HttpBody;
+    use tokio::io::{AsyncRead, AsyncWrite};
+
+    use crate::api::${hub_type};
+    use crate::client;
+
+    /// A bucket, whose objects are handled by the calls of a hub.
+    pub struct Bucket<'a, S> {
+        hub: &'a ${hub_type}<S>,
+        name: String,
+    }
+
+    /// An object within a bucket, handled by the calls of a hub.
+    pub struct Object<'a, S> {
+        hub: &'a ${hub_type}<S>,
+        bucket: String,
+        name: String,
+    }
+
+    impl<'a, S> Bucket<'a, S>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        /// The bucket called `name`, whose objects are handled by the calls of `hub`.
+        pub fn new(hub: &'a ${hub_type}<S>, name: impl Into<String>) -> Bucket<'a, S> {
+            Bucket { hub, name: name.into() }
+        }
+
+        /// Returns the name of the bucket.
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        /// The object called `name` within the bucket.
+        pub fn object(&self, name: impl Into<String>) -> Object<'a, S> {
+            Object {
+                hub: self.hub,
+                bucket: self.name.clone(),
+                name: name.into(),
+            }
+        }
+
+        /// Upload the file at `path` as an object named like the file, see [`Object::upload_file()`].
+        pub async fn upload_file(&self, path: impl AsRef<Path>, mime_type: mime::Mime) -> client::Result<crate::api::Object> {
+            let path = path.as_ref();
+            let name = path.file_name().and_then(|name| name.to_str()).ok_or_else(|| {
+                client::Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} doesn't end with a file name", path.display()),
+                ))
+            })?;
+            self.object(name).upload_file(path, mime_type).await
+        }
+
+        /// Concatenate the objects called `sources`, in this order, into the object called `destination`,
+        /// which is replaced if it exists. At most 32 objects can be composed by a single call.
+        pub async fn compose(&self, sources: &[&str], destination: &str, content_type: ${"Option<&str>"}) -> client::Result<crate::api::Object> {
+            let request = crate::api::ComposeRequest {
+                destination: Some(crate::api::Object {
+                    content_type: content_type.map(str::to_string),
+                    ..Default::default()
+                }),
+                source_objects: Some(sources.iter().map(|source| crate::api::ComposeRequestSourceObjects {
+                    name: Some(source.to_string()),
+                    ..Default::default()
+                }).collect()),
+                ..Default::default()
+            };
+            let (_, object) = self.hub.${objects}().compose(request, &self.name, destination).${api.terms.action}().await?;
+            Ok(object)
+        }
+    }
+
+    impl<'a, S> Object<'a, S>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        /// The object called `name` within the bucket called `bucket`.
+        pub fn new(hub: &'a ${hub_type}<S>, bucket: impl Into<String>, name: impl Into<String>) -> Object<'a, S> {
+            Object {
+                hub,
+                bucket: bucket.into(),
+                name: name.into(),
+            }
+        }
+
+        /// Returns the name of the bucket of the object.
+        pub fn bucket(&self) -> &str {
+            &self.bucket
+        }
+
+        /// Returns the name of the object.
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        /// Returns the metadata of the object.
+        pub async fn metadata(&self) -> client::Result<crate::api::Object> {
+            let (_, object) = self.hub.${objects}().get(&self.bucket, &self.name).${api.terms.action}().await?;
+            Ok(object)
+        }
+
+        /// Upload the file at `path` as the contents of the object, which is replaced if it exists, and
+        /// return its metadata. The file is uploaded using a resumable upload, so large files survive
+        /// interruptions.
+        pub async fn upload_file(&self, path: impl AsRef<Path>, mime_type: mime::Mime) -> client::Result<crate::api::Object> {
+            let file = fs::File::open(path).map_err(client::Error::Io)?;
+            let request = crate::api::Object {
+                name: Some(self.name.clone()),
+                ..Default::default()
+            };
+            let (_, object) = self.hub.${objects}().insert(request, &self.bucket).upload_resumable(file, mime_type).await?;
+            Ok(object)
+        }
+
+        /// Returns the contents of the object as a stream of chunks, which are received as they are read.
+        /// The returned [`hyper::Body`] implements [`HttpBody`], whose `data()` method yields the next chunk.
+        pub async fn read_stream(&self) -> client::Result<hyper::Body> {
+            let (res, _) = self.hub.${objects}().get(&self.bucket, &self.name).param("alt", "media").${api.terms.action}().await?;
+            Ok(res.into_body())
+        }
+
+        /// Write the contents of the object to the file at `path`, which is replaced if it exists, and
+        /// return the amount of bytes written. The file is removed if the download fails.
+        pub async fn download_to_file(&self, path: impl AsRef<Path>) -> client::Result<u64> {
+            let path = path.as_ref();
+            let mut body = self.read_stream().await?;
+            let mut file = fs::File::create(path).map_err(client::Error::Io)?;
+            let mut written = 0;
+            let result = async {
+                while let Some(chunk) = body.data().await {
+                    let chunk = chunk.map_err(client::Error::HttpError)?;
+                    file.write_all(&chunk).map_err(client::Error::Io)?;
+                    written += chunk.len() as u64;
+                }
+                file.flush().map_err(client::Error::Io)
+            }.await;
+            if result.is_err() {
+                fs::remove_file(path).ok();
+            }
+            result.map(|_| written)
+        }
+
+        /// Returns a url allowing anyone holding it to use the HTTP `method` on the object, like `GET` to
+        /// download it or `PUT` to upload it, until it expires after `expires_in`, at most seven days.
+        ///
+        /// The url is signed locally by `signer`, on behalf of its service account, whose permissions on
+        /// the object are granted by the url.
+        pub fn signed_url(&self, signer: &client::UrlSigner, method: &str, expires_in: Duration) -> io::Result<String> {
+            signer.sign(method, &self.bucket, &self.name, expires_in, client::chrono::Utc::now())
+        }
+
+        /// Returns the description of a url granting the HTTP `method` on the object for `expires_in`, which
+        /// can require headers, like the `content_type()` of an upload, before it is signed by a
+        /// [`client::UrlSigner`] or, without a private key, a [`client::IamUrlSigner`].
+        pub fn signed_url_request(&self, method: &str, expires_in: Duration) -> client::SignedUrlRequest {
+            client::SignedUrlRequest::new(method, self.bucket.as_str(), self.name.as_str(), expires_in)
+        }
+    }
+}
+</%def>
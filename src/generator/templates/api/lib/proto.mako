@@ -0,0 +1,80 @@
+<%!
+    from generator.lib.util import proto_interop_fields
+%>\
+## Build the conversions between the schemas and the messages generated by prost from the protos of the API
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c)">\
+<%
+    config = api.proto_interop
+    features = ['feature = "proto-interop"']
+    # the strict types of required fields don't match the messages of proto3
+    if api.get('required_response_fields'):
+        features.append('not(feature = "strict-required")')
+%>\
+/// Conversions between the schemas and the messages of the same name generated by prost, in
+/// `${config.module}`, see [`client::proto`].
+% if len(features) > 1:
+#[cfg(all(${', '.join(features)}))]
+% else:
+#[cfg(${features[0]})]
+% endif
+mod proto_interop {
+% if config.get('enums'):
+    use std::convert::TryFrom;
+
+% endif
+    use crate::client::proto::Proto;
+    use ${config.module} as proto;
+
+    use super::*;
+% for name in config.messages:
+<%
+    assert name in c.schemas, "proto_interop message %s is not a schema" % name
+    fields = proto_interop_fields(config, c.schemas[name])
+%>\
+
+    impl Proto<proto::${name}> for ${name} {
+        #[allow(clippy::needless_update)]
+        fn into_proto(self) -> proto::${name} {
+            proto::${name} {
+            % for field, proto_field, enum in fields:
+            % if enum:
+                ${proto_field}: self.${field}.as_deref().and_then(proto::${enum}::from_str_name).map_or(0, |value| value as i32),
+            % else:
+                ${proto_field}: self.${field}.into_proto(),
+            % endif
+            % endfor
+                ..Default::default()
+            }
+        }
+
+        #[allow(clippy::needless_update)]
+        fn from_proto(message: proto::${name}) -> ${name} {
+            ${name} {
+            % for field, proto_field, enum in fields:
+            % if enum:
+                ${field}: proto::${enum}::try_from(message.${proto_field}).ok().filter(|value| *value as i32 != 0).map(|value| value.as_str_name().to_string()),
+            % else:
+                ${field}: Proto::from_proto(message.${proto_field}),
+            % endif
+            % endfor
+                ..Default::default()
+            }
+        }
+    }
+
+    impl From<proto::${name}> for ${name} {
+        fn from(message: proto::${name}) -> ${name} {
+            ${name}::from_proto(message)
+        }
+    }
+
+    impl From<${name}> for proto::${name} {
+        fn from(value: ${name}) -> proto::${name} {
+            value.into_proto()
+        }
+    }
+% endfor
+}
+</%def>
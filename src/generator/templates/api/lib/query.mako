@@ -0,0 +1,176 @@
+<%!
+    from generator.lib.util import (mangle_ident, to_fqan)
+%>\
+## Build the query helper of a data warehouse API, running queries and decoding their rows, on top of the calls of its jobs resource
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c, hub_type)">\
+<%
+    resource = api.query_rows.resource
+    jobs = mangle_ident(resource)
+    for activity in ('query', 'getQueryResults'):
+        assert to_fqan(c.rtc_map[resource], resource, activity) in c.fqan_map, \
+            "query_rows requires %s.%s" % (resource, activity)
+%>\
+/// Queries whose rows are decoded into your own types, built on the calls of [`${hub_type}::${jobs}()`].
+///
+/// A [`Project`] runs queries billed to it using [`Project::run_query()`], and [`Query::rows()`] waits for a query
+/// to complete and fetches all pages of its results. The cells of each row are converted according to the types of
+/// the fields of the results' schema, as described by [`client::table_rows`], and deserialized into a type of yours
+/// using serde, whose members are named like the columns.
+#[cfg(feature = "client")]
+pub mod query {
+    use std::error::Error as StdError;
+    use std::io;
+
+    use serde::de::DeserializeOwned;
+    use serde_json as json;
+    use tokio::io::{AsyncRead, AsyncWrite};
+
+    use crate::api::{${hub_type}, QueryRequest, TableRow, TableSchema};
+    use crate::client;
+
+    /// How long the server waits for a query to complete before answering a call polling its results, in milliseconds.
+    const POLL_TIMEOUT_MS: u32 = 10_000;
+
+    /// A project, to which the queries it runs are billed.
+    pub struct Project<'a, S> {
+        hub: &'a ${hub_type}<S>,
+        id: String,
+    }
+
+    /// A query to run, whose results are fetched by [`Query::rows()`].
+    pub struct Query<'a, S> {
+        hub: &'a ${hub_type}<S>,
+        project_id: String,
+        request: QueryRequest,
+    }
+
+    impl<'a, S> Project<'a, S>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        /// The project with the id `id`, whose queries are run by the calls of `hub`.
+        pub fn new(hub: &'a ${hub_type}<S>, id: impl Into<String>) -> Project<'a, S> {
+            Project { hub, id: id.into() }
+        }
+
+        /// Returns the id of the project.
+        pub fn id(&self) -> &str {
+            &self.id
+        }
+
+        /// The query `sql`, in GoogleSQL, which is run once its rows are requested.
+        pub fn run_query(&self, sql: impl Into<String>) -> Query<'a, S> {
+            Query {
+                hub: self.hub,
+                project_id: self.id.clone(),
+                request: QueryRequest {
+                    query: Some(sql.into()),
+                    use_legacy_sql: Some(false),
+                    ..Default::default()
+                },
+            }
+        }
+    }
+
+    impl<'a, S> Query<'a, S>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        /// Run the query in `location`, like `EU`, which is where the tables it reads are.
+        pub fn location(mut self, location: impl Into<String>) -> Query<'a, S> {
+            self.request.location = Some(location.into());
+            self
+        }
+
+        /// Fetch at most `size` rows per page of results.
+        pub fn page_size(mut self, size: u32) -> Query<'a, S> {
+            self.request.max_results = Some(size);
+            self
+        }
+
+        /// Returns the request running the query, to set any of its other members, like its `query_parameters`.
+        pub fn request_mut(&mut self) -> &mut QueryRequest {
+            &mut self.request
+        }
+
+        /// Run the query, wait for it to complete and return all rows of its results, each decoded into `T`.
+        ///
+        /// A row which can't be decoded fails with [`client::Error::JsonDecodeError`], holding the row as JSON.
+        pub async fn rows<T: DeserializeOwned>(self) -> client::Result<Vec<T>> {
+            let page_size = self.request.max_results;
+            let requested_location = self.request.location.clone();
+            let (_, response) = self.hub.${jobs}().query(self.request, &self.project_id).${api.terms.action}().await?;
+            let mut rows = Vec::new();
+            let mut decoder = None;
+            let mut page_token = None;
+            let job = response.job_reference.unwrap_or_default();
+            let location = job.location.or(requested_location);
+            if response.job_complete == Some(true) {
+                decode(response.schema.as_ref(), response.rows, &mut decoder, &mut rows)?;
+                match response.page_token {
+                    Some(token) => page_token = Some(token),
+                    None => return Ok(rows),
+                }
+            }
+            let job_id = job.job_id.ok_or_else(|| {
+                client::Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the results of the query are incomplete, but it has no job to fetch them from",
+                ))
+            })?;
+            loop {
+                // the server answers once the query completed, or after the timeout if it didn't
+                let mut call = self.hub.${jobs}().get_query_results(&self.project_id, &job_id).timeout_ms(POLL_TIMEOUT_MS);
+                if let Some(location) = &location {
+                    call = call.location(location);
+                }
+                if let Some(size) = page_size {
+                    call = call.max_results(size);
+                }
+                if let Some(token) = &page_token {
+                    call = call.page_token(token);
+                }
+                let (_, results) = call.${api.terms.action}().await?;
+                if results.job_complete != Some(true) {
+                    continue;
+                }
+                decode(results.schema.as_ref(), results.rows, &mut decoder, &mut rows)?;
+                match results.page_token {
+                    Some(token) => page_token = Some(token),
+                    None => return Ok(rows),
+                }
+            }
+        }
+    }
+
+    /// Decode `rows` into `decoded`, using the decoder of the first `schema` returned along with rows.
+    fn decode<T: DeserializeOwned>(schema: ${"Option<&TableSchema>"}, rows: Option<Vec<TableRow>>,
+                                   decoder: &mut Option<client::RowDecoder>, decoded: &mut Vec<T>) -> client::Result<()> {
+        let rows = rows.unwrap_or_default();
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let decoder = match decoder {
+            Some(decoder) => decoder,
+            None => {
+                let schema = json::to_value(schema).expect("serde to work");
+                let new = client::RowDecoder::new(&schema).map_err(|err| client::Error::JsonDecodeError(schema.to_string(), err))?;
+                decoder.insert(new)
+            }
+        };
+        for row in rows {
+            let row = json::to_value(row).expect("serde to work");
+            decoded.push(decoder.decode(&row).map_err(|err| client::Error::JsonDecodeError(row.to_string(), err))?);
+        }
+        Ok(())
+    }
+}
+</%def>
@@ -6,7 +6,9 @@
                       rust_copy_value_s, organize_params, REQUEST_VALUE_PROPERTY_NAME,
                       build_all_params, rb_type_params_s, hub_type_params_s, mb_type_params_s, mb_additional_type_params, 
                       struct_type_bounds_s, METHODS_RESOURCE, SPACES_PER_TAB, prefix_all_but_first_with,
-                      METHODS_BUILDER_MARKER_TRAIT, remove_empty_lines, method_default_scope, rust_doc_sanitize)
+                      METHODS_BUILDER_MARKER_TRAIT, remove_empty_lines, method_default_scope, rust_doc_sanitize,
+                      iam_policy_cycles, operation_listings, method_response, setter_fn_name, list_items_property,
+                      method_media_params, request_id_param)
 %>\
 <%namespace name="util" file="../../../lib/util.mako"/>\
 <%namespace name="lib" file="lib.mako"/>\
@@ -43,15 +45,18 @@ ${lib.test_hub(hub_type_name, comments=False)}\
 let rb = hub.${mangle_ident(resource)}();
 </%block>
 </%block>
+#[cfg(feature = "client")]
 pub struct ${ThisType}
     where ${struct_type_bounds_s()} {
 
     hub: &'a ${hub_type_name}${hub_type_params_s()},
 }
 
+#[cfg(feature = "client")]
 impl${rb_params} ${METHODS_BUILDER_MARKER_TRAIT} for ${ThisType} {}
 
 ## Builder Creators Methods ####################
+#[cfg(feature = "client")]
 impl${rb_params} ${ThisType} {
     % for a in c.rta_map[resource]:
 <%
@@ -70,7 +75,7 @@ impl${rb_params} ${ThisType} {
     mb_tparams = mb_type_params_s(m)
     # we would could have information about data requirements for each property in it's dict.
     # for now, we just hardcode it, and treat the entries as way to easily change param names
-    assert len(api.properties) == 2, "Hardcoded for now, thanks to scope requirements"
+    assert len(api.properties) == 10, "Hardcoded for now, thanks to scope requirements"
 
     type_params = ''
     if mb_additional_type_params(m):
@@ -120,7 +125,7 @@ impl${rb_params} ${ThisType} {
             ${property(p.name)}: Default::default(),
             % endfor
 % for prop_key, custom_name in api.properties.items():
-            % if prop_key == 'scopes' and not method_default_scope(m):
+            % if prop_key == 'scopes' and not method_default_scope(m) or prop_key == 'upload' and not method_media_params(m) or prop_key == 'auto_request_id' and not request_id_param(m):
 <% continue %>\
             % endif
             ${custom_name}: Default::default(),
@@ -128,5 +133,350 @@ impl${rb_params} ${ThisType} {
         }
     }
     % endfor ## for each activity
+    % for cycle in iam_policy_cycles(c, resource):
+<%
+    def call_args(required_props):
+        args = list()
+        for p in required_props:
+            if p.name == REQUEST_VALUE_PROPERTY_NAME:
+                args.append('request')
+            elif p.get('resource_name'):
+                args.append(mangle_ident(p.name) + '.clone()')
+            else:
+                args.append(mangle_ident(p.name))
+        return ', '.join(args)
+
+    get_m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, cycle.get_activity)]
+    set_m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, cycle.set_activity)]
+    get_params, get_request = build_all_params(c, get_m)
+    set_params, set_request = build_all_params(c, set_m)
+    get_args = call_args(organize_params(get_params, get_request)[0])
+    set_args = call_args(organize_params(set_params, set_request)[0])
+    method_args = ''.join(', %s: %s' % (mangle_ident(p.name), activity_input_type(schemas, p)) for p in cycle.params)
+%>
+    /// Modify the IAM policy of a resource in a read-modify-write cycle: the policy is fetched using
+    /// [`Self::${mangle_ident(cycle.get_activity)}()`], changed by `modify`, and set using
+    /// [`Self::${mangle_ident(cycle.set_activity)}()`] along with its etag, so the server rejects it if
+    /// the policy was modified concurrently. In this case, the cycle is restarted with the current policy,
+    /// up to [`client::etag::MAX_CONFLICT_RETRIES`] times, which is why `modify` may be called more than once.
+    ///
+    /// Policies are requested in version 3, so modifying them preserves their conditional role bindings.
+    ///
+    /// # Arguments
+    ///
+    % for p in cycle.params:
+<%
+        arg_prefix = "/// * `" + p.name + "` - "
+%>    ${arg_prefix}${p.get('description', "No description provided.")
+        | rust_doc_sanitize(documentationLink), remove_empty_lines, prefix_all_but_first_with(' ' * SPACES_PER_TAB + '///'  + ' ' * (len(arg_prefix) - len('///')))}
+    % endfor
+    /// * `modify` - Applies the desired changes to the policy.
+    pub async fn ${mangle_ident(cycle.activity)}<F>(&self${method_args}, modify: F) -> client::Result<${cycle.policy_type}>
+    where
+        F: FnMut(&mut ${cycle.policy_type}),
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        % for p in cycle.params:
+        % if p.get('resource_name'):
+        let ${mangle_ident(p.name)}: ${p.resource_name.type_name} = ${mangle_ident(p.name)}.into();
+        let ${mangle_ident(p.name)} = &${mangle_ident(p.name)};
+        % endif
+        % endfor
+        client::etag::read_modify_write(
+            move || async move {
+                % if cycle.get_request_type:
+                let mut request = ${cycle.get_request_type}::default();
+                % if cycle.version_options:
+                request.${mangle_ident(cycle.version_options[0])} = Some(${cycle.version_options[1]} {
+                    requested_policy_version: Some(3),
+                    ..Default::default()
+                });
+                % endif
+                % endif
+                let (_, policy) = self.${mangle_ident(cycle.get_activity)}(${get_args})
+                % if cycle.version_param:
+                    .${mangle_ident(setter_fn_name(cycle.version_param))}(3)
+                % endif
+                    .doit().await?;
+                Ok(policy)
+            },
+            modify,
+            move |policy| async move {
+                let mut request = ${cycle.set_request_type}::default();
+                request.policy = Some(policy);
+                let (_, policy) = self.${mangle_ident(cycle.set_activity)}(${set_args}).doit().await?;
+                Ok(policy)
+            },
+        ).await
+    }
+    % endfor ## for each IAM policy cycle
+    % for listing in operation_listings(c, resource):
+<%
+    list_m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, listing.list_activity)]
+    list_params, list_request = build_all_params(c, list_m)
+    page_token_param = next(p for p in organize_params(list_params, list_request)[1] if p.name == 'pageToken')
+    page_response = method_response(c, list_m)
+    next_page_token = 'page.next_page_token'
+    if 'nextPageToken' in (api.get('required_response_fields') or dict()).get(page_response.id, list()):
+        # it's non-optional with the strict-required feature
+        next_page_token += '.into()'
+
+    list_args = list()
+    for p in listing.params:
+        if p.get('resource_name'):
+            list_args.append(mangle_ident(p.name) + '.clone()')
+        elif p.get('type') == 'string':
+            list_args.append('&' + mangle_ident(p.name))
+        else:
+            list_args.append(mangle_ident(p.name))
+    cancel_args = list()
+    for p in listing.cancel_params:
+        if p.name == REQUEST_VALUE_PROPERTY_NAME:
+            cancel_args.append('%s::default()' % listing.cancel_request_type)
+        elif p.get('resource_name'):
+            cancel_args.append('name')
+        else:
+            cancel_args.append('&name')
+    name_param = next(p for p in listing.cancel_params if p.name != REQUEST_VALUE_PROPERTY_NAME)
+    method_args = ''.join(', %s: %s' % (mangle_ident(p.name), activity_input_type(schemas, p)) for p in listing.params)
+    arg_names = ''.join('%s, ' % mangle_ident(p.name) for p in listing.params)
+%>
+    /// Stream the operations selected by `filter`, whose pages are listed using
+    /// [`Self::${mangle_ident(listing.list_activity)}()`] as the operations are taken from the stream.
+    ///
+    /// # Arguments
+    ///
+    % for p in listing.params:
+<%
+        arg_prefix = "/// * `" + p.name + "` - "
+%>    ${arg_prefix}${p.get('description', "No description provided.")
+        | rust_doc_sanitize(documentationLink), remove_empty_lines, prefix_all_but_first_with(' ' * SPACES_PER_TAB + '///'  + ' ' * (len(arg_prefix) - len('///')))}
+    % endfor
+    /// * `filter` - Selects the operations to stream.
+    pub fn ${mangle_ident(listing.stream_activity)}(&self${method_args}, filter: client::OperationFilter) -> client::Operations<'a, ${listing.operation_type}>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        % for p in listing.params:
+        % if p.get('resource_name'):
+        let ${mangle_ident(p.name)}: ${p.resource_name.type_name} = ${mangle_ident(p.name)}.into();
+        % elif p.get('type') == 'string':
+        let ${mangle_ident(p.name)} = ${mangle_ident(p.name)}.to_string();
+        % endif
+        % endfor
+        let hub = self.hub;
+        client::Operations::new(filter, move |page_token| {
+            let mut call = hub.${mangle_ident(resource)}().${mangle_ident(listing.list_activity)}(${', '.join(list_args)});
+            if let Some(page_token) = page_token {
+                call = call.${mangle_ident(setter_fn_name(page_token_param))}(&page_token);
+            }
+            async move {
+                let (_, page) = call.${api.terms.action}().await?;
+                Ok((page.operations.unwrap_or_default(), ${next_page_token}))
+            }
+        })
+    }
+
+    /// Cancel all operations selected by `filter` which aren't done yet using
+    /// [`Self::${mangle_ident(listing.cancel_activity)}()`], e.g. to clean up operations which are stuck.
+    /// Returns the number of cancelled operations, and stops at the first failure.
+    ///
+    /// # Arguments
+    ///
+    % for p in listing.params:
+<%
+        arg_prefix = "/// * `" + p.name + "` - "
+%>    ${arg_prefix}${p.get('description', "No description provided.")
+        | rust_doc_sanitize(documentationLink), remove_empty_lines, prefix_all_but_first_with(' ' * SPACES_PER_TAB + '///'  + ' ' * (len(arg_prefix) - len('///')))}
+    % endfor
+    /// * `filter` - Selects the operations to cancel.
+    pub async fn ${mangle_ident(listing.cancel_all_activity)}(&self${method_args}, filter: client::OperationFilter) -> client::Result<usize>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        self.${mangle_ident(listing.stream_activity)}(${arg_names}filter).cancel_all(|name| async move {
+            % if name_param.get('resource_name'):
+            let name: ${name_param.resource_name.type_name} = name.parse()
+                .map_err(|err| client::Error::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+            % endif
+            self.${mangle_ident(listing.cancel_activity)}(${', '.join(cancel_args)}).${api.terms.action}().await?;
+            Ok(())
+        }).await
+    }
+    % endfor ## for each operation listing
+    % if api.get('upgrade_plan') and api.upgrade_plan.resource == resource:
+<%
+    get_m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, api.upgrade_plan.get)]
+    name_param = organize_params(*build_all_params(c, get_m))[0][0]
+    list_m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, api.upgrade_plan.list)]
+    list_required, list_optional = organize_params(*build_all_params(c, list_m))[:2]
+    parent_param = list_required[0]
+    setters = dict((p.name, mangle_ident(setter_fn_name(p))) for p in list_optional)
+    page_response = method_response(c, list_m)
+    versions_property = mangle_ident(list_items_property(schemas, page_response)[0])
+    next_page_token = 'page.next_page_token'
+    if 'nextPageToken' in (api.get('required_response_fields') or dict()).get(page_response.id, list()):
+        # it's non-optional with the strict-required feature
+        next_page_token += '.into()'
+    plan_activity = api.upgrade_plan.get[:-len('get')] + 'upgrade_plan'
+%>
+    /// Plan the upgrade of the instance with the given name to one of the versions available in its location,
+    /// which are listed using [`Self::${mangle_ident(api.upgrade_plan.list)}()`]. With `latest_patch_only`, only the
+    /// latest patch of each minor version is a target.
+    ///
+    /// The current version of the instance is obtained using [`Self::${mangle_ident(api.upgrade_plan.get)}()`].
+    pub async fn ${mangle_ident(plan_activity)}(&self, name: ${activity_input_type(schemas, name_param)}, latest_patch_only: bool) -> client::Result<client::UpgradePlan>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        % if name_param.get('resource_name'):
+        let name: ${name_param.resource_name.type_name} = name.into();
+        let (_, instance) = self.${mangle_ident(api.upgrade_plan.get)}(name.clone()).${api.terms.action}().await?;
+        % else:
+        let (_, instance) = self.${mangle_ident(api.upgrade_plan.get)}(name).${api.terms.action}().await?;
+        % endif
+        // the versions are listed in the location, the parent of the collection of instances
+        let name = name.to_string();
+        let parent = name.rsplitn(3, '/').nth(2).unwrap_or_default();
+        % if parent_param.get('resource_name'):
+        let parent: ${parent_param.resource_name.type_name} = parent.parse()
+            .map_err(|err| client::Error::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+        % endif
+        let mut versions = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut call = self.${mangle_ident(api.upgrade_plan.list)}(${parent_param.get('resource_name') and 'parent.clone()' or 'parent'})
+                .${setters['latestPatchOnly']}(latest_patch_only);
+            if let Some(page_token) = page_token.as_ref() {
+                call = call.${setters['pageToken']}(page_token);
+            }
+            let (_, page) = call.${api.terms.action}().await?;
+            versions.extend(page.${versions_property}.unwrap_or_default());
+            let next_page_token: Option<String> = ${next_page_token};
+            page_token = next_page_token.filter(|token| !token.is_empty());
+            if page_token.is_none() {
+                break;
+            }
+        }
+        client::UpgradePlan::new(&instance, &versions, latest_patch_only)
+            .map_err(|err| client::Error::Io(io::Error::new(io::ErrorKind::InvalidData, err)))
+    }
+    % endif
+    % if api.get('subscriber') and api.subscriber.resource == resource:
+<%
+    pull_m = c.fqan_map[to_fqan(c.rtc_map[resource], resource, api.subscriber.pull)]
+    subscription_param = [p for p in organize_params(*build_all_params(c, pull_m))[0]
+                          if p.name != REQUEST_VALUE_PROPERTY_NAME][0]
+    subscription_type = hub_type_name + 'Subscription'
+    assert subscription_type not in schemas, "%s clashes with a schema" % subscription_type
+    subscriber_activity = api.subscriber.pull[:-len('pull')] + 'subscriber'
+    arg_prefix = "/// * `" + subscription_param.name + "` - "
+%>
+    /// Receive the messages of a subscription continuously, like the official client libraries do. The returned
+    /// [`client::Subscriber`] pulls messages using [`Self::${mangle_ident(api.subscriber.pull)}()`], extends the ack
+    /// deadlines of messages while they are handled, and acknowledges them in batches.
+    ///
+    /// # Arguments
+    ///
+    ${arg_prefix}${subscription_param.get('description', "No description provided.")
+        | rust_doc_sanitize(documentationLink), remove_empty_lines, prefix_all_but_first_with(' ' * SPACES_PER_TAB + '///'  + ' ' * (len(arg_prefix) - len('///')))}
+    /// * `settings` - The flow control and lease management of the subscriber.
+    pub fn ${mangle_ident(subscriber_activity)}(&self, ${mangle_ident(subscription_param.name)}: ${activity_input_type(schemas, subscription_param)}, settings: client::SubscriberSettings) -> client::Subscriber<${subscription_type}<S>>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let subscription = ${subscription_type} {
+            hub: self.hub.clone(),
+            % if subscription_param.get('resource_name'):
+            subscription: ${mangle_ident(subscription_param.name)}.into(),
+            % else:
+            subscription: ${mangle_ident(subscription_param.name)}.to_string(),
+            % endif
+        };
+        client::Subscriber::new(subscription, settings)
+    }
+    % endif
+}
+% if api.get('subscriber') and api.subscriber.resource == resource:
+<%
+    message_type = schemas['ReceivedMessage'].properties.message.get(TREF)
+    subscription_arg = 'self.subscription.clone()'
+    if not subscription_param.get('resource_name'):
+        subscription_arg = '&self.subscription'
+%>
+
+/// The subscription of a [`client::Subscriber`], as returned by
+/// [`${rb_type(resource)}::${mangle_ident(subscriber_activity)}()`].
+#[cfg(feature = "client")]
+pub struct ${subscription_type}<S> {
+    hub: ${hub_type_name}<S>,
+    subscription: ${subscription_param.get('resource_name') and subscription_param.resource_name.type_name or 'String'},
 }
+
+#[cfg(feature = "client")]
+impl<S> client::subscriber::Subscription for ${subscription_type}<S>
+where
+    S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+    S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Message = ${message_type};
+
+    fn pull(&self, max_messages: i32) -> client::subscriber::SubscriptionFuture<'_, Vec<client::subscriber::Received<${message_type}>>> {
+        Box::pin(async move {
+            let request = PullRequest {
+                max_messages: Some(max_messages),
+                ..Default::default()
+            };
+            let (_, response) = self.hub.${mangle_ident(resource)}().${mangle_ident(api.subscriber.pull)}(request, ${subscription_arg}).${api.terms.action}().await?;
+            Ok(response.received_messages.unwrap_or_default().into_iter().filter_map(|received| {
+                let message = received.message.unwrap_or_default();
+                Some(client::subscriber::Received {
+                    ack_id: received.ack_id?,
+                    size: message.data.as_ref().map_or(0, |data| data.len()),
+                    message,
+                    delivery_attempt: received.delivery_attempt,
+                })
+            }).collect())
+        })
+    }
+
+    fn acknowledge(&self, ack_ids: Vec<String>) -> client::subscriber::SubscriptionFuture<'_, ()> {
+        Box::pin(async move {
+            let request = AcknowledgeRequest {
+                ack_ids: Some(ack_ids),
+            };
+            self.hub.${mangle_ident(resource)}().${mangle_ident(api.subscriber.acknowledge)}(request, ${subscription_arg}).${api.terms.action}().await?;
+            Ok(())
+        })
+    }
+
+    fn modify_ack_deadline(&self, ack_ids: Vec<String>, seconds: i32) -> client::subscriber::SubscriptionFuture<'_, ()> {
+        Box::pin(async move {
+            let request = ModifyAckDeadlineRequest {
+                ack_ids: Some(ack_ids),
+                ack_deadline_seconds: Some(seconds),
+            };
+            self.hub.${mangle_ident(resource)}().${mangle_ident(api.subscriber.modify_ack_deadline)}(request, ${subscription_arg}).${api.terms.action}().await?;
+            Ok(())
+        })
+    }
+}
+% endif
 </%def>
@@ -3,29 +3,57 @@
                       IO_TYPES, activity_split, enclose_in, REQUEST_MARKER_TRAIT, mb_type, indent_all_but_first_by,
                       NESTED_TYPE_SUFFIX, RESPONSE_MARKER_TRAIT, split_camelcase_s, METHODS_RESOURCE,
                       PART_MARKER_TRAIT, canonical_type_name, TO_PARTS_MARKER, UNUSED_TYPE_MARKER, is_schema_with_optionals,
-                      rust_doc_sanitize, items)
+                      rust_doc_sanitize, items, list_items_property, strict_required_properties,
+                      required_request_properties, LABELS_TYPE, map_fields)
 %>\
 ## Build a schema which must be an object
 ###################################################################################################################
 ###################################################################################################################
-<%def name="_new_object(s, properties, c, allow_optionals)">\
+<%def name="_new_object(s, properties, c, allow_optionals, strict=())">\
 <% struct = 'pub struct ' + s.id %>\
 % if properties:
 ${struct} {
 % for pn, p in items(properties):
-    ${p.get('description', 'no description provided') | rust_doc_sanitize(documentationLink), rust_doc_comment, indent_all_but_first_by(1)}
+<%
+    rust_ty = to_rust_type(schemas, s.id, pn, p, allow_optionals=allow_optionals)
+    serde_ty, use_custom_serde = to_serde_type(schemas, s.id, pn, p, allow_optionals=allow_optionals)
+    description = p.get('description', 'no description provided')
+%>\
+    % if pn in strict:
+<%
+        strict_ty = to_rust_type(schemas, s.id, pn, p, allow_optionals=False)
+        strict_serde_ty, strict_custom_serde = to_serde_type(schemas, s.id, pn, p, allow_optionals=False)
+    %>\
+    ${description | rust_doc_sanitize(documentationLink), rust_doc_comment, indent_all_but_first_by(1)}
+    #[cfg(feature = "strict-required")]
+    % if pn != mangle_ident(pn):
+    #[serde(rename="${pn}")]
+    % endif
+    % if strict_custom_serde:
+    #[serde_as(as = "::client::serde::required::Wrapper<${strict_serde_ty}>")]
+    % else:
+    #[serde_as(as = "::client::serde::required::Wrapper")]
+    % endif
+    pub ${mangle_ident(pn)}: ${strict_ty},
+    % endif
+    ${description | rust_doc_sanitize(documentationLink), rust_doc_comment, indent_all_but_first_by(1)}
+    % if pn in strict:
+    #[cfg(not(feature = "strict-required"))]
+    % endif
     % if pn != mangle_ident(pn):
     #[serde(rename="${pn}")]
     % endif
-    <%
-        rust_ty = to_rust_type(schemas, s.id, pn, p, allow_optionals=allow_optionals)
-        serde_ty, use_custom_serde = to_serde_type(schemas, s.id, pn, p, allow_optionals=allow_optionals)
-    %>
     % if use_custom_serde:
     #[serde_as(as = "${serde_ty}")]
     % endif
     pub ${mangle_ident(pn)}: ${rust_ty},
 % endfor
+<% assert 'extras' not in [mangle_ident(pn) for pn in properties], "%s has an extras property" % s.id %>\
+    /// The fields received which are not part of this schema, like those added to the API after this crate was generated.
+    /// They are sent again when the value is part of a request, so updating a resource doesn't clear them.
+    #[cfg(feature = "extras")]
+    #[serde(flatten)]
+    pub extras: HashMap<String, json::Value>,
 }
 % elif 'additionalProperties' in s:
 ${struct}(pub ${to_rust_type(schemas, s.id, NESTED_TYPE_SUFFIX, s, allow_optionals=allow_optionals)});
@@ -79,6 +107,8 @@ ${struct} { _never_set: Option<bool> }
         traits.remove('Default')
 
     s_type = s.id
+    list_items = RESPONSE_MARKER_TRAIT in nt_markers and s.type == 'object' and list_items_property(schemas, s)
+    strict = s.type == 'object' and strict_required_properties(api.get('required_response_fields'), s, markers) or set()
 %>\
 <%block filter="rust_doc_sanitize(documentationLink), rust_doc_comment">\
 ${doc(s, c)}\
@@ -86,7 +116,7 @@ ${doc(s, c)}\
 #[serde_with::serde_as(crate = "::client::serde_with")]
 #[derive(${', '.join(traits)})]
 % if s.type == 'object':
-${_new_object(s, s.get('properties'), c, allow_optionals)}\
+${_new_object(s, s.get('properties'), c, allow_optionals, strict)}\
 % elif s.type == 'array':
 % if s.items.get('type') != 'object':
 pub struct ${s_type}(${to_rust_type(schemas, s.id, NESTED_TYPE_SUFFIX, s, allow_optionals=allow_optionals)});
@@ -112,6 +142,149 @@ impl ${marker_trait} for ${s_type} {}
 % endif
 % endfor
 
+% if list_items:
+<%
+    pn, item_type = list_items
+    mn = 'self.' + mangle_ident(pn)
+%>\
+impl ${s_type} {
+    /// Returns the amount of `${pn}` in this response, which is zero if the server sent none.
+    pub fn len(&self) -> usize {
+        ${mn}.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Returns true if this response holds no `${pn}`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the `${pn}` of this response.
+    pub fn iter(&self) -> std::slice::Iter<'_, ${item_type}> {
+        ${mn}.as_deref().unwrap_or_default().iter()
+    }
+}
+
+impl IntoIterator for ${s_type} {
+    type Item = ${item_type};
+    type IntoIter = std::vec::IntoIter<${item_type}>;
+
+    /// Consumes this response, yielding its `${pn}`.
+    fn into_iter(self) -> Self::IntoIter {
+        ${mn}.unwrap_or_default().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ${s_type} {
+    type Item = &'a ${item_type};
+    type IntoIter = std::slice::Iter<'a, ${item_type}>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+% endif
+% if s_type in (api.get('deduplicated_names') or dict()) and 'name' in (s.get('properties') or dict()):
+#[cfg(feature = "client")]
+impl ${s_type} {
+    /// Name this ${s_type.lower()} within `parent` by a hash of `namespace` and its other fields, as derived by
+    /// [`client::tasks::task_id()`]. Creating it again with the same fields fails instead of creating a duplicate.
+    pub fn deduplicated(mut self, parent: impl std::fmt::Display, namespace: &str) -> ${s_type} {
+        self.name = None;
+        let id = client::tasks::task_id(namespace, &self);
+        self.name = Some(format!("{}/${api.deduplicated_names[s_type]}/{}", parent, id));
+        self
+    }
+}
+
+% endif
+% if REQUEST_MARKER_TRAIT in markers:
+<% fields = s.type == 'object' and map_fields(schemas, s) or list() %>\
+impl client::MapFields for ${s_type} {
+% if fields:
+    fn is_map(path: &[&str]) -> bool {
+        match path {
+        % for pn, t in fields:
+            % if t is None:
+            ["${pn}"] => true,
+            % else:
+            ["${pn}", rest @ ..] => <${t} as client::MapFields>::is_map(rest),
+            % endif
+        % endfor
+            _ => false,
+        }
+    }
+% elif s.type == 'any' or 'additionalProperties' in s and not s.get('properties'):
+    fn is_map(path: &[&str]) -> bool {
+        path.is_empty()
+    }
+% else:
+    fn is_map(_path: &[&str]) -> bool {
+        false
+    }
+% endif
+}
+
+% endif
+% if REQUEST_MARKER_TRAIT in markers and s.type == 'object' and s.get('properties') and s_type + 'Builder' not in schemas:
+<%
+    builder_type = s_type + 'Builder'
+    required = required_request_properties(s)
+%>\
+impl ${s_type} {
+    /// Returns a builder of ${s_type} values, setting their fields fluently.
+    pub fn builder() -> ${builder_type} {
+        ${builder_type} { value: Default::default() }
+    }
+}
+
+/// A builder of [`${s_type}`] values, as returned by [`${s_type}::builder()`].
+#[derive(Clone, Debug)]
+pub struct ${builder_type} {
+    value: ${s_type},
+}
+
+impl ${builder_type} {
+    % for pn, p in items(s.properties):
+<%
+        rust_ty = to_rust_type(schemas, s.id, pn, p, allow_optionals=allow_optionals)
+        value_ty = to_rust_type(schemas, s.id, pn, p, allow_optionals=False)
+        value = 'value'
+        if value_ty in ('String', LABELS_TYPE):
+            value_ty = 'impl Into<%s>' % value_ty
+            value = 'value.into()'
+        if rust_ty.startswith('Option<'):
+            value = 'Some(%s)' % value
+        setter = mangle_ident(pn)
+        if setter == 'build':
+            setter += '_'
+%>\
+    /// Sets the ${'required ' if pn in required else ''}*${split_camelcase_s(pn)}* field.
+    pub fn ${setter}(mut self, value: ${value_ty}) -> ${builder_type} {
+        self.value.${mangle_ident(pn)} = ${value};
+        self
+    }
+
+    % endfor
+    /// Returns the built value, failing if fields the server requires aren't set.
+    pub fn build(self) -> Result<${s_type}, client::MissingFieldsError> {
+    % if required:
+        client::MissingFieldsError::check("${s_type}", &[
+        % for pn in required:
+<%
+            rust_ty = to_rust_type(schemas, s.id, pn, s.properties[pn], allow_optionals=allow_optionals)
+            field = 'self.value.' + mangle_ident(pn)
+            is_set = rust_ty.startswith('Option<') and field + '.is_some()' or '!%s.is_empty()' % field
+%>\
+            ("${pn}", ${is_set}),
+        % endfor
+        ])?;
+    % endif
+        Ok(self.value)
+    }
+}
+
+% endif
 % if TO_PARTS_MARKER in nt_markers and allow_optionals:
 impl ${TO_PARTS_MARKER} for ${s_type} {
     /// Return a comma separated list of members that are currently set, i.e. for which `self.member.is_some()`.
@@ -127,6 +300,11 @@ impl ${TO_PARTS_MARKER} for ${s_type} {
             if rt.startswith('Vec') or rt.startswith('HashMap'):
                 check = 'len() > 0'
 %>\
+        % if pn in strict:
+        #[cfg(feature = "strict-required")]
+        { r = r + "${pn},"; }
+        #[cfg(not(feature = "strict-required"))]
+        % endif
         if ${mn}.${check} { r = r + "${pn},"; }
         % endfor
         ## remove (possibly non-existing) trailing comma
@@ -0,0 +1,190 @@
+<%!
+    from generator.lib.util import (mangle_ident, to_fqan, method_response)
+%>\
+## Build the incremental synchronization of mailboxes, on top of the calls listing their history
+###################################################################################################################
+###################################################################################################################
+<%def name="new(c, hub_type)">\
+<%
+    config = api.history_sync
+    resource = config.resource
+    users = mangle_ident(resource)
+    responses = dict()
+    for activity in ('list', 'profile', 'watch'):
+        fqan = to_fqan(c.rtc_map[resource], resource, config[activity])
+        assert fqan in c.fqan_map, "history_sync requires %s.%s" % (resource, config[activity])
+        responses[activity] = method_response(c, c.fqan_map[fqan]).id
+    assert responses['list'] == 'ListHistoryResponse' and 'historyId' in c.schemas['Profile'].properties
+    list_history = mangle_ident(config.list)
+%>\
+/// Incremental synchronization of mailboxes, built on the calls of [`${hub_type}::${users}()`].
+///
+/// A [`HistorySynchronizer`] keeps the history id a copy of a mailbox is synchronized with as its checkpoint,
+/// and turns the history records after it into [`Change`]s. Once the checkpoint expired, it tells to
+/// synchronize the mailbox fully, see [`client::history`].
+#[cfg(feature = "client")]
+pub mod sync {
+    use std::error::Error as StdError;
+    use std::io;
+
+    use tokio::io::{AsyncRead, AsyncWrite};
+
+    use crate::api::{${hub_type}, ${responses['watch']}, History, Message, WatchRequest};
+    use crate::client;
+
+    /// A change of a mailbox, as recorded by its history. The messages typically only have their `id` and
+    /// `thread_id`.
+    #[derive(Clone, Debug)]
+    pub enum Change {
+        /// The message was added to the mailbox.
+        MessageAdded(Message),
+        /// The message was deleted from the mailbox.
+        MessageDeleted(Message),
+        /// The labels with the ids `label_ids` were added to the message.
+        LabelsAdded { message: Message, label_ids: Vec<String> },
+        /// The labels with the ids `label_ids` were removed from the message.
+        LabelsRemoved { message: Message, label_ids: Vec<String> },
+    }
+
+    /// The outcome of [`HistorySynchronizer::sync()`].
+    #[derive(Clone, Debug)]
+    pub enum Synced {
+        /// The changes since the previous checkpoint, in chronological order.
+        Changes(Vec<Change>),
+        /// There was no checkpoint, or it expired, so the mailbox must be synchronized fully, like by listing
+        /// its messages. The checkpoint was set to the current history id before, so changes made during the
+        /// full synchronization are returned again by the next `sync()`.
+        FullSync,
+    }
+
+    /// Synchronizes a copy of a mailbox incrementally, using [`${hub_type}::${users}()`]`.${list_history}()`.
+    pub struct HistorySynchronizer<'a, S> {
+        hub: &'a ${hub_type}<S>,
+        user_id: String,
+        history_id: Option<u64>,
+        label_id: Option<String>,
+    }
+
+    impl<'a, S> HistorySynchronizer<'a, S>
+    where
+        S: tower_service::Service<http::Uri> + Clone + Send + Sync + 'static,
+        S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        S::Future: Send + Unpin + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        /// Synchronize the mailbox of `user_id`, which is `me` for the authenticated user, from the checkpoint
+        /// `history_id` stored by a previous run, or fully if it is `None`.
+        pub fn new(hub: &'a ${hub_type}<S>, user_id: impl Into<String>, history_id: Option<u64>) -> HistorySynchronizer<'a, S> {
+            HistorySynchronizer {
+                hub,
+                user_id: user_id.into(),
+                history_id,
+                label_id: None,
+            }
+        }
+
+        /// Only synchronize the messages with the label `label_id`, and only watch for their changes.
+        pub fn label_id(mut self, label_id: impl Into<String>) -> HistorySynchronizer<'a, S> {
+            self.label_id = Some(label_id.into());
+            self
+        }
+
+        /// Returns the checkpoint, the history id of the last change returned, to be stored for the next run.
+        pub fn history_id(&self) -> Option<u64> {
+            self.history_id
+        }
+
+        /// Returns true if the mailbox changed after the checkpoint, according to `notification`.
+        pub fn is_behind(&self, notification: &client::history::Notification) -> bool {
+            match self.history_id {
+                Some(history_id) => notification.history_id > history_id,
+                None => true,
+            }
+        }
+
+        /// List the changes since the checkpoint, page by page, and advance the checkpoint past them once all
+        /// pages were listed, or tell to synchronize fully if there is no checkpoint or it expired.
+        pub async fn sync(&mut self) -> client::Result<Synced> {
+            let start_history_id = match self.history_id {
+                Some(history_id) => history_id,
+                None => return self.full_sync().await,
+            };
+            let mut changes = Vec::new();
+            let mut history_id = start_history_id;
+            let mut page_token: Option<String> = None;
+            loop {
+                let mut call = self.hub.${users}().${list_history}(&self.user_id).start_history_id(start_history_id);
+                if let Some(label_id) = self.label_id.as_ref() {
+                    call = call.label_id(label_id);
+                }
+                if let Some(page_token) = page_token.as_ref() {
+                    call = call.page_token(page_token);
+                }
+                let response = match call.${api.terms.action}().await {
+                    Ok((_, response)) => response,
+                    Err(err) if client::history::is_expired(&err) => return self.full_sync().await,
+                    Err(err) => return Err(err),
+                };
+                for record in response.history.unwrap_or_default() {
+                    if let Some(id) = record.id {
+                        history_id = history_id.max(id);
+                    }
+                    changes.extend(record_changes(record));
+                }
+                if let Some(id) = response.history_id {
+                    history_id = history_id.max(id);
+                }
+                page_token = response.next_page_token;
+                if page_token.is_none() {
+                    break;
+                }
+            }
+            self.history_id = Some(history_id);
+            Ok(Synced::Changes(changes))
+        }
+
+        /// Publish the changes of the mailbox to the Pub/Sub topic `topic_name`, like
+        /// `projects/my-project/topics/gmail`, as [`client::history::Notification`]s. The watch expires after
+        /// seven days, so it must be renewed before, like once a day.
+        pub async fn watch(&self, topic_name: &str) -> client::Result<${responses['watch']}> {
+            let request = WatchRequest {
+                topic_name: Some(topic_name.to_string()),
+                label_ids: self.label_id.clone().map(|label_id| vec![label_id]),
+                ..Default::default()
+            };
+            let (_, response) = self.hub.${users}().${mangle_ident(config.watch)}(request, &self.user_id).${api.terms.action}().await?;
+            Ok(response)
+        }
+
+        async fn full_sync(&mut self) -> client::Result<Synced> {
+            let (_, profile) = self.hub.${users}().${mangle_ident(config.profile)}(&self.user_id).${api.terms.action}().await?;
+            let history_id = profile.history_id.ok_or_else(|| {
+                client::Error::Io(io::Error::new(io::ErrorKind::InvalidData, "the profile has no history id"))
+            })?;
+            self.history_id = Some(history_id);
+            Ok(Synced::FullSync)
+        }
+    }
+
+    fn record_changes(record: History) -> Vec<Change> {
+        let mut changes = Vec::new();
+        for added in record.messages_added.unwrap_or_default() {
+            changes.extend(added.message.map(Change::MessageAdded));
+        }
+        for deleted in record.messages_deleted.unwrap_or_default() {
+            changes.extend(deleted.message.map(Change::MessageDeleted));
+        }
+        for added in record.labels_added.unwrap_or_default() {
+            if let Some(message) = added.message {
+                changes.push(Change::LabelsAdded { message, label_ids: added.label_ids.unwrap_or_default() });
+            }
+        }
+        for removed in record.labels_removed.unwrap_or_default() {
+            if let Some(message) = removed.message {
+                changes.push(Change::LabelsRemoved { message, label_ids: removed.label_ids.unwrap_or_default() });
+            }
+        }
+        changes
+    }
+}
+</%def>
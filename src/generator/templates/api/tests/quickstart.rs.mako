@@ -0,0 +1,12 @@
+<%namespace name="util" file="../../../lib/util.mako"/>\
+<%! from generator.lib.util import rust_comment %>\
+<%block filter="rust_comment">\
+<%util:gen_info source="${self.uri}" />\
+</%block>
+//! Checks that the quickstart example keeps compiling against the current versions of the
+//! dependencies of `${util.crate_name()}`, and runs without credentials.
+
+#[test]
+fn quickstart() {
+    trybuild::TestCases::new().pass("examples/quickstart.rs");
+}
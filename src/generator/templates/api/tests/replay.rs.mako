@@ -0,0 +1,118 @@
+<%namespace name="util" file="../../../lib/util.mako"/>\
+<%
+    from generator.lib.util import new_context, rust_comment, hub_type, mangle_ident, example_call
+
+    c = new_context(schemas, resources)
+    hub_type_name = hub_type(c.schemas, util.canonical_name())
+    call = example_call(c, api.get('server_streaming'))
+    if call and call.page_token_setter:
+        next_page_token = 'page.next_page_token'
+        if 'nextPageToken' in (api.get('required_response_fields') or dict()).get(call.response_type, list()):
+            # it's non-optional with the strict-required feature
+            next_page_token += '.into()'
+%>\
+<%block filter="rust_comment">\
+<%util:gen_info source="${self.uri}" />\
+</%block>
+//! Replays the exchanges of the quickstart call recorded in `tests/fixtures/replay.har`, covering
+//! the building of its url, the decoding of its responses and its pagination without a network.
+//! Without a fixture, the call is answered with an error, which still needs to be decoded.
+//!
+//! To record the fixture against the live API instead, set `GOOGLE_APIS_RECORD_TOKEN` to an access
+//! token, or `GOOGLE_APIS_RECORD_KEY` to an API key, and run `cargo test --features replay --test replay`.
+//! Credentials are redacted from the recording.
+% if call:
+use std::env;
+use std::error::Error as StdError;
+use std::path::Path;
+
+use ${util.library_name()}::{${hub_type_name}, client, hyper, hyper_rustls};
+% for type_name in call.resource_name_types:
+use ${util.library_name()}::api::${type_name};
+% endfor
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/replay.har");
+% if call.page_token_setter:
+// the amount of pages requested at most
+const MAX_PAGES: usize = 3;
+% endif
+
+// Makes the quickstart call, returning the amount of successful responses. Errors returned by the
+// server are expected, as the call is made with placeholder arguments.
+async fn call<S>(hub: &${hub_type_name}<S>) -> usize
+where
+    S: tower_service::Service<hyper::Uri> + Clone + Send + Sync + 'static,
+    S::Response: hyper::client::connect::Connection + tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+% if call.page_token_setter:
+    let mut pages = 0;
+    let mut page_token: Option<String> = None;
+    while pages < MAX_PAGES {
+        let mut call = hub.${mangle_ident(call.resource)}().${mangle_ident(call.activity)}(${', '.join(call.args)});
+        if let Some(page_token) = page_token.as_ref() {
+            call = call.${call.page_token_setter}(page_token);
+        }
+        let page = match call.${api.terms.action}().await {
+            Ok((_, page)) => page,
+            Err(err) => return expect_api_error(err, pages),
+        };
+        pages += 1;
+        let next_page_token: Option<String> = ${next_page_token};
+        page_token = next_page_token.filter(|token| !token.is_empty());
+        if page_token.is_none() {
+            break;
+        }
+    }
+    pages
+% else:
+    match hub.${mangle_ident(call.resource)}().${mangle_ident(call.activity)}(${', '.join(call.args)}).${api.terms.action}().await {
+        Ok(_) => 1,
+        Err(err) => expect_api_error(err, 0),
+    }
+% endif
+}
+
+fn expect_api_error(err: client::Error, responses: usize) -> usize {
+    match err {
+        client::Error::Api(_) | client::Error::BadRequest(_) | client::Error::Failure(_) => responses,
+        err => panic!("the call failed unexpectedly: {}", err),
+    }
+}
+
+#[tokio::test]
+async fn replay() {
+    let token = env::var("GOOGLE_APIS_RECORD_TOKEN").ok();
+    let key = env::var("GOOGLE_APIS_RECORD_KEY").ok();
+    if token.is_some() || key.is_some() {
+        let auth: Box<dyn client::GetToken> = match token {
+            Some(token) => Box::new(token),
+            None => Box::new(client::ApiKey::new(key.unwrap_or_default())),
+        };
+        let connector = hyper_rustls::HttpsConnectorBuilder::new().with_native_roots().unwrap().https_or_http().enable_http1().build();
+        let mut hub = ${hub_type_name}::new(hyper::Client::builder().build(connector), auth);
+        let recorder = client::HarRecorder::with_bodies();
+        hub.with_interceptor(recorder.clone());
+        call(&hub).await;
+        std::fs::create_dir_all(Path::new(FIXTURE).parent().unwrap()).unwrap();
+        recorder.save(FIXTURE).unwrap();
+        return;
+    }
+
+    let recorded = Path::new(FIXTURE).exists();
+    let replay = if recorded {
+        client::Replay::load(FIXTURE).unwrap()
+    } else {
+        client::Replay::default()
+    };
+    let hub = ${hub_type_name}::new(hyper::Client::builder().build(replay.clone()), client::ApiKey::new("replay"));
+    call(&hub).await;
+    if recorded {
+        assert_eq!(replay.unmatched(), Vec::<String>::new(), "requests differ from the recording");
+        assert_eq!(replay.remaining(), 0, "recorded requests weren't made");
+    } else {
+        assert_eq!(replay.unmatched().len(), 1, "the call wasn't made");
+    }
+}
+% endif
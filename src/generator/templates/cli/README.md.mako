@@ -1,6 +1,10 @@
 <%
     from generator.lib.util import (markdown_comment, new_context)
-    from generator.lib.cli import (CONFIG_DIR, CONFIG_DIR_FLAG, SCOPE_FLAG, application_secret_path, DEBUG_FLAG)
+    from generator.lib.cli import (CONFIG_DIR, CONFIG_DIR_FLAG, SCOPE_FLAG, application_secret_path, DEBUG_FLAG, DRY_RUN_FLAG, FORMAT_FLAG,
+                                   HEADER_FLAG, ALL_PAGES_FLAG, MAX_ITEMS_FLAG, WAIT_TIMEOUT_FLAG, mangle_subcommand, operation_waits,
+                                   POLL_FLAG, operation_poll,
+                                   IMPERSONATE_FLAG, KEY_FILE_FLAG, SUBJECT_FLAG, DEVICE_FLOW_FLAG, PROFILE_FLAG, CONFIG_CMD, PROGRESS_FLAG, OFFLINE_FLAG, FIXTURES_FLAG,
+                                   METHODS_CMD, DESCRIBE_CMD, REQUEST_FILE_FLAG)
 
     c = new_context(schemas, resources)
 %>\
@@ -15,6 +19,30 @@ By default all output is printed to standard out, but flags can be set to direct
 capabilities. Errors will be printed to standard error, and cause the program's exit code to be non-zero.
 
 If data-structures are requested, these will be returned as pretty-printed JSON, to be useful as input to other tools.
+Use `--format` to choose compact JSON, YAML or a table instead, and `--field` to output only a single value of the result.
+Tables may be narrowed to the `--columns` of interest, and ordered using `--sort-by`, like `--format table --columns name,state --sort-by ~updateTime`.
+
+List methods return a single page of results. Add `--${ALL_PAGES_FLAG}` to fetch all pages and output their items as one
+list, or `--${MAX_ITEMS_FLAG} <n>` to stop once the given number of items was received.
+<%
+    waits = [(resource, w[0]) for resource in sorted(c.rta_map.keys()) for w in operation_waits(c, resource)]
+%>\
+% if waits:
+
+Long-running operations can be awaited with `${util.program_name()} ${mangle_subcommand(waits[0][0])} ${mangle_subcommand(waits[0][1])} <name>`, which
+polls the operation until it is done and outputs it. It fails if the operation failed, or if it isn't done after
+`--${WAIT_TIMEOUT_FLAG} <seconds>`, so scripts don't need a polling loop of their own.
+% endif
+<%
+    polls = [(resource, method) for resource in sorted(c.rta_map.keys()) for method in sorted(c.rta_map[resource])
+             if operation_poll(c, resource, method)]
+%>\
+% if polls:
+
+Methods returning a long-running operation, like `${mangle_subcommand(polls[0][0])} ${mangle_subcommand(polls[0][1])}`, accept `--${POLL_FLAG}` to wait until
+it is done and output the finished operation instead. The operation is polled less often the longer it takes, or every
+`--${POLL_FLAG} <seconds>`, for at most `--${WAIT_TIMEOUT_FLAG} <seconds>` if given.
+% endif
 % if documentationLink:
 
 Everything else about the *${util.canonical_name()}* API can be found at the
@@ -39,10 +67,16 @@ This documentation was generated from the *${util.canonical_name()}* API at revi
 ${argparse.grammar(c)}
 ```
 
+All methods are listed with a summary by `${util.program_name()} ${METHODS_CMD}`, and the arguments, parameters and request fields of one by
+`${util.program_name()} ${DESCRIBE_CMD} <resource> <method>`. Both print JSON, YAML or a table instead when `--${FORMAT_FLAG}` is given, which lets
+scripts and shell completions discover what a method takes.
+
 # Configuration
 
 The program will store all persistent data in the `${CONFIG_DIR}` directory in *JSON* files prefixed with `${util.program_name()}-`.  You can change the directory used to store configuration with the `--${CONFIG_DIR_FLAG}` flag on a per-invocation basis.
 
+All configuration flags may also be set using environment variables, which is convenient in containers and other automation. The variable of a flag is its name in upper case, with dashes replaced by underscores and prefixed by `GOOGLE_CLI_`, like `GOOGLE_CLI_CONFIG_DIR` for `--${CONFIG_DIR_FLAG}` or `GOOGLE_CLI_FORMAT` for `--${FORMAT_FLAG}`. Flags which take multiple values, like `--${SCOPE_FLAG}`, take them separated by commas, except for `--${HEADER_FLAG}`, which takes them from separate lines, and flags without a value, like `--${DRY_RUN_FLAG}`, are set by any value but `0`, `false` or `no`. Flags given on the command line take precedence.
+
 More information about the various kinds of persistent data are given in the following paragraphs.
 
 # Authentication
@@ -64,6 +98,34 @@ is necessary.
 
 To revoke granted authentication, please refer to the [official documentation][revoke-access].
 
+To run a command as a service account without a key file, add `--${IMPERSONATE_FLAG} <email>`, like with `gcloud`.
+The tokens of the user are then exchanged for short-lived tokens of the service account, which requires the user to
+have the *Service Account Token Creator* role on it. Given comma-separated email addresses, the impersonation is
+delegated through each service account in turn, and the last one is impersonated.
+
+To run a command as a service account with a key file instead, add `--${KEY_FILE_FLAG} <path>` naming its JSON key. If an
+administrator of a Google Workspace domain granted the service account domain-wide delegation of the scopes used, add
+`--${SUBJECT_FLAG} <email>` to act as the user with the given email address, as needed to access their mail or calendars.
+
+On machines without a browser, like servers reached over SSH, add `--${DEVICE_FLOW_FLAG}`. The CLI then writes a code
+to standard error, which is entered at the given address on another device, like a phone, to grant permissions. This
+requires the client secret to be of the type *TVs and Limited Input devices*.
+
+To use several accounts or projects side by side, select a profile with `--${PROFILE_FLAG} <name>`. Each profile keeps
+its tokens in its own directory, `${CONFIG_DIR}/profiles/<name>`, while the `default` profile, used unless another
+one is selected, keeps them in the configuration directory itself. A profile may set the project calls are attributed
+to and the output format used unless `--${FORMAT_FLAG}` is given, like `${util.program_name()} --${PROFILE_FLAG} work ${CONFIG_CMD} set project my-project`.
+`${util.program_name()} ${CONFIG_CMD} list` lists the defaults of a profile, and `${util.program_name()} ${CONFIG_CMD} profiles` lists all profiles.
+
+Given `-` as file, the request structure of `--${REQUEST_FILE_FLAG}` and the media of uploads are read from standard input,
+like `tar c photos | ${util.program_name()} ... -u resumable - -m application/x-tar`. Resumable uploads then send it in
+chunks as it is read, without knowing its size in advance, while simple uploads read all of it into memory first.
+
+Tools wrapping the program may follow long operations with `--${PROGRESS_FLAG} json`. Each chunk of a resumable upload,
+each poll of an operation waited for and each page listed with `--${ALL_PAGES_FLAG}` is then reported on standard error
+as a line holding a JSON object, like `{"event":"upload","bytes":1048576,"total_bytes":4194304}`, while the result is
+still written to standard output. The `event` field is one of `upload`, `operation` or `page`.
+
 # Application Secrets
 
 In order to allow any application to use Google services, it will need to be registered using the 
@@ -92,6 +154,21 @@ The `--${DEBUG_FLAG}` flag will print errors using the `Debug` representation to
 
 You may consider redirecting standard error into a file for ease of use, e.g. `${util.program_name()} --${DEBUG_FLAG} <resource> <method> [options] 2>debug.txt`.
 
+To see what a method would send without sending it, add the `--${DRY_RUN_FLAG}` flag. It prints the method, url,
+query parameters, headers and body of the request to standard output, with credentials like the `Authorization` header
+redacted, which makes it easy to reproduce the call with tools like `curl`.
+
+To send additional headers with each request, like a request reason or a header routing requests to a staging backend,
+add `--${HEADER_FLAG} 'Name: value'` once per header. It replaces the header of the same name the request carries
+otherwise, like the `x-goog-user-project` header, and is included in the output of `--${DRY_RUN_FLAG}`.
+
+To demo or test a runbook without touching real projects, add `--${OFFLINE_FLAG} --${FIXTURES_FLAG} <dir>`. Requests are then
+answered with the responses recorded in the `.har` files of the directory, in the order of their names, instead of
+being sent, and no credentials are needed. Each recorded response is served once, to the first request with the same
+method, path and query, and requests without one fail with status 501. Fixtures are HTTP Archives holding the bodies
+of the responses, like those recorded by the `HarRecorder` of the library crate, or exported from the network panel
+of browsers.
+
 
 [scopes]: https://developers.google.com/+/api/oauth#scopes
 [revoke-access]: http://webapps.stackexchange.com/a/30849
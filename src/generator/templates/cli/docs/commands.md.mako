@@ -4,9 +4,9 @@
     from generator.lib.util import (hash_comment, new_context, method_default_scope, indent_all_but_first_by, is_repeated_property, custom_sorted)
     from generator.lib.cli import (subcommand_md_filename, new_method_context, SPLIT_START, SPLIT_END, pretty, SCOPE_FLAG,
                      mangle_subcommand, is_request_value_property, FIELD_SEP, PARAM_FLAG, UPLOAD_FLAG, docopt_mode,
-                     FILE_ARG, MIME_ARG, OUT_ARG, OUTPUT_FLAG, to_cli_schema, cli_schema_to_yaml, SchemaEntry,
+                     FILE_ARG, MIME_ARG, OUT_ARG, OUTPUT_FLAG, FORMAT_FLAG, FIELD_FLAG, COLUMNS_FLAG, SORT_BY_FLAG, to_cli_schema, cli_schema_to_yaml, SchemaEntry,
                      STRUCT_FLAG, field_to_value, CTYPE_ARRAY, CTYPE_MAP, to_docopt_arg, FILE_FLAG, MIME_FLAG, 
-                     DEFAULT_MIME)
+                     DEFAULT_MIME, SCHEMA_CMD, REQUEST_FILE_FLAG, REQUEST_JSON_FLAG)
 
     from copy import deepcopy
 
@@ -77,6 +77,11 @@ can be set completely with the following arguments which are assumed to be execu
 
 ${self._list_schem_args(request_cli_schema)}
 
+Alternatively, the whole request value can be loaded from a JSON or YAML file using `--${REQUEST_FILE_FLAG} <path>`, or
+given inline as JSON using `--${REQUEST_JSON_FLAG} '<json>'`. Fields set with `-${STRUCT_FLAG}` are applied on top of it.
+
+The JSON Schema of the request value can be printed using `${util.program_name()} ${SCHEMA_CMD} ${mangle_subcommand(resource)} ${mangle_subcommand(method)}`, which is useful to validate request values in an editor.
+
 ${'###'} About Cursors
 
 The cursor position is key to comfortably set complex nested structures. The following rules apply:
@@ -135,6 +140,13 @@ The method's return value is a byte stream of the downloadable resource.
 % endif
       The *destination* may be `-` to indicate standard output, or a filepath that is to contain the received bytes.
       If unset, it defaults to standard output.
+% if mc.response_schema:
+
+The JSON-encoded structure may be written in another format using the global `--${FORMAT_FLAG} <json|json-compact|yaml|table>`
+flag, and `--${FIELD_FLAG} <path>` writes only the value at the given path, like `items[*].name`.
+Tables show the columns given by `--${COLUMNS_FLAG} <path>,...`, like `name,state,metadata.updateTime`, and
+`--${SORT_BY_FLAG} <path>` sorts their rows by the value at the given path, in descending order if prefixed with `~`.
+% endif
 % endif # have output
 % if oprops:
 # Optional Method Properties
@@ -2,11 +2,20 @@
 <%!
     import os
 
-    from generator.lib.util import (put_and, supports_scopes, api_index, indent_by, enclose_in, put_and, escape_rust_string)
+    from generator.lib.util import (put_and, supports_scopes, api_index, indent_by, enclose_in, put_and, escape_rust_string,
+                      is_server_streaming_method)
     from generator.lib.cli import (mangle_subcommand, new_method_context, PARAM_FLAG, STRUCT_FLAG, UPLOAD_FLAG, OUTPUT_FLAG, VALUE_ARG,
                      CONFIG_DIR, SCOPE_FLAG, is_request_value_property, FIELD_SEP, docopt_mode, FILE_ARG, MIME_ARG, OUT_ARG,
                      CONFIG_DIR_FLAG, KEY_VALUE_ARG, to_docopt_arg, DEBUG_FLAG, MODE_ARG, SCOPE_ARG,
-                     CONFIG_DIR_ARG, FILE_FLAG, MIME_FLAG, subcommand_md_filename)
+                     CONFIG_DIR_ARG, FILE_FLAG, MIME_FLAG, subcommand_md_filename, SCHEMA_CMD, METHODS_CMD, DESCRIBE_CMD, SCHEMA_RESOURCE_ARG,
+                     SCHEMA_METHOD_ARG, REQUEST_FILE_FLAG, REQUEST_FILE_ARG, REQUEST_JSON_FLAG, REQUEST_JSON_ARG,
+                     ADDRESS_FAMILY_FLAG, ADDRESS_FAMILY_ARG, FORMAT_FLAG, FORMAT_ARG, FIELD_FLAG, FIELD_ARG,
+                     COLUMNS_FLAG, COLUMNS_ARG, SORT_BY_FLAG, SORT_BY_ARG, DRY_RUN_FLAG,
+                     HEADER_FLAG, HEADER_ARG, ALL_PAGES_FLAG, MAX_ITEMS_FLAG, paged_items_property,
+                     WAIT_TIMEOUT_FLAG, WAIT_TIMEOUT_ARG, operation_waits, POLL_FLAG, POLL_ARG, operation_poll,
+                     IMPERSONATE_FLAG, IMPERSONATE_ARG, KEY_FILE_FLAG, KEY_FILE_ARG, SUBJECT_FLAG, SUBJECT_ARG, DEVICE_FLOW_FLAG,
+                     PROFILE_FLAG, PROFILE_ARG, CONFIG_CMD, CONFIG_KEY_ARG, CONFIG_VALUE_ARG,
+                     PROGRESS_FLAG, PROGRESS_ARG, OFFLINE_FLAG, FIXTURES_FLAG, FIXTURES_ARG)
 
     def rust_boolean(v):
         return v and 'true' or 'false'
@@ -38,7 +47,9 @@ ${util.program_name()} [options]
     # end for each required property
 
     if mc.request_value:
-        args.append('(-%s %s)...' % (STRUCT_FLAG, '<%s>' % KEY_VALUE_ARG))
+        args.append('(-%s %s | --%s <%s> | --%s <%s>)...' % (STRUCT_FLAG, '<%s>' % KEY_VALUE_ARG,
+                                                           REQUEST_FILE_FLAG, REQUEST_FILE_ARG,
+                                                           REQUEST_JSON_FLAG, REQUEST_JSON_ARG))
     # end request_value
 
     if mc.media_params:
@@ -54,10 +65,25 @@ ${util.program_name()} [options]
     if mc.response_schema or mc.m.get('supportsMediaDownload', False):
         args.append('[-%s <%s>]' % (OUTPUT_FLAG, OUT_ARG))
     # handle output
+
+    if paged_items_property(c, mc):
+        args.append('[--%s] [--%s <%s>]' % (ALL_PAGES_FLAG, MAX_ITEMS_FLAG, MAX_ITEMS_FLAG))
+    # handle paging
+
+    if not is_server_streaming_method(api.get('server_streaming'), mc.m) and operation_poll(c, resource, method):
+        args.append('[--%s [<%s>]] [--%s <%s>]' % (POLL_FLAG, POLL_ARG, WAIT_TIMEOUT_FLAG, WAIT_TIMEOUT_ARG))
+    # handle polling
 %>\
                 ${mangle_subcommand(method)} ${' '.join(args)}
     % endfor # each method
+    % for wait_method, get_method, name_param in operation_waits(c, resource):
+                ${mangle_subcommand(wait_method)} <${mangle_subcommand(name_param.name)}> [--${WAIT_TIMEOUT_FLAG} <${WAIT_TIMEOUT_ARG}>] [-${OUTPUT_FLAG} <${OUT_ARG}>]
+    % endfor # each operation wait
 % endfor # end for each resource
+        ${SCHEMA_CMD} <${SCHEMA_RESOURCE_ARG}> <${SCHEMA_METHOD_ARG}>
+        ${METHODS_CMD}
+        ${DESCRIBE_CMD} <${SCHEMA_RESOURCE_ARG}> <${SCHEMA_METHOD_ARG}>
+        ${CONFIG_CMD} (list | set <${CONFIG_KEY_ARG}> [<${CONFIG_VALUE_ARG}>] | profiles)
   ${util.program_name()} --help
 
 Configuration:
@@ -71,6 +97,61 @@ Configuration:
             A directory into which we will store our persistent data. Defaults to
             a user-writable directory that we will create during the first invocation.
             [default: ${CONFIG_DIR}]
+  --${PROFILE_FLAG} <${PROFILE_ARG}>
+            The profile whose credentials and defaults are used, which allows using
+            several accounts or projects side by side. Its defaults are listed and
+            set with '${CONFIG_CMD}'. [default: default]
+  --${FORMAT_FLAG} <${FORMAT_ARG}>
+            The format of the server's result, one of 'json', 'json-compact', 'yaml'
+            or 'table'. Tables show the items of list results as rows. Defaults to the
+            format of the profile, or json.
+  --${FIELD_FLAG} <${FIELD_ARG}>
+            Only output the value at the given path of the server's result, like
+            'items[*].name'. Strings are written without quotes.
+  --${COLUMNS_FLAG} <${COLUMNS_ARG}>
+            The columns of table output as comma-separated field paths of each row,
+            like 'name,state,metadata.updateTime'. Defaults to all top-level fields.
+  --${SORT_BY_FLAG} <${SORT_BY_ARG}>
+            Sort the rows of table output by the value at the given field path of each
+            row, in descending order if prefixed with '~'.
+  --${PROGRESS_FLAG} <${PROGRESS_ARG}>
+            Report the progress of uploads, of waits for operations and of listing
+            all pages on standard error, one of 'none' or 'json'. With 'json', each
+            step is written as a line holding a JSON object. [default: none]
+  --${ADDRESS_FAMILY_FLAG} <${ADDRESS_FAMILY_ARG}>
+            The address family to try first when connecting to hosts reachable via
+            IPv4 and IPv6, one of 'any', 'ipv4' or 'ipv6'. [default: any]
+  [--${HEADER_FLAG} <${HEADER_ARG}>]...
+            A header to send with each request, like 'X-Goog-Request-Reason: audit'.
+            It replaces the header of the same name the request carries otherwise.
+  --${IMPERSONATE_FLAG} <${IMPERSONATE_ARG}>
+            Act as the service account with the given email address, using tokens
+            created with the user's credentials. Comma-separated addresses delegate
+            the impersonation through all but the last of them.
+  --${KEY_FILE_FLAG} <${KEY_FILE_ARG}>
+            Authorize calls with the JSON key of a service account in the given file,
+            instead of the user's credentials.
+  --${SUBJECT_FLAG} <${SUBJECT_ARG}>
+            Act as the user of a Google Workspace domain with the given email address,
+            using the domain-wide delegation granted to the service account of
+            --${KEY_FILE_FLAG}.
+  --${DEVICE_FLOW_FLAG}
+            Authorize the user by entering a code on another device, instead of
+            opening a browser, like on a server without one. Ignored with --${KEY_FILE_FLAG}.
+  --${DRY_RUN_FLAG}
+            Print the request the method would send, with credentials redacted,
+            instead of sending it.
+  --${OFFLINE_FLAG}
+            Answer requests with the responses recorded in the fixtures, instead of
+            sending them, and without credentials. Requests without a recorded
+            response fail.
+  --${FIXTURES_FLAG} <${FIXTURES_ARG}>
+            The directory of the fixtures served by --${OFFLINE_FLAG}, which are HAR files
+            holding the bodies of the responses, replayed in the order of their names.
+
+Each configuration flag not given on the command line is taken from the environment
+variable named after it, like GOOGLE_CLI_CONFIG_DIR for --${CONFIG_DIR_FLAG}. Headers are taken
+from separate lines of GOOGLE_CLI_HEADER.
 </%def>
 
 
@@ -106,6 +187,126 @@ Configuration:
         False,
     ))
 
+    global_args.append((
+        PROFILE_FLAG,
+        "The profile whose credentials and defaults are used, which allows using several accounts or "
+        "projects side by side. Its defaults are listed and set with '%s'. [default: default]" % CONFIG_CMD,
+        PROFILE_ARG,
+        False,
+    ))
+
+    global_args.append((
+        FORMAT_FLAG,
+        "The format of the server's result, one of 'json', 'json-compact', 'yaml' or 'table'. "
+        "Tables show the items of list results as rows. Defaults to the format of the profile, or json.",
+        FORMAT_ARG,
+        False,
+    ))
+
+    global_args.append((
+        FIELD_FLAG,
+        "Only output the value at the given path of the server's result, like 'items[*].name'. "
+        "Strings are written without quotes.",
+        FIELD_ARG,
+        False,
+    ))
+
+    global_args.append((
+        COLUMNS_FLAG,
+        "The columns of table output as comma-separated field paths of each row, "
+        "like 'name,state,metadata.updateTime'. Defaults to all top-level fields.",
+        COLUMNS_ARG,
+        False,
+    ))
+
+    global_args.append((
+        SORT_BY_FLAG,
+        "Sort the rows of table output by the value at the given field path of each row, "
+        "in descending order if prefixed with '~'.",
+        SORT_BY_ARG,
+        False,
+    ))
+
+    global_args.append((
+        PROGRESS_FLAG,
+        "Report the progress of uploads, of waits for operations and of listing all pages on standard error, "
+        "one of 'none' or 'json'. With 'json', each step is written as a line holding a JSON object. "
+        "[default: none]",
+        PROGRESS_ARG,
+        False,
+    ))
+
+    global_args.append((
+        ADDRESS_FAMILY_FLAG,
+        "The address family to try first when connecting to hosts reachable via IPv4 and IPv6, "
+        "one of 'any', 'ipv4' or 'ipv6'. The other family is tried shortly after. [default: any]",
+        ADDRESS_FAMILY_ARG,
+        False,
+    ))
+
+    global_args.append((
+        HEADER_FLAG,
+        "A header to send with each request, like 'X-Goog-Request-Reason: audit'. "
+        "It replaces the header of the same name the request carries otherwise.",
+        HEADER_ARG,
+        True,
+    ))
+
+    global_args.append((
+        IMPERSONATE_FLAG,
+        "Act as the service account with the given email address, using tokens created with the user's "
+        "credentials. Comma-separated addresses delegate the impersonation through all but the last of them.",
+        IMPERSONATE_ARG,
+        False,
+    ))
+
+    global_args.append((
+        KEY_FILE_FLAG,
+        "Authorize calls with the JSON key of a service account in the given file, instead of the user's "
+        "credentials.",
+        KEY_FILE_ARG,
+        False,
+    ))
+
+    global_args.append((
+        SUBJECT_FLAG,
+        "Act as the user of a Google Workspace domain with the given email address, using the domain-wide "
+        "delegation granted to the service account of --%s." % KEY_FILE_FLAG,
+        SUBJECT_ARG,
+        False,
+    ))
+
+    global_args.append((
+        DEVICE_FLOW_FLAG,
+        "Authorize the user by entering a code on another device, instead of opening a browser, like on a server "
+        "without one. Ignored with --%s." % KEY_FILE_FLAG,
+        None,
+        False,
+    ))
+
+    global_args.append((
+        DRY_RUN_FLAG,
+        "Print the request the method would send, with credentials redacted, instead of sending it.",
+        None,
+        False,
+    ))
+
+    global_args.append((
+        OFFLINE_FLAG,
+        "Answer requests with the responses recorded in the fixtures, instead of sending them, and without "
+        "credentials. Requests without a recorded response fail.",
+        None,
+        False,
+    ))
+
+    global_args.append((
+        FIXTURES_FLAG,
+        "The directory of the fixtures served by --%s, which are HAR files holding the bodies of the responses, "
+        "replayed in the order of their names." % OFFLINE_FLAG,
+        FIXTURES_ARG,
+        False,
+    ))
+
     global_args.append((
         DEBUG_FLAG,
         "Debug print all errors",
@@ -115,6 +316,8 @@ Configuration:
 %>\
 <%
     have_media_params = False
+    for cmd in (SCHEMA_CMD, METHODS_CMD, DESCRIBE_CMD, CONFIG_CMD):
+        assert cmd not in [mangle_subcommand(r) for r in c.rta_map], "the '%s' command clashes with a resource" % cmd
     for resource in sorted(c.rta_map.keys()):
         methods = sorted(c.rta_map[resource])
         for method in methods:
@@ -132,9 +335,10 @@ let arg_data = [
 % for resource in sorted(c.rta_map.keys()):
 <%
     methods = sorted(c.rta_map[resource])
+    waits = operation_waits(c, resource)
 %>\
 <%block filter="indent_by(4)">\
-("${mangle_subcommand(resource)}", "methods: ${put_and(["'%s'" % mangle_subcommand(m) for m in methods])}", vec![
+("${mangle_subcommand(resource)}", "methods: ${put_and(["'%s'" % mangle_subcommand(m) for m in sorted(methods + [w[0] for w in waits])])}", vec![
     % for method in methods:
 <%
     mc = new_method_context(resource, method, c)
@@ -171,7 +375,7 @@ let arg_data = [
     if mc.media_params:
         args.append((
                 UPLOAD_FLAG,
-                "Specify the upload protocol (%s) and the file to upload" % '|'.join(mp.protocol for mp in mc.media_params),
+                "Specify the upload protocol (%s) and the file to upload, or '-' to read it from standard input" % '|'.join(mp.protocol for mp in mc.media_params),
                 MODE_ARG,
                 True,
                 True,
@@ -197,6 +401,43 @@ let arg_data = [
                 False,
             ))
     # handle output
+
+    paged_items = paged_items_property(c, mc)
+    if paged_items:
+        args.append((
+                None,
+                "Fetch all pages of the result, and output their '%s' as one list" % paged_items,
+                ALL_PAGES_FLAG,
+                False,
+                False,
+            ))
+        args.append((
+                None,
+                "Fetch pages of the result until the given number of '%s' was received, and output them as one list" % paged_items,
+                MAX_ITEMS_FLAG,
+                False,
+                False,
+            ))
+    # handle paging
+
+    poll = not is_server_streaming_method(api.get('server_streaming'), mc.m) and operation_poll(c, resource, method)
+    if poll:
+        args.append((
+                None,
+                "Wait until the returned operation is done, polling it using '%s' every given number of seconds, "
+                "or less often the longer it takes, and output it. Fails if the operation failed" % mangle_subcommand(poll[1]),
+                POLL_FLAG,
+                False,
+                False,
+            ))
+        args.append((
+                None,
+                "Fail if the operation isn't done after the given number of seconds of polling",
+                WAIT_TIMEOUT_FLAG,
+                False,
+                False,
+            ))
+    # handle polling
 %>\
     ("${mangle_subcommand(method)}",
             ${rust_optional(mc.m.get('description'))},
@@ -214,6 +455,30 @@ let arg_data = [
             % endfor
           ]),
     % endfor # each method
+    % for wait_method, get_method, name_param in waits:
+    ("${mangle_subcommand(wait_method)}",
+            Some(r##"Wait until the given operation is done, polling it using '${mangle_subcommand(get_method)}', and output it. Fails if the operation failed."##),
+            "Details at ${doc_base_url}/${os.path.splitext(subcommand_md_filename(resource, get_method))[0]}",
+          vec![
+            (Some(r##"${mangle_subcommand(name_param.name)}"##),
+             None,
+             ${rust_optional(name_param.get('description'))},
+             Some(true),
+             Some(false)),
+
+            (Some(r##"${WAIT_TIMEOUT_FLAG}"##),
+             None,
+             Some(r##"Fail if the operation isn't done after the given number of seconds"##),
+             Some(false),
+             Some(false)),
+
+            (Some(r##"${OUT_ARG}"##),
+             Some(r##"${OUTPUT_FLAG}"##),
+             Some(r##"Specify the file into which to write the program's output"##),
+             Some(false),
+             Some(false)),
+          ]),
+    % endfor # each operation wait
     ]),
 </%block>
 % endfor # end for each resource
@@ -274,6 +539,16 @@ for &(main_command_name, about, ref subcommands) in arg_data.iter() {
             if let &Some(multi) = multi {
                 arg = arg.multiple(multi);
             }
+            if arg_name_str == "${ALL_PAGES_FLAG}" || arg_name_str == "${MAX_ITEMS_FLAG}" || arg_name_str == "${WAIT_TIMEOUT_FLAG}" {
+                arg = arg.long(arg_name_str)
+                         .takes_value(arg_name_str != "${ALL_PAGES_FLAG}");
+            }
+            if arg_name_str == "${POLL_FLAG}" {
+                arg = arg.long(arg_name_str)
+                         .takes_value(true)
+                         .min_values(0)
+                         .value_name("${POLL_ARG}");
+            }
             % if have_media_params:
             if arg_name_str == "${MODE_ARG}" {
                 arg = arg.number_of_values(2);
@@ -287,11 +562,64 @@ for &(main_command_name, about, ref subcommands) in arg_data.iter() {
                                     .takes_value(true));
             }
             % endif
+            if arg_name_str == "${KEY_VALUE_ARG}" {
+                arg = arg.required_unless_one(&["${REQUEST_FILE_FLAG}", "${REQUEST_JSON_FLAG}"]);
+
+                scmd = scmd.arg(Arg::with_name("${REQUEST_FILE_FLAG}")
+                                    .long("${REQUEST_FILE_FLAG}")
+                                    .value_name("${REQUEST_FILE_ARG}")
+                                    .required(false)
+                                    .conflicts_with("${REQUEST_JSON_FLAG}")
+                                    .help("Load the request structure from a JSON or YAML file, or '-' to read it from standard input. Fields set with -${STRUCT_FLAG} are applied on top of it")
+                                    .takes_value(true))
+                           .arg(Arg::with_name("${REQUEST_JSON_FLAG}")
+                                    .long("${REQUEST_JSON_FLAG}")
+                                    .value_name("${REQUEST_JSON_ARG}")
+                                    .required(false)
+                                    .help("Load the request structure from the given JSON. Fields set with -${STRUCT_FLAG} are applied on top of it")
+                                    .takes_value(true));
+            }
             scmd = scmd.arg(arg);
         }
         mcmd = mcmd.subcommand(scmd);
     }
     app = app.subcommand(mcmd);
 }
+
+app = app.subcommand(SubCommand::with_name("${SCHEMA_CMD}")
+                         .about("Print the JSON schema of the request value of the given method")
+                         .arg(Arg::with_name("${SCHEMA_RESOURCE_ARG}")
+                                  .help("The resource, like the first sub-command of a method call")
+                                  .required(true))
+                         .arg(Arg::with_name("${SCHEMA_METHOD_ARG}")
+                                  .help("The method, like the second sub-command of a method call")
+                                  .required(true)));
+
+app = app.subcommand(SubCommand::with_name("${METHODS_CMD}")
+                         .about("List all methods with a summary, in columns or in the format given with --${FORMAT_FLAG}"));
+
+app = app.subcommand(SubCommand::with_name("${DESCRIBE_CMD}")
+                         .about("Print the arguments, parameters and request fields of the given method, as text or in the format given with --${FORMAT_FLAG}")
+                         .arg(Arg::with_name("${SCHEMA_RESOURCE_ARG}")
+                                  .help("The resource, like the first sub-command of a method call")
+                                  .required(true))
+                         .arg(Arg::with_name("${SCHEMA_METHOD_ARG}")
+                                  .help("The method, like the second sub-command of a method call")
+                                  .required(true)));
+
+app = app.subcommand(SubCommand::with_name("${CONFIG_CMD}")
+                         .about("List or set the defaults of the selected profile")
+                         .subcommand(SubCommand::with_name("list")
+                                         .about("List the defaults of the selected profile"))
+                         .subcommand(SubCommand::with_name("set")
+                                         .about("Set a default of the selected profile, or unset it if no value is given")
+                                         .arg(Arg::with_name("${CONFIG_KEY_ARG}")
+                                                  .help("The default to set, one of 'project' or 'format'")
+                                                  .required(true))
+                                         .arg(Arg::with_name("${CONFIG_VALUE_ARG}")
+                                                  .help("The project calls are attributed to, or the output format")
+                                                  .required(false)))
+                         .subcommand(SubCommand::with_name("profiles")
+                                         .about("List the profiles of the configuration directory")));
 </%block>
 </%def>
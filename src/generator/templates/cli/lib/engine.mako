@@ -2,14 +2,20 @@
 <%!
     from generator.lib.util import (hub_type, mangle_ident, indent_all_but_first_by, activity_rust_type, setter_fn_name, ADD_PARAM_FN,
                       upload_action_fn, is_schema_with_optionals, schema_markers, indent_by, method_default_scope,
-                      ADD_SCOPE_FN, TREF, enclose_in)
+                      ADD_SCOPE_FN, TREF, enclose_in, is_server_streaming_method)
     from generator.lib.cli import (mangle_subcommand, new_method_context, PARAM_FLAG, STRUCT_FLAG, OUTPUT_FLAG, VALUE_ARG,
                      CONFIG_DIR, SCOPE_FLAG, is_request_value_property, FIELD_SEP, docopt_mode, FILE_ARG, MIME_ARG, OUT_ARG,
                      call_method_ident, POD_TYPES, opt_value, ident,
                      KEY_VALUE_ARG, to_cli_schema, SchemaEntry, CTYPE_POD, actual_json_type, CTYPE_MAP, CTYPE_ARRAY,
                      application_secret_path, CONFIG_DIR_FLAG, req_value, MODE_ARG,
                      opt_values, SCOPE_ARG, CONFIG_DIR_ARG, DEFAULT_MIME, field_vec, comma_sep_fields, JSON_TYPE_TO_ENUM_MAP,
-                     CTYPE_TO_ENUM_MAP)
+                     CTYPE_TO_ENUM_MAP, cli_schema_to_json_schema_s, method_descriptions_s, REQUEST_FILE_FLAG, REQUEST_JSON_FLAG,
+                     FORMAT_ARG, FORMAT_FLAG, FIELD_ARG, FIELD_FLAG,
+                     COLUMNS_ARG, COLUMNS_FLAG, SORT_BY_ARG, SORT_BY_FLAG, DRY_RUN_FLAG, HEADER_ARG, HEADER_FLAG,
+                     ALL_PAGES_FLAG, MAX_ITEMS_FLAG, paged_items_property, WAIT_TIMEOUT_FLAG, operation_waits,
+                     POLL_FLAG, operation_poll,
+                     IMPERSONATE_ARG, IMPERSONATE_FLAG, KEY_FILE_ARG, KEY_FILE_FLAG, SUBJECT_ARG, SUBJECT_FLAG,
+                     DEVICE_FLOW_FLAG, PROFILE_ARG, PROFILE_FLAG, PROGRESS_ARG, PROGRESS_FLAG, OFFLINE_FLAG)
     from generator.lib.types import JSON_TO_RUST_DEFAULT
     v_arg = '<%s>' % VALUE_ARG
     SOPT = 'self.opt'
@@ -33,7 +39,8 @@
 %>\
 use client::{InvalidOptionsError, CLIError, arg_from_str, writer_from_opts, parse_kv_arg,
           input_file_from_opts, input_mime_from_opts, FieldCursor, FieldError, CallType, UploadProtocol,
-          calltype_from_str, remove_json_null_values, ComplexType, JsonType, JsonTypeInfo};
+          calltype_from_str, remove_json_null_values, ComplexType, JsonType, JsonTypeInfo,
+          request_value_from_opts, merge_json_values, InputError, write_output, OutputFormat, FieldPath, TableOptions};
 
 use std::default::Default;
 use std::error::Error as StdError;
@@ -49,6 +56,18 @@ use tower_service;
 enum DoitError {
     IoError(String, io::Error),
     ApiError(Error),
+    ## the calls made for some of many items failed
+    BulkError(BulkError),
+    OutputError(client::output::OutputError),
+}
+
+/// Reports the progress of resumable uploads.
+struct UploadProgress<'p>(&'p client::Progress);
+
+impl Delegate for UploadProgress<'_> {
+    fn upload_progress(&mut self, persisted: u64, total_length: u64) {
+        self.0.emit(client::ProgressEvent::Upload { bytes: persisted, total_bytes: total_length });
+    }
 }
 
 struct Engine<'n, S> {
@@ -56,6 +75,12 @@ struct Engine<'n, S> {
     hub: ${hub_type_name}<S>,
     gp: ${"Vec<&'static str>"},
     gpm: Vec<(&'static str, &'static str)>,
+    output_format: OutputFormat,
+    output_field: Option<FieldPath>,
+    table_options: TableOptions,
+    headers: http::HeaderMap,
+    print_request: bool,
+    progress: client::Progress,
 }
 
 
@@ -74,6 +99,13 @@ where
     }
 
     % endfor # each method
+    % for wait_method, get_method, name_param in operation_waits(c, resource):
+    async fn ${call_method_ident(resource, wait_method)}(&self, opt: &ArgMatches<'n>, dry_run: bool, err: &mut InvalidOptionsError)
+                                                    -> Result<(), DoitError> {
+        ${self._operation_wait_impl(c, resource, get_method, name_param) | indent_all_but_first_by(2)}
+    }
+
+    % endfor # each operation wait
 % endfor
     async fn _doit(&self, dry_run: bool) -> Result<Result<(), DoitError>, Option<InvalidOptionsError>> {
         let mut err = InvalidOptionsError::new();
@@ -89,6 +121,11 @@ where
                         call_result = self.${call_method_ident(resource, method)}(opt, dry_run, &mut err).await;
                     },
                     % endfor # each method
+                    % for wait_method, get_method, name_param in operation_waits(c, resource):
+                    ("${mangle_subcommand(wait_method)}", Some(opt)) => {
+                        call_result = self.${call_method_ident(resource, wait_method)}(opt, dry_run, &mut err).await;
+                    },
+                    % endfor # each operation wait
                     _ => {
                         err.issues.push(CLIError::MissingMethodError("${mangle_subcommand(resource)}".to_string()));
                         writeln!(io::stderr(), "{}\n", opt.usage()).ok();
@@ -114,37 +151,111 @@ where
 
     // Please note that this call will fail if any part of the opt can't be handled
     async fn new(opt: ArgMatches<'n>, connector: S) -> Result<Engine<'n, S>, InvalidOptionsError> {
-        let (config_dir, secret) = {
-            let config_dir = match client::assure_config_dir_exists(opt.value_of("${CONFIG_DIR_ARG}").unwrap_or("${CONFIG_DIR}")) {
+        let (profile, secret) = {
+            let config_dir = match client::assure_config_dir_exists(client::flag_value(&opt, "${CONFIG_DIR_ARG}", "${CONFIG_DIR_FLAG}").as_deref().unwrap_or("${CONFIG_DIR}")) {
+                Err(e) => return Err(InvalidOptionsError::single(e, 3)),
+                Ok(p) => p,
+            };
+            let profile = match client::Profile::open(&config_dir, client::flag_value(&opt, "${PROFILE_ARG}", "${PROFILE_FLAG}").as_deref().unwrap_or(client::DEFAULT_PROFILE)) {
                 Err(e) => return Err(InvalidOptionsError::single(e, 3)),
                 Ok(p) => p,
             };
 
             match client::application_secret_from_directory(&config_dir, "${application_secret_path(util.program_name())}",
                                                          "${api.credentials.replace('"', r'\"')}") {
-                Ok(secret) => (config_dir, secret),
+                Ok(secret) => (profile, secret),
                 Err(e) => return Err(InvalidOptionsError::single(e, 4))
             }
         };
 
+        let mut err = InvalidOptionsError::new();
+        let output_format = client::flag_value(&opt, "${FORMAT_ARG}", "${FORMAT_FLAG}").or_else(|| profile.get("format").map(str::to_string));
+        let output_format: OutputFormat = arg_from_str(output_format.as_deref().unwrap_or("json"), &mut err, "--${FORMAT_FLAG}", "output format");
+        let output_field: Option<FieldPath> = client::flag_value(&opt, "${FIELD_ARG}", "${FIELD_FLAG}").map(|v| arg_from_str(&v, &mut err, "--${FIELD_FLAG}", "field path"));
+        let table_options = TableOptions {
+            columns: client::flag_value(&opt, "${COLUMNS_ARG}", "${COLUMNS_FLAG}").map(|v| arg_from_str(&v, &mut err, "--${COLUMNS_FLAG}", "column paths")),
+            sort_by: client::flag_value(&opt, "${SORT_BY_ARG}", "${SORT_BY_FLAG}").map(|v| arg_from_str(&v, &mut err, "--${SORT_BY_FLAG}", "sort path")),
+        };
+        let progress: client::ProgressFormat = arg_from_str(client::flag_value(&opt, "${PROGRESS_ARG}", "${PROGRESS_FLAG}").as_deref().unwrap_or("none"), &mut err, "--${PROGRESS_FLAG}", "progress format");
+        let headers = client::parse_headers(&client::flag_lines(&opt, "${HEADER_ARG}", "${HEADER_FLAG}"), &mut err);
+        let impersonation = client::flag_value(&opt, "${IMPERSONATE_ARG}", "${IMPERSONATE_FLAG}")
+            .and_then(|v| client::parse_impersonation_chain(&v, &mut err));
+        let key_file = client::flag_value(&opt, "${KEY_FILE_ARG}", "${KEY_FILE_FLAG}");
+        let subject = client::flag_value(&opt, "${SUBJECT_ARG}", "${SUBJECT_FLAG}");
+        if subject.is_some() && key_file.is_none() {
+            err.issues.push(client::CLIError::MissingKeyFile);
+        }
+        if !err.issues.is_empty() {
+            return Err(err);
+        }
+        let print_request = client::flag_is_present(&opt, "${DRY_RUN_FLAG}");
+
         let client = hyper::Client::builder().build(connector);
 
-        let auth = oauth2::InstalledFlowAuthenticator::with_client(
-            secret,
-            oauth2::InstalledFlowReturnMethod::HTTPRedirect,
-            client.clone(),
-        ).persist_tokens_to_disk(format!("{}/${util.program_name()}", config_dir)).build().await.unwrap();
+        ## the connector serves the fixtures, which were recorded with credentials redacted
+        let mut hub = if client::flag_is_present(&opt, "${OFFLINE_FLAG}") {
+            ${hub_type_name}::new(client, NoToken)
+        } else {
+            let auth: Box<dyn GetToken> = match key_file {
+                Some(path) => {
+                    let auth = match (oauth2::read_service_account_key(&path).await, subject) {
+                        (Ok(key), Some(subject)) => flows::domain_wide_delegation(client.clone(), key, &subject).await,
+                        (Ok(key), None) => oauth2::ServiceAccountAuthenticator::with_client(key, client.clone()).build().await
+                            .map(|auth| Box::new(auth) as Box<dyn GetToken>),
+                        (Err(io_err), _) => Err(io_err),
+                    };
+                    match auth {
+                        Ok(auth) => auth,
+                        Err(io_err) => return Err(InvalidOptionsError::single(
+                            client::CLIError::Configuration(client::ConfigurationError::Io((path, io_err))), 4)),
+                    }
+                }
+                None if client::flag_is_present(&opt, "${DEVICE_FLOW_FLAG}") => {
+                    flows::device_flow(client.clone(), secret, Some(profile.token_path("${util.program_name()}").into())).await.unwrap()
+                }
+                None => Box::new(oauth2::InstalledFlowAuthenticator::with_client(
+                    secret,
+                    oauth2::InstalledFlowReturnMethod::HTTPRedirect,
+                    client.clone(),
+                ).persist_tokens_to_disk(profile.token_path("${util.program_name()}")).build().await.unwrap()),
+            };
+
+            match impersonation {
+                Some((service_account, delegates)) => {
+                    let clock = ServerClock::default();
+                    let auth = ServiceAccountImpersonation::new(client.clone(), auth, service_account)
+                        .delegates(delegates)
+                        .clock(clock.clone());
+                    let mut hub = ${hub_type_name}::new(client, auth);
+                    hub.server_clock(clock);
+                    hub
+                }
+                None => ${hub_type_name}::new(client, auth),
+            }
+        };
+        if !headers.is_empty() {
+            hub.add_request_signer(StaticHeaders::new(headers.clone()));
+        }
+        if let Some(project) = profile.get("project") {
+            hub.quota_project(project.to_string());
+        }
 
 <% gpm = gen_global_parameter_names(parameters) %>\
         let engine = Engine {
             opt: opt,
-            hub: ${hub_type_name}::new(client, auth),
+            hub,
             gp: ${field_vec(gpm)},
             gpm: vec![
                 % for pn in list(pn for pn in gpm if mangle_subcommand(pn) != pn):
                     ("${mangle_subcommand(pn)}", "${pn}"),
                 % endfor # each global parameter
-                ]
+                ],
+            output_format,
+            output_field,
+            table_options,
+            headers,
+            print_request,
+            progress: client::Progress::new(progress),
         };
 
         match engine._doit(true).await {
@@ -163,11 +274,41 @@ where
 }
 </%def>
 
+## Generates a lookup of the JSON Schema of each method's request value, as used by the `schema` subcommand.
+<%def name="request_schemas(c)">\
+/// Returns the JSON Schema of the request value of the given method, or `None` if the method
+/// is unknown or doesn't take a request value.
+fn request_json_schema(resource: &str, method: &str) -> ${"Option<&'static str>"} {
+    match (resource, method) {
+% for resource in sorted(c.rta_map.keys()):
+    % for method in sorted(c.rta_map[resource]):
+<%
+    mc = new_method_context(resource, method, c)
+%>\
+        % if mc.request_value:
+        ("${mangle_subcommand(resource)}", "${mangle_subcommand(method)}") => Some(r##"${cli_schema_to_json_schema_s(to_cli_schema(c, mc.request_value))}"##),
+        % endif
+    % endfor # each method
+% endfor # each resource
+        _ => None,
+    }
+}
+</%def>
+
+## Generates the descriptions of all methods, as listed by the `list-methods` subcommand and shown by the `describe` one.
+<%def name="method_descriptions(c)">\
+/// The descriptions of all methods, see `client::describe::Methods`.
+const METHODS: &str = r##"${method_descriptions_s(c)}"##;
+</%def>
+
 <%def name="_method_call_impl(c, resource, method)" buffered="True">\
 <%
     mc = new_method_context(resource, method, c)
     supports_media_download = mc.m.get('supportsMediaDownload', False)
     handle_output = mc.response_schema or supports_media_download
+    server_streaming = mc.response_schema and is_server_streaming_method(api.get('server_streaming'), mc.m)
+    paged_items = paged_items_property(c, mc)
+    poll = not server_streaming and operation_poll(c, resource, method)
     optional_props = [p for p in mc.optional_props if not p.get('skip_example', False)]
     optional_prop_names = set(p.name for p in optional_props)
 
@@ -190,6 +331,8 @@ where
     % if is_request_value_property(mc, p):
 <% request_prop_type = prop_type %>\
 ${self._request_value_impl(c, request_cli_schema, prop_name, request_prop_type)}\
+    % elif p.get('resource_name'):
+let ${prop_name}: api::${p.resource_name.type_name} = arg_from_str(&${opt_value(p.name)}, err, "<${mangle_subcommand(p.name)}>", "resource name");
     % elif p.type != 'string':
     % if p.get('repeated', False):
 let ${prop_name}: Vec<${prop_type} = Vec::new();
@@ -208,7 +351,15 @@ let ${prop_name}: ${prop_type} = arg_from_str(&${opt_value(p.name)}, err, "<${ma
         # if type is not available, we know it's the request value, which should also be borrowed
         borrow = borrow_prefix(p)
         arg_name = mangle_ident(p.name)
-        if p.get('type', '') == 'string':
+        if p.get('resource_name'):
+            borrow = ''
+            if paged_items:
+                # each page is requested with a copy of the resource name
+                arg_name += '.clone()'
+        elif paged_items and is_request_value_property(mc, p):
+            # each page is requested with a copy of the request value
+            arg_name += '.clone()'
+        elif p.get('type', '') == 'string':
             if p.get('repeated', False):
                 arg_name = opt_values(p.name) + '.map(|&v| v.to_string()).collect::<Vec<String>>()'
             else:
@@ -219,6 +370,10 @@ let ${prop_name}: ${prop_type} = arg_from_str(&${opt_value(p.name)}, err, "<${ma
 % if track_download_flag:
 let mut download_mode = false;
 % endif
+% if paged_items:
+## the call is built again for each page, which is requested using the token of the previous one
+let make_call = |page_token: ${"Option<&str>"}, err: &mut InvalidOptionsError| {
+% endif
 let mut call = self.hub.${mangle_ident(resource)}().${mangle_ident(method)}(${', '.join(call_args)});
 % if handle_props:
 for parg in ${opt_values(VALUE_ARG)} {
@@ -230,6 +385,9 @@ for parg in ${opt_values(VALUE_ARG)} {
     default_value = JSON_TO_RUST_DEFAULT[ptype]
     value_unwrap = f"value.unwrap_or({default_value})"
 %>\
+        % if p.get('deprecated', False):
+        #[allow(deprecated)]
+        % endif
         "${mangle_subcommand(p.name)}" => {
         % if p.name == 'alt':
             if ${value_unwrap} == "media" {
@@ -275,23 +433,66 @@ ${value_unwrap}\
     }
 }
 % endif # handle call parameters
+% if paged_items:
+    if let Some(page_token) = page_token {
+        call = call.page_token(page_token);
+    }
+    % if method_default_scope(mc.m):
+    if !dry_run {
+        for scope in client::flag_values(&${SOPT}, "${SCOPE_ARG}", "${SCOPE_FLAG}") {
+            call = call.${ADD_SCOPE_FN}(scope);
+        }
+    }
+    % endif
+    call
+};
+let mut call = make_call(None, err);
+let all_pages = opt.is_present("${ALL_PAGES_FLAG}");
+let max_items: Option<usize> = opt.value_of("${MAX_ITEMS_FLAG}").map(|v| arg_from_str(v, err, "--${MAX_ITEMS_FLAG}", "integer"));
+% endif # support paging
 % if mc.media_params:
 let vals = opt.values_of("${MODE_ARG}").unwrap().collect::<Vec<${'&'}str>>();
 let protocol = calltype_from_str(vals[0], [${', '.join('"%s"' % mp.protocol for mp in mc.media_params)}].iter().map(|&v| v.to_string()).collect(), err);
-let mut input_file = input_file_from_opts(vals[1], err);
+let input_file = input_file_from_opts(vals[1], err);
+% if mc.request_value:
+if vals[1] == client::STDIN_PATH && opt.value_of("${REQUEST_FILE_FLAG}") == Some(client::STDIN_PATH) {
+    err.issues.push(CLIError::Input(InputError::StdinUsedTwice));
+}
+% endif
 let mime_type = input_mime_from_opts(${opt_value(MIME_ARG, default=DEFAULT_MIME)}, err);
 % else:
 let protocol = CallType::Standard;
 % endif # support upload
+% if poll:
+let poll_interval: Option<Option<std::time::Duration>> = if opt.is_present("${POLL_FLAG}") {
+    Some(opt.value_of("${POLL_FLAG}").map(|v| std::time::Duration::from_secs(arg_from_str(v, err, "--${POLL_FLAG}", "integer"))))
+} else {
+    None
+};
+let timeout: Option<u64> = opt.value_of("${WAIT_TIMEOUT_FLAG}").map(|v| arg_from_str(v, err, "--${WAIT_TIMEOUT_FLAG}", "integer"));
+% endif # support polling
 if dry_run {
     Ok(())
 } else {
     assert!(err.issues.len() == 0);
-    % if method_default_scope(mc.m):
-    for scope in ${opt_values(SCOPE_ARG, opt=SOPT)} {
+    % if method_default_scope(mc.m) and not paged_items:
+    for scope in client::flag_values(&${SOPT}, "${SCOPE_ARG}", "${SCOPE_FLAG}") {
         call = call.${ADD_SCOPE_FN}(scope);
     }
     % endif
+    if self.print_request {
+        let (mut parts, body) = match call.build_request().await {
+            Ok(request) => request.into_parts(),
+            Err(api_err) => return Err(DoitError::ApiError(api_err)),
+        };
+        ## the hub adds them when sending the request
+        parts.headers.extend(self.headers.clone());
+        let body = hyper::body::to_bytes(body).await.expect("request bodies to be in memory");
+        if let Err(io_err) = client::write_request(&mut io::stdout(), &parts, &body) {
+            return Err(DoitError::OutputError(io_err.into()));
+        }
+        return Ok(());
+    }
     ## Make the call, handle uploads, handle downloads (also media downloads|json decoding)
     % if handle_output:
     let mut ostream = match writer_from_opts(opt.value_of("${(OUT_ARG)}")) {
@@ -299,10 +500,86 @@ if dry_run {
         Err(io_err) => return Err(DoitError::IoError(${opt_value(OUT_ARG, default='-')}.to_string(), io_err)),
     };
     % endif # handle output
+    % if paged_items:
+    if all_pages || max_items.is_some() {
+        ## the items of all pages are merged into the last one, whose other fields are output along with them
+        let mut items = Vec::new();
+        let mut pages = 0;
+        let mut value;
+        loop {
+            let page = match call.${api.terms.action}().await {
+                Ok((_, page)) => page,
+                Err(api_err) => return Err(DoitError::ApiError(api_err)),
+            };
+            value = json::value::to_value(&page).expect("serde to work");
+            if let Some(json::Value::Array(page_items)) = value.get_mut("${paged_items}").map(json::Value::take) {
+                items.extend(page_items);
+            }
+            pages += 1;
+            self.progress.emit(client::ProgressEvent::Page { pages, items: items.len() });
+            if let Some(max_items) = max_items {
+                if items.len() >= max_items {
+                    items.truncate(max_items);
+                    break;
+                }
+            }
+            match value.get("nextPageToken").and_then(|token| token.as_str()).filter(|token| !token.is_empty()) {
+                Some(page_token) => call = make_call(Some(page_token), &mut InvalidOptionsError::new()),
+                None => break,
+            }
+        }
+        value["${paged_items}"] = json::Value::Array(items);
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("nextPageToken");
+        }
+        remove_json_null_values(&mut value);
+        if let Err(output_err) = write_output(&mut ostream, &value, self.output_format, self.output_field.as_ref(), &self.table_options) {
+            return Err(DoitError::OutputError(output_err));
+        }
+        return Ok(());
+    }
+    % endif # support paging
+    % if mc.media_params:
+    let mut upload_progress = UploadProgress(&self.progress);
+    if self.progress.is_enabled() {
+        call = call.delegate(&mut upload_progress);
+    }
+    % endif
     match match protocol {
         % if mc.media_params:
         % for p in mc.media_params:
-        CallType::Upload(UploadProtocol::${p.protocol.capitalize()}) => call.${upload_action_fn(api.terms.upload_action, p.type.suffix)}(input_file.unwrap(), mime_type.unwrap()).await,
+        % if p.protocol == 'resumable':
+        ## standard input can't seek, so it is streamed to a session instead, without knowing its size in advance
+        CallType::Upload(UploadProtocol::Resumable) if input_file.as_ref().map_or(false, client::InputFile::is_stdin) => {
+            match call.${upload_action_fn(api.terms.upload_action, p.type.suffix)}_session(mime_type.unwrap()).await {
+                Ok(mut session) => {
+                    let mut stdin = tokio::io::stdin();
+                    let mut chunk = vec![0; client::STDIN_CHUNK_SIZE];
+                    loop {
+                        match tokio::io::AsyncReadExt::read(&mut stdin, &mut chunk).await {
+                            % if mc.response_schema:
+                            Ok(0) => break session.finalize(&[]).await,
+                            % else:
+                            ## the session decodes the body of the response, which the call doesn't return
+                            Ok(0) => break session.finalize(&[]).await.map(|(response, _)| response),
+                            % endif
+                            Ok(len) => {
+                                if let Err(api_err) = session.append(&chunk[..len]).await {
+                                    break Err(api_err);
+                                }
+                            }
+                            Err(io_err) => break Err(Error::Io(io_err)),
+                        }
+                    }
+                }
+                Err(api_err) => Err(api_err),
+            }
+        }
+        % endif
+        CallType::Upload(UploadProtocol::${p.protocol.capitalize()}) => match input_file.unwrap().into_seekable() {
+            Ok(reader) => call.${upload_action_fn(api.terms.upload_action, p.type.suffix)}(reader, mime_type.unwrap()).await,
+            Err(io_err) => Err(Error::Io(io_err)),
+        },
         % endfor
         CallType::Standard => unreachable!()
         % else:
@@ -311,6 +588,23 @@ if dry_run {
         % endif
     } {
         Err(api_err) => Err(DoitError::ApiError(api_err)),
+        % if server_streaming:
+        ## each message is written as soon as it was received
+        Ok(mut messages) => {
+            while let Some(message) = messages.next_item().await {
+                let message = match message {
+                    Ok(message) => message,
+                    Err(api_err) => return Err(DoitError::ApiError(api_err)),
+                };
+                let mut value = json::value::to_value(&message).expect("serde to work");
+                remove_json_null_values(&mut value);
+                if let Err(output_err) = write_output(&mut ostream, &value, self.output_format, self.output_field.as_ref(), &self.table_options) {
+                    return Err(DoitError::OutputError(output_err));
+                }
+            }
+            Ok(())
+        }
+        % else:
         % if mc.response_schema:
         Ok((mut response, output_schema)) => {
         % else:
@@ -322,11 +616,28 @@ if dry_run {
             % if track_download_flag:
             if !download_mode {
             % endif
-            % if mc.response_schema:
+            % if poll:
+            ## the returned operation is output once it is done, like the wait subcommands do
+            let mut value = match poll_interval {
+                Some(interval) if !output_schema.done.unwrap_or(false) => {
+                    ${self._operation_poll_impl(c, *poll) | indent_all_but_first_by(5)}
+                }
+                _ => json::value::to_value(&output_schema).expect("serde to work"),
+            };
+            % elif mc.response_schema:
             let mut value = json::value::to_value(&output_schema).expect("serde to work");
+            % endif
+            % if mc.response_schema:
             remove_json_null_values(&mut value);
-            json::to_writer_pretty(&mut ostream, &value).unwrap();
-            ostream.flush().unwrap();
+            if let Err(output_err) = write_output(&mut ostream, &value, self.output_format, self.output_field.as_ref(), &self.table_options) {
+                return Err(DoitError::OutputError(output_err));
+            }
+            % endif
+            % if poll:
+            if poll_interval.is_some() {
+                let name = value.get("name").and_then(|n| n.as_str()).unwrap_or("unknown");
+                ${self._operation_failure() | indent_all_but_first_by(4)}
+            }
             % endif
             % if track_download_flag:
             } else {
@@ -342,10 +653,127 @@ if dry_run {
             % endif
             Ok(())
         }
+        % endif ## server_streaming
     }
 }\
 </%def>
 
+## Waits for the operation of the given name by polling it with get_method until it is done
+<%def name="_operation_wait_impl(c, resource, get_method, name_param)" buffered="True">\
+<%
+    name_arg = mangle_subcommand(name_param.name)
+%>\
+% if name_param.get('resource_name'):
+let name: api::${name_param.resource_name.type_name} = arg_from_str(&${opt_value(name_param.name)}, err, "<${name_arg}>", "resource name");
+% else:
+let name = ${opt_value(name_param.name)};
+% endif
+let timeout: Option<u64> = opt.value_of("${WAIT_TIMEOUT_FLAG}").map(|v| arg_from_str(v, err, "--${WAIT_TIMEOUT_FLAG}", "integer"));
+if dry_run {
+    return Ok(());
+}
+assert!(err.issues.len() == 0);
+${self._operation_get_call(c, resource, get_method, name_param)}
+if self.print_request {
+    let (mut parts, body) = match make_call().build_request().await {
+        Ok(request) => request.into_parts(),
+        Err(api_err) => return Err(DoitError::ApiError(api_err)),
+    };
+    parts.headers.extend(self.headers.clone());
+    let body = hyper::body::to_bytes(body).await.expect("request bodies to be in memory");
+    if let Err(io_err) = client::write_request(&mut io::stdout(), &parts, &body) {
+        return Err(DoitError::OutputError(io_err.into()));
+    }
+    return Ok(());
+}
+let mut ostream = match writer_from_opts(opt.value_of("${(OUT_ARG)}")) {
+    Ok(mut f) => f,
+    Err(io_err) => return Err(DoitError::IoError(${opt_value(OUT_ARG, default='-')}.to_string(), io_err)),
+};
+let interval: Option<std::time::Duration> = None;
+let mut value = {
+    ${self._operation_poll_loop() | indent_all_but_first_by(1)}
+};
+remove_json_null_values(&mut value);
+if let Err(output_err) = write_output(&mut ostream, &value, self.output_format, self.output_field.as_ref(), &self.table_options) {
+    return Err(DoitError::OutputError(output_err));
+}
+${self._operation_failure()}
+Ok(())\
+</%def>
+
+## Polls the operation returned by a call until it is done, evaluating to it
+<%def name="_operation_poll_impl(c, resource, get_method, name_param)" buffered="True">\
+let name = output_schema.name.clone().unwrap_or_default();
+% if name_param.get('resource_name'):
+let name: api::${name_param.resource_name.type_name} = match name.parse() {
+    Ok(name) => name,
+    Err(parse_err) => {
+        let msg = format!("The operation name '{}' is invalid: {}", name, parse_err);
+        return Err(DoitError::ApiError(Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg))));
+    }
+};
+% else:
+let name = name.as_str();
+% endif
+${self._operation_get_call(c, resource, get_method, name_param)}
+${self._operation_poll_loop()}\
+</%def>
+
+## Defines make_call, building the call of get_method getting the operation called `name`
+<%def name="_operation_get_call(c, resource, get_method, name_param)" buffered="True">\
+<%
+    mc = new_method_context(resource, get_method, c)
+%>\
+let make_call = || {
+    let mut call = self.hub.${mangle_ident(resource)}().${mangle_ident(get_method)}(${name_param.get('resource_name') and 'name.clone()' or 'name'});
+    % if method_default_scope(mc.m):
+    for scope in client::flag_values(&${SOPT}, "${SCOPE_ARG}", "${SCOPE_FLAG}") {
+        call = call.${ADD_SCOPE_FN}(scope);
+    }
+    % endif
+    call
+};\
+</%def>
+
+## Polls the operation using make_call until it is done, evaluating to it. It is polled every `interval` if that
+## is set, and less often the longer it takes otherwise, for at most `timeout` seconds if that is set.
+<%def name="_operation_poll_loop()" buffered="True">\
+let deadline = timeout.map(|secs| std::time::Instant::now() + std::time::Duration::from_secs(secs));
+let mut backoff = std::time::Duration::from_secs(1);
+let started = std::time::Instant::now();
+loop {
+    let operation = match make_call().${api.terms.action}().await {
+        Ok((_, operation)) => operation,
+        Err(api_err) => return Err(DoitError::ApiError(api_err)),
+    };
+    let done = operation.done.unwrap_or(false);
+    self.progress.emit(client::ProgressEvent::Operation { name: &name.to_string(), done, elapsed_secs: started.elapsed().as_secs_f64() });
+    if done {
+        break json::value::to_value(&operation).expect("serde to work");
+    }
+    let mut delay = interval.unwrap_or(backoff);
+    if let Some(deadline) = deadline {
+        let now = std::time::Instant::now();
+        if now >= deadline {
+            let msg = format!("The operation '{}' wasn't done after {}s", name, timeout.unwrap_or(0));
+            return Err(DoitError::ApiError(Error::Io(io::Error::new(io::ErrorKind::TimedOut, msg))));
+        }
+        delay = delay.min(deadline - now);
+    }
+    tokio::time::sleep(delay).await;
+    backoff = (backoff * 2).min(std::time::Duration::from_secs(10));
+}\
+</%def>
+
+## Fails if the operation in `value`, called `name`, failed
+<%def name="_operation_failure()" buffered="True">\
+if let Some(status) = value.get("error") {
+    let msg = format!("The operation '{}' failed: {}", name, status.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error"));
+    return Err(DoitError::ApiError(Error::Io(io::Error::new(io::ErrorKind::Other, msg))));
+}\
+</%def>
+
 <%def name="_request_value_impl(c, request_cli_schema, request_prop_name, request_prop_type)">
 <%
     allow_optionals_fn = lambda s: is_schema_with_optionals(schema_markers(s, c, transitive=False))
@@ -416,5 +844,13 @@ for kvarg in ${opt_values(KEY_VALUE_ARG)} {
         FieldCursor::from(field_cursor_str).set_json_value(&mut object, value.unwrap(), type_info, err, &temp_cursor);
     }
 }
-let mut ${request_prop_name}: api::${request_prop_type} = json::value::from_value(object).unwrap();
+let mut request_value = request_value_from_opts(opt.value_of("${REQUEST_FILE_FLAG}"), opt.value_of("${REQUEST_JSON_FLAG}"), err);
+merge_json_values(&mut request_value, object);
+let mut ${request_prop_name}: api::${request_prop_type} = match json::value::from_value(request_value) {
+    Ok(value) => value,
+    Err(json_err) => {
+        err.issues.push(CLIError::Input(InputError::RequestValue("${request_prop_name}".to_string(), json_err.to_string())));
+        json::value::from_value(json::value::Value::Object(Default::default())).unwrap()
+    }
+};
 </%def>
@@ -4,7 +4,10 @@
 <%  
     from generator.lib.util import (new_context, rust_comment, to_extern_crate_name, library_to_crate_name, library_name,
                       indent_all_but_first_by)
-    from generator.lib.cli import OUT_ARG, DEBUG_FLAG, opt_value
+    from generator.lib.cli import (OUT_ARG, DEBUG_FLAG, ADDRESS_FAMILY_ARG, ADDRESS_FAMILY_FLAG, SCHEMA_CMD, SCHEMA_RESOURCE_ARG, SCHEMA_METHOD_ARG, opt_value,
+                                   METHODS_CMD, DESCRIBE_CMD, FORMAT_ARG, FORMAT_FLAG,
+                                   CONFIG_CMD, CONFIG_KEY_ARG, CONFIG_VALUE_ARG, CONFIG_DIR, CONFIG_DIR_ARG, CONFIG_DIR_FLAG,
+                                   PROFILE_ARG, PROFILE_FLAG, OFFLINE_FLAG, FIXTURES_ARG, FIXTURES_FLAG)
 
     c = new_context(schemas, resources)
     default_user_agent = "google-cli-rust-client/" + cargo.build_version
@@ -21,26 +24,147 @@ use std::env;
 use std::io::{self, Write};
 use clap::{App, SubCommand, Arg};
 
-use ${to_extern_crate_name(library_to_crate_name(library_name(name, version), make.depends_on_suffix))}::{api, Error, oauth2, client::chrono, FieldMask};
+use ${to_extern_crate_name(library_to_crate_name(library_name(name, version), make.depends_on_suffix))}::{api, Error, Delegate, oauth2, client::chrono, client::connector, client::StaticHeaders, client::ServerClock, client::ServiceAccountImpersonation, client::GetToken, client::flows, client::BulkError, client::NoToken, client::Replay, FieldMask};
 
 
 use google_clis_common as client;
 
 ${engine.new(c)}\
 
+${engine.request_schemas(c)}\
+
+${engine.method_descriptions(c)}\
+
 #[tokio::main]
 async fn main() {
     let mut exit_status = 0i32;
     ${argparse.new(c) | indent_all_but_first_by(1)}\
     let matches = app.get_matches();
 
-    let debug = matches.is_present("a${DEBUG_FLAG}");
-    let connector = hyper_rustls::HttpsConnectorBuilder::new().with_native_roots()
-        .unwrap()
-        .https_or_http()
-        .enable_http1()
-        .build();
+    let debug = client::flag_is_present(&matches, "${DEBUG_FLAG}");
+    if let ("${SCHEMA_CMD}", Some(opt)) = matches.subcommand() {
+        let resource = opt.value_of("${SCHEMA_RESOURCE_ARG}").unwrap();
+        let method = opt.value_of("${SCHEMA_METHOD_ARG}").unwrap();
+        match request_json_schema(resource, method) {
+            Some(schema) => {
+                let value: json::Value = json::from_str(schema).expect("valid json schema");
+                json::to_writer_pretty(io::stdout(), &value).ok();
+                writeln!(io::stdout()).ok();
+            },
+            None => {
+                exit_status = 1;
+                writeln!(io::stderr(), "{}", client::CLIError::UnknownRequestSchema(resource.to_string(), method.to_string())).ok();
+            }
+        }
+        std::process::exit(exit_status);
+    }
+    if let (cmd @ ("${METHODS_CMD}" | "${DESCRIBE_CMD}"), Some(opt)) = matches.subcommand() {
+        let format = client::flag_value(&matches, "${FORMAT_ARG}", "${FORMAT_FLAG}").map(|format| format.parse::<client::OutputFormat>());
+        let format = match format.transpose() {
+            Ok(format) => format,
+            Err(err) => {
+                writeln!(io::stderr(), "{}", err).ok();
+                std::process::exit(1);
+            }
+        };
+        let methods = client::describe::Methods::parse(METHODS);
+        let result = if cmd == "${METHODS_CMD}" {
+            methods.write_list(&mut io::stdout(), format)
+        } else {
+            let resource = opt.value_of("${SCHEMA_RESOURCE_ARG}").unwrap();
+            let method = opt.value_of("${SCHEMA_METHOD_ARG}").unwrap();
+            match methods.find(resource, method) {
+                Some(description) => client::describe::write_method(&mut io::stdout(), description, format),
+                None => {
+                    exit_status = 1;
+                    write!(io::stderr(), "{}", client::CLIError::UnknownMethod(resource.to_string(), method.to_string())).ok();
+                    Ok(())
+                }
+            }
+        };
+        if let Err(err) = result {
+            exit_status = 1;
+            write!(io::stderr(), "{}", err).ok();
+        }
+        std::process::exit(exit_status);
+    }
+    if let ("${CONFIG_CMD}", Some(opt)) = matches.subcommand() {
+        let config_dir = client::flag_value(&matches, "${CONFIG_DIR_ARG}", "${CONFIG_DIR_FLAG}");
+        let profile = client::flag_value(&matches, "${PROFILE_ARG}", "${PROFILE_FLAG}");
+        let result = client::assure_config_dir_exists(config_dir.as_deref().unwrap_or("${CONFIG_DIR}")).and_then(|config_dir| {
+            let mut profile = client::Profile::open(&config_dir, profile.as_deref().unwrap_or(client::DEFAULT_PROFILE))?;
+            match opt.subcommand() {
+                ("set", Some(opt)) => profile.set(opt.value_of("${CONFIG_KEY_ARG}").unwrap(), opt.value_of("${CONFIG_VALUE_ARG}")),
+                ("profiles", _) => {
+                    for name in client::list_profiles(&config_dir)? {
+                        let marker = if name == profile.name() { "*" } else { " " };
+                        writeln!(io::stdout(), "{} {}", marker, name).ok();
+                    }
+                    Ok(())
+                },
+                _ => {
+                    for (key, value) in profile.settings() {
+                        writeln!(io::stdout(), "{} = {}", key, value).ok();
+                    }
+                    Ok(())
+                }
+            }
+        });
+        if let Err(err) = result {
+            exit_status = 1;
+            writeln!(io::stderr(), "{}", err).ok();
+        }
+        std::process::exit(exit_status);
+    }
 
+    let mut err = client::InvalidOptionsError::new();
+    let address_family: connector::AddressFamily = client::arg_from_str(client::flag_value(&matches, "${ADDRESS_FAMILY_ARG}", "${ADDRESS_FAMILY_FLAG}").as_deref().unwrap_or("any"),
+                                                                        &mut err, "--${ADDRESS_FAMILY_FLAG}", "address family");
+    let replay = if client::flag_is_present(&matches, "${OFFLINE_FLAG}") {
+        match client::flag_value(&matches, "${FIXTURES_ARG}", "${FIXTURES_FLAG}") {
+            Some(dir) => match Replay::load_dir(&dir) {
+                Ok(replay) => Some(replay),
+                Err(io_err) => {
+                    err.issues.push(client::CLIError::Configuration(client::ConfigurationError::Io((dir, io_err))));
+                    None
+                }
+            },
+            None => {
+                err.issues.push(client::CLIError::MissingFixtures);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if !err.issues.is_empty() {
+        writeln!(io::stderr(), "{}", err).ok();
+        std::process::exit(err.exit_code);
+    }
+
+    exit_status = match replay {
+        Some(replay) => run(matches, replay, debug).await,
+        None => {
+            let connector = hyper_rustls::HttpsConnectorBuilder::new().with_native_roots()
+                .unwrap()
+                .https_or_http()
+                .enable_http1()
+                .wrap_connector(connector::http_connector(address_family));
+            run(matches, connector, debug).await
+        }
+    };
+    std::process::exit(exit_status);
+}
+
+// Runs the command of `matches`, sending its requests through `connector`, and returns the exit status.
+async fn run<S>(matches: ArgMatches<'_>, connector: S, debug: bool) -> i32
+where
+    S: tower_service::Service<Uri> + Clone + Send + Sync + 'static,
+    S::Response: hyper::client::connect::Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    S::Future: Send + Unpin + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    let mut exit_status = 0i32;
     match Engine::new(matches, connector).await {
         Err(err) => {
             exit_status = err.exit_code;
@@ -53,6 +177,9 @@ async fn main() {
                     DoitError::IoError(path, err) => {
                         writeln!(io::stderr(), "Failed to open output file '{}': {}", path, err).ok();
                     },
+                    DoitError::OutputError(err) => {
+                        writeln!(io::stderr(), "{}", err).ok();
+                    },
                     DoitError::ApiError(err) => {
                         if debug {
                             writeln!(io::stderr(), "{:#?}", err).ok();
@@ -60,10 +187,16 @@ async fn main() {
                             writeln!(io::stderr(), "{}", err).ok();
                         }
                     }
+                    DoitError::BulkError(err) => {
+                        if debug {
+                            writeln!(io::stderr(), "{:#?}", err).ok();
+                        } else {
+                            writeln!(io::stderr(), "{}", err).ok();
+                        }
+                    }
                 }
             }
         }
     }
-
-    std::process::exit(exit_status);
+    exit_status
 }
@@ -0,0 +1,34 @@
+<%! from generator.lib.util import (hash_comment, registered_apis, library_name) %>\
+<%namespace name="util" file="../../lib/util.mako"/>\
+<%block filter="hash_comment">\
+<%util:gen_info source="${self.uri}" />\
+</%block>
+<%
+    apis = registered_apis(directories.api_base, api.list, api.get('blacklist', list()), cargo.build_version)
+%>\
+[package]
+
+name = "google-apis"
+version = "${cargo.build_version}"
+authors = [${",\n           ".join('"%s"' % a for a in cargo.authors)}]
+description = "All google-api crates of one release, each behind a feature named after its library, like drive3"
+repository = "${cargo.repo_base_url}/tree/main/${directories.output}/google-apis"
+documentation = "https://docs.rs/google-apis"
+license = "${copyright.license_abbrev}"
+keywords = ["google", "web", "api"]
+edition = "2018"
+
+## The crates are required with their exact versions, so all of them are those of the same generator run, and share
+## the same google-apis-common and yup-oauth2. It is published after them.
+[dependencies]
+google-apis-common = { path = "../../google-apis-common", version = "6.0.3", default-features = false }
+% for a in apis:
+${a.crate_name} = { path = "../${library_name(a.name, a.version)}", version = "=${a.crate_version}", optional = true }
+% endfor
+
+## Each API is a feature. These are more than the 300 crates.io accepts by default, which it needs to be asked to raise.
+[features]
+default = []
+% for a in apis:
+${library_name(a.name, a.version)} = ["dep:${a.crate_name}"]
+% endfor
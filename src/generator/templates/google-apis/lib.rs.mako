@@ -0,0 +1,37 @@
+<%! from generator.lib.util import (rust_comment, registered_apis, library_name, to_extern_crate_name) %>\
+<%namespace name="util" file="../../lib/util.mako"/>\
+<%block filter="rust_comment">\
+<%util:gen_info source="${self.uri}" />\
+</%block>
+<%
+    apis = registered_apis(directories.api_base, api.list, api.get('blacklist', list()), cargo.build_version)
+%>\
+//! All google-api crates of release ${cargo.build_version}, each re-exported as a module named after its library when
+//! the feature of the same name is enabled:
+//!
+//! ```toml
+//! google-apis = { version = "${cargo.build_version}", features = ["drive3", "storage1"] }
+//! ```
+//!
+//! ```ignore
+//! use google_apis::{drive3, storage1};
+//! ```
+//!
+//! Unlike depending on each crate, which may be of another release, this guarantees that all of them were generated
+//! by the same run, and share the same [`common`] crate and authenticator. Features of the crates themselves, like
+//! `gzip`, are enabled by depending on them as well, without a version of their own:
+//!
+//! ```toml
+//! google-drive3 = { version = "*", features = ["gzip"] }
+//! ```
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+pub use google_apis_common as common;
+% for a in apis:
+<% lib = library_name(a.name, a.version) %>\
+
+/// The ${a.title} (${a.version}), see [the documentation of its crate](https://docs.rs/${a.crate_name}).
+#[cfg(feature = "${lib}")]
+#[cfg_attr(docsrs, doc(cfg(feature = "${lib}")))]
+pub use ${to_extern_crate_name(a.crate_name)} as ${lib};
+% endfor
@@ -0,0 +1,29 @@
+<%! from generator.lib.util import (rust_comment, registered_apis, escape_rust_string) %>\
+<%namespace name="util" file="../lib/util.mako"/>\
+<%block filter="rust_comment">\
+<%util:gen_info source="${self.uri}" />\
+</%block>
+
+use crate::ApiInfo;
+
+/// All generated APIs, sorted by name.
+pub(crate) static APIS: &[ApiInfo] = &[
+% for a in registered_apis(directories.api_base, api.list, api.get('blacklist', list()), cargo.build_version):
+    ApiInfo {
+        name: "${a.name}",
+        version: "${a.version}",
+        title: "${a.title | escape_rust_string}",
+        crate_name: "${a.crate_name}",
+        cli_crate_name: "${a.cli_crate_name}",
+        crate_version: "${a.crate_version}",
+        revision: "${a.revision}",
+        root_url: "${a.root_url}",
+        base_url: "${a.base_url}",
+    % if a.documentation_link:
+        documentation_link: Some("${a.documentation_link}"),
+    % else:
+        documentation_link: None,
+    % endif
+    },
+% endfor
+];